@@ -0,0 +1,54 @@
+//! Compares byte array parameters accessed inside a critical region (`CriticalBytes`) with copied ones (`JavaArray<i8>`),
+//! for a 10 MB array.
+//!
+//! Unlike tests, benchmarks run without `-Xcheck:jni`, whose checks copy the arrays of critical regions.
+//!
+//! The native methods are called from a Java loop, so that only the calls from Java are timed.
+//! Run with `cargo bench -p integration-tests --bench critical_arrays`.
+
+use std::time::{Duration, Instant};
+
+use integration_tests::with_env;
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::{JObject, JValue};
+use robusta_jni::jni::JNIEnv;
+
+const LEN: usize = 10 * 1024 * 1024;
+const CALLS: i32 = 200;
+
+/// Average duration of a call made by the method `name` of `CriticalLoops` on `data`.
+fn time_calls(env: JNIEnv, name: &str, data: JObject) -> JniResult<Duration> {
+    let run_calls = |calls: i32| env.call_static_method("com/robusta/tests/CriticalLoops", name, "([BI)J", &[JValue::Object(data), JValue::Int(calls)]);
+
+    // Warm up, so that the loop is compiled
+    run_calls(CALLS / 10)?;
+
+    let start = Instant::now();
+    run_calls(CALLS)?;
+    Ok(start.elapsed() / CALLS as u32)
+}
+
+fn main() {
+    with_env(|env| {
+        let bytes: Vec<i8> = (0..LEN).map(|i| i as i8).collect();
+        let data = env.new_byte_array(LEN as i32)?;
+        env.set_byte_array_region(data, 0, &bytes)?;
+        let data = JObject::from(data);
+
+        // Reading every byte, then a single one, where copying the array is most of the work
+        for (workload, copied_method, critical_method) in [("sum", "copiedSum", "sum"), ("last byte", "copiedLast", "last")] {
+            let copied = time_calls(env, copied_method, data)?;
+            let critical = time_calls(env, critical_method, data)?;
+
+            println!("{:<9}  copied (JavaArray<i8>)      {:>10?}/call", workload, copied);
+            println!(
+                "{:<9}  critical (CriticalBytes)    {:>10?}/call ({:.1}x)",
+                workload,
+                critical,
+                copied.as_secs_f64() / critical.as_secs_f64()
+            );
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,107 @@
+//! Compares imported method calls with and without `#[cached]`.
+//!
+//! Run with `cargo bench -p integration-tests`.
+
+use std::time::{Duration, Instant};
+
+use integration_tests::with_env;
+use robusta_jni::bridge;
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::JObject;
+use robusta_jni::jni::JNIEnv;
+
+use crate::jni::Greeter;
+
+const CALLS: u32 = 100_000;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Greeter<'env> {
+        pub raw: JObject<'env>,
+    }
+
+    impl Signature for Greeter<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Greeter;";
+    }
+
+    impl Signature for &Greeter<'_> {
+        const SIG_TYPE: &'static str = <Greeter as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Greeter<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Greeter<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Greeter { raw: s })
+        }
+    }
+
+    impl<'env> Greeter<'env> {
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>, greeting: String) -> JniResult<Self> {}
+
+        pub extern "java" fn greet(&self, env: JNIEnv<'env>, name: String) -> JniResult<String> {}
+
+        #[cached]
+        #[java_name = "greet"]
+        pub extern "java" fn greet_cached(&self, env: JNIEnv<'env>, name: String) -> JniResult<String> {}
+
+        pub extern "java" fn greetedCount(env: JNIEnv<'env>) -> JniResult<i32> {}
+
+        #[cached]
+        #[java_name = "greetedCount"]
+        pub extern "java" fn greeted_count_cached(env: JNIEnv<'env>) -> JniResult<i32> {}
+    }
+}
+
+/// Average duration of `call`, run `CALLS` times in local frames.
+fn time_calls(env: JNIEnv, mut call: impl FnMut() -> JniResult<()>) -> JniResult<Duration> {
+    // Warm up, which also fills the caches
+    call()?;
+
+    let start = Instant::now();
+    for _ in 0..CALLS {
+        env.with_local_frame(8, || call().map(|_| JObject::null()))?;
+    }
+    Ok(start.elapsed() / CALLS)
+}
+
+fn compare(name: &str, uncached: Duration, cached: Duration) {
+    println!(
+        "{:<24} uncached {:>8?}/call, cached {:>8?}/call ({:.1}x)",
+        name,
+        uncached,
+        cached,
+        uncached.as_secs_f64() / cached.as_secs_f64()
+    );
+}
+
+fn main() {
+    with_env(|env| {
+        let greeter = Greeter::new(env, "Hello".into())?;
+
+        let uncached = time_calls(env, || Greeter::greetedCount(env).map(drop))?;
+        let cached = time_calls(env, || Greeter::greeted_count_cached(env).map(drop))?;
+        compare("static method", uncached, cached);
+
+        let uncached = time_calls(env, || greeter.greet(env, "robusta".into()).map(drop))?;
+        let cached = time_calls(env, || greeter.greet_cached(env, "robusta".into()).map(drop))?;
+        compare("instance method", uncached, cached);
+
+        Ok(())
+    })
+}
@@ -0,0 +1,61 @@
+//! Compares exported methods only taking and returning primitives, which are called directly, with the usual conversions.
+//!
+//! The native methods are called from a Java loop, so that only the calls from Java are timed.
+//! Run with `cargo bench -p integration-tests --bench primitive_calls`.
+
+use std::time::{Duration, Instant};
+
+use integration_tests::with_env;
+use robusta_jni::bridge;
+use robusta_jni::jni::errors::Result as JniResult;
+
+use crate::jni::ArithmeticLoops;
+
+const CALLS: i32 = 10_000_000;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct ArithmeticLoops;
+
+    impl ArithmeticLoops {
+        pub extern "java" fn add(env: JNIEnv, calls: i32) -> JniResult<i64> {}
+        pub extern "java" fn uncheckedAdd(env: JNIEnv, calls: i32) -> JniResult<i64> {}
+        pub extern "java" fn checkedAdd(env: JNIEnv, calls: i32) -> JniResult<i64> {}
+    }
+}
+
+/// Average duration of a call made by `run_calls`, which makes `calls` calls.
+fn time_calls(mut run_calls: impl FnMut(i32) -> JniResult<i64>) -> JniResult<Duration> {
+    // Warm up, so that the loop is compiled
+    run_calls(CALLS / 10)?;
+
+    let start = Instant::now();
+    run_calls(CALLS)?;
+    Ok(start.elapsed() / CALLS as u32)
+}
+
+fn main() {
+    with_env(|env| {
+        let converted = time_calls(|calls| ArithmeticLoops::checkedAdd(env, calls))?;
+        let direct = time_calls(|calls| ArithmeticLoops::add(env, calls))?;
+        let unchecked = time_calls(|calls| ArithmeticLoops::uncheckedAdd(env, calls))?;
+
+        println!("converted (Result)   {:>8?}/call", converted);
+        println!(
+            "direct               {:>8?}/call ({:.1}x)",
+            direct,
+            converted.as_secs_f64() / direct.as_secs_f64()
+        );
+        println!(
+            "direct, unchecked    {:>8?}/call ({:.1}x)",
+            unchecked,
+            converted.as_secs_f64() / unchecked.as_secs_f64()
+        );
+
+        Ok(())
+    })
+}
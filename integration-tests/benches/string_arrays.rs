@@ -0,0 +1,51 @@
+//! Compares converting 10k strings as a `String[]` (`Box<[String]>`) with converting them as an `ArrayList` (`Vec<String>`),
+//! to Java and back.
+//!
+//! Run with `cargo bench -p integration-tests --bench string_arrays`.
+
+use std::time::{Duration, Instant};
+
+use integration_tests::with_env;
+use robusta_jni::convert::{TryFromJavaValue, TryIntoJavaValue};
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::JObject;
+use robusta_jni::jni::JNIEnv;
+
+const LEN: usize = 10_000;
+const ROUNDS: u32 = 50;
+
+/// Average duration of converting `strings` to `T` and back, run `ROUNDS` times in local frames.
+fn time_round_trips<'env, T>(env: JNIEnv<'env>, strings: &T) -> JniResult<Duration>
+where
+    T: Clone + PartialEq + TryIntoJavaValue<'env> + TryFromJavaValue<'env>,
+    <T as TryFromJavaValue<'env>>::Source: From<<T as TryIntoJavaValue<'env>>::Target>,
+{
+    let round_trip = || -> JniResult<JObject<'env>> {
+        let converted = TryIntoJavaValue::try_into(strings.clone(), env)?;
+        assert!(<T as TryFromJavaValue>::try_from(converted.into(), env)? == *strings);
+        Ok(JObject::null())
+    };
+
+    // Warm up, so that the Java methods called by conversions are compiled
+    env.with_local_frame(8, round_trip)?;
+
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        // The frame holds the array and every element of the `ArrayList`, which isn't released element by element
+        env.with_local_frame(LEN as i32 + 8, round_trip)?;
+    }
+    Ok(start.elapsed() / ROUNDS)
+}
+
+fn main() {
+    with_env(|env| {
+        let strings: Vec<String> = (0..LEN).map(|i| format!("string #{}", i)).collect();
+
+        let list = time_round_trips(env, &strings)?;
+        let array = time_round_trips(env, &strings.into_boxed_slice())?;
+
+        println!("ArrayList (Vec<String>)     {:>10?}/round trip", list);
+        println!("String[] (Box<[String]>)    {:>10?}/round trip ({:.1}x)", array, list.as_secs_f64() / array.as_secs_f64());
+        Ok(())
+    })
+}
@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn java_home() -> Option<PathBuf> {
+    if let Some(java_home) = std::env::var_os("JAVA_HOME") {
+        return Some(PathBuf::from(java_home));
+    }
+
+    let output = Command::new("java")
+        .args(["-XshowSettings:properties", "-version"])
+        .output()
+        .ok()?;
+
+    String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("java.home = ").map(PathBuf::from))
+}
+
+fn find_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    for path in std::fs::read_dir(dir).ok()?.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path.is_dir() {
+            if let Some(found) = find_file(&path, name) {
+                return Some(found);
+            }
+        } else if path.file_name().is_some_and(|n| n == name) {
+            return Some(path);
+        }
+    }
+
+    None
+}
+
+fn main() {
+    // Stubs for the exported fixtures are compiled together with the sources in `java/` when the JVM is started
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let stubs_dir = Path::new(&out_dir).join("stubs");
+
+    println!("cargo:rustc-env=ROBUSTA_JAVA_OUTPUT_DIR={}", stubs_dir.display());
+    // Kotlin declarations of the same fixtures, only compiled by `tests/kotlin_stubs.rs`
+    println!("cargo:rustc-env=ROBUSTA_KOTLIN_OUTPUT_DIR={}", Path::new(&out_dir).join("kotlin-stubs").display());
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=JAVA_HOME");
+
+    // `jni` links test and benchmark executables to the JVM library, which isn't in the default library search path
+    let jvm_library = match std::env::var("CARGO_CFG_TARGET_OS").as_deref() {
+        Ok("windows") => None,
+        Ok("macos") => Some("libjli.dylib"),
+        _ => Some("libjvm.so"),
+    };
+
+    if let Some(library_dir) = jvm_library
+        .and_then(|library| find_file(&java_home()?, library))
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+    {
+        println!("cargo:rustc-link-arg-tests=-Wl,-rpath,{}", library_dir.display());
+        println!("cargo:rustc-link-arg-benches=-Wl,-rpath,{}", library_dir.display());
+    }
+}
@@ -0,0 +1,26 @@
+//! Example of a third-party macro built on `robusta_codegen_core::api`.
+//!
+//! `#[count_calls(COUNTER)]` works like `#[bridge]`, and also increments `COUNTER` (an `AtomicUsize`) on every call
+//! of an exported method.
+
+use proc_macro::TokenStream;
+use proc_macro_error::proc_macro_error;
+use quote::quote;
+use robusta_codegen_core::api::BridgeModule;
+use syn::{parse_macro_input, Path};
+
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn count_calls(args: TokenStream, raw_input: TokenStream) -> TokenStream {
+    let counter = parse_macro_input!(args as Path);
+    let module = parse_macro_input!(raw_input as BridgeModule);
+
+    module
+        .expand_with(|_, body| {
+            quote! {{
+                #counter.fetch_add(1, ::std::sync::atomic::Ordering::SeqCst);
+                #body
+            }}
+        })
+        .into()
+}
@@ -0,0 +1,64 @@
+//! A crate using `#[bridge]` through a facade module, with `robusta_jni` renamed: `::robusta_jni` doesn't resolve here,
+//! so this crate only builds if the generated code names the crate with the path given by `#[bridge(crate = "...")]`.
+//!
+//! Building this crate (e.g. as part of `cargo test --workspace`) is the test.
+
+pub mod facade {
+    pub use jni_bridge as robusta;
+}
+
+use crate::facade::robusta::bridge;
+
+#[bridge(crate = "crate::facade::robusta", preload_classes("com.robusta.facade.Greeter"), register_natives)]
+pub mod jni {
+    use crate::facade::robusta::convert::{Signature, TryFromJavaValue, TryIntoJavaValue};
+    use crate::facade::robusta::jni::errors::Result as JniResult;
+    use crate::facade::robusta::jni::objects::JObject;
+    use crate::facade::robusta::jni::JNIEnv;
+
+    #[package(com.robusta.facade)]
+    pub struct Greeter<'env> {
+        raw: JObject<'env>,
+    }
+
+    impl Signature for Greeter<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/facade/Greeter;";
+    }
+
+    impl Signature for &Greeter<'_> {
+        const SIG_TYPE: &'static str = <Greeter as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Greeter<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Greeter<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Greeter { raw: s })
+        }
+    }
+
+    impl<'env> Greeter<'env> {
+        pub extern "jni" fn greeting(name: String) -> String {
+            format!("Hello, {}!", name)
+        }
+
+        #[call_type(safe(exception_class = "java.lang.IllegalArgumentException"))]
+        pub extern "jni" fn parse(text: &str) -> Result<i32, std::num::ParseIntError> {
+            text.parse()
+        }
+
+        pub extern "jni" fn total(values: Vec<i64>) -> i64 {
+            values.into_iter().sum()
+        }
+
+        pub extern "java" fn name(&self, env: &JNIEnv<'env>) -> JniResult<String> {}
+    }
+}
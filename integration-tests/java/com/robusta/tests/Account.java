@@ -0,0 +1,28 @@
+package com.robusta.tests;
+
+/** A mutable bean, created with its no-argument constructor and filled with its setters. */
+public class Account {
+    private String owner;
+    private long balance;
+
+    public String getOwner() {
+        return owner;
+    }
+
+    public void setOwner(String owner) {
+        this.owner = owner;
+    }
+
+    public long getBalance() {
+        return balance;
+    }
+
+    public void setBalance(long balance) {
+        this.balance = balance;
+    }
+
+    @Override
+    public String toString() {
+        return owner + ": " + balance;
+    }
+}
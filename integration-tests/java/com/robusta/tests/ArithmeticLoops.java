@@ -0,0 +1,30 @@
+package com.robusta.tests;
+
+/**
+ * Calls the {@link Arithmetic} native methods in a loop from Java, for {@code benches/primitive_calls.rs}.
+ */
+public class ArithmeticLoops {
+    public static long add(int calls) {
+        long total = 0;
+        for (int i = 0; i < calls; i++) {
+            total = Arithmetic.add(total, i);
+        }
+        return total;
+    }
+
+    public static long uncheckedAdd(int calls) {
+        long total = 0;
+        for (int i = 0; i < calls; i++) {
+            total = Arithmetic.uncheckedAdd(total, i);
+        }
+        return total;
+    }
+
+    public static long checkedAdd(int calls) {
+        long total = 0;
+        for (int i = 0; i < calls; i++) {
+            total = Arithmetic.checkedAdd(total, i);
+        }
+        return total;
+    }
+}
@@ -0,0 +1,54 @@
+package com.robusta.tests;
+
+import java.util.concurrent.CompletableFuture;
+import java.util.concurrent.ExecutionException;
+import java.util.concurrent.TimeUnit;
+
+/**
+ * Calls to the {@code async} methods of {@link AsyncCalls}, waiting for their results, for {@code tests/async_methods.rs}.
+ * A future completed exceptionally throws its exception.
+ */
+public class Awaits {
+    private static <T> T await(CompletableFuture<T> future) throws Exception {
+        try {
+            return future.get(10, TimeUnit.SECONDS);
+        } catch (ExecutionException e) {
+            if (e.getCause() instanceof RuntimeException) {
+                throw (RuntimeException) e.getCause();
+            }
+            throw e;
+        }
+    }
+
+    public static int add(int a, int b) throws Exception {
+        return await(AsyncCalls.add(a, b));
+    }
+
+    public static String greet(String name) throws Exception {
+        return await(AsyncCalls.greet(name));
+    }
+
+    public static long parse(String value) throws Exception {
+        return await(AsyncCalls.parse(value));
+    }
+
+    public static long parseStrictly(String value) throws Exception {
+        return await(AsyncCalls.parseStrictly(value));
+    }
+
+    public static int explode() throws Exception {
+        return await(AsyncCalls.explode());
+    }
+
+    public static boolean flushCompletesWithNull() throws Exception {
+        return await(AsyncCalls.flush()) == null;
+    }
+
+    /** Whether the future of {@code waitFor} is returned before it completes, and then completes with {@code key}. */
+    public static boolean returnsBeforeCompletion(String key) throws Exception {
+        CompletableFuture<String> future = AsyncCalls.waitFor(key);
+        boolean pending = !future.isDone();
+        AsyncCalls.open(key);
+        return pending && await(future).equals(key);
+    }
+}
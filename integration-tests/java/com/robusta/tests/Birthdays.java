@@ -0,0 +1,24 @@
+package com.robusta.tests;
+
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.List;
+
+public class Birthdays {
+    /**
+     * Users made older by a native method, read from the list of users it returns.
+     */
+    public static String celebrate() {
+        List<User> users = Arrays.asList(new User("Ada", 36, "ada@example.com"), new User("Grace", 45, null));
+        ArrayList<User> older = Beans.birthdays(users);
+
+        StringBuilder description = new StringBuilder();
+        for (User user : older) {
+            if (description.length() > 0) {
+                description.append(", ");
+            }
+            description.append(user.getUserName()).append(" is ").append(user.getAge());
+        }
+        return description.toString();
+    }
+}
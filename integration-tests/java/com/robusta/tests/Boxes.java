@@ -0,0 +1,31 @@
+package com.robusta.tests;
+
+import java.util.ArrayList;
+
+public class Boxes {
+    /**
+     * Unboxes every element, so that elements of any other class throw a {@code ClassCastException}.
+     */
+    public static ArrayList<Integer> doubled(ArrayList<Integer> values) {
+        ArrayList<Integer> result = new ArrayList<>();
+        for (int value : values) {
+            result.add(value * 2);
+        }
+        return result;
+    }
+
+    /**
+     * Doubles every element, keeping {@code null}s.
+     */
+    public static ArrayList<Integer> doubledOrNull(ArrayList<Integer> values) {
+        ArrayList<Integer> result = new ArrayList<>();
+        for (Integer value : values) {
+            result.add(value == null ? null : value * 2);
+        }
+        return result;
+    }
+
+    public static boolean sameInstance(Integer a, Integer b) {
+        return a == b;
+    }
+}
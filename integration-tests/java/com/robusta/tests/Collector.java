@@ -0,0 +1,28 @@
+package com.robusta.tests;
+
+import java.util.ArrayList;
+import java.util.List;
+import java.util.function.BiConsumer;
+import java.util.function.Consumer;
+
+/** Collects the values it's called with, as a `Consumer` or as a `BiConsumer` of pairs. */
+public class Collector implements Consumer<Object>, BiConsumer<Object, Object> {
+    private final List<String> values = new ArrayList<>();
+
+    @Override
+    public synchronized void accept(Object value) {
+        if ("reject".equals(value)) {
+            throw new IllegalArgumentException("rejected " + value);
+        }
+        values.add(value + " (" + value.getClass().getSimpleName() + ")");
+    }
+
+    @Override
+    public synchronized void accept(Object first, Object second) {
+        values.add(first + "=" + second + " (" + second.getClass().getSimpleName() + ")");
+    }
+
+    public synchronized String collected() {
+        return String.join(", ", values);
+    }
+}
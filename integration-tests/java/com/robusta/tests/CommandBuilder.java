@@ -0,0 +1,20 @@
+package com.robusta.tests;
+
+import java.util.ArrayList;
+import java.util.Arrays;
+
+public class CommandBuilder {
+    private final ArrayList<String> command;
+
+    public CommandBuilder(String[] command) {
+        this.command = new ArrayList<>(Arrays.asList(command));
+    }
+
+    public void args(ArrayList<String> args) {
+        command.addAll(args);
+    }
+
+    public ArrayList<String> command() {
+        return command;
+    }
+}
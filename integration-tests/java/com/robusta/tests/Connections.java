@@ -0,0 +1,7 @@
+package com.robusta.tests;
+
+public class Connections {
+    public static String describe(String host, int port, boolean secure) {
+        return Conversions.describeConnection(host, port, secure);
+    }
+}
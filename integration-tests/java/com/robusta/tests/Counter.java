@@ -0,0 +1,14 @@
+package com.robusta.tests;
+
+public class Counter {
+    public static int created = 0;
+    public static String label = "counter";
+
+    public int count;
+    public String name = "unnamed";
+
+    public Counter(int count) {
+        this.count = count;
+        created++;
+    }
+}
@@ -0,0 +1,38 @@
+package com.robusta.tests;
+
+/**
+ * Calls the {@link CriticalArrays} native methods in a loop from Java, for {@code benches/critical_arrays.rs}.
+ */
+public class CriticalLoops {
+    public static long sum(byte[] data, int calls) {
+        long total = 0;
+        for (int i = 0; i < calls; i++) {
+            total += CriticalArrays.sum(data);
+        }
+        return total;
+    }
+
+    public static long copiedSum(byte[] data, int calls) {
+        long total = 0;
+        for (int i = 0; i < calls; i++) {
+            total += CriticalArrays.copiedSum(data);
+        }
+        return total;
+    }
+
+    public static long last(byte[] data, int calls) {
+        long total = 0;
+        for (int i = 0; i < calls; i++) {
+            total += CriticalArrays.last(data);
+        }
+        return total;
+    }
+
+    public static long copiedLast(byte[] data, int calls) {
+        long total = 0;
+        for (int i = 0; i < calls; i++) {
+            total += CriticalArrays.copiedLast(data);
+        }
+        return total;
+    }
+}
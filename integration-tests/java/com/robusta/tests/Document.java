@@ -0,0 +1,12 @@
+package com.robusta.tests;
+
+/** A document with a large payload, converted by {@code fixtures/lazy_fields.rs}. */
+public class Document {
+    public String title;
+    public byte[] payload;
+
+    public Document(String title, int size) {
+        this.title = title;
+        this.payload = new byte[size];
+    }
+}
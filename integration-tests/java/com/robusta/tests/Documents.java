@@ -0,0 +1,16 @@
+package com.robusta.tests;
+
+/** Calls to {@link LazyFields} with new {@link Document}s, for {@code tests/lazy_fields.rs}. */
+public class Documents {
+    public static String title(String title, int size) {
+        return LazyFields.title(new Document(title, size));
+    }
+
+    public static int payloadSize(String title, int size) {
+        return LazyFields.payloadSize(new Document(title, size));
+    }
+
+    public static int ownedPayloadSize(String title, int size) {
+        return LazyFields.ownedPayloadSize(new Document(title, size));
+    }
+}
@@ -0,0 +1,10 @@
+package com.robusta.tests;
+
+public class Fixtures {
+    /**
+     * Loads the native library from this class, so that its class loader (the application one) is able to find native methods.
+     */
+    public static void load(String path) {
+        System.load(path);
+    }
+}
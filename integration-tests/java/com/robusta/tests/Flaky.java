@@ -0,0 +1,52 @@
+package com.robusta.tests;
+
+import java.util.concurrent.ConcurrentHashMap;
+import java.util.concurrent.atomic.AtomicInteger;
+
+/**
+ * Methods failing a given number of times before succeeding, for {@code tests/retry.rs}. Calls and failures are counted for
+ * each key, so that tests can run in parallel.
+ */
+public class Flaky {
+    private static final ConcurrentHashMap<String, AtomicInteger> failures = new ConcurrentHashMap<>();
+    private static final ConcurrentHashMap<String, AtomicInteger> calls = new ConcurrentHashMap<>();
+
+    private final String key;
+
+    public Flaky(String key) {
+        call(key, new IllegalStateException("not ready: " + key));
+        this.key = key;
+    }
+
+    /** Makes the next {@code times} calls with {@code key} fail, and resets its call count. */
+    public static void failNext(String key, int times) {
+        failures.put(key, new AtomicInteger(times));
+        calls.put(key, new AtomicInteger());
+    }
+
+    public static int calls(String key) {
+        return calls.get(key).get();
+    }
+
+    public static String fetch(String key) {
+        call(key, new IllegalStateException("not ready: " + key));
+        return "value of " + key;
+    }
+
+    public static String validate(String key) {
+        call(key, new IllegalArgumentException("invalid: " + key));
+        return key;
+    }
+
+    public int size() {
+        call(key, new IllegalStateException("not ready: " + key));
+        return key.length();
+    }
+
+    private static void call(String key, RuntimeException failure) {
+        calls.get(key).incrementAndGet();
+        if (failures.get(key).getAndDecrement() > 0) {
+            throw failure;
+        }
+    }
+}
@@ -0,0 +1,24 @@
+package com.robusta.tests;
+
+public class Greeter {
+    private static int greeted = 0;
+
+    private final String greeting;
+
+    public Greeter(String greeting) {
+        this.greeting = greeting;
+    }
+
+    public String greet(String name) {
+        greeted++;
+        return greeting + ", " + name + "!";
+    }
+
+    public static int greetedCount() {
+        return greeted;
+    }
+
+    public static int failing(String message) {
+        throw new IllegalStateException(message);
+    }
+}
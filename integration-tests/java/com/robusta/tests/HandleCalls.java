@@ -0,0 +1,17 @@
+package com.robusta.tests;
+
+public class HandleCalls {
+    public static int callStatic(int v) {
+        return Handle.twice(v);
+    }
+
+    public static boolean callStaticWithHandles(boolean sameHandle) {
+        Handle handle = new Handle();
+        return Handle.same(handle, sameHandle ? handle : new Handle());
+    }
+
+    public static boolean callInstance(boolean sameHandle) {
+        Handle handle = new Handle();
+        return handle.sameAs(sameHandle ? handle : new Handle());
+    }
+}
@@ -0,0 +1,13 @@
+package com.robusta.tests;
+
+public class IncomparableKey {
+    @Override
+    public int hashCode() {
+        return 0;
+    }
+
+    @Override
+    public boolean equals(Object other) {
+        throw new IllegalStateException("keys can't be compared");
+    }
+}
@@ -0,0 +1,16 @@
+package com.robusta.tests;
+
+import java.util.concurrent.atomic.AtomicLong;
+
+public class Incrementer {
+    /** Starts a thread incrementing `counter` `times` times, returned as an `Object` to match `JObject`. */
+    public static Object start(AtomicLong counter, int times) {
+        Thread thread = new Thread(() -> {
+            for (int i = 0; i < times; i++) {
+                counter.incrementAndGet();
+            }
+        });
+        thread.start();
+        return thread;
+    }
+}
@@ -0,0 +1,15 @@
+package com.robusta.tests;
+
+import java.util.ArrayList;
+
+public class InitializationLog {
+    private static final ArrayList<String> initialized = new ArrayList<>();
+
+    static synchronized void record(Class<?> c) {
+        initialized.add(c.getName());
+    }
+
+    public static synchronized ArrayList<String> initialized() {
+        return new ArrayList<>(initialized);
+    }
+}
@@ -0,0 +1,22 @@
+package com.robusta.tests;
+
+/** A label whose text is returned by the native methods of {@code fixtures/labels.rs}. */
+public class Label {
+    public String text;
+
+    public Label(String text) {
+        this.text = text;
+    }
+
+    public native String text();
+
+    public native String uncheckedText();
+
+    public native String display();
+
+    public native boolean isSame(Label other);
+
+    public native boolean startsWith(Label prefix);
+
+    public native boolean startsWith(String prefix);
+}
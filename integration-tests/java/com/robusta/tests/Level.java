@@ -0,0 +1,8 @@
+package com.robusta.tests;
+
+public enum Level {
+    LOW,
+    MEDIUM,
+    HIGH,
+    CRITICAL
+}
@@ -0,0 +1,6 @@
+package com.robusta.tests;
+
+/** Passed as a raw `JObject` to exported methods with a `#[sig]` parameter. */
+public interface Listener {
+    String onEvent(String event);
+}
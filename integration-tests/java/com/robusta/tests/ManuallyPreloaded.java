@@ -0,0 +1,7 @@
+package com.robusta.tests;
+
+public class ManuallyPreloaded {
+    static {
+        InitializationLog.record(ManuallyPreloaded.class);
+    }
+}
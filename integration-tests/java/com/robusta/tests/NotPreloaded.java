@@ -0,0 +1,7 @@
+package com.robusta.tests;
+
+public class NotPreloaded {
+    static {
+        InitializationLog.record(NotPreloaded.class);
+    }
+}
@@ -0,0 +1,32 @@
+package com.robusta.tests;
+
+import java.util.ArrayList;
+import java.util.Arrays;
+
+public class NullCalls {
+    /**
+     * How native methods see {@code null} and non-null arguments, in call order.
+     */
+    public static ArrayList<String> describeArguments() {
+        return new ArrayList<>(Arrays.asList(
+            Nulls.describeString(null),
+            Nulls.describeString("robusta"),
+            Nulls.describeInteger(null),
+            Nulls.describeInteger(42),
+            Nulls.describeJOptionString(null),
+            Nulls.describeJOptionString("robusta"),
+            Nulls.uncheckedDescribeString(null),
+            Nulls.uncheckedDescribeString("robusta"),
+            Nulls.uncheckedDescribeJOptionString(null),
+            Nulls.uncheckedDescribeJOptionString("robusta")
+        ));
+    }
+
+    public static boolean noneIsNull() {
+        return Nulls.noneString() == null && Nulls.uncheckedNoneString() == null;
+    }
+
+    public static String some(String value) {
+        return Nulls.someString(value);
+    }
+}
@@ -0,0 +1,14 @@
+package com.robusta.tests;
+
+public class PrefixListener implements Listener {
+    private final String prefix;
+
+    public PrefixListener(String prefix) {
+        this.prefix = prefix;
+    }
+
+    @Override
+    public String onEvent(String event) {
+        return prefix + event;
+    }
+}
@@ -0,0 +1,7 @@
+package com.robusta.tests;
+
+public class Preloaded {
+    static {
+        InitializationLog.record(Preloaded.class);
+    }
+}
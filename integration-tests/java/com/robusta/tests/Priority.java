@@ -0,0 +1,8 @@
+package com.robusta.tests;
+
+public enum Priority {
+    LOWEST,
+    NORMAL,
+    URGENT,
+    BLOCKER
+}
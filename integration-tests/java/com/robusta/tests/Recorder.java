@@ -0,0 +1,37 @@
+package com.robusta.tests;
+
+import java.util.ArrayList;
+import java.util.concurrent.CountDownLatch;
+
+public class Recorder {
+    private final ArrayList<Long> values = new ArrayList<>();
+    private final CountDownLatch entered = new CountDownLatch(1);
+    private final CountDownLatch open = new CountDownLatch(1);
+
+    public synchronized void record(long value) {
+        values.add(value);
+    }
+
+    /** Records `value` only after {@link #openGate()} is called. */
+    public void recordGated(long value) throws InterruptedException {
+        entered.countDown();
+        open.await();
+        record(value);
+    }
+
+    public void awaitGate() throws InterruptedException {
+        entered.await();
+    }
+
+    public void openGate() {
+        open.countDown();
+    }
+
+    public void fail(long value) {
+        throw new IllegalStateException("failing listener");
+    }
+
+    public synchronized long[] recorded() {
+        return values.stream().mapToLong(Long::longValue).toArray();
+    }
+}
@@ -0,0 +1,9 @@
+package com.robusta.tests;
+
+public class Registry {
+    public static int count = 0;
+
+    public static native int count();
+
+    public static native int countPlus(int offset);
+}
@@ -0,0 +1,35 @@
+package com.robusta.tests;
+
+/** A builder whose setters return {@code this}, chained by {@code tests/builders.rs}. */
+public class RequestBuilder {
+    private final String url;
+    private final StringBuilder headers = new StringBuilder();
+    private String method = "GET";
+    private int timeout = 10;
+
+    public RequestBuilder(String url) {
+        this.url = url;
+    }
+
+    public RequestBuilder method(String method) {
+        this.method = method;
+        return this;
+    }
+
+    public RequestBuilder header(String name, String value) {
+        if (name.isEmpty()) {
+            throw new IllegalArgumentException("header names can't be empty");
+        }
+        headers.append(" [").append(name).append(": ").append(value).append("]");
+        return this;
+    }
+
+    public RequestBuilder timeout(int seconds) {
+        this.timeout = seconds;
+        return this;
+    }
+
+    public String build() {
+        return method + " " + url + headers + " (" + timeout + "s)";
+    }
+}
@@ -0,0 +1,11 @@
+package com.robusta.tests;
+
+public class StringArrays {
+    public static int[] lengths(String[] values) {
+        int[] lengths = new int[values.length];
+        for (int i = 0; i < values.length; i++) {
+            lengths[i] = values[i].length();
+        }
+        return lengths;
+    }
+}
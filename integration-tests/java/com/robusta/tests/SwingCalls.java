@@ -0,0 +1,36 @@
+package com.robusta.tests;
+
+import javax.swing.SwingUtilities;
+
+public class SwingCalls {
+    private final String label;
+
+    public SwingCalls(String label) {
+        this.label = label;
+    }
+
+    public static String threadName() {
+        return Thread.currentThread().getName();
+    }
+
+    public String label() {
+        return label + " on " + threadName();
+    }
+
+    public static long add(int a, long b) {
+        if (!SwingUtilities.isEventDispatchThread()) {
+            throw new IllegalStateException("not on the event dispatch thread");
+        }
+        return a + b;
+    }
+
+    public static void fail(String message) {
+        throw new IllegalStateException(message);
+    }
+
+    public static String dispatchedFromEventDispatchThread() throws Exception {
+        String[] name = new String[1];
+        SwingUtilities.invokeAndWait(() -> name[0] = Dispatched.threadName());
+        return name[0];
+    }
+}
@@ -0,0 +1,69 @@
+package com.robusta.tests;
+
+import java.util.concurrent.CountDownLatch;
+
+public class Tally {
+    public int count;
+
+    public native void increment();
+
+    public native void incrementSynchronized();
+
+    public native boolean meet(CountDownLatch latch);
+
+    /**
+     * Increments a new tally {@code times} times from each of {@code threads} threads started together, with
+     * {@code incrementSynchronized} or {@code increment}, returning its count.
+     */
+    public static int incrementConcurrently(boolean synchronize, int threads, int times) throws InterruptedException {
+        Tally tally = new Tally();
+        CountDownLatch start = new CountDownLatch(1);
+        Thread[] workers = new Thread[threads];
+        for (int i = 0; i < threads; i++) {
+            workers[i] = new Thread(() -> {
+                try {
+                    start.await();
+                } catch (InterruptedException e) {
+                    throw new RuntimeException(e);
+                }
+
+                for (int j = 0; j < times; j++) {
+                    if (synchronize) {
+                        tally.incrementSynchronized();
+                    } else {
+                        tally.increment();
+                    }
+                }
+            });
+            workers[i].start();
+        }
+
+        start.countDown();
+        for (Thread worker : workers) {
+            worker.join();
+        }
+        return tally.count;
+    }
+
+    /**
+     * Calls {@code meet} on a new tally from each of {@code threads} threads, returning whether all the calls met.
+     */
+    public static boolean meetConcurrently(int threads) throws InterruptedException {
+        Tally tally = new Tally();
+        CountDownLatch latch = new CountDownLatch(threads);
+        boolean[] met = new boolean[threads];
+        Thread[] workers = new Thread[threads];
+        for (int i = 0; i < threads; i++) {
+            int worker = i;
+            workers[i] = new Thread(() -> met[worker] = tally.meet(latch));
+            workers[i].start();
+        }
+
+        boolean allMet = true;
+        for (int i = 0; i < threads; i++) {
+            workers[i].join();
+            allMet &= met[i];
+        }
+        return allMet;
+    }
+}
@@ -0,0 +1,13 @@
+package com.robusta.tests;
+
+public class Temperature {
+    public double celsius;
+
+    public Temperature(double celsius) {
+        this.celsius = celsius;
+    }
+
+    public native double fahrenheit();
+
+    public native double kelvin();
+}
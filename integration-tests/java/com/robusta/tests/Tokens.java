@@ -0,0 +1,7 @@
+package com.robusta.tests;
+
+public class Tokens {
+    public static Object newToken() {
+        return new Object();
+    }
+}
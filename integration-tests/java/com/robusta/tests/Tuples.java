@@ -0,0 +1,10 @@
+package com.robusta.tests;
+
+public class Tuples {
+    public static String describeNameAndLength(String name) {
+        Object[] nameAndLength = Conversions.nameAndLength(name);
+        String returnedName = (String) nameAndLength[0];
+        int length = (Integer) nameAndLength[1];
+        return returnedName + " has " + length + " characters";
+    }
+}
@@ -0,0 +1,31 @@
+package com.robusta.tests;
+
+/** An immutable value class, created with a constructor taking every property. */
+public class User {
+    private final String userName;
+    private final int age;
+    private final String email;
+
+    public User(String userName, int age, String email) {
+        this.userName = userName;
+        this.age = age;
+        this.email = email;
+    }
+
+    public String getUserName() {
+        return userName;
+    }
+
+    public int getAge() {
+        return age;
+    }
+
+    public String getEmail() {
+        return email;
+    }
+
+    @Override
+    public String toString() {
+        return userName + " (" + age + ", " + email + ")";
+    }
+}
@@ -0,0 +1,28 @@
+package com.robusta.tests
+
+import kotlinx.coroutines.delay
+import kotlinx.coroutines.yield
+
+/**
+ * Suspend functions called through `#[suspend]` imported methods (see `tests/coroutines.rs`).
+ */
+class Suspending(private val greeting: String) {
+    suspend fun greet(name: String?): String {
+        // Actually suspends, so that the result is only available once the coroutine is resumed
+        delay(10)
+        return "$greeting, ${name ?: "stranger"}"
+    }
+
+    suspend fun fail(message: String): String {
+        yield()
+        throw IllegalStateException(message)
+    }
+
+    companion object {
+        @JvmStatic
+        suspend fun answer(offset: Int): Int {
+            yield()
+            return 42 + offset
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! A `#![no_std]` crate using `#[bridge]`, which only builds if the generated code doesn't depend on the `std` prelude or paths.
+//!
+//! Code generated by the macros must only name `::core` items, or items re-exported by `robusta_jni`. Building this crate
+//! (e.g. as part of `cargo test --workspace`) is the test.
+
+#![no_std]
+
+extern crate alloc;
+
+use robusta_jni::bridge;
+
+#[bridge(preload_classes("com.robusta.nostd.Portable"))]
+pub mod jni {
+    use alloc::string::String;
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+
+    use robusta_jni::convert::{Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.nostd)]
+    pub struct Portable<'env> {
+        raw: JObject<'env>,
+    }
+
+    impl Signature for Portable<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/nostd/Portable;";
+    }
+
+    impl Signature for &Portable<'_> {
+        const SIG_TYPE: &'static str = <Portable as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Portable<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Portable<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Portable { raw: s })
+        }
+    }
+
+    impl<'env> Portable<'env> {
+        pub extern "jni" fn sum(values: Vec<i64>) -> i64 {
+            values.into_iter().sum()
+        }
+
+        #[call_type(safe(exception_class = "java.lang.IllegalArgumentException"))]
+        pub extern "jni" fn parse(text: &str) -> Result<i32, core::num::ParseIntError> {
+            text.parse()
+        }
+
+        #[deprecated(since = "0.2.0", note = "use `greeting` instead")]
+        #[deprecation_warning]
+        #[call_type(safe(message = "no greeting"))]
+        pub extern "jni" fn hello(name: String) -> JniResult<String> {
+            Ok(name)
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn greeting(name: String) -> String {
+            name
+        }
+
+        #[saturate]
+        pub extern "jni" fn length(data: &[u8], #[default = "0"] offset: i64) -> u64 {
+            (data.len() as u64).saturating_sub(offset as u64)
+        }
+
+        pub extern "jni" fn tag(#[intern_cache(size = 8)] tag: Arc<str>) -> String {
+            String::from(&*tag)
+        }
+
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>, count: i32) -> JniResult<Self> {}
+
+        pub extern "java" fn describe(&self, env: JNIEnv, verbose: bool) -> JniResult<String> {}
+
+        #[field]
+        pub extern "java" fn get_count(&self, env: JNIEnv<'env>) -> JniResult<i32> {}
+    }
+}
@@ -0,0 +1,33 @@
+//! Exported methods only taking and returning primitives, which are called without conversions.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    #[package(com.robusta.tests)]
+    pub struct Arithmetic;
+
+    impl Arithmetic {
+        pub extern "jni" fn add(a: i64, b: i64) -> i64 {
+            a.wrapping_add(b)
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedAdd(a: i64, b: i64) -> i64 {
+            a.wrapping_add(b)
+        }
+
+        /// Same as `add`, through the conversions of methods returning a `Result`.
+        pub extern "jni" fn checkedAdd(a: i64, b: i64) -> Result<i64, String> {
+            a.checked_add(b).ok_or_else(|| format!("{} + {} overflows", a, b))
+        }
+
+        pub extern "jni" fn divide(a: i32, b: i32) -> i32 {
+            a / b
+        }
+
+        pub extern "jni" fn mean(a: f64, b: f32) -> f64 {
+            (a + f64::from(b)) / 2.0
+        }
+    }
+}
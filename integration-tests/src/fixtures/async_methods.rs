@@ -0,0 +1,106 @@
+//! `async` exported methods, whose futures run on a thread of their own.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+use robusta_jni::bridge;
+use robusta_jni::runtime::{self, BoxFuture};
+
+/// Gates opened by [`AsyncCalls::open`], with the waker of the future waiting on each.
+static GATES: Mutex<BTreeMap<String, (bool, Option<Waker>)>> = Mutex::new(BTreeMap::new());
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Polls `future` on the current thread until it completes, parking the thread in between.
+fn block_on(mut future: BoxFuture) {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    while future.as_mut().poll(&mut cx).is_pending() {
+        thread::park();
+    }
+}
+
+/// Future completing once the gate `key` is opened.
+struct Gate {
+    key: String,
+}
+
+impl Future for Gate {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut gates = GATES.lock().unwrap();
+        let gate = gates.entry(self.key.clone()).or_default();
+        if gate.0 {
+            Poll::Ready(())
+        } else {
+            gate.1 = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[bridge]
+pub mod jni {
+    use super::*;
+
+    #[package(com.robusta.tests)]
+    pub struct AsyncCalls;
+
+    impl AsyncCalls {
+        /// Runs each future on a new thread, which isn't attached to the JVM beforehand.
+        pub extern "jni" fn installSpawner() {
+            runtime::set_spawner(|future| {
+                thread::spawn(move || block_on(future));
+            });
+        }
+
+        pub async extern "jni" fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        pub async extern "jni" fn greet(name: String) -> String {
+            format!("Hello, {}!", name)
+        }
+
+        pub async extern "jni" fn parse(value: String) -> Result<i64, String> {
+            value.parse().map_err(|_| format!("not a number: {}", value))
+        }
+
+        #[call_type(safe(exception_class = "java.lang.IllegalArgumentException"))]
+        pub async extern "jni" fn parseStrictly(value: String) -> Result<i64, String> {
+            value.parse().map_err(|_| format!("not a number: {}", value))
+        }
+
+        pub async extern "jni" fn explode() -> i32 {
+            panic!("exploded asynchronously")
+        }
+
+        pub async extern "jni" fn flush() {}
+
+        /// Completes with `key` once the gate `key` is opened.
+        pub async extern "jni" fn waitFor(key: String) -> String {
+            Gate { key: key.clone() }.await;
+            key
+        }
+
+        pub extern "jni" fn open(key: String) {
+            let mut gates = GATES.lock().unwrap();
+            let gate = gates.entry(key).or_default();
+            gate.0 = true;
+            if let Some(waker) = gate.1.take() {
+                waker.wake();
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! Exported methods updating counters shared with Java.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::convert::JAtomicLong;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Atomics;
+
+    impl Atomics {
+        pub extern "jni" fn incrementTimes<'env>(counter: JAtomicLong<'env>, times: i32) -> JniResult<i64> {
+            for _ in 0..times {
+                counter.fetch_add(1)?;
+            }
+
+            counter.get()
+        }
+
+        pub extern "jni" fn newCounter<'env>(env: JNIEnv<'env>, value: i64) -> JniResult<JAtomicLong<'env>> {
+            JAtomicLong::new(env, value)
+        }
+    }
+}
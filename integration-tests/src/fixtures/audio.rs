@@ -0,0 +1,22 @@
+//! Exported methods taking and returning interleaved audio samples.
+
+#[robusta_jni::bridge]
+pub mod jni {
+    use robusta_jni::audio::Samples;
+
+    #[package(com.robusta.tests)]
+    pub struct Audio;
+
+    impl Audio {
+        pub extern "jni" fn swapChannels(samples: Samples<i16>) -> Samples<i16> {
+            let channels = samples.channels();
+            let data = samples.iter_frames().flat_map(|frame| frame.iter().rev().copied()).collect();
+
+            Samples::new(data, channels).unwrap()
+        }
+
+        pub extern "jni" fn floatIdentity(samples: Samples<f32>) -> Samples<f32> {
+            samples
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Exported methods taking and returning plain data structs, converted with the accessors of their Java classes.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+    #[derive(Signature, TryFromJavaValue, TryIntoJavaValue, FromJavaValue, IntoJavaValue, Clone, Debug, PartialEq)]
+    #[package(com.robusta.tests)]
+    pub struct User {
+        #[java_field(name = "userName")]
+        pub name: String,
+        pub age: i32,
+        pub email: Option<String>,
+    }
+
+    #[derive(Signature, TryFromJavaValue, TryIntoJavaValue, Clone, Debug, PartialEq)]
+    #[package(com.robusta.tests)]
+    #[bean(setters)]
+    pub struct Account {
+        pub owner: String,
+        pub balance: i64,
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct Beans;
+
+    impl Beans {
+        pub extern "jni" fn birthday(user: User) -> User {
+            User { age: user.age + 1, ..user }
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedBirthday(user: User) -> User {
+            User { age: user.age + 1, ..user }
+        }
+
+        pub extern "jni" fn birthdays(users: Vec<User>) -> Vec<User> {
+            users.into_iter().map(|user| User { age: user.age + 1, ..user }).collect()
+        }
+
+        pub extern "jni" fn deposit(account: Account, amount: i64) -> Account {
+            Account { balance: account.balance + amount, ..account }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Exported methods reading direct buffers allocated by Java, and giving Rust allocations to Java as direct buffers.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::convert::{DirectByteBuffer, DirectBytes};
+
+    #[package(com.robusta.tests)]
+    pub struct Buffers;
+
+    impl Buffers {
+        /// Sum of the bytes from the position to the limit of the buffer, as unsigned bytes.
+        pub extern "jni" fn sum<'env>(buffer: DirectByteBuffer<'env>) -> i64 {
+            buffer.iter().map(|b| *b as i64).sum()
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedSum<'env>(buffer: DirectByteBuffer<'env>) -> i64 {
+            buffer.iter().map(|b| *b as i64).sum()
+        }
+
+        /// Address of the first byte read from the buffer.
+        pub extern "jni" fn address<'env>(buffer: DirectByteBuffer<'env>) -> i64 {
+            buffer.as_ptr() as i64
+        }
+
+        pub extern "jni" fn filled(len: i32, value: i8) -> DirectBytes {
+            DirectBytes::from(vec![value as u8; len as usize])
+        }
+
+        /// Takes back a buffer created by `filled`, returning the sum of its bytes.
+        pub extern "jni" fn release(buffer: DirectBytes) -> i64 {
+            buffer.iter().map(|b| *b as i64).sum()
+        }
+    }
+}
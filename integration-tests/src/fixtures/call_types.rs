@@ -0,0 +1,21 @@
+//! Exported methods inheriting their call type from the module.
+
+use robusta_jni::bridge;
+
+#[bridge]
+#[call_type(unchecked(catch_panics))]
+pub mod jni {
+    #[package(com.robusta.tests)]
+    pub struct CallTypes;
+
+    impl CallTypes {
+        pub extern "jni" fn uncheckedParse(s: String) -> Result<i32, String> {
+            s.parse().map_err(|_| format!("`{}` is not a number", s))
+        }
+
+        #[call_type(safe(exception_class = "java.lang.IllegalArgumentException"))]
+        pub extern "jni" fn safeParse(s: String) -> Result<i32, String> {
+            s.parse().map_err(|_| format!("`{}` is not a number", s))
+        }
+    }
+}
@@ -0,0 +1,24 @@
+//! Static exported methods taking the class they're called on.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JClass;
+    use robusta_jni::jni::JNIEnv;
+
+    /// Declared in `java/`, with a static `count` field.
+    #[package(com.robusta.tests)]
+    pub struct Registry;
+
+    impl Registry {
+        pub extern "jni" fn count<'env>(env: JNIEnv<'env>, class: JClass<'env>) -> JniResult<i32> {
+            env.get_static_field(class, "count", "I")?.i()
+        }
+
+        pub extern "jni" fn countPlus<'env>(env: &JNIEnv<'env>, class: JClass<'env>, offset: i32) -> JniResult<i32> {
+            Ok(env.get_static_field(class, "count", "I")?.i()? + offset)
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! Identity functions for each library-provided conversion.
+
+use std::net::{AddrParseError, IpAddr};
+
+use robusta_jni::bridge;
+use robusta_jni::convert::{Charset, Mapper, ReverseMapper};
+
+/// A charset name no JVM knows.
+pub enum NoSuchCharset {}
+
+impl Charset for NoSuchCharset {
+    const NAME: &'static str = "no-such-charset";
+}
+
+/// Parses strings as IP addresses, for `Adapt<String, ParseIp>`.
+pub enum ParseIp {}
+
+impl Mapper<String> for ParseIp {
+    type Output = IpAddr;
+    type Error = AddrParseError;
+
+    fn map(value: String) -> Result<IpAddr, AddrParseError> {
+        value.parse()
+    }
+}
+
+impl ReverseMapper<String> for ParseIp {
+    fn unmap(value: IpAddr) -> String {
+        value.to_string()
+    }
+}
+
+#[bridge]
+pub mod jni {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::time::{Duration, SystemTime};
+
+    use chrono::{DateTime, Utc};
+
+    use robusta_jni::convert::{Adapt, BigIntegerBytes, Boxed, EncodedString, JavaArray, Latin1, Passthrough, PassthroughOwned, Utf16Le};
+
+    use super::{NoSuchCharset, ParseIp};
+
+    #[package(com.robusta.tests)]
+    pub struct Conversions;
+
+    pub struct ConnectionConfig {
+        host: String,
+        port: i32,
+        secure: bool,
+    }
+
+    impl Conversions {
+        pub extern "jni" fn intIdentity(v: i32) -> i32 {
+            v
+        }
+
+        pub extern "jni" fn boolIdentity(v: bool) -> bool {
+            v
+        }
+
+        pub extern "jni" fn charIdentity(v: char) -> char {
+            v
+        }
+
+        pub extern "jni" fn byteIdentity(v: i8) -> i8 {
+            v
+        }
+
+        pub extern "jni" fn floatIdentity(v: f32) -> f32 {
+            v
+        }
+
+        pub extern "jni" fn doubleIdentity(v: f64) -> f64 {
+            v
+        }
+
+        pub extern "jni" fn longIdentity(v: i64) -> i64 {
+            v
+        }
+
+        pub extern "jni" fn shortIdentity(v: i16) -> i16 {
+            v
+        }
+
+        pub extern "jni" fn unsignedIntIdentity(v: u32) -> u32 {
+            v
+        }
+
+        pub extern "jni" fn unsignedLongIdentity(v: u64) -> u64 {
+            v
+        }
+
+        pub extern "jni" fn usizeIdentity(v: usize) -> usize {
+            v
+        }
+
+        pub extern "jni" fn unsignedLongMax() -> u64 {
+            u64::MAX
+        }
+
+        pub extern "jni" fn stringIdentity(v: String) -> String {
+            v
+        }
+
+        pub extern "jni" fn intVecIdentity(v: Vec<i32>) -> Vec<i32> {
+            v
+        }
+
+        pub extern "jni" fn stringVecIdentity(v: Vec<String>) -> Vec<String> {
+            v
+        }
+
+        pub extern "jni" fn joinStrings(v: Vec<String>) -> String {
+            v.join(",")
+        }
+
+        #[java_list]
+        pub extern "jni" fn stringListIdentity(v: Vec<String>) -> Vec<String> {
+            v
+        }
+
+        pub extern "jni" fn boxedIntIdentity(v: Boxed<i32>) -> Boxed<i32> {
+            v
+        }
+
+        pub extern "jni" fn boxedCharIdentity(v: Boxed<char>) -> Boxed<char> {
+            v
+        }
+
+        pub extern "jni" fn boxedDoubleIdentity(v: Boxed<f64>) -> Boxed<f64> {
+            v
+        }
+
+        pub extern "jni" fn boxedLongVecIdentity(v: Vec<Boxed<i64>>) -> Vec<Boxed<i64>> {
+            v
+        }
+
+        pub extern "jni" fn optionalIntIdentity(v: Option<i32>) -> Option<i32> {
+            v
+        }
+
+        pub extern "jni" fn optionalBoolIdentity(v: Option<bool>) -> Option<bool> {
+            v
+        }
+
+        pub extern "jni" fn optionalCharIdentity(v: Option<char>) -> Option<char> {
+            v
+        }
+
+        pub extern "jni" fn optionalStringIdentity(v: Option<String>) -> Option<String> {
+            v
+        }
+
+        pub extern "jni" fn optionalIntVecIdentity(v: Option<Vec<i32>>) -> Option<Vec<i32>> {
+            v
+        }
+
+        pub extern "jni" fn optionalStringVecIdentity(v: Vec<Option<String>>) -> Vec<Option<String>> {
+            v
+        }
+
+        pub extern "jni" fn intArrayIdentity(v: JavaArray<i32>) -> JavaArray<i32> {
+            v
+        }
+
+        pub extern "jni" fn boolArrayIdentity(v: JavaArray<bool>) -> JavaArray<bool> {
+            v
+        }
+
+        pub extern "jni" fn charArrayIdentity(v: JavaArray<char>) -> JavaArray<char> {
+            v
+        }
+
+        pub extern "jni" fn doubleArrayIdentity(v: JavaArray<f64>) -> JavaArray<f64> {
+            v
+        }
+
+        pub extern "jni" fn stringArrayIdentity(v: JavaArray<String>) -> JavaArray<String> {
+            v
+        }
+
+        pub extern "jni" fn stringSliceIdentity(v: Box<[String]>) -> Box<[String]> {
+            v
+        }
+
+        pub extern "jni" fn optionalStringArrayIdentity(v: JavaArray<Option<String>>) -> JavaArray<Option<String>> {
+            v
+        }
+
+        pub extern "jni" fn boxedLongArrayIdentity(v: JavaArray<Boxed<i64>>) -> JavaArray<Boxed<i64>> {
+            v
+        }
+
+        pub extern "jni" fn nameAndLength(name: String) -> (String, i32) {
+            let length = name.chars().count() as i32;
+            (name, length)
+        }
+
+        pub extern "jni" fn pairIdentity(v: (String, i32)) -> (String, i32) {
+            v
+        }
+
+        pub extern "jni" fn tripleIdentity(v: (Option<String>, i64, bool)) -> (Option<String>, i64, bool) {
+            v
+        }
+
+        pub extern "jni" fn nestedTupleIdentity(v: (char, (f64, String))) -> (char, (f64, String)) {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedPairIdentity(v: (String, i32)) -> (String, i32) {
+            v
+        }
+
+        pub extern "jni" fn passthroughIdentity<'env>(v: Passthrough<'env>) -> Passthrough<'env> {
+            v
+        }
+
+        pub extern "jni" fn ownedPassthroughIdentity(v: PassthroughOwned) -> PassthroughOwned {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedPassthroughIdentity<'env>(v: Passthrough<'env>) -> Passthrough<'env> {
+            v
+        }
+
+        pub extern "jni" fn describeConnection(#[flatten] config: ConnectionConfig) -> String {
+            let scheme = if config.secure { "https" } else { "http" };
+            format!("{}://{}:{}", scheme, config.host, config.port)
+        }
+
+        pub extern "jni" fn latin1StringIdentity(v: EncodedString<Latin1>) -> EncodedString<Latin1> {
+            v
+        }
+
+        pub extern "jni" fn utf16LeStringIdentity(v: EncodedString<Utf16Le>) -> EncodedString<Utf16Le> {
+            v
+        }
+
+        pub extern "jni" fn unsupportedCharsetLength(v: EncodedString<NoSuchCharset>) -> i32 {
+            v.len() as i32
+        }
+
+        pub extern "jni" fn isLoopback(address: Adapt<String, ParseIp>) -> bool {
+            address.is_loopback()
+        }
+
+        pub extern "jni" fn loopback(v6: bool) -> Adapt<String, ParseIp> {
+            Adapt::new(if v6 { IpAddr::V6(Ipv6Addr::LOCALHOST) } else { IpAddr::V4(Ipv4Addr::LOCALHOST) })
+        }
+
+        pub extern "jni" fn durationIdentity(v: Duration) -> Duration {
+            v
+        }
+
+        pub extern "jni" fn instantIdentity(v: SystemTime) -> SystemTime {
+            v
+        }
+
+        pub extern "jni" fn dateTimeIdentity(v: DateTime<Utc>) -> DateTime<Utc> {
+            v
+        }
+
+        pub extern "jni" fn i128Identity(v: i128) -> i128 {
+            v
+        }
+
+        pub extern "jni" fn u128Identity(v: u128) -> u128 {
+            v
+        }
+
+        pub extern "jni" fn bigIntegerBytesIdentity(v: BigIntegerBytes) -> BigIntegerBytes {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedI128Identity(v: i128) -> i128 {
+            v
+        }
+
+        pub extern "jni" fn optionalDurationIdentity(v: Option<Duration>) -> Option<Duration> {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedDurationIdentity(v: Duration) -> Duration {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedInstantIdentity(v: SystemTime) -> SystemTime {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedOptionalLongIdentity(v: Option<i64>) -> Option<i64> {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedOptionalStringIdentity(v: Option<String>) -> Option<String> {
+            v
+        }
+
+        #[reinterpret]
+        pub extern "jni" fn reinterpretedIntIdentity(#[reinterpret] v: u32) -> u32 {
+            v
+        }
+
+        #[reinterpret]
+        pub extern "jni" fn reinterpretedByteIdentity(#[reinterpret] v: u8) -> u8 {
+            v
+        }
+
+        pub extern "jni" fn describeReinterpretedInt(#[reinterpret] v: u32) -> String {
+            v.to_string()
+        }
+
+        #[saturate]
+        pub extern "jni" fn saturatedSum(a: i64, b: i64) -> Result<i64, String> {
+            a.checked_add(b).ok_or_else(|| "overflow".to_string())
+        }
+
+        #[truncate]
+        pub extern "jni" fn truncatedUnsignedLongIdentity(#[truncate] v: u64) -> u64 {
+            v
+        }
+
+        #[reinterpret]
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedReinterpretedLongIdentity(#[reinterpret] v: u64) -> u64 {
+            v
+        }
+
+        pub extern "jni" fn borrowedStringIdentity(v: &str) -> String {
+            v.to_string()
+        }
+
+        pub extern "jni" fn borrowedStringCharCount(v: &str) -> i32 {
+            v.chars().count() as i32
+        }
+
+        pub extern "jni" fn borrowedByteSum(data: &[u8]) -> i64 {
+            data.iter().map(|&b| b as i64).sum()
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedBorrowedStringIdentity(v: &str) -> String {
+            v.to_string()
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedIntIdentity(v: i32) -> i32 {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedStringIdentity(v: String) -> String {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedStringArrayIdentity(v: JavaArray<String>) -> JavaArray<String> {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedStringSliceIdentity(v: Box<[String]>) -> Box<[String]> {
+            v
+        }
+    }
+}
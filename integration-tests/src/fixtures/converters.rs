@@ -0,0 +1,49 @@
+//! Exported methods converting parameters and return values with functions given by `#[convert]`, here between hex strings
+//! and bytes.
+
+use robusta_jni::bridge;
+
+/// Converts hex strings passed by Java to bytes, and back.
+pub mod hex {
+    use std::num::ParseIntError;
+
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JString;
+    use robusta_jni::jni::JNIEnv;
+
+    pub fn from_java<'env>(hex: JString<'env>, env: JNIEnv<'env>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let hex: String = env.get_string(hex)?.into();
+        let bytes: Result<Vec<u8>, ParseIntError> = (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..(i + 2).min(hex.len())], 16)).collect();
+        Ok(bytes?)
+    }
+
+    pub fn from_java_unchecked<'env>(hex: JString<'env>, env: JNIEnv<'env>) -> Vec<u8> {
+        from_java(hex, env).unwrap()
+    }
+
+    pub fn to_java<'env>(bytes: Vec<u8>, env: JNIEnv<'env>) -> JniResult<JString<'env>> {
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        env.new_string(hex)
+    }
+}
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::jni::objects::JString;
+
+    #[package(com.robusta.tests)]
+    pub struct Converters;
+
+    impl Converters {
+        #[convert(into_with = "super::hex::to_java", target = "JString<'env>")]
+        pub extern "jni" fn reverseHex(#[convert(with = "super::hex::from_java", source = "JString<'_>")] bytes: Vec<u8>) -> Vec<u8> {
+            bytes.into_iter().rev().collect()
+        }
+
+        /// Passed as a `JObject`, which converts to the `JString` the function takes.
+        #[call_type(unchecked)]
+        pub extern "jni" fn hexLength(#[convert(with = "super::hex::from_java_unchecked")] #[sig("Ljava/lang/String;")] bytes: Vec<u8>) -> i32 {
+            bytes.len() as i32
+        }
+    }
+}
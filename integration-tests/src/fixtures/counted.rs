@@ -0,0 +1,28 @@
+//! Exported methods instrumented by a third-party macro, `call_counter::count_calls`, built on `robusta_codegen_core::api`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use call_counter::count_calls;
+
+/// Number of calls of the exported methods of [`jni::Counted`], in the library loaded by the JVM.
+pub static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[count_calls(crate::fixtures::counted::CALLS)]
+pub mod jni {
+    use super::*;
+
+    #[package(com.robusta.tests)]
+    pub struct Counted;
+
+    impl Counted {
+        pub extern "jni" fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        pub extern "jni" fn ping() {}
+
+        pub extern "jni" fn calls() -> i64 {
+            CALLS.load(Ordering::SeqCst) as i64
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Byte array parameters accessed inside a critical region.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::convert::critical::in_critical_region;
+    use robusta_jni::convert::{CriticalBytes, JavaArray};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct CriticalArrays;
+
+    impl CriticalArrays {
+        pub extern "jni" fn sum<'env>(data: CriticalBytes<'env>) -> i64 {
+            data.iter().map(|&b| b as i64).sum()
+        }
+
+        /// Same as `sum`, with the array copied into Rust memory.
+        pub extern "jni" fn copiedSum(data: JavaArray<i8>) -> i64 {
+            data.iter().map(|&b| b as u8 as i64).sum()
+        }
+
+        /// Only reads the last byte, so that copying the array is most of the work of `copiedLast`.
+        pub extern "jni" fn last<'env>(data: CriticalBytes<'env>) -> i64 {
+            data.last().map_or(-1, |&b| b as i64)
+        }
+
+        /// Same as `last`, with the array copied into Rust memory.
+        pub extern "jni" fn copiedLast(data: JavaArray<i8>) -> i64 {
+            data.last().map_or(-1, |&b| b as u8 as i64)
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedSum<'env>(data: CriticalBytes<'env>) -> i64 {
+            data.iter().map(|&b| b as i64).sum()
+        }
+
+        pub extern "jni" fn fill<'env>(value: i8, mut data: CriticalBytes<'env>) {
+            data.fill(value as u8);
+        }
+
+        /// Fills the array, then panics while it's still borrowed.
+        pub extern "jni" fn fillThenPanic<'env>(value: i8, mut data: CriticalBytes<'env>) {
+            data.fill(value as u8);
+            panic!("panicked inside the critical region");
+        }
+
+        /// Whether the thread is inside a critical region while `data` is alive, and outside once it's dropped.
+        pub extern "jni" fn regionLasts<'env>(data: CriticalBytes<'env>) -> bool {
+            let inside = in_critical_region();
+            drop(data);
+            inside && !in_critical_region()
+        }
+
+        pub extern "jni" fn inCriticalRegion() -> bool {
+            in_critical_region()
+        }
+
+        #[java_name = "inCriticalRegion"]
+        pub extern "java" fn java_in_critical_region(env: &JNIEnv) -> JniResult<bool> {}
+
+        /// Calls back into Java with `data` alive, which debug builds report with a panic.
+        pub extern "jni" fn callJavaInside<'env>(env: JNIEnv<'env>, data: CriticalBytes<'env>) -> JniResult<bool> {
+            let inside = Self::java_in_critical_region(&env);
+            drop(data);
+            inside
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Exported methods with `#[default]` parameters.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    // The generated default values stay unambiguous with the conversion traits in scope
+    #[allow(unused_imports)]
+    use robusta_jni::convert::{FromJavaValue, IntoJavaValue, TryFromJavaValue, TryIntoJavaValue};
+
+    #[package(com.robusta.tests)]
+    pub struct Defaults;
+
+    impl Defaults {
+        pub extern "jni" fn orDefault(v: i32, #[default = "100"] fallback: i32) -> String {
+            format!("{} {}", v, fallback)
+        }
+
+        pub extern "jni" fn join(values: Vec<String>, #[default = ", "] separator: String) -> String {
+            values.join(&separator)
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedOrDefault(v: i64, #[default = -1] fallback: i64) -> i64 {
+            v + fallback
+        }
+    }
+}
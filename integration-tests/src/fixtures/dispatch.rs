@@ -0,0 +1,26 @@
+//! Exported methods calling imported methods on the Swing event dispatch thread, for calls made from that thread.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Dispatched;
+
+    impl Dispatched {
+        pub extern "jni" fn threadName<'env>(env: JNIEnv<'env>) -> JniResult<String> {
+            SwingCalls::thread_name(env)
+        }
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct SwingCalls;
+
+    impl SwingCalls {
+        #[dispatch(swing)]
+        pub extern "java" fn thread_name<'env>(env: JNIEnv<'env>) -> JniResult<String> {}
+    }
+}
@@ -0,0 +1,23 @@
+//! Exported methods keeping Java objects in Rust sets.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use std::collections::HashSet;
+
+    use robusta_jni::convert::{ByJavaEquality, ByJavaString};
+
+    #[package(com.robusta.tests)]
+    pub struct Keys;
+
+    impl Keys {
+        pub extern "jni" fn distinctCount<'env>(values: Vec<ByJavaEquality<'env>>) -> i32 {
+            values.into_iter().collect::<HashSet<_>>().len() as i32
+        }
+
+        pub extern "jni" fn distinctStringCount(values: Vec<ByJavaString>) -> i32 {
+            values.into_iter().collect::<HashSet<_>>().len() as i32
+        }
+    }
+}
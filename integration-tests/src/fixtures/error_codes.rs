@@ -0,0 +1,35 @@
+//! Exported methods taking and returning an error code enum.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::convert::ErrorCode;
+
+    #[derive(Clone, Copy, Debug, PartialEq, ErrorCode)]
+    #[package(com.robusta.tests)]
+    pub enum Status {
+        Success = 0,
+        NotFound = 2,
+        PermissionDenied = -13,
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct ErrorCodes;
+
+    impl ErrorCodes {
+        pub extern "jni" fn echo(status: Status) -> Status {
+            status
+        }
+
+        #[call_type(unchecked(catch_panics))]
+        pub extern "jni" fn uncheckedEcho(status: Status) -> Status {
+            status
+        }
+
+        /// Name of the variant with code `code`, looked up on the Rust side.
+        pub extern "jni" fn rustName(code: i32) -> Option<String> {
+            Status::name_of(code).map(String::from)
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Exported methods raising Java exceptions.
+
+use std::error::Error;
+use std::{fmt, io};
+
+use robusta_jni::bridge;
+use robusta_jni::exception::JavaException;
+
+#[derive(Debug)]
+pub struct NegativeValue(i32);
+
+impl fmt::Display for NegativeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is negative", self.0)
+    }
+}
+
+#[derive(Debug, JavaException)]
+pub enum RepoError {
+    #[java_exception("java.util.NoSuchElementException")]
+    NotFound(String),
+    #[java_exception("java.lang.SecurityException")]
+    PermissionDenied,
+    #[java_exception("java.io.IOException")]
+    Io(io::Error),
+    #[java_exception("com.robusta.tests.MissingException")]
+    Unsupported,
+    Corrupted,
+}
+
+impl fmt::Display for RepoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepoError::NotFound(key) => write!(f, "no value for `{}`", key),
+            RepoError::PermissionDenied => f.write_str("permission denied"),
+            RepoError::Io(_) => f.write_str("can't read the repository"),
+            RepoError::Unsupported => f.write_str("unsupported key"),
+            RepoError::Corrupted => f.write_str("corrupted repository"),
+        }
+    }
+}
+
+impl Error for RepoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RepoError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[bridge]
+pub mod jni {
+    use std::io;
+
+    use robusta_jni::jni::errors::Error;
+    use robusta_jni::jni::JNIEnv;
+
+    use super::{NegativeValue, RepoError};
+
+    #[package(com.robusta.tests)]
+    pub struct Errors;
+
+    impl Errors {
+        pub extern "jni" fn failing() -> ::robusta_jni::jni::errors::Result<i32> {
+            Err(Error::NullPtr("failing fixture"))
+        }
+
+        #[call_type(safe(exception_class = "java.lang.IllegalArgumentException", message = "invalid char value"))]
+        pub extern "jni" fn checkedChar(c: char) -> char {
+            c
+        }
+
+        pub extern "jni" fn plainChar(c: char) -> char {
+            c
+        }
+
+        #[call_type(safe(exception_class = "java.lang.IllegalStateException", message = "invalid unsigned value"))]
+        pub extern "jni" fn checkedUnsigned(v: u32) -> u32 {
+            v
+        }
+
+        pub extern "jni" fn greeting(name: String) -> Result<String, String> {
+            if name.is_empty() {
+                Err("name must not be empty".to_string())
+            } else {
+                Ok(format!("Hello, {}!", name))
+            }
+        }
+
+        #[call_type(safe(exception_class = "com.robusta.tests.MissingException"))]
+        pub extern "jni" fn missingExceptionClass() -> Result<i32, String> {
+            Err("original error".to_string())
+        }
+
+        #[call_type(safe(exception_class = "java.lang.IllegalArgumentException"))]
+        pub extern "jni" fn checkPositive(v: i32) -> Result<(), NegativeValue> {
+            if v < 0 {
+                Err(NegativeValue(v))
+            } else {
+                Ok(())
+            }
+        }
+
+        pub extern "jni" fn requireName(name: String) -> Result<(), String> {
+            if name.is_empty() {
+                Err("name must not be empty".to_string())
+            } else {
+                Ok(())
+            }
+        }
+
+        #[call_type(safe(exception_class = "java.lang.IllegalStateException"))]
+        pub extern "jni" fn load(key: String) -> Result<String, RepoError> {
+            match key.as_str() {
+                "name" => Ok("robusta".to_string()),
+                "secret" => Err(RepoError::PermissionDenied),
+                "file" => Err(RepoError::Io(io::Error::new(io::ErrorKind::NotFound, "no such file"))),
+                "unsupported" => Err(RepoError::Unsupported),
+                "corrupted" => Err(RepoError::Corrupted),
+                _ => Err(RepoError::NotFound(key)),
+            }
+        }
+
+        #[call_type(safe(message = "can't load the value"))]
+        pub extern "jni" fn loadWithMessage(key: String) -> Result<String, RepoError> {
+            Err(RepoError::NotFound(key))
+        }
+
+        /// Throws its own exception through the environment, which stays pending as the returned error is discarded.
+        pub extern "jni" fn throwingManually(env: &JNIEnv, v: i32) -> ::robusta_jni::jni::errors::Result<i32> {
+            if v < 0 {
+                env.throw_new("java/lang/ArithmeticException", format!("{} has no square root", v))?;
+                return Err(Error::JavaException);
+            }
+
+            Ok(v)
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedThrowingManually(env: JNIEnv, v: i32) -> i32 {
+            if v < 0 {
+                env.throw_new("java/lang/ArithmeticException", format!("{} has no square root", v)).unwrap();
+            }
+
+            v
+        }
+
+        pub extern "jni" fn panicking() -> i32 {
+            panic!("boom")
+        }
+
+        pub extern "jni" fn panickingWithValue(v: i32) -> String {
+            panic!("boom {}", v)
+        }
+
+        pub extern "jni" fn panickingWithoutMessage() {
+            std::panic::panic_any(42)
+        }
+
+        #[call_type(unchecked(catch_panics))]
+        pub extern "jni" fn uncheckedPanicking() -> i32 {
+            panic!("unchecked boom")
+        }
+    }
+}
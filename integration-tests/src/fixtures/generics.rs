@@ -0,0 +1,40 @@
+//! Generic structs bridged to a Java class for each of their instantiations.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use std::fmt::Debug;
+
+    use robusta_jni::convert::Signature;
+
+    /// Bridged to `HolderOfString` and `HolderOfLong`.
+    #[derive(Signature)]
+    #[package(com.robusta.tests)]
+    #[instantiate(OfString = Holder<String>, OfLong = Holder<i64>)]
+    pub struct Holder<T> {
+        value: T,
+    }
+
+    impl<T: Debug> Holder<T> {
+        pub extern "jni" fn describe(value: T) -> String {
+            format!("{:?}", Holder { value }.value)
+        }
+
+        pub extern "jni" fn identity(value: T) -> T {
+            value
+        }
+    }
+
+    impl Holder<String> {
+        pub extern "jni" fn length(value: String) -> i32 {
+            value.chars().count() as i32
+        }
+    }
+
+    impl Holder<i64> {
+        pub extern "jni" fn twice(value: i64) -> i64 {
+            value * 2
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Exported methods keeping Java objects after they return, with global and weak references.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use std::sync::Mutex;
+    use std::thread;
+
+    use robusta_jni::convert::{Global, Weak};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    static STASHED: Mutex<Option<Global<String>>> = Mutex::new(None);
+    static WATCHED: Mutex<Option<Weak<String>>> = Mutex::new(None);
+
+    #[package(com.robusta.tests)]
+    pub struct Globals;
+
+    impl Globals {
+        /// Keeps `value` alive until `clearStash` is called.
+        pub extern "jni" fn stash(value: Global<String>) {
+            *STASHED.lock().unwrap() = Some(value);
+        }
+
+        pub extern "jni" fn stashed() -> Option<Global<String>> {
+            STASHED.lock().unwrap().clone()
+        }
+
+        /// The stashed string, read from a thread started by Rust.
+        pub extern "jni" fn stashedFromThread(env: &JNIEnv) -> JniResult<Option<String>> {
+            let vm = env.get_java_vm()?;
+            let stashed = STASHED.lock().unwrap().clone();
+
+            thread::spawn(move || match stashed {
+                Some(global) => global.materialize(*vm.attach_current_thread()?).map(Some),
+                None => Ok(None),
+            })
+            .join()
+            .unwrap()
+        }
+
+        pub extern "jni" fn clearStash() {
+            *STASHED.lock().unwrap() = None;
+        }
+
+        /// Watches `value` without keeping it alive.
+        pub extern "jni" fn watch(value: Weak<String>) {
+            *WATCHED.lock().unwrap() = Some(value);
+        }
+
+        /// The watched string, or `null` once it's collected.
+        pub extern "jni" fn watched(env: &JNIEnv) -> JniResult<Option<String>> {
+            match &*WATCHED.lock().unwrap() {
+                Some(weak) => weak.upgrade(*env),
+                None => Ok(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Exported methods keeping Rust state in Java `long`s, created, used and destroyed through handles.
+
+use robusta_jni::bridge;
+
+/// State of a counter owned by Java.
+pub struct Counter {
+    pub value: std::sync::atomic::AtomicI64,
+}
+
+#[bridge]
+pub mod jni {
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    use robusta_jni::convert::{Handle, HandleRef};
+
+    use super::Counter;
+
+    #[package(com.robusta.tests)]
+    pub struct Handles;
+
+    impl Handles {
+        pub extern "jni" fn createCounter(start: i64) -> Handle<Counter> {
+            Handle::new(Counter { value: AtomicI64::new(start) })
+        }
+
+        pub extern "jni" fn increment<'env>(counter: HandleRef<'env, Counter>) -> i64 {
+            counter.value.fetch_add(1, Ordering::SeqCst) + 1
+        }
+
+        /// Consumes the counter, returning its last value.
+        pub extern "jni" fn destroyCounter(counter: Handle<Counter>) -> i64 {
+            counter.into_inner().value.into_inner()
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn incrementUnchecked<'env>(counter: HandleRef<'env, Counter>) -> i64 {
+            counter.value.fetch_add(1, Ordering::SeqCst) + 1
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn destroyCounterUnchecked(counter: Handle<Counter>) -> i64 {
+            counter.into_inner().value.into_inner()
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Exported methods converting their strings through `#[intern_cache]`.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use std::sync::Arc;
+
+    #[package(com.robusta.tests)]
+    pub struct Interned;
+
+    impl Interned {
+        pub extern "jni" fn internedString(#[intern_cache(size = 4)] v: Arc<str>) -> String {
+            v.to_string()
+        }
+
+        /// Address of the cached string, the same for every equal string while it stays in the cache.
+        pub extern "jni" fn internedStringId(#[intern_cache(size = 4)] v: Arc<str>) -> i64 {
+            Arc::as_ptr(&v) as *const u8 as i64
+        }
+    }
+}
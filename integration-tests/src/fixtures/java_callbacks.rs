@@ -0,0 +1,38 @@
+//! Exported methods calling Java lambdas, on the calling thread and on threads of their own.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use std::thread;
+
+    use robusta_jni::convert::JavaCallback;
+    use robusta_jni::jni::errors::Result as JniResult;
+
+    #[package(com.robusta.tests)]
+    pub struct JavaCallbacks;
+
+    impl JavaCallbacks {
+        pub extern "jni" fn forEachLine<'env>(text: String, callback: JavaCallback<'env, (String,)>) -> JniResult<()> {
+            for line in text.lines() {
+                callback.call((line.to_string(),))?;
+            }
+            Ok(())
+        }
+
+        pub extern "jni" fn forEachLineInThread<'env>(text: String, callback: JavaCallback<'env, (String,)>) -> JniResult<()> {
+            let callback = callback.to_global()?;
+
+            thread::spawn(move || text.lines().try_for_each(|line| callback.call((line.to_string(),))))
+                .join()
+                .expect("callback thread panicked")
+        }
+
+        pub extern "jni" fn forEachLength<'env>(text: String, callback: JavaCallback<'env, (String, i32)>) -> JniResult<()> {
+            for line in text.lines() {
+                callback.call((line.to_string(), line.len() as i32))?;
+            }
+            Ok(())
+        }
+    }
+}
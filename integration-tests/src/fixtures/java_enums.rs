@@ -0,0 +1,90 @@
+//! Enums converted to Java enums, with exported methods of their own.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::convert::JavaEnum;
+
+    /// Converted by name to its generated Java enum, declaring its exported methods.
+    #[derive(Clone, Copy, Debug, PartialEq, JavaEnum)]
+    #[package(com.robusta.tests)]
+    pub enum Health {
+        Ok,
+        Degraded,
+        #[java_name = "OFFLINE"]
+        Down,
+    }
+
+    impl Health {
+        pub extern "jni" fn isHealthy(self) -> bool {
+            self == Health::Ok
+        }
+
+        pub extern "jni" fn worse(&self) -> Health {
+            match self {
+                Health::Ok => Health::Degraded,
+                Health::Degraded | Health::Down => Health::Down,
+            }
+        }
+
+        pub extern "jni" fn parse(name: String) -> Option<Health> {
+            [Health::Ok, Health::Degraded, Health::Down].iter().copied().find(|h| h.java_name() == name)
+        }
+    }
+
+    /// Converted by name to a Java enum with a `CRITICAL` constant, lacking the `Unreleased` variant.
+    #[derive(Clone, Copy, Debug, PartialEq, JavaEnum)]
+    #[package(com.robusta.tests)]
+    pub enum Level {
+        Low,
+        Medium,
+        High,
+        Unreleased,
+    }
+
+    /// Converted by ordinal to a Java enum with a fourth `BLOCKER` constant.
+    #[derive(Clone, Copy, Debug, PartialEq, JavaEnum)]
+    #[package(com.robusta.tests)]
+    #[by_ordinal]
+    pub enum Priority {
+        Lowest,
+        Normal,
+        Urgent,
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct JavaEnums;
+
+    impl JavaEnums {
+        pub extern "jni" fn healthIdentity(v: Health) -> Health {
+            v
+        }
+
+        pub extern "jni" fn levelIdentity(v: Level) -> Level {
+            v
+        }
+
+        pub extern "jni" fn priorityIdentity(v: Priority) -> Priority {
+            v
+        }
+
+        pub extern "jni" fn optionalLevelIdentity(v: Option<Level>) -> Option<Level> {
+            v
+        }
+
+        pub extern "jni" fn unreleasedLevel() -> Level {
+            Level::Unreleased
+        }
+
+        #[call_type(unchecked(catch_panics))]
+        pub extern "jni" fn uncheckedLevelIdentity(v: Level) -> Level {
+            v
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedPriorityIdentity(v: Priority) -> Priority {
+            v
+        }
+    }
+}
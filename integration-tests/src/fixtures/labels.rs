@@ -0,0 +1,79 @@
+//! Exported methods returning strings borrowed from their receiver, or from static data.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use std::borrow::Cow;
+
+    use robusta_jni::convert::{FromJavaValue, Signature, TryFromJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::{JObject, JString};
+    use robusta_jni::jni::JNIEnv;
+
+    /// A `com.robusta.tests.Label`, with a copy of its text.
+    #[package(com.robusta.tests)]
+    pub struct Label<'env> {
+        raw: JObject<'env>,
+        text: String,
+    }
+
+    impl<'env> Signature for Label<'env> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Label;";
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Label<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, env: JNIEnv<'env>) -> JniResult<Self> {
+            let text = env.get_field(s, "text", "Ljava/lang/String;")?.l()?;
+            Ok(Label {
+                raw: s,
+                text: TryFromJavaValue::try_from(<JString as From<JObject>>::from(text), env)?,
+            })
+        }
+    }
+
+    impl<'env> FromJavaValue<'env> for Label<'env> {
+        type Source = JObject<'env>;
+
+        fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+            TryFromJavaValue::try_from(s, env).unwrap()
+        }
+    }
+
+    impl<'env> Label<'env> {
+        pub extern "jni" fn text(&self) -> &str {
+            &self.text
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedText(&self) -> &str {
+            &self.text
+        }
+
+        /// The text, or a placeholder for empty labels, allocated only when the text is trimmed.
+        pub extern "jni" fn display(&self) -> Cow<'_, str> {
+            match self.text.trim() {
+                "" => Cow::Borrowed("(untitled)"),
+                trimmed if trimmed.len() == self.text.len() => Cow::Borrowed(&self.text),
+                trimmed => Cow::Owned(trimmed.to_string()),
+            }
+        }
+
+        pub extern "jni" fn isSame(&self, env: JNIEnv, other: Self) -> JniResult<bool> {
+            env.is_same_object(self.raw, other.raw)
+        }
+
+        /// Overloads telling apart a `Label` parameter, whose type in the native symbol is the one of its `Signature`.
+        #[java_name = "startsWith"]
+        pub extern "jni" fn starts_with_label(&self, prefix: Self) -> bool {
+            self.text.starts_with(&prefix.text)
+        }
+
+        #[java_name = "startsWith"]
+        pub extern "jni" fn starts_with_text(&self, prefix: String) -> bool {
+            self.text.starts_with(&prefix)
+        }
+    }
+}
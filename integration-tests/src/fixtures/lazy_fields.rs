@@ -0,0 +1,95 @@
+//! Structs converted from Java objects, with fields read only when they're used.
+
+use std::cell::Cell;
+use std::thread;
+
+use robusta_jni::bridge;
+use robusta_jni::convert::{JavaArray, Lazy, Signature, TryFromJavaValue};
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::{JObject, JString};
+use robusta_jni::jni::JNIEnv;
+
+thread_local! {
+    /// Number of `Payload`s converted on the current thread.
+    static PAYLOAD_LOADS: Cell<i64> = const { Cell::new(0) };
+}
+
+/// The contents of a `byte[]` field, counting its conversions.
+pub struct Payload(pub Vec<i8>);
+
+impl Signature for Payload {
+    const SIG_TYPE: &'static str = "[B";
+}
+
+impl<'env> TryFromJavaValue<'env> for Payload {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> JniResult<Self> {
+        PAYLOAD_LOADS.with(|loads| loads.set(loads.get() + 1));
+        let bytes: JavaArray<i8> = TryFromJavaValue::try_from(s, env)?;
+        Ok(Payload(bytes.0))
+    }
+}
+
+/// A `com.robusta.tests.Document`, whose payload is converted like a `#[lazy]` field of a struct deriving `TryFromJavaValue`.
+pub struct Document<'env> {
+    title: String,
+    payload: Lazy<'env, Payload>,
+}
+
+impl<'env> Signature for Document<'env> {
+    const SIG_TYPE: &'static str = "Lcom/robusta/tests/Document;";
+}
+
+impl<'env> TryFromJavaValue<'env> for Document<'env> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> JniResult<Self> {
+        let title = env.get_field(s, "title", "Ljava/lang/String;")?.l()?;
+        Ok(Document {
+            title: TryFromJavaValue::try_from(JString::from(title), env)?,
+            payload: Lazy::new(env, s, "com/robusta/tests/Document", "payload")?,
+        })
+    }
+}
+
+#[bridge]
+pub mod jni {
+    use super::*;
+
+    #[package(com.robusta.tests)]
+    pub struct LazyFields;
+
+    impl<'env> LazyFields {
+        pub extern "jni" fn title(document: Document<'env>) -> String {
+            assert!(!document.payload.is_loaded());
+            document.title
+        }
+
+        /// Reads the payload twice, returning its size.
+        pub extern "jni" fn payloadSize(env: JNIEnv, document: Document<'env>) -> JniResult<i32> {
+            let first = document.payload.get(env)?;
+            let second = document.payload.get(env)?;
+            assert!(std::ptr::eq(first, second));
+            Ok(second.0.len() as i32)
+        }
+
+        /// Reads the payload from another thread, which isn't attached to the JVM beforehand.
+        pub extern "jni" fn ownedPayloadSize(env: JNIEnv, document: Document<'env>) -> JniResult<i32> {
+            let payload = document.payload.into_owned(env)?;
+            thread::spawn(move || {
+                assert!(!payload.is_loaded());
+                let size = payload.get()?.0.len();
+                assert!(payload.is_loaded());
+                Ok(size as i32)
+            })
+            .join()
+            .unwrap()
+        }
+
+        /// Number of payloads converted on the calling thread.
+        pub extern "jni" fn loads() -> i64 {
+            PAYLOAD_LOADS.with(Cell::get)
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Exported methods run between the calls of a middleware, recording each call.
+
+use std::cell::Cell;
+use std::sync::Mutex;
+
+use robusta_jni::bridge;
+use robusta_jni::middleware::{CallCtx, CallOutcome, Middleware};
+
+/// Calls seen by [`Recorder`], in the library loaded by the JVM.
+static LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+thread_local! {
+    /// Identifier of the current call, set by [`Recorder::before`].
+    static CALL_ID: Cell<i64> = const { Cell::new(0) };
+}
+
+#[derive(Default)]
+pub struct Recorder;
+
+impl Middleware for Recorder {
+    fn before(&self, ctx: &CallCtx) {
+        CALL_ID.with(|id| id.set(id.get() + 1));
+        LOG.lock().unwrap().push(format!("before {}.{}", ctx.class(), ctx.method()));
+    }
+
+    fn after(&self, ctx: &CallCtx, outcome: &CallOutcome) {
+        LOG.lock().unwrap().push(format!("after {}: {:?}", ctx.method(), outcome));
+    }
+}
+
+#[bridge(middleware = "crate::fixtures::middleware::Recorder")]
+pub mod jni {
+    use super::*;
+
+    #[package(com.robusta.tests)]
+    pub struct Middlewares;
+
+    impl Middlewares {
+        pub extern "jni" fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        pub extern "jni" fn fail(message: String) -> Result<i32, String> {
+            Err(message)
+        }
+
+        pub extern "jni" fn explode() -> i32 {
+            panic!("exploded")
+        }
+
+        #[call_type(unchecked(catch_panics))]
+        pub extern "jni" fn uncheckedExplode() -> i32 {
+            panic!("exploded unchecked")
+        }
+
+        pub extern "jni" fn callId() -> i64 {
+            CALL_ID.with(Cell::get)
+        }
+
+        /// The calls recorded so far, ending with this call's `before`, which is taken off the log.
+        pub extern "jni" fn takeLog() -> Vec<String> {
+            LOG.lock().unwrap().drain(..).collect()
+        }
+    }
+}
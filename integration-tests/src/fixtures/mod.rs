@@ -0,0 +1,37 @@
+//! Exported methods called by the integration tests.
+
+pub mod arithmetic;
+pub mod async_methods;
+pub mod atomics;
+pub mod audio;
+pub mod beans;
+pub mod buffers;
+pub mod call_types;
+pub mod classes;
+pub mod conversions;
+pub mod converters;
+pub mod counted;
+pub mod critical;
+pub mod defaults;
+pub mod dispatch;
+pub mod equality;
+pub mod error_codes;
+pub mod errors;
+pub mod generics;
+pub mod globals;
+pub mod handles;
+pub mod interned;
+pub mod java_callbacks;
+pub mod java_enums;
+pub mod labels;
+pub mod lazy_fields;
+pub mod middleware;
+pub mod names;
+pub mod nulls;
+pub mod plain_receivers;
+pub mod preload;
+pub mod raw_objects;
+pub mod receivers;
+pub mod references;
+pub mod split;
+pub mod synchronized;
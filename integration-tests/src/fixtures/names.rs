@@ -0,0 +1,54 @@
+//! Exported methods with Java names different from the Rust ones, overloaded, or that need escaping in native symbols.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    #[package(com.robusta.tests)]
+    pub struct Names;
+
+    impl Names {
+        #[java_name = "getUserName"]
+        pub extern "jni" fn get_user_name(id: i32) -> String {
+            format!("user{}", id)
+        }
+
+        pub extern "jni" fn greet(name: String) -> String {
+            format!("Hello, {}!", name)
+        }
+
+        #[java_name = "greet"]
+        pub extern "jni" fn greet_times(name: String, times: i32) -> String {
+            vec![format!("Hello, {}!", name); times.max(0) as usize].join(" ")
+        }
+    }
+}
+
+#[bridge]
+pub mod underscored {
+    #[package(com.robusta.tests.snake_case)]
+    pub struct Underscored;
+
+    impl Underscored {
+        pub extern "jni" fn user_name(id: i32) -> String {
+            format!("user_{}", id)
+        }
+    }
+}
+
+#[bridge(rename_all = "camelCase")]
+pub mod renamed {
+    #[package(com.robusta.tests)]
+    pub struct Renamed;
+
+    impl Renamed {
+        pub extern "jni" fn parse_json_url(url: String) -> String {
+            format!("json+{}", url)
+        }
+
+        #[java_name = "legacy_count"]
+        pub extern "jni" fn legacy_count() -> i32 {
+            3
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Exported methods receiving and returning `null`, called by `NullCalls` on the Java side.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::convert::JOption;
+
+    #[package(com.robusta.tests)]
+    pub struct Nulls;
+
+    impl Nulls {
+        pub extern "jni" fn describeString(v: Option<String>) -> String {
+            format!("{:?}", v)
+        }
+
+        pub extern "jni" fn describeInteger(v: Option<i32>) -> String {
+            format!("{:?}", v)
+        }
+
+        pub extern "jni" fn describeJOptionString(v: JOption<String>) -> String {
+            format!("{:?}", <JOption<String> as Into<Option<String>>>::into(v))
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedDescribeString(v: Option<String>) -> String {
+            format!("{:?}", v)
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedDescribeJOptionString(v: JOption<String>) -> String {
+            format!("{:?}", <JOption<String> as Into<Option<String>>>::into(v))
+        }
+
+        pub extern "jni" fn noneString() -> JOption<String> {
+            JOption::None
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedNoneString() -> JOption<String> {
+            JOption::None
+        }
+
+        pub extern "jni" fn someString(v: String) -> JOption<String> {
+            JOption::Some(v)
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Instance methods exported on a struct without lifetime parameters.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::convert::{FromJavaValue, Signature, TryFromJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    /// A copy of the fields of a `Temperature`, keeping no reference to the Java object.
+    #[package(com.robusta.tests)]
+    pub struct Temperature {
+        celsius: f64,
+    }
+
+    impl Signature for Temperature {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Temperature;";
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Temperature {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Temperature { celsius: env.get_field(s, "celsius", "D")?.d()? })
+        }
+    }
+
+    impl<'env> FromJavaValue<'env> for Temperature {
+        type Source = JObject<'env>;
+
+        fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+            TryFromJavaValue::try_from(s, env).unwrap()
+        }
+    }
+
+    impl Temperature {
+        pub extern "jni" fn fahrenheit(&self) -> f64 {
+            self.celsius * 9.0 / 5.0 + 32.0
+        }
+
+        pub extern "jni" fn kelvin(self) -> f64 {
+            self.celsius + 273.15
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Work done when the library is loaded: classes preloaded before any exported method is called, and the exported methods of
+//! this module registered with their classes, without exporting their symbols (except for an explicit `#[export_name]`).
+//!
+//! A library has a single `JNI_OnLoad`, which is generated for this module.
+
+use robusta_jni::bridge;
+
+#[bridge(preload_classes("com.robusta.tests.Preloaded"), register_natives)]
+pub mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Preloading;
+
+    impl Preloading {
+        pub extern "jni" fn initializedClasses<'env>(env: JNIEnv<'env>) -> JniResult<Vec<String>> {
+            InitializationLog::initialized(env)
+        }
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct InitializationLog;
+
+    impl InitializationLog {
+        pub extern "java" fn initialized<'env>(env: JNIEnv<'env>) -> JniResult<Vec<String>> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct Registered;
+
+    #[export_visibility(hidden)]
+    impl Registered {
+        pub extern "jni" fn greet(name: String) -> String {
+            format!("Hello, {}!", name)
+        }
+
+        #[java_name = "sum"]
+        pub extern "jni" fn sum_ints(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        #[java_name = "sum"]
+        pub extern "jni" fn sum_strings(a: String, b: String) -> String {
+            a + &b
+        }
+
+        pub extern "jni" fn length(text: &str, #[default = 0] extra: i32) -> i32 {
+            text.len() as i32 + extra
+        }
+
+        /// Exported under a symbol of its own, besides being registered.
+        #[export_name = "robusta_tests_registered_shout"]
+        pub extern "jni" fn shout(text: String) -> String {
+            text.to_uppercase()
+        }
+    }
+}
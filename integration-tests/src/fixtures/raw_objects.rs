@@ -0,0 +1,26 @@
+//! Exported methods taking and returning raw objects, declared with the types of their Java counterparts.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct RawObjects;
+
+    impl RawObjects {
+        pub extern "jni" fn notify<'env>(env: &JNIEnv<'env>, #[sig("Lcom/robusta/tests/Listener;")] listener: JObject<'env>, event: String) -> JniResult<String> {
+            let event = env.new_string(event)?;
+            let reply = env.call_method(listener, "onEvent", "(Ljava/lang/String;)Ljava/lang/String;", &[JObject::from(event).into()])?.l()?;
+            Ok(env.get_string(reply.into())?.into())
+        }
+
+        #[sig("Lcom/robusta/tests/Listener;")]
+        pub extern "jni" fn firstListener<'env>(env: &JNIEnv<'env>, #[sig("[Lcom/robusta/tests/Listener;")] listeners: JObject<'env>) -> JniResult<JObject<'env>> {
+            env.get_object_array_element(listeners.into_inner(), 0)
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Static and instance methods exported on the same class.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::convert::{Signature, TryFromJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Handle<'env> {
+        raw: JObject<'env>,
+    }
+
+    impl<'env> Signature for Handle<'env> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Handle;";
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Handle<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Handle { raw: s })
+        }
+    }
+
+    impl<'env> Handle<'env> {
+        pub extern "jni" fn twice(v: i32) -> i32 {
+            v * 2
+        }
+
+        pub extern "jni" fn same(env: JNIEnv, a: Handle<'env>, b: Self) -> ::robusta_jni::jni::errors::Result<bool> {
+            env.is_same_object(a.raw, b.raw)
+        }
+
+        pub extern "jni" fn sameAs(self, env: JNIEnv, other: Self) -> ::robusta_jni::jni::errors::Result<bool> {
+            env.is_same_object(self.raw, other.raw)
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Exported methods creating references, counted by `robusta_jni::debug` in the library loaded by the JVM.
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use robusta_jni::convert::PassthroughOwned;
+    use robusta_jni::debug;
+
+    #[package(com.robusta.tests)]
+    pub struct References;
+
+    impl References {
+        /// Forgets the owned wrapper instead of dropping it, so its global reference is never deleted.
+        pub extern "jni" fn leakOwned(v: PassthroughOwned) {
+            std::mem::forget(v)
+        }
+
+        pub extern "jni" fn dropOwned(v: PassthroughOwned) {
+            drop(v)
+        }
+
+        /// Returns a local reference to `v`, which the JVM deletes when the call returns.
+        pub extern "jni" fn downgradeOwned(v: PassthroughOwned) -> PassthroughOwned {
+            v
+        }
+
+        /// Outstanding references of each site, as `site kind count`.
+        pub extern "jni" fn refReport() -> Vec<String> {
+            debug::ref_report()
+                .iter()
+                .map(|c| format!("{} {} {}", c.site.unwrap_or("-"), c.kind, c.outstanding()))
+                .collect()
+        }
+
+        pub extern "jni" fn setPanicThreshold(threshold: i32) {
+            debug::set_panic_threshold(if threshold < 0 { None } else { Some(threshold as usize) })
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! A bridge module whose structs are declared in files of their own, in `split/`.
+
+use robusta_jni::bridge;
+
+#[bridge(path = "src/fixtures")]
+pub mod jni {
+    pub mod canvas {
+        include!("split/canvas.rs");
+
+        pub mod layers {
+            include!("split/canvas/layers.rs");
+        }
+    }
+
+    pub mod shape {
+        include!("split/shape.rs");
+    }
+}
@@ -0,0 +1,15 @@
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::JNIEnv;
+
+use super::shape::Shape;
+
+/// Draws `Shape`s, declared in another file of the module.
+#[package(com.robusta.tests.split)]
+pub struct Canvas;
+
+impl Canvas {
+    pub extern "jni" fn totalSides<'env>(env: JNIEnv<'env>, shape: Shape<'env>, copies: i32) -> JniResult<i32> {
+        let sides = env.call_method(shape.raw, "sides", "()I", &[])?.i()?;
+        Ok(sides * copies)
+    }
+}
@@ -0,0 +1,14 @@
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::JNIEnv;
+
+use super::super::shape::Shape;
+
+/// Layers of a `Canvas`, declared in the directory of its file.
+#[package(com.robusta.tests.split)]
+pub struct Layers;
+
+impl Layers {
+    pub extern "jni" fn top<'env>(env: JNIEnv<'env>) -> JniResult<Shape<'env>> {
+        Ok(Shape { raw: env.new_object("com/robusta/tests/split/Shape", "()V", &[])? })
+    }
+}
@@ -0,0 +1,36 @@
+use robusta_jni::convert::{Signature, TryFromJavaValue, TryIntoJavaValue};
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::JObject;
+use robusta_jni::jni::JNIEnv;
+
+/// A shape with a number of sides, drawn on a `Canvas`.
+#[package(com.robusta.tests.split)]
+pub struct Shape<'env> {
+    pub raw: JObject<'env>,
+}
+
+impl Signature for Shape<'_> {
+    const SIG_TYPE: &'static str = "Lcom/robusta/tests/split/Shape;";
+}
+
+impl<'env> TryIntoJavaValue<'env> for Shape<'env> {
+    type Target = JObject<'env>;
+
+    fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+        Ok(self.raw)
+    }
+}
+
+impl<'env> TryFromJavaValue<'env> for Shape<'env> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+        Ok(Shape { raw: s })
+    }
+}
+
+impl<'env> Shape<'env> {
+    pub extern "jni" fn sides(self) -> i32 {
+        4
+    }
+}
@@ -0,0 +1,58 @@
+//! Instance methods mutating the Java object they're called on, called from many Java threads at once.
+
+use std::thread;
+use std::time::Duration;
+
+use robusta_jni::bridge;
+
+#[bridge]
+pub mod jni {
+    use super::*;
+    use robusta_jni::convert::{Field, Signature, TryFromJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::{JObject, JValue};
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Tally<'env> {
+        count: Field<'env, i32>,
+    }
+
+    impl<'env> Signature for Tally<'env> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Tally;";
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Tally<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Tally {
+                count: Field::field_try_from(s, "com/robusta/tests/Tally", "count", env)?,
+            })
+        }
+    }
+
+    impl<'env> Tally<'env> {
+        /// Reads the count, and writes it back incremented a while later: increments made in between are lost.
+        pub extern "jni" fn increment(&mut self) -> JniResult<()> {
+            let count = self.count.get()?;
+            thread::sleep(Duration::from_millis(1));
+            self.count.set(count + 1)
+        }
+
+        #[synchronized]
+        pub extern "jni" fn incrementSynchronized(mut self) -> JniResult<()> {
+            self.increment()
+        }
+
+        /// Counts `latch` down and waits for it to reach zero, which it only does if the other calls run meanwhile.
+        pub extern "jni" fn meet(&self, env: &JNIEnv<'env>, #[sig("Ljava/util/concurrent/CountDownLatch;")] latch: JObject<'env>) -> JniResult<bool> {
+            env.call_method(latch, "countDown", "()V", &[])?;
+            let seconds = env
+                .get_static_field("java/util/concurrent/TimeUnit", "SECONDS", "Ljava/util/concurrent/TimeUnit;")?
+                .l()?;
+            env.call_method(latch, "await", "(JLjava/util/concurrent/TimeUnit;)Z", &[JValue::Long(10), seconds.into()])?
+                .z()
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! JVM bootstrap shared by all integration tests.
+
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use robusta_jni::convert::FromJavaValue;
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::JString;
+use robusta_jni::jni::{InitArgsBuilder, JNIEnv, JavaVM};
+
+const JAVA_SOURCES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/java");
+const STUBS_DIR: &str = env!("ROBUSTA_JAVA_OUTPUT_DIR");
+const CLASSES_DIR: &str = concat!(env!("OUT_DIR"), "/classes");
+const KOTLIN_SOURCES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/kotlin");
+const KOTLIN_STUBS_DIR: &str = env!("ROBUSTA_KOTLIN_OUTPUT_DIR");
+
+/// Class path of the Kotlin standard library and `kotlinx-coroutines-core`, needed by the fixtures in `kotlin/`.
+///
+/// These fixtures are only compiled (with `kotlinc`) when it's set, and the tests using them are ignored by default.
+const KOTLIN_CLASSPATH_VAR: &str = "ROBUSTA_KOTLIN_CLASSPATH";
+
+static JVM: OnceLock<JavaVM> = OnceLock::new();
+
+#[cfg(windows)]
+const PATH_SEPARATOR: char = ';';
+#[cfg(not(windows))]
+const PATH_SEPARATOR: char = ':';
+
+fn collect_sources(dir: &Path, extension: &str, sources: &mut Vec<PathBuf>) {
+    let entries = std::fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("can't read Java sources in `{}` ({})", dir.display(), e));
+
+    for path in entries.map(|e| e.unwrap().path()) {
+        if path.is_dir() {
+            collect_sources(&path, extension, sources);
+        } else if path.extension().is_some_and(|e| e == extension) {
+            sources.push(path);
+        }
+    }
+}
+
+fn javac() -> PathBuf {
+    match std::env::var_os("JAVA_HOME") {
+        Some(java_home) => Path::new(&java_home).join("bin").join("javac"),
+        None => PathBuf::from("javac"),
+    }
+}
+
+/// Compiles the fixture sources in `java/` together with the stubs generated for the exported fixtures.
+///
+/// Sources in `java/` replace the stubs of the same classes, for fixtures whose class needs more than native methods.
+fn compile_fixtures() {
+    let mut sources = Vec::new();
+    collect_sources(Path::new(JAVA_SOURCES_DIR), "java", &mut sources);
+
+    let mut stubs = Vec::new();
+    collect_sources(Path::new(STUBS_DIR), "java", &mut stubs);
+    sources.extend(stubs.into_iter().filter(|stub| {
+        let relative_path = stub.strip_prefix(STUBS_DIR).expect("stub outside the stubs directory");
+        !Path::new(JAVA_SOURCES_DIR).join(relative_path).exists()
+    }));
+
+    let status = Command::new(javac())
+        .arg("-d")
+        .arg(CLASSES_DIR)
+        .args(&sources)
+        .status()
+        .expect("can't run javac, is a JDK installed?");
+
+    assert!(status.success(), "can't compile Java fixtures");
+}
+
+fn kotlinc() -> PathBuf {
+    match std::env::var_os("KOTLIN_HOME") {
+        Some(kotlin_home) => Path::new(&kotlin_home).join("bin").join("kotlinc"),
+        None => PathBuf::from("kotlinc"),
+    }
+}
+
+/// Compiles the Kotlin sources in `sources_dir` against `classpath` into `output_dir`, returning whether they compiled.
+fn compile_kotlin(sources_dir: &Path, classpath: &str, output_dir: &Path) -> bool {
+    let mut sources = Vec::new();
+    collect_sources(sources_dir, "kt", &mut sources);
+
+    Command::new(kotlinc())
+        .arg("-classpath")
+        .arg(classpath)
+        .arg("-d")
+        .arg(output_dir)
+        .args(&sources)
+        .status()
+        .expect("can't run kotlinc, is the Kotlin compiler installed?")
+        .success()
+}
+
+/// Compiles the fixture sources in `kotlin/` against `classpath`.
+fn compile_kotlin_fixtures(classpath: &str) {
+    assert!(
+        compile_kotlin(Path::new(KOTLIN_SOURCES_DIR), classpath, Path::new(CLASSES_DIR)),
+        "can't compile Kotlin fixtures"
+    );
+}
+
+/// Compiles the Kotlin declarations generated for the exported fixtures into `output_dir`, against the compiled Java fixtures and
+/// support classes, returning whether they compiled.
+///
+/// Like the fixtures in `kotlin/`, this needs `kotlinc` and `ROBUSTA_KOTLIN_CLASSPATH`.
+pub fn compile_kotlin_stubs(output_dir: &Path) -> bool {
+    let kotlin_classpath = std::env::var(KOTLIN_CLASSPATH_VAR).expect("ROBUSTA_KOTLIN_CLASSPATH isn't set");
+    jvm();
+
+    let classpath = format!("{}{}{}", CLASSES_DIR, PATH_SEPARATOR, kotlin_classpath);
+    compile_kotlin(Path::new(KOTLIN_STUBS_DIR), &classpath, output_dir)
+}
+
+/// Directory of the Kotlin declarations generated for the exported fixtures.
+pub fn kotlin_stubs_dir() -> &'static Path {
+    Path::new(KOTLIN_STUBS_DIR)
+}
+
+/// Path of this crate's cdylib, which Cargo places next to the test executables.
+pub fn native_library() -> PathBuf {
+    let exe = std::env::current_exe().expect("can't get test executable path");
+    exe.with_file_name(format!("{}integration_tests{}", DLL_PREFIX, DLL_SUFFIX))
+}
+
+/// Returns the JVM shared by all tests, creating it on first use.
+pub fn jvm() -> &'static JavaVM {
+    JVM.get_or_init(|| {
+        compile_fixtures();
+
+        let mut classpath = CLASSES_DIR.to_string();
+        if let Ok(kotlin_classpath) = std::env::var(KOTLIN_CLASSPATH_VAR) {
+            compile_kotlin_fixtures(&kotlin_classpath);
+            classpath = format!("{}{}{}", classpath, PATH_SEPARATOR, kotlin_classpath);
+        }
+
+        let mut vm_args = InitArgsBuilder::new()
+            .option(&format!("-Djava.class.path={}", classpath))
+            // Swing calls (see `tests/dispatch.rs`) don't need a display
+            .option("-Djava.awt.headless=true");
+        // Turns misuse of the JNI into fatal errors instead of undefined behavior. Left out of benchmarks (built in release mode),
+        // as the checks copy arrays in critical regions, and add a cost to every call.
+        if cfg!(debug_assertions) {
+            vm_args = vm_args.option("-Xcheck:jni");
+        }
+        let vm_args = vm_args.build().expect("can't create JVM arguments");
+        let vm = JavaVM::new(vm_args).expect("can't create JVM");
+
+        {
+            let env = vm.attach_current_thread().expect("can't attach thread to JVM");
+            let library_path = env
+                .new_string(native_library().to_string_lossy())
+                .expect("can't create library path string");
+
+            env.call_static_method(
+                "com/robusta/tests/Fixtures",
+                "load",
+                "(Ljava/lang/String;)V",
+                &[library_path.into()],
+            )
+            .unwrap_or_else(|e| {
+                let _ = env.exception_describe();
+                panic!("can't load native library `{}` ({})", native_library().display(), e)
+            });
+        }
+
+        vm
+    })
+}
+
+/// Runs `f` with the current thread attached to the JVM.
+///
+/// If `f` returns an error, the pending Java exception (if any) is printed and the test fails.
+pub fn with_env<F, T>(f: F) -> T
+where
+    F: for<'env> FnOnce(JNIEnv<'env>) -> JniResult<T>,
+{
+    let guard = jvm()
+        .attach_current_thread()
+        .expect("can't attach thread to JVM");
+    let env = *guard;
+
+    f(env).unwrap_or_else(|e| {
+        if env.exception_check().unwrap_or(false) {
+            let _ = env.exception_describe();
+            let _ = env.exception_clear();
+        }
+
+        panic!("JNI call failed ({})", e)
+    })
+}
+
+/// Clears the pending Java exception, returning its class name and message.
+pub fn take_exception(env: JNIEnv) -> JniResult<Option<(String, Option<String>)>> {
+    if !env.exception_check()? {
+        return Ok(None);
+    }
+
+    let exception = env.exception_occurred()?;
+    env.exception_clear()?;
+
+    let class = env
+        .call_method(exception, "getClass", "()Ljava/lang/Class;", &[])?
+        .l()?;
+    let class_name = env
+        .call_method(class, "getName", "()Ljava/lang/String;", &[])?
+        .l()?;
+    let message = env
+        .call_method(exception, "getMessage", "()Ljava/lang/String;", &[])?
+        .l()?;
+
+    let class_name: JString = class_name.into();
+    let message: JString = message.into();
+
+    let class_name = <String as FromJavaValue>::from(class_name, env);
+    let message = if message.is_null() {
+        None
+    } else {
+        Some(<String as FromJavaValue>::from(message, env))
+    };
+
+    Ok(Some((class_name, message)))
+}
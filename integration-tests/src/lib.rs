@@ -0,0 +1,36 @@
+//! Integration tests of `robusta_jni` against a real JVM.
+//!
+//! This crate is built as a native library exporting the methods declared in [`fixtures`]. The JVM shared by all tests
+//! (see [`jvm`]) compiles the Java sources in `java/` together with the stubs generated for the fixtures, then loads the library.
+//!
+//! Tests usually call exported fixtures back through `extern "java"` declarations of the same Java class, so that each round trip
+//! exercises conversions in both directions:
+//!
+//! * to test a new conversion, add an exported method to [`fixtures::conversions`] and the matching imported declaration
+//!   and test case to `tests/conversions.rs`;
+//! * Java-side fixtures (e.g. for imported methods) go in the `java/` directory, in the `com.robusta.tests` package.
+//!
+//! Running the tests only requires a JDK, found through `JAVA_HOME` or in `PATH`.
+
+pub mod fixtures;
+mod jvm;
+
+pub use jvm::{compile_kotlin_stubs, jvm, kotlin_stubs_dir, native_library, take_exception, with_env};
+
+/// Asserts that each value is returned unchanged by calling `method` (with the environment as first argument).
+///
+/// ```ignore
+/// assert_round_trip!(Conversions::intIdentity, 0, i32::MIN, i32::MAX);
+/// ```
+#[macro_export]
+macro_rules! assert_round_trip {
+    ($method:path, $($value:expr),+ $(,)?) => {
+        $crate::with_env(|env| {
+            $(
+                let value = $value;
+                assert_eq!($method(env, ::std::clone::Clone::clone(&value))?, value);
+            )+
+            Ok(())
+        })
+    };
+}
@@ -0,0 +1,46 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+
+use crate::jni::Arithmetic;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Arithmetic;
+
+    impl Arithmetic {
+        pub extern "java" fn add(env: JNIEnv, a: i64, b: i64) -> JniResult<i64> {}
+        pub extern "java" fn uncheckedAdd(env: JNIEnv, a: i64, b: i64) -> JniResult<i64> {}
+        pub extern "java" fn checkedAdd(env: JNIEnv, a: i64, b: i64) -> JniResult<i64> {}
+        pub extern "java" fn divide(env: JNIEnv, a: i32, b: i32) -> JniResult<i32> {}
+        pub extern "java" fn mean(env: JNIEnv, a: f64, b: f32) -> JniResult<f64> {}
+    }
+}
+
+#[test]
+fn primitive_methods_pass_values_through() {
+    with_env(|env| {
+        assert_eq!(Arithmetic::add(env, 40, 2)?, 42);
+        assert_eq!(Arithmetic::add(env, i64::MAX, 1)?, i64::MIN);
+        assert_eq!(Arithmetic::uncheckedAdd(env, -40, -2)?, -42);
+        assert_eq!(Arithmetic::checkedAdd(env, 40, 2)?, 42);
+        assert_eq!(Arithmetic::mean(env, 1.5, 2.5)?, 2.0);
+        Ok(())
+    })
+}
+
+#[test]
+fn panics_in_primitive_methods_throw() {
+    with_env(|env| {
+        assert!(Arithmetic::divide(env, 1, 0).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert_eq!(message.as_deref(), Some("attempt to divide by zero"));
+
+        assert_eq!(Arithmetic::divide(env, 7, 2)?, 3);
+        Ok(())
+    })
+}
@@ -0,0 +1,86 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::JNIEnv;
+
+use crate::jni::{AsyncCalls, Awaits};
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct AsyncCalls;
+
+    impl AsyncCalls {
+        pub extern "java" fn installSpawner(env: JNIEnv) -> JniResult<()> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct Awaits;
+
+    impl Awaits {
+        pub extern "java" fn add(env: JNIEnv, a: i32, b: i32) -> JniResult<i32> {}
+        pub extern "java" fn greet(env: JNIEnv, name: String) -> JniResult<String> {}
+        pub extern "java" fn parse(env: JNIEnv, value: String) -> JniResult<i64> {}
+        pub extern "java" fn parseStrictly(env: JNIEnv, value: String) -> JniResult<i64> {}
+        pub extern "java" fn explode(env: JNIEnv) -> JniResult<i32> {}
+        pub extern "java" fn flushCompletesWithNull(env: JNIEnv) -> JniResult<bool> {}
+        pub extern "java" fn returnsBeforeCompletion(env: JNIEnv, key: String) -> JniResult<bool> {}
+    }
+}
+
+/// Runs `f` once the fixtures have a spawner, running each future on a thread of its own.
+fn with_spawner(f: impl for<'env> FnOnce(JNIEnv<'env>) -> JniResult<()>) {
+    with_env(|env| {
+        AsyncCalls::installSpawner(env)?;
+        f(env)
+    })
+}
+
+#[test]
+fn futures_complete_with_converted_values() {
+    with_spawner(|env| {
+        assert_eq!(Awaits::add(env, 2, 3)?, 5);
+        assert_eq!(Awaits::greet(env, "async".into())?, "Hello, async!");
+        assert_eq!(Awaits::parse(env, "42".into())?, 42);
+        assert!(Awaits::flushCompletesWithNull(env)?);
+        Ok(())
+    })
+}
+
+#[test]
+fn futures_are_returned_before_completing() {
+    with_spawner(|env| {
+        assert!(Awaits::returnsBeforeCompletion(env, "returned-early".into())?);
+        Ok(())
+    })
+}
+
+#[test]
+fn errors_complete_futures_exceptionally() {
+    with_spawner(|env| {
+        assert!(Awaits::parse(env, "forty-two".into()).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert_eq!(message.as_deref(), Some("not a number: forty-two"));
+
+        assert!(Awaits::parseStrictly(env, "forty-two".into()).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.IllegalArgumentException");
+        assert_eq!(message.as_deref(), Some("not a number: forty-two"));
+        Ok(())
+    })
+}
+
+#[test]
+fn panics_complete_futures_exceptionally() {
+    with_spawner(|env| {
+        assert!(Awaits::explode(env).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert_eq!(message.as_deref(), Some("exploded asynchronously"));
+        Ok(())
+    })
+}
@@ -0,0 +1,91 @@
+use integration_tests::{jvm, with_env};
+use robusta_jni::bridge;
+use robusta_jni::convert::{JAtomicLong, TryFromJavaValue};
+use robusta_jni::jni::errors::Error;
+
+use crate::jni::{Atomics, Incrementer};
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::JAtomicLong;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Atomics;
+
+    impl Atomics {
+        pub extern "java" fn incrementTimes<'env>(env: JNIEnv<'env>, counter: JAtomicLong<'env>, times: i32) -> JniResult<i64> {}
+        pub extern "java" fn newCounter<'env>(env: JNIEnv<'env>, value: i64) -> JniResult<JAtomicLong<'env>> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct Incrementer;
+
+    impl Incrementer {
+        pub extern "java" fn start<'env>(env: JNIEnv<'env>, counter: JAtomicLong<'env>, times: i32) -> JniResult<JObject<'env>> {}
+    }
+}
+
+#[test]
+fn counters_are_passed_both_ways() {
+    with_env(|env| {
+        let counter = Atomics::newCounter(env, 40)?;
+        assert_eq!(Atomics::incrementTimes(env, counter, 2)?, 42);
+        assert_eq!(counter.get()?, 42);
+        Ok(())
+    })
+}
+
+#[test]
+fn operations_follow_rust_atomics() {
+    with_env(|env| {
+        let counter = JAtomicLong::new(env, 1)?;
+
+        assert_eq!(counter.fetch_add(2)?, 1);
+        assert_eq!(counter.compare_exchange(3, 10)?, Ok(3));
+        assert_eq!(counter.compare_exchange(3, 20)?, Err(10));
+        counter.set(-5)?;
+        assert_eq!(counter.get()?, -5);
+        Ok(())
+    })
+}
+
+#[test]
+fn concurrent_increments_from_both_sides() {
+    const TIMES: i32 = 10_000;
+
+    with_env(|env| {
+        let counter = JAtomicLong::new(env, 0)?;
+        let java_thread = Incrementer::start(env, counter, TIMES)?;
+
+        let global = counter.to_global()?;
+        let rust_thread = std::thread::spawn(move || {
+            let guard = jvm().attach_current_thread().unwrap();
+            let counter = global.bind(*guard).unwrap();
+            for _ in 0..TIMES {
+                counter.fetch_add(1).unwrap();
+            }
+        });
+
+        rust_thread.join().unwrap();
+        env.call_method(java_thread, "join", "()V", &[])?;
+
+        assert_eq!(counter.get()?, 2 * TIMES as i64);
+        Ok(())
+    })
+}
+
+#[test]
+fn other_objects_are_rejected() {
+    with_env(|env| {
+        let string = env.new_string("0")?;
+
+        assert!(matches!(
+            <JAtomicLong as TryFromJavaValue>::try_from(string.into(), env),
+            Err(Error::WrongJValueType(..))
+        ));
+        Ok(())
+    })
+}
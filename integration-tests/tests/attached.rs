@@ -0,0 +1,63 @@
+use std::thread;
+
+use integration_tests::jvm;
+use robusta_jni::bridge;
+use robusta_jni::jni::JavaVM;
+use robusta_jni::vm::{set_java_vm, with_attached_env};
+
+use crate::jni::Integer;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+
+    #[package(java.lang)]
+    pub struct Integer;
+
+    impl Integer {
+        #[attach]
+        pub extern "java" fn toHexString(value: i32) -> JniResult<String> {}
+
+        #[attach]
+        #[call_type(unchecked)]
+        #[java_name = "parseInt"]
+        pub extern "java" fn parse_int_unchecked(s: String) -> i32 {}
+    }
+}
+
+fn set_vm() {
+    // SAFETY: the pointer is the one of the test JVM, which is never destroyed
+    set_java_vm(unsafe { JavaVM::from_raw(jvm().get_java_vm_pointer()) }.unwrap());
+}
+
+#[test]
+fn attached_methods_are_called_from_plain_threads() {
+    set_vm();
+
+    let (hex, parsed) = thread::spawn(|| (Integer::toHexString(255).unwrap(), Integer::parse_int_unchecked("42".into())))
+        .join()
+        .unwrap();
+    assert_eq!(hex, "ff");
+    assert_eq!(parsed, 42);
+}
+
+#[test]
+fn attachments_are_reused_by_a_thread() {
+    set_vm();
+
+    thread::spawn(|| {
+        let first = with_attached_env(|env| Ok(env.get_native_interface() as usize)).unwrap();
+        assert_eq!(Integer::toHexString(16).unwrap(), "10");
+        let second = with_attached_env(|env| Ok(env.get_native_interface() as usize)).unwrap();
+        assert_eq!(first, second);
+
+        // The thread is a daemon thread, which doesn't keep the JVM from exiting
+        let is_daemon = with_attached_env(|env| {
+            let thread = env.call_static_method("java/lang/Thread", "currentThread", "()Ljava/lang/Thread;", &[])?.l()?;
+            env.call_method(thread, "isDaemon", "()Z", &[])?.z()
+        });
+        assert!(is_daemon.unwrap());
+    })
+    .join()
+    .unwrap();
+}
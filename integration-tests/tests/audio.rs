@@ -0,0 +1,86 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::audio::{InvalidSamples, Samples};
+use robusta_jni::bridge;
+use robusta_jni::convert::{TryFromJavaValue, TryIntoJavaValue};
+use robusta_jni::jni::errors::Error;
+use robusta_jni::jni::objects::{JObject, JValue};
+
+use crate::jni::Audio;
+
+#[bridge]
+mod jni {
+    use robusta_jni::audio::Samples;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Audio;
+
+    impl Audio {
+        pub extern "java" fn swapChannels(env: JNIEnv, samples: Samples<i16>) -> JniResult<Samples<i16>> {}
+        pub extern "java" fn floatIdentity(env: JNIEnv, samples: Samples<f32>) -> JniResult<Samples<f32>> {}
+    }
+}
+
+#[test]
+fn stereo_samples_keep_their_interleaving() {
+    with_env(|env| {
+        // Left samples are positive, right samples negative
+        let samples = Samples::new(vec![1, -1, 2, -2, i16::MAX, i16::MIN], 2).unwrap();
+        let swapped = Audio::swapChannels(env, samples)?;
+
+        assert_eq!(swapped.channels(), 2);
+        assert_eq!(swapped.frames(), 3);
+        assert_eq!(swapped.data(), &[-1, 1, -2, 2, i16::MIN, i16::MAX]);
+        Ok(())
+    })
+}
+
+#[test]
+fn float_samples_round_trip() {
+    with_env(|env| {
+        let samples = Samples::new(vec![0.0, 0.25, -0.5, 1.0, -1.0, f32::MIN_POSITIVE], 3).unwrap();
+
+        assert_eq!(Audio::floatIdentity(env, samples.clone())?, samples);
+        Ok(())
+    })
+}
+
+#[test]
+fn samples_must_be_whole_frames() {
+    assert_eq!(Samples::new(vec![0i16; 5], 2), Err(InvalidSamples { len: 5, channels: 2 }));
+    assert_eq!(Samples::<i16>::new(Vec::new(), 0), Err(InvalidSamples { len: 0, channels: 0 }));
+    assert!(Samples::<i16>::new(Vec::new(), 2).is_ok());
+}
+
+#[test]
+fn java_samples_of_incomplete_frames_are_rejected() {
+    with_env(|env| {
+        let samples = TryIntoJavaValue::try_into(Samples::new(vec![0i16; 6], 2).unwrap(), env)?;
+        // Fields are final, but JNI can still set them
+        env.set_field(samples, "channels", "I", JValue::Int(4))?;
+
+        assert!(matches!(
+            <Samples<i16> as TryFromJavaValue>::try_from(samples, env),
+            Err(Error::WrongJValueType(..))
+        ));
+        assert!(matches!(
+            <Samples<i16> as TryFromJavaValue>::try_from(JObject::null(), env),
+            Err(Error::NullPtr(_))
+        ));
+        Ok(())
+    })
+}
+
+#[test]
+fn java_constructor_checks_frames() {
+    with_env(|env| {
+        let data = env.new_float_array(3)?;
+        let result = env.new_object("robusta/audio/FloatSamples", "([FI)V", &[JObject::from(data).into(), JValue::Int(2)]);
+
+        assert!(result.is_err());
+        let (class, _) = take_exception(env)?.expect("constructor should throw");
+        assert_eq!(class, "java.lang.IllegalArgumentException");
+        Ok(())
+    })
+}
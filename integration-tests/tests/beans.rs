@@ -0,0 +1,122 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::convert::TryIntoJavaValue;
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::{JObject, JValue};
+use robusta_jni::jni::JNIEnv;
+
+use integration_tests::fixtures::beans::jni::{Account, User};
+
+use crate::jni::{Beans, Birthdays};
+
+#[bridge]
+mod jni {
+    use integration_tests::fixtures::beans::jni::{Account, User};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Beans;
+
+    impl Beans {
+        pub extern "java" fn birthday(env: JNIEnv, user: User) -> JniResult<User> {}
+        pub extern "java" fn uncheckedBirthday(env: JNIEnv, user: User) -> JniResult<User> {}
+        pub extern "java" fn birthdays(env: JNIEnv, #[java_list] users: Vec<User>) -> JniResult<Vec<User>> {}
+        pub extern "java" fn deposit(env: JNIEnv, account: Account, amount: i64) -> JniResult<Account> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct Birthdays;
+
+    impl Birthdays {
+        pub extern "java" fn celebrate(env: JNIEnv) -> JniResult<String> {}
+    }
+}
+
+fn to_string(env: JNIEnv, value: JObject) -> JniResult<String> {
+    let string = env.call_method(value, "toString", "()Ljava/lang/String;", &[])?.l()?;
+    Ok(env.get_string(string.into())?.into())
+}
+
+fn user(email: Option<&str>) -> User {
+    User {
+        name: "Ada".to_string(),
+        age: 36,
+        email: email.map(ToString::to_string),
+    }
+}
+
+#[test]
+fn beans_are_read_with_getters_and_created_with_constructors() {
+    with_env(|env| {
+        let older = Beans::birthday(env, user(Some("ada@example.com")))?;
+        assert_eq!(older, User { age: 37, ..user(Some("ada@example.com")) });
+
+        assert_eq!(Beans::birthday(env, user(None))?, User { age: 37, ..user(None) });
+        assert_eq!(Beans::uncheckedBirthday(env, user(None))?, User { age: 37, ..user(None) });
+        Ok(())
+    })
+}
+
+#[test]
+fn beans_are_instances_of_their_java_class() {
+    with_env(|env| {
+        let user = TryIntoJavaValue::try_into(user(Some("ada@example.com")), env)?;
+        assert!(env.is_instance_of(user, "com/robusta/tests/User")?);
+        assert_eq!(to_string(env, user)?, "Ada (36, ada@example.com)");
+        Ok(())
+    })
+}
+
+#[test]
+fn vecs_of_beans_are_lists_of_their_java_class() {
+    with_env(|env| {
+        let users = vec![user(Some("ada@example.com")), User { name: "Grace".to_string(), ..user(None) }];
+        let older = Beans::birthdays(env, users.clone())?;
+        assert_eq!(older, users.into_iter().map(|u| User { age: u.age + 1, ..u }).collect::<Vec<_>>());
+
+        let list = TryIntoJavaValue::try_into(vec![user(None)], env)?;
+        let first = env.call_method(list, "get", "(I)Ljava/lang/Object;", &[JValue::Int(0)])?.l()?;
+        assert!(env.is_instance_of(first, "com/robusta/tests/User")?);
+        Ok(())
+    })
+}
+
+#[test]
+fn vecs_of_beans_are_consumed_as_typed_lists_in_java() {
+    with_env(|env| {
+        assert_eq!(Birthdays::celebrate(env)?, "Ada is 37, Grace is 46");
+        Ok(())
+    })
+}
+
+#[test]
+fn beans_with_setters_are_filled_after_their_construction() {
+    with_env(|env| {
+        let account = Account {
+            owner: "Ada".to_string(),
+            balance: 100,
+        };
+
+        let deposited = Beans::deposit(env, account.clone(), 50)?;
+        assert_eq!(deposited, Account { balance: 150, ..account.clone() });
+        assert_eq!(to_string(env, TryIntoJavaValue::try_into(account, env)?)?, "Ada: 100");
+        Ok(())
+    })
+}
+
+#[test]
+fn null_beans_throw_java_exception() {
+    with_env(|env| {
+        let result = env.call_static_method(
+            "com/robusta/tests/Beans",
+            "birthday",
+            "(Lcom/robusta/tests/User;)Lcom/robusta/tests/User;",
+            &[JValue::Object(JObject::null())],
+        );
+
+        assert!(result.is_err());
+        assert!(take_exception(env)?.is_some());
+        Ok(())
+    })
+}
@@ -0,0 +1,103 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+use robusta_jni::convert::{Boxed, TryFromJavaValue, TryIntoJavaValue};
+use robusta_jni::jni::errors::Error;
+use robusta_jni::jni::objects::{JObject, JValue};
+
+use crate::jni::Boxes;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::Boxed;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Boxes;
+
+    impl Boxes {
+        pub extern "java" fn doubled(env: JNIEnv, values: Vec<Boxed<i32>>) -> JniResult<Vec<Boxed<i32>>> {}
+        pub extern "java" fn doubledOrNull(env: JNIEnv, values: Vec<Option<i32>>) -> JniResult<Vec<Option<i32>>> {}
+        pub extern "java" fn sameInstance(env: JNIEnv, a: Boxed<i32>, b: Boxed<i32>) -> JniResult<bool> {}
+    }
+}
+
+#[test]
+fn boxed_values_are_elements_of_java_lists() {
+    with_env(|env| {
+        let values = vec![Boxed(1), Boxed(-64), Boxed(1 << 20)];
+
+        // `doubled` unboxes each element, which would throw if they weren't `Integer`s
+        assert_eq!(Boxes::doubled(env, values)?, vec![Boxed(2), Boxed(-128), Boxed(1 << 21)]);
+        Ok(())
+    })
+}
+
+#[test]
+fn vecs_of_primitives_are_lists_of_boxed_values() {
+    with_env(|env| {
+        let list = TryIntoJavaValue::try_into(vec![7, 8], env)?;
+        let first = env.call_method(list, "get", "(I)Ljava/lang/Object;", &[JValue::Int(0)])?.l()?;
+        assert!(env.is_instance_of(first, "java/lang/Integer")?);
+
+        assert_eq!(<Vec<i32> as TryFromJavaValue>::try_from(JObject::from(list), env)?, vec![7, 8]);
+        Ok(())
+    })
+}
+
+#[test]
+fn null_elements_round_trip_as_none() {
+    with_env(|env| {
+        let values = vec![Some(1), None, Some(-3)];
+        assert_eq!(Boxes::doubledOrNull(env, values)?, vec![Some(2), None, Some(-6)]);
+        Ok(())
+    })
+}
+
+#[test]
+fn null_elements_of_primitive_vecs_fail_checked_conversions() {
+    with_env(|env| {
+        let list = TryIntoJavaValue::try_into(vec![Some(1), None], env)?;
+        assert!(matches!(
+            <Vec<i32> as TryFromJavaValue>::try_from(JObject::from(list), env),
+            Err(Error::NullPtr("java/lang/Integer"))
+        ));
+
+        let strings = TryIntoJavaValue::try_into(vec!["1".to_string()], env)?;
+        assert!(matches!(
+            <Vec<i32> as TryFromJavaValue>::try_from(JObject::from(strings), env),
+            Err(Error::WrongJValueType("java/lang/Integer", _))
+        ));
+        assert!(matches!(
+            <Option<i32> as TryFromJavaValue>::try_from(env.new_string("1")?.into(), env),
+            Err(Error::WrongJValueType(..))
+        ));
+        Ok(())
+    })
+}
+
+#[test]
+fn small_values_come_from_the_integer_cache() {
+    with_env(|env| {
+        assert!(Boxes::sameInstance(env, Boxed(100), Boxed(100))?);
+        assert!(!Boxes::sameInstance(env, Boxed(100_000), Boxed(100_000))?);
+        Ok(())
+    })
+}
+
+#[test]
+fn objects_of_other_classes_are_rejected() {
+    with_env(|env| {
+        let string = env.new_string("42")?;
+
+        assert!(matches!(
+            <Boxed<i32> as TryFromJavaValue>::try_from(string.into(), env),
+            Err(Error::WrongJValueType(..))
+        ));
+        assert!(matches!(
+            <Boxed<i32> as TryFromJavaValue>::try_from(JObject::null(), env),
+            Err(Error::NullPtr(_))
+        ));
+        Ok(())
+    })
+}
@@ -0,0 +1,84 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::{JByteBuffer, JObject, JValue};
+use robusta_jni::jni::JNIEnv;
+
+const CLASS: &str = "com/robusta/tests/Buffers";
+
+/// A direct buffer allocated and written by Java, with its position and limit set around `bytes`.
+fn java_buffer<'env>(env: JNIEnv<'env>, bytes: &[i8], position: i32) -> JniResult<JObject<'env>> {
+    let buffer = env
+        .call_static_method("java/nio/ByteBuffer", "allocateDirect", "(I)Ljava/nio/ByteBuffer;", &[JValue::Int(position + bytes.len() as i32 + 4)])?
+        .l()?;
+    let array = env.byte_array_from_slice(&bytes.iter().map(|b| *b as u8).collect::<Vec<_>>())?;
+    // `position(int)` and `limit(int)` return a `ByteBuffer` since Java 9, and still a `Buffer` through their bridge methods
+    env.call_method(buffer, "position", "(I)Ljava/nio/Buffer;", &[JValue::Int(position)])?;
+    env.call_method(buffer, "put", "([B)Ljava/nio/ByteBuffer;", &[JValue::Object(JObject::from(array))])?;
+    env.call_method(buffer, "limit", "(I)Ljava/nio/Buffer;", &[JValue::Int(position + bytes.len() as i32)])?;
+    env.call_method(buffer, "position", "(I)Ljava/nio/Buffer;", &[JValue::Int(position)])?;
+    Ok(buffer)
+}
+
+fn call<'env>(env: JNIEnv<'env>, name: &str, sig: &str, args: &[JValue<'env>]) -> JniResult<JValue<'env>> {
+    env.call_static_method(CLASS, name, sig, args)
+}
+
+#[test]
+fn java_buffers_are_read_from_position_to_limit() {
+    with_env(|env| {
+        let buffer = java_buffer(env, &[1, 2, -1, 127], 3)?;
+        assert_eq!(call(env, "sum", "(Ljava/nio/ByteBuffer;)J", &[JValue::Object(buffer)])?.j()?, 1 + 2 + 255 + 127);
+        assert_eq!(call(env, "uncheckedSum", "(Ljava/nio/ByteBuffer;)J", &[JValue::Object(buffer)])?.j()?, 1 + 2 + 255 + 127);
+
+        // Rust reads the memory of the buffer in place
+        let address = env.get_direct_buffer_address(JByteBuffer::from(buffer))?.as_ptr() as i64;
+        assert_eq!(call(env, "address", "(Ljava/nio/ByteBuffer;)J", &[JValue::Object(buffer)])?.j()?, address + 3);
+
+        let empty = java_buffer(env, &[], 0)?;
+        assert_eq!(call(env, "sum", "(Ljava/nio/ByteBuffer;)J", &[JValue::Object(empty)])?.j()?, 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn heap_and_null_buffers_are_rejected() {
+    with_env(|env| {
+        let heap = env.call_static_method("java/nio/ByteBuffer", "allocate", "(I)Ljava/nio/ByteBuffer;", &[JValue::Int(8)])?.l()?;
+        assert!(call(env, "sum", "(Ljava/nio/ByteBuffer;)J", &[JValue::Object(heap)]).is_err());
+        assert!(take_exception(env)?.is_some());
+
+        assert!(call(env, "sum", "(Ljava/nio/ByteBuffer;)J", &[JValue::Object(JObject::null())]).is_err());
+        assert!(take_exception(env)?.is_some());
+        Ok(())
+    })
+}
+
+#[test]
+fn rust_bytes_are_read_by_java_and_taken_back() {
+    with_env(|env| {
+        let buffer = call(env, "filled", "(IB)Ljava/nio/ByteBuffer;", &[JValue::Int(16), JValue::Byte(-2)])?.l()?;
+        assert!(env.call_method(buffer, "isDirect", "()Z", &[])?.z()?);
+        assert_eq!(env.call_method(buffer, "remaining", "()I", &[])?.i()?, 16);
+        assert_eq!(env.call_method(buffer, "get", "(I)B", &[JValue::Int(15)])?.b()?, -2);
+
+        // Java writes to the Rust allocation, which Rust then reads when taking it back
+        env.call_method(buffer, "put", "(IB)Ljava/nio/ByteBuffer;", &[JValue::Int(0), JValue::Byte(1)])?;
+        assert_eq!(call(env, "release", "(Ljava/nio/ByteBuffer;)J", &[JValue::Object(buffer)])?.j()?, 1 + 15 * 254);
+        Ok(())
+    })
+}
+
+#[test]
+fn only_live_rust_buffers_are_taken_back() {
+    with_env(|env| {
+        let buffer = call(env, "filled", "(IB)Ljava/nio/ByteBuffer;", &[JValue::Int(4), JValue::Byte(1)])?.l()?;
+        assert_eq!(call(env, "release", "(Ljava/nio/ByteBuffer;)J", &[JValue::Object(buffer)])?.j()?, 4);
+
+        let java = java_buffer(env, &[1, 2], 0)?;
+        assert!(call(env, "release", "(Ljava/nio/ByteBuffer;)J", &[JValue::Object(java)]).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert!(message.unwrap().contains("not created by DirectBytes"));
+        Ok(())
+    })
+}
@@ -0,0 +1,105 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::jni::errors::Error;
+
+use crate::jni::RequestBuilder;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct RequestBuilder<'env> {
+        pub raw: JObject<'env>,
+    }
+
+    impl Signature for RequestBuilder<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/RequestBuilder;";
+    }
+
+    impl Signature for &RequestBuilder<'_> {
+        const SIG_TYPE: &'static str = <RequestBuilder as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &RequestBuilder<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> IntoJavaValue<'env> for &RequestBuilder<'env> {
+        type Target = JObject<'env>;
+
+        fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+            self.raw
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for RequestBuilder<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(RequestBuilder { raw: s })
+        }
+    }
+
+    impl<'env> RequestBuilder<'env> {
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>, url: String) -> JniResult<Self> {}
+
+        #[returns_self]
+        pub extern "java" fn method(self, env: JNIEnv<'env>, method: String) -> JniResult<Self> {}
+
+        #[returns_self]
+        pub extern "java" fn header(self, env: JNIEnv<'env>, name: String, value: String) -> JniResult<Self> {}
+
+        #[returns_self]
+        #[call_type(unchecked)]
+        pub extern "java" fn timeout(self, env: JNIEnv<'env>, seconds: i32) -> Self {}
+
+        pub extern "java" fn build(&self, env: JNIEnv<'env>) -> JniResult<String> {}
+    }
+}
+
+#[test]
+fn builder_calls_are_chained() {
+    with_env(|env| {
+        let request = RequestBuilder::new(env, "https://example.com".into())?
+            .method(env, "POST".into())?
+            .header(env, "Accept".into(), "text/plain".into())?
+            .timeout(env, 30)
+            .build(env)?;
+
+        assert_eq!(request, "POST https://example.com [Accept: text/plain] (30s)");
+        Ok(())
+    })
+}
+
+#[test]
+fn chained_calls_return_the_same_object() {
+    with_env(|env| {
+        let builder = RequestBuilder::new(env, "https://example.com".into())?;
+        let raw = builder.raw;
+
+        let builder = builder.method(env, "PUT".into())?.timeout(env, 5);
+        assert!(env.is_same_object(raw, builder.raw)?);
+        assert_eq!(builder.build(env)?, "PUT https://example.com (5s)");
+        Ok(())
+    })
+}
+
+#[test]
+fn exceptions_interrupt_chains() {
+    with_env(|env| {
+        let result = RequestBuilder::new(env, "https://example.com".into())?.header(env, String::new(), "empty".into());
+
+        assert!(matches!(result, Err(Error::JavaException)));
+        assert!(take_exception(env)?.is_some());
+        Ok(())
+    })
+}
@@ -0,0 +1,50 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+
+use crate::jni::CallTypes;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct CallTypes;
+
+    impl CallTypes {
+        pub extern "java" fn uncheckedParse(env: JNIEnv, s: String) -> JniResult<i32> {}
+        pub extern "java" fn safeParse(env: JNIEnv, s: String) -> JniResult<i32> {}
+    }
+}
+
+#[test]
+fn methods_inherit_module_call_type() {
+    with_env(|env| {
+        assert_eq!(CallTypes::uncheckedParse(env, "42".into())?, 42);
+        assert!(CallTypes::uncheckedParse(env, "forty-two".into()).is_err());
+
+        // Unchecked methods unwrap the returned `Result`, panicking on errors
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert!(message.unwrap().contains("called `Result::unwrap()` on an `Err` value"));
+        Ok(())
+    })
+}
+
+#[test]
+fn method_call_type_overrides_module_call_type() {
+    with_env(|env| {
+        assert_eq!(CallTypes::safeParse(env, "42".into())?, 42);
+        assert!(CallTypes::safeParse(env, "forty-two".into()).is_err());
+
+        let exception = take_exception(env)?;
+        assert_eq!(
+            exception,
+            Some((
+                "java.lang.IllegalArgumentException".to_string(),
+                Some("`forty-two` is not a number".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+use std::thread;
+
+use integration_tests::{jvm, with_env};
+use robusta_jni::callback::{CallbackError, OverflowPolicy, QueuedCallback, Shutdown};
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::GlobalRef;
+use robusta_jni::jni::sys::jlongArray;
+use robusta_jni::jni::JNIEnv;
+
+const PRODUCERS: i64 = 8;
+const CALLS_PER_PRODUCER: i64 = 2000;
+
+fn new_recorder(env: JNIEnv) -> JniResult<GlobalRef> {
+    let recorder = env.new_object("com/robusta/tests/Recorder", "()V", &[])?;
+    env.new_global_ref(recorder)
+}
+
+fn recorder_callback(
+    recorder: &GlobalRef,
+    method: &str,
+    capacity: usize,
+    policy: OverflowPolicy,
+) -> QueuedCallback<i64> {
+    with_env(|env| QueuedCallback::new(&env, recorder.as_obj(), method, capacity, policy))
+}
+
+fn call_recorder(recorder: &GlobalRef, method: &str) {
+    with_env(|env| env.call_method(recorder.as_obj(), method, "()V", &[]).map(drop))
+}
+
+fn recorded(recorder: &GlobalRef) -> Vec<i64> {
+    with_env(|env| {
+        let array = env
+            .call_method(recorder.as_obj(), "recorded", "()[J", &[])?
+            .l()?
+            .into_inner() as jlongArray;
+        let mut values = vec![0; env.get_array_length(array)? as usize];
+        env.get_long_array_region(array, 0, &mut values)?;
+        Ok(values)
+    })
+}
+
+#[test]
+fn calls_from_many_threads_keep_per_producer_order() {
+    let recorder = with_env(new_recorder);
+    let callback = Arc::new(recorder_callback(&recorder, "record", 64, OverflowPolicy::Block));
+
+    let producers: Vec<_> = (0..PRODUCERS)
+        .map(|producer| {
+            let callback = callback.clone();
+            thread::spawn(move || {
+                for sequence in 0..CALLS_PER_PRODUCER {
+                    callback.call(producer << 32 | sequence).unwrap();
+                }
+            })
+        })
+        .collect();
+
+    producers.into_iter().for_each(|p| p.join().unwrap());
+    callback.shutdown(Shutdown::Drain);
+    assert_eq!(callback.failures(), 0);
+
+    let values = recorded(&recorder);
+    assert_eq!(values.len() as i64, PRODUCERS * CALLS_PER_PRODUCER);
+
+    let mut next_sequence = vec![0; PRODUCERS as usize];
+    for value in values {
+        let (producer, sequence) = ((value >> 32) as usize, value & 0xffff_ffff);
+        assert_eq!(sequence, next_sequence[producer], "out of order call from producer {}", producer);
+        next_sequence[producer] += 1;
+    }
+}
+
+#[test]
+fn error_policy_gives_back_values_when_full() {
+    let recorder = with_env(new_recorder);
+    let callback = recorder_callback(&recorder, "recordGated", 1, OverflowPolicy::Error);
+
+    callback.call(0).unwrap();
+    call_recorder(&recorder, "awaitGate");
+    callback.call(1).unwrap();
+    assert_eq!(callback.call(2), Err(CallbackError::Full(2)));
+
+    call_recorder(&recorder, "openGate");
+    callback.shutdown(Shutdown::Drain);
+    assert_eq!(recorded(&recorder), vec![0, 1]);
+}
+
+#[test]
+fn drop_oldest_policy_discards_oldest_values() {
+    let recorder = with_env(new_recorder);
+    let callback = recorder_callback(&recorder, "recordGated", 2, OverflowPolicy::DropOldest);
+
+    callback.call(0).unwrap();
+    call_recorder(&recorder, "awaitGate");
+    (1..=4).for_each(|v| callback.call(v).unwrap());
+
+    call_recorder(&recorder, "openGate");
+    drop(callback);
+    assert_eq!(recorded(&recorder), vec![0, 3, 4]);
+}
+
+#[test]
+fn discarding_shutdown_drops_queued_values() {
+    let recorder = with_env(new_recorder);
+    let callback = Arc::new(recorder_callback(&recorder, "recordGated", 4, OverflowPolicy::Block));
+
+    callback.call(0).unwrap();
+    call_recorder(&recorder, "awaitGate");
+    callback.call(1).unwrap();
+    callback.call(2).unwrap();
+
+    let shutdown = {
+        let callback = callback.clone();
+        thread::spawn(move || callback.shutdown(Shutdown::Discard))
+    };
+
+    // The dispatcher is still delivering `0`, so wait for the queue to be closed before letting it finish
+    while callback.call(3).is_ok() {
+        thread::yield_now();
+    }
+    assert_eq!(callback.call(4), Err(CallbackError::Closed(4)));
+
+    call_recorder(&recorder, "openGate");
+    shutdown.join().unwrap();
+    assert_eq!(recorded(&recorder), vec![0]);
+}
+
+#[test]
+fn throwing_listener_counts_failures() {
+    let recorder = with_env(new_recorder);
+    let callback = recorder_callback(&recorder, "fail", 4, OverflowPolicy::Block);
+
+    (0..3).for_each(|v| callback.call(v).unwrap());
+    callback.shutdown(Shutdown::Drain);
+    assert_eq!(callback.failures(), 3);
+
+    // Exceptions were cleared on the dispatcher thread and don't leak to others
+    let env = jvm().attach_current_thread().unwrap();
+    assert!(!env.exception_check().unwrap());
+    assert!(recorded(&recorder).is_empty());
+}
@@ -0,0 +1,34 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+
+use crate::jni::Registry;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Registry;
+
+    impl Registry {
+        pub extern "java" fn count(env: JNIEnv) -> JniResult<i32> {}
+        pub extern "java" fn countPlus(env: JNIEnv, offset: i32) -> JniResult<i32> {}
+
+        #[field(static, name = "count")]
+        pub extern "java" fn set_count(env: JNIEnv, value: i32) -> JniResult<()> {}
+    }
+}
+
+#[test]
+fn static_methods_read_fields_of_their_class() {
+    with_env(|env| {
+        Registry::set_count(env, 7)?;
+        assert_eq!(Registry::count(env)?, 7);
+        assert_eq!(Registry::countPlus(env, 3)?, 10);
+
+        Registry::set_count(env, -1)?;
+        assert_eq!(Registry::count(env)?, -1);
+        Ok(())
+    })
+}
@@ -0,0 +1,88 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+
+use crate::jni::CommandBuilder;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct CommandBuilder<'env> {
+        pub raw: JObject<'env>,
+    }
+
+    impl Signature for CommandBuilder<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/CommandBuilder;";
+    }
+
+    impl Signature for &CommandBuilder<'_> {
+        const SIG_TYPE: &'static str = <CommandBuilder as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &CommandBuilder<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for CommandBuilder<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(CommandBuilder { raw: s })
+        }
+    }
+
+    impl<'env> CommandBuilder<'env> {
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>, command: &[&str]) -> JniResult<Self> {}
+
+        #[constructor]
+        pub extern "java" fn from_owned(env: JNIEnv<'env>, command: &[String]) -> JniResult<Self> {}
+
+        pub extern "java" fn args(&self, env: JNIEnv<'env>, args: Vec<&str>) -> JniResult<()> {}
+
+        #[constructor]
+        #[call_type(unchecked)]
+        pub extern "java" fn new_unchecked(env: JNIEnv<'env>, command: &[&str]) -> JObject<'env> {}
+
+        pub extern "java" fn command(&self, env: JNIEnv<'env>) -> JniResult<Vec<String>> {}
+    }
+}
+
+#[test]
+fn borrowed_string_slices_are_passed_as_arrays() {
+    with_env(|env| {
+        let builder = CommandBuilder::new(env, &["git", "status"])?;
+        assert_eq!(builder.command(env)?, vec!["git", "status"]);
+
+        let command = vec!["cargo".to_string(), "test".to_string()];
+        let builder = CommandBuilder::from_owned(env, &command)?;
+        assert_eq!(builder.command(env)?, command);
+
+        let builder = CommandBuilder { raw: CommandBuilder::new_unchecked(env, &["ls", "-l"]) };
+        assert_eq!(builder.command(env)?, vec!["ls", "-l"]);
+
+        let builder = CommandBuilder::new(env, &[])?;
+        assert!(builder.command(env)?.is_empty());
+        Ok(())
+    })
+}
+
+#[test]
+fn vecs_of_borrowed_strings_are_passed_as_lists() {
+    with_env(|env| {
+        let builder = CommandBuilder::new(env, &["cargo"])?;
+        let flag = String::from("--offline");
+        builder.args(env, vec!["build", flag.as_str()])?;
+
+        assert_eq!(builder.command(env)?, vec!["cargo", "build", "--offline"]);
+        Ok(())
+    })
+}
@@ -0,0 +1,711 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{TimeZone, Utc};
+
+use integration_tests::{assert_round_trip, take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::convert::{Adapt, BigIntegerBytes, Boxed, EncodedString, JavaArray, Latin1, Passthrough, TryFromJavaValue, TryIntoJavaValue, Utf16Le};
+use robusta_jni::jni::errors::{Error, Result as JniResult};
+use robusta_jni::jni::objects::{JObject, JValue};
+
+use crate::jni::{Connections, Conversions, StringArrays, Tokens, Tuples};
+
+#[bridge]
+mod jni {
+    use std::time::{Duration, SystemTime};
+
+    use chrono::{DateTime, Utc};
+    use integration_tests::fixtures::conversions::ParseIp;
+    use robusta_jni::convert::{Adapt, BigIntegerBytes, Boxed, EncodedString, JavaArray, Latin1, Passthrough, PassthroughOwned, Utf16Le};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Conversions;
+
+    impl Conversions {
+        pub extern "java" fn intIdentity(env: JNIEnv, v: i32) -> JniResult<i32> {}
+        pub extern "java" fn boolIdentity(env: JNIEnv, v: bool) -> JniResult<bool> {}
+        pub extern "java" fn charIdentity(env: JNIEnv, v: char) -> JniResult<char> {}
+        pub extern "java" fn byteIdentity(env: JNIEnv, v: i8) -> JniResult<i8> {}
+        pub extern "java" fn floatIdentity(env: JNIEnv, v: f32) -> JniResult<f32> {}
+        pub extern "java" fn doubleIdentity(env: JNIEnv, v: f64) -> JniResult<f64> {}
+        pub extern "java" fn longIdentity(env: JNIEnv, v: i64) -> JniResult<i64> {}
+        pub extern "java" fn shortIdentity(env: JNIEnv, v: i16) -> JniResult<i16> {}
+        pub extern "java" fn unsignedIntIdentity(env: JNIEnv, v: u32) -> JniResult<u32> {}
+        pub extern "java" fn unsignedLongIdentity(env: JNIEnv, v: u64) -> JniResult<u64> {}
+        pub extern "java" fn usizeIdentity(env: JNIEnv, v: usize) -> JniResult<usize> {}
+        pub extern "java" fn unsignedLongMax(env: JNIEnv) -> JniResult<i64> {}
+        pub extern "java" fn stringIdentity(env: JNIEnv, v: String) -> JniResult<String> {}
+        pub extern "java" fn intVecIdentity(env: JNIEnv, #[java_list] v: Vec<i32>) -> JniResult<Vec<i32>> {}
+        pub extern "java" fn stringVecIdentity(env: JNIEnv, #[java_list] v: Vec<String>) -> JniResult<Vec<String>> {}
+        #[java_list]
+        pub extern "java" fn stringListIdentity(env: JNIEnv, #[java_list] v: Vec<String>) -> JniResult<Vec<String>> {}
+        pub extern "java" fn boxedIntIdentity(env: JNIEnv, v: Boxed<i32>) -> JniResult<Boxed<i32>> {}
+        pub extern "java" fn boxedCharIdentity(env: JNIEnv, v: Boxed<char>) -> JniResult<Boxed<char>> {}
+        pub extern "java" fn boxedDoubleIdentity(env: JNIEnv, v: Boxed<f64>) -> JniResult<Boxed<f64>> {}
+        pub extern "java" fn boxedLongVecIdentity(env: JNIEnv, #[java_list] v: Vec<Boxed<i64>>) -> JniResult<Vec<Boxed<i64>>> {}
+        pub extern "java" fn optionalIntIdentity(env: JNIEnv, v: Option<i32>) -> JniResult<Option<i32>> {}
+        pub extern "java" fn optionalBoolIdentity(env: JNIEnv, v: Option<bool>) -> JniResult<Option<bool>> {}
+        pub extern "java" fn optionalCharIdentity(env: JNIEnv, v: Option<char>) -> JniResult<Option<char>> {}
+        pub extern "java" fn optionalStringIdentity(env: JNIEnv, v: Option<String>) -> JniResult<Option<String>> {}
+        pub extern "java" fn optionalIntVecIdentity(env: JNIEnv, #[java_list] v: Option<Vec<i32>>) -> JniResult<Option<Vec<i32>>> {}
+        pub extern "java" fn optionalStringVecIdentity(env: JNIEnv, #[java_list] v: Vec<Option<String>>) -> JniResult<Vec<Option<String>>> {}
+        pub extern "java" fn intArrayIdentity(env: JNIEnv, v: JavaArray<i32>) -> JniResult<JavaArray<i32>> {}
+        pub extern "java" fn boolArrayIdentity(env: JNIEnv, v: JavaArray<bool>) -> JniResult<JavaArray<bool>> {}
+        pub extern "java" fn charArrayIdentity(env: JNIEnv, v: JavaArray<char>) -> JniResult<JavaArray<char>> {}
+        pub extern "java" fn doubleArrayIdentity(env: JNIEnv, v: JavaArray<f64>) -> JniResult<JavaArray<f64>> {}
+        pub extern "java" fn stringArrayIdentity(env: JNIEnv, v: JavaArray<String>) -> JniResult<JavaArray<String>> {}
+        pub extern "java" fn stringSliceIdentity(env: JNIEnv, v: Box<[String]>) -> JniResult<Box<[String]>> {}
+        pub extern "java" fn optionalStringArrayIdentity(env: JNIEnv, v: JavaArray<Option<String>>) -> JniResult<JavaArray<Option<String>>> {}
+        pub extern "java" fn boxedLongArrayIdentity(env: JNIEnv, v: JavaArray<Boxed<i64>>) -> JniResult<JavaArray<Boxed<i64>>> {}
+        pub extern "java" fn pairIdentity(env: JNIEnv, v: (String, i32)) -> JniResult<(String, i32)> {}
+        pub extern "java" fn tripleIdentity(env: JNIEnv, v: (Option<String>, i64, bool)) -> JniResult<(Option<String>, i64, bool)> {}
+        pub extern "java" fn nestedTupleIdentity(env: JNIEnv, v: (char, (f64, String))) -> JniResult<(char, (f64, String))> {}
+        pub extern "java" fn uncheckedPairIdentity(env: JNIEnv, v: (String, i32)) -> JniResult<(String, i32)> {}
+        pub extern "java" fn latin1StringIdentity(env: JNIEnv, v: EncodedString<Latin1>) -> JniResult<EncodedString<Latin1>> {}
+        pub extern "java" fn utf16LeStringIdentity(env: JNIEnv, v: EncodedString<Utf16Le>) -> JniResult<EncodedString<Utf16Le>> {}
+        pub extern "java" fn isLoopback(env: JNIEnv, address: String) -> JniResult<bool> {}
+        pub extern "java" fn loopback(env: JNIEnv, v6: bool) -> JniResult<Adapt<String, ParseIp>> {}
+        #[java_name = "stringIdentity"]
+        pub extern "java" fn ipIdentity(env: JNIEnv, v: Adapt<String, ParseIp>) -> JniResult<Adapt<String, ParseIp>> {}
+        pub extern "java" fn passthroughIdentity<'env>(env: JNIEnv<'env>, v: Passthrough<'env>) -> JniResult<Passthrough<'env>> {}
+        pub extern "java" fn ownedPassthroughIdentity(env: JNIEnv, v: PassthroughOwned) -> JniResult<PassthroughOwned> {}
+        pub extern "java" fn uncheckedPassthroughIdentity<'env>(env: JNIEnv<'env>, v: Passthrough<'env>) -> JniResult<Passthrough<'env>> {}
+        pub extern "java" fn durationIdentity(env: JNIEnv, v: Duration) -> JniResult<Duration> {}
+        pub extern "java" fn instantIdentity(env: JNIEnv, v: SystemTime) -> JniResult<SystemTime> {}
+        pub extern "java" fn dateTimeIdentity(env: JNIEnv, v: DateTime<Utc>) -> JniResult<DateTime<Utc>> {}
+        pub extern "java" fn i128Identity(env: JNIEnv, v: i128) -> JniResult<i128> {}
+        pub extern "java" fn u128Identity(env: JNIEnv, v: u128) -> JniResult<u128> {}
+        pub extern "java" fn bigIntegerBytesIdentity(env: JNIEnv, v: BigIntegerBytes) -> JniResult<BigIntegerBytes> {}
+        pub extern "java" fn uncheckedI128Identity(env: JNIEnv, v: i128) -> JniResult<i128> {}
+        pub extern "java" fn optionalDurationIdentity(env: JNIEnv, v: Option<Duration>) -> JniResult<Option<Duration>> {}
+        pub extern "java" fn uncheckedDurationIdentity(env: JNIEnv, v: Duration) -> JniResult<Duration> {}
+        pub extern "java" fn uncheckedInstantIdentity(env: JNIEnv, v: SystemTime) -> JniResult<SystemTime> {}
+        pub extern "java" fn uncheckedOptionalLongIdentity(env: JNIEnv, v: Option<i64>) -> JniResult<Option<i64>> {}
+        pub extern "java" fn uncheckedOptionalStringIdentity(env: JNIEnv, v: Option<String>) -> JniResult<Option<String>> {}
+        pub extern "java" fn reinterpretedIntIdentity(env: JNIEnv, v: i32) -> JniResult<i32> {}
+        pub extern "java" fn reinterpretedByteIdentity(env: JNIEnv, v: i8) -> JniResult<i8> {}
+        pub extern "java" fn describeReinterpretedInt(env: JNIEnv, v: i32) -> JniResult<String> {}
+        pub extern "java" fn saturatedSum(env: JNIEnv, a: i64, b: i64) -> JniResult<i32> {}
+        pub extern "java" fn truncatedUnsignedLongIdentity(env: JNIEnv, v: i32) -> JniResult<i32> {}
+        pub extern "java" fn uncheckedReinterpretedLongIdentity(env: JNIEnv, v: i64) -> JniResult<i64> {}
+        pub extern "java" fn borrowedStringIdentity(env: JNIEnv, v: String) -> JniResult<String> {}
+        pub extern "java" fn borrowedStringCharCount(env: JNIEnv, v: String) -> JniResult<i32> {}
+        pub extern "java" fn uncheckedBorrowedStringIdentity(env: JNIEnv, v: String) -> JniResult<String> {}
+        pub extern "java" fn uncheckedIntIdentity(env: JNIEnv, v: i32) -> JniResult<i32> {}
+        pub extern "java" fn uncheckedStringIdentity(env: JNIEnv, v: String) -> JniResult<String> {}
+        pub extern "java" fn uncheckedStringArrayIdentity(env: JNIEnv, v: JavaArray<String>) -> JniResult<JavaArray<String>> {}
+        pub extern "java" fn uncheckedStringSliceIdentity(env: JNIEnv, v: Box<[String]>) -> JniResult<Box<[String]>> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct StringArrays;
+
+    impl StringArrays {
+        pub extern "java" fn lengths(env: JNIEnv, values: JavaArray<String>) -> JniResult<JavaArray<i32>> {}
+
+        #[call_type(unchecked)]
+        #[java_name = "lengths"]
+        pub extern "java" fn lengths_unchecked(env: JNIEnv, values: JavaArray<String>) -> JavaArray<i32> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct Tuples;
+
+    impl Tuples {
+        pub extern "java" fn describeNameAndLength(env: JNIEnv, name: String) -> JniResult<String> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct Tokens;
+
+    impl Tokens {
+        pub extern "java" fn newToken<'env>(env: JNIEnv<'env>) -> JniResult<Passthrough<'env>> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct Connections;
+
+    impl Connections {
+        pub extern "java" fn describe(env: JNIEnv, host: String, port: i32, secure: bool) -> JniResult<String> {}
+    }
+}
+
+#[test]
+fn int_round_trip() {
+    assert_round_trip!(Conversions::intIdentity, 0, 42, i32::MIN, i32::MAX);
+}
+
+#[test]
+fn bool_round_trip() {
+    assert_round_trip!(Conversions::boolIdentity, true, false);
+}
+
+#[test]
+fn char_round_trip() {
+    assert_round_trip!(Conversions::charIdentity, 'a', 'è', '\u{ffff}');
+}
+
+#[test]
+fn byte_round_trip() {
+    assert_round_trip!(Conversions::byteIdentity, 0, i8::MIN, i8::MAX);
+}
+
+#[test]
+fn float_round_trip() {
+    assert_round_trip!(Conversions::floatIdentity, 0.0, -1.5, f32::MAX, f32::INFINITY);
+}
+
+#[test]
+fn double_round_trip() {
+    assert_round_trip!(Conversions::doubleIdentity, 0.0, -1.5, f64::MIN_POSITIVE, f64::NEG_INFINITY);
+}
+
+#[test]
+fn long_round_trip() {
+    assert_round_trip!(Conversions::longIdentity, 0, i64::MIN, i64::MAX);
+}
+
+#[test]
+fn short_round_trip() {
+    assert_round_trip!(Conversions::shortIdentity, 0, i16::MIN, i16::MAX);
+}
+
+#[test]
+fn unsigned_round_trip() {
+    assert_round_trip!(Conversions::unsignedIntIdentity, 0, u32::MAX);
+    assert_round_trip!(Conversions::unsignedLongIdentity, 0, i64::MAX as u64);
+    assert_round_trip!(Conversions::usizeIdentity, 0, 42);
+}
+
+#[test]
+fn unsigned_values_out_of_range_throw_with_the_value() {
+    with_env(|env| {
+        for (value, message) in [(-1, "-1 is out of the range of u32"), (1 << 32, "4294967296 is out of the range of u32")] {
+            let result = env.call_static_method("com/robusta/tests/Conversions", "unsignedIntIdentity", "(J)J", &[JValue::Long(value)]);
+            assert!(matches!(result, Err(Error::JavaException)));
+            assert_eq!(
+                take_exception(env)?,
+                Some(("java.lang.IllegalArgumentException".to_string(), Some(message.to_string())))
+            );
+        }
+
+        assert!(Conversions::unsignedLongMax(env).is_err());
+        assert_eq!(
+            take_exception(env)?,
+            Some((
+                "java.lang.IllegalArgumentException".to_string(),
+                Some("18446744073709551615 is out of the range of long".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn string_round_trip() {
+    assert_round_trip!(
+        Conversions::stringIdentity,
+        String::new(),
+        "hello".to_string(),
+        "unicode: ❤ 𝄞".to_string()
+    );
+}
+
+#[test]
+fn vec_round_trip() {
+    assert_round_trip!(Conversions::intVecIdentity, vec![], vec![1, 2, 3]);
+    assert_round_trip!(
+        Conversions::stringVecIdentity,
+        vec!["a".to_string(), "b".to_string()]
+    );
+}
+
+#[test]
+fn vecs_are_taken_from_any_list() {
+    with_env(|env| {
+        let list = env.new_object("java/util/LinkedList", "()V", &[])?;
+        for value in ["a", "b", "c"] {
+            env.call_method(list, "add", "(Ljava/lang/Object;)Z", &[env.new_string(value)?.into()])?;
+        }
+        let unmodifiable = env
+            .call_static_method("java/util/Collections", "unmodifiableList", "(Ljava/util/List;)Ljava/util/List;", &[list.into()])?
+            .l()?;
+
+        for list in [list, unmodifiable] {
+            let joined = env
+                .call_static_method("com/robusta/tests/Conversions", "joinStrings", "(Ljava/util/List;)Ljava/lang/String;", &[list.into()])?
+                .l()?;
+            assert_eq!(String::from(env.get_string(joined.into())?), "a,b,c");
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn java_list_returns_are_lists() {
+    assert_round_trip!(Conversions::stringListIdentity, vec![], vec!["a".to_string(), "b".to_string()]);
+}
+
+#[test]
+fn array_round_trip() {
+    assert_round_trip!(Conversions::intArrayIdentity, JavaArray(vec![]), JavaArray(vec![1, i32::MIN, i32::MAX]));
+    assert_round_trip!(Conversions::boolArrayIdentity, JavaArray(vec![true, false, true]));
+    assert_round_trip!(Conversions::charArrayIdentity, JavaArray(vec!['a', 'é', '\u{ffff}']));
+    assert_round_trip!(Conversions::doubleArrayIdentity, JavaArray(vec![-1.5, f64::INFINITY]));
+    assert_round_trip!(
+        Conversions::stringArrayIdentity,
+        JavaArray(vec![]),
+        JavaArray(vec!["a".to_string(), "unicode: ❤ 𝄞".to_string()])
+    );
+    assert_round_trip!(
+        Conversions::optionalStringArrayIdentity,
+        JavaArray(vec![Some("a".to_string()), None, Some(String::new())])
+    );
+    assert_round_trip!(Conversions::boxedLongArrayIdentity, JavaArray(vec![Boxed(1), Boxed(i64::MIN)]));
+    assert_round_trip!(Conversions::uncheckedStringArrayIdentity, JavaArray(vec!["b".to_string()]));
+}
+
+#[test]
+fn arrays_are_passed_to_java_methods() {
+    with_env(|env| {
+        let values = JavaArray(vec!["".to_string(), "abc".to_string(), "❤".to_string()]);
+        assert_eq!(StringArrays::lengths(env, values.clone())?, JavaArray(vec![0, 3, 1]));
+        assert_eq!(StringArrays::lengths_unchecked(env, values), JavaArray(vec![0, 3, 1]));
+        Ok(())
+    })
+}
+
+#[test]
+fn null_elements_fail_conversion() {
+    with_env(|env| {
+        // The exported method gets a `String[]` with a `null` element
+        let values = JavaArray(vec![Some("a".to_string()), None]);
+        let array = <JavaArray<Option<String>> as TryIntoJavaValue>::try_into(values, env)?;
+        let result = env.call_static_method(
+            "com/robusta/tests/Conversions",
+            "stringArrayIdentity",
+            "([Ljava/lang/String;)[Ljava/lang/String;",
+            &[array.into()],
+        );
+        assert!(result.is_err());
+        assert_eq!(take_exception(env)?.map(|(class, _)| class).as_deref(), Some("java.lang.RuntimeException"));
+        Ok(())
+    })
+}
+
+#[test]
+fn string_slice_round_trip() {
+    let strings = |values: &[&str]| values.iter().map(ToString::to_string).collect::<Box<[String]>>();
+    assert_round_trip!(Conversions::stringSliceIdentity, strings(&[]), strings(&["a", "", "unicode: ❤ 𝄞 日本"]));
+    assert_round_trip!(Conversions::uncheckedStringSliceIdentity, strings(&["b"]));
+}
+
+#[test]
+fn null_string_slice_elements_fail_conversion() {
+    with_env(|env| {
+        let values = JavaArray(vec![Some("a".to_string()), None]);
+        let array = <JavaArray<Option<String>> as TryIntoJavaValue>::try_into(values, env)?;
+        assert!(matches!(
+            <Box<[String]> as TryFromJavaValue>::try_from(array, env),
+            Err(Error::NullPtr("String[] element"))
+        ));
+
+        let result = env.call_static_method(
+            "com/robusta/tests/Conversions",
+            "stringSliceIdentity",
+            "([Ljava/lang/String;)[Ljava/lang/String;",
+            &[array.into()],
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            take_exception(env)?,
+            Some(("java.lang.RuntimeException".to_string(), Some("Null pointer in String[] element".to_string())))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn encoded_string_round_trip() {
+    assert_round_trip!(
+        Conversions::latin1StringIdentity,
+        EncodedString::new(String::new()),
+        EncodedString::new("café ÿ".to_string())
+    );
+    assert_round_trip!(
+        Conversions::utf16LeStringIdentity,
+        EncodedString::new(String::new()),
+        EncodedString::new("unicode: ❤ 𝄞 日本".to_string())
+    );
+}
+
+#[test]
+fn encoded_strings_are_bytes_in_their_charset() {
+    with_env(|env| {
+        let bytes = <EncodedString<Latin1> as TryIntoJavaValue>::try_into(EncodedString::new("é❤".to_string()), env)?;
+        // Characters outside of the charset are replaced by Java
+        assert_eq!(<JavaArray<i8> as TryFromJavaValue>::try_from(bytes, env)?, JavaArray(vec![0xe9_u8 as i8, b'?' as i8]));
+
+        let bytes = <EncodedString<Utf16Le> as TryIntoJavaValue>::try_into(EncodedString::new("é".to_string()), env)?;
+        assert_eq!(<JavaArray<i8> as TryFromJavaValue>::try_from(bytes, env)?, JavaArray(vec![0xe9_u8 as i8, 0]));
+        Ok(())
+    })
+}
+
+#[test]
+fn adapted_values_are_mapped() {
+    with_env(|env| {
+        assert!(Conversions::isLoopback(env, "127.0.0.1".into())?);
+        assert!(!Conversions::isLoopback(env, "2001:db8::1".into())?);
+        assert_eq!(*Conversions::loopback(env, false)?, IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(*Conversions::loopback(env, true)?, IpAddr::V6(Ipv6Addr::LOCALHOST));
+        Ok(())
+    });
+    assert_round_trip!(
+        Conversions::ipIdentity,
+        Adapt::new(IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1))),
+        Adapt::new(IpAddr::V6(Ipv6Addr::LOCALHOST))
+    );
+}
+
+#[test]
+fn unmappable_values_throw_java_exception() {
+    with_env(|env| {
+        assert!(Conversions::isLoopback(env, "localhost".into()).is_err());
+        assert_eq!(
+            take_exception(env)?,
+            Some(("java.lang.IllegalArgumentException".to_string(), Some("invalid IP address syntax".to_string())))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn unsupported_charsets_throw_java_exception() {
+    with_env(|env| {
+        let bytes = <JavaArray<i8> as TryIntoJavaValue>::try_into(JavaArray(vec![b'a' as i8]), env)?;
+        let result = env.call_static_method("com/robusta/tests/Conversions", "unsupportedCharsetLength", "([B)I", &[bytes.into()]);
+        assert!(result.is_err());
+        assert_eq!(
+            take_exception(env)?.map(|(class, _)| class).as_deref(),
+            Some("java.nio.charset.UnsupportedCharsetException")
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn duration_round_trip() {
+    assert_round_trip!(
+        Conversions::durationIdentity,
+        Duration::ZERO,
+        Duration::from_nanos(1),
+        Duration::new(90, 500_000_000),
+        Duration::new(i64::MAX as u64, 999_999_999)
+    );
+    assert_round_trip!(Conversions::optionalDurationIdentity, None, Some(Duration::from_millis(1500)));
+    assert_round_trip!(Conversions::uncheckedDurationIdentity, Duration::new(3600, 1));
+}
+
+#[test]
+fn instant_round_trip() {
+    assert_round_trip!(
+        Conversions::instantIdentity,
+        UNIX_EPOCH,
+        UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789),
+        UNIX_EPOCH - Duration::from_secs(86_400),
+        UNIX_EPOCH - Duration::new(1, 500_000_000),
+        SystemTime::now()
+    );
+    assert_round_trip!(Conversions::uncheckedInstantIdentity, UNIX_EPOCH - Duration::from_nanos(1));
+    assert_round_trip!(
+        Conversions::dateTimeIdentity,
+        Utc.timestamp_opt(0, 0).unwrap(),
+        Utc.timestamp_opt(-62_135_596_800, 999_999_999).unwrap(),
+        Utc.timestamp_opt(1_700_000_000, 1).unwrap()
+    );
+}
+
+#[test]
+fn times_are_java_time_objects() {
+    with_env(|env| {
+        let to_string = |value: JObject| -> JniResult<String> {
+            let string = env.call_method(value, "toString", "()Ljava/lang/String;", &[])?.l()?;
+            Ok(env.get_string(string.into())?.into())
+        };
+
+        let duration = <Duration as TryIntoJavaValue>::try_into(Duration::new(90, 500_000_000), env)?;
+        assert_eq!(to_string(duration)?, "PT1M30.5S");
+        let instant = <SystemTime as TryIntoJavaValue>::try_into(UNIX_EPOCH - Duration::new(1, 500_000_000), env)?;
+        assert_eq!(to_string(instant)?, "1969-12-31T23:59:58.500Z");
+        let date_time = <chrono::DateTime<Utc> as TryIntoJavaValue>::try_into(Utc.timestamp_opt(86_400, 0).unwrap(), env)?;
+        assert_eq!(to_string(date_time)?, "1970-01-02T00:00:00Z");
+        Ok(())
+    })
+}
+
+#[test]
+fn times_out_of_range_throw_java_exception() {
+    with_env(|env| {
+        assert!(Conversions::durationIdentity(env, Duration::from_secs(u64::MAX)).is_err());
+        assert_eq!(take_exception(env)?.map(|(class, _)| class).as_deref(), Some("java.lang.IllegalArgumentException"));
+
+        assert!(Conversions::instantIdentity(env, UNIX_EPOCH + Duration::from_secs(i64::MAX as u64)).is_err());
+        assert_eq!(take_exception(env)?.map(|(class, _)| class).as_deref(), Some("java.time.DateTimeException"));
+
+        let negative = env
+            .call_static_method("java/time/Duration", "ofSeconds", "(J)Ljava/time/Duration;", &[JValue::Long(-1)])?
+            .l()?;
+        let result = env.call_static_method(
+            "com/robusta/tests/Conversions",
+            "durationIdentity",
+            "(Ljava/time/Duration;)Ljava/time/Duration;",
+            &[negative.into()],
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            take_exception(env)?,
+            Some((
+                "java.lang.IllegalArgumentException".to_string(),
+                Some("negative duration of -1 s and 0 ns can't be converted to a Duration".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn big_integer_round_trip() {
+    assert_round_trip!(Conversions::i128Identity, 0, -1, i64::MIN as i128 - 1, i128::MIN, i128::MAX);
+    assert_round_trip!(Conversions::u128Identity, 0, u64::MAX as u128 + 1, i128::MAX as u128 + 1, u128::MAX);
+    assert_round_trip!(Conversions::uncheckedI128Identity, -(1 << 100), i128::MIN);
+    assert_round_trip!(
+        Conversions::bigIntegerBytesIdentity,
+        BigIntegerBytes(vec![0x80]),
+        BigIntegerBytes([&[0x01][..], &[0; 20]].concat())
+    );
+}
+
+#[test]
+fn big_integers_are_java_big_integers() {
+    with_env(|env| {
+        let to_string = |value: JObject| -> JniResult<String> {
+            let string = env.call_method(value, "toString", "()Ljava/lang/String;", &[])?.l()?;
+            Ok(env.get_string(string.into())?.into())
+        };
+
+        assert_eq!(to_string(TryIntoJavaValue::try_into(i128::MIN, env)?)?, "-170141183460469231731687303715884105728");
+        assert_eq!(to_string(TryIntoJavaValue::try_into(u128::MAX, env)?)?, "340282366920938463463374607431768211455");
+        assert_eq!(to_string(TryIntoJavaValue::try_into(BigIntegerBytes(vec![0xff, 0x00]), env)?)?, "-256");
+        Ok(())
+    })
+}
+
+#[test]
+fn big_integers_out_of_range_throw_arithmetic_exception() {
+    with_env(|env| {
+        let call = |method: &str, value: &str| -> JniResult<()> {
+            let value = env.new_object("java/math/BigInteger", "(Ljava/lang/String;)V", &[env.new_string(value)?.into()])?;
+            env.call_static_method(
+                "com/robusta/tests/Conversions",
+                method,
+                "(Ljava/math/BigInteger;)Ljava/math/BigInteger;",
+                &[value.into()],
+            )?;
+            Ok(())
+        };
+
+        assert!(call("i128Identity", "170141183460469231731687303715884105728").is_err());
+        assert_eq!(
+            take_exception(env)?,
+            Some((
+                "java.lang.ArithmeticException".to_string(),
+                Some("BigInteger 170141183460469231731687303715884105728 is out of the range of i128".to_string())
+            ))
+        );
+
+        assert!(call("u128Identity", "340282366920938463463374607431768211456").is_err());
+        assert_eq!(take_exception(env)?.map(|(class, _)| class).as_deref(), Some("java.lang.ArithmeticException"));
+        assert!(call("u128Identity", "-1").is_err());
+        assert_eq!(take_exception(env)?.map(|(class, _)| class).as_deref(), Some("java.lang.ArithmeticException"));
+        Ok(())
+    })
+}
+
+#[test]
+fn tuple_round_trip() {
+    assert_round_trip!(Conversions::pairIdentity, (String::new(), 0), ("unicode: ❤".to_string(), i32::MIN));
+    assert_round_trip!(Conversions::tripleIdentity, (None, 1, true), (Some("a".to_string()), i64::MAX, false));
+    assert_round_trip!(Conversions::nestedTupleIdentity, ('a', (1.5, "b".to_string())), ('é', (f64::INFINITY, String::new())));
+    assert_round_trip!(Conversions::uncheckedPairIdentity, ("b".to_string(), -1));
+}
+
+#[test]
+fn tuples_are_unpacked_by_java() {
+    with_env(|env| {
+        assert_eq!(Tuples::describeNameAndLength(env, "❤ abc".to_string())?, "❤ abc has 5 characters");
+        Ok(())
+    })
+}
+
+#[test]
+fn tuples_of_another_length_fail_conversion() {
+    with_env(|env| {
+        let triple = <(String, i32, bool) as TryIntoJavaValue>::try_into(("a".to_string(), 1, true), env)?;
+        let result = env.call_static_method(
+            "com/robusta/tests/Conversions",
+            "pairIdentity",
+            "([Ljava/lang/Object;)[Ljava/lang/Object;",
+            &[triple.into()],
+        );
+        assert!(result.is_err());
+        assert_eq!(take_exception(env)?.map(|(class, _)| class).as_deref(), Some("java.lang.RuntimeException"));
+        Ok(())
+    })
+}
+
+#[test]
+fn passthrough_objects_are_returned_unchanged() {
+    with_env(|env| {
+        let token = Tokens::newToken(env)?;
+
+        let returned = Conversions::passthroughIdentity(env, token)?;
+        assert!(env.is_same_object(token.as_obj(), returned.as_obj())?);
+
+        let returned = Conversions::ownedPassthroughIdentity(env, token.upgrade()?)?;
+        assert!(env.is_same_object(token.as_obj(), returned.as_obj())?);
+        assert!(env.is_same_object(token.as_obj(), returned.downgrade(env)?.as_obj())?);
+
+        let returned = Conversions::uncheckedPassthroughIdentity(env, token)?;
+        assert!(env.is_same_object(token.as_obj(), returned.as_obj())?);
+
+        let null = Conversions::passthroughIdentity(env, Passthrough::new(env, JObject::null()))?;
+        assert!(null.as_obj().is_null());
+        Ok(())
+    })
+}
+
+#[test]
+fn passthrough_objects_are_debugged_by_class() {
+    with_env(|env| {
+        let token = Passthrough::new(env, env.new_string("token")?.into());
+        assert_eq!(format!("{:?}", token), "Passthrough(java.lang.String)");
+        assert_eq!(format!("{:?}", token.upgrade()?), "PassthroughOwned(java.lang.String)");
+        assert_eq!(format!("{:?}", Passthrough::new(env, JObject::null())), "Passthrough(null)");
+        Ok(())
+    })
+}
+
+#[test]
+fn flattened_params_are_passed_separately_by_java() {
+    with_env(|env| {
+        assert_eq!(Connections::describe(env, "example.com".to_string(), 443, true)?, "https://example.com:443");
+        assert_eq!(Connections::describe(env, "localhost".to_string(), 8080, false)?, "http://localhost:8080");
+        Ok(())
+    })
+}
+
+#[test]
+fn boxed_round_trip() {
+    assert_round_trip!(Conversions::boxedIntIdentity, Boxed(0), Boxed(i32::MIN), Boxed(i32::MAX));
+    assert_round_trip!(Conversions::boxedCharIdentity, Boxed('a'), Boxed('\u{ffff}'));
+    assert_round_trip!(Conversions::boxedDoubleIdentity, Boxed(-1.5), Boxed(f64::INFINITY));
+    assert_round_trip!(Conversions::boxedLongVecIdentity, vec![], vec![Boxed(1), Boxed(i64::MIN)]);
+}
+
+#[test]
+fn reinterpreted_round_trip() {
+    assert_round_trip!(Conversions::reinterpretedIntIdentity, 0, -1, i32::MIN, i32::MAX);
+    assert_round_trip!(Conversions::reinterpretedByteIdentity, 0, -1, i8::MIN);
+    assert_round_trip!(Conversions::uncheckedReinterpretedLongIdentity, 0, -1, i64::MIN, i64::MAX);
+}
+
+#[test]
+fn reinterpreted_values_are_unsigned_in_rust() {
+    with_env(|env| {
+        assert_eq!(Conversions::describeReinterpretedInt(env, -1)?, "4294967295");
+        assert_eq!(Conversions::describeReinterpretedInt(env, i32::MIN)?, "2147483648");
+        Ok(())
+    })
+}
+
+#[test]
+fn saturated_and_truncated_values() {
+    with_env(|env| {
+        assert_eq!(Conversions::saturatedSum(env, 1, 2)?, 3);
+        assert_eq!(Conversions::saturatedSum(env, i32::MAX as i64, 1)?, i32::MAX);
+        assert_eq!(Conversions::saturatedSum(env, -3_000_000_000, 0)?, i32::MIN);
+        assert_eq!(Conversions::truncatedUnsignedLongIdentity(env, -1)?, -1);
+        assert_eq!(Conversions::truncatedUnsignedLongIdentity(env, i32::MIN)?, i32::MIN);
+        Ok(())
+    })
+}
+
+#[test]
+fn borrowed_string_round_trip() {
+    assert_round_trip!(
+        Conversions::borrowedStringIdentity,
+        String::new(),
+        "hello".to_string(),
+        "surrogate pairs: 𝄞 😀".to_string(),
+        "nul: \0".to_string()
+    );
+    assert_round_trip!(Conversions::uncheckedBorrowedStringIdentity, "unchecked: 𝄞".to_string());
+}
+
+#[test]
+fn borrowed_strings_are_utf8() {
+    with_env(|env| {
+        assert_eq!(Conversions::borrowedStringCharCount(env, "ascii".to_string())?, 5);
+        // Two surrogate pairs in Java, two characters in Rust
+        assert_eq!(Conversions::borrowedStringCharCount(env, "𝄞😀".to_string())?, 2);
+        Ok(())
+    })
+}
+
+#[test]
+fn borrowed_byte_arrays() {
+    with_env(|env| {
+        let sum = |data: JObject| -> JniResult<i64> {
+            env.call_static_method("com/robusta/tests/Conversions", "borrowedByteSum", "([B)J", &[data.into()])?.j()
+        };
+
+        assert_eq!(sum(JObject::from(env.byte_array_from_slice(&[])?))?, 0);
+        assert_eq!(sum(JObject::from(env.byte_array_from_slice(&[1, 2, 255])?))?, 258);
+        assert_eq!(sum(JObject::from(env.byte_array_from_slice(&[0x80; 4096])?))?, 0x80 * 4096);
+
+        assert!(sum(JObject::null()).is_err());
+        let (class, _) = take_exception(env)?.expect("null array should throw");
+        assert_eq!(class, "java.lang.RuntimeException");
+        Ok(())
+    })
+}
+
+#[test]
+fn unchecked_round_trip() {
+    assert_round_trip!(Conversions::uncheckedIntIdentity, 0, i32::MIN);
+    assert_round_trip!(Conversions::uncheckedStringIdentity, "unchecked".to_string());
+}
+
+#[test]
+fn option_round_trip() {
+    assert_round_trip!(Conversions::optionalIntIdentity, None, Some(0), Some(i32::MIN));
+    assert_round_trip!(Conversions::optionalBoolIdentity, None, Some(true), Some(false));
+    assert_round_trip!(Conversions::optionalCharIdentity, None, Some('è'));
+    assert_round_trip!(Conversions::optionalStringIdentity, None, Some(String::new()), Some("some".to_string()));
+    assert_round_trip!(Conversions::optionalIntVecIdentity, None, Some(vec![]), Some(vec![1, 2, 3]));
+    assert_round_trip!(
+        Conversions::optionalStringVecIdentity,
+        vec![],
+        vec![Some("a".to_string()), None, Some("c".to_string())]
+    );
+}
+
+#[test]
+fn unchecked_option_round_trip() {
+    assert_round_trip!(Conversions::uncheckedOptionalLongIdentity, None, Some(i64::MAX));
+    assert_round_trip!(Conversions::uncheckedOptionalStringIdentity, None, Some("unchecked".to_string()));
+}
@@ -0,0 +1,40 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+
+use crate::jni::Converters;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Converters;
+
+    impl Converters {
+        pub extern "java" fn reverseHex(env: JNIEnv, hex: String) -> JniResult<String> {}
+        pub extern "java" fn hexLength(env: JNIEnv, hex: String) -> JniResult<i32> {}
+    }
+}
+
+#[test]
+fn params_and_return_values_are_converted_by_their_functions() {
+    with_env(|env| {
+        assert_eq!(Converters::reverseHex(env, "0a1b2c".into())?, "2c1b0a");
+        assert_eq!(Converters::reverseHex(env, "".into())?, "");
+        assert_eq!(Converters::hexLength(env, "deadbeef".into())?, 4);
+        Ok(())
+    })
+}
+
+#[test]
+fn conversion_function_errors_throw() {
+    with_env(|env| {
+        assert!(Converters::reverseHex(env, "zz".into()).is_err());
+
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert!(message.unwrap().contains("invalid digit"));
+        Ok(())
+    })
+}
@@ -0,0 +1,99 @@
+//! Calls of the Kotlin suspend functions in `kotlin/`.
+//!
+//! These tests need `kotlinc` (on the path, or in `$KOTLIN_HOME/bin`) and `ROBUSTA_KOTLIN_CLASSPATH` set to the class path of
+//! the Kotlin standard library and `kotlinx-coroutines-core-jvm`, and are ignored by default:
+//!
+//! ```text
+//! ROBUSTA_KOTLIN_CLASSPATH=kotlin-stdlib.jar:kotlinx-coroutines-core-jvm.jar cargo test --test coroutines -- --ignored
+//! ```
+
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+
+use crate::jni::Suspending;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Suspending<'env> {
+        pub raw: JObject<'env>,
+    }
+
+    impl Signature for Suspending<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Suspending;";
+    }
+
+    impl Signature for &Suspending<'_> {
+        const SIG_TYPE: &'static str = <Suspending as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Suspending<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Suspending<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Suspending { raw: s })
+        }
+    }
+
+    impl<'env> Suspending<'env> {
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>, greeting: String) -> JniResult<Self> {}
+
+        #[suspend]
+        pub extern "java" fn greet(&self, env: JNIEnv<'env>, name: Option<String>) -> JniResult<String> {}
+
+        #[suspend]
+        pub extern "java" fn fail(&self, env: JNIEnv<'env>, message: String) -> JniResult<String> {}
+
+        #[suspend]
+        pub extern "java" fn answer(env: JNIEnv<'env>, offset: i32) -> JniResult<i32> {}
+    }
+}
+
+#[test]
+#[ignore = "needs kotlinc and ROBUSTA_KOTLIN_CLASSPATH, see the module documentation"]
+fn suspend_functions_return_their_result() {
+    with_env(|env| {
+        let suspending = Suspending::new(env, "Hello".into())?;
+        assert_eq!(suspending.greet(env, Some("Rust".into()))?, "Hello, Rust");
+        assert_eq!(Suspending::answer(env, 1)?, 43);
+        Ok(())
+    })
+}
+
+#[test]
+#[ignore = "needs kotlinc and ROBUSTA_KOTLIN_CLASSPATH, see the module documentation"]
+fn none_is_passed_as_null() {
+    with_env(|env| {
+        let suspending = Suspending::new(env, "Hello".into())?;
+        assert_eq!(suspending.greet(env, None)?, "Hello, stranger");
+        Ok(())
+    })
+}
+
+#[test]
+#[ignore = "needs kotlinc and ROBUSTA_KOTLIN_CLASSPATH, see the module documentation"]
+fn exceptions_are_thrown_to_the_caller() {
+    with_env(|env| {
+        let suspending = Suspending::new(env, "Hello".into())?;
+        assert!(suspending.fail(env, "not now".into()).is_err());
+        assert_eq!(
+            take_exception(env)?,
+            Some(("java.lang.IllegalStateException".to_string(), Some("not now".to_string())))
+        );
+        Ok(())
+    })
+}
@@ -0,0 +1,34 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+
+use crate::jni::Counted;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Counted;
+
+    impl Counted {
+        pub extern "java" fn add(env: JNIEnv, a: i32, b: i32) -> JniResult<i32> {}
+
+        pub extern "java" fn ping(env: JNIEnv) -> JniResult<()> {}
+
+        pub extern "java" fn calls(env: JNIEnv) -> JniResult<i64> {}
+    }
+}
+
+#[test]
+fn wrapped_exported_methods_count_calls() {
+    with_env(|env| {
+        assert_eq!(Counted::add(env, 2, 3)?, 5);
+        Counted::ping(env)?;
+        Counted::ping(env)?;
+
+        // The counter is incremented before the body runs, so `calls` counts itself
+        assert_eq!(Counted::calls(env)?, 4);
+        Ok(())
+    })
+}
@@ -0,0 +1,94 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::{JObject, JValue};
+use robusta_jni::jni::sys::jbyteArray;
+use robusta_jni::jni::JNIEnv;
+
+const CLASS: &str = "com/robusta/tests/CriticalArrays";
+
+fn byte_array(env: JNIEnv, bytes: &[i8]) -> JniResult<jbyteArray> {
+    let array = env.new_byte_array(bytes.len() as i32)?;
+    env.set_byte_array_region(array, 0, bytes)?;
+    Ok(array)
+}
+
+fn contents(env: JNIEnv, array: jbyteArray) -> JniResult<Vec<i8>> {
+    let mut bytes = vec![0; env.get_array_length(array)? as usize];
+    env.get_byte_array_region(array, 0, &mut bytes)?;
+    Ok(bytes)
+}
+
+fn sum(env: JNIEnv, name: &str, array: jbyteArray) -> JniResult<i64> {
+    env.call_static_method(CLASS, name, "([B)J", &[JValue::Object(JObject::from(array))])?.j()
+}
+
+fn fill(env: JNIEnv, name: &str, value: i8, array: jbyteArray) -> JniResult<()> {
+    env.call_static_method(CLASS, name, "(B[B)V", &[JValue::Byte(value), JValue::Object(JObject::from(array))])?;
+    Ok(())
+}
+
+#[test]
+fn critical_bytes_are_read() {
+    with_env(|env| {
+        let array = byte_array(env, &[1, 2, -1, 127])?;
+        assert_eq!(sum(env, "sum", array)?, 1 + 2 + 255 + 127);
+        assert_eq!(sum(env, "uncheckedSum", array)?, 1 + 2 + 255 + 127);
+        assert_eq!(sum(env, "copiedSum", array)?, 1 + 2 + 255 + 127);
+        assert_eq!(sum(env, "sum", byte_array(env, &[])?)?, 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn mutations_are_visible_to_java() {
+    with_env(|env| {
+        let array = byte_array(env, &[0; 16])?;
+        fill(env, "fill", -2, array)?;
+        assert_eq!(contents(env, array)?, [-2; 16]);
+        Ok(())
+    })
+}
+
+#[test]
+fn region_is_left_on_drop_and_panic() {
+    with_env(|env| {
+        let array = byte_array(env, &[0; 4])?;
+        let region_lasts = env.call_static_method(CLASS, "regionLasts", "([B)Z", &[JValue::Object(JObject::from(array))])?.z()?;
+        assert!(region_lasts);
+
+        // The array is released while unwinding, with the changes made before the panic
+        assert!(fill(env, "fillThenPanic", 7, array).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert_eq!(message.as_deref(), Some("panicked inside the critical region"));
+        assert_eq!(contents(env, array)?, [7; 4]);
+
+        assert!(!env.call_static_method(CLASS, "inCriticalRegion", "()Z", &[])?.z()?);
+        Ok(())
+    })
+}
+
+#[test]
+fn calls_into_java_inside_the_region_are_reported() {
+    with_env(|env| {
+        let array = byte_array(env, &[0; 4])?;
+        let result = env.call_static_method(CLASS, "callJavaInside", "([B)Z", &[JValue::Object(JObject::from(array))]);
+        assert!(result.is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert_eq!(
+            message.as_deref(),
+            Some("called into the JVM while a `CriticalBytes` is alive on this thread; drop it first")
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn null_arrays_are_rejected() {
+    with_env(|env| {
+        assert!(sum(env, "sum", JObject::null().into_inner()).is_err());
+        assert!(take_exception(env)?.is_some());
+        Ok(())
+    })
+}
@@ -0,0 +1,52 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+use robusta_jni::convert::JOption;
+
+use crate::jni::Defaults;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::JOption;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Defaults;
+
+    impl Defaults {
+        pub extern "java" fn orDefault(env: JNIEnv, v: i32, fallback: JOption<i32>) -> JniResult<String> {}
+        pub extern "java" fn join(env: JNIEnv, #[java_list] values: Vec<String>, separator: Option<String>) -> JniResult<String> {}
+        pub extern "java" fn uncheckedOrDefault(env: JNIEnv, v: i64, fallback: JOption<i64>) -> JniResult<i64> {}
+
+        // Overloads of the generated Java stubs, without the default parameters
+        #[java_name = "orDefault"]
+        pub extern "java" fn or_default_overload(env: JNIEnv, v: i32) -> JniResult<String> {}
+        #[java_name = "join"]
+        pub extern "java" fn join_overload(env: JNIEnv, #[java_list] values: Vec<String>) -> JniResult<String> {}
+    }
+}
+
+#[test]
+fn null_arguments_take_the_default_value() {
+    with_env(|env| {
+        assert_eq!(Defaults::orDefault(env, 1, JOption::Some(2))?, "1 2");
+        assert_eq!(Defaults::orDefault(env, 1, JOption::None)?, "1 100");
+
+        let values = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(Defaults::join(env, values.clone(), Some("-".into()))?, "a-b");
+        assert_eq!(Defaults::join(env, values, None)?, "a, b");
+
+        assert_eq!(Defaults::uncheckedOrDefault(env, 1, JOption::Some(2))?, 3);
+        assert_eq!(Defaults::uncheckedOrDefault(env, 1, JOption::None)?, 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn stub_overloads_pass_null_for_default_parameters() {
+    with_env(|env| {
+        assert_eq!(Defaults::or_default_overload(env, 1)?, "1 100");
+        assert_eq!(Defaults::join_overload(env, vec!["a".to_string(), "b".to_string()])?, "a, b");
+        Ok(())
+    })
+}
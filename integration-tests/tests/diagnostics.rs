@@ -0,0 +1,8 @@
+//! Errors reported by `#[bridge]` for modules it can't generate bindings for.
+
+#[test]
+fn bridge_diagnostics() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+    t.pass("tests/ui/pass/*.rs");
+}
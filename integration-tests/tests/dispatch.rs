@@ -0,0 +1,126 @@
+use std::thread;
+
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+
+use crate::jni::SwingCalls;
+
+// Swing names its (single) event dispatch thread `AWT-EventQueue-0`
+const EVENT_DISPATCH_THREAD: &str = "AWT-EventQueue-";
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct SwingCalls<'env> {
+        pub raw: JObject<'env>,
+    }
+
+    impl Signature for SwingCalls<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/SwingCalls;";
+    }
+
+    impl Signature for &SwingCalls<'_> {
+        const SIG_TYPE: &'static str = <SwingCalls as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &SwingCalls<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for SwingCalls<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(SwingCalls { raw: s })
+        }
+    }
+
+    impl<'env> SwingCalls<'env> {
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>, label: String) -> JniResult<Self> {}
+
+        pub extern "java" fn thread_name(env: JNIEnv<'env>) -> JniResult<String> {}
+
+        #[dispatch(swing)]
+        #[java_name = "threadName"]
+        pub extern "java" fn dispatched_thread_name(env: JNIEnv<'env>) -> JniResult<String> {}
+
+        #[dispatch(swing)]
+        #[call_type(unchecked)]
+        #[java_name = "threadName"]
+        pub extern "java" fn dispatched_thread_name_unchecked(env: JNIEnv<'env>) -> String {}
+
+        #[dispatch(swing)]
+        pub extern "java" fn label(&self, env: JNIEnv<'env>) -> JniResult<String> {}
+
+        #[dispatch(swing)]
+        pub extern "java" fn add(env: JNIEnv<'env>, a: i32, b: i64) -> JniResult<i64> {}
+
+        #[dispatch(swing)]
+        pub extern "java" fn fail(env: JNIEnv<'env>, message: String) -> JniResult<()> {}
+
+        pub extern "java" fn dispatched_from_event_dispatch_thread(env: JNIEnv<'env>) -> JniResult<String> {}
+    }
+}
+
+#[test]
+fn dispatched_methods_run_on_event_dispatch_thread() {
+    with_env(|env| {
+        assert!(!SwingCalls::thread_name(env)?.starts_with(EVENT_DISPATCH_THREAD));
+        assert!(SwingCalls::dispatched_thread_name(env)?.starts_with(EVENT_DISPATCH_THREAD));
+        assert!(SwingCalls::dispatched_thread_name_unchecked(env).starts_with(EVENT_DISPATCH_THREAD));
+
+        let calls = SwingCalls::new(env, "status".into())?;
+        assert!(calls.label(env)?.starts_with(&format!("status on {}", EVENT_DISPATCH_THREAD)));
+        Ok(())
+    })
+}
+
+#[test]
+fn primitives_are_passed_and_returned() {
+    with_env(|env| {
+        assert_eq!(SwingCalls::add(env, 2, 40)?, 42);
+        assert_eq!(SwingCalls::add(env, i32::MIN, i64::MAX)?, i64::MAX + i32::MIN as i64);
+        Ok(())
+    })
+}
+
+#[test]
+fn exceptions_are_thrown_in_calling_thread() {
+    with_env(|env| {
+        assert!(SwingCalls::fail(env, "not now".into()).is_err());
+        assert_eq!(
+            take_exception(env)?,
+            Some(("java.lang.IllegalStateException".to_string(), Some("not now".to_string())))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn calls_from_event_dispatch_thread_are_direct() {
+    with_env(|env| {
+        assert!(SwingCalls::dispatched_from_event_dispatch_thread(env)?.starts_with(EVENT_DISPATCH_THREAD));
+        Ok(())
+    })
+}
+
+#[test]
+fn calls_from_many_threads_are_dispatched() {
+    let threads: Vec<_> = (0..8)
+        .map(|i| thread::spawn(move || with_env(|env| SwingCalls::add(env, i, 1))))
+        .collect();
+
+    for (i, thread) in threads.into_iter().enumerate() {
+        assert_eq!(thread.join().unwrap(), i as i64 + 1);
+    }
+}
@@ -0,0 +1,75 @@
+//! Modules checked with `#[bridge(dry_run)]` are left as plain Rust, even with constructs `#[bridge]` rejects.
+
+use robusta_jni::bridge;
+use robusta_jni::convert::JavaArray;
+
+#[bridge(dry_run)]
+#[allow(deprecated)]
+mod jni {
+    use std::marker::PhantomData;
+
+    use robusta_jni::convert::JavaArray;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.example)]
+    pub struct Checked;
+
+    impl Checked {
+        pub extern "jni" fn shout(text: Option<&str>) -> String {
+            text.unwrap_or_default().to_uppercase()
+        }
+
+        #[java_name = "sum"]
+        pub extern "jni" fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        pub extern "jni" fn sum(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        pub extern "jni" fn cells(rows: JavaArray<Vec<i32>>) -> i32 {
+            rows.0.iter().map(Vec::len).sum::<usize>() as i32
+        }
+
+        pub extern "java" fn describe(env: &JNIEnv, id: i32) -> JniResult<String> {}
+    }
+
+    pub struct Unpackaged;
+
+    impl Unpackaged {
+        pub extern "jni" fn answer() -> i32 {
+            42
+        }
+    }
+
+    #[package(com.example)]
+    pub struct Borrowed<'a> {
+        pub marker: PhantomData<&'a ()>,
+    }
+
+    impl<'a> Borrowed<'a> {
+        pub extern "jni" fn size(self) -> i32 {
+            0
+        }
+    }
+}
+
+#[test]
+fn methods_stay_plain_rust() {
+    assert_eq!(jni::Checked::shout(Some("robusta")), "ROBUSTA");
+    assert_eq!(jni::Checked::add(1, 2), 3);
+    assert_eq!(jni::Checked::sum(3, 4), 7);
+    assert_eq!(jni::Checked::cells(JavaArray(vec![vec![1, 2], vec![3]])), 3);
+    assert_eq!(jni::Unpackaged::answer(), 42);
+    assert_eq!(jni::Borrowed { marker: Default::default() }.size(), 0);
+}
+
+#[test]
+fn no_native_functions_are_exported() {
+    // Built at runtime, so that the test binary only contains the symbol if it's exported
+    let symbol = ["Java", "com", "example", "Checked", "shout"].join("_");
+    let binary = std::fs::read(std::env::current_exe().unwrap()).unwrap();
+    assert!(!binary.windows(symbol.len()).any(|w| w == symbol.as_bytes()));
+}
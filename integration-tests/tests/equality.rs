@@ -0,0 +1,96 @@
+use std::collections::{HashMap, HashSet};
+
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::convert::{ByJavaEquality, ByJavaString};
+use robusta_jni::jni::errors::{Error, Result as JniResult};
+use robusta_jni::jni::objects::{JObject, JValue};
+use robusta_jni::jni::JNIEnv;
+
+use crate::jni::Keys;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{ByJavaEquality, ByJavaString};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Keys;
+
+    impl Keys {
+        pub extern "java" fn distinctCount<'env>(env: JNIEnv<'env>, #[java_list] values: Vec<ByJavaEquality<'env>>) -> JniResult<i32> {}
+        pub extern "java" fn distinctStringCount(env: JNIEnv, #[java_list] values: Vec<ByJavaString>) -> JniResult<i32> {}
+    }
+}
+
+fn uuid<'env>(env: JNIEnv<'env>, value: &str) -> JniResult<JObject<'env>> {
+    let value = env.new_string(value)?;
+    env.call_static_method("java/util/UUID", "fromString", "(Ljava/lang/String;)Ljava/util/UUID;", &[JValue::Object(value.into())])?
+        .l()
+}
+
+const FIRST: &str = "123e4567-e89b-12d3-a456-426614174000";
+const SECOND: &str = "00000000-0000-0000-0000-000000000001";
+
+#[test]
+fn equal_objects_are_the_same_key() {
+    with_env(|env| {
+        let (first, copy) = (uuid(env, FIRST)?, uuid(env, FIRST)?);
+        assert!(!env.is_same_object(first, copy)?);
+
+        let mut names = HashMap::new();
+        names.insert(ByJavaEquality::new(env, first)?, "first");
+        names.insert(ByJavaEquality::new(env, copy)?, "copy");
+        names.insert(ByJavaEquality::new(env, uuid(env, SECOND)?)?, "second");
+
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[&ByJavaEquality::new(env, uuid(env, FIRST)?)?], "copy");
+        Ok(())
+    })
+}
+
+#[test]
+fn keys_are_deduplicated_by_exported_methods() {
+    with_env(|env| {
+        let values = [uuid(env, FIRST)?, uuid(env, SECOND)?, uuid(env, FIRST)?];
+
+        let keys = values.iter().map(|v| ByJavaEquality::new(env, *v)).collect::<JniResult<Vec<_>>>()?;
+        assert_eq!(Keys::distinctCount(env, keys)?, 2);
+
+        let keys = values.iter().map(|v| ByJavaString::new(env, *v)).collect::<JniResult<Vec<_>>>()?;
+        assert_eq!(Keys::distinctStringCount(env, keys)?, 2);
+        Ok(())
+    })
+}
+
+#[test]
+fn string_keys_outlive_the_call() {
+    let keys: HashSet<ByJavaString> = with_env(|env| {
+        [FIRST, SECOND, FIRST].iter().map(|v| ByJavaString::new(env, uuid(env, v)?)).collect()
+    });
+
+    assert_eq!(keys.len(), 2);
+    assert!(keys.iter().any(|k| k.key() == SECOND));
+}
+
+#[test]
+fn exceptions_thrown_by_equals_are_returned() {
+    with_env(|env| {
+        let new_key = || env.new_object("com/robusta/tests/IncomparableKey", "()V", &[]);
+        let (first, second) = (ByJavaEquality::new(env, new_key()?)?, ByJavaEquality::new(env, new_key()?)?);
+
+        assert!(matches!(first.java_equals(&second), Err(Error::JavaException)));
+        assert_eq!(take_exception(env)?.map(|(class, _)| class).as_deref(), Some("java.lang.IllegalStateException"));
+        Ok(())
+    })
+}
+
+#[test]
+fn null_objects_are_rejected() {
+    with_env(|env| {
+        assert!(matches!(ByJavaEquality::new(env, JObject::null()), Err(Error::NullPtr(_))));
+        assert!(matches!(ByJavaString::new(env, JObject::null()), Err(Error::NullPtr(_))));
+        Ok(())
+    })
+}
@@ -0,0 +1,82 @@
+use integration_tests::fixtures::error_codes::jni::Status;
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::convert::ErrorCode;
+use robusta_jni::jni::objects::{JString, JValue};
+
+use crate::jni::ErrorCodes;
+
+#[bridge]
+mod jni {
+    use integration_tests::fixtures::error_codes::jni::Status;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct ErrorCodes;
+
+    impl ErrorCodes {
+        pub extern "java" fn echo(env: JNIEnv, status: Status) -> JniResult<Status> {}
+        #[java_name = "echo"]
+        pub extern "java" fn echoCode(env: JNIEnv, code: i32) -> JniResult<i32> {}
+        pub extern "java" fn uncheckedEcho(env: JNIEnv, code: i32) -> JniResult<i32> {}
+        pub extern "java" fn rustName(env: JNIEnv, code: i32) -> JniResult<Option<String>> {}
+    }
+}
+
+#[test]
+fn error_codes_round_trip() {
+    with_env(|env| {
+        for status in [Status::Success, Status::NotFound, Status::PermissionDenied] {
+            assert_eq!(ErrorCodes::echo(env, status)?, status);
+        }
+        assert_eq!(ErrorCodes::echoCode(env, -13)?, -13);
+        Ok(())
+    })
+}
+
+#[test]
+fn unknown_codes_are_rejected() {
+    with_env(|env| {
+        assert!(ErrorCodes::echoCode(env, 42).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.IllegalArgumentException");
+        assert_eq!(message.as_deref(), Some("42 is not a known `Status` code"));
+
+        assert!(ErrorCodes::uncheckedEcho(env, 1).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert_eq!(message.as_deref(), Some("1 is not a known `Status` code"));
+
+        assert_eq!(Status::from_code(42), None);
+        assert_eq!(Status::from_code(2), Some(Status::NotFound));
+        Ok(())
+    })
+}
+
+#[test]
+fn names_are_looked_up_from_both_sides() {
+    with_env(|env| {
+        let java_name = |code: i32| -> robusta_jni::jni::errors::Result<Option<String>> {
+            let name = env
+                .call_static_method("com/robusta/tests/Status", "nameOf", "(I)Ljava/lang/String;", &[JValue::Int(code)])?
+                .l()?;
+            if name.is_null() {
+                return Ok(None);
+            }
+
+            Ok(Some(env.get_string(JString::from(name))?.into()))
+        };
+
+        assert_eq!(java_name(-13)?.as_deref(), Some("PermissionDenied"));
+        assert_eq!(java_name(42)?, None);
+        assert_eq!(ErrorCodes::rustName(env, 2)?.as_deref(), Some("NotFound"));
+        assert_eq!(ErrorCodes::rustName(env, 42)?, None);
+        assert_eq!(Status::NotFound.name(), "NotFound");
+
+        // Constants of the stub match the discriminants
+        let constant = env.get_static_field("com/robusta/tests/Status", "PERMISSION_DENIED", "I")?.i()?;
+        assert_eq!(constant, Status::PermissionDenied.code());
+        Ok(())
+    })
+}
@@ -0,0 +1,284 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::jni::errors::Result as JniResult;
+
+use crate::jni::{Errors, Greeter};
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Errors;
+
+    impl Errors {
+        pub extern "java" fn failing(env: JNIEnv) -> JniResult<i32> {}
+        pub extern "java" fn checkedChar(env: JNIEnv, c: u16) -> JniResult<u16> {}
+        pub extern "java" fn plainChar(env: JNIEnv, c: u16) -> JniResult<u16> {}
+        pub extern "java" fn checkedUnsigned(env: JNIEnv, v: i64) -> JniResult<i64> {}
+        pub extern "java" fn greeting(env: JNIEnv, name: String) -> JniResult<String> {}
+        pub extern "java" fn checkPositive(env: JNIEnv, v: i32) -> JniResult<()> {}
+        pub extern "java" fn requireName(env: JNIEnv, name: String) -> JniResult<()> {}
+        pub extern "java" fn missingExceptionClass(env: JNIEnv) -> JniResult<i32> {}
+        pub extern "java" fn load(env: JNIEnv, key: String) -> JniResult<String> {}
+        pub extern "java" fn loadWithMessage(env: JNIEnv, key: String) -> JniResult<String> {}
+        pub extern "java" fn throwingManually(env: JNIEnv, v: i32) -> JniResult<i32> {}
+        pub extern "java" fn uncheckedThrowingManually(env: JNIEnv, v: i32) -> JniResult<i32> {}
+        pub extern "java" fn panicking(env: JNIEnv) -> JniResult<i32> {}
+        pub extern "java" fn panickingWithValue(env: JNIEnv, v: i32) -> JniResult<String> {}
+        pub extern "java" fn panickingWithoutMessage(env: JNIEnv) -> JniResult<()> {}
+        pub extern "java" fn uncheckedPanicking(env: JNIEnv) -> JniResult<i32> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct Greeter;
+
+    impl Greeter {
+        pub extern "java" fn failing(env: JNIEnv, message: String) -> JniResult<i32> {}
+    }
+}
+
+#[test]
+fn returned_error_throws_runtime_exception() {
+    with_env(|env| {
+        assert!(Errors::failing(env).is_err());
+
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert!(message.unwrap().contains("failing fixture"));
+        Ok(())
+    })
+}
+
+#[test]
+fn conversion_error_throws_configured_exception() {
+    with_env(|env| {
+        assert_eq!(Errors::checkedChar(env, 'a' as u16)?, 'a' as u16);
+        // A lone surrogate isn't a valid `char`
+        assert!(Errors::checkedChar(env, 0xd800).is_err());
+
+        let exception = take_exception(env)?;
+        assert_eq!(
+            exception,
+            Some((
+                "java.lang.IllegalArgumentException".to_string(),
+                Some("invalid char value".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn conversion_error_throws_its_display_output() {
+    with_env(|env| {
+        assert!(Errors::plainChar(env, 0xd800).is_err());
+
+        let exception = take_exception(env)?;
+        assert_eq!(
+            exception,
+            Some((
+                "java.lang.RuntimeException".to_string(),
+                Some("Invalid JValue type cast: char. Actual type: jchar".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn out_of_range_exception_is_not_replaced_by_configured_one() {
+    with_env(|env| {
+        assert_eq!(Errors::checkedUnsigned(env, 42)?, 42);
+        assert!(Errors::checkedUnsigned(env, -1).is_err());
+
+        let exception = take_exception(env)?;
+        assert_eq!(
+            exception,
+            Some((
+                "java.lang.IllegalArgumentException".to_string(),
+                Some("-1 is out of the range of u32".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn returned_err_throws_its_message() {
+    with_env(|env| {
+        assert_eq!(Errors::greeting(env, "Rust".into())?, "Hello, Rust!");
+        assert!(Errors::greeting(env, "".into()).is_err());
+
+        let exception = take_exception(env)?;
+        assert_eq!(
+            exception,
+            Some((
+                "java.lang.RuntimeException".to_string(),
+                Some("name must not be empty".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn returned_unit_err_throws_configured_exception() {
+    with_env(|env| {
+        Errors::checkPositive(env, 1)?;
+        assert!(take_exception(env)?.is_none());
+        assert!(Errors::checkPositive(env, -1).is_err());
+
+        let exception = take_exception(env)?;
+        assert_eq!(
+            exception,
+            Some((
+                "java.lang.IllegalArgumentException".to_string(),
+                Some("-1 is negative".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn returned_unit_err_throws_its_message() {
+    with_env(|env| {
+        Errors::requireName(env, "Rust".into())?;
+        assert!(take_exception(env)?.is_none());
+        assert!(Errors::requireName(env, "".into()).is_err());
+
+        let exception = take_exception(env)?;
+        assert_eq!(
+            exception,
+            Some((
+                "java.lang.RuntimeException".to_string(),
+                Some("name must not be empty".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn errors_throw_the_exception_class_of_their_variant() {
+    with_env(|env| {
+        let expect_exception = |key: &str, class: &str, message: &str| -> JniResult<()> {
+            assert!(Errors::load(env, key.into()).is_err());
+            let exception = take_exception(env)?;
+            assert_eq!(exception, Some((class.to_string(), Some(message.to_string()))), "for key `{}`", key);
+            Ok(())
+        };
+
+        expect_exception("missing", "java.util.NoSuchElementException", "no value for `missing`")?;
+        expect_exception("secret", "java.lang.SecurityException", "permission denied")?;
+        expect_exception("file", "java.io.IOException", "can't read the repository: no such file")?;
+        expect_exception("corrupted", "java.lang.IllegalStateException", "corrupted repository")?;
+
+        assert_eq!(Errors::load(env, "name".into())?, "robusta");
+        assert!(take_exception(env)?.is_none());
+        Ok(())
+    })
+}
+
+#[test]
+fn missing_class_of_an_error_falls_back_to_runtime_exception() {
+    with_env(|env| {
+        assert!(Errors::load(env, "unsupported".into()).is_err());
+
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        let message = message.unwrap();
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert!(message.starts_with("unsupported key"), "unexpected message: {}", message);
+        assert!(message.contains("com.robusta.tests.MissingException"), "unexpected message: {}", message);
+        Ok(())
+    })
+}
+
+#[test]
+fn configured_message_replaces_the_message_of_typed_errors() {
+    with_env(|env| {
+        assert!(Errors::loadWithMessage(env, "missing".into()).is_err());
+
+        let exception = take_exception(env)?;
+        assert_eq!(
+            exception,
+            Some((
+                "java.util.NoSuchElementException".to_string(),
+                Some("can't load the value".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn java_exception_is_returned_as_error() {
+    with_env(|env| {
+        assert!(Greeter::failing(env, "from Java".into()).is_err());
+
+        let exception = take_exception(env)?;
+        assert_eq!(
+            exception,
+            Some((
+                "java.lang.IllegalStateException".to_string(),
+                Some("from Java".to_string())
+            ))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn exception_thrown_through_env_param_is_kept() {
+    with_env(|env| {
+        let expect_arithmetic = || -> JniResult<()> {
+            let exception = take_exception(env)?;
+            assert_eq!(exception, Some(("java.lang.ArithmeticException".to_string(), Some("-4 has no square root".to_string()))));
+            Ok(())
+        };
+
+        assert_eq!(Errors::throwingManually(env, 4)?, 4);
+        assert!(Errors::throwingManually(env, -4).is_err());
+        expect_arithmetic()?;
+
+        assert_eq!(Errors::uncheckedThrowingManually(env, 4)?, 4);
+        assert!(Errors::uncheckedThrowingManually(env, -4).is_err());
+        expect_arithmetic()
+    })
+}
+
+#[test]
+fn missing_exception_class_falls_back_to_runtime_exception() {
+    with_env(|env| {
+        assert!(Errors::missingExceptionClass(env).is_err());
+
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        let message = message.unwrap();
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert!(message.starts_with("original error"), "unexpected message: {}", message);
+        assert!(message.contains("com.robusta.tests.MissingException"), "unexpected message: {}", message);
+        Ok(())
+    })
+}
+
+#[test]
+fn panic_throws_runtime_exception() {
+    with_env(|env| {
+        let expect_panic = |message: &str| -> JniResult<()> {
+            let exception = take_exception(env)?;
+            assert_eq!(exception, Some(("java.lang.RuntimeException".to_string(), Some(message.to_string()))));
+            Ok(())
+        };
+
+        assert!(Errors::panicking(env).is_err());
+        expect_panic("boom")?;
+        assert!(Errors::panickingWithValue(env, 42).is_err());
+        expect_panic("boom 42")?;
+        assert!(Errors::panickingWithoutMessage(env).is_err());
+        expect_panic("native method panicked")?;
+        assert!(Errors::uncheckedPanicking(env).is_err());
+        expect_panic("unchecked boom")
+    })
+}
@@ -0,0 +1,106 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+
+use crate::jni::Counter;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Counter<'env> {
+        raw: JObject<'env>,
+    }
+
+    impl Signature for Counter<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Counter;";
+    }
+
+    impl Signature for &Counter<'_> {
+        const SIG_TYPE: &'static str = <Counter as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Counter<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> IntoJavaValue<'env> for &Counter<'env> {
+        type Target = JObject<'env>;
+
+        fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+            self.raw
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Counter<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Counter { raw: s })
+        }
+    }
+
+    impl<'env> Counter<'env> {
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>, count: i32) -> JniResult<Self> {}
+
+        #[field]
+        pub extern "java" fn get_count(&self, env: JNIEnv<'env>) -> JniResult<i32> {}
+
+        #[field]
+        pub extern "java" fn set_count(&self, env: JNIEnv<'env>, value: i32) -> JniResult<()> {}
+
+        #[field(name = "name")]
+        pub extern "java" fn display_name(&self, env: JNIEnv<'env>) -> JniResult<String> {}
+
+        #[field(name = "name")]
+        #[call_type(unchecked)]
+        pub extern "java" fn set_display_name(&self, env: JNIEnv<'env>, value: String) {}
+
+        #[field(static)]
+        pub extern "java" fn get_created(env: JNIEnv<'env>) -> JniResult<i32> {}
+
+        #[field(static, name = "label")]
+        pub extern "java" fn label(env: JNIEnv<'env>) -> JniResult<String> {}
+
+        #[field(static, name = "label")]
+        pub extern "java" fn set_label(env: JNIEnv<'env>, value: String) -> JniResult<()> {}
+    }
+}
+
+#[test]
+fn instance_fields_round_trip() {
+    with_env(|env| {
+        let counter = Counter::new(env, 3)?;
+        assert_eq!(counter.get_count(env)?, 3);
+
+        counter.set_count(env, -7)?;
+        assert_eq!(counter.get_count(env)?, -7);
+
+        assert_eq!(counter.display_name(env)?, "unnamed");
+        counter.set_display_name(env, "clicks".into());
+        assert_eq!(counter.display_name(env)?, "clicks");
+        Ok(())
+    })
+}
+
+#[test]
+fn static_fields_round_trip() {
+    with_env(|env| {
+        let before = Counter::get_created(env)?;
+        Counter::new(env, 0)?;
+        assert_eq!(Counter::get_created(env)?, before + 1);
+
+        assert_eq!(Counter::label(env)?, "counter");
+        Counter::set_label(env, "renamed".into())?;
+        assert_eq!(Counter::label(env)?, "renamed");
+        Ok(())
+    })
+}
@@ -0,0 +1,56 @@
+use integration_tests::fixtures::generics::jni::Holder as ExportedHolder;
+use integration_tests::with_env;
+use robusta_jni::bridge;
+use robusta_jni::convert::Signature;
+
+use crate::jni::Holder;
+
+#[bridge]
+mod jni {
+    use std::marker::PhantomData;
+
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    #[instantiate(OfString = Holder<String>, OfLong = Holder<i64>)]
+    pub struct Holder<T>(PhantomData<T>);
+
+    impl Holder<String> {
+        pub extern "java" fn describe(env: JNIEnv, value: String) -> JniResult<String> {}
+        pub extern "java" fn identity(env: JNIEnv, value: String) -> JniResult<String> {}
+        pub extern "java" fn length(env: JNIEnv, value: String) -> JniResult<i32> {}
+    }
+
+    impl Holder<i64> {
+        pub extern "java" fn describe(env: JNIEnv, value: i64) -> JniResult<String> {}
+        pub extern "java" fn identity(env: JNIEnv, value: i64) -> JniResult<i64> {}
+        pub extern "java" fn twice(env: JNIEnv, value: i64) -> JniResult<i64> {}
+    }
+}
+
+#[test]
+fn each_instantiation_is_a_class() {
+    assert_eq!(<ExportedHolder<String> as Signature>::SIG_TYPE, "Lcom/robusta/tests/HolderOfString;");
+    assert_eq!(<ExportedHolder<i64> as Signature>::SIG_TYPE, "Lcom/robusta/tests/HolderOfLong;");
+}
+
+#[test]
+fn generic_methods_are_exported_for_each_instantiation() {
+    with_env(|env| {
+        assert_eq!(Holder::<String>::describe(env, "robusta".into())?, "\"robusta\"");
+        assert_eq!(Holder::<i64>::describe(env, 42)?, "42");
+        assert_eq!(Holder::<String>::identity(env, "robusta".into())?, "robusta");
+        assert_eq!(Holder::<i64>::identity(env, i64::MIN)?, i64::MIN);
+        Ok(())
+    })
+}
+
+#[test]
+fn instantiation_methods_are_exported_to_their_class() {
+    with_env(|env| {
+        assert_eq!(Holder::<String>::length(env, "héllo".into())?, 5);
+        assert_eq!(Holder::<i64>::twice(env, 21)?, 42);
+        Ok(())
+    })
+}
@@ -0,0 +1,87 @@
+use std::thread;
+use std::time::Duration;
+
+use integration_tests::with_env;
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::{JObject, JString, JValue};
+use robusta_jni::jni::JNIEnv;
+
+const CLASS: &str = "com/robusta/tests/Globals";
+
+fn call<'env>(env: JNIEnv<'env>, name: &str, sig: &str, args: &[JValue<'env>]) -> JniResult<JValue<'env>> {
+    env.call_static_method(CLASS, name, sig, args)
+}
+
+fn gc(env: JNIEnv) -> JniResult<()> {
+    env.call_static_method("java/lang/System", "gc", "()V", &[])?;
+    Ok(())
+}
+
+/// Runs the garbage collector until `collected` holds, or gives up after a few tries.
+fn collect_until(env: JNIEnv, mut collected: impl FnMut() -> JniResult<bool>) -> JniResult<bool> {
+    for _ in 0..20 {
+        gc(env)?;
+        if collected()? {
+            return Ok(true);
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    Ok(false)
+}
+
+fn optional_string(env: JNIEnv, value: JValue) -> JniResult<Option<String>> {
+    let obj = value.l()?;
+    if obj.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(env.get_string(JString::from(obj))?.into()))
+}
+
+#[test]
+fn global_references_keep_objects_for_other_threads() {
+    with_env(|env| {
+        // A new string, referenced by this local reference only
+        let kept = env.new_string("kept")?;
+        call(env, "stash", "(Ljava/lang/String;)V", &[JValue::Object(kept.into())])?;
+
+        // Java gets back the very same object
+        let stashed = call(env, "stashed", "()Ljava/lang/String;", &[])?.l()?;
+        assert!(env.is_same_object(stashed, kept)?);
+        env.delete_local_ref(stashed)?;
+        env.delete_local_ref(kept.into())?;
+
+        gc(env)?;
+        let from_thread = call(env, "stashedFromThread", "()Ljava/lang/String;", &[])?;
+        assert_eq!(optional_string(env, from_thread)?.as_deref(), Some("kept"));
+
+        call(env, "clearStash", "()V", &[])?;
+        let cleared = call(env, "stashedFromThread", "()Ljava/lang/String;", &[])?;
+        assert_eq!(optional_string(env, cleared)?, None);
+        Ok(())
+    })
+}
+
+#[test]
+fn weak_references_observe_collection() {
+    with_env(|env| {
+        let watched = env.new_string("watched")?;
+        call(env, "watch", "(Ljava/lang/String;)V", &[JValue::Object(watched.into())])?;
+
+        let alive = call(env, "watched", "()Ljava/lang/String;", &[])?;
+        assert_eq!(optional_string(env, alive)?.as_deref(), Some("watched"));
+        env.delete_local_ref(alive.l()?)?;
+
+        // Once Java releases the string, only the weak reference is left
+        env.delete_local_ref(JObject::from(watched))?;
+        let collected = collect_until(env, || {
+            let value = call(env, "watched", "()Ljava/lang/String;", &[])?.l()?;
+            if value.is_null() {
+                return Ok(true);
+            }
+            env.delete_local_ref(value)?;
+            Ok(false)
+        })?;
+        assert!(collected, "the watched string was never collected");
+        Ok(())
+    })
+}
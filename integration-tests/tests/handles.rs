@@ -0,0 +1,65 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+
+use crate::jni::Handles;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Handles;
+
+    impl Handles {
+        pub extern "java" fn createCounter(env: JNIEnv, start: i64) -> JniResult<i64> {}
+        pub extern "java" fn increment(env: JNIEnv, counter: i64) -> JniResult<i64> {}
+        pub extern "java" fn destroyCounter(env: JNIEnv, counter: i64) -> JniResult<i64> {}
+        pub extern "java" fn incrementUnchecked(env: JNIEnv, counter: i64) -> JniResult<i64> {}
+        pub extern "java" fn destroyCounterUnchecked(env: JNIEnv, counter: i64) -> JniResult<i64> {}
+    }
+}
+
+#[test]
+fn handles_are_created_used_and_destroyed() {
+    with_env(|env| {
+        let counter = Handles::createCounter(env, 40)?;
+        assert_ne!(counter, 0);
+        assert_eq!(Handles::increment(env, counter)?, 41);
+        assert_eq!(Handles::incrementUnchecked(env, counter)?, 42);
+        assert_eq!(Handles::destroyCounter(env, counter)?, 42);
+
+        let counter = Handles::createCounter(env, 0)?;
+        assert_eq!(Handles::increment(env, counter)?, 1);
+        assert_eq!(Handles::destroyCounterUnchecked(env, counter)?, 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn destroyed_handles_throw() {
+    with_env(|env| {
+        let counter = Handles::createCounter(env, 0)?;
+        Handles::destroyCounter(env, counter)?;
+
+        assert!(Handles::destroyCounter(env, counter).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert!(message.unwrap().contains("destroyed handle"));
+
+        assert!(Handles::increment(env, counter).is_err());
+        assert!(take_exception(env)?.is_some());
+        Ok(())
+    })
+}
+
+#[test]
+fn null_handles_throw() {
+    with_env(|env| {
+        assert!(Handles::increment(env, 0).is_err());
+        assert!(take_exception(env)?.is_some());
+        assert!(Handles::destroyCounter(env, 0).is_err());
+        assert!(take_exception(env)?.is_some());
+        Ok(())
+    })
+}
@@ -0,0 +1,246 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::jni::errors::Error;
+use robusta_jni::jni::objects::JObject;
+
+use crate::jni::{Greeter, Integer, System};
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Greeter<'env> {
+        pub raw: JObject<'env>,
+    }
+
+    impl Signature for Greeter<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Greeter;";
+    }
+
+    impl Signature for &Greeter<'_> {
+        const SIG_TYPE: &'static str = <Greeter as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Greeter<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> IntoJavaValue<'env> for &Greeter<'env> {
+        type Target = JObject<'env>;
+
+        fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+            self.raw
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Greeter<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Greeter { raw: s })
+        }
+    }
+
+    impl<'env> Greeter<'env> {
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>, greeting: String) -> JniResult<Self> {}
+
+        #[constructor]
+        pub extern "java" fn new_raw(env: JNIEnv<'env>, greeting: String) -> JniResult<JObject<'env>> {}
+
+        #[constructor]
+        #[call_type(unchecked)]
+        pub extern "java" fn new_unchecked(env: JNIEnv<'env>, greeting: String) -> JObject<'env> {}
+
+        pub extern "java" fn greet(&self, env: JNIEnv<'env>, name: String) -> JniResult<String> {}
+
+        pub extern "java" fn greetedCount(env: JNIEnv<'env>) -> JniResult<i32> {}
+
+        #[constructor]
+        #[cached]
+        pub extern "java" fn new_cached(env: JNIEnv<'env>, greeting: String) -> JniResult<Self> {}
+
+        #[cached]
+        #[java_name = "greet"]
+        pub extern "java" fn greet_cached(&self, env: JNIEnv<'env>, name: String) -> JniResult<String> {}
+
+        #[cached]
+        #[call_type(unchecked)]
+        #[java_name = "greet"]
+        pub extern "java" fn greet_cached_unchecked(&self, env: JNIEnv<'env>, name: String) -> String {}
+
+        #[cached]
+        #[java_name = "greetedCount"]
+        pub extern "java" fn greeted_count_cached(env: JNIEnv<'env>) -> JniResult<i32> {}
+    }
+
+    // Only converted from Java: imported methods can return it without converting it to Java
+    #[package(java.lang)]
+    pub struct Integer<'env> {
+        pub raw: JObject<'env>,
+    }
+
+    impl Signature for Integer<'_> {
+        const SIG_TYPE: &'static str = "Ljava/lang/Integer;";
+    }
+
+    impl Signature for &Integer<'_> {
+        const SIG_TYPE: &'static str = <Integer as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Integer<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Integer<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Integer { raw: s })
+        }
+    }
+
+    impl<'env> Integer<'env> {
+        #[static_method]
+        pub extern "java" fn parse_int(env: JNIEnv<'env>, s: String) -> JniResult<i32> {}
+
+        #[static_method]
+        #[call_type(unchecked)]
+        #[java_name = "parseInt"]
+        pub extern "java" fn parse_int_unchecked(env: JNIEnv<'env>, s: String) -> i32 {}
+
+        #[static_method]
+        pub extern "java" fn value_of(env: JNIEnv<'env>, value: i32) -> JniResult<Self> {}
+
+        pub extern "java" fn int_value(&self, env: JNIEnv<'env>) -> JniResult<i32> {}
+
+        // `Integer` has both a static and an instance `toString` method
+        #[static_method]
+        #[java_name = "toString"]
+        pub extern "java" fn string_of(env: JNIEnv<'env>, value: i32) -> JniResult<String> {}
+
+        #[java_name = "toString"]
+        pub extern "java" fn string(&self, env: JNIEnv<'env>) -> JniResult<String> {}
+    }
+
+    #[package(java.lang)]
+    pub struct System;
+
+    impl System {
+        #[static_method]
+        pub extern "java" fn gc(env: JNIEnv) -> JniResult<()> {}
+    }
+}
+
+#[test]
+fn constructor_and_instance_method() {
+    with_env(|env| {
+        let greeter = Greeter::new(env, "Hello".into())?;
+        assert_eq!(greeter.greet(env, "robusta".into())?, "Hello, robusta!");
+        Ok(())
+    })
+}
+
+#[test]
+fn constructors_can_return_raw_objects() {
+    with_env(|env| {
+        let greeter = Greeter { raw: Greeter::new_raw(env, "Hey".into())? };
+        assert_eq!(greeter.greet(env, "you".into())?, "Hey, you!");
+
+        let greeter = Greeter { raw: Greeter::new_unchecked(env, "Howdy".into()) };
+        assert_eq!(greeter.greet(env, "partner".into())?, "Howdy, partner!");
+        Ok(())
+    })
+}
+
+#[test]
+fn static_method() {
+    with_env(|env| {
+        let greeter = Greeter::new(env, "Hi".into())?;
+        let before = Greeter::greetedCount(env)?;
+        greeter.greet(env, "there".into())?;
+
+        assert!(Greeter::greetedCount(env)? > before);
+        Ok(())
+    })
+}
+
+#[test]
+fn static_methods_of_library_classes() {
+    with_env(|env| {
+        assert_eq!(Integer::parse_int(env, "42".into())?, 42);
+        assert_eq!(Integer::parse_int_unchecked(env, "-7".into()), -7);
+
+        let integer = Integer::value_of(env, 1234)?;
+        assert_eq!(integer.int_value(env)?, 1234);
+
+        assert_eq!(Integer::string_of(env, 56)?, "56");
+        assert_eq!(integer.string(env)?, "1234");
+
+        System::gc(env)?;
+        Ok(())
+    })
+}
+
+#[test]
+fn static_methods_return_exceptions() {
+    with_env(|env| {
+        assert!(matches!(Integer::parse_int(env, "forty-two".into()), Err(Error::JavaException)));
+
+        let (class, message) = take_exception(env)?.unwrap();
+        assert_eq!(class, "java.lang.NumberFormatException");
+        assert_eq!(message.as_deref(), Some("For input string: \"forty-two\""));
+        Ok(())
+    })
+}
+
+#[test]
+fn cached_methods_return_the_same_results_on_every_call() {
+    with_env(|env| {
+        let hello = Greeter::new_cached(env, "Hello".into())?;
+        let bye = Greeter::new_cached(env, "Bye".into())?;
+
+        for i in 0..100 {
+            env.with_local_frame(16, || {
+                let name = format!("robusta {}", i);
+                assert_eq!(hello.greet_cached(env, name.clone())?, format!("Hello, {}!", name));
+                assert_eq!(bye.greet_cached(env, name.clone())?, format!("Bye, {}!", name));
+                assert_eq!(hello.greet_cached_unchecked(env, name.clone()), format!("Hello, {}!", name));
+                assert_eq!(Greeter::greeted_count_cached(env)?, Greeter::greetedCount(env)?);
+                Ok(JObject::null())
+            })?;
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn cached_methods_can_be_called_from_other_threads() {
+    with_env(|env| {
+        let greeter = Greeter::new_cached(env, "Hi".into())?;
+        assert_eq!(greeter.greet_cached(env, "main".into())?, "Hi, main!");
+        Ok(())
+    });
+
+    std::thread::spawn(|| {
+        with_env(|env| {
+            let greeter = Greeter::new_cached(env, "Hi".into())?;
+            assert_eq!(greeter.greet_cached(env, "thread".into())?, "Hi, thread!");
+            Ok(())
+        })
+    })
+    .join()
+    .unwrap();
+}
@@ -0,0 +1,69 @@
+use std::thread;
+
+use integration_tests::with_env;
+use robusta_jni::bridge;
+use robusta_jni::jni::objects::JObject;
+
+use crate::jni::Interned;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Interned;
+
+    impl Interned {
+        pub extern "java" fn internedString(env: JNIEnv, v: String) -> JniResult<String> {}
+        pub extern "java" fn internedStringId(env: JNIEnv, v: String) -> JniResult<i64> {}
+    }
+}
+
+#[test]
+fn strings_with_colliding_hashes_are_told_apart() {
+    with_env(|env| {
+        // "Aa" and "BB" have the same hash code
+        assert_eq!(Interned::internedString(env, "Aa".into())?, "Aa");
+        assert_eq!(Interned::internedString(env, "BB".into())?, "BB");
+        assert_eq!(Interned::internedString(env, "Aa".into())?, "Aa");
+        assert_eq!(Interned::internedString(env, "BB".into())?, "BB");
+
+        assert_ne!(Interned::internedStringId(env, "Aa".into())?, Interned::internedStringId(env, "BB".into())?);
+        Ok(())
+    })
+}
+
+#[test]
+fn equal_strings_share_their_cached_value() {
+    with_env(|env| {
+        // Each call passes a new Java string
+        let id = Interned::internedStringId(env, "tag".into())?;
+        assert_eq!(Interned::internedStringId(env, "tag".into())?, id);
+        Ok(())
+    })
+}
+
+#[test]
+fn cache_is_shared_between_threads() {
+    let threads: Vec<_> = (0..4)
+        .map(|i| {
+            thread::spawn(move || {
+                with_env(|env| {
+                    for j in 0..100 {
+                        let tag = format!("tag{}", (i + j) % 6);
+                        env.with_local_frame(4, || {
+                            assert_eq!(Interned::internedString(env, tag.clone())?, tag);
+                            Ok(JObject::null())
+                        })?;
+                    }
+                    Ok(())
+                })
+            })
+        })
+        .collect();
+
+    for t in threads {
+        t.join().unwrap();
+    }
+}
@@ -0,0 +1,94 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::convert::JavaCallback;
+use robusta_jni::jni::errors::{Error, Result as JniResult};
+use robusta_jni::jni::objects::JObject;
+use robusta_jni::jni::JNIEnv;
+
+use crate::jni::JavaCallbacks;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::JavaCallback;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct JavaCallbacks;
+
+    impl JavaCallbacks {
+        pub extern "java" fn forEachLine<'env>(env: JNIEnv<'env>, text: String, callback: JavaCallback<'env, (String,)>) -> JniResult<()> {}
+        pub extern "java" fn forEachLineInThread<'env>(env: JNIEnv<'env>, text: String, callback: JavaCallback<'env, (String,)>) -> JniResult<()> {}
+        pub extern "java" fn forEachLength<'env>(env: JNIEnv<'env>, text: String, callback: JavaCallback<'env, (String, i32)>) -> JniResult<()> {}
+    }
+}
+
+fn new_collector(env: JNIEnv) -> JniResult<JObject> {
+    env.new_object("com/robusta/tests/Collector", "()V", &[])
+}
+
+fn collected(env: JNIEnv, collector: JObject) -> JniResult<String> {
+    let collected = env.call_method(collector, "collected", "()Ljava/lang/String;", &[])?.l()?;
+    Ok(env.get_string(collected.into())?.into())
+}
+
+#[test]
+fn consumers_are_called_with_converted_values() {
+    with_env(|env| {
+        let collector = new_collector(env)?;
+
+        JavaCallbacks::forEachLine(env, "first\nsecond".into(), JavaCallback::from_object(env, collector)?)?;
+        assert_eq!(collected(env, collector)?, "first (String), second (String)");
+        Ok(())
+    })
+}
+
+#[test]
+fn global_consumers_are_called_from_other_threads() {
+    with_env(|env| {
+        let collector = new_collector(env)?;
+
+        JavaCallbacks::forEachLineInThread(env, "first\nsecond".into(), JavaCallback::from_object(env, collector)?)?;
+        assert_eq!(collected(env, collector)?, "first (String), second (String)");
+        Ok(())
+    })
+}
+
+#[test]
+fn bi_consumers_receive_boxed_primitives() {
+    with_env(|env| {
+        let collector = new_collector(env)?;
+
+        JavaCallbacks::forEachLength(env, "ab\nabc".into(), JavaCallback::from_object(env, collector)?)?;
+        assert_eq!(collected(env, collector)?, "ab=2 (Integer), abc=3 (Integer)");
+        Ok(())
+    })
+}
+
+#[test]
+fn exceptions_of_callbacks_stop_the_calls() {
+    with_env(|env| {
+        let collector = new_collector(env)?;
+
+        let result = JavaCallbacks::forEachLine(env, "first\nreject\nlast".into(), JavaCallback::from_object(env, collector)?);
+        assert!(matches!(result, Err(Error::JavaException)));
+
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.IllegalArgumentException");
+        assert_eq!(message.as_deref(), Some("rejected reject"));
+        assert_eq!(collected(env, collector)?, "first (String)");
+        Ok(())
+    })
+}
+
+#[test]
+fn objects_of_other_classes_are_rejected() {
+    with_env(|env| {
+        let not_a_consumer = env.new_object("java/lang/Object", "()V", &[])?;
+
+        let result = JavaCallback::<(String,)>::from_object(env, not_a_consumer);
+        assert!(matches!(result, Err(Error::WrongJValueType("java/util/function/Consumer", _))));
+        assert!(matches!(JavaCallback::<()>::from_object(env, JObject::null()), Err(Error::NullPtr(_))));
+        Ok(())
+    })
+}
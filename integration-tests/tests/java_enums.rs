@@ -0,0 +1,141 @@
+use integration_tests::fixtures::java_enums::jni::{Health, Level, Priority};
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::convert::JavaEnum;
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::{JObject, JValue};
+use robusta_jni::jni::JNIEnv;
+
+use crate::jni::JavaEnums;
+
+#[bridge]
+mod jni {
+    use integration_tests::fixtures::java_enums::jni::{Health, Level, Priority};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct JavaEnums;
+
+    impl JavaEnums {
+        pub extern "java" fn healthIdentity(env: JNIEnv, v: Health) -> JniResult<Health> {}
+        pub extern "java" fn levelIdentity(env: JNIEnv, v: Level) -> JniResult<Level> {}
+        pub extern "java" fn priorityIdentity(env: JNIEnv, v: Priority) -> JniResult<Priority> {}
+        pub extern "java" fn optionalLevelIdentity(env: JNIEnv, v: Option<Level>) -> JniResult<Option<Level>> {}
+        pub extern "java" fn unreleasedLevel(env: JNIEnv) -> JniResult<Level> {}
+        pub extern "java" fn uncheckedPriorityIdentity(env: JNIEnv, v: Priority) -> JniResult<Priority> {}
+    }
+}
+
+/// The constant `name` of the Java enum `class`.
+fn constant<'env>(env: JNIEnv<'env>, class: &str, name: &str) -> JniResult<JObject<'env>> {
+    env.get_static_field(class, name, format!("L{};", class))?.l()
+}
+
+/// Calls the static method `name` of `JavaEnums` with a raw Java object.
+fn call_raw<'env>(env: JNIEnv<'env>, name: &str, sig: &str, arg: JObject<'env>) -> JniResult<JObject<'env>> {
+    env.call_static_method("com/robusta/tests/JavaEnums", name, sig, &[JValue::Object(arg)])?.l()
+}
+
+#[test]
+fn enums_round_trip() {
+    with_env(|env| {
+        for health in [Health::Ok, Health::Degraded, Health::Down] {
+            assert_eq!(JavaEnums::healthIdentity(env, health)?, health);
+        }
+        for level in [Level::Low, Level::Medium, Level::High] {
+            assert_eq!(JavaEnums::levelIdentity(env, level)?, level);
+        }
+        for priority in [Priority::Lowest, Priority::Normal, Priority::Urgent] {
+            assert_eq!(JavaEnums::priorityIdentity(env, priority)?, priority);
+            assert_eq!(JavaEnums::uncheckedPriorityIdentity(env, priority)?, priority);
+        }
+        assert_eq!(JavaEnums::optionalLevelIdentity(env, Some(Level::Medium))?, Some(Level::Medium));
+        assert_eq!(JavaEnums::optionalLevelIdentity(env, None)?, None);
+        Ok(())
+    })
+}
+
+#[test]
+fn variants_are_java_constants() {
+    with_env(|env| {
+        assert_eq!(Health::CONSTANTS, ["OK", "DEGRADED", "OFFLINE"]);
+        assert_eq!(Health::Down.java_name(), "OFFLINE");
+
+        let offline = constant(env, "com/robusta/tests/Health", "OFFLINE")?;
+        let returned = call_raw(env, "healthIdentity", "(Lcom/robusta/tests/Health;)Lcom/robusta/tests/Health;", offline)?;
+        assert!(env.is_same_object(offline, returned)?);
+
+        // Converted by ordinal, regardless of the names of the constants
+        let urgent = constant(env, "com/robusta/tests/Priority", "URGENT")?;
+        assert_eq!(env.call_method(urgent, "ordinal", "()I", &[])?.i()?, Priority::Urgent.index() as i32);
+        Ok(())
+    })
+}
+
+#[test]
+fn exported_methods_are_called_on_constants() {
+    with_env(|env| {
+        let ok = constant(env, "com/robusta/tests/Health", "OK")?;
+        assert!(env.call_method(ok, "isHealthy", "()Z", &[])?.z()?);
+
+        let worse = env.call_method(ok, "worse", "()Lcom/robusta/tests/Health;", &[])?.l()?;
+        assert!(env.is_same_object(worse, constant(env, "com/robusta/tests/Health", "DEGRADED")?)?);
+        assert!(!env.call_method(worse, "isHealthy", "()Z", &[])?.z()?);
+
+        let name = JObject::from(env.new_string("OFFLINE")?);
+        let parsed = env
+            .call_static_method("com/robusta/tests/Health", "parse", "(Ljava/lang/String;)Lcom/robusta/tests/Health;", &[JValue::Object(name)])?
+            .l()?;
+        assert!(env.is_same_object(parsed, constant(env, "com/robusta/tests/Health", "OFFLINE")?)?);
+        Ok(())
+    })
+}
+
+#[test]
+fn unknown_constants_throw_java_exception() {
+    with_env(|env| {
+        // A Java constant with no Rust variant, by name and by ordinal
+        let critical = constant(env, "com/robusta/tests/Level", "CRITICAL")?;
+        assert!(call_raw(env, "levelIdentity", "(Lcom/robusta/tests/Level;)Lcom/robusta/tests/Level;", critical).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.IllegalArgumentException");
+        assert_eq!(message.as_deref(), Some("no variant has the name of constant `CRITICAL` of `com.robusta.tests.Level`"));
+
+        let blocker = constant(env, "com/robusta/tests/Priority", "BLOCKER")?;
+        assert!(call_raw(env, "priorityIdentity", "(Lcom/robusta/tests/Priority;)Lcom/robusta/tests/Priority;", blocker).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.IllegalArgumentException");
+        assert_eq!(message.as_deref(), Some("no variant has ordinal 3 of `com.robusta.tests.Priority`"));
+
+        // Unchecked conversions panic instead
+        assert!(call_raw(env, "uncheckedLevelIdentity", "(Lcom/robusta/tests/Level;)Lcom/robusta/tests/Level;", critical).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert_eq!(message.as_deref(), Some("no variant has the name of constant `CRITICAL` of `com.robusta.tests.Level`"));
+
+        // A Rust variant with no Java constant
+        assert!(JavaEnums::unreleasedLevel(env).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.IllegalArgumentException");
+        assert_eq!(message.as_deref(), Some("No enum constant com.robusta.tests.Level.UNRELEASED"));
+
+        assert!(JavaEnums::levelIdentity(env, Level::Unreleased).is_err());
+        let (class, _) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.IllegalArgumentException");
+        Ok(())
+    })
+}
+
+#[test]
+fn null_constants_are_rejected() {
+    with_env(|env| {
+        let null = JObject::null();
+        let returned = call_raw(env, "optionalLevelIdentity", "(Lcom/robusta/tests/Level;)Lcom/robusta/tests/Level;", null)?;
+        assert!(returned.is_null());
+
+        assert!(call_raw(env, "levelIdentity", "(Lcom/robusta/tests/Level;)Lcom/robusta/tests/Level;", null).is_err());
+        assert!(take_exception(env)?.is_some());
+        Ok(())
+    })
+}
@@ -0,0 +1,53 @@
+//! Kotlin declarations generated for the exported fixtures, as `ROBUSTA_KOTLIN_OUTPUT_DIR` is set by the build script.
+//!
+//! Compiling them needs `kotlinc` (on the path, or in `$KOTLIN_HOME/bin`) and `ROBUSTA_KOTLIN_CLASSPATH` set to the class path
+//! of the Kotlin standard library, and is ignored by default:
+//!
+//! ```text
+//! ROBUSTA_KOTLIN_CLASSPATH=kotlin-stdlib.jar cargo test --test kotlin_stubs -- --ignored
+//! ```
+
+use integration_tests::{compile_kotlin_stubs, kotlin_stubs_dir};
+
+fn kotlin_stub(path: &str) -> String {
+    let path = kotlin_stubs_dir().join(path);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("can't read `{}` ({})", path.display(), e))
+}
+
+#[test]
+fn static_methods_are_declared_in_the_companion_object() {
+    let conversions = kotlin_stub("com/robusta/tests/Conversions.kt");
+
+    assert!(conversions.contains("\nopen class Conversions {\n    companion object {\n"));
+    assert!(conversions.contains("        @JvmStatic\n        external fun intIdentity(v: Int): Int\n"));
+    assert!(conversions.contains("        external fun intArrayIdentity(v: IntArray): IntArray\n"));
+}
+
+#[test]
+fn options_are_nullable() {
+    let conversions = kotlin_stub("com/robusta/tests/Conversions.kt");
+
+    assert!(conversions.contains("        external fun optionalIntIdentity(v: Int?): Int?\n"));
+    assert!(conversions.contains("        external fun optionalStringIdentity(v: String?): String?\n"));
+    assert!(conversions.contains("        external fun optionalIntVecIdentity(v: List<Int>?): java.util.ArrayList<Int>?\n"));
+    assert!(conversions.contains("        external fun optionalStringVecIdentity(v: List<String?>): java.util.ArrayList<String?>\n"));
+}
+
+#[test]
+fn methods_of_java_enums_are_declared_in_enum_classes() {
+    let health = kotlin_stub("com/robusta/tests/Health.kt");
+
+    assert!(health.contains("\nenum class Health {\n"));
+    assert!(health.contains("    external fun isHealthy(): Boolean\n"));
+    assert!(health.contains("        @JvmStatic\n        external fun parse(name: String): com.robusta.tests.Health?\n"));
+}
+
+#[test]
+#[ignore]
+fn generated_declarations_compile() {
+    let output_dir = std::env::temp_dir().join(format!("robusta-kotlin-stubs-{}", std::process::id()));
+
+    let compiled = compile_kotlin_stubs(&output_dir);
+    let _ = std::fs::remove_dir_all(&output_dir);
+    assert!(compiled, "can't compile the Kotlin declarations in `{}`", kotlin_stubs_dir().display());
+}
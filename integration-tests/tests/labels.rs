@@ -0,0 +1,56 @@
+use integration_tests::with_env;
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::{JObject, JValue};
+use robusta_jni::jni::JNIEnv;
+
+fn label<'env>(env: JNIEnv<'env>, text: &str) -> JniResult<JObject<'env>> {
+    let text = env.new_string(text)?;
+    env.new_object("com/robusta/tests/Label", "(Ljava/lang/String;)V", &[JValue::Object(text.into())])
+}
+
+fn call_string(env: JNIEnv, label: JObject, method: &str) -> JniResult<String> {
+    let string = env.call_method(label, method, "()Ljava/lang/String;", &[])?.l()?;
+    Ok(env.get_string(string.into())?.into())
+}
+
+#[test]
+fn str_return_values_borrow_from_receivers() {
+    with_env(|env| {
+        let label = label(env, "héllo ❤")?;
+        assert_eq!(call_string(env, label, "text")?, "héllo ❤");
+        assert_eq!(call_string(env, label, "uncheckedText")?, "héllo ❤");
+        Ok(())
+    })
+}
+
+#[test]
+fn cow_return_values_are_borrowed_or_owned() {
+    with_env(|env| {
+        assert_eq!(call_string(env, label(env, "robusta")?, "display")?, "robusta");
+        assert_eq!(call_string(env, label(env, "  robusta ")?, "display")?, "robusta");
+        assert_eq!(call_string(env, label(env, " ")?, "display")?, "(untitled)");
+        Ok(())
+    })
+}
+
+#[test]
+fn labels_keep_their_object() {
+    with_env(|env| {
+        let label = label(env, "robusta")?;
+        assert!(env.call_method(label, "isSame", "(Lcom/robusta/tests/Label;)Z", &[JValue::Object(label)])?.z()?);
+        Ok(())
+    })
+}
+
+#[test]
+fn overloads_taking_labels_are_told_apart() {
+    with_env(|env| {
+        let prefix = label(env, "rob")?;
+        let label = label(env, "robusta")?;
+        assert!(env.call_method(label, "startsWith", "(Lcom/robusta/tests/Label;)Z", &[JValue::Object(prefix)])?.z()?);
+
+        let text = env.new_string("rust")?;
+        assert!(!env.call_method(label, "startsWith", "(Ljava/lang/String;)Z", &[JValue::Object(text.into())])?.z()?);
+        Ok(())
+    })
+}
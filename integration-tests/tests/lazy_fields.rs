@@ -0,0 +1,56 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+
+use crate::jni::{Documents, LazyFields};
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct LazyFields;
+
+    impl LazyFields {
+        pub extern "java" fn loads(env: JNIEnv) -> JniResult<i64> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct Documents;
+
+    impl Documents {
+        pub extern "java" fn title(env: JNIEnv, title: String, size: i32) -> JniResult<String> {}
+        pub extern "java" fn payloadSize(env: JNIEnv, title: String, size: i32) -> JniResult<i32> {}
+        pub extern "java" fn ownedPayloadSize(env: JNIEnv, title: String, size: i32) -> JniResult<i32> {}
+    }
+}
+
+// Payloads are counted on each thread, and every test calls from a thread of its own
+
+#[test]
+fn unused_lazy_fields_are_never_read() {
+    with_env(|env| {
+        let loads = LazyFields::loads(env)?;
+        assert_eq!(Documents::title(env, "unread".into(), 1 << 20)?, "unread");
+        assert_eq!(LazyFields::loads(env)?, loads);
+        Ok(())
+    })
+}
+
+#[test]
+fn lazy_fields_are_read_once() {
+    with_env(|env| {
+        let loads = LazyFields::loads(env)?;
+        assert_eq!(Documents::payloadSize(env, "read twice".into(), 1 << 20)?, 1 << 20);
+        assert_eq!(LazyFields::loads(env)?, loads + 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn owned_lazy_fields_attach_on_demand() {
+    with_env(|env| {
+        assert_eq!(Documents::ownedPayloadSize(env, "owned".into(), 1024)?, 1024);
+        Ok(())
+    })
+}
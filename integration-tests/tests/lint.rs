@@ -0,0 +1,30 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+
+#[bridge(lint_raw_jni = "deny")]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[allow_raw_jni]
+    pub fn string_length(env: JNIEnv, s: &str) -> JniResult<i32> {
+        let s = env.new_string(s)?;
+        env.call_method(s, "length", "()I", &[])?.i()
+    }
+
+    pub fn string_hash(env: JNIEnv, s: &str) -> JniResult<i32> {
+        #[allow_raw_jni]
+        let s = env.new_string(s)?;
+        let hash = #[allow_raw_jni] env.call_method(s, "hashCode", "()I", &[])?;
+        hash.i()
+    }
+}
+
+#[test]
+fn allowed_raw_calls_are_kept() {
+    with_env(|env| {
+        assert_eq!(jni::string_length(env, "robusta")?, 7);
+        assert_eq!(jni::string_hash(env, "")?, 0);
+        Ok(())
+    })
+}
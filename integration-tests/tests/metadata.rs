@@ -0,0 +1,95 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+use robusta_jni::metadata::{unresolved, MethodMetadata};
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Greeter<'env> {
+        pub raw: JObject<'env>,
+    }
+
+    impl Signature for Greeter<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Greeter;";
+    }
+
+    impl Signature for &Greeter<'_> {
+        const SIG_TYPE: &'static str = <Greeter as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Greeter<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Greeter<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Greeter { raw: s })
+        }
+    }
+
+    impl<'env> Greeter<'env> {
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>, greeting: String) -> JniResult<Self> {}
+
+        pub extern "java" fn greet(&self, env: JNIEnv<'env>, name: String) -> JniResult<String> {}
+
+        #[call_type(unchecked)]
+        pub extern "java" fn greetedCount(env: JNIEnv<'env>) -> i32 {}
+
+        // `greet` takes a `String`
+        #[java_name = "greet"]
+        pub extern "java" fn greet_number(&self, env: JNIEnv<'env>, name: i32) -> JniResult<String> {}
+
+        #[field]
+        pub extern "java" fn greeting(&self, env: JNIEnv<'env>) -> JniResult<String> {}
+    }
+}
+
+fn method(name: &'static str, params: &'static [&'static str], ret: &'static str, is_static: bool) -> MethodMetadata {
+    MethodMetadata {
+        class: "com/robusta/tests/Greeter",
+        name,
+        params,
+        ret,
+        is_static,
+    }
+}
+
+#[test]
+fn imported_methods_are_described() {
+    const STRING: &str = "Ljava/lang/String;";
+
+    assert_eq!(
+        jni::bridge_metadata(),
+        &[
+            method("<init>", &[STRING], "V", false),
+            method("greet", &[STRING], STRING, false),
+            method("greetedCount", &[], "I", true),
+            method("greet", &["I"], STRING, false),
+        ]
+    );
+    assert_eq!(jni::bridge_metadata()[1].descriptor(), "(Ljava/lang/String;)Ljava/lang/String;");
+    assert_eq!(
+        jni::bridge_metadata()[2].to_string(),
+        "static com.robusta.tests.Greeter.greetedCount()I"
+    );
+}
+
+#[test]
+fn mismatched_methods_are_unresolved() {
+    with_env(|env| {
+        assert_eq!(unresolved(&env, jni::bridge_metadata())?, vec![method("greet", &["I"], "Ljava/lang/String;", false)]);
+        Ok(())
+    })
+}
@@ -0,0 +1,64 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+
+use crate::jni::Middlewares;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Middlewares;
+
+    impl Middlewares {
+        pub extern "java" fn add(env: JNIEnv, a: i32, b: i32) -> JniResult<i32> {}
+        pub extern "java" fn fail(env: JNIEnv, message: String) -> JniResult<i32> {}
+        pub extern "java" fn explode(env: JNIEnv) -> JniResult<i32> {}
+        pub extern "java" fn uncheckedExplode(env: JNIEnv) -> JniResult<i32> {}
+        pub extern "java" fn callId(env: JNIEnv) -> JniResult<i64> {}
+        pub extern "java" fn takeLog(env: JNIEnv) -> JniResult<Vec<String>> {}
+    }
+}
+
+#[test]
+fn middleware_runs_around_every_call() {
+    with_env(|env| {
+        // Each call gets a new id on its thread, set before the method reads it
+        let first = Middlewares::callId(env)?;
+        assert_eq!(Middlewares::callId(env)?, first + 1);
+        Middlewares::takeLog(env)?;
+
+        assert_eq!(Middlewares::add(env, 2, 3)?, 5);
+
+        assert!(Middlewares::fail(env, "failed".into()).is_err());
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert_eq!(message.as_deref(), Some("failed"));
+
+        assert!(Middlewares::explode(env).is_err());
+        let (_, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(message.as_deref(), Some("exploded"));
+
+        assert!(Middlewares::uncheckedExplode(env).is_err());
+        let (_, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(message.as_deref(), Some("exploded unchecked"));
+
+        assert_eq!(
+            Middlewares::takeLog(env)?,
+            [
+                "after takeLog: Returned",
+                "before com.robusta.tests.Middlewares.add",
+                "after add: Returned",
+                "before com.robusta.tests.Middlewares.fail",
+                "after fail: Threw",
+                "before com.robusta.tests.Middlewares.explode",
+                "after explode: Panicked",
+                "before com.robusta.tests.Middlewares.uncheckedExplode",
+                "after uncheckedExplode: Panicked",
+                "before com.robusta.tests.Middlewares.takeLog",
+            ]
+        );
+        Ok(())
+    })
+}
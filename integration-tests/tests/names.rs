@@ -0,0 +1,99 @@
+use integration_tests::fixtures::names::jni::Names as ExportedNames;
+use integration_tests::with_env;
+use robusta_jni::bridge;
+
+use crate::jni::Names;
+use crate::renamed::Renamed;
+use crate::underscored::Underscored;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Names;
+
+    impl Names {
+        #[java_name = "getUserName"]
+        pub extern "java" fn user_name(env: JNIEnv, id: i32) -> JniResult<String> {}
+
+        pub extern "java" fn greet(env: JNIEnv, name: String) -> JniResult<String> {}
+
+        #[java_name = "greet"]
+        pub extern "java" fn greet_times(env: JNIEnv, name: String, times: i32) -> JniResult<String> {}
+    }
+}
+
+#[bridge]
+mod underscored {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests.snake_case)]
+    pub struct Underscored;
+
+    impl Underscored {
+        #[java_name = "user_name"]
+        pub extern "java" fn user_name(env: JNIEnv, id: i32) -> JniResult<String> {}
+    }
+}
+
+#[bridge(rename_all = "camelCase")]
+mod renamed {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Renamed;
+
+    impl Renamed {
+        pub extern "java" fn parse_json_url(env: JNIEnv, url: String) -> JniResult<String> {}
+
+        #[java_name = "parseJsonUrl"]
+        pub extern "java" fn parse(env: JNIEnv, url: String) -> JniResult<String> {}
+
+        #[java_name = "legacy_count"]
+        pub extern "java" fn legacy_count(env: JNIEnv) -> JniResult<i32> {}
+    }
+}
+
+#[test]
+fn exported_method_is_called_by_java_name() {
+    with_env(|env| {
+        assert_eq!(Names::user_name(env, 42)?, "user42");
+        Ok(())
+    })
+}
+
+#[test]
+fn overloaded_methods_are_told_apart() {
+    with_env(|env| {
+        assert_eq!(Names::greet(env, "Ada".into())?, "Hello, Ada!");
+        assert_eq!(Names::greet_times(env, "Ada".into(), 2)?, "Hello, Ada! Hello, Ada!");
+        Ok(())
+    })
+}
+
+#[test]
+fn exported_method_keeps_rust_name() {
+    assert_eq!(ExportedNames::get_user_name(7), "user7");
+}
+
+#[test]
+fn underscores_in_package_and_method_names_are_mangled() {
+    with_env(|env| {
+        assert_eq!(Underscored::user_name(env, 3)?, "user_3");
+        Ok(())
+    })
+}
+
+#[test]
+fn rename_all_converts_method_names_to_camel_case() {
+    with_env(|env| {
+        assert_eq!(Renamed::parse_json_url(env, "a.b".into())?, "json+a.b");
+        assert_eq!(Renamed::parse(env, "c".into())?, "json+c");
+        assert_eq!(Renamed::legacy_count(env)?, 3);
+        Ok(())
+    })
+}
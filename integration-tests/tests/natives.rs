@@ -0,0 +1,70 @@
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+
+use integration_tests::{native_library, with_env};
+use robusta_jni::bridge;
+
+use crate::jni::Registered;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Registered;
+
+    impl Registered {
+        pub extern "java" fn greet(env: JNIEnv, name: String) -> JniResult<String> {}
+
+        #[java_name = "sum"]
+        pub extern "java" fn sum_ints(env: JNIEnv, a: i32, b: i32) -> JniResult<i32> {}
+
+        #[java_name = "sum"]
+        pub extern "java" fn sum_strings(env: JNIEnv, a: String, b: String) -> JniResult<String> {}
+
+        pub extern "java" fn length(env: JNIEnv, text: String, extra: Option<i32>) -> JniResult<i32> {}
+
+        pub extern "java" fn shout(env: JNIEnv, text: String) -> JniResult<String> {}
+    }
+}
+
+/// Whether the loaded fixture library exports `symbol`.
+fn exports(symbol: &str) -> bool {
+    let path = CString::new(native_library().as_os_str().as_bytes()).unwrap();
+    let symbol = CString::new(symbol).unwrap();
+
+    unsafe {
+        let library = libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_NOLOAD);
+        assert!(!library.is_null(), "fixture library isn't loaded");
+        let exported = !libc::dlsym(library, symbol.as_ptr()).is_null();
+        libc::dlclose(library);
+        exported
+    }
+}
+
+#[test]
+fn registered_methods_are_called() {
+    with_env(|env| {
+        assert_eq!(Registered::greet(env, "Duke".into())?, "Hello, Duke!");
+        assert_eq!(Registered::sum_ints(env, 2, 3)?, 5);
+        assert_eq!(Registered::sum_strings(env, "2".into(), "3".into())?, "23");
+        assert_eq!(Registered::length(env, "four".into(), None)?, 4);
+        assert_eq!(Registered::length(env, "four".into(), Some(2))?, 6);
+        assert_eq!(Registered::shout(env, "hey".into())?, "HEY");
+        Ok(())
+    })
+}
+
+#[test]
+fn registered_methods_have_no_symbols() {
+    with_env(|_| Ok(()));
+
+    assert!(exports("JNI_OnLoad"));
+    assert!(!exports("Java_com_robusta_tests_Registered_greet"));
+    assert!(!exports("Java_com_robusta_tests_Registered_sum__II"));
+    assert!(!exports("Java_com_robusta_tests_Preloading_initializedClasses"));
+    assert!(exports("robusta_tests_registered_shout"));
+    // Modules without `register_natives` still export their methods
+    assert!(exports("Java_com_robusta_tests_Arithmetic_add"));
+}
@@ -0,0 +1,40 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+
+use crate::jni::NullCalls;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct NullCalls;
+
+    impl NullCalls {
+        pub extern "java" fn describeArguments(env: JNIEnv) -> JniResult<Vec<String>> {}
+        pub extern "java" fn noneIsNull(env: JNIEnv) -> JniResult<bool> {}
+        pub extern "java" fn some(env: JNIEnv, value: String) -> JniResult<String> {}
+    }
+}
+
+#[test]
+fn null_arguments_are_none() {
+    with_env(|env| {
+        let some = "Some(\"robusta\")";
+        assert_eq!(
+            NullCalls::describeArguments(env)?,
+            vec!["None", some, "None", "Some(42)", "None", some, "None", some, "None", some]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn returned_none_is_null() {
+    with_env(|env| {
+        assert!(NullCalls::noneIsNull(env)?);
+        assert_eq!(NullCalls::some(env, "robusta".into())?, "robusta");
+        Ok(())
+    })
+}
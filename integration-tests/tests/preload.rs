@@ -0,0 +1,68 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::preload::preloaded_class;
+
+use crate::jni::{InitializationLog, Preloading};
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Preloading;
+
+    impl Preloading {
+        pub extern "java" fn initializedClasses<'env>(env: JNIEnv<'env>) -> JniResult<Vec<String>> {}
+    }
+
+    #[package(com.robusta.tests)]
+    pub struct InitializationLog;
+
+    impl InitializationLog {
+        pub extern "java" fn initialized<'env>(env: JNIEnv<'env>) -> JniResult<Vec<String>> {}
+    }
+}
+
+#[bridge(preload_classes("com.robusta.tests.ManuallyPreloaded"), manual_preload)]
+mod manual {}
+
+#[bridge(preload_classes("java.util.HashMap", "com.robusta.tests.Missing", "com.robusta.tests.NotPreloaded"), manual_preload)]
+mod missing {}
+
+#[test]
+fn classes_are_preloaded_before_the_first_exported_call() {
+    with_env(|env| {
+        // The fixture calls back `InitializationLog` without touching `Preloaded` itself
+        assert!(Preloading::initializedClasses(env)?.contains(&"com.robusta.tests.Preloaded".to_string()));
+        Ok(())
+    })
+}
+
+#[test]
+fn classes_are_preloaded_on_demand() {
+    with_env(|env| {
+        let initialized = "com.robusta.tests.ManuallyPreloaded".to_string();
+        assert!(!InitializationLog::initialized(env)?.contains(&initialized));
+
+        manual::preload(&env)?;
+        assert!(InitializationLog::initialized(env)?.contains(&initialized));
+        Ok(())
+    })
+}
+
+#[test]
+fn missing_classes_are_reported_by_name() {
+    with_env(|env| {
+        assert!(missing::preload(&env).is_err());
+
+        let (class, message) = take_exception(env)?.expect("preloading should throw");
+        assert_eq!(class, "java.lang.NoClassDefFoundError");
+        assert_eq!(message.as_deref(), Some("can't preload class `com.robusta.tests.Missing`"));
+
+        // Preloading stops at the first missing class
+        assert!(preloaded_class("java/util/HashMap").is_some());
+        assert!(!InitializationLog::initialized(env)?.contains(&"com.robusta.tests.NotPreloaded".to_string()));
+        Ok(())
+    })
+}
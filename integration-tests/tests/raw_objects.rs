@@ -0,0 +1,50 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+use robusta_jni::jni::objects::{JObject, JValue};
+
+use crate::jni::RawObjects;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct RawObjects;
+
+    impl RawObjects {
+        pub extern "java" fn notify<'env>(env: JNIEnv<'env>, #[sig("Lcom/robusta/tests/Listener;")] listener: JObject<'env>, event: String) -> JniResult<String> {}
+        #[sig("Lcom/robusta/tests/Listener;")]
+        pub extern "java" fn firstListener<'env>(env: JNIEnv<'env>, #[sig("[Lcom/robusta/tests/Listener;")] listeners: JObject<'env>) -> JniResult<JObject<'env>> {}
+    }
+}
+
+fn new_listener<'env>(env: robusta_jni::jni::JNIEnv<'env>, prefix: &str) -> robusta_jni::jni::errors::Result<JObject<'env>> {
+    let prefix = env.new_string(prefix)?;
+    env.new_object("com/robusta/tests/PrefixListener", "(Ljava/lang/String;)V", &[JValue::Object(prefix.into())])
+}
+
+#[test]
+fn raw_objects_are_passed_with_their_declared_class() {
+    with_env(|env| {
+        let listener = new_listener(env, "got ")?;
+
+        assert_eq!(RawObjects::notify(env, listener, "ping".into())?, "got ping");
+        Ok(())
+    })
+}
+
+#[test]
+fn raw_objects_are_returned_with_their_declared_class() {
+    with_env(|env| {
+        let listeners = env.new_object_array(2, "com/robusta/tests/Listener", JObject::null())?;
+        env.set_object_array_element(listeners, 0, new_listener(env, "first ")?)?;
+        env.set_object_array_element(listeners, 1, new_listener(env, "second ")?)?;
+
+        let first = RawObjects::firstListener(env, JObject::from(listeners))?;
+        assert!(env.is_instance_of(first, "com/robusta/tests/Listener")?);
+        assert_eq!(RawObjects::notify(env, first, "event".into())?, "first event");
+        Ok(())
+    })
+}
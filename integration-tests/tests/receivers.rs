@@ -0,0 +1,64 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+use robusta_jni::jni::objects::JValue;
+
+use crate::jni::HandleCalls;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct HandleCalls;
+
+    impl HandleCalls {
+        pub extern "java" fn callStatic(env: JNIEnv, v: i32) -> JniResult<i32> {}
+        pub extern "java" fn callStaticWithHandles(env: JNIEnv, sameHandle: bool) -> JniResult<bool> {}
+        pub extern "java" fn callInstance(env: JNIEnv, sameHandle: bool) -> JniResult<bool> {}
+    }
+}
+
+#[test]
+fn static_native() {
+    with_env(|env| {
+        assert_eq!(HandleCalls::callStatic(env, 21)?, 42);
+        Ok(())
+    })
+}
+
+#[test]
+fn static_native_taking_struct_by_value() {
+    with_env(|env| {
+        assert!(HandleCalls::callStaticWithHandles(env, true)?);
+        assert!(!HandleCalls::callStaticWithHandles(env, false)?);
+        Ok(())
+    })
+}
+
+#[test]
+fn instance_native_on_same_class() {
+    with_env(|env| {
+        assert!(HandleCalls::callInstance(env, true)?);
+        assert!(!HandleCalls::callInstance(env, false)?);
+        Ok(())
+    })
+}
+
+#[test]
+fn borrowed_receiver_without_lifetime() {
+    with_env(|env| {
+        let temperature = env.new_object("com/robusta/tests/Temperature", "(D)V", &[JValue::Double(100.0)])?;
+        assert_eq!(env.call_method(temperature, "fahrenheit", "()D", &[])?.d()?, 212.0);
+        Ok(())
+    })
+}
+
+#[test]
+fn owned_receiver_without_lifetime() {
+    with_env(|env| {
+        let temperature = env.new_object("com/robusta/tests/Temperature", "(D)V", &[JValue::Double(-273.15)])?;
+        assert_eq!(env.call_method(temperature, "kelvin", "()D", &[])?.d()?, 0.0);
+        Ok(())
+    })
+}
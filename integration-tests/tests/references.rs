@@ -0,0 +1,51 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::convert::Passthrough;
+
+use crate::jni::References;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::Passthrough;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct References;
+
+    impl References {
+        pub extern "java" fn leakOwned<'env>(env: JNIEnv<'env>, v: Passthrough<'env>) -> JniResult<()> {}
+        pub extern "java" fn dropOwned<'env>(env: JNIEnv<'env>, v: Passthrough<'env>) -> JniResult<()> {}
+        pub extern "java" fn downgradeOwned<'env>(env: JNIEnv<'env>, v: Passthrough<'env>) -> JniResult<Passthrough<'env>> {}
+        pub extern "java" fn refReport(env: JNIEnv) -> JniResult<Vec<String>> {}
+        pub extern "java" fn setPanicThreshold(env: JNIEnv, threshold: i32) -> JniResult<()> {}
+    }
+}
+
+#[test]
+fn leaked_references_are_reported_by_site() {
+    with_env(|env| {
+        let token = Passthrough::new(env, env.new_string("token")?.into());
+        References::dropOwned(env, token)?;
+        References::downgradeOwned(env, token)?;
+        References::leakOwned(env, token)?;
+
+        let report = References::refReport(env)?;
+        assert!(report.contains(&"com.robusta.tests.References.leakOwned PassthroughOwned 1".to_string()), "{:?}", report);
+        assert!(!report.iter().any(|r| r.contains("dropOwned") || r.contains("downgradeOwned")), "{:?}", report);
+
+        // Another leak from the same site now reaches the threshold, and panics instead
+        References::setPanicThreshold(env, 1)?;
+        assert!(References::leakOwned(env, token).is_err());
+
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.RuntimeException");
+        assert_eq!(
+            message.as_deref(),
+            Some("1 outstanding references held by `PassthroughOwned` created in com.robusta.tests.References.leakOwned")
+        );
+
+        References::setPanicThreshold(env, -1)?;
+        Ok(())
+    })
+}
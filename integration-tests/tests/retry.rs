@@ -0,0 +1,173 @@
+use integration_tests::{take_exception, with_env};
+use robusta_jni::bridge;
+use robusta_jni::jni::errors::Error;
+
+use crate::jni::Flaky;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Flaky<'env> {
+        raw: JObject<'env>,
+    }
+
+    impl Signature for Flaky<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Flaky;";
+    }
+
+    impl Signature for &Flaky<'_> {
+        const SIG_TYPE: &'static str = <Flaky as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Flaky<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> IntoJavaValue<'env> for &Flaky<'env> {
+        type Target = JObject<'env>;
+
+        fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+            self.raw
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Flaky<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Flaky { raw: s })
+        }
+    }
+
+    impl<'env> Flaky<'env> {
+        #[constructor]
+        #[retry(times = 3, on = "java.lang.IllegalStateException")]
+        pub extern "java" fn new(env: JNIEnv<'env>, key: String) -> JniResult<Self> {}
+
+        pub extern "java" fn failNext(env: JNIEnv<'env>, key: String, times: i32) -> JniResult<()> {}
+
+        pub extern "java" fn calls(env: JNIEnv<'env>, key: String) -> JniResult<i32> {}
+
+        #[retry(times = 3, on = "java.lang.IllegalStateException", backoff_ms = 10)]
+        pub extern "java" fn fetch(env: JNIEnv<'env>, key: String) -> JniResult<String> {}
+
+        #[java_name = "fetch"]
+        pub extern "java" fn fetch_once(env: JNIEnv<'env>, key: String) -> JniResult<String> {}
+
+        #[cached]
+        #[call_type(unchecked)]
+        #[java_name = "fetch"]
+        #[retry(times = 3, on = "java.lang.IllegalStateException")]
+        pub extern "java" fn fetch_unchecked(env: JNIEnv<'env>, key: String) -> String {}
+
+        // Retried on a superclass of the thrown exception
+        #[java_name = "fetch"]
+        #[retry(times = 1, on = "java.lang.IllegalArgumentException", on = "java.lang.RuntimeException")]
+        pub extern "java" fn fetch_any(env: JNIEnv<'env>, key: String) -> JniResult<String> {}
+
+        #[retry(times = 3, on = "java.lang.IllegalStateException")]
+        pub extern "java" fn validate(env: JNIEnv<'env>, key: String) -> JniResult<String> {}
+
+        #[java_name = "validate"]
+        #[retry(times = 3, on = "com.robusta.tests.NoSuchException", on = "java.lang.IllegalArgumentException")]
+        pub extern "java" fn validate_missing_class(env: JNIEnv<'env>, key: String) -> JniResult<String> {}
+
+        #[retry(times = 3, on = "java.lang.IllegalStateException")]
+        pub extern "java" fn size(&self, env: JNIEnv<'env>) -> JniResult<i32> {}
+    }
+}
+
+#[test]
+fn transient_failures_are_retried() {
+    with_env(|env| {
+        Flaky::failNext(env, "transient".into(), 2)?;
+        assert_eq!(Flaky::fetch(env, "transient".into())?, "value of transient");
+        assert_eq!(Flaky::calls(env, "transient".into())?, 3);
+        assert!(!env.exception_check()?);
+
+        // Without `#[retry]`, the first failure is final
+        Flaky::failNext(env, "once".into(), 2)?;
+        assert!(matches!(Flaky::fetch_once(env, "once".into()), Err(Error::JavaException)));
+        assert_eq!(take_exception(env)?.expect("no exception thrown").0, "java.lang.IllegalStateException");
+        assert_eq!(Flaky::calls(env, "once".into())?, 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn last_failure_is_surfaced() {
+    with_env(|env| {
+        Flaky::failNext(env, "exhausted".into(), 10)?;
+        assert!(matches!(Flaky::fetch(env, "exhausted".into()), Err(Error::JavaException)));
+        let (class, message) = take_exception(env)?.expect("no exception thrown");
+        assert_eq!(class, "java.lang.IllegalStateException");
+        assert_eq!(message.as_deref(), Some("not ready: exhausted"));
+        assert_eq!(Flaky::calls(env, "exhausted".into())?, 4);
+        Ok(())
+    })
+}
+
+#[test]
+fn unlisted_exceptions_are_not_retried() {
+    with_env(|env| {
+        Flaky::failNext(env, "invalid".into(), 2)?;
+        assert!(matches!(Flaky::validate(env, "invalid".into()), Err(Error::JavaException)));
+        assert_eq!(take_exception(env)?.expect("no exception thrown").0, "java.lang.IllegalArgumentException");
+        assert_eq!(Flaky::calls(env, "invalid".into())?, 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn exceptions_match_subclasses() {
+    with_env(|env| {
+        Flaky::failNext(env, "subclass".into(), 1)?;
+        assert_eq!(Flaky::fetch_any(env, "subclass".into())?, "value of subclass");
+        assert_eq!(Flaky::calls(env, "subclass".into())?, 2);
+        Ok(())
+    })
+}
+
+#[test]
+fn missing_exception_classes_match_nothing() {
+    with_env(|env| {
+        Flaky::failNext(env, "missing class".into(), 1)?;
+        assert_eq!(Flaky::validate_missing_class(env, "missing class".into())?, "missing class");
+        assert_eq!(Flaky::calls(env, "missing class".into())?, 2);
+        assert!(!env.exception_check()?);
+        Ok(())
+    })
+}
+
+#[test]
+fn unchecked_methods_are_retried() {
+    with_env(|env| {
+        Flaky::failNext(env, "unchecked".into(), 2)?;
+        assert_eq!(Flaky::fetch_unchecked(env, "unchecked".into()), "value of unchecked");
+        assert_eq!(Flaky::calls(env, "unchecked".into())?, 3);
+        Ok(())
+    })
+}
+
+#[test]
+fn constructors_and_instance_methods_are_retried() {
+    with_env(|env| {
+        Flaky::failNext(env, "instance".into(), 2)?;
+        let flaky = Flaky::new(env, "instance".into())?;
+        assert_eq!(Flaky::calls(env, "instance".into())?, 3);
+
+        Flaky::failNext(env, "instance".into(), 3)?;
+        assert_eq!(flaky.size(env)?, 8);
+        assert_eq!(Flaky::calls(env, "instance".into())?, 4);
+        Ok(())
+    })
+}
@@ -0,0 +1,83 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+
+use crate::jni::{Canvas, Layers, Shape};
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests.split)]
+    pub struct Shape<'env> {
+        pub raw: JObject<'env>,
+    }
+
+    impl Signature for Shape<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/split/Shape;";
+    }
+
+    impl Signature for &Shape<'_> {
+        const SIG_TYPE: &'static str = <Shape as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Shape<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Shape<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Shape { raw: s })
+        }
+    }
+
+    impl<'env> Shape<'env> {
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>) -> JniResult<Self> {}
+
+        pub extern "java" fn sides(&self, env: JNIEnv<'env>) -> JniResult<i32> {}
+    }
+
+    #[package(com.robusta.tests.split)]
+    pub struct Canvas;
+
+    impl Canvas {
+        pub extern "java" fn totalSides<'env>(env: JNIEnv<'env>, shape: &Shape<'env>, copies: i32) -> JniResult<i32> {}
+    }
+
+    #[package(com.robusta.tests.split)]
+    pub struct Layers;
+
+    impl Layers {
+        pub extern "java" fn top<'env>(env: JNIEnv<'env>) -> JniResult<Shape<'env>> {}
+    }
+}
+
+#[test]
+fn structs_of_module_files_are_bridged() {
+    with_env(|env| {
+        let shape = Shape::new(env)?;
+        assert_eq!(shape.sides(env)?, 4);
+        Ok(())
+    })
+}
+
+#[test]
+fn module_files_refer_to_each_other() {
+    with_env(|env| {
+        // `Canvas` takes a `Shape`, declared in another file
+        assert_eq!(Canvas::totalSides(env, &Shape::new(env)?, 3)?, 12);
+
+        // `Layers` is declared in a file in the directory of `Canvas`
+        assert_eq!(Layers::top(env)?.sides(env)?, 4);
+        Ok(())
+    })
+}
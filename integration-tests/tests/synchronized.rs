@@ -0,0 +1,38 @@
+use integration_tests::with_env;
+use robusta_jni::bridge;
+
+use crate::jni::Tally;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Tally;
+
+    impl Tally {
+        pub extern "java" fn incrementConcurrently(env: JNIEnv, synchronize: bool, threads: i32, times: i32) -> JniResult<i32> {}
+        pub extern "java" fn meetConcurrently(env: JNIEnv, threads: i32) -> JniResult<bool> {}
+    }
+}
+
+const THREADS: i32 = 4;
+const TIMES: i32 = 25;
+
+#[test]
+fn synchronized_methods_never_run_concurrently() {
+    with_env(|env| {
+        assert_eq!(Tally::incrementConcurrently(env, true, THREADS, TIMES)?, THREADS * TIMES);
+        Ok(())
+    })
+}
+
+#[test]
+fn other_methods_run_concurrently() {
+    with_env(|env| {
+        // Each call waits for the others to start, which they can only do without a monitor keeping them out
+        assert!(Tally::meetConcurrently(env, THREADS)?);
+        Ok(())
+    })
+}
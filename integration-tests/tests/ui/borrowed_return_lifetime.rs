@@ -0,0 +1,15 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    pub struct Parser;
+
+    impl Parser {
+        pub extern "jni" fn firstWord<'a>(text: &'a str) -> &'a str {
+            text.split(' ').next().unwrap_or_default()
+        }
+    }
+}
+
+fn main() {}
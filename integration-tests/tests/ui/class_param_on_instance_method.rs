@@ -0,0 +1,34 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{Signature, TryFromJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::{JClass, JObject};
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.example)]
+    pub struct Counter<'env> {
+        raw: JObject<'env>,
+    }
+
+    impl<'env> Signature for Counter<'env> {
+        const SIG_TYPE: &'static str = "Lcom/example/Counter;";
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Counter<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Counter { raw: s })
+        }
+    }
+
+    impl<'env> Counter<'env> {
+        pub extern "jni" fn isInstance(self, env: &JNIEnv<'env>, class: JClass<'env>) -> JniResult<bool> {
+            env.is_instance_of(self.raw, class)
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::CriticalBytes;
+
+    #[package(com.example)]
+    pub struct Images;
+
+    impl Images {
+        pub extern "jni" fn checksum<'env>(pixels: CriticalBytes<'env>, name: String) -> i64 {
+            0
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+#![deny(deprecated)]
+
+use robusta_jni::bridge;
+
+#[bridge(debug_signatures)]
+mod jni {
+    use robusta_jni::convert::Signature;
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Counter;
+
+    impl Counter {
+        pub extern "jni" fn add(value: i32, step: i64) -> i64 {
+            value as i64 + step
+        }
+
+        pub extern "jni" fn label(names: Vec<String>) -> Option<String> {
+            names.into_iter().next()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,53 @@
+#![deny(deprecated)]
+
+use robusta_jni::bridge;
+
+#[bridge(dry_run)]
+mod jni {
+    use std::marker::PhantomData;
+
+    use robusta_jni::convert::JavaArray;
+
+    #[package(com.example)]
+    pub struct Checked;
+
+    impl Checked {
+        pub extern "jni" fn shout(text: Option<&str>) -> String {
+            text.unwrap_or_default().to_uppercase()
+        }
+
+        #[java_name = "sum"]
+        pub extern "jni" fn add(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        pub extern "jni" fn sum(a: i32, b: i32) -> i32 {
+            a + b
+        }
+
+        pub extern "jni" fn cells(rows: JavaArray<Vec<i32>>) -> i32 {
+            rows.0.iter().map(Vec::len).sum::<usize>() as i32
+        }
+    }
+
+    pub struct Unpackaged;
+
+    impl Unpackaged {
+        pub extern "jni" fn answer() -> i32 {
+            42
+        }
+    }
+
+    #[package(com.example)]
+    pub struct Borrowed<'a> {
+        marker: PhantomData<&'a ()>,
+    }
+
+    impl<'a> Borrowed<'a> {
+        pub extern "jni" fn size(self) -> i32 {
+            0
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    pub struct Counter;
+
+    impl Counter {
+        pub extern "jni" fn add(value: i32) -> i32 {
+            value
+        }
+
+        pub extern "jni" fn reset() {}
+    }
+
+    impl Counter {
+        #[java_name = "add"]
+        pub extern "jni" fn add_once(value: i32) -> i32 {
+            value + 1
+        }
+
+        #[java_name = "reset"]
+        pub extern "jni" fn reset_to(value: i32) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    #[package(com.example.tools)]
+    pub struct Counter;
+
+    impl Counter {
+        pub extern "jni" fn reset() {}
+    }
+}
+
+fn main() {}
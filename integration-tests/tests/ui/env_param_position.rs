@@ -0,0 +1,23 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.example)]
+    pub struct Greeter;
+
+    impl Greeter {
+        pub extern "jni" fn greet(name: String, env: &JNIEnv) -> String {
+            let _ = env;
+            name
+        }
+
+        pub extern "jni" fn shout(env: &mut JNIEnv, name: String) -> String {
+            let _ = env;
+            name.to_uppercase()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use robusta_jni::convert::ErrorCode;
+
+#[derive(ErrorCode)]
+#[package(com.example)]
+enum Status {
+    Success = 0,
+    Pending,
+    Overflow = 1 << 40,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use std::marker::PhantomData;
+
+    #[package(com.example)]
+    pub struct Holder<'env, T: Default> {
+        value: T,
+        marker: PhantomData<&'env ()>,
+    }
+
+    impl<'env, T: Default> Holder<'env, T> {
+        pub extern "jni" fn make(value: T) -> i32 {
+            0
+        }
+
+        pub extern "jni" fn reset() -> Option<Self> {
+            None
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{DirectByteBuffer, HandleRef, Signature};
+
+    pub struct Counter {
+        value: i64,
+    }
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Buffers;
+
+    impl Buffers {
+        pub extern "jni" fn sum(buffer: DirectByteBuffer) -> i64 {
+            buffer.iter().map(|b| *b as i64).sum()
+        }
+
+        pub extern "jni" fn value(counter: HandleRef<Counter>) -> i64 {
+            counter.value
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    pub struct Greeter;
+
+    pub type Alias = Greeter;
+
+    impl Greeter {
+        pub extern "jni" fn greet() -> String {
+            "hello".to_string()
+        }
+    }
+
+    impl Alias {
+        pub extern "jni" fn greetAgain() -> String {
+            "hello again".to_string()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    pub struct Greeter;
+
+    impl Greeter {
+        pub extern "jni" fn greet() -> String {
+            "hello".to_string()
+        }
+    }
+
+    impl<T> super::Wrapper<T> {
+        pub extern "jni" fn wrap() -> i32 {
+            0
+        }
+    }
+}
+
+pub struct Wrapper<T>(T);
+
+fn main() {}
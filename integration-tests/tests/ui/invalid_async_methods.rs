@@ -0,0 +1,41 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::Signature;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JClass;
+    use robusta_jni::jni::JNIEnv;
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Indexer;
+
+    impl Indexer {
+        pub async extern "jni" fn borrowed(path: &str, data: &[u8]) -> i64 {
+            0
+        }
+
+        pub async extern "jni" fn with_env(env: JNIEnv, class: JClass, path: String) -> i64 {
+            0
+        }
+
+        pub async extern "jni" fn by_ref(&self, path: String) -> i64 {
+            0
+        }
+
+        #[reinterpret]
+        pub async extern "jni" fn converted(path: String) -> u32 {
+            0
+        }
+
+        #[synchronized]
+        pub async extern "jni" fn locked(path: String) -> i64 {
+            0
+        }
+
+        pub async extern "java" fn imported(env: JNIEnv, path: String) -> JniResult<i64> {}
+    }
+}
+
+fn main() {}
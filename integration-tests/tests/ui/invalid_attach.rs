@@ -0,0 +1,25 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.example)]
+    pub struct Counter<'env> {
+        raw: JObject<'env>,
+    }
+
+    impl<'env> Counter<'env> {
+        #[attach]
+        pub extern "java" fn current(&self) -> JniResult<i32> {}
+
+        #[attach]
+        pub extern "java" fn reset(env: JNIEnv<'env>) -> JniResult<()> {}
+
+        pub extern "java" fn total() -> JniResult<i64> {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    pub struct Codec;
+
+    impl Codec {
+        pub extern "jni" fn without_function(#[convert(source = "JString")] bytes: Vec<u8>) -> i32 {
+            bytes.len() as i32
+        }
+
+        pub extern "jni" fn misplaced(#[convert(into_with = "encode")] bytes: Vec<u8>) -> i32 {
+            bytes.len() as i32
+        }
+
+        #[convert(into_with = "encode")]
+        pub extern "jni" fn nothing_returned(bytes: Vec<u8>) {}
+    }
+}
+
+fn main() {}
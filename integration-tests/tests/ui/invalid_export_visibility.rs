@@ -0,0 +1,37 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod exported {
+    #[package(com.example)]
+    pub struct Exported;
+
+    #[export_visibility(hidden)]
+    impl Exported {
+        pub extern "jni" fn inherited() {}
+    }
+
+    #[package(com.example)]
+    pub struct Methods;
+
+    impl Methods {
+        #[export_visibility(hidden)]
+        pub extern "jni" fn hidden() {}
+
+        #[export_visibility(public)]
+        pub extern "jni" fn visible() {}
+    }
+}
+
+#[bridge(register_natives)]
+mod registered {
+    #[package(com.example)]
+    pub struct Registered;
+
+    impl Registered {
+        #[export_visibility(hidden)]
+        #[export_name = "registered_renamed"]
+        pub extern "jni" fn renamed() {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use std::marker::PhantomData;
+
+    #[package(com.example)]
+    #[instantiate(OfInt = Counter<i32>)]
+    pub struct Counter;
+
+    impl Counter {
+        pub extern "jni" fn count() -> i32 {
+            0
+        }
+    }
+
+    #[package(com.example)]
+    #[instantiate(OfString = Holder<String>, OfInt = Counter<i32>, OfPair = Holder<i32, i32>, OfString = Holder<i64>)]
+    pub struct Holder<T>(PhantomData<T>);
+
+    impl<T> Holder<T> {
+        pub extern "jni" fn version() -> i32 {
+            1
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use robusta_jni::exception::JavaException;
+
+#[derive(Debug, JavaException)]
+enum RepoError {
+    #[java_exception(java.util.NoSuchElementException)]
+    NotFound,
+    #[java_exception("")]
+    Corrupted,
+}
+
+impl std::fmt::Display for RepoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("repository error")
+    }
+}
+
+impl std::error::Error for RepoError {}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.example)]
+    pub struct Foo;
+
+    impl Foo {
+        #[java_list]
+        pub extern "jni" fn count(values: Vec<String>) -> i32 {
+            values.len() as i32
+        }
+
+        #[java_list(mutable)]
+        pub extern "jni" fn names() -> Vec<String> {
+            Vec::new()
+        }
+
+        pub extern "java" fn add(env: JNIEnv, #[java_list] value: String) -> JniResult<()> {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.example)]
+    pub struct Service;
+
+    impl Service {
+        #[retry(times = 3)]
+        pub extern "java" fn without_classes(env: JNIEnv, key: String) -> JniResult<String> {}
+
+        #[retry(times = 3, on = "java/lang/IllegalStateException")]
+        pub extern "java" fn binary_name(env: JNIEnv, key: String) -> JniResult<String> {}
+
+        #[retry(on = "java.lang.IllegalStateException")]
+        pub extern "java" fn without_times(env: JNIEnv, key: String) -> JniResult<String> {}
+
+        #[field(static)]
+        #[retry(times = 3, on = "java.lang.IllegalStateException")]
+        pub extern "java" fn get_count(env: JNIEnv) -> JniResult<i32> {}
+    }
+}
+
+fn main() {}
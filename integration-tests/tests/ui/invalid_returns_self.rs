@@ -0,0 +1,26 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.example)]
+    pub struct Builder<'env> {
+        raw: JObject<'env>,
+    }
+
+    impl<'env> Builder<'env> {
+        #[returns_self]
+        pub extern "java" fn width(&self, env: JNIEnv<'env>, width: i32) -> JniResult<Self> {}
+
+        #[returns_self]
+        pub extern "java" fn height(self, env: JNIEnv<'env>, height: i32) -> JniResult<i32> {}
+
+        #[returns_self]
+        pub extern "java" fn create(env: JNIEnv<'env>) -> JniResult<Self> {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::objects::JObject;
+
+    #[package(com.example)]
+    pub struct Foo;
+
+    impl Foo {
+        pub extern "jni" fn unquoted<'env>(#[sig(Lcom/example/Callback;)] callback: JObject<'env>) {}
+
+        pub extern "jni" fn unterminated<'env>(#[sig("Lcom/example/Callback")] callback: JObject<'env>) {}
+
+        pub extern "jni" fn dotted<'env>(#[sig("Lcom.example.Callback;")] callback: JObject<'env>) {}
+
+        pub extern "jni" fn primitive<'env>(#[sig("I")] count: JObject<'env>) {}
+
+        pub extern "jni" fn converted(#[sig("Ljava/lang/CharSequence;")] text: String) {}
+
+        #[sig("Lcom/example/Callback;")]
+        pub extern "jni" fn nothing() {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.example)]
+    pub struct Counter<'env> {
+        raw: JObject<'env>,
+    }
+
+    impl<'env> Counter<'env> {
+        #[static_method]
+        pub extern "java" fn current(&self, env: JNIEnv<'env>) -> JniResult<i32> {}
+
+        #[static_method]
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>) -> JniResult<Self> {}
+
+        pub extern "java" fn reset(env: JNIEnv<'env>) {}
+    }
+}
+
+fn main() {}
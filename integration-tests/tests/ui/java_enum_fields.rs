@@ -0,0 +1,11 @@
+use robusta_jni::convert::JavaEnum;
+
+#[derive(JavaEnum)]
+#[package(com.example)]
+enum Shape {
+    Point,
+    Circle(f64),
+    Rectangle { width: f64, height: f64 },
+}
+
+fn main() {}
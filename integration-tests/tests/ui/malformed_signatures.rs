@@ -0,0 +1,21 @@
+use robusta_jni::convert::Signature;
+
+#[derive(Signature)]
+#[signature("com/example/Missing;")]
+struct MissingPrefix;
+
+#[derive(Signature)]
+#[signature("Lcom/example/Unterminated")]
+struct Unterminated;
+
+#[derive(Signature)]
+#[signature("L;")]
+struct Empty;
+
+#[derive(Signature)]
+#[signature("Lcom.example.Dotted;")]
+enum Dotted {
+    A,
+}
+
+fn main() {}
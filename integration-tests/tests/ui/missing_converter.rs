@@ -0,0 +1,15 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    pub struct Codec;
+
+    impl Codec {
+        pub extern "jni" fn length(#[convert(with = "decode_hex")] bytes: Vec<u8>) -> i32 {
+            bytes.len() as i32
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use robusta_jni::bridge;
+
+#[bridge(path = "src/bridge")]
+mod jni {
+    include!("shapes.rs");
+
+    mod canvas {
+        include!(concat!("canvas", ".rs"));
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    pub struct Greeter;
+
+    impl Greeter {
+        pub extern "jni" fn greet() -> String {
+            "hello".to_string()
+        }
+    }
+
+    pub struct Counter;
+
+    impl Counter {
+        pub extern "jni" fn count() -> i32 {
+            0
+        }
+    }
+}
+
+fn main() {}
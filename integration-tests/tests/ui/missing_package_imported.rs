@@ -0,0 +1,15 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    pub struct Clock;
+
+    impl Clock {
+        pub extern "java" fn now(env: JNIEnv) -> JniResult<i64> {}
+    }
+}
+
+fn main() {}
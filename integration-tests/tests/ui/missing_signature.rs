@@ -0,0 +1,24 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{Signature, TryFromJavaValue};
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Accounts;
+
+    #[derive(TryFromJavaValue)]
+    #[package(com.example)]
+    pub struct User {
+        name: String,
+    }
+
+    impl Accounts {
+        pub extern "jni" fn register(users: Vec<User>, admin: Option<User>) -> i32 {
+            users.len() as i32 + admin.map_or(0, |_| 1)
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    pub struct Server;
+
+    pub struct Endpoint {
+        host: String,
+        port: i32,
+    }
+
+    pub struct ServerConfig {
+        #[flatten]
+        endpoint: Endpoint,
+        workers: i32,
+    }
+
+    impl Server {
+        pub extern "jni" fn start(#[flatten] config: ServerConfig) -> bool {
+            config.workers > 0
+        }
+    }
+}
+
+fn main() {}
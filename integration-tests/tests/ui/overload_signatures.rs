@@ -0,0 +1,41 @@
+use robusta_jni::bridge;
+use robusta_jni::convert::{Signature, TryFromJavaValue};
+use robusta_jni::jni::errors::Result as JniResult;
+use robusta_jni::jni::objects::JObject;
+use robusta_jni::jni::JNIEnv;
+
+/// The contents of a `byte[]`, whose class isn't known to the macro.
+pub struct Payload(Vec<i8>);
+
+impl Signature for Payload {
+    const SIG_TYPE: &'static str = "[B";
+}
+
+impl<'env> TryFromJavaValue<'env> for Payload {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> JniResult<Self> {
+        TryFromJavaValue::try_from(s, env).map(|bytes: robusta_jni::convert::JavaArray<i8>| Payload(bytes.0))
+    }
+}
+
+#[bridge]
+mod jni {
+    use super::Payload;
+
+    #[package(com.example)]
+    pub struct Storage;
+
+    impl Storage {
+        pub extern "jni" fn size(payload: Payload) -> i32 {
+            payload.0.len() as i32
+        }
+
+        #[java_name = "size"]
+        pub extern "jni" fn size_of_text(text: String) -> i32 {
+            text.len() as i32
+        }
+    }
+}
+
+fn main() {}
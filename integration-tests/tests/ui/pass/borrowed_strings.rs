@@ -0,0 +1,52 @@
+use std::borrow::Cow;
+
+use robusta_jni::bridge;
+#[allow(unused_imports)]
+use robusta_jni::convert::{FromJavaValue, IntoJavaValue, TryFromJavaValue, TryIntoJavaValue};
+
+#[bridge]
+mod jni {
+    use std::borrow::Cow;
+
+    #[allow(unused_imports)]
+    use robusta_jni::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Greeter;
+
+    impl Greeter {
+        pub extern "jni" fn name() -> &'static str {
+            "robusta"
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedName() -> Cow<'static, str> {
+            Cow::Borrowed("robusta")
+        }
+
+        pub extern "jni" fn names() -> Vec<&'static str> {
+            vec!["robusta", "jni"]
+        }
+
+        pub extern "jni" fn checkedName(valid: bool) -> Result<Cow<'static, str>, String> {
+            if valid {
+                Ok("robusta".into())
+            } else {
+                Err("invalid".into())
+            }
+        }
+
+        pub extern "java" fn greet(env: &JNIEnv, name: &str, aliases: Vec<Cow<str>>) -> JniResult<()> {}
+    }
+}
+
+// The conversion traits in scope don't make `Into` conversions of strings ambiguous
+fn main() {
+    let name: String = "robusta".into();
+    let cow: Cow<str> = name.as_str().into();
+    let owned: String = cow.into();
+    let _: Box<str> = owned.as_str().into();
+}
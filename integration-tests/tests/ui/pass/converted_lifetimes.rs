@@ -0,0 +1,40 @@
+use robusta_jni::bridge;
+
+mod hex {
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JString;
+    use robusta_jni::jni::JNIEnv;
+
+    pub fn from_java<'env>(hex: JString<'env>, env: JNIEnv<'env>) -> JniResult<Vec<u8>> {
+        let hex: String = env.get_string(hex)?.into();
+        Ok(hex.into_bytes())
+    }
+
+    pub fn to_java<'env>(bytes: Vec<u8>, env: JNIEnv<'env>) -> JniResult<JString<'env>> {
+        env.new_string(String::from_utf8_lossy(&bytes))
+    }
+}
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::Signature;
+    use robusta_jni::jni::objects::JString;
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Converted;
+
+    impl Converted {
+        #[convert(into_with = "super::hex::to_java", target = "JString<'env>")]
+        pub extern "jni" fn named(#[convert(with = "super::hex::from_java", source = "JString<'env>")] bytes: Vec<u8>) -> Vec<u8> {
+            bytes
+        }
+
+        #[convert(into_with = "super::hex::to_java", target = "JString<'_>")]
+        pub extern "jni" fn elided(#[convert(with = "super::hex::from_java", source = "JString<'_>")] bytes: Vec<u8>) -> Vec<u8> {
+            bytes
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use robusta_jni::convert::Signature;
+
+#[derive(Signature)]
+#[package(com.example)]
+struct Packaged;
+
+#[derive(Signature)]
+#[signature("Lcom/example/Image$Format;")]
+struct Overridden<'env> {
+    _name: &'env str,
+}
+
+#[derive(Signature)]
+#[package(com.example)]
+enum Shape {
+    Point,
+    Circle(f64),
+}
+
+fn main() {
+    assert_eq!(Packaged::SIG_TYPE, "Lcom/example/Packaged;");
+    assert_eq!(<Overridden as Signature>::SIG_TYPE, "Lcom/example/Image$Format;");
+    assert_eq!(<&Overridden as Signature>::SIG_TYPE, "Lcom/example/Image$Format;");
+    assert_eq!(<&mut Shape as Signature>::SIG_TYPE, "Lcom/example/Shape;");
+}
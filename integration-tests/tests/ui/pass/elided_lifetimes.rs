@@ -0,0 +1,43 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::Signature;
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JString;
+    use robusta_jni::jni::JNIEnv;
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Text;
+
+    impl Text {
+        pub extern "jni" fn same(text: JString<'_>) -> JString<'_> {
+            text
+        }
+
+        pub extern "jni" fn length(text: &'_ str) -> i32 {
+            text.len() as i32
+        }
+
+        pub extern "jni" fn owned<'a>(text: &'a str) -> String {
+            text.to_string()
+        }
+
+        pub extern "jni" fn named<'a>(env: &JNIEnv<'a>, text: JString<'a>) -> JniResult<JString<'a>> {
+            env.new_string(String::from(env.get_string(text)?).to_uppercase())
+        }
+    }
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Lines;
+
+    impl<'a> Lines {
+        pub extern "jni" fn first(text: JString<'a>) -> JString<'a> {
+            text
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,51 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{FromJavaValue, Signature, TryFromJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.example)]
+    pub struct Counter<'env> {
+        raw: JObject<'env>,
+    }
+
+    impl<'env> Signature for Counter<'env> {
+        const SIG_TYPE: &'static str = "Lcom/example/Counter;";
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Counter<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Counter { raw: s })
+        }
+    }
+
+    impl<'env> FromJavaValue<'env> for Counter<'env> {
+        type Source = JObject<'env>;
+
+        fn from(s: Self::Source, _env: JNIEnv<'env>) -> Self {
+            Counter { raw: s }
+        }
+    }
+
+    impl<'env> Counter<'env> {
+        pub extern "jni" fn isNull(env: &JNIEnv<'env>, other: Self) -> JniResult<bool> {
+            env.is_same_object(other.raw, JObject::null())
+        }
+
+        pub extern "jni" fn sameAs(self, env: &JNIEnv<'env>, other: Self) -> JniResult<bool> {
+            env.is_same_object(self.raw, other.raw)
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedSameAs(self, env: &JNIEnv<'env>, other: Self) -> bool {
+            env.is_same_object(self.raw, other.raw).unwrap()
+        }
+    }
+}
+
+fn main() {}
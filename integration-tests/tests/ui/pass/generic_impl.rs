@@ -0,0 +1,19 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use std::marker::PhantomData;
+
+    #[package(com.example)]
+    pub struct Greeter<'env> {
+        marker: PhantomData<&'env ()>,
+    }
+
+    impl<'env> Greeter<'env> {
+        pub extern "jni" fn greet() -> String {
+            "hello".to_string()
+        }
+    }
+}
+
+fn main() {}
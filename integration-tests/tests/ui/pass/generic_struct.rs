@@ -0,0 +1,44 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use std::marker::PhantomData;
+
+    #[package(com.example)]
+    pub struct Holder<'env, T: Default> {
+        value: T,
+        marker: PhantomData<&'env ()>,
+    }
+
+    impl<'env, T: Default> Holder<'env, T> {
+        pub fn new() -> Self {
+            Holder {
+                value: T::default(),
+                marker: PhantomData,
+            }
+        }
+
+        pub extern "jni" fn version() -> i32 {
+            1
+        }
+    }
+
+    impl<'env> Holder<'env, i32> {
+        pub extern "jni" fn defaultValue() -> i32 {
+            Self::new().value
+        }
+    }
+
+    #[package(com.example)]
+    pub struct Counter<T = i64> {
+        count: T,
+    }
+
+    impl<T> Counter<T> {
+        pub extern "jni" fn describe(name: String) -> String {
+            format!("{}: {}", name, std::any::type_name::<T>())
+        }
+    }
+}
+
+fn main() {}
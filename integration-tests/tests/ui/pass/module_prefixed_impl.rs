@@ -0,0 +1,15 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    pub struct Greeter;
+
+    impl self::Greeter {
+        pub extern "jni" fn greet() -> String {
+            "hello".to_string()
+        }
+    }
+}
+
+fn main() {}
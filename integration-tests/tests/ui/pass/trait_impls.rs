@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use robusta_jni::bridge;
+
+static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+#[bridge]
+mod jni {
+    use std::fmt;
+
+    use super::DROPS;
+
+    #[package(com.example)]
+    pub struct Foo {
+        pub name: String,
+    }
+
+    impl Foo {
+        pub extern "jni" fn greet(name: String) -> String {
+            Foo { name }.to_string()
+        }
+    }
+
+    impl fmt::Display for Foo {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "hello, {}", self.name)
+        }
+    }
+
+    impl Drop for Foo {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+}
+
+fn main() {
+    let foo = jni::Foo { name: "world".into() };
+    assert_eq!(foo.to_string(), "hello, world");
+    drop(foo);
+    assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,27 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::Signature;
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Counter;
+
+    impl Counter {
+        pub extern "jni" fn reset() -> () {}
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedReset() -> () {}
+
+        pub extern "jni" fn resetTo(value: i32) -> Result<(), String> {
+            if value < 0 {
+                Err(format!("{} is negative", value))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use robusta_jni::bridge;
+#[allow(unused_imports)]
+use robusta_jni::convert::{FromJavaValue, IntoJavaValue, TryFromJavaValue, TryIntoJavaValue};
+
+#[bridge]
+mod jni {
+    #[allow(unused_imports)]
+    use robusta_jni::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Counter;
+
+    impl Counter {
+        pub extern "jni" fn next(current: u32) -> u64 {
+            current as u64 + 1
+        }
+
+        #[call_type(unchecked)]
+        pub extern "jni" fn uncheckedNext(current: usize) -> usize {
+            current + 1
+        }
+
+        pub extern "jni" fn checkedNext(current: u64) -> Result<u64, String> {
+            current.checked_add(1).ok_or_else(|| "overflow".to_string())
+        }
+
+        pub extern "java" fn reset(env: &JNIEnv, value: u32) -> JniResult<u64> {}
+    }
+}
+
+// The conversion traits in scope don't make `Into` conversions of unsigned integers ambiguous
+fn main() {
+    let wide: u64 = 42u32.into();
+    let _: u128 = wide.into();
+}
@@ -0,0 +1,21 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example)]
+    pub struct Counter;
+
+    impl Counter {
+        extern "jni" fn count() -> i32 {
+            0
+        }
+
+        pub(crate) extern "jni" fn reset() {}
+
+        pub extern "jni" fn total() -> i64 {
+            0
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use robusta_jni::bridge;
+
+#[bridge(register_natives)]
+mod jni {
+    use std::ffi::c_void;
+
+    use robusta_jni::jni::sys::{jint, JavaVM, JNI_VERSION_1_8};
+
+    #[package(com.example)]
+    pub struct Loader;
+
+    impl Loader {
+        pub extern "jni" fn version() -> i32 {
+            1
+        }
+    }
+
+    #[no_mangle]
+    pub extern "system" fn JNI_OnLoad(_vm: *mut JavaVM, _reserved: *mut c_void) -> jint {
+        JNI_VERSION_1_8
+    }
+}
+
+fn main() {}
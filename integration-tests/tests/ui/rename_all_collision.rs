@@ -0,0 +1,24 @@
+use robusta_jni::bridge;
+
+#[bridge(rename_all = "camelCase")]
+mod jni {
+    #[package(com.example)]
+    pub struct Urls;
+
+    impl Urls {
+        pub extern "jni" fn getUrl(id: i32) -> String {
+            id.to_string()
+        }
+
+        pub extern "jni" fn get_url(id: i32) -> String {
+            id.to_string()
+        }
+
+        // Different parameters make an overload instead
+        pub extern "jni" fn get_url_(name: String) -> String {
+            name
+        }
+    }
+}
+
+fn main() {}
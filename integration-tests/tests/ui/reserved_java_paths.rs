@@ -0,0 +1,28 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    #[package(com.example.native)]
+    pub struct Greeter;
+
+    impl Greeter {
+        pub extern "jni" fn greet() -> String {
+            "hello".to_string()
+        }
+    }
+}
+
+#[bridge]
+mod exceptions {
+    #[package(com.example)]
+    pub struct Greeter;
+
+    impl Greeter {
+        #[call_type(safe(exception_class = "com.example.new.GreetingException"))]
+        pub extern "jni" fn greet() -> Result<String, String> {
+            Ok("hello".to_string())
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use std::marker::PhantomData;
+
+    #[package(com.example)]
+    pub struct Holder<T>(PhantomData<T>);
+
+    impl Holder<String> {
+        pub extern "jni" fn describe(value: String) -> String {
+            value
+        }
+    }
+
+    impl Holder<i64> {
+        pub extern "jni" fn describe(value: i64) -> String {
+            value.to_string()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use robusta_jni::convert::Signature;
+
+#[derive(Signature)]
+#[package(com.example)]
+struct Wrapper(i32);
+
+#[derive(Signature)]
+#[package(com.example)]
+union Bits {
+    int: i32,
+    float: f32,
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{Field, Signature, TryFromJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[derive(Signature)]
+    #[package(com.example)]
+    pub struct Counter<'env> {
+        count: Field<'env, i32>,
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Counter<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Counter {
+                count: Field::field_try_from(s, "com/example/Counter", "count", env)?,
+            })
+        }
+    }
+
+    impl<'env> Counter<'env> {
+        #[synchronized]
+        pub extern "jni" fn increment(&mut self) -> JniResult<()> {
+            let count = self.count.get()?;
+            self.count.set(count + 1)
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use robusta_jni::bridge;
+
+#[bridge]
+mod jni {
+    use std::marker::PhantomData;
+
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.example)]
+    #[instantiate(OfString = Holder<String>, OfLong = Holder<i64>)]
+    pub struct Holder<T>(PhantomData<T>);
+
+    impl<T> Holder<T> {
+        pub extern "jni" fn version() -> i32 {
+            1
+        }
+
+        pub extern "java" fn value(env: JNIEnv) -> JniResult<i32> {}
+    }
+
+    impl Holder<bool> {
+        pub extern "jni" fn flag() -> bool {
+            true
+        }
+    }
+}
+
+fn main() {}
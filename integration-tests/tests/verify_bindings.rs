@@ -0,0 +1,115 @@
+//! Imported methods looked up with the `verify-bindings` feature, which the integration tests enable.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use integration_tests::with_env;
+use robusta_jni::bridge;
+use robusta_jni::metadata::MethodMetadata;
+use robusta_jni::verify::{report, UnresolvedBinding};
+use robusta_jni::verify_bindings;
+
+use crate::jni::Greeter;
+
+#[bridge]
+mod jni {
+    use robusta_jni::convert::{Signature, TryFromJavaValue, TryIntoJavaValue};
+    use robusta_jni::jni::errors::Result as JniResult;
+    use robusta_jni::jni::objects::JObject;
+    use robusta_jni::jni::JNIEnv;
+
+    #[package(com.robusta.tests)]
+    pub struct Greeter<'env> {
+        pub raw: JObject<'env>,
+    }
+
+    impl Signature for Greeter<'_> {
+        const SIG_TYPE: &'static str = "Lcom/robusta/tests/Greeter;";
+    }
+
+    impl Signature for &Greeter<'_> {
+        const SIG_TYPE: &'static str = <Greeter as Signature>::SIG_TYPE;
+    }
+
+    impl<'env> TryIntoJavaValue<'env> for &Greeter<'env> {
+        type Target = JObject<'env>;
+
+        fn try_into(self, _env: JNIEnv<'env>) -> JniResult<Self::Target> {
+            Ok(self.raw)
+        }
+    }
+
+    impl<'env> TryFromJavaValue<'env> for Greeter<'env> {
+        type Source = JObject<'env>;
+
+        fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> JniResult<Self> {
+            Ok(Greeter { raw: s })
+        }
+    }
+
+    impl<'env> Greeter<'env> {
+        #[constructor]
+        pub extern "java" fn new(env: JNIEnv<'env>, greeting: String) -> JniResult<Self> {}
+
+        pub extern "java" fn greet(&self, env: JNIEnv<'env>, name: String) -> JniResult<String> {}
+
+        // `greet` takes a `String`
+        #[java_name = "greet"]
+        pub extern "java" fn greet_number(&self, env: JNIEnv<'env>, name: i32) -> JniResult<String> {}
+    }
+}
+
+const GREET_NUMBER: MethodMetadata = MethodMetadata {
+    class: "com/robusta/tests/Greeter",
+    name: "greet",
+    params: &["I"],
+    ret: "Ljava/lang/String;",
+    is_static: false,
+};
+
+/// The methods of the test module that don't exist, leaving out the other modules of the program.
+fn unresolved_in_this_module(unresolved: Vec<UnresolvedBinding>) -> Vec<UnresolvedBinding> {
+    unresolved.into_iter().filter(|b| b.module == "verify_bindings::jni").collect()
+}
+
+#[test]
+fn mismatched_methods_are_reported() {
+    with_env(|env| {
+        let unresolved = unresolved_in_this_module(verify_bindings(&env)?);
+        assert_eq!(unresolved, vec![UnresolvedBinding { module: "verify_bindings::jni", method: GREET_NUMBER }]);
+
+        assert_eq!(
+            report(&unresolved),
+            "1 imported method(s) not found:\n  com.robusta.tests.Greeter.greet(I)Ljava/lang/String; (declared in `verify_bindings::jni`)"
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn mismatched_methods_panic_before_their_first_call() {
+    with_env(|env| {
+        let greeter = Greeter::new(env, "Hello".into())?;
+
+        let panic = panic::catch_unwind(AssertUnwindSafe(|| greeter.greet_number(env, 1))).expect_err("the call was made");
+        let message = panic.downcast_ref::<String>().expect("the panic message is a `String`");
+        assert_eq!(
+            message,
+            "can't find imported method com.robusta.tests.Greeter.greet(I)Ljava/lang/String;, \
+             check its parameter and return types against the Java declaration"
+        );
+        // The method isn't called, so no `NoSuchMethodError` is pending
+        assert!(!env.exception_check()?);
+        Ok(())
+    })
+}
+
+#[test]
+fn existing_methods_are_called() {
+    with_env(|env| {
+        let greeter = Greeter::new(env, "Hello".into())?;
+        assert_eq!(greeter.greet(env, "verifier".into())?, "Hello, verifier!");
+        // Later calls don't look the method up again
+        assert_eq!(greeter.greet(env, "verifier".into())?, "Hello, verifier!");
+        Ok(())
+    })
+}
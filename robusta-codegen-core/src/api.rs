@@ -0,0 +1,347 @@
+//! Stable interface for building procedural macros on top of `#[bridge]`.
+//!
+//! A [`BridgeModule`] is a `mod` item parsed the same way `#[bridge]` parses it. It describes the classes it bridges
+//! (see [`BridgeModule::classes`]), and expands either exactly like `#[bridge]` or with the body of every exported method
+//! rewritten by a user-supplied wrapper.
+//!
+//! Errors in the module are reported through [`proc-macro-error`](https://docs.rs/proc-macro-error/1), so the entry point
+//! of a macro using this module must be annotated with `#[proc_macro_error]`:
+//!
+//! ```ignore
+//! use proc_macro::TokenStream;
+//! use proc_macro_error::proc_macro_error;
+//! use quote::quote;
+//! use robusta_codegen_core::api::BridgeModule;
+//! use syn::parse_macro_input;
+//!
+//! #[proc_macro_error]
+//! #[proc_macro_attribute]
+//! pub fn traced_bridge(_args: TokenStream, raw_input: TokenStream) -> TokenStream {
+//!     let module = parse_macro_input!(raw_input as BridgeModule);
+//!
+//!     module
+//!         .expand_with(|method, body| {
+//!             let name = &method.java_name;
+//!             quote! {{
+//!                 println!("calling {}", #name);
+//!                 #body
+//!             }}
+//!         })
+//!         .into()
+//! }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::parse::{Parse, ParseStream};
+use syn::visit::Visit;
+use syn::{Attribute, ImplItem, ImplItemMethod, ItemImpl, Visibility};
+
+use inflector::cases::camelcase::to_camel_case;
+
+use crate::manifest;
+use crate::stubs::{self, ClassNames, ImplCollector, JavaType};
+use crate::transformation::context::StructContext;
+use crate::transformation::{BridgeOptions, CallTypeAttribute, ModTransformer};
+use crate::utils::{get_abi, impl_struct_name};
+use crate::validation::JNIBridgeModule;
+
+/// A `mod` item annotated with `#[bridge]`, or with a macro built on this module.
+pub struct BridgeModule {
+    module: JNIBridgeModule,
+    options: BridgeOptions,
+}
+
+impl Parse for BridgeModule {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(BridgeModule {
+            module: input.parse()?,
+            options: BridgeOptions::default(),
+        })
+    }
+}
+
+/// A Java class bridged by a Rust struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BridgeClass {
+    /// Simple name of the Java class: the name of the Rust struct, followed by the suffix of the instantiation for generic
+    /// structs with an `#[instantiate]` attribute.
+    pub name: String,
+    /// Java package of the class, `None` for the default package.
+    pub package: Option<String>,
+    /// Exported and imported methods, in declaration order. Field accessors aren't included.
+    pub methods: Vec<BridgeMethod>,
+}
+
+impl BridgeClass {
+    /// Fully qualified Java class name, e.g. `com.example.Foo`.
+    pub fn qualified_name(&self) -> String {
+        match &self.package {
+            Some(package) => format!("{}.{}", package, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// A method of a bridged class.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BridgeMethod {
+    /// Name of the Rust method.
+    pub rust_name: String,
+    /// Name of the Java method, `<init>` for constructors.
+    pub java_name: String,
+    pub direction: Direction,
+    pub call_type: CallType,
+    /// Whether the Java method is `static`, i.e. the Rust method has no `self` receiver.
+    pub is_static: bool,
+    /// JNI method descriptor, e.g. `(ILjava/lang/String;)V`.
+    ///
+    /// Like Java stubs, descriptors are inferred from the syntax of the Rust types: types that aren't library-provided conversions
+    /// or structs bridged by the same module are described as `java/lang/Object`.
+    pub descriptor: String,
+    /// Rust's own `#[deprecated]` attribute of the method, `None` if it isn't deprecated.
+    pub deprecation: Option<Deprecation>,
+}
+
+/// Details of a `#[deprecated]` attribute.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Deprecation {
+    pub since: Option<String>,
+    pub note: Option<String>,
+}
+
+/// Which side of the bridge implements a method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Direction {
+    /// Implemented in Rust and called from Java (`extern "jni"`).
+    Exported,
+    /// Implemented in Java and called from Rust (`extern "java"`).
+    Imported,
+}
+
+/// How a method converts values and reports errors, as chosen with `#[call_type]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CallType {
+    /// `#[call_type(safe)]`, the default.
+    Safe,
+    /// `#[call_type(unchecked)]`.
+    Unchecked,
+}
+
+impl BridgeModule {
+    /// Classes bridged by the module, ordered by name.
+    pub fn classes(&self) -> Vec<BridgeClass> {
+        let mut impl_collector = ImplCollector::default();
+        impl_collector.visit_item_mod(&self.module.module_decl);
+
+        // Invalid attributes are reported when transforming the module
+        let module_call_type = parse_call_type(&self.module.module_decl.attrs);
+
+        let mut classes = Vec::new();
+        for (struct_name, package) in &self.module.package_map {
+            let class_names = ClassNames {
+                package_map: &self.module.package_map,
+                self_name: struct_name,
+                flattened_structs: &self.module.flattened_structs,
+                error_codes: &self.module.error_codes,
+                rename_all: self.module.rename_all,
+                instantiations: &self.module.instantiations,
+            };
+            let impls: Vec<(String, ItemImpl)> = impl_collector
+                .impls
+                .iter()
+                .filter(|i| impl_struct_name(i, &self.module.module_decl.ident).as_deref() == Some(struct_name.as_str()))
+                .flat_map(|i| stubs::impl_classes(&self.module, struct_name, i))
+                .collect();
+
+            // Generic structs with `#[instantiate]` are bridged to a class for each instantiation
+            let names = match self.module.instantiations.get(struct_name) {
+                Some(instantiations) => instantiations.iter().map(|i| i.class_name(struct_name)).collect(),
+                None => vec![struct_name.clone()],
+            };
+            for name in names {
+                let methods = impls
+                    .iter()
+                    .filter(|(class_name, _)| *class_name == name)
+                    .flat_map(|(_, i)| {
+                        // The innermost attribute wins: the method's, then the impl block's, then the module's
+                        let default_call_type = parse_call_type(&i.attrs).or(module_call_type).unwrap_or(CallType::Safe);
+                        i.items.iter().map(move |item| (item, default_call_type))
+                    })
+                    .filter_map(|(item, default_call_type)| match item {
+                        ImplItem::Method(m) => bridge_method(&class_names, m, default_call_type),
+                        _ => None,
+                    })
+                    .collect();
+
+                classes.push(BridgeClass {
+                    name,
+                    package: package.as_ref().map(ToString::to_string).filter(|p| !p.is_empty()),
+                    methods,
+                });
+            }
+        }
+
+        classes.sort_by(|a, b| a.name.cmp(&b.name));
+        classes
+    }
+
+    /// Applies the arguments of a `#[bridge(...)]` attribute, e.g. `preload_classes("com.example.Foo")`.
+    pub fn with_args(mut self, args: TokenStream) -> Self {
+        self.options = BridgeOptions::parse(args);
+        self.module.rename_all = self.options.rename_all;
+        self
+    }
+
+    /// Expands the module exactly like `#[bridge]`, including writing Java and Kotlin stubs and the manifest.
+    pub fn expand(self) -> TokenStream {
+        stubs::write_java_stubs(&self.module);
+        stubs::write_kotlin_stubs(&self.module);
+        manifest::write_manifest(&self.classes());
+        ModTransformer::new(self.module).with_options(self.options).transform_module()
+    }
+
+    /// Expands the module like `#[bridge]`, replacing the body of each exported method's JNI wrapper with what `wrapper` returns.
+    ///
+    /// `wrapper` receives the method and the generated body, a block converting parameters and calling the Rust method, and must
+    /// return a block evaluating to the same value. Inside it, the environment is in scope as `env: JNIEnv<'env>`.
+    pub fn expand_with<F>(self, mut wrapper: F) -> TokenStream
+    where
+        F: FnMut(&BridgeMethod, TokenStream) -> TokenStream,
+    {
+        stubs::write_java_stubs(&self.module);
+        stubs::write_kotlin_stubs(&self.module);
+
+        let classes = self.classes();
+        manifest::write_manifest(&classes);
+        let body_wrapper = move |context: &StructContext, node: &ImplItemMethod, body: TokenStream| {
+            let method = classes
+                .iter()
+                .filter(|c| c.name == context.class_name())
+                .flat_map(|c| &c.methods)
+                .find(|m| m.direction == Direction::Exported && node.sig.ident == m.rust_name);
+
+            match method {
+                Some(method) => wrapper(method, body),
+                None => body,
+            }
+        };
+
+        ModTransformer::new(self.module)
+            .with_options(self.options)
+            .with_body_wrapper(Box::new(body_wrapper))
+            .transform_module()
+    }
+}
+
+fn parse_call_type(attrs: &[Attribute]) -> Option<CallType> {
+    attrs
+        .iter()
+        .find(|a| a.path.is_ident("call_type"))
+        .and_then(|a| syn::parse2::<CallTypeAttribute>(a.to_token_stream()).ok())
+        .map(|a| match a.call_type {
+            crate::transformation::CallType::Safe(_) => CallType::Safe,
+            crate::transformation::CallType::Unchecked(_) => CallType::Unchecked,
+        })
+}
+
+fn bridge_method(class_names: &ClassNames, method: &ImplItemMethod, default_call_type: CallType) -> Option<BridgeMethod> {
+    let direction = match get_abi(&method.sig).as_deref() {
+        Some("jni") if matches!(method.vis, Visibility::Public(_)) => Direction::Exported,
+        // Field accessors don't call Java methods
+        Some("java") if !method.attrs.iter().any(|a| a.path.is_ident("field")) => Direction::Imported,
+        _ => return None,
+    };
+
+    let call_type = parse_call_type(&method.attrs).unwrap_or(default_call_type);
+    let has_java_name = method.attrs.iter().any(|a| a.path.is_ident("java_name"));
+    let is_constructor = direction == Direction::Imported && method.attrs.iter().any(|a| a.path.is_ident("constructor"));
+
+    let stub = class_names.method_stub(method);
+    let java_name = if is_constructor {
+        "<init>".to_string()
+    } else if direction == Direction::Imported && !has_java_name && class_names.rename_all.is_none() {
+        to_camel_case(&stub.name)
+    } else {
+        stub.name
+    };
+    let return_type = if is_constructor { JavaType::Void } else { stub.return_type };
+    let params: String = stub.params.iter().map(|p| p.ty.descriptor()).collect();
+
+    Some(BridgeMethod {
+        rust_name: method.sig.ident.to_string(),
+        java_name,
+        direction,
+        call_type,
+        is_static: stub.is_static,
+        descriptor: format!("({}){}", params, return_type.descriptor()),
+        deprecation: stub.deprecation.map(|d| Deprecation { since: d.since, note: d.note }),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn classes_describe_bridged_methods() {
+        let module: BridgeModule = syn::parse2(quote! {
+            mod jni {
+                #[package(com.example)]
+                pub struct Foo;
+
+                impl Foo {
+                    #[java_name = "getName"]
+                    pub extern "jni" fn name(id: i32, tags: Vec<String>) -> Option<String> {
+                        None
+                    }
+
+                    #[call_type(unchecked)]
+                    pub extern "jni" fn resize(self, env: JNIEnv, size: Self) {}
+
+                    pub extern "java" fn on_change(&self, env: JNIEnv, values: Box<[i64]>) -> JniResult<bool> {}
+
+                    #[constructor]
+                    pub extern "java" fn new(env: JNIEnv, size: i32) -> JniResult<Self> {}
+
+                    #[field]
+                    pub extern "java" fn get_size(&self, env: JNIEnv) -> JniResult<i32> {}
+
+                    fn helper() {}
+                }
+            }
+        })
+        .unwrap();
+
+        let method = |rust_name: &str, java_name: &str, direction, call_type, is_static, descriptor: &str| BridgeMethod {
+            rust_name: rust_name.into(),
+            java_name: java_name.into(),
+            direction,
+            call_type,
+            is_static,
+            descriptor: descriptor.into(),
+            deprecation: None,
+        };
+
+        assert_eq!(
+            module.classes(),
+            vec![BridgeClass {
+                name: "Foo".into(),
+                package: Some("com.example".into()),
+                methods: vec![
+                    method("name", "getName", Direction::Exported, CallType::Safe, true, "(ILjava/util/List;)Ljava/lang/String;"),
+                    method("resize", "resize", Direction::Exported, CallType::Unchecked, false, "(Lcom/example/Foo;)V"),
+                    method("on_change", "onChange", Direction::Imported, CallType::Safe, false, "([J)Z"),
+                    method("new", "<init>", Direction::Imported, CallType::Safe, true, "(I)V"),
+                ],
+            }]
+        );
+    }
+}
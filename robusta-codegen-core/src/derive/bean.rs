@@ -0,0 +1,269 @@
+//! Conversion derives for plain data structs, which have no `#[instance]` field: their fields are read with the getters of
+//! the Java class, and written with its constructor or setters, like those of a Java bean.
+
+use darling::util::Flag;
+use darling::FromMeta;
+use inflector::cases::camelcase::to_camel_case;
+use proc_macro2::{Ident, TokenStream};
+use proc_macro_error::{abort, emit_error};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_quote, Attribute, Data, DataStruct, DeriveInput, Fields, GenericParam, Generics, Meta, Type};
+
+/// The derived conversion trait.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BeanTrait {
+    From,
+    TryFrom,
+    Into,
+    TryInto,
+}
+
+impl BeanTrait {
+    fn is_checked(self) -> bool {
+        matches!(self, BeanTrait::TryFrom | BeanTrait::TryInto)
+    }
+
+    fn path(self) -> TokenStream {
+        match self {
+            BeanTrait::From => quote! { ::robusta_jni::convert::FromJavaValue },
+            BeanTrait::TryFrom => quote! { ::robusta_jni::convert::TryFromJavaValue },
+            BeanTrait::Into => quote! { ::robusta_jni::convert::IntoJavaValue },
+            BeanTrait::TryInto => quote! { ::robusta_jni::convert::TryIntoJavaValue },
+        }
+    }
+
+    /// What follows a JNI call: `?` for checked conversions, `.unwrap()` for unchecked ones.
+    fn unwrap(self) -> TokenStream {
+        if self.is_checked() {
+            quote! { ? }
+        } else {
+            quote! { .unwrap() }
+        }
+    }
+}
+
+/// Options of `#[bean]`, on the struct.
+#[derive(Default, FromMeta)]
+#[darling(default)]
+struct BeanAttribute {
+    /// Create Java objects with their no-argument constructor and a setter for each field, instead of a constructor taking
+    /// every field.
+    setters: Flag,
+}
+
+/// Options of `#[java_field]`, on the fields of the struct.
+#[derive(Default, FromMeta)]
+#[darling(default)]
+struct JavaFieldAttribute {
+    /// Name of the Java property, the field name in `camelCase` if omitted.
+    name: Option<String>,
+}
+
+fn parse_attribute<T: FromMeta + Default>(attrs: &[Attribute], name: &str) -> T {
+    let attr = match attrs.iter().find(|a| a.path.is_ident(name)) {
+        Some(attr) => attr,
+        None => return T::default(),
+    };
+
+    match attr.parse_meta() {
+        Ok(Meta::Path(_)) => T::default(),
+        Ok(meta) => T::from_meta(&meta).unwrap_or_else(|e| {
+            emit_error!(attr, "invalid `{}` attribute ({})", name, e);
+            T::default()
+        }),
+        Err(e) => {
+            emit_error!(e.span(), "invalid `{}` attribute ({})", name, e);
+            T::default()
+        }
+    }
+}
+
+/// Whether `input` is a plain data struct, converted with the accessors of its Java class.
+pub(crate) fn is_bean(input: &DeriveInput) -> bool {
+    match &input.data {
+        Data::Struct(DataStruct { fields, .. }) => !fields.iter().any(|f| f.attrs.iter().any(|a| a.path.is_ident("instance"))),
+        _ => false,
+    }
+}
+
+/// `getName`, `setName`, ... for the property `name`.
+fn accessor_name(prefix: &str, property: &str) -> String {
+    let mut chars = property.chars();
+    match chars.next() {
+        Some(first) => prefix.chars().chain(first.to_uppercase()).chain(chars).collect(),
+        None => prefix.to_string(),
+    }
+}
+
+struct BeanField {
+    ident: Ident,
+    ty: Type,
+    property: String,
+}
+
+fn bean_fields(input: &DeriveInput) -> Vec<BeanField> {
+    let fields = match &input.data {
+        Data::Struct(DataStruct { fields: Fields::Named(fields), .. }) => fields,
+        Data::Struct(DataStruct { fields: Fields::Unit, .. }) => return Vec::new(),
+        Data::Struct(DataStruct { fields, .. }) => abort!(fields, "conversions of tuple structs can't be derived";
+            help = "name the fields of `{}`, which are read with the getters of the Java class", input.ident),
+        _ => unreachable!("only structs are beans"),
+    };
+
+    fields
+        .named
+        .iter()
+        .filter_map(|f| {
+            if let Some(attr) = f.attrs.iter().find(|a| a.path.is_ident("field") || a.path.is_ident("lazy")) {
+                emit_error!(attr, "`#[{}]` fields need an `#[instance]` field holding the Java object", attr.path.get_ident().unwrap();
+                    help = "without an `#[instance]` field, the fields are read with getters and written with a constructor");
+                return None;
+            }
+
+            let ident = f.ident.clone().unwrap();
+            let property = parse_attribute::<JavaFieldAttribute>(&f.attrs, "java_field")
+                .name
+                .unwrap_or_else(|| to_camel_case(&ident.to_string()));
+
+            Some(BeanField { ident, ty: f.ty.clone(), property })
+        })
+        .collect()
+}
+
+/// The generics of `input`, with an `'env` lifetime for the trait if it doesn't have one.
+fn impl_generics(input: &DeriveInput) -> Generics {
+    let mut generics = input.generics.clone();
+    let has_env = generics.lifetimes().any(|l| l.lifetime.ident == "env");
+    if !has_env {
+        generics.params.insert(0, GenericParam::Lifetime(parse_quote! { 'env }));
+    }
+
+    generics
+}
+
+/// Implements the conversion trait `bean_trait` for the plain data struct `input`.
+pub(crate) fn bean_derive(input: DeriveInput, bean_trait: BeanTrait) -> TokenStream {
+    let fields = bean_fields(&input);
+    let setters = parse_attribute::<BeanAttribute>(&input.attrs, "bean").setters.is_some();
+
+    let name = &input.ident;
+    let generics = impl_generics(&input);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let trait_path = bean_trait.path();
+    let unwrap = bean_trait.unwrap();
+    // The conversions themselves only return a `Result` when they're checked
+    let try_convert = if bean_trait.is_checked() { quote! { ? } } else { quote! {} };
+    let idents: Vec<_> = fields.iter().map(|f| &f.ident).collect();
+
+    let body = match bean_trait {
+        BeanTrait::From | BeanTrait::TryFrom => {
+            let (method, wrap, output) = if bean_trait.is_checked() {
+                (quote! { try_from }, quote! { ::core::result::Result::Ok }, quote! { ::robusta_jni::jni::errors::Result<Self> })
+            } else {
+                (quote! { from }, quote! {}, quote! { Self })
+            };
+            let reads = fields.iter().map(|f| {
+                let BeanField { ident, ty, property } = f;
+                let getter = accessor_name("get", property);
+                quote_spanned! { ty.span() =>
+                    let #ident: #ty = #trait_path::#method(
+                        ::core::convert::TryInto::try_into(::robusta_jni::convert::JValueWrapper::from(
+                            env.call_method(source, #getter, ::robusta_jni::signature::descriptor(&[], <#ty as #trait_path>::SIG_TYPE), &[])#unwrap
+                        ))#unwrap,
+                        env,
+                    )#try_convert;
+                }
+            });
+
+            quote! {
+                type Source = ::robusta_jni::jni::objects::JObject<'env>;
+
+                fn #method(source: Self::Source, env: ::robusta_jni::jni::JNIEnv<'env>) -> #output {
+                    #(#reads)*
+
+                    #wrap(Self { #(#idents),* })
+                }
+            }
+        }
+        BeanTrait::Into | BeanTrait::TryInto => {
+            let (method, wrap, output) = if bean_trait.is_checked() {
+                (quote! { try_into }, quote! { ::core::result::Result::Ok }, quote! { ::robusta_jni::jni::errors::Result<Self::Target> })
+            } else {
+                (quote! { into }, quote! {}, quote! { Self::Target })
+            };
+            let value = |f: &BeanField| {
+                let BeanField { ident, ty, .. } = f;
+                quote_spanned! { ty.span() => ::core::convert::Into::into(<#ty as #trait_path>::#method(self.#ident, env)#try_convert) }
+            };
+            let create = if setters {
+                let writes = fields.iter().map(|f| {
+                    let ty = &f.ty;
+                    let setter = accessor_name("set", &f.property);
+                    let value = value(f);
+                    quote_spanned! { ty.span() =>
+                        env.call_method(object, #setter, ::robusta_jni::signature::descriptor(&[<#ty as #trait_path>::SIG_TYPE], "V"), &[#value])#unwrap;
+                    }
+                });
+                quote! {
+                    let object = env.new_object(class, "()V", &[])#unwrap;
+                    #(#writes)*
+                    #wrap(object)
+                }
+            } else {
+                let types = fields.iter().map(|f| &f.ty);
+                let values = fields.iter().map(value);
+                // Checked conversions return the `Result` of the constructor as is
+                let unwrap = if bean_trait.is_checked() { quote! {} } else { unwrap.clone() };
+                quote! {
+                    env.new_object(
+                        class,
+                        ::robusta_jni::signature::descriptor(&[#(<#types as #trait_path>::SIG_TYPE),*], "V"),
+                        &[#(#values),*],
+                    )#unwrap
+                }
+            };
+
+            quote! {
+                type Target = ::robusta_jni::jni::objects::JObject<'env>;
+
+                fn #method(self, env: ::robusta_jni::jni::JNIEnv<'env>) -> #output {
+                    let descriptor = <Self as ::robusta_jni::convert::Signature>::SIG_TYPE;
+                    let class = &descriptor[1..descriptor.len() - 1];
+                    #create
+                }
+            }
+        }
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #trait_path<'env> for #name #ty_generics #where_clause {
+            #body
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn accessors_are_named_after_camel_case_properties() {
+        let input: DeriveInput = parse_quote! {
+            struct User {
+                user_name: String,
+                #[java_field(name = "years")]
+                age: i32,
+            }
+        };
+
+        let accessors: Vec<_> = bean_fields(&input).iter().map(|f| (accessor_name("get", &f.property), accessor_name("set", &f.property))).collect();
+        assert_eq!(
+            accessors,
+            [("getUserName".to_string(), "setUserName".to_string()), ("getYears".to_string(), "setYears".to_string())]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::derive::bean::{bean_derive, is_bean, BeanTrait};
 use crate::derive::utils::generic_params_to_args;
 use crate::transformation::JavaPath;
 use proc_macro2::{Ident, TokenStream};
@@ -20,9 +21,14 @@ struct TraitAutoDeriveData {
     generic_args: AngleBracketedGenericArguments,
     data_fields: Vec<Field>,
     class_fields: Vec<Field>,
+    lazy_fields: Vec<Field>,
 }
 
-pub(crate) fn into_java_value_macro_derive(input: DeriveInput) -> TokenStream {
+pub fn into_java_value_macro_derive(input: DeriveInput) -> TokenStream {
+    if is_bean(&input) {
+        return bean_derive(input, BeanTrait::Into);
+    }
+
     let input_span = input.span();
     match into_java_value_macro_derive_impl(input) {
         Ok(t) => t,
@@ -72,7 +78,11 @@ fn into_java_value_macro_derive_impl(input: DeriveInput) -> syn::Result<TokenStr
     })
 }
 
-pub(crate) fn tryinto_java_value_macro_derive(input: DeriveInput) -> TokenStream {
+pub fn tryinto_java_value_macro_derive(input: DeriveInput) -> TokenStream {
+    if is_bean(&input) {
+        return bean_derive(input, BeanTrait::TryInto);
+    }
+
     let input_span = input.span();
     match tryinto_java_value_macro_derive_impl(input) {
         Ok(t) => t,
@@ -122,7 +132,11 @@ fn tryinto_java_value_macro_derive_impl(input: DeriveInput) -> syn::Result<Token
     })
 }
 
-pub(crate) fn from_java_value_macro_derive(input: DeriveInput) -> TokenStream {
+pub fn from_java_value_macro_derive(input: DeriveInput) -> TokenStream {
+    if is_bean(&input) {
+        return bean_derive(input, BeanTrait::From);
+    }
+
     let input_span = input.span();
     match from_java_value_macro_derive_impl(input) {
         Ok(t) => t,
@@ -140,6 +154,7 @@ fn from_java_value_macro_derive_impl(input: DeriveInput) -> syn::Result<TokenStr
         generic_args,
         data_fields,
         class_fields,
+        lazy_fields,
     } = get_trait_impl_components("FromJavaValue", input);
 
     let data_fields_struct_init: Vec<_> = data_fields
@@ -178,6 +193,26 @@ fn from_java_value_macro_derive_impl(input: DeriveInput) -> syn::Result<TokenStr
         })
         .collect();
 
+    let lazy_fields_struct_init: Vec<_> = lazy_fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect();
+    let lazy_fields_env_init: Vec<_> = lazy_fields
+        .iter()
+        .map(|f| {
+            let field_ident = f.ident.as_ref().unwrap();
+            let field_name = field_ident.to_string();
+            let field_type = &f.ty;
+
+            quote_spanned! { f.span() =>
+                let #field_ident: #field_type = ::robusta_jni::convert::Lazy::new(env,
+                    source,
+                    #classpath_path,
+                    #field_name).unwrap();
+            }
+        })
+        .collect();
+
     Ok(quote! {
         #instance_field_type_assertion
 
@@ -188,18 +223,24 @@ fn from_java_value_macro_derive_impl(input: DeriveInput) -> syn::Result<TokenStr
             fn from(source: Self::Source, env: ::robusta_jni::jni::JNIEnv<'env>) -> Self {
                 #(#data_fields_env_init)*
                 #(#class_fields_env_init)*
+                #(#lazy_fields_env_init)*
 
                 Self {
                     #instance_ident: ::robusta_jni::jni::objects::AutoLocal::new(env, source),
-                    #(#data_fields_struct_init),*
-                    #(#class_fields_struct_init),*
+                    #(#data_fields_struct_init,)*
+                    #(#class_fields_struct_init,)*
+                    #(#lazy_fields_struct_init,)*
                 }
             }
         }
     })
 }
 
-pub(crate) fn tryfrom_java_value_macro_derive(input: DeriveInput) -> TokenStream {
+pub fn tryfrom_java_value_macro_derive(input: DeriveInput) -> TokenStream {
+    if is_bean(&input) {
+        return bean_derive(input, BeanTrait::TryFrom);
+    }
+
     let input_span = input.span();
     match tryfrom_java_value_macro_derive_impl(input) {
         Ok(t) => t,
@@ -217,6 +258,7 @@ fn tryfrom_java_value_macro_derive_impl(input: DeriveInput) -> syn::Result<Token
         generic_args,
         data_fields,
         class_fields,
+        lazy_fields,
     } = get_trait_impl_components("FromJavaValue", input);
 
     let data_fields_struct_init: Vec<_> = data_fields
@@ -252,6 +294,23 @@ fn tryfrom_java_value_macro_derive_impl(input: DeriveInput) -> syn::Result<Token
         }
     }).collect();
 
+    let lazy_fields_struct_init: Vec<_> = lazy_fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect();
+    let lazy_fields_env_init: Vec<_> = lazy_fields.iter().map(|f| {
+        let field_ident = f.ident.as_ref().unwrap();
+        let field_name = field_ident.to_string();
+        let field_type = &f.ty;
+
+        quote_spanned! { f.span() =>
+            let #field_ident: #field_type = ::robusta_jni::convert::Lazy::new(env,
+                source,
+                #classpath_path,
+                #field_name)?;
+        }
+    }).collect();
+
     Ok(quote! {
         #instance_field_type_assertion
 
@@ -262,11 +321,13 @@ fn tryfrom_java_value_macro_derive_impl(input: DeriveInput) -> syn::Result<Token
             fn try_from(source: Self::Source, env: ::robusta_jni::jni::JNIEnv<'env>) -> ::robusta_jni::jni::errors::Result<Self> {
                 #(#data_fields_env_init)*
                 #(#class_fields_env_init)*
+                #(#lazy_fields_env_init)*
 
                 Ok(Self {
                     #instance_ident: ::robusta_jni::jni::objects::AutoLocal::new(env, source),
-                    #(#data_fields_struct_init),*
-                    #(#class_fields_struct_init),*
+                    #(#data_fields_struct_init,)*
+                    #(#class_fields_struct_init,)*
+                    #(#lazy_fields_struct_init,)*
                 })
             }
         }
@@ -351,6 +412,24 @@ fn get_trait_impl_components(trait_name: &str, input: DeriveInput) -> TraitAutoD
                 })
                 .collect();
 
+            let lazy_fields: Vec<_> = fields
+                .iter()
+                .filter(|f| {
+                    let attr = f.attrs.iter().find(|a| {
+                        a.path.get_ident().map(|i| i.to_string()).as_deref() == Some("lazy")
+                    });
+                    attr.is_some()
+                })
+                .collect();
+
+            for field in lazy_fields.iter().filter(|f| class_fields.contains(f)) {
+                emit_error!(
+                    field,
+                    "cannot have both `#[field]` and `#[lazy]` attributes";
+                    help = "`#[lazy]` fields are read once on first access, `#[field]` fields on every access"
+                )
+            }
+
             if instance_fields.len() > 1 {
                 emit_error!(
                     input_span,
@@ -404,6 +483,7 @@ fn get_trait_impl_components(trait_name: &str, input: DeriveInput) -> TraitAutoD
                         .filter(|f| {
                             f.ident.as_ref() != Some(instance_ident)
                                 && class_fields.iter().all(|g| g != f)
+                                && lazy_fields.iter().all(|g| g != f)
                         })
                         .cloned()
                         .collect();
@@ -417,6 +497,7 @@ fn get_trait_impl_components(trait_name: &str, input: DeriveInput) -> TraitAutoD
                         generic_args,
                         data_fields,
                         class_fields: class_fields.into_iter().cloned().collect(),
+                        lazy_fields: lazy_fields.into_iter().cloned().collect(),
                     }
                 }
             }
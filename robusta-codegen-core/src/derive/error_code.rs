@@ -0,0 +1,164 @@
+use std::convert::TryFrom;
+
+use inflector::cases::screamingsnakecase::to_screaming_snake_case;
+use proc_macro2::{Ident, TokenStream};
+use proc_macro_error::{abort, emit_error};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Data, DataEnum, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Lit, UnOp};
+
+use crate::stubs::{write_error_code_stub, ErrorCodeStub};
+use crate::transformation::JavaPath;
+use crate::utils::jni_function_name;
+
+pub fn error_code_macro_derive(input: DeriveInput) -> TokenStream {
+    let input_span = input.span();
+    match error_code_macro_derive_impl(input) {
+        Ok(t) => t,
+        Err(_) => quote_spanned! { input_span => },
+    }
+}
+
+/// Value of an integer literal discriminant, possibly negated, as a Java `int`.
+fn discriminant_code(expr: &Expr) -> Option<i32> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(i), .. }) => i.base10_parse().ok(),
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) => match &**expr {
+            Expr::Lit(ExprLit { lit: Lit::Int(i), .. }) => i.base10_parse::<i64>().ok().and_then(|v| i32::try_from(-v).ok()),
+            _ => None,
+        },
+        Expr::Group(g) => discriminant_code(&g.expr),
+        Expr::Paren(p) => discriminant_code(&p.expr),
+        _ => None,
+    }
+}
+
+fn error_code_macro_derive_impl(input: DeriveInput) -> syn::Result<TokenStream> {
+    let input_span = input.span();
+    let variants = match input.data {
+        Data::Enum(DataEnum { variants, .. }) => variants,
+        _ => abort!(input_span, "`ErrorCode` auto-derive implemented for enums only"),
+    };
+    if !input.generics.params.is_empty() {
+        abort!(input.generics, "error code enums can't be generic");
+    }
+
+    let package_attr = input.attrs.iter().find(|a| a.path.is_ident("package"));
+    let package = match package_attr {
+        None => abort!(input_span, "missing `#[package()]` attribute";
+            help = "add `#[package(com.example)]` before `enum {}`, with the Java package of the class declaring `nameOf`", input.ident),
+        Some(attr) => attr.parse_args::<JavaPath>()?,
+    };
+
+    let enum_name = input.ident;
+    let mut codes: Vec<(&Ident, i32)> = Vec::new();
+    for variant in &variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            emit_error!(variant.fields, "variant `{}` of an error code enum can't have fields", variant.ident);
+            continue;
+        }
+
+        match &variant.discriminant {
+            None => emit_error!(variant.ident, "variant `{}` of an error code enum needs an explicit discriminant", variant.ident;
+                help = "add `= <code>` after it, so that its code doesn't change when variants are added or reordered"),
+            Some((_, expr)) => match discriminant_code(expr) {
+                Some(code) => codes.push((&variant.ident, code)),
+                None => emit_error!(expr, "discriminant of `{}` must be an integer literal that fits in an `i32`", variant.ident;
+                    note = "Java receives the code as an `int`, and its stub declares it as a constant"),
+            },
+        }
+    }
+    if codes.len() != variants.len() {
+        return Ok(TokenStream::new());
+    }
+
+    write_error_code_stub(&ErrorCodeStub {
+        package: Some(package.clone()),
+        name: enum_name.to_string(),
+        constants: codes.iter().map(|(v, code)| (to_screaming_snake_case(&v.to_string()), *code)).collect(),
+    });
+
+    let enum_name_str = enum_name.to_string();
+    let code_table = codes.iter().map(|(v, code)| {
+        let name = v.to_string();
+        quote! { (#code, #name) }
+    });
+    let code_arms = codes.iter().map(|(v, code)| quote! { #enum_name::#v => #code });
+    let from_code_arms = codes.iter().map(|(v, code)| quote! { #code => ::core::option::Option::Some(#enum_name::#v) });
+    let name_of = format_ident!(
+        "{}",
+        jni_function_name(&package.to_string(), &enum_name_str, "nameOf"),
+        span = enum_name.span()
+    );
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl ::robusta_jni::convert::ErrorCode for #enum_name {
+            const CODES: &'static [(i32, &'static str)] = &[#(#code_table),*];
+
+            fn code(&self) -> i32 {
+                match self {
+                    #(#code_arms),*
+                }
+            }
+
+            fn from_code(code: i32) -> ::core::option::Option<Self> {
+                match code {
+                    #(#from_code_arms,)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl ::robusta_jni::convert::Signature for #enum_name {
+            const SIG_TYPE: &'static str = "I";
+        }
+
+        #[automatically_derived]
+        impl<'env> ::robusta_jni::convert::TryIntoJavaValue<'env> for #enum_name {
+            type Target = ::robusta_jni::jni::sys::jint;
+
+            fn try_into(self, _env: ::robusta_jni::jni::JNIEnv<'env>) -> ::robusta_jni::jni::errors::Result<Self::Target> {
+                ::core::result::Result::Ok(::robusta_jni::convert::ErrorCode::code(&self))
+            }
+        }
+
+        #[automatically_derived]
+        impl<'env> ::robusta_jni::convert::IntoJavaValue<'env> for #enum_name {
+            type Target = ::robusta_jni::jni::sys::jint;
+
+            fn into(self, _env: ::robusta_jni::jni::JNIEnv<'env>) -> Self::Target {
+                ::robusta_jni::convert::ErrorCode::code(&self)
+            }
+        }
+
+        #[automatically_derived]
+        impl<'env> ::robusta_jni::convert::TryFromJavaValue<'env> for #enum_name {
+            type Source = ::robusta_jni::jni::sys::jint;
+
+            fn try_from(s: Self::Source, env: ::robusta_jni::jni::JNIEnv<'env>) -> ::robusta_jni::jni::errors::Result<Self> {
+                ::robusta_jni::__private::checked_from_code(env, s, #enum_name_str)
+            }
+        }
+
+        #[automatically_derived]
+        impl<'env> ::robusta_jni::convert::FromJavaValue<'env> for #enum_name {
+            type Source = ::robusta_jni::jni::sys::jint;
+
+            fn from(s: Self::Source, _env: ::robusta_jni::jni::JNIEnv<'env>) -> Self {
+                ::robusta_jni::__private::unchecked_from_code(s, #enum_name_str)
+            }
+        }
+
+        #[no_mangle]
+        #[allow(non_snake_case)]
+        pub extern "system" fn #name_of<'env>(
+            env: ::robusta_jni::jni::JNIEnv<'env>,
+            _class: ::robusta_jni::jni::objects::JClass<'env>,
+            code: ::robusta_jni::jni::sys::jint,
+        ) -> ::robusta_jni::jni::sys::jstring {
+            ::robusta_jni::__private::java_name_of::<#enum_name>(env, code)
+        }
+    })
+}
@@ -0,0 +1,137 @@
+use inflector::cases::screamingsnakecase::to_screaming_snake_case;
+use proc_macro2::TokenStream;
+use proc_macro_error::{abort, emit_error};
+use quote::{quote, quote_spanned};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Data, DataEnum, DeriveInput, Fields, Token, Variant};
+
+use crate::transformation::utils::get_java_name;
+use crate::transformation::JavaPath;
+
+pub fn java_enum_macro_derive(input: DeriveInput) -> TokenStream {
+    let input_span = input.span();
+    match java_enum_macro_derive_impl(input) {
+        Ok(t) => t,
+        Err(_) => quote_spanned! { input_span => },
+    }
+}
+
+/// Name of the Java constant of each variant: its `#[java_name]`, or its name in `SCREAMING_SNAKE_CASE`.
+pub(crate) fn java_constant_names(variants: &Punctuated<Variant, Token![,]>) -> Vec<String> {
+    variants
+        .iter()
+        .map(|v| get_java_name(&v.attrs).map_or_else(|| to_screaming_snake_case(&v.ident.to_string()), |n| n.value()))
+        .collect()
+}
+
+fn java_enum_macro_derive_impl(input: DeriveInput) -> syn::Result<TokenStream> {
+    let input_span = input.span();
+    let variants = match input.data {
+        Data::Enum(DataEnum { variants, .. }) => variants,
+        _ => abort!(input_span, "`JavaEnum` auto-derive implemented for enums only"),
+    };
+    if !input.generics.params.is_empty() {
+        abort!(input.generics, "Java enums can't be generic");
+    }
+
+    let package_attr = input.attrs.iter().find(|a| a.path.is_ident("package"));
+    let package = match package_attr {
+        None => abort!(input_span, "missing `#[package()]` attribute";
+            help = "add `#[package(com.example)]` before `enum {}`, with the Java package of its Java enum", input.ident),
+        Some(attr) => attr.parse_args::<JavaPath>()?,
+    };
+    let by_ordinal = input.attrs.iter().any(|a| a.path.is_ident("by_ordinal"));
+
+    let with_fields: Vec<_> = variants.iter().filter(|v| !matches!(v.fields, Fields::Unit)).collect();
+    for variant in &with_fields {
+        emit_error!(variant.fields, "variant `{}` of a Java enum can't have fields", variant.ident;
+            note = "variants are converted to constants of the Java enum, which only have a name and an ordinal");
+    }
+    if !with_fields.is_empty() {
+        return Ok(TokenStream::new());
+    }
+
+    let enum_name = input.ident;
+    let class = {
+        let mut class = package.to_classpath_path();
+        if !class.is_empty() {
+            class.push('/');
+        }
+        class.push_str(&enum_name.to_string());
+        class
+    };
+    let signature = format!("L{};", class);
+    let constants = java_constant_names(&variants);
+    let index_arms = variants.iter().enumerate().map(|(i, v)| {
+        let ident = &v.ident;
+        quote! { #enum_name::#ident => #i }
+    });
+    let from_index_arms = variants.iter().enumerate().map(|(i, v)| {
+        let ident = &v.ident;
+        quote! { #i => ::core::option::Option::Some(#enum_name::#ident) }
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl ::robusta_jni::convert::JavaEnum for #enum_name {
+            const CLASS: &'static str = #class;
+            const CONSTANTS: &'static [&'static str] = &[#(#constants),*];
+            const BY_ORDINAL: bool = #by_ordinal;
+
+            fn index(&self) -> usize {
+                match self {
+                    #(#index_arms),*
+                }
+            }
+
+            fn from_index(index: usize) -> ::core::option::Option<Self> {
+                match index {
+                    #(#from_index_arms,)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl ::robusta_jni::convert::Signature for #enum_name {
+            const SIG_TYPE: &'static str = #signature;
+        }
+
+        #[automatically_derived]
+        impl<'env> ::robusta_jni::convert::TryIntoJavaValue<'env> for #enum_name {
+            type Target = ::robusta_jni::jni::objects::JObject<'env>;
+
+            fn try_into(self, env: ::robusta_jni::jni::JNIEnv<'env>) -> ::robusta_jni::jni::errors::Result<Self::Target> {
+                ::robusta_jni::__private::checked_to_java(&self, env)
+            }
+        }
+
+        #[automatically_derived]
+        impl<'env> ::robusta_jni::convert::IntoJavaValue<'env> for #enum_name {
+            type Target = ::robusta_jni::jni::objects::JObject<'env>;
+
+            fn into(self, env: ::robusta_jni::jni::JNIEnv<'env>) -> Self::Target {
+                ::robusta_jni::__private::unchecked_to_java(&self, env)
+            }
+        }
+
+        #[automatically_derived]
+        impl<'env> ::robusta_jni::convert::TryFromJavaValue<'env> for #enum_name {
+            type Source = ::robusta_jni::jni::objects::JObject<'env>;
+
+            fn try_from(s: Self::Source, env: ::robusta_jni::jni::JNIEnv<'env>) -> ::robusta_jni::jni::errors::Result<Self> {
+                ::robusta_jni::__private::checked_from_java(s, env)
+            }
+        }
+
+        #[automatically_derived]
+        impl<'env> ::robusta_jni::convert::FromJavaValue<'env> for #enum_name {
+            type Source = ::robusta_jni::jni::objects::JObject<'env>;
+
+            fn from(s: Self::Source, env: ::robusta_jni::jni::JNIEnv<'env>) -> Self {
+                ::robusta_jni::__private::unchecked_from_java(s, env)
+            }
+        }
+    })
+}
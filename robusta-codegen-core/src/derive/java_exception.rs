@@ -0,0 +1,103 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::{abort, emit_error};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, LitStr};
+
+use crate::transformation::JavaPath;
+
+pub fn java_exception_macro_derive(input: DeriveInput) -> TokenStream {
+    let type_class = exception_class(&input.attrs);
+    let name = &input.ident;
+
+    let class_expr = |class: Option<String>| match class {
+        Some(class) => quote! { ::core::option::Option::Some(#class) },
+        None => quote! { ::core::option::Option::None },
+    };
+    let body = match &input.data {
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|v| {
+                let ident = &v.ident;
+                let class = class_expr(exception_class(&v.attrs).or_else(|| type_class.clone()));
+                quote! { #name::#ident { .. } => #class }
+            });
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Struct(_) => class_expr(type_class),
+        Data::Union(_) => abort!(input.span(), "`JavaException` auto-derive implemented for enums and structs only"),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::robusta_jni::exception::IntoJavaException for #name #ty_generics #where_clause {
+            fn exception_class(&self) -> ::core::option::Option<&'static str> {
+                #body
+            }
+        }
+    }
+}
+
+/// Class given by the `#[java_exception("java.util.NoSuchElementException")]` attribute in `attrs`, with slashes.
+fn exception_class(attrs: &[Attribute]) -> Option<String> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("java_exception"))?;
+    let class = match attr.parse_args::<LitStr>() {
+        Ok(class) => class,
+        Err(_) => {
+            emit_error!(attr, "expected the name of an exception class";
+                help = "write it as a string, e.g. `#[java_exception(\"java.io.IOException\")]`");
+            return None;
+        }
+    };
+
+    match class.value().parse::<JavaPath>() {
+        Ok(path) if !class.value().is_empty() => Some(path.to_classpath_path()),
+        Ok(_) => {
+            emit_error!(class, "the exception class can't be empty");
+            None
+        }
+        Err(e) => {
+            emit_error!(class, "{}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn variants_without_class_use_the_class_of_the_enum() {
+        let input: DeriveInput = parse_quote! {
+            #[java_exception("java.lang.IllegalStateException")]
+            enum RepoError {
+                #[java_exception("java.util.NoSuchElementException")]
+                NotFound,
+                Io(std::io::Error),
+                Corrupted { offset: u64 },
+            }
+        };
+
+        let expected = quote! {
+            #[automatically_derived]
+            impl ::robusta_jni::exception::IntoJavaException for RepoError {
+                fn exception_class(&self) -> ::core::option::Option<&'static str> {
+                    match self {
+                        RepoError::NotFound { .. } => ::core::option::Option::Some("java/util/NoSuchElementException"),
+                        RepoError::Io { .. } => ::core::option::Option::Some("java/lang/IllegalStateException"),
+                        RepoError::Corrupted { .. } => ::core::option::Option::Some("java/lang/IllegalStateException"),
+                    }
+                }
+            }
+        };
+
+        assert_eq!(java_exception_macro_derive(input).to_string(), expected.to_string());
+    }
+}
@@ -0,0 +1,7 @@
+pub(crate) mod signature;
+pub(crate) mod bean;
+pub(crate) mod convert;
+pub(crate) mod error_code;
+pub(crate) mod java_enum;
+pub(crate) mod java_exception;
+mod utils;
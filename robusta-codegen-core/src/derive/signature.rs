@@ -0,0 +1,155 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::{abort, emit_error};
+use quote::{quote, quote_spanned};
+use syn::{Attribute, Data, DataStruct, DeriveInput, Fields, GenericArgument, LitStr, PathArguments};
+use syn::spanned::Spanned;
+
+use crate::transformation::JavaPath;
+use crate::validation::Instantiation;
+
+use super::utils::generic_params_to_args;
+
+pub fn signature_macro_derive(input: DeriveInput) -> TokenStream {
+    let input_span = input.span();
+    match signature_macro_derive_impl(input) {
+        Ok(t) => t,
+        Err(_) => quote_spanned! { input_span => }
+    }
+}
+
+/// Checks that `descriptor`, given in a `#[signature]` attribute, is the descriptor of a class, e.g. `Lcom/example/Foo;`.
+/// Reports an error spanned on the literal and returns `false` if it isn't.
+fn check_class_descriptor(descriptor: &LitStr) -> bool {
+    let value = descriptor.value();
+    let well_formed = value.len() > 2 && value.starts_with('L') && value.ends_with(';');
+
+    if !well_formed {
+        let class = value.trim_start_matches('L').trim_end_matches(';').replace('.', "/");
+        let example = if class.is_empty() { "com/example/Foo" } else { class.as_str() };
+        emit_error!(descriptor, "malformed class descriptor `{}`", value;
+            note = "descriptors of classes are the binary name of the class between `L` and `;`";
+            help = "try `#[signature(\"L{};\")]`", example);
+        false
+    } else if value.contains('.') {
+        emit_error!(descriptor, "class descriptor `{}` separates packages with dots", value;
+            help = "separate them with slashes: `#[signature(\"{}\")]`", value.replace('.', "/"));
+        false
+    } else {
+        true
+    }
+}
+
+/// Type signature of `type_name`, from its `#[signature]` override or its `#[package]` attribute.
+fn type_signature(type_name: &str, attrs: &[Attribute], input_span: proc_macro2::Span) -> syn::Result<Option<String>> {
+    if let Some(attr) = attrs.iter().find(|a| a.path.is_ident("signature")) {
+        let descriptor = attr.parse_args::<LitStr>()?;
+        return Ok(Some(descriptor.value()).filter(|_| check_class_descriptor(&descriptor)));
+    }
+
+    match attrs.iter().find(|a| a.path.is_ident("package")) {
+        None => abort!(input_span, "missing `#[package()]` attribute";
+            help = "add `#[package(com.example)]`, or give the descriptor of the class with `#[signature(\"Lcom/example/{};\")]`", type_name),
+        Some(attr) => {
+            let mut class = attr.parse_args::<JavaPath>()?.to_classpath_path();
+            if !class.is_empty() {
+                class.push('/');
+            }
+            Ok(Some(["L", class.as_str(), type_name, ";"].join("")))
+        }
+    }
+}
+
+fn signature_macro_derive_impl(input: DeriveInput) -> syn::Result<TokenStream> {
+    let input_span = input.span();
+
+    match &input.data {
+        Data::Struct(DataStruct { fields: Fields::Unnamed(fields), .. }) => {
+            abort!(fields, "`Signature` can't be derived for tuple structs";
+                help = "name the fields of `{}`, or implement `Signature` by hand", input.ident)
+        }
+        Data::Union(u) => abort!(u.union_token, "`Signature` can't be derived for unions";
+            note = "`Signature` auto-derive is implemented for structs with named fields, unit structs and enums"),
+        Data::Struct(_) | Data::Enum(_) => {}
+    }
+
+    let type_name = input.ident;
+    let instantiations = input.attrs.iter()
+        .filter(|a| a.path.is_ident("instantiate"))
+        .map(Instantiation::parse_attribute)
+        .collect::<syn::Result<Vec<_>>>()?;
+    if !instantiations.is_empty() {
+        if let Some(attr) = input.attrs.iter().find(|a| a.path.is_ident("signature")) {
+            abort!(attr, "`#[signature]` can't be combined with `#[instantiate]`";
+                note = "each instantiation is a class of its own, named after the `#[package]` and the instantiation");
+        }
+
+        let package = match input.attrs.iter().find(|a| a.path.is_ident("package")) {
+            None => abort!(input_span, "missing `#[package()]` attribute"),
+            Some(attr) => attr.parse_args::<JavaPath>()?,
+        };
+        let package_str = {
+            let mut s = package.to_classpath_path();
+            if !s.is_empty() {
+                s.push('/')
+            }
+            s
+        };
+
+        let name = type_name.to_string();
+        // Each instantiation is a class of its own, whose signature only depends on the lifetimes of the struct
+        let impls = instantiations.iter().flatten().map(|instantiation| {
+            let ty = &instantiation.ty;
+            let signature = ["L", package_str.as_str(), instantiation.class_name(&name).as_str(), ";"].join("");
+            let lifetimes = match ty.segments.last().map(|s| &s.arguments) {
+                Some(PathArguments::AngleBracketed(a)) => a.args.iter().filter_map(|a| match a {
+                    GenericArgument::Lifetime(l) => Some(l.clone()),
+                    _ => None,
+                }).collect(),
+                _ => Vec::new(),
+            };
+
+            quote! {
+                #[automatically_derived]
+                impl<#(#lifetimes),*> ::robusta_jni::convert::Signature for #ty {
+                    const SIG_TYPE: &'static str = #signature;
+                }
+
+                #[automatically_derived]
+                impl<#(#lifetimes),*> ::robusta_jni::convert::Signature for &#ty {
+                    const SIG_TYPE: &'static str = #signature;
+                }
+
+                #[automatically_derived]
+                impl<#(#lifetimes),*> ::robusta_jni::convert::Signature for &mut #ty {
+                    const SIG_TYPE: &'static str = #signature;
+                }
+            }
+        });
+
+        return Ok(quote! { #(#impls)* });
+    }
+
+    let signature = match type_signature(&type_name.to_string(), &input.attrs, input_span)? {
+        Some(signature) => signature,
+        None => return Ok(TokenStream::new()),
+    };
+    let generics = input.generics.clone();
+    let generic_args = generic_params_to_args(input.generics);
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl#generics ::robusta_jni::convert::Signature for #type_name#generic_args {
+            const SIG_TYPE: &'static str = #signature;
+        }
+
+        #[automatically_derived]
+        impl#generics ::robusta_jni::convert::Signature for &#type_name#generic_args {
+            const SIG_TYPE: &'static str = #signature;
+        }
+
+        #[automatically_derived]
+        impl#generics ::robusta_jni::convert::Signature for &mut #type_name#generic_args {
+            const SIG_TYPE: &'static str = #signature;
+        }
+    })
+}
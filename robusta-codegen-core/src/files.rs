@@ -0,0 +1,173 @@
+//! `include!("file.rs")` items in bridge modules, expanded with `#[bridge(path = "...")]` so that the module can span files.
+//!
+//! Attribute macros only see the tokens of their item: `include!` is expanded by rustc after `#[bridge]`, and out-of-line
+//! `mod foo;` declarations can't be in the input of attribute macros on stable Rust. With a `path` option, which gives the
+//! directory of the module's file, `#[bridge]` reads and parses the files of `include!` items itself, and bridges their items
+//! as if they had been written in place. Like with rustc, paths of `include!`s in an included file are relative to its own
+//! directory.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::TokenStream;
+use proc_macro_error::emit_error;
+use quote::ToTokens;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Item, ItemMacro, ItemMod, Lit, LitStr, Meta, NestedMeta, Token};
+
+/// Expands the `include!` items of the module `input`, if the arguments of `#[bridge(args)]` have a `path`.
+pub fn inline_module_files(args: TokenStream, input: TokenStream) -> TokenStream {
+    let dir = match module_dir(args) {
+        Some(dir) => dir,
+        None => return input,
+    };
+    // Invalid modules are reported when they're parsed again
+    let mut module: ItemMod = match syn::parse2(input.clone()) {
+        Ok(module) => module,
+        Err(_) => return input,
+    };
+
+    if let Some((_, items)) = &mut module.content {
+        *items = inline_items(std::mem::take(items), &dir);
+    }
+
+    module.into_token_stream()
+}
+
+/// The directory given with `path = "..."`, relative to the crate root.
+fn module_dir(args: TokenStream) -> Option<PathBuf> {
+    let nested = Punctuated::<NestedMeta, Token![,]>::parse_terminated.parse2(args).ok()?;
+
+    nested.into_iter().find_map(|m| match m {
+        NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("path") => match nv.lit {
+            Lit::Str(path) => Some(PathBuf::from(path.value())),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+fn is_include(item: &ItemMacro) -> bool {
+    item.mac.path.segments.last().is_some_and(|s| s.ident == "include")
+}
+
+/// `items`, with the items of the files of `include!`s in `dir` instead of them, including those in inline modules.
+fn inline_items(items: Vec<Item>, dir: &Path) -> Vec<Item> {
+    items
+        .into_iter()
+        .flat_map(|item| match item {
+            Item::Macro(include) if is_include(&include) => included_items(&include, dir),
+            Item::Mod(mut module) => {
+                if let Some((_, items)) = &mut module.content {
+                    *items = inline_items(std::mem::take(items), dir);
+                }
+                vec![Item::Mod(module)]
+            }
+            item => vec![item],
+        })
+        .collect()
+}
+
+/// The items of the file of `include`, relative to `dir`.
+fn included_items(include: &ItemMacro, dir: &Path) -> Vec<Item> {
+    let file = match include.mac.parse_body::<LitStr>() {
+        Ok(file) => file,
+        Err(_) => {
+            emit_error!(include.mac.tokens, "expected the path of a file, e.g. `include!(\"shapes.rs\")`");
+            return Vec::new();
+        }
+    };
+
+    let path = dir.join(file.value());
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").map_or_else(PathBuf::new, PathBuf::from);
+    let source = match fs::read_to_string(root.join(&path)) {
+        Ok(source) => source,
+        Err(e) => {
+            emit_error!(file, "can't read `{}`: {}", path.display(), e;
+                note = "included files are relative to the `path` of `#[bridge]`, itself relative to the crate root");
+            return Vec::new();
+        }
+    };
+
+    let parsed = match syn::parse_file(&source) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            emit_error!(file, "can't parse `{}`: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+    if let Some(attr) = parsed.attrs.first() {
+        emit_error!(file, "included files can't have inner attributes";
+            help = "move `{}` to the module including `{}`", attr.to_token_stream(), path.display());
+    }
+
+    let mut items = inline_items(parsed.items, path.parent().unwrap_or(dir));
+
+    // Changes to the file recompile the crate, like with rustc's `include!`
+    let path = LitStr::new(&root.join(&path).to_string_lossy(), include.span());
+    items.push(parse_quote! { const _: &[u8] = ::core::include_bytes!(#path); });
+    items
+}
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn included_files_are_inlined() {
+        let dir = std::env::temp_dir().join(format!("robusta-include-{}", std::process::id()));
+        fs::create_dir_all(dir.join("shapes")).unwrap();
+        fs::write(dir.join("canvas.rs"), "pub struct Canvas;").unwrap();
+        fs::write(dir.join("shapes").join("mod.rs"), "pub struct Square;\ninclude!(\"sides.rs\");").unwrap();
+        fs::write(dir.join("shapes").join("sides.rs"), "pub const SIDES: i32 = 4;").unwrap();
+
+        let dir_literal = dir.to_string_lossy();
+        let module: ItemMod = syn::parse2(inline_module_files(
+            quote! { path = #dir_literal },
+            quote! {
+                mod jni {
+                    include!("canvas.rs");
+
+                    mod shapes {
+                        include!("shapes/mod.rs");
+                    }
+                }
+            },
+        ))
+        .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let file = |path: PathBuf| path.to_string_lossy().into_owned();
+        let (canvas, shapes, sides) = (
+            file(dir.join("canvas.rs")),
+            file(dir.join("shapes").join("mod.rs")),
+            file(dir.join("shapes").join("sides.rs")),
+        );
+        let expected: ItemMod = parse_quote! {
+            mod jni {
+                pub struct Canvas;
+                const _: &[u8] = ::core::include_bytes!(#canvas);
+
+                mod shapes {
+                    pub struct Square;
+                    pub const SIDES: i32 = 4;
+                    const _: &[u8] = ::core::include_bytes!(#sides);
+                    const _: &[u8] = ::core::include_bytes!(#shapes);
+                }
+            }
+        };
+
+        assert_eq!(module.into_token_stream().to_string(), expected.into_token_stream().to_string());
+    }
+
+    #[test]
+    fn modules_without_path_are_left_alone() {
+        let module = quote! { mod jni { include!("shapes.rs"); } };
+
+        assert_eq!(inline_module_files(quote! { register_natives }, module.clone()).to_string(), module.to_string());
+    }
+}
@@ -0,0 +1,31 @@
+//! Code generation behind the procedural macros of [`robusta_jni`](https://docs.rs/robusta_jni).
+//!
+//! The [`api`] module lets other procedural macros parse and expand `#[bridge]` modules, e.g. to instrument every exported method.
+//! It is the only part of this crate covered by semantic versioning: everything else is an implementation detail of
+//! `robusta-codegen` and can change in any release.
+
+extern crate proc_macro;
+
+mod files;
+mod manifest;
+mod stubs;
+mod transformation;
+mod utils;
+mod validation;
+mod derive;
+
+pub mod api;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::derive::convert::{
+        from_java_value_macro_derive, into_java_value_macro_derive, tryfrom_java_value_macro_derive,
+        tryinto_java_value_macro_derive,
+    };
+    pub use crate::derive::error_code::error_code_macro_derive;
+    pub use crate::derive::java_enum::java_enum_macro_derive;
+    pub use crate::derive::java_exception::java_exception_macro_derive;
+    pub use crate::derive::signature::signature_macro_derive;
+    pub use crate::files::inline_module_files;
+    pub use crate::transformation::dry_run::bridge_dry_run;
+}
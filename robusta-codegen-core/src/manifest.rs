@@ -0,0 +1,242 @@
+//! Machine-readable manifest of the exported methods.
+//!
+//! When the `ROBUSTA_MANIFEST` environment variable is set at compile time, the `#[bridge]` macro writes the class, Java name,
+//! descriptor and call type of every exported method to the JSON file it names, with their deprecation, so that Java builds
+//! can check which native methods exist. Each module replaces the entries of its own classes, keeping those of the other
+//! modules of the crate, and entries are sorted so that the file only changes when the methods do.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use proc_macro2::Span;
+use proc_macro_error::emit_error;
+use serde::{Deserialize, Serialize};
+
+use crate::api::{BridgeClass, CallType, Direction};
+
+pub(crate) const MANIFEST_VAR: &str = "ROBUSTA_MANIFEST";
+
+#[derive(Serialize, Deserialize, Default)]
+struct Manifest {
+    methods: Vec<ManifestMethod>,
+}
+
+/// An exported method, as listed in the manifest.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct ManifestMethod {
+    /// Binary name of the class, e.g. `com/example/Foo`.
+    class: String,
+    name: String,
+    descriptor: String,
+    /// `safe` or `unchecked`.
+    call_type: String,
+    is_static: bool,
+    deprecated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    since: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+fn binary_name(class: &BridgeClass) -> String {
+    class.qualified_name().replace('.', "/")
+}
+
+fn manifest_methods(classes: &[BridgeClass]) -> Vec<ManifestMethod> {
+    classes
+        .iter()
+        .flat_map(|class| class.methods.iter().map(move |method| (binary_name(class), method)))
+        .filter(|(_, method)| method.direction == Direction::Exported)
+        .map(|(class, method)| ManifestMethod {
+            class,
+            name: method.java_name.clone(),
+            descriptor: method.descriptor.clone(),
+            call_type: match method.call_type {
+                CallType::Safe => "safe",
+                CallType::Unchecked => "unchecked",
+            }
+            .into(),
+            is_static: method.is_static,
+            deprecated: method.deprecation.is_some(),
+            since: method.deprecation.as_ref().and_then(|d| d.since.clone()),
+            note: method.deprecation.as_ref().and_then(|d| d.note.clone()),
+        })
+        .collect()
+}
+
+/// The manifest `existing` (empty if there's none yet), with the entries of `classes` replaced by their current methods.
+fn merge(existing: &str, classes: &[BridgeClass]) -> Result<String, serde_json::Error> {
+    let mut manifest: Manifest = if existing.trim().is_empty() {
+        Manifest::default()
+    } else {
+        serde_json::from_str(existing)?
+    };
+
+    let replaced: BTreeSet<String> = classes.iter().map(binary_name).collect();
+    manifest.methods.retain(|m| !replaced.contains(&m.class));
+    manifest.methods.extend(manifest_methods(classes));
+    manifest.methods.sort();
+    manifest.methods.dedup();
+
+    let mut json = serde_json::to_string_pretty(&manifest)?;
+    json.push('\n');
+    Ok(json)
+}
+
+/// Writes the exported methods of `classes` to the manifest if `ROBUSTA_MANIFEST` is set.
+pub(crate) fn write_manifest(classes: &[BridgeClass]) {
+    let path = match std::env::var_os(MANIFEST_VAR) {
+        Some(path) => path,
+        None => return,
+    };
+    let path = Path::new(&path);
+
+    let existing = std::fs::read_to_string(path).unwrap_or_default();
+    let manifest = match merge(&existing, classes) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            emit_error!(Span::call_site(), "can't update the bridge manifest `{}` ({})", path.display(), e;
+                help = "delete it to write a new one");
+            return;
+        }
+    };
+    if manifest == existing {
+        return;
+    }
+
+    let result = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(path, manifest));
+    if let Err(e) = result {
+        emit_error!(Span::call_site(), "can't write the bridge manifest `{}` ({})", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+
+    use crate::api::BridgeModule;
+
+    use super::*;
+
+    fn classes(module: proc_macro2::TokenStream) -> Vec<BridgeClass> {
+        syn::parse2::<BridgeModule>(module).unwrap().classes()
+    }
+
+    #[test]
+    fn manifest_lists_exported_methods_sorted() {
+        let classes = classes(quote! {
+            #[call_type(unchecked)]
+            mod jni {
+                #[package(com.example)]
+                pub struct Foo;
+
+                impl Foo {
+                    #[deprecated(since = "1.2", note = "use `sum` instead")]
+                    pub extern "jni" fn total(values: Vec<i32>) -> i64 {}
+
+                    #[call_type(safe)]
+                    pub extern "jni" fn sum(self, values: Vec<i32>) -> i64 {}
+
+                    pub extern "java" fn notify(&self, env: JNIEnv) -> JniResult<()> {}
+                }
+
+                #[package(com.example.tools)]
+                pub struct Bar;
+
+                #[call_type(safe)]
+                impl Bar {
+                    #[deprecated]
+                    pub extern "jni" fn reset() {}
+                }
+            }
+        });
+
+        assert_eq!(
+            merge("", &classes).unwrap(),
+            r#"{
+  "methods": [
+    {
+      "class": "com/example/Foo",
+      "name": "sum",
+      "descriptor": "(Ljava/util/List;)J",
+      "call_type": "safe",
+      "is_static": false,
+      "deprecated": false
+    },
+    {
+      "class": "com/example/Foo",
+      "name": "total",
+      "descriptor": "(Ljava/util/List;)J",
+      "call_type": "unchecked",
+      "is_static": true,
+      "deprecated": true,
+      "since": "1.2",
+      "note": "use `sum` instead"
+    },
+    {
+      "class": "com/example/tools/Bar",
+      "name": "reset",
+      "descriptor": "()V",
+      "call_type": "safe",
+      "is_static": true,
+      "deprecated": true
+    }
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn modules_replace_the_entries_of_their_classes() {
+        let first = classes(quote! {
+            mod first {
+                #[package(com.example)]
+                pub struct Foo;
+
+                impl Foo {
+                    pub extern "jni" fn old() {}
+                }
+            }
+        });
+        let second = classes(quote! {
+            mod second {
+                #[package(com.example)]
+                pub struct Bar;
+
+                impl Bar {
+                    pub extern "jni" fn run() {}
+                }
+            }
+        });
+        let first_updated = classes(quote! {
+            mod first {
+                #[package(com.example)]
+                pub struct Foo;
+
+                impl Foo {
+                    pub extern "jni" fn new(value: i32) {}
+                }
+            }
+        });
+
+        let manifest = merge("", &first).unwrap();
+        let manifest = merge(&manifest, &second).unwrap();
+        let manifest = merge(&manifest, &first_updated).unwrap();
+        // Expanding a module again leaves the manifest unchanged
+        assert_eq!(merge(&manifest, &first_updated).unwrap(), manifest);
+
+        let parsed: Manifest = serde_json::from_str(&manifest).unwrap();
+        let methods: Vec<(&str, &str, &str)> = parsed.methods.iter().map(|m| (m.class.as_str(), m.name.as_str(), m.descriptor.as_str())).collect();
+        assert_eq!(methods, [("com/example/Bar", "run", "()V"), ("com/example/Foo", "new", "(I)V")]);
+    }
+
+    #[test]
+    fn invalid_manifests_are_reported() {
+        assert!(merge("not json", &[]).is_err());
+    }
+}
@@ -0,0 +1,293 @@
+use std::fmt::Write;
+
+use crate::stubs::types::{self, Language};
+use crate::stubs::{ClassStub, ErrorCodeStub, JavaType, MethodStub};
+
+fn type_name(ty: &JavaType) -> String {
+    types::type_name(ty, Language::Java)
+}
+
+fn write_method_header(out: &mut String, method: &MethodStub) {
+    if let Some(message) = method.deprecation_message() {
+        let description = if message.is_empty() { message } else { format!(" {}", message) };
+        writeln!(out, "    /**\n     * @deprecated{}\n     */", description).unwrap();
+        writeln!(out, "    @Deprecated").unwrap();
+    }
+}
+
+fn modifiers(method: &MethodStub) -> &'static str {
+    if method.is_static {
+        "public static"
+    } else {
+        "public"
+    }
+}
+
+fn write_native_method(out: &mut String, method: &MethodStub) {
+    let params: Vec<_> = method
+        .params
+        .iter()
+        .map(|p| format!("{} {}", type_name(&p.ty), p.name))
+        .collect();
+
+    write_method_header(out, method);
+    writeln!(
+        out,
+        "    {} native {} {}({});",
+        modifiers(method),
+        type_name(&method.return_type),
+        method.name,
+        params.join(", ")
+    )
+    .unwrap();
+}
+
+/// Writes an overload for each trailing parameter with a default value, passing `null` in its place.
+fn write_default_overloads(out: &mut String, method: &MethodStub) {
+    let required_params = method.params.iter().take_while(|p| !p.has_default).count();
+
+    for arity in required_params..method.params.len() {
+        let params: Vec<_> = method.params[..arity]
+            .iter()
+            .map(|p| format!("{} {}", type_name(&p.ty), p.name))
+            .collect();
+        let args: Vec<_> = method
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, p)| if i < arity { p.name.as_str() } else { "null" })
+            .collect();
+        let return_keyword = if method.return_type == JavaType::Void { "" } else { "return " };
+
+        writeln!(out).unwrap();
+        write_method_header(out, method);
+        writeln!(
+            out,
+            "    {} {} {}({}) {{\n        {}{}({});\n    }}",
+            modifiers(method),
+            type_name(&method.return_type),
+            method.name,
+            params.join(", "),
+            return_keyword,
+            method.name,
+            args.join(", ")
+        )
+        .unwrap();
+    }
+}
+
+pub(crate) fn render(class: &ClassStub) -> String {
+    let mut out = String::new();
+    writeln!(out, "// Generated by robusta from the `#[bridge]` module declaring `{}`. Do not edit.", class.struct_name).unwrap();
+
+    if let Some(package) = class.package.as_ref().map(ToString::to_string).filter(|p| !p.is_empty()) {
+        writeln!(out, "package {};", package).unwrap();
+    }
+
+    if class.constants.is_empty() {
+        writeln!(out, "\npublic class {} {{", class.name).unwrap();
+    } else {
+        writeln!(out, "\npublic enum {} {{", class.name).unwrap();
+        writeln!(out, "    {};", class.constants.join(",\n    ")).unwrap();
+    }
+    for (i, method) in class.methods.iter().enumerate() {
+        if i > 0 || !class.constants.is_empty() {
+            writeln!(out).unwrap();
+        }
+
+        write_native_method(&mut out, method);
+        write_default_overloads(&mut out, method);
+    }
+    writeln!(out, "}}").unwrap();
+
+    out
+}
+
+pub(crate) fn render_error_codes(stub: &ErrorCodeStub) -> String {
+    let mut out = String::new();
+    writeln!(out, "// Generated by robusta from `#[derive(ErrorCode)]` on `{}`. Do not edit.", stub.name).unwrap();
+
+    if let Some(package) = stub.package.as_ref().map(ToString::to_string).filter(|p| !p.is_empty()) {
+        writeln!(out, "package {};", package).unwrap();
+    }
+
+    writeln!(out, "\npublic final class {} {{", stub.name).unwrap();
+    for (name, code) in &stub.constants {
+        writeln!(out, "    public static final int {} = {};", name, code).unwrap();
+    }
+    writeln!(out, "\n    private {}() {{}}\n", stub.name).unwrap();
+    writeln!(out, "    /**\n     * Name of the variant with code {{@code code}}, or {{@code null}} if there's none.\n     */").unwrap();
+    writeln!(out, "    public static native String nameOf(int code);").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use crate::stubs::{ClassStub, ErrorCodeStub, JavaType, MethodStub, ParamStub};
+    use crate::transformation::utils::Deprecation;
+    use crate::transformation::JavaPath;
+
+    use super::{render, render_error_codes};
+
+    #[test]
+    fn stub_declares_native_methods() {
+        let class = ClassStub {
+            package: Some(JavaPath::from_str("com.example").unwrap()),
+            name: "Foo".into(),
+            struct_name: "Foo".into(),
+            constants: Vec::new(),
+            methods: vec![
+                MethodStub {
+                    name: "sum".into(),
+                    is_static: true,
+                    params: vec![ParamStub {
+                        name: "values".into(),
+                        ty: JavaType::ArrayList(Box::new(JavaType::Object("java.lang.Integer".into()))),
+                        has_default: false,
+                    }],
+                    return_type: JavaType::Primitive("long"),
+                    deprecation: None,
+                },
+                MethodStub {
+                    name: "resize".into(),
+                    is_static: false,
+                    params: vec![
+                        ParamStub {
+                            name: "width".into(),
+                            ty: JavaType::Primitive("int"),
+                            has_default: false,
+                        },
+                        ParamStub {
+                            name: "height".into(),
+                            ty: JavaType::Object("java.lang.Integer".into()),
+                            has_default: true,
+                        },
+                    ],
+                    return_type: JavaType::Void,
+                    deprecation: None,
+                },
+            ],
+        };
+
+        assert_eq!(
+            render(&class),
+            "// Generated by robusta from the `#[bridge]` module declaring `Foo`. Do not edit.
+package com.example;
+
+public class Foo {
+    public static native long sum(java.util.ArrayList<Integer> values);
+
+    public native void resize(int width, Integer height);
+
+    public void resize(int width) {
+        resize(width, null);
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn enum_stub_declares_constants() {
+        let class = ClassStub {
+            package: Some(JavaPath::from_str("com.example").unwrap()),
+            name: "Status".into(),
+            struct_name: "Status".into(),
+            constants: vec!["OK".into(), "DEGRADED".into()],
+            methods: vec![MethodStub {
+                name: "isHealthy".into(),
+                is_static: false,
+                params: Vec::new(),
+                return_type: JavaType::Primitive("boolean"),
+                deprecation: None,
+            }],
+        };
+
+        assert_eq!(
+            render(&class),
+            "// Generated by robusta from the `#[bridge]` module declaring `Status`. Do not edit.
+package com.example;
+
+public enum Status {
+    OK,
+    DEGRADED;
+
+    public native boolean isHealthy();
+}
+"
+        );
+    }
+
+    #[test]
+    fn deprecated_methods_are_annotated() {
+        let method = |name: &str, deprecation| MethodStub {
+            name: name.into(),
+            is_static: true,
+            params: Vec::new(),
+            return_type: JavaType::Void,
+            deprecation,
+        };
+        let class = ClassStub {
+            package: None,
+            name: "Foo".into(),
+            struct_name: "Foo".into(),
+            constants: Vec::new(),
+            methods: vec![
+                method("reset", Some(Deprecation { since: Some("1.2".into()), note: Some("use clear".into()) })),
+                method("flush", Some(Deprecation::default())),
+            ],
+        };
+
+        assert_eq!(
+            render(&class),
+            "// Generated by robusta from the `#[bridge]` module declaring `Foo`. Do not edit.
+
+public class Foo {
+    /**
+     * @deprecated since 1.2: use clear
+     */
+    @Deprecated
+    public static native void reset();
+
+    /**
+     * @deprecated
+     */
+    @Deprecated
+    public static native void flush();
+}
+"
+        );
+    }
+
+    #[test]
+    fn error_code_stub_declares_constants() {
+        let stub = ErrorCodeStub {
+            package: Some(JavaPath::from_str("com.example").unwrap()),
+            name: "Status".into(),
+            constants: vec![("OK".into(), 0), ("NOT_FOUND".into(), -2)],
+        };
+
+        assert_eq!(
+            render_error_codes(&stub),
+            "// Generated by robusta from `#[derive(ErrorCode)]` on `Status`. Do not edit.
+package com.example;
+
+public final class Status {
+    public static final int OK = 0;
+    public static final int NOT_FOUND = -2;
+
+    private Status() {}
+
+    /**
+     * Name of the variant with code {@code code}, or {@code null} if there's none.
+     */
+    public static native String nameOf(int code);
+}
+"
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use std::fmt::Write;
+
+use crate::stubs::types::{self, Language};
+use crate::stubs::{ClassStub, ErrorCodeStub, JavaType, MethodStub};
+
+/// Hard keywords of Kotlin, which are only valid names between backticks.
+const KOTLIN_KEYWORDS: [&str; 28] = [
+    "as", "break", "class", "continue", "do", "else", "false", "for", "fun", "if", "in", "interface", "is", "null", "object",
+    "package", "return", "super", "this", "throw", "true", "try", "typealias", "typeof", "val", "var", "when", "while",
+];
+
+fn type_name(ty: &JavaType) -> String {
+    types::type_name(ty, Language::Kotlin)
+}
+
+fn identifier(name: &str) -> String {
+    if KOTLIN_KEYWORDS.contains(&name) {
+        format!("`{}`", name)
+    } else {
+        name.into()
+    }
+}
+
+fn string_literal(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"").replace('$', "\\$"))
+}
+
+fn write_package(out: &mut String, package: Option<String>) {
+    if let Some(package) = package.filter(|p| !p.is_empty()) {
+        let segments: Vec<_> = package.split('.').map(identifier).collect();
+        writeln!(out, "package {}", segments.join(".")).unwrap();
+    }
+}
+
+/// Return type of the declaration, left out for `Unit`.
+fn return_type(method: &MethodStub) -> String {
+    match &method.return_type {
+        JavaType::Void => String::new(),
+        ty => format!(": {}", type_name(ty)),
+    }
+}
+
+fn write_method_header(out: &mut String, indent: &str, method: &MethodStub) {
+    if let Some(message) = method.deprecation_message() {
+        writeln!(out, "{}@Deprecated({})", indent, string_literal(&message)).unwrap();
+    }
+    // Functions of the companion object are static methods of the class
+    if method.is_static {
+        writeln!(out, "{}@JvmStatic", indent).unwrap();
+    }
+}
+
+/// `override ` for methods overriding a method of `Any`, which Kotlin doesn't declare implicitly like Java.
+fn modifiers(method: &MethodStub) -> &'static str {
+    if !method.is_static && method.params.is_empty() && matches!(method.name.as_str(), "toString" | "hashCode") {
+        "override "
+    } else {
+        ""
+    }
+}
+
+fn write_external_fun(out: &mut String, indent: &str, method: &MethodStub) {
+    let params: Vec<_> = method
+        .params
+        .iter()
+        .map(|p| format!("{}: {}", identifier(&p.name), type_name(&p.ty)))
+        .collect();
+
+    write_method_header(out, indent, method);
+    writeln!(
+        out,
+        "{}{}external fun {}({}){}",
+        indent,
+        modifiers(method),
+        identifier(&method.name),
+        params.join(", "),
+        return_type(method)
+    )
+    .unwrap();
+}
+
+/// Writes an overload for each trailing parameter with a default value, passing `null` in its place.
+fn write_default_overloads(out: &mut String, indent: &str, method: &MethodStub) {
+    let required_params = method.params.iter().take_while(|p| !p.has_default).count();
+
+    for arity in required_params..method.params.len() {
+        let params: Vec<_> = method.params[..arity]
+            .iter()
+            .map(|p| format!("{}: {}", identifier(&p.name), type_name(&p.ty)))
+            .collect();
+        let args: Vec<_> = method
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, p)| if i < arity { identifier(&p.name) } else { "null".into() })
+            .collect();
+
+        writeln!(out).unwrap();
+        write_method_header(out, indent, method);
+        writeln!(
+            out,
+            "{}fun {}({}){} = {}({})",
+            indent,
+            identifier(&method.name),
+            params.join(", "),
+            return_type(method),
+            identifier(&method.name),
+            args.join(", ")
+        )
+        .unwrap();
+    }
+}
+
+fn write_functions(out: &mut String, indent: &str, methods: &[&MethodStub]) {
+    for (i, method) in methods.iter().enumerate() {
+        if i > 0 {
+            writeln!(out).unwrap();
+        }
+
+        write_external_fun(out, indent, method);
+        write_default_overloads(out, indent, method);
+    }
+}
+
+pub(crate) fn render(class: &ClassStub) -> String {
+    let mut out = String::new();
+    writeln!(out, "// Generated by robusta from the `#[bridge]` module declaring `{}`. Do not edit.", class.struct_name).unwrap();
+    write_package(&mut out, class.package.as_ref().map(ToString::to_string));
+
+    // Java stubs are classes that can be extended, like Kotlin's `open` classes
+    if class.constants.is_empty() {
+        writeln!(out, "\nopen class {} {{", identifier(&class.name)).unwrap();
+    } else {
+        writeln!(out, "\nenum class {} {{", identifier(&class.name)).unwrap();
+        writeln!(out, "    {};", class.constants.join(",\n    ")).unwrap();
+    }
+
+    let (static_methods, methods): (Vec<_>, Vec<_>) = class.methods.iter().partition(|m| m.is_static);
+    if !methods.is_empty() {
+        if !class.constants.is_empty() {
+            writeln!(out).unwrap();
+        }
+        write_functions(&mut out, "    ", &methods);
+    }
+    if !static_methods.is_empty() {
+        if !methods.is_empty() || !class.constants.is_empty() {
+            writeln!(out).unwrap();
+        }
+        writeln!(out, "    companion object {{").unwrap();
+        write_functions(&mut out, "        ", &static_methods);
+        writeln!(out, "    }}").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    out
+}
+
+pub(crate) fn render_error_codes(stub: &ErrorCodeStub) -> String {
+    let mut out = String::new();
+    writeln!(out, "// Generated by robusta from `#[derive(ErrorCode)]` on `{}`. Do not edit.", stub.name).unwrap();
+    write_package(&mut out, stub.package.as_ref().map(ToString::to_string));
+
+    writeln!(out, "\nclass {} private constructor() {{", identifier(&stub.name)).unwrap();
+    writeln!(out, "    companion object {{").unwrap();
+    for (name, code) in &stub.constants {
+        writeln!(out, "        const val {} = {}", name, code).unwrap();
+    }
+    writeln!(out, "\n        /**\n         * Name of the variant with code [code], or `null` if there's none.\n         */").unwrap();
+    writeln!(out, "        @JvmStatic\n        external fun nameOf(code: Int): String?").unwrap();
+    writeln!(out, "    }}\n}}").unwrap();
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use crate::stubs::{ClassStub, ErrorCodeStub, JavaType, MethodStub, ParamStub};
+    use crate::transformation::utils::Deprecation;
+    use crate::transformation::JavaPath;
+
+    use super::{render, render_error_codes};
+
+    #[test]
+    fn stub_declares_external_functions() {
+        let class = ClassStub {
+            package: Some(JavaPath::from_str("com.example").unwrap()),
+            name: "Foo".into(),
+            struct_name: "Foo".into(),
+            constants: Vec::new(),
+            methods: vec![
+                MethodStub {
+                    name: "sum".into(),
+                    is_static: true,
+                    params: vec![ParamStub {
+                        name: "values".into(),
+                        ty: JavaType::List(Box::new(JavaType::Object("java.lang.Integer".into()))),
+                        has_default: false,
+                    }],
+                    return_type: JavaType::Primitive("long"),
+                    deprecation: None,
+                },
+                MethodStub {
+                    name: "resize".into(),
+                    is_static: false,
+                    params: vec![
+                        ParamStub {
+                            name: "width".into(),
+                            ty: JavaType::Primitive("int"),
+                            has_default: false,
+                        },
+                        ParamStub {
+                            name: "height".into(),
+                            ty: JavaType::Object("java.lang.Integer".into()).nullable(),
+                            has_default: true,
+                        },
+                    ],
+                    return_type: JavaType::Void,
+                    deprecation: None,
+                },
+                MethodStub {
+                    name: "find".into(),
+                    is_static: false,
+                    params: vec![ParamStub {
+                        name: "in".into(),
+                        ty: JavaType::Array(Box::new(JavaType::Primitive("byte"))),
+                        has_default: false,
+                    }],
+                    return_type: JavaType::Object("java.lang.String".into()).nullable(),
+                    deprecation: None,
+                },
+            ],
+        };
+
+        assert_eq!(
+            render(&class),
+            "// Generated by robusta from the `#[bridge]` module declaring `Foo`. Do not edit.
+package com.example
+
+open class Foo {
+    external fun resize(width: Int, height: Int?)
+
+    fun resize(width: Int) = resize(width, null)
+
+    external fun find(`in`: ByteArray): String?
+
+    companion object {
+        @JvmStatic
+        external fun sum(values: List<Int>): Long
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn enum_stub_declares_constants() {
+        let class = ClassStub {
+            package: Some(JavaPath::from_str("com.example").unwrap()),
+            name: "Status".into(),
+            struct_name: "Status".into(),
+            constants: vec!["OK".into(), "DEGRADED".into()],
+            methods: vec![MethodStub {
+                name: "isHealthy".into(),
+                is_static: false,
+                params: Vec::new(),
+                return_type: JavaType::Primitive("boolean"),
+                deprecation: None,
+            }],
+        };
+
+        assert_eq!(
+            render(&class),
+            "// Generated by robusta from the `#[bridge]` module declaring `Status`. Do not edit.
+package com.example
+
+enum class Status {
+    OK,
+    DEGRADED;
+
+    external fun isHealthy(): Boolean
+}
+"
+        );
+    }
+
+    #[test]
+    fn deprecated_functions_are_annotated() {
+        let method = |name: &str, deprecation| MethodStub {
+            name: name.into(),
+            is_static: true,
+            params: Vec::new(),
+            return_type: JavaType::Void,
+            deprecation,
+        };
+        let class = ClassStub {
+            package: None,
+            name: "Foo".into(),
+            struct_name: "Foo".into(),
+            constants: Vec::new(),
+            methods: vec![
+                method("reset", Some(Deprecation { since: Some("1.2".into()), note: Some("use `clear(\"$all\")`".into()) })),
+                method("flush", Some(Deprecation::default())),
+            ],
+        };
+
+        assert_eq!(
+            render(&class),
+            r#"// Generated by robusta from the `#[bridge]` module declaring `Foo`. Do not edit.
+
+open class Foo {
+    companion object {
+        @Deprecated("since 1.2: use `clear(\"\$all\")`")
+        @JvmStatic
+        external fun reset()
+
+        @Deprecated("")
+        @JvmStatic
+        external fun flush()
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn error_code_stub_declares_constants() {
+        let stub = ErrorCodeStub {
+            package: Some(JavaPath::from_str("com.example").unwrap()),
+            name: "Status".into(),
+            constants: vec![("OK".into(), 0), ("NOT_FOUND".into(), -2)],
+        };
+
+        assert_eq!(
+            render_error_codes(&stub),
+            "// Generated by robusta from `#[derive(ErrorCode)]` on `Status`. Do not edit.
+package com.example
+
+class Status private constructor() {
+    companion object {
+        const val OK = 0
+        const val NOT_FOUND = -2
+
+        /**
+         * Name of the variant with code [code], or `null` if there's none.
+         */
+        @JvmStatic
+        external fun nameOf(code: Int): String?
+    }
+}
+"
+        );
+    }
+}
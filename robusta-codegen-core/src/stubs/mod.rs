@@ -0,0 +1,941 @@
+//! Java source stubs generation.
+//!
+//! When the `ROBUSTA_JAVA_OUTPUT_DIR` environment variable is set at compile time, the `#[bridge]` macro writes a `.java` file
+//! for each bridged struct, declaring the `native` counterparts of its exported methods.
+//!
+//! Java types are inferred from the syntax of the Rust types, as trait implementations (e.g. `Signature`) can't be resolved
+//! during macro expansion: types that aren't library-provided conversions or bridged structs of the same module map to `Object`.
+//!
+//! Java classes some library-provided conversions need (e.g. `robusta.audio.ShortSamples`) are written to the same directory,
+//! whenever a bridged method uses them, as are `robusta.dispatch.Dispatch` and `robusta.coroutines.Suspend` for modules with
+//! `#[dispatch]` and `#[suspend]` methods.
+//!
+//! When `ROBUSTA_KOTLIN_OUTPUT_DIR` is set, a `.kt` file is written for each bridged struct as well, declaring its exported
+//! methods as `external fun`s (in the `companion object` for static methods). Both languages spell the Java types of the
+//! methods through the [`types`] module, and `Option`s are nullable Kotlin types.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use proc_macro2::Span;
+use proc_macro_error::emit_error;
+use quote::ToTokens;
+use syn::visit::Visit;
+use syn::{Attribute, FnArg, GenericArgument, ImplItem, ItemImpl, Lit, Meta, MetaNameValue, Pat, PathArguments, ReturnType, Type, Visibility};
+
+use crate::transformation::utils::{bridged_struct_type, get_converted_jni_type, get_deprecation, get_result_value_type, get_sig_descriptor, instantiated_method, Deprecation, NumericConversion};
+use crate::transformation::{JavaPath, RenameRule, JAVA_RESERVED_WORDS};
+use crate::utils::{get_abi, get_env_arg, impl_struct_name, is_class_type, is_self_method};
+use crate::validation::{FlattenedStructs, Instantiations, JNIBridgeModule};
+
+mod java;
+mod kotlin;
+mod support;
+mod types;
+
+pub(crate) const JAVA_OUTPUT_DIR_VAR: &str = "ROBUSTA_JAVA_OUTPUT_DIR";
+pub(crate) const KOTLIN_OUTPUT_DIR_VAR: &str = "ROBUSTA_KOTLIN_OUTPUT_DIR";
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum JavaType {
+    Void,
+    Primitive(&'static str),
+    /// Fully qualified class name
+    Object(String),
+    /// `Option` values, which are only told apart from the type itself in Kotlin
+    Nullable(Box<JavaType>),
+    ArrayList(Box<JavaType>),
+    /// `java.util.List`, for `Vec` parameters and `#[java_list]` return values
+    List(Box<JavaType>),
+    Array(Box<JavaType>),
+    /// Returned by `async` methods
+    CompletableFuture(Box<JavaType>),
+}
+
+impl JavaType {
+    fn object(class: &str) -> Self {
+        JavaType::Object(class.into())
+    }
+
+    /// Wrapper class for primitive types, the type itself otherwise.
+    pub(crate) fn boxed(self) -> Self {
+        match self {
+            JavaType::Primitive(p) => JavaType::object(types::primitive(p).map_or("java.lang.Object", |p| p.wrapper)),
+            JavaType::Void => JavaType::object("java.lang.Void"),
+            t => t,
+        }
+    }
+
+    /// Nullable wrapper class of the type, for values that can be `null`.
+    pub(crate) fn nullable(self) -> Self {
+        match self.boxed() {
+            JavaType::Nullable(t) => JavaType::Nullable(t),
+            t => JavaType::Nullable(Box::new(t)),
+        }
+    }
+
+    /// `List` for an `ArrayList`, as `Vec`s are converted from any list, the type itself otherwise.
+    pub(crate) fn list(self) -> Self {
+        match self {
+            JavaType::ArrayList(t) => JavaType::List(t),
+            JavaType::Nullable(t) => JavaType::Nullable(Box::new(t.list())),
+            t => t,
+        }
+    }
+
+    /// Type of the JNI type descriptor `descriptor`, e.g. `Lcom/example/Callback;`, or `None` if it's invalid.
+    pub(crate) fn from_descriptor(descriptor: &str) -> Option<Self> {
+        let is_identifier = |s: &str| {
+            let mut chars = s.chars();
+            chars.next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$') && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        };
+
+        Some(match descriptor {
+            "V" => JavaType::Void,
+            d if d.len() == 1 => JavaType::Primitive(types::PRIMITIVES.iter().find(|p| p.descriptor == d)?.java),
+            d if d.starts_with('[') => match JavaType::from_descriptor(&d[1..])? {
+                JavaType::Void => return None,
+                element => JavaType::Array(Box::new(element)),
+            },
+            d => {
+                let class = d.strip_prefix('L')?.strip_suffix(';')?;
+                if !class.split('/').all(is_identifier) {
+                    return None;
+                }
+                JavaType::Object(class.replace('/', "."))
+            }
+        })
+    }
+
+    /// JNI type descriptor, e.g. `I` or `Ljava/lang/String;`.
+    pub(crate) fn descriptor(&self) -> String {
+        match self {
+            JavaType::Void => "V".into(),
+            JavaType::Primitive(p) => types::primitive(p).map_or("Ljava/lang/Object;", |p| p.descriptor).into(),
+            JavaType::Object(class) => format!("L{};", class.replace('.', "/")),
+            JavaType::Nullable(t) => t.descriptor(),
+            JavaType::ArrayList(_) => "Ljava/util/ArrayList;".into(),
+            JavaType::List(_) => "Ljava/util/List;".into(),
+            JavaType::CompletableFuture(_) => "Ljava/util/concurrent/CompletableFuture;".into(),
+            JavaType::Array(t) => format!("[{}", t.descriptor()),
+        }
+    }
+}
+
+pub(crate) struct ParamStub {
+    pub(crate) name: String,
+    pub(crate) ty: JavaType,
+    pub(crate) has_default: bool,
+}
+
+pub(crate) struct MethodStub {
+    pub(crate) name: String,
+    pub(crate) is_static: bool,
+    pub(crate) params: Vec<ParamStub>,
+    pub(crate) return_type: JavaType,
+    pub(crate) deprecation: Option<Deprecation>,
+}
+
+impl MethodStub {
+    /// Description of the deprecation of a deprecated method, e.g. `since 1.2: use clear`, which is empty without `since` and `note`.
+    fn deprecation_message(&self) -> Option<String> {
+        self.deprecation.as_ref().map(|deprecation| match (&deprecation.since, &deprecation.note) {
+            (Some(since), Some(note)) => format!("since {}: {}", since, note),
+            (Some(since), None) => format!("since {}", since),
+            (None, Some(note)) => note.clone(),
+            (None, None) => String::new(),
+        })
+    }
+}
+
+pub(crate) struct ClassStub {
+    pub(crate) package: Option<JavaPath>,
+    pub(crate) name: String,
+    /// The bridged struct, whose name differs from the class name for instantiations of generic structs.
+    pub(crate) struct_name: String,
+    /// Constants of the Java enum of an enum deriving `JavaEnum`, empty for classes.
+    pub(crate) constants: Vec<String>,
+    pub(crate) methods: Vec<MethodStub>,
+}
+
+/// Path of the source file of class `name` with the `extension` of its language, relative to the output directory.
+fn class_file_path(package: Option<&JavaPath>, name: &str, extension: &str) -> PathBuf {
+    let mut path = PathBuf::new();
+    if let Some(package) = package {
+        path.extend(package.to_string().split('.').filter(|s| !s.is_empty()));
+    }
+    path.push(format!("{}.{}", name, extension));
+
+    path
+}
+
+impl ClassStub {
+    /// Path of the stub file with `extension`, relative to the output directory.
+    fn file_path(&self, extension: &str) -> PathBuf {
+        class_file_path(self.package.as_ref(), &self.name, extension)
+    }
+}
+
+/// Class of an enum deriving `ErrorCode`, with a constant for each variant and the `nameOf` native method.
+pub(crate) struct ErrorCodeStub {
+    pub(crate) package: Option<JavaPath>,
+    pub(crate) name: String,
+    /// Constant name and code of each variant, e.g. `NOT_FOUND` for `NotFound`.
+    pub(crate) constants: Vec<(String, i32)>,
+}
+
+/// Maps bridged struct names to their fully qualified Java class name.
+pub(crate) struct ClassNames<'a> {
+    pub(crate) package_map: &'a BTreeMap<String, Option<JavaPath>>,
+    pub(crate) self_name: &'a str,
+    /// Fields of the structs of `#[flatten]` parameters, which are passed as a Java parameter each.
+    pub(crate) flattened_structs: &'a FlattenedStructs,
+    /// Enums deriving `ErrorCode`, converted to `int`s.
+    pub(crate) error_codes: &'a BTreeSet<String>,
+    /// Naming convention of the Java methods without a `#[java_name]`.
+    pub(crate) rename_all: Option<RenameRule>,
+    /// Java classes of the generic structs with an `#[instantiate]` attribute.
+    pub(crate) instantiations: &'a Instantiations,
+}
+
+impl<'a> ClassNames<'a> {
+    /// Class name of the struct `struct_name` at `path`, whose type arguments select the class of instantiated structs.
+    fn qualified_name(&self, struct_name: &str, path: &syn::Path) -> Option<String> {
+        let class_name = match self.instantiations.get(struct_name) {
+            Some(instantiations) => instantiations.iter().find(|i| i.matches(path))?.class_name(struct_name),
+            None => struct_name.to_string(),
+        };
+
+        self.package_map.get(struct_name).map(|package| {
+            match package.as_ref().map(ToString::to_string).filter(|p| !p.is_empty()) {
+                Some(p) => format!("{}.{}", p, class_name),
+                None => class_name,
+            }
+        })
+    }
+
+    pub(crate) fn java_type(&self, ty: &Type) -> JavaType {
+        match ty {
+            Type::Reference(r) => self.java_type(&r.elem),
+            // `&[u8]` parameters are borrowed from `byte[]`s
+            Type::Slice(s) if matches!(&*s.elem, Type::Path(p) if p.path.is_ident("u8")) => JavaType::Array(Box::new(JavaType::Primitive("byte"))),
+            Type::Slice(s) => JavaType::Array(Box::new(self.java_type(&s.elem))),
+            Type::Array(a) => JavaType::Array(Box::new(self.java_type(&a.elem))),
+            Type::Paren(p) => self.java_type(&p.elem),
+            Type::Group(g) => self.java_type(&g.elem),
+            Type::Tuple(t) if t.elems.is_empty() => JavaType::Void,
+            Type::Tuple(t) => match t.elems.len() {
+                2 if cfg!(feature = "kotlin-tuples") => JavaType::object("kotlin.Pair"),
+                3 if cfg!(feature = "kotlin-tuples") => JavaType::object("kotlin.Triple"),
+                _ => JavaType::Array(Box::new(JavaType::object("java.lang.Object"))),
+            },
+            Type::Path(p) => {
+                let segment = match p.path.segments.last() {
+                    Some(s) => s,
+                    None => return JavaType::object("java.lang.Object"),
+                };
+                let type_args: Vec<&Type> = match &segment.arguments {
+                    PathArguments::AngleBracketed(a) => a
+                        .args
+                        .iter()
+                        .filter_map(|a| match a {
+                            GenericArgument::Type(t) => Some(t),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                let first_arg = |f: &dyn Fn(JavaType) -> JavaType| {
+                    type_args
+                        .first()
+                        .map_or(JavaType::object("java.lang.Object"), |t| f(self.java_type(t)))
+                };
+
+                match segment.ident.to_string().as_str() {
+                    "bool" | "jboolean" => JavaType::Primitive("boolean"),
+                    "i8" | "jbyte" => JavaType::Primitive("byte"),
+                    "char" | "jchar" => JavaType::Primitive("char"),
+                    "i16" | "jshort" => JavaType::Primitive("short"),
+                    "i32" | "jint" => JavaType::Primitive("int"),
+                    "i64" | "jlong" | "u32" | "u64" | "usize" => JavaType::Primitive("long"),
+                    // A `Handle` struct of the module is a class of its own
+                    name @ ("Handle" | "HandleRef") if !self.package_map.contains_key(name) => JavaType::Primitive("long"),
+                    "f32" | "jfloat" => JavaType::Primitive("float"),
+                    "f64" | "jdouble" => JavaType::Primitive("double"),
+                    "str" | "String" | "JString" | "jstring" => JavaType::object("java.lang.String"),
+                    "JClass" | "jclass" => JavaType::object("java.lang.Class"),
+                    "DirectByteBuffer" | "DirectBytes" => JavaType::object("java.nio.ByteBuffer"),
+                    "Passthrough" | "PassthroughOwned" => JavaType::object("java.lang.Object"),
+                    "JAtomicLong" | "GlobalAtomicLong" => JavaType::object("java.util.concurrent.atomic.AtomicLong"),
+                    "JavaCallback" | "GlobalJavaCallback" => match type_args.first() {
+                        Some(Type::Tuple(t)) if t.elems.is_empty() => JavaType::object("java.lang.Runnable"),
+                        Some(Type::Tuple(t)) if t.elems.len() == 2 => JavaType::object("java.util.function.BiConsumer"),
+                        _ => JavaType::object("java.util.function.Consumer"),
+                    },
+                    "i128" | "u128" | "BigIntegerBytes" => JavaType::object("java.math.BigInteger"),
+                    "Duration" => JavaType::object("java.time.Duration"),
+                    "SystemTime" | "DateTime" => JavaType::object("java.time.Instant"),
+                    "jbooleanArray" => JavaType::Array(Box::new(JavaType::Primitive("boolean"))),
+                    "jbyteArray" | "EncodedString" | "CriticalBytes" => JavaType::Array(Box::new(JavaType::Primitive("byte"))),
+                    "jcharArray" => JavaType::Array(Box::new(JavaType::Primitive("char"))),
+                    "jshortArray" => JavaType::Array(Box::new(JavaType::Primitive("short"))),
+                    "jintArray" => JavaType::Array(Box::new(JavaType::Primitive("int"))),
+                    "jlongArray" => JavaType::Array(Box::new(JavaType::Primitive("long"))),
+                    "jfloatArray" => JavaType::Array(Box::new(JavaType::Primitive("float"))),
+                    "jdoubleArray" => JavaType::Array(Box::new(JavaType::Primitive("double"))),
+                    "Arc" | "Rc" | "Box" | "Cow" | "Result" | "JniResult" | "Adapt" | "Global" | "Weak" => first_arg(&|t| t),
+                    "Option" | "JOption" => first_arg(&JavaType::nullable),
+                    "Boxed" => first_arg(&JavaType::boxed),
+                    "Vec" => first_arg(&|t| JavaType::ArrayList(Box::new(t.boxed()))),
+                    "JavaArray" => first_arg(&|t| JavaType::Array(Box::new(t))),
+                    "Samples" => first_arg(&|t| match t {
+                        JavaType::Primitive("short") => JavaType::object("robusta.audio.ShortSamples"),
+                        JavaType::Primitive("float") => JavaType::object("robusta.audio.FloatSamples"),
+                        _ => JavaType::object("java.lang.Object"),
+                    }),
+                    "Self" => JavaType::Object(self.qualified_name(self.self_name, &p.path).unwrap_or_else(|| self.self_name.into())),
+                    name if self.error_codes.contains(name) => JavaType::Primitive("int"),
+                    name => match self.qualified_name(name, &p.path) {
+                        Some(class) => JavaType::Object(class),
+                        None => JavaType::object("java.lang.Object"),
+                    },
+                }
+            }
+            _ => JavaType::object("java.lang.Object"),
+        }
+    }
+
+    pub(crate) fn method_stub(&self, method: &syn::ImplItemMethod) -> MethodStub {
+        let is_static = !is_self_method(&method.sig);
+        let (mut signature, _) = get_env_arg(method.sig.clone());
+        // The class static methods are called on isn't a Java parameter either
+        if is_static && matches!(signature.inputs.first(), Some(FnArg::Typed(t)) if is_class_type(&t.ty)) {
+            signature.inputs = signature.inputs.into_iter().skip(1).collect();
+        }
+        // Exported methods take `Vec`s from any `List`, imported ones only declare them as such with `#[java_list]`
+        let is_exported = get_abi(&method.sig).as_deref() == Some("jni");
+        let is_list = |attrs: &[Attribute]| attrs.iter().any(|a| a.path.is_ident("java_list"));
+
+        let params = signature
+            .inputs
+            .iter()
+            .flat_map(|arg| match arg {
+                FnArg::Receiver(_) => Vec::new(),
+                FnArg::Typed(t) => {
+                    let name = match &*t.pat {
+                        Pat::Ident(i) if i.ident == "self" => return Vec::new(),
+                        Pat::Ident(i) => i.ident.to_string(),
+                        p => p.to_token_stream().to_string(),
+                    };
+
+                    // Each field is a parameter, named like the parameters of the JNI function
+                    let flattened_fields = match &*t.ty {
+                        Type::Path(p) if t.attrs.iter().any(|a| a.path.is_ident("flatten")) => {
+                            p.path.segments.last().and_then(|s| self.flattened_structs.get(&s.ident.to_string()))
+                        }
+                        _ => None,
+                    };
+                    if let Some(fields) = flattened_fields {
+                        return fields
+                            .iter()
+                            .map(|(field, ty)| ParamStub {
+                                name: format!("{}_{}", name, field),
+                                ty: self.java_type(ty).list(),
+                                has_default: false,
+                            })
+                            .collect();
+                    }
+
+                    let has_default = t.attrs.iter().any(|a| a.path.is_ident("default"));
+                    let ty = match NumericConversion::from_attrs(&t.attrs).and_then(|c| c.java_primitive(&t.ty)) {
+                        Some(primitive) => JavaType::Primitive(primitive),
+                        None => match get_sig_descriptor(&t.attrs).and_then(|d| JavaType::from_descriptor(&d)) {
+                            Some(ty) => ty,
+                            None => self.java_type(&get_converted_jni_type(&t.attrs, true).unwrap_or_else(|| (*t.ty).clone())),
+                        },
+                    };
+                    let ty = if is_exported || is_list(&t.attrs) { ty.list() } else { ty };
+
+                    vec![ParamStub {
+                        name: if JAVA_RESERVED_WORDS.contains(&name.as_str()) {
+                            format!("{}_", name)
+                        } else {
+                            name
+                        },
+                        ty: if has_default { ty.nullable() } else { ty },
+                        has_default,
+                    }]
+                }
+            })
+            .collect();
+
+        let return_type = match &signature.output {
+            ReturnType::Default => JavaType::Void,
+            ReturnType::Type(_, t) => {
+                let value_type = get_result_value_type(t).unwrap_or(t);
+                match NumericConversion::from_attrs(&method.attrs).and_then(|c| c.java_primitive(value_type)) {
+                    Some(primitive) => JavaType::Primitive(primitive),
+                    None => match get_sig_descriptor(&method.attrs).and_then(|d| JavaType::from_descriptor(&d)) {
+                        Some(ty) => ty,
+                        None => match self.java_type(&get_converted_jni_type(&method.attrs, false).unwrap_or_else(|| (**t).clone())) {
+                            ty if is_list(&method.attrs) => ty.list(),
+                            ty => ty,
+                        },
+                    },
+                }
+            }
+        };
+        let return_type = match signature.asyncness {
+            Some(_) => JavaType::CompletableFuture(Box::new(return_type.boxed())),
+            None => return_type,
+        };
+
+        // Invalid names are reported when transforming the module
+        let name = method
+            .attrs
+            .iter()
+            .find(|a| a.path.is_ident("java_name"))
+            .and_then(|a| match a.parse_meta() {
+                Ok(Meta::NameValue(MetaNameValue { lit: Lit::Str(name), .. })) => Some(name.value()),
+                _ => None,
+            })
+            .unwrap_or_else(|| match self.rename_all {
+                Some(rule) => rule.apply(&signature.ident.to_string()),
+                None => signature.ident.to_string(),
+            });
+
+        MethodStub {
+            name,
+            is_static,
+            params,
+            return_type,
+            deprecation: get_deprecation(&method.attrs),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ImplCollector<'ast> {
+    pub(crate) impls: Vec<&'ast ItemImpl>,
+}
+
+impl<'ast> Visit<'ast> for ImplCollector<'ast> {
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        if node.trait_.is_none() {
+            self.impls.push(node);
+        }
+    }
+}
+
+/// Simple names of the Java classes bridged by the methods of `item_impl`, an impl block of `struct_name`, each with the impl block
+/// as declared for that class: instantiations of structs with `#[instantiate]` have their types in method signatures.
+pub(crate) fn impl_classes(module: &JNIBridgeModule, struct_name: &str, item_impl: &ItemImpl) -> Vec<(String, ItemImpl)> {
+    let (instantiations, self_path) = match (module.instantiations.get(struct_name), &*item_impl.self_ty) {
+        (Some(instantiations), Type::Path(p)) => (instantiations, &p.path),
+        _ => return vec![(struct_name.to_string(), item_impl.clone())],
+    };
+
+    instantiations
+        .iter()
+        .filter_map(|instantiation| {
+            let bindings = instantiation.bindings(item_impl)?;
+            let struct_type = bridged_struct_type(self_path, &bindings);
+            let items = item_impl
+                .items
+                .iter()
+                .map(|item| match item {
+                    ImplItem::Method(m) => ImplItem::Method(instantiated_method(m, &bindings, &struct_type)),
+                    item => item.clone(),
+                })
+                .collect();
+
+            Some((instantiation.class_name(struct_name), ItemImpl { items, ..item_impl.clone() }))
+        })
+        .collect()
+}
+
+/// Calls `f` with the bridged struct, the class name and the class names of the module for each Java class the `impl` blocks of
+/// the module declare methods of, and the impl block as declared for that class.
+pub(crate) fn for_each_impl(module: &JNIBridgeModule, mut f: impl FnMut(&str, &str, &ClassNames, &ItemImpl)) {
+    let mut impl_collector = ImplCollector::default();
+    impl_collector.visit_item_mod(&module.module_decl);
+
+    for item_impl in impl_collector.impls {
+        let struct_name = match impl_struct_name(item_impl, &module.module_decl.ident) {
+            Some(struct_name) => struct_name,
+            None => continue,
+        };
+        if !module.package_map.contains_key(&struct_name) {
+            continue;
+        }
+
+        let class_names = ClassNames {
+            package_map: &module.package_map,
+            self_name: &struct_name,
+            flattened_structs: &module.flattened_structs,
+            error_codes: &module.error_codes,
+            rename_all: module.rename_all,
+            instantiations: &module.instantiations,
+        };
+        for (class_name, item_impl) in impl_classes(module, &struct_name, item_impl) {
+            f(&struct_name, &class_name, &class_names, &item_impl);
+        }
+    }
+}
+
+/// Collects a stub for each Java class of the module with at least one exported method, in order of their first impl block,
+/// followed by the Java enums without exported methods.
+pub(crate) fn class_stubs(module: &JNIBridgeModule) -> Vec<ClassStub> {
+    let mut stubs: Vec<ClassStub> = Vec::new();
+    for_each_impl(module, |struct_name, class_name, class_names, item_impl| {
+        let methods: Vec<_> = item_impl
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ImplItem::Method(m) if matches!(m.vis, Visibility::Public(_)) && get_abi(&m.sig).as_deref() == Some("jni") => {
+                    Some(class_names.method_stub(m))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let class_stub = match stubs.iter().position(|s| s.name == class_name) {
+            Some(i) => &mut stubs[i],
+            None => {
+                stubs.push(ClassStub {
+                    package: module.package_map[struct_name].clone(),
+                    name: class_name.to_string(),
+                    struct_name: struct_name.to_string(),
+                    constants: Vec::new(),
+                    methods: Vec::new(),
+                });
+                stubs.last_mut().unwrap()
+            }
+        };
+        for method in methods {
+            // Methods exported under the same name and parameters are reported, and only the first one is declared
+            let descriptor = |m: &MethodStub| m.params.iter().map(|p| p.ty.descriptor()).collect::<String>();
+            if !class_stub.methods.iter().any(|m| m.name == method.name && descriptor(m) == descriptor(&method)) {
+                class_stub.methods.push(method);
+            }
+        }
+    });
+
+    // Java enums are declared even without exported methods, as types of the methods of other classes
+    for (enum_name, constants) in &module.java_enums {
+        match stubs.iter_mut().find(|s| s.struct_name == *enum_name) {
+            Some(stub) => stub.constants = constants.clone(),
+            None => stubs.push(ClassStub {
+                package: module.package_map[enum_name].clone(),
+                name: enum_name.clone(),
+                struct_name: enum_name.clone(),
+                constants: constants.clone(),
+                methods: Vec::new(),
+            }),
+        }
+    }
+
+    stubs.into_iter().filter(|s| !s.methods.is_empty() || !s.constants.is_empty()).collect()
+}
+
+/// Fully qualified names of the support classes used by the exported and imported methods of the module.
+fn support_classes(module: &JNIBridgeModule) -> BTreeSet<String> {
+    fn collect(ty: &JavaType, classes: &mut BTreeSet<String>) {
+        match ty {
+            JavaType::Object(class) if support::render(class).is_some() => {
+                classes.insert(class.clone());
+            }
+            JavaType::Nullable(t) | JavaType::ArrayList(t) | JavaType::List(t) | JavaType::Array(t) | JavaType::CompletableFuture(t) => {
+                collect(t, classes)
+            }
+            _ => {}
+        }
+    }
+
+    let mut classes = BTreeSet::new();
+    for_each_impl(module, |_, _, class_names, item_impl| {
+        for item in &item_impl.items {
+            if let ImplItem::Method(m) = item {
+                let abi = get_abi(&m.sig);
+                if matches!(abi.as_deref(), Some("jni") | Some("java")) {
+                    let stub = class_names.method_stub(m);
+                    stub.params.iter().for_each(|p| collect(&p.ty, &mut classes));
+                    collect(&stub.return_type, &mut classes);
+                }
+                if abi.as_deref() == Some("java") && m.attrs.iter().any(|a| a.path.is_ident("dispatch")) {
+                    classes.insert("robusta.dispatch.Dispatch".to_string());
+                }
+                if abi.as_deref() == Some("java") && m.attrs.iter().any(|a| a.path.is_ident("suspend")) {
+                    classes.insert("robusta.coroutines.Suspend".to_string());
+                }
+            }
+        }
+    });
+
+    classes
+}
+
+fn write_if_changed(path: &Path, source: &str) {
+    if std::fs::read_to_string(path).is_ok_and(|existing| existing == source) {
+        return;
+    }
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(path, source));
+
+    if let Err(e) = result {
+        emit_error!(Span::call_site(), "can't write stub `{}` ({})", path.display(), e);
+    }
+}
+
+/// Writes Java stubs for the module if `ROBUSTA_JAVA_OUTPUT_DIR` is set. Files are left untouched if their contents didn't change.
+pub(crate) fn write_java_stubs(module: &JNIBridgeModule) {
+    let output_dir = match std::env::var_os(JAVA_OUTPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => return,
+    };
+
+    for stub in class_stubs(module) {
+        write_if_changed(&output_dir.join(stub.file_path("java")), &java::render(&stub));
+    }
+
+    for class in support_classes(module) {
+        if let Some(source) = support::render(&class) {
+            let path: PathBuf = class.split('.').collect();
+            write_if_changed(&output_dir.join(path.with_extension("java")), &source);
+        }
+    }
+}
+
+/// Writes Kotlin declarations for the module if `ROBUSTA_KOTLIN_OUTPUT_DIR` is set. The Java support classes they may use are
+/// only written with the Java stubs.
+pub(crate) fn write_kotlin_stubs(module: &JNIBridgeModule) {
+    let output_dir = match std::env::var_os(KOTLIN_OUTPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => return,
+    };
+
+    for stub in class_stubs(module) {
+        write_if_changed(&output_dir.join(stub.file_path("kt")), &kotlin::render(&stub));
+    }
+}
+
+/// Writes the class of an enum deriving `ErrorCode` if `ROBUSTA_JAVA_OUTPUT_DIR` or `ROBUSTA_KOTLIN_OUTPUT_DIR` is set.
+pub(crate) fn write_error_code_stub(stub: &ErrorCodeStub) {
+    if let Some(output_dir) = std::env::var_os(JAVA_OUTPUT_DIR_VAR) {
+        let path = PathBuf::from(output_dir).join(class_file_path(stub.package.as_ref(), &stub.name, "java"));
+        write_if_changed(&path, &java::render_error_codes(stub));
+    }
+    if let Some(output_dir) = std::env::var_os(KOTLIN_OUTPUT_DIR_VAR) {
+        let path = PathBuf::from(output_dir).join(class_file_path(stub.package.as_ref(), &stub.name, "kt"));
+        write_if_changed(&path, &kotlin::render_error_codes(stub));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn rust_types_map_to_java_types() {
+        let mut package_map = BTreeMap::new();
+        package_map.insert("Foo".to_string(), Some(JavaPath::from_str("com.example").unwrap()));
+        package_map.insert("Bar".to_string(), Some(JavaPath::from_str("").unwrap()));
+        package_map.insert("Handle".to_string(), Some(JavaPath::from_str("com.example").unwrap()));
+        let error_codes = BTreeSet::from(["Status".to_string()]);
+        let class_names = ClassNames {
+            package_map: &package_map,
+            self_name: "Foo",
+            flattened_structs: &FlattenedStructs::new(),
+            error_codes: &error_codes,
+            rename_all: None,
+            instantiations: &Instantiations::new(),
+        };
+
+        let java_type = |ty: Type| class_names.java_type(&ty);
+        assert_eq!(java_type(parse_quote! { u32 }), JavaType::Primitive("long"));
+        assert_eq!(java_type(parse_quote! { HandleRef<'env, Buffer> }), JavaType::Primitive("long"));
+        assert_eq!(java_type(parse_quote! { Handle<'env> }), JavaType::object("com.example.Handle"));
+        assert_eq!(java_type(parse_quote! { DirectByteBuffer<'env> }), JavaType::object("java.nio.ByteBuffer"));
+        assert_eq!(java_type(parse_quote! { Arc<str> }), JavaType::object("java.lang.String"));
+        assert_eq!(java_type(parse_quote! { Cow<'_, str> }), JavaType::object("java.lang.String"));
+        assert_eq!(java_type(parse_quote! { Option<i32> }), JavaType::object("java.lang.Integer").nullable());
+        assert_eq!(java_type(parse_quote! { JOption<String> }), JavaType::object("java.lang.String").nullable());
+        assert_eq!(java_type(parse_quote! { Vec<Boxed<f64>> }), JavaType::ArrayList(Box::new(JavaType::object("java.lang.Double"))));
+        assert_eq!(
+            java_type(parse_quote! { Vec<String> }),
+            JavaType::ArrayList(Box::new(JavaType::object("java.lang.String")))
+        );
+        assert_eq!(java_type(parse_quote! { Vec<Foo> }), JavaType::ArrayList(Box::new(JavaType::object("com.example.Foo"))));
+        assert_eq!(java_type(parse_quote! { Vec<Global<Bar>> }), JavaType::ArrayList(Box::new(JavaType::object("Bar"))));
+        assert_eq!(java_type(parse_quote! { JavaArray<i32> }), JavaType::Array(Box::new(JavaType::Primitive("int"))));
+        assert_eq!(
+            java_type(parse_quote! { JavaArray<Option<String>> }),
+            JavaType::Array(Box::new(JavaType::object("java.lang.String").nullable()))
+        );
+        assert_eq!(
+            java_type(parse_quote! { (String, i32) }),
+            if cfg!(feature = "kotlin-tuples") {
+                JavaType::object("kotlin.Pair")
+            } else {
+                JavaType::Array(Box::new(JavaType::object("java.lang.Object")))
+            }
+        );
+        assert_eq!(java_type(parse_quote! { EncodedString<Latin1> }), JavaType::Array(Box::new(JavaType::Primitive("byte"))));
+        assert_eq!(java_type(parse_quote! { CriticalBytes<'env> }), JavaType::Array(Box::new(JavaType::Primitive("byte"))));
+        assert_eq!(java_type(parse_quote! { ::robusta_jni::jni::errors::Result<()> }), JavaType::Void);
+        assert_eq!(java_type(parse_quote! { JniResult<bool> }), JavaType::Primitive("boolean"));
+        assert_eq!(java_type(parse_quote! { JAtomicLong<'env> }), JavaType::object("java.util.concurrent.atomic.AtomicLong"));
+        assert_eq!(java_type(parse_quote! { Passthrough<'env> }), JavaType::object("java.lang.Object"));
+        assert_eq!(java_type(parse_quote! { Weak<String> }), JavaType::object("java.lang.String"));
+        assert_eq!(java_type(parse_quote! { JavaCallback<'env, ()> }), JavaType::object("java.lang.Runnable"));
+        assert_eq!(java_type(parse_quote! { JavaCallback<'env, (String,)> }), JavaType::object("java.util.function.Consumer"));
+        assert_eq!(java_type(parse_quote! { GlobalJavaCallback<(i32, i32)> }), JavaType::object("java.util.function.BiConsumer"));
+        assert_eq!(java_type(parse_quote! { u128 }), JavaType::object("java.math.BigInteger"));
+        assert_eq!(java_type(parse_quote! { std::time::Duration }), JavaType::object("java.time.Duration"));
+        assert_eq!(java_type(parse_quote! { DateTime<Utc> }), JavaType::object("java.time.Instant"));
+        assert_eq!(java_type(parse_quote! { Adapt<String, ParseIp> }), JavaType::object("java.lang.String"));
+        assert_eq!(java_type(parse_quote! { &[u8] }), JavaType::Array(Box::new(JavaType::Primitive("byte"))));
+        assert_eq!(java_type(parse_quote! { &str }), JavaType::object("java.lang.String"));
+        assert_eq!(java_type(parse_quote! { Samples<i16> }), JavaType::object("robusta.audio.ShortSamples"));
+        assert_eq!(java_type(parse_quote! { Self }), JavaType::object("com.example.Foo"));
+        assert_eq!(java_type(parse_quote! { Bar<'env> }), JavaType::object("Bar"));
+        assert_eq!(java_type(parse_quote! { HashMap<i32, i32> }), JavaType::object("java.lang.Object"));
+        assert_eq!(java_type(parse_quote! { Status }), JavaType::Primitive("int"));
+    }
+
+    #[test]
+    fn numeric_conversions_change_java_types() {
+        let package_map = BTreeMap::new();
+        let class_names = ClassNames {
+            package_map: &package_map,
+            self_name: "Foo",
+            flattened_structs: &FlattenedStructs::new(),
+            error_codes: &BTreeSet::new(),
+            rename_all: None,
+            instantiations: &Instantiations::new(),
+        };
+
+        let stub = class_names.method_stub(&parse_quote! {
+            #[saturate]
+            pub extern "jni" fn foo(#[reinterpret] flags: u32, #[truncate] id: u64, #[reinterpret] mask: u8, count: u32) -> Result<u64, String> {}
+        });
+        let params: String = stub.params.iter().map(|p| p.ty.descriptor()).collect();
+        assert_eq!(params, "IIBJ");
+        assert_eq!(stub.return_type, JavaType::Primitive("int"));
+    }
+
+    #[test]
+    fn async_methods_return_completable_futures() {
+        let package_map = BTreeMap::new();
+        let class_names = ClassNames {
+            package_map: &package_map,
+            self_name: "Foo",
+            flattened_structs: &FlattenedStructs::new(),
+            error_codes: &BTreeSet::new(),
+            rename_all: None,
+            instantiations: &Instantiations::new(),
+        };
+
+        let stub = class_names.method_stub(&parse_quote! { pub async extern "jni" fn fetch(key: String) -> Result<i64, String> {} });
+        assert_eq!(stub.return_type, JavaType::CompletableFuture(Box::new(JavaType::object("java.lang.Long"))));
+        assert_eq!(stub.return_type.descriptor(), "Ljava/util/concurrent/CompletableFuture;");
+
+        let stub = class_names.method_stub(&parse_quote! { pub async extern "jni" fn flush() {} });
+        assert_eq!(stub.return_type, JavaType::CompletableFuture(Box::new(JavaType::object("java.lang.Void"))));
+    }
+
+    #[test]
+    fn vec_params_of_exported_methods_are_lists() {
+        let package_map = BTreeMap::new();
+        let class_names = ClassNames {
+            package_map: &package_map,
+            self_name: "Foo",
+            flattened_structs: &FlattenedStructs::new(),
+            error_codes: &BTreeSet::new(),
+            rename_all: None,
+            instantiations: &Instantiations::new(),
+        };
+        let strings = || Box::new(JavaType::object("java.lang.String"));
+
+        let stub = class_names.method_stub(&parse_quote! { pub extern "jni" fn sorted(values: Vec<String>) -> Vec<String> {} });
+        assert_eq!(stub.params[0].ty, JavaType::List(strings()));
+        assert_eq!(stub.return_type, JavaType::ArrayList(strings()));
+
+        let stub = class_names.method_stub(&parse_quote! {
+            #[java_list]
+            pub extern "jni" fn sorted(values: Vec<String>) -> Vec<String> {}
+        });
+        assert_eq!(stub.return_type, JavaType::List(strings()));
+        assert_eq!(stub.return_type.descriptor(), "Ljava/util/List;");
+
+        // Imported methods call the Java method as it's declared
+        let stub = class_names.method_stub(&parse_quote! {
+            pub extern "java" fn sorted(env: JNIEnv, values: Vec<String>, #[java_list] others: Vec<String>) -> JniResult<Vec<String>> {}
+        });
+        let params: String = stub.params.iter().map(|p| p.ty.descriptor()).collect();
+        assert_eq!(params, "Ljava/util/ArrayList;Ljava/util/List;");
+    }
+
+    #[test]
+    fn flattened_params_are_expanded_into_fields() {
+        let package_map = BTreeMap::new();
+        let mut flattened_structs = FlattenedStructs::new();
+        flattened_structs.insert(
+            "Config".to_string(),
+            vec![(parse_quote! { host }, parse_quote! { String }), (parse_quote! { port }, parse_quote! { i32 })],
+        );
+        let class_names = ClassNames {
+            package_map: &package_map,
+            self_name: "Foo",
+            flattened_structs: &flattened_structs,
+            error_codes: &BTreeSet::new(),
+            rename_all: None,
+            instantiations: &Instantiations::new(),
+        };
+
+        let stub = class_names.method_stub(&parse_quote! {
+            pub extern "jni" fn connect(#[flatten] config: Config, retries: i32) {}
+        });
+        let names: Vec<&str> = stub.params.iter().map(|p| p.name.as_str()).collect();
+        let params: String = stub.params.iter().map(|p| p.ty.descriptor()).collect();
+        assert_eq!(names, ["config_host", "config_port", "retries"]);
+        assert_eq!(params, "Ljava/lang/String;II");
+    }
+
+    #[test]
+    fn instantiations_have_a_class_each() {
+        let module: JNIBridgeModule = syn::parse2(quote::quote! {
+            mod jni {
+                #[package(com.example)]
+                #[instantiate(OfString = Holder<String>, OfLong = Holder<i64>)]
+                pub struct Holder<T> {
+                    value: T,
+                }
+
+                impl<T> Holder<T> {
+                    pub extern "jni" fn wrap(value: T) -> Self {}
+                }
+
+                impl Holder<i64> {
+                    pub extern "jni" fn twice(value: i64) -> i64 {}
+                }
+            }
+        })
+        .unwrap();
+
+        let stubs = class_stubs(&module);
+        let classes: Vec<&str> = stubs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(classes, ["HolderOfString", "HolderOfLong"]);
+        assert!(stubs.iter().all(|s| s.struct_name == "Holder"));
+
+        let descriptors = |stub: &ClassStub| {
+            stub.methods
+                .iter()
+                .map(|m| format!("{}({}){}", m.name, m.params.iter().map(|p| p.ty.descriptor()).collect::<String>(), m.return_type.descriptor()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(descriptors(&stubs[0]), ["wrap(Ljava/lang/String;)Lcom/example/HolderOfString;"]);
+        assert_eq!(descriptors(&stubs[1]), ["wrap(J)Lcom/example/HolderOfLong;", "twice(J)J"]);
+    }
+
+    #[test]
+    fn java_enums_have_a_stub_each() {
+        let module: JNIBridgeModule = syn::parse2(quote::quote! {
+            mod jni {
+                #[derive(JavaEnum)]
+                #[package(com.example)]
+                pub enum Status {
+                    Ok,
+                    #[java_name = "OFFLINE"]
+                    Down,
+                }
+
+                impl Status {
+                    pub extern "jni" fn isHealthy(self) -> bool {}
+                }
+
+                #[derive(JavaEnum)]
+                #[package(com.example)]
+                pub enum Level {
+                    Low,
+                    VeryHigh,
+                }
+
+                #[package(com.example)]
+                pub struct Monitor;
+
+                impl Monitor {
+                    pub extern "jni" fn level(status: Status) -> Level {}
+                }
+            }
+        })
+        .unwrap();
+
+        let stubs = class_stubs(&module);
+        let classes: Vec<(&str, &[String])> = stubs.iter().map(|s| (s.name.as_str(), s.constants.as_slice())).collect();
+        assert_eq!(
+            classes,
+            [
+                ("Status", &["OK".to_string(), "OFFLINE".to_string()][..]),
+                ("Monitor", &[][..]),
+                ("Level", &["LOW".to_string(), "VERY_HIGH".to_string()][..]),
+            ]
+        );
+        assert_eq!(stubs[1].methods[0].params[0].ty, JavaType::object("com.example.Status"));
+        assert_eq!(stubs[1].methods[0].return_type, JavaType::object("com.example.Level"));
+    }
+
+    #[test]
+    fn java_types_have_jni_descriptors() {
+        assert_eq!(JavaType::Void.descriptor(), "V");
+        assert_eq!(JavaType::Primitive("long").descriptor(), "J");
+        assert_eq!(JavaType::object("java.lang.String").descriptor(), "Ljava/lang/String;");
+        assert_eq!(
+            JavaType::ArrayList(Box::new(JavaType::object("java.lang.Integer"))).descriptor(),
+            "Ljava/util/ArrayList;"
+        );
+        assert_eq!(JavaType::Array(Box::new(JavaType::Primitive("boolean"))).descriptor(), "[Z");
+    }
+
+    #[test]
+    fn sig_attributes_give_the_java_types_of_raw_objects() {
+        let package_map = BTreeMap::new();
+        let class_names = ClassNames {
+            package_map: &package_map,
+            self_name: "Foo",
+            flattened_structs: &FlattenedStructs::new(),
+            error_codes: &BTreeSet::new(),
+            rename_all: None,
+            instantiations: &Instantiations::new(),
+        };
+
+        let stub = class_names.method_stub(&parse_quote! {
+            #[sig("[Lcom/example/Callback;")]
+            pub extern "jni" fn register(#[sig("Lcom/example/Callback;")] callback: JObject<'env>, fallback: JObject<'env>) -> JObject<'env> {}
+        });
+        let params: String = stub.params.iter().map(|p| p.ty.descriptor()).collect();
+        assert_eq!(params, "Lcom/example/Callback;Ljava/lang/Object;");
+        assert_eq!(stub.return_type, JavaType::Array(Box::new(JavaType::object("com.example.Callback"))));
+    }
+
+    #[test]
+    fn descriptors_are_parsed_into_java_types() {
+        assert_eq!(JavaType::from_descriptor("J"), Some(JavaType::Primitive("long")));
+        assert_eq!(JavaType::from_descriptor("Ljava/lang/Runnable;"), Some(JavaType::object("java.lang.Runnable")));
+        assert_eq!(
+            JavaType::from_descriptor("[[I"),
+            Some(JavaType::Array(Box::new(JavaType::Array(Box::new(JavaType::Primitive("int"))))))
+        );
+
+        for invalid in ["", "L;", "Ljava/lang/Runnable", "java.lang.Runnable", "Ljava.lang.Runnable;", "Ljava//Runnable;", "[V", "Q"] {
+            assert_eq!(JavaType::from_descriptor(invalid), None, "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Java classes needed by library-provided conversions and by `#[dispatch]` and `#[suspend]`, written together with the stubs of the modules using them.
+
+/// Interleaved audio samples, see `robusta_jni::audio::Samples`.
+fn samples_class(name: &str, sample_type: &str) -> String {
+    format!(
+        "// Generated by robusta. Do not edit.
+package robusta.audio;
+
+/**
+ * Interleaved audio samples: frame {{@code i}} is made of samples {{@code i * channels}} to {{@code (i + 1) * channels - 1}} of {{@code data}}.
+ */
+public final class {name} {{
+    public final {sample_type}[] data;
+    public final int channels;
+
+    public {name}({sample_type}[] data, int channels) {{
+        if (channels <= 0) {{
+            throw new IllegalArgumentException(\"channel count must be positive\");
+        }}
+        if (data.length % channels != 0) {{
+            throw new IllegalArgumentException(data.length + \" samples are not a multiple of the channel count \" + channels);
+        }}
+
+        this.data = data;
+        this.channels = channels;
+    }}
+
+    public int frames() {{
+        return data.length / channels;
+    }}
+}}
+",
+        name = name,
+        sample_type = sample_type
+    )
+}
+
+/// Calls imported methods annotated with `#[dispatch]` on a toolkit's thread, see `robusta_jni::dispatch`.
+const DISPATCH_CLASS: &str = "// Generated by robusta. Do not edit.
+package robusta.dispatch;
+
+import java.lang.reflect.InvocationTargetException;
+import java.lang.reflect.Method;
+import java.util.concurrent.CountDownLatch;
+
+/**
+ * A method call made on the Swing event dispatch thread or the JavaFX application thread on behalf of another thread.
+ */
+public final class Dispatch implements Runnable {
+    private final Method method;
+    private final Object receiver;
+    private final Object[] args;
+    private final CountDownLatch done = new CountDownLatch(1);
+    private Object result;
+    private Throwable exception;
+
+    private Dispatch(Method method, Object receiver, Object[] args) {
+        this.method = method;
+        this.receiver = receiver;
+        this.args = args;
+    }
+
+    @Override
+    public void run() {
+        try {
+            result = method.invoke(receiver, args);
+        } catch (InvocationTargetException e) {
+            exception = e.getCause();
+        } catch (Throwable e) {
+            exception = e;
+        } finally {
+            done.countDown();
+        }
+    }
+
+    /**
+     * Calls {@code method} on the thread of {@code toolkit} ({@code \"swing\"} or {@code \"javafx\"}) and waits for it to return.
+     * The method is called directly if the current thread is already the toolkit's thread.
+     *
+     * @return the result of the method, boxed if it's a primitive
+     * @throws Throwable the exception thrown by the method
+     */
+    public static Object invoke(String toolkit, Method method, Object receiver, Object[] args) throws Throwable {
+        // Native code can call any method, not only the ones accessible from here
+        try {
+            method.setAccessible(true);
+        } catch (RuntimeException e) {
+            // Reported by `Method.invoke` if the method really is inaccessible
+        }
+
+        Dispatch call = new Dispatch(method, receiver, args);
+        switch (toolkit) {
+            case \"swing\":
+                if (javax.swing.SwingUtilities.isEventDispatchThread()) {
+                    call.run();
+                } else {
+                    javax.swing.SwingUtilities.invokeAndWait(call);
+                }
+                break;
+            case \"javafx\":
+                // Through reflection, since JavaFX isn't part of the JDK
+                try {
+                    Class<?> platform = Class.forName(\"javafx.application.Platform\");
+                    if ((Boolean) platform.getMethod(\"isFxApplicationThread\").invoke(null)) {
+                        call.run();
+                    } else {
+                        platform.getMethod(\"runLater\", Runnable.class).invoke(null, call);
+                    }
+                } catch (InvocationTargetException e) {
+                    throw e.getCause();
+                }
+                break;
+            default:
+                throw new IllegalArgumentException(\"unknown toolkit \" + toolkit);
+        }
+
+        call.done.await();
+        if (call.exception != null) {
+            throw call.exception;
+        }
+        return call.result;
+    }
+}
+";
+
+/// Calls Kotlin `suspend` functions imported with `#[suspend]` until they complete, see `robusta_jni::coroutines`.
+const SUSPEND_CLASS: &str = "// Generated by robusta. Do not edit.
+package robusta.coroutines;
+
+import java.lang.reflect.InvocationHandler;
+import java.lang.reflect.InvocationTargetException;
+import java.lang.reflect.Method;
+import java.lang.reflect.Proxy;
+import java.util.Arrays;
+
+/**
+ * A call of a Kotlin suspend function, started as the block of {@code kotlinx.coroutines.BuildersKt.runBlocking}.
+ *
+ * Kotlin classes are only used through reflection, so that this class can be compiled without them.
+ */
+public final class Suspend implements InvocationHandler {
+    private final Method method;
+    private final Object receiver;
+    private final Object[] args;
+
+    private Suspend(Method method, Object receiver, Object[] args) {
+        this.method = method;
+        this.receiver = receiver;
+        this.args = args;
+    }
+
+    /**
+     * Implements {@code Function2.invoke(CoroutineScope, Continuation)}, calling the function with the continuation of the block.
+     */
+    @Override
+    public Object invoke(Object proxy, Method called, Object[] calledArgs) throws Throwable {
+        switch (called.getName()) {
+            case \"invoke\":
+                Object[] withContinuation = Arrays.copyOf(args, args.length + 1);
+                withContinuation[args.length] = calledArgs[1];
+                try {
+                    return method.invoke(receiver, withContinuation);
+                } catch (InvocationTargetException e) {
+                    throw e.getCause();
+                }
+            case \"equals\":
+                return proxy == calledArgs[0];
+            case \"hashCode\":
+                return System.identityHashCode(proxy);
+            case \"toString\":
+                return \"suspend call of \" + method;
+            default:
+                throw new UnsupportedOperationException(called.toString());
+        }
+    }
+
+    /**
+     * Calls the suspend function {@code method}, whose last parameter is its {@code Continuation}, and blocks until it completes.
+     *
+     * @return the result of the function, boxed if it's a primitive
+     * @throws Throwable the exception thrown by the function
+     */
+    public static Object invoke(Method method, Object receiver, Object[] args) throws Throwable {
+        // Native code can call any method, not only the ones accessible from here
+        try {
+            method.setAccessible(true);
+        } catch (RuntimeException e) {
+            // Reported by `Method.invoke` if the method really is inaccessible
+        }
+
+        ClassLoader loader = method.getDeclaringClass().getClassLoader();
+        Class<?> function2 = Class.forName(\"kotlin.jvm.functions.Function2\", false, loader);
+        Class<?> coroutineContext = Class.forName(\"kotlin.coroutines.CoroutineContext\", false, loader);
+        Object emptyContext = Class.forName(\"kotlin.coroutines.EmptyCoroutineContext\", true, loader).getField(\"INSTANCE\").get(null);
+        Method runBlocking = Class.forName(\"kotlinx.coroutines.BuildersKt\", true, loader).getMethod(\"runBlocking\", coroutineContext, function2);
+
+        Object block = Proxy.newProxyInstance(loader, new Class<?>[] { function2 }, new Suspend(method, receiver, args));
+        try {
+            return runBlocking.invoke(null, emptyContext, block);
+        } catch (InvocationTargetException e) {
+            throw e.getCause();
+        }
+    }
+}
+";
+
+/// Source of the support class with the given fully qualified name, if there's one.
+pub(crate) fn render(class: &str) -> Option<String> {
+    match class {
+        "robusta.audio.ShortSamples" => Some(samples_class("ShortSamples", "short")),
+        "robusta.audio.FloatSamples" => Some(samples_class("FloatSamples", "float")),
+        "robusta.dispatch.Dispatch" => Some(DISPATCH_CLASS.to_string()),
+        "robusta.coroutines.Suspend" => Some(SUSPEND_CLASS.to_string()),
+        _ => None,
+    }
+}
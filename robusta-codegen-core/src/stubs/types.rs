@@ -0,0 +1,141 @@
+//! Source text of the Java types of bridged methods, shared by the Java and Kotlin generators so that both declare the same
+//! JNI signatures.
+
+use crate::stubs::JavaType;
+
+/// Language of the generated declarations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Language {
+    Java,
+    Kotlin,
+}
+
+/// A Java primitive type, with its JNI type descriptor and its names in both languages.
+pub(crate) struct Primitive {
+    pub(crate) java: &'static str,
+    pub(crate) descriptor: &'static str,
+    /// Wrapper class of its boxed values.
+    pub(crate) wrapper: &'static str,
+    /// Kotlin type, whose arrays are e.g. `IntArray`.
+    pub(crate) kotlin: &'static str,
+}
+
+pub(crate) const PRIMITIVES: [Primitive; 8] = [
+    Primitive { java: "boolean", descriptor: "Z", wrapper: "java.lang.Boolean", kotlin: "Boolean" },
+    Primitive { java: "byte", descriptor: "B", wrapper: "java.lang.Byte", kotlin: "Byte" },
+    Primitive { java: "char", descriptor: "C", wrapper: "java.lang.Character", kotlin: "Char" },
+    Primitive { java: "short", descriptor: "S", wrapper: "java.lang.Short", kotlin: "Short" },
+    Primitive { java: "int", descriptor: "I", wrapper: "java.lang.Integer", kotlin: "Int" },
+    Primitive { java: "long", descriptor: "J", wrapper: "java.lang.Long", kotlin: "Long" },
+    Primitive { java: "float", descriptor: "F", wrapper: "java.lang.Float", kotlin: "Float" },
+    Primitive { java: "double", descriptor: "D", wrapper: "java.lang.Double", kotlin: "Double" },
+];
+
+/// The primitive type named `java`, e.g. `int`.
+pub(crate) fn primitive(java: &str) -> Option<&'static Primitive> {
+    PRIMITIVES.iter().find(|p| p.java == java)
+}
+
+/// Java classes with type parameters, which Kotlin doesn't allow as raw types, and their number of type parameters.
+const GENERIC_CLASSES: [(&str, usize); 5] = [
+    ("java.lang.Class", 1),
+    ("java.util.function.Consumer", 1),
+    ("java.util.function.BiConsumer", 2),
+    ("kotlin.Pair", 2),
+    ("kotlin.Triple", 3),
+];
+
+/// Source text of a class, leaving out the `java.lang` package. Type arguments are `Int` rather than `Int?` in Kotlin.
+fn class_name(class: &str, language: Language, is_type_argument: bool) -> String {
+    if language == Language::Kotlin {
+        match class {
+            "java.lang.String" => return "String".into(),
+            "java.lang.Object" => return "Any".into(),
+            _ => {}
+        }
+        // Kotlin types are only boxed when they're nullable, or type arguments
+        if let Some(p) = PRIMITIVES.iter().find(|p| p.wrapper == class) {
+            return if is_type_argument { p.kotlin.into() } else { format!("{}?", p.kotlin) };
+        }
+    }
+
+    let name = match class.strip_prefix("java.lang.") {
+        Some(name) if !name.contains('.') => name.into(),
+        _ => class.to_string(),
+    };
+    match GENERIC_CLASSES.iter().find(|(c, _)| *c == class) {
+        Some((_, arity)) if language == Language::Kotlin => format!("{}<{}>", name, vec!["*"; *arity].join(", ")),
+        _ => name,
+    }
+}
+
+fn source_text(ty: &JavaType, language: Language, is_type_argument: bool) -> String {
+    let argument = |t: &JavaType| source_text(t, language, true);
+
+    match (ty, language) {
+        (JavaType::Void, Language::Java) => "void".into(),
+        (JavaType::Void, Language::Kotlin) => "Unit".into(),
+        (JavaType::Primitive(p), Language::Java) => (*p).into(),
+        (JavaType::Primitive(p), Language::Kotlin) => primitive(p).map_or("Any", |p| p.kotlin).into(),
+        (JavaType::Object(class), _) => class_name(class, language, is_type_argument),
+        (JavaType::Nullable(t), Language::Java) => source_text(t, language, is_type_argument),
+        (JavaType::Nullable(t), Language::Kotlin) => format!("{}?", argument(t)),
+        (JavaType::ArrayList(t), _) => format!("java.util.ArrayList<{}>", argument(t)),
+        (JavaType::List(t), Language::Java) => format!("java.util.List<{}>", argument(t)),
+        (JavaType::List(t), Language::Kotlin) => format!("List<{}>", argument(t)),
+        (JavaType::Array(t), Language::Java) => format!("{}[]", argument(t)),
+        (JavaType::Array(t), Language::Kotlin) => match &**t {
+            JavaType::Primitive(p) => format!("{}Array", primitive(p).map_or("Any", |p| p.kotlin)),
+            t => format!("Array<{}>", argument(t)),
+        },
+        (JavaType::CompletableFuture(t), _) => format!("java.util.concurrent.CompletableFuture<{}>", argument(t)),
+    }
+}
+
+/// Source text of a type in `language`, e.g. `java.util.List<Integer>` in Java and `List<Int>` in Kotlin.
+pub(crate) fn type_name(ty: &JavaType, language: Language) -> String {
+    source_text(ty, language, false)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::stubs::JavaType;
+
+    use super::{type_name, Language};
+
+    #[test]
+    fn types_are_spelled_in_both_languages() {
+        let object = |class: &str| JavaType::Object(class.into());
+        let nullable = |t: JavaType| JavaType::Nullable(Box::new(t));
+        let types = [
+            (JavaType::Void, "void", "Unit"),
+            (JavaType::Primitive("boolean"), "boolean", "Boolean"),
+            (object("java.lang.String"), "String", "String"),
+            (nullable(object("java.lang.String")), "String", "String?"),
+            (object("java.lang.Integer"), "Integer", "Int?"),
+            (nullable(object("java.lang.Integer")), "Integer", "Int?"),
+            (object("java.lang.Object"), "Object", "Any"),
+            (object("java.lang.Class"), "Class", "Class<*>"),
+            (object("java.util.function.BiConsumer"), "java.util.function.BiConsumer", "java.util.function.BiConsumer<*, *>"),
+            (JavaType::Array(Box::new(JavaType::Primitive("int"))), "int[]", "IntArray"),
+            (JavaType::Array(Box::new(object("java.lang.Long"))), "Long[]", "Array<Long>"),
+            (JavaType::List(Box::new(object("java.lang.String"))), "java.util.List<String>", "List<String>"),
+            (
+                JavaType::ArrayList(Box::new(nullable(object("java.lang.String")))),
+                "java.util.ArrayList<String>",
+                "java.util.ArrayList<String?>",
+            ),
+            (
+                JavaType::CompletableFuture(Box::new(object("java.lang.Void"))),
+                "java.util.concurrent.CompletableFuture<Void>",
+                "java.util.concurrent.CompletableFuture<Void>",
+            ),
+            (object("com.example.Foo"), "com.example.Foo", "com.example.Foo"),
+        ];
+
+        for (ty, java, kotlin) in types {
+            assert_eq!(type_name(&ty, Language::Java), java);
+            assert_eq!(type_name(&ty, Language::Kotlin), kotlin);
+        }
+    }
+}
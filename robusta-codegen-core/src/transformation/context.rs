@@ -0,0 +1,34 @@
+use proc_macro2::Ident;
+use syn::{Path, LifetimeDef, Type};
+use crate::transformation::{JavaPath, RenameRule};
+use crate::validation::FlattenedStructs;
+
+#[derive(Clone)]
+pub(crate) struct StructContext {
+    pub(crate) struct_type: Path,
+    pub(crate) struct_name: String,
+    pub(crate) struct_lifetimes: Vec<LifetimeDef>,
+    /// Type parameters of the impl block, with the type native functions are bridged with.
+    pub(crate) struct_type_params: Vec<(Ident, Type)>,
+    pub(crate) package: Option<JavaPath>,
+    pub(crate) flattened_structs: FlattenedStructs,
+    /// Naming convention of the Java methods without a `#[java_name]`.
+    pub(crate) rename_all: Option<RenameRule>,
+    /// Suffix of the Java class name, for an instantiation of a generic struct declared with `#[instantiate]`.
+    pub(crate) instantiation: Option<Ident>,
+}
+
+impl StructContext {
+    /// Simple name of the Java class bridged by the struct.
+    pub(crate) fn class_name(&self) -> String {
+        match &self.instantiation {
+            Some(suffix) => format!("{}{}", self.struct_name, suffix),
+            None => self.struct_name.clone(),
+        }
+    }
+
+    /// Java name of the method `ident` without a `#[java_name]`, if the module has a naming convention.
+    pub(crate) fn renamed(&self, ident: &Ident) -> Option<String> {
+        self.rename_all.map(|rule| rule.apply(&ident.to_string()))
+    }
+}
@@ -0,0 +1,244 @@
+//! `#[bridge(dry_run)]`: reports everything `#[bridge]` would reject in a module as warnings, and leaves it as plain Rust.
+//!
+//! The module is validated and transformed like with `#[bridge]`, but the errors are collected instead of failing the build,
+//! and the transformed module is thrown away. proc-macro-error only shows warnings on nightly, so each error is reported as
+//! the use of a `#[deprecated]` item, spanned at the start of the rejected construct.
+
+use std::cell::Cell;
+use std::panic::AssertUnwindSafe;
+
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use proc_macro_error::Level;
+use quote::ToTokens;
+use syn::fold::{self, Fold};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Attribute, Error, ImplItemMethod, Item, ItemFn, ItemImpl, ItemMod, ItemStruct, LitStr, Meta, NestedMeta, PatType, Signature, Token};
+
+use crate::transformation::lint::AllowAttributeCleaner;
+use crate::transformation::utils::is_bridge_attribute;
+use crate::transformation::{bridged_struct_attributes, BridgeOptions, ModTransformer};
+use crate::utils::get_abi;
+use crate::validation::JNIBridgeModule;
+
+thread_local! {
+    static DRY_RUN: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Level of the diagnostics about constructs that are accepted but can't work once bridged, which dry runs report as well.
+pub(crate) fn rejection_level() -> Level {
+    if DRY_RUN.with(Cell::get) {
+        Level::Error
+    } else {
+        Level::Warning
+    }
+}
+
+/// Expands `input` for `#[bridge(args)]` if `args` has `dry_run`.
+pub fn bridge_dry_run(args: TokenStream, input: TokenStream) -> Option<TokenStream> {
+    let nested = Punctuated::<NestedMeta, Token![,]>::parse_terminated.parse2(args.clone()).ok()?;
+    if !nested.iter().any(|m| matches!(m, NestedMeta::Meta(Meta::Path(p)) if p.is_ident("dry_run"))) {
+        return None;
+    }
+
+    let module: ItemMod = match syn::parse2(input) {
+        Ok(module) => module,
+        Err(e) => return Some(Error::new(e.span(), "`bridge` attribute is supported on mod items only").to_compile_error()),
+    };
+
+    let problems = capture_errors(|| {
+        // Invalid structs are reported, and the rest of the module is checked without them
+        let (mut bridge_module, _) = JNIBridgeModule::validate(module.clone());
+        let options = BridgeOptions::parse(args);
+        bridge_module.rename_all = options.rename_all;
+
+        ModTransformer::new(bridge_module).with_options(options).transform_module();
+    });
+
+    let mut module = DryRunCleaner.fold_item_mod(AllowAttributeCleaner.fold_item_mod(module));
+    if let Some((_, items)) = &mut module.content {
+        items.extend(problems.iter().map(|(span, message)| warning(*span, "RejectedByBridge", message)));
+    }
+
+    Some(module.into_token_stream())
+}
+
+/// Runs `f`, returning the span and message of each distinct error it reports instead of failing the build.
+fn capture_errors(f: impl FnOnce()) -> Vec<(Span, String)> {
+    DRY_RUN.with(|d| d.set(true));
+    // Entry points can be nested: the inner one turns the errors reported inside it into `compile_error!` invocations
+    let errors: TokenStream = proc_macro_error::entry_point(
+        AssertUnwindSafe(|| {
+            f();
+            proc_macro::TokenStream::new()
+        }),
+        false,
+    )
+    .into();
+    DRY_RUN.with(|d| d.set(false));
+
+    let mut problems: Vec<(Span, String)> = Vec::new();
+    let mut tokens = errors.into_iter();
+    // Each error is `compile_error! { "message" }`, with `compile_error` spanned at the start of the erroneous tokens
+    while let (Some(TokenTree::Ident(ident)), Some(_), Some(TokenTree::Group(group))) = (tokens.next(), tokens.next(), tokens.next()) {
+        let message = match syn::parse2::<LitStr>(group.stream()) {
+            Ok(message) => message.value(),
+            Err(_) => continue,
+        };
+
+        // The same error can be reported once for each method using a construct, e.g. a struct without `'env` lifetime
+        let span = ident.span();
+        if !problems.iter().any(|(s, m)| format!("{:?}", s) == format!("{:?}", span) && *m == message) {
+            problems.push((span, message));
+        }
+    }
+
+    problems
+}
+
+/// An item using a `#[deprecated]` struct named `marker` at `span`, whose deprecation note is `message`: a warning shown on
+/// stable compilers as well.
+pub(crate) fn warning(span: Span, marker: &str, message: &str) -> Item {
+    // Suggestions follow the message on their own lines, as `= help: ...`
+    let note = message.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join("\n");
+    let declared = Ident::new(marker, Span::call_site());
+    let used = Ident::new(marker, span);
+
+    parse_quote! {
+        const _: () = {
+            #[deprecated(note = #note)]
+            struct #declared;
+
+            let _ = #used;
+        };
+    }
+}
+
+/// Makes a bridge module plain Rust, by removing the attributes and ABIs that only `#[bridge]` knows.
+///
+/// Imported methods have no body to keep, so they panic when called.
+struct DryRunCleaner;
+
+impl DryRunCleaner {
+    fn clean_function(attrs: &mut Vec<Attribute>, sig: &Signature, block: &mut syn::Block) {
+        attrs.retain(|a| !is_bridge_attribute(a));
+
+        if get_abi(sig).as_deref() == Some("java") {
+            attrs.push(parse_quote! { #[allow(unused_variables)] });
+            *block = parse_quote! {{
+                ::core::unimplemented!("imported methods aren't generated by `#[bridge(dry_run)]`")
+            }};
+        }
+    }
+}
+
+impl Fold for DryRunCleaner {
+    fn fold_item_mod(&mut self, mut node: ItemMod) -> ItemMod {
+        node.attrs.retain(|a| !a.path.is_ident("call_type"));
+        fold::fold_item_mod(self, node)
+    }
+
+    fn fold_item_struct(&mut self, node: ItemStruct) -> ItemStruct {
+        ItemStruct {
+            attrs: bridged_struct_attributes(node.attrs),
+            ..node
+        }
+    }
+
+    fn fold_item_impl(&mut self, mut node: ItemImpl) -> ItemImpl {
+        if node.trait_.is_some() {
+            return node;
+        }
+
+        node.attrs.retain(|a| !is_bridge_attribute(a));
+        fold::fold_item_impl(self, node)
+    }
+
+    fn fold_impl_item_method(&mut self, mut node: ImplItemMethod) -> ImplItemMethod {
+        DryRunCleaner::clean_function(&mut node.attrs, &node.sig, &mut node.block);
+        fold::fold_impl_item_method(self, node)
+    }
+
+    fn fold_item_fn(&mut self, mut node: ItemFn) -> ItemFn {
+        DryRunCleaner::clean_function(&mut node.attrs, &node.sig, &mut node.block);
+        fold::fold_item_fn(self, node)
+    }
+
+    fn fold_signature(&mut self, mut node: Signature) -> Signature {
+        if matches!(get_abi(&node).as_deref(), Some("jni") | Some("java")) {
+            node.abi = None;
+        }
+        fold::fold_signature(self, node)
+    }
+
+    fn fold_pat_type(&mut self, mut node: PatType) -> PatType {
+        node.attrs.retain(|a| !is_bridge_attribute(a));
+        fold::fold_pat_type(self, node)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use quote::quote;
+
+    use super::*;
+
+    #[test]
+    fn dry_runs_leave_plain_rust() {
+        let module: ItemMod = parse_quote! {
+            #[call_type(unchecked)]
+            mod jni {
+                #[package(com.example)]
+                pub struct Foo;
+
+                #[package(com.example)]
+                #[derive(Signature)]
+                pub struct Bar;
+
+                #[export_visibility(hidden)]
+                impl Foo {
+                    #[java_name = "getName"]
+                    #[allow_raw_jni]
+                    pub extern "jni" fn name(#[default = "0"] id: i32) -> String {
+                        id.to_string()
+                    }
+
+                    #[constructor]
+                    pub extern "java" fn new(env: &JNIEnv) -> JniResult<Self> {}
+                }
+            }
+        };
+
+        let cleaned = DryRunCleaner.fold_item_mod(AllowAttributeCleaner.fold_item_mod(module));
+        let expected: ItemMod = parse_quote! {
+            mod jni {
+                pub struct Foo;
+
+                #[package(com.example)]
+                #[derive(Signature)]
+                pub struct Bar;
+
+                impl Foo {
+                    pub fn name(id: i32) -> String {
+                        id.to_string()
+                    }
+
+                    #[allow(unused_variables)]
+                    pub fn new(env: &JNIEnv) -> JniResult<Self> {
+                        ::core::unimplemented!("imported methods aren't generated by `#[bridge(dry_run)]`")
+                    }
+                }
+            }
+        };
+
+        assert_eq!(cleaned.into_token_stream().to_string(), quote! { #expected }.to_string());
+    }
+
+    #[test]
+    fn dry_runs_are_requested_in_the_arguments() {
+        let module = quote! { mod jni {} };
+
+        assert!(bridge_dry_run(quote! { register_natives }, module.clone()).is_none());
+        assert!(bridge_dry_run(quote! { dry_run = "yes" }, module).is_none());
+    }
+}
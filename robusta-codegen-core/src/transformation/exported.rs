@@ -0,0 +1,2665 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::{Ident, TokenStream};
+use proc_macro_error::{emit_error, emit_warning};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::fold::Fold;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::Extern;
+use syn::Lifetime;
+use syn::Token;
+use syn::{parse_quote, GenericArgument, GenericParam, Generics, LifetimeDef, Path, PathArguments, TypeTuple};
+use syn::{
+    Abi, Block, Expr, FnArg, ImplItem, ImplItemMethod, LitStr, Pat, PatIdent, PatType, ReturnType, Signature,
+    PathSegment, Type, TypeBareFn, TypeReference, VisPublic, Visibility,
+};
+
+use crate::transformation::context::StructContext;
+use crate::transformation::imported::{method_metadata, static_sig_types};
+use crate::transformation::utils::{borrowed_type, bridged_struct_type, check_array_types, check_critical_params, check_java_list, check_owned_type, check_struct_type_params, check_sig_descriptor, get_call_type, get_converter, get_default_value, get_deprecation, get_hidden_export, get_intern_cache_size, get_java_name, get_numeric_conversion, get_result_types, get_result_value_type, is_bridge_attribute, is_passthrough_type, owned_string_value, widen_wrapper, widened_type, AttributeFilter, Converter, NumericConversion};
+use crate::transformation::{BodyWrapper, CallType, FreestandingTransformer, SafeParams, JAVA_RESERVED_WORDS};
+use crate::utils::{get_abi, is_class_type, is_env_type, is_self_method, jni_function_name, mangle_jni_name};
+use crate::validation::FlattenedStructs;
+use std::iter::FromIterator;
+
+pub struct ExportedMethodTransformer<'ctx> {
+    pub(crate) struct_context: &'ctx StructContext,
+    /// Argument descriptors of overloaded methods, by Rust method name.
+    pub(crate) overloads: BTreeMap<Ident, String>,
+    /// Call type of methods without a `#[call_type]` attribute, inherited from the impl block or the module.
+    pub(crate) call_type: CallType,
+    pub(crate) body_wrapper: Option<&'ctx mut BodyWrapper<'ctx>>,
+    /// Whether methods are registered from `JNI_OnLoad` rather than exported by symbol.
+    pub(crate) register_natives: bool,
+    /// Type run around every exported method, from `#[bridge(middleware = "...")]`.
+    pub(crate) middleware: Option<Path>,
+    /// Whether the `impl` block is `#[export_visibility(hidden)]`.
+    pub(crate) hidden: bool,
+    /// Which attributes of the methods their JNI functions get.
+    pub(crate) attribute_filter: AttributeFilter,
+    /// A `NativeMethod` expression for each exported method, when registering them.
+    pub(crate) natives: Vec<TokenStream>,
+}
+
+impl<'ctx> Fold for ExportedMethodTransformer<'ctx> {
+    fn fold_impl_item(&mut self, node: ImplItem) -> ImplItem {
+        match node {
+            // No function is generated for methods using type parameters, which would only add unresolved names to the errors
+            ImplItem::Method(m) if get_abi(&m.sig).as_deref() == Some("jni") && !check_struct_type_params(&m.sig, self.struct_context) => {
+                ImplItem::Verbatim(TokenStream::new())
+            }
+            node => syn::fold::fold_impl_item(self, node),
+        }
+    }
+
+    fn fold_impl_item_method(&mut self, node: ImplItemMethod) -> ImplItemMethod {
+        let abi = get_abi(&node.sig);
+        match (&node.vis, &abi.as_deref()) {
+            (Visibility::Public(_), Some("jni")) => {
+                let call_type_attribute = get_call_type(&node.attrs)
+                    .map(|c| c.call_type)
+                    .unwrap_or_else(|| self.call_type.clone());
+
+                let mut jni_method_transformer = ExternJNIMethodTransformer::new(
+                    self.struct_context,
+                    call_type_attribute,
+                    self.overloads.get(&node.sig.ident).cloned(),
+                    self.body_wrapper.as_deref_mut().map(|w| w as &mut BodyWrapper),
+                    ExportOptions {
+                        register_natives: self.register_natives,
+                        middleware: self.middleware.as_ref(),
+                        hidden: self.hidden,
+                        attribute_filter: self.attribute_filter.clone(),
+                    },
+                );
+                let method = jni_method_transformer.fold_impl_item_method(node);
+                self.natives.extend(jni_method_transformer.native);
+                method
+            }
+            _ => node,
+        }
+    }
+}
+
+struct ExternJNIMethodTransformer<'ctx> {
+    struct_context: &'ctx StructContext,
+    call_type: CallType,
+    /// Argument descriptor appended to the symbol if the method is overloaded.
+    overload_descriptor: Option<String>,
+    body_wrapper: Option<&'ctx mut BodyWrapper<'ctx>>,
+    register_natives: bool,
+    middleware: Option<&'ctx Path>,
+    /// Whether the `impl` block is `#[export_visibility(hidden)]`.
+    hidden: bool,
+    attribute_filter: AttributeFilter,
+    /// The `NativeMethod` expression of the transformed method, when registering it.
+    native: Option<TokenStream>,
+}
+
+/// Options of the `impl` block and the module that apply to each exported method.
+struct ExportOptions<'ctx> {
+    register_natives: bool,
+    middleware: Option<&'ctx Path>,
+    hidden: bool,
+    attribute_filter: AttributeFilter,
+}
+
+impl<'ctx> ExternJNIMethodTransformer<'ctx> {
+    fn new(
+        struct_context: &'ctx StructContext,
+        call_type: CallType,
+        overload_descriptor: Option<String>,
+        body_wrapper: Option<&'ctx mut BodyWrapper<'ctx>>,
+        options: ExportOptions<'ctx>,
+    ) -> Self {
+        let ExportOptions { register_natives, middleware, hidden, attribute_filter } = options;
+        ExternJNIMethodTransformer {
+            struct_context,
+            call_type,
+            overload_descriptor,
+            body_wrapper,
+            register_natives,
+            middleware,
+            hidden,
+            attribute_filter,
+            native: None,
+        }
+    }
+}
+
+impl<'ctx> Fold for ExternJNIMethodTransformer<'ctx> {
+    fn fold_impl_item_method(&mut self, node: ImplItemMethod) -> ImplItemMethod {
+        check_array_types(&node.sig);
+        check_critical_params(&node.sig);
+
+        let return_conversion = get_numeric_conversion(&node.attrs);
+        if let (Some(_), ReturnType::Default) = (return_conversion, &node.sig.output) {
+            let attr = node.attrs.iter().find(|a| NumericConversion::is_attribute(a)).unwrap();
+            emit_error!(attr, "`#[{}]` on a method converts its return value, but this method doesn't return anything", attr.path.to_token_stream();
+                help = "to convert a parameter, put the attribute on the parameter");
+        }
+
+        let return_converter = match (get_converter(&node.attrs, false), node.attrs.iter().find(|a| a.path.is_ident("convert"))) {
+            (Some(_), Some(attr)) if matches!(node.sig.output, ReturnType::Default) => {
+                emit_error!(attr, "`#[convert]` on a method converts its return value, but this method doesn't return anything";
+                    help = "to convert a parameter, put `#[convert(with = \"...\")]` on the parameter");
+                None
+            }
+            (Some(_), Some(attr)) if return_conversion.is_some() => {
+                emit_error!(attr, "`#[convert]` can't be used with `#[truncate]`, `#[saturate]` or `#[reinterpret]`";
+                    note = "the function of `#[convert]` does the whole conversion");
+                None
+            }
+            (Some(_), Some(_)) if node.sig.asyncness.is_some() => {
+                emit_error!(node.sig.asyncness, "return values of `async` methods can't be converted with `#[convert]`";
+                    note = "they complete a `CompletableFuture`, as boxed values");
+                None
+            }
+            (converter, _) => converter,
+        };
+
+        let return_type = match &node.sig.output {
+            ReturnType::Type(_, ty) => Some(&**ty),
+            ReturnType::Default => None,
+        };
+        // The descriptor of a converted return value is the one of the JNI type Java gets back
+        let return_descriptor = check_sig_descriptor(&node.attrs, return_converter.as_ref().map(|c| &c.jni_type).or(return_type))
+            .or_else(|| check_java_list(&node.attrs, return_converter.as_ref().map(|c| &c.jni_type).or(return_type)));
+        if let (Some(_), Some(asyncness)) = (&return_descriptor, &node.sig.asyncness) {
+            let attribute = if node.attrs.iter().any(|a| a.path.is_ident("sig")) { "sig" } else { "java_list" };
+            emit_error!(asyncness, "`async` methods can't have a `#[{}]`", attribute;
+                note = "they return a `CompletableFuture`");
+        }
+
+        let mut jni_signature = JNISignature::new(
+            node.sig.clone(),
+            &self.struct_context,
+            self.call_type.clone(),
+            return_conversion,
+            return_converter.clone(),
+        );
+        if let Some(descriptor) = return_descriptor.filter(|_| node.sig.asyncness.is_none()) {
+            jni_signature.return_sig_type = Some(quote! { #descriptor });
+        }
+
+        let transformed_jni_signature = jni_signature.transformed_signature();
+        let method_call = jni_signature.signature_call();
+        let java_name = get_java_name(&node.attrs).map(|n| n.value()).or_else(|| {
+            let renamed = self.struct_context.renamed(&node.sig.ident)?;
+            if JAVA_RESERVED_WORDS.contains(&renamed.as_str()) {
+                emit_error!(node.sig.ident, "`{}` is renamed to `{}`, which is a reserved word in Java", node.sig.ident, renamed;
+                    help = "choose another name with `#[java_name = \"...\"]`");
+            }
+            Some(renamed)
+        });
+
+        let new_block: Block = match &self.call_type {
+            // Without conversions there's nothing to fail and no exception to throw, only panics to catch (see below)
+            // (spanned at the macro, as wrappers of the block would otherwise be linted for unnecessary braces around it)
+            _ if jni_signature.is_passthrough() => parse_quote! {{
+                #method_call
+            }},
+            CallType::Unchecked { .. } if jni_signature.asyncness => {
+                let completable = spawn_completable(&node.sig, &method_call, "java/lang/RuntimeException", None);
+                parse_quote_spanned! { node.span() => {
+                    #completable.unwrap()
+                }}
+            }
+            CallType::Unchecked { .. } => match &return_converter {
+                Some(Converter { function, .. }) => parse_quote_spanned! { function.span() => {
+                    ::core::convert::Into::into(#function(#method_call, env))
+                }},
+                None => {
+                    let value = match &node.sig.output {
+                        ReturnType::Type(_, ty) => owned_string_value(ty, method_call.clone()).map(|(_, value)| value),
+                        ReturnType::Default => None,
+                    }
+                    .unwrap_or_else(|| method_call.clone());
+                    parse_quote_spanned! { node.span() => {
+                        ::robusta_jni::convert::IntoJavaValue::into(#value, env)
+                    }}
+                }
+            },
+
+            CallType::Safe(exception_details) => {
+                let outer_call_inputs = {
+                    let mut inputs: Punctuated<Expr, Token![,]> = jni_signature
+                        .args_iter()
+                        .map(|p| -> Expr {
+                            let PatType { pat, .. } = p;
+
+                            match &**pat {
+                                Pat::Ident(PatIdent { ident, .. }) => {
+                                    parse_quote_spanned!(ident.span() => #ident)
+                                }
+                                _ => panic!("Non-identifier argument pattern in function"),
+                            }
+                        })
+                        .collect();
+
+                    inputs.push(parse_quote!(env));
+                    if jni_signature.takes_class() {
+                        inputs.push(parse_quote!(class));
+                    }
+                    inputs
+                };
+                let outer_signature = {
+                    let mut s = transformed_jni_signature.clone();
+                    s.ident = Ident::new("outer", s.ident.span());
+
+                    s.inputs.iter_mut().for_each(|i| {
+                        if let FnArg::Typed(PatType { pat, .. }) = i {
+                            if let Pat::Ident(PatIdent { mutability, .. }) = pat.as_mut() {
+                                *mutability = None
+                            }
+                        }
+                    });
+
+                    s.inputs.push(FnArg::Typed(PatType {
+                        attrs: vec![],
+                        pat: Box::new(Pat::Ident(PatIdent {
+                            attrs: vec![],
+                            by_ref: None,
+                            mutability: None,
+                            ident: Ident::new("env", s.inputs.span()),
+                            subpat: None,
+                        })),
+                        colon_token: Token![:](s.inputs.span()),
+                        ty: Box::new(parse_quote! { ::robusta_jni::jni::JNIEnv<'env> }),
+                    }));
+                    if jni_signature.takes_class() {
+                        s.inputs.push(parse_quote! { class: ::robusta_jni::jni::objects::JClass<'env> });
+                    }
+
+                    let outer_signature_span = s.span();
+                    let outer_output_type: Type = match s.output {
+                        ReturnType::Default => parse_quote!(()),
+                        ReturnType::Type(_, ty) => *ty,
+                    };
+
+                    s.output = ReturnType::Type(
+                        Token![->](outer_signature_span),
+                        Box::new(
+                            parse_quote_spanned!(outer_output_type.span() => ::core::result::Result<#outer_output_type, ::robusta_jni::__private::Box<dyn ::robusta_jni::__private::Error>>),
+                        ),
+                    );
+                    s.abi = None;
+                    s
+                };
+
+                let default_exception_class = "java.lang.RuntimeException".parse().unwrap();
+                let (exception_class, message) = match exception_details {
+                    Some(SafeParams {
+                        exception_class,
+                        message,
+                    }) => {
+                        let exception_class_result =
+                            exception_class.as_ref().unwrap_or(&default_exception_class);
+
+                        (exception_class_result, message.as_deref())
+                    }
+                    None => (&default_exception_class, None),
+                };
+
+                let exception_classpath_path = exception_class.to_classpath_path();
+                let is_default_exception_class = exception_class == &default_exception_class;
+                // Errors of `Result<T, E>` methods may implement `IntoJavaException`, and choose the class of their exception
+                let error_type = match &node.sig.output {
+                    ReturnType::Type(_, ty) if !jni_signature.asyncness => get_result_types(ty).map(|(_, e)| e),
+                    _ => None,
+                };
+                // Without a configured message, the error itself describes what went wrong
+                let (error_binding, exception_message): (Pat, Expr) = match message {
+                    Some(m) if error_type.is_none() => (parse_quote! { _ }, parse_quote! { #m }),
+                    Some(m) => (parse_quote! { e }, parse_quote! { #m }),
+                    None => (parse_quote! { e }, parse_quote! { e }),
+                };
+                let exception_class_expr: Expr = if error_type.is_some() {
+                    parse_quote! { ::robusta_jni::__private::exception_class_of(&*e).unwrap_or(#exception_classpath_path) }
+                } else {
+                    parse_quote! { #exception_classpath_path }
+                };
+
+                /* The configured class may be missing at runtime (e.g. removed by a code shrinker), or lack a `(String)` constructor:
+                 * rather than leaving the Java caller without any exception, throw a `RuntimeException` reporting both problems. */
+                let throw_fallback = if error_type.is_some() {
+                    quote_spanned! { node.span() =>
+                        let r = r.or_else(|e| {
+                            let _ = env.exception_clear();
+                            env.throw_new(
+                                "java/lang/RuntimeException",
+                                ::robusta_jni::__private::format!("{} (could not throw configured exception class `{}`: {})", message, class.replace('/', "."), e),
+                            )
+                        });
+                    }
+                } else if is_default_exception_class {
+                    quote! {}
+                } else {
+                    let exception_class_name = exception_class.to_string();
+                    quote_spanned! { node.span() =>
+                        let r = r.or_else(|e| {
+                            let _ = env.exception_clear();
+                            env.throw_new(
+                                "java/lang/RuntimeException",
+                                ::robusta_jni::__private::format!("{} (could not throw configured exception class `{}`: {})", message, #exception_class_name, e),
+                            )
+                        });
+                    }
+                };
+
+                // The `Ok` value of `Result`s is converted, by the function of `#[convert]` if there's one
+                let into_java = |value: Expr, ty: Option<&Type>| -> Expr {
+                    match &return_converter {
+                        Some(Converter { function, .. }) => parse_quote_spanned! { function.span() =>
+                            ::core::convert::Into::into(#function(#value, env)?)
+                        },
+                        None => {
+                            let value = ty.and_then(|ty| owned_string_value(ty, value.clone())).map_or(value, |(_, value)| value);
+                            parse_quote_spanned! { node.span() => ::robusta_jni::convert::TryIntoJavaValue::try_into(#value, env)? }
+                        }
+                    }
+                };
+                let outer_body: Expr = if jni_signature.asyncness {
+                    let completable = spawn_completable(&node.sig, &method_call, &exception_classpath_path, message);
+                    parse_quote_spanned! { node.span() => Ok(#completable?) }
+                } else {
+                    match &node.sig.output {
+                        ReturnType::Type(_, ty) => get_result_types(ty),
+                        ReturnType::Default => None,
+                    }
+                    .map_or_else(
+                        || {
+                            let return_type = match &node.sig.output {
+                                ReturnType::Type(_, ty) => Some(&**ty),
+                                ReturnType::Default => None,
+                            };
+                            let value = into_java(method_call.clone(), return_type);
+                            parse_quote_spanned! { node.span() => Ok(#value) }
+                        },
+                        |(ok_type, error_type)| {
+                            let value = into_java(parse_quote! { v }, Some(ok_type));
+                            parse_quote_spanned! { error_type.span() =>
+                                match #method_call {
+                                    Ok(v) => Ok(#value),
+                                    Err(e) => Err({
+                                        #[allow(unused_imports)]
+                                        use ::robusta_jni::__private::{DisplayedError as _, TypedError as _};
+                                        (&::robusta_jni::__private::ErrorRef(&e)).to_method_error()
+                                    }),
+                                }
+                            }
+                        },
+                    )
+                };
+
+                parse_quote_spanned! { node.span() => {
+                    #outer_signature {
+                        #outer_body
+                    }
+
+                    match outer(#outer_call_inputs) {
+                        Ok(result) => result,
+                        Err(#error_binding) => {
+                            // An exception thrown by Java code (e.g. during a conversion) is left to propagate, as it's more precise
+                            if !env.exception_check().unwrap_or(false) {
+                                let message = ::robusta_jni::__private::ToString::to_string(&#exception_message);
+                                let class = #exception_class_expr;
+                                let r = env.throw_new(class, message.as_str());
+
+                                #throw_fallback
+
+                                if let Err(e) = r {
+                                    ::robusta_jni::__private::println!("Error while throwing Java exception: {}", e);
+                                }
+                            }
+
+                            /* We never hand out Rust references and the object returned is ignored
+                             * by the JVM, so it should be safe to just return zeroed memory.
+                             * Also, all primitives have a valid zero representation and because objects
+                             * are represented as pointers this should not have any unsafe side effects.
+                             * (Uninitialized memory would probably work as well)
+                             */
+                            unsafe { ::core::mem::zeroed() }
+                        }
+                    }
+                }}
+            }
+        };
+
+        let deprecation = get_deprecation(&node.attrs);
+        let deprecation_warning = node
+            .attrs
+            .iter()
+            .find(|a| a.path.is_ident("deprecation_warning"));
+
+        let java_class_name = [
+            self.struct_context
+                .package
+                .as_ref()
+                .map(|p| p.to_string())
+                .unwrap_or_default(),
+            self.struct_context.class_name(),
+        ]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(".");
+        let method_name = java_name.clone().unwrap_or_else(|| node.sig.ident.to_string());
+        let java_method_name = format!("{}.{}", java_class_name, method_name);
+
+        let new_block: Block = match (&deprecation, deprecation_warning) {
+            (Some(deprecation), Some(_)) => {
+                let warning = format!(
+                    "Warning: called native method `{}`, which is {}",
+                    java_method_name,
+                    deprecation.describe()
+                );
+
+                parse_quote_spanned! { node.span() => {
+                    static DEPRECATION_WARNING: ::robusta_jni::__private::Once = ::robusta_jni::__private::Once::new();
+                    DEPRECATION_WARNING.call_once(|| ::robusta_jni::__private::eprintln!("{}", #warning));
+
+                    #new_block
+                }}
+            }
+            (None, Some(attr)) => {
+                emit_error!(attr, "`#[deprecation_warning]` can only be used on `#[deprecated]` methods");
+                new_block
+            }
+            (_, None) => new_block,
+        };
+
+        // Calls on the same object (or of static methods on the same class) are serialized like Java's `synchronized` methods
+        let new_block: Block = match node.attrs.iter().find(|a| a.path.is_ident("synchronized")) {
+            Some(attr) if jni_signature.asyncness => {
+                emit_error!(attr, "`async` methods can't be `#[synchronized]`";
+                    note = "the monitor would only be held until the future is spawned, not until it completes");
+                new_block
+            }
+            Some(_) if mutable_receiver(&node.sig).is_some() => {
+                emit_error!(mutable_receiver(&node.sig), "`#[synchronized]` methods can't take `&mut self`";
+                    help = "take `mut self` by value instead";
+                    note = "`&mut self` only borrows a value converted for this call, not the Java object whose monitor is held");
+                new_block
+            }
+            Some(_) => {
+                let monitor: Expr = if is_self_method(&node.sig) {
+                    parse_quote! { ::robusta_jni::convert::JavaValue::autobox(receiver, env) }
+                } else {
+                    parse_quote! { class }
+                };
+
+                // `MonitorEnter` only fails when the JVM is out of memory
+                parse_quote_spanned! { node.span() => {
+                    let _monitor = env.lock_obj(#monitor).expect("could not enter the monitor of a `#[synchronized]` method");
+                    #new_block
+                }}
+            }
+            None => new_block,
+        };
+
+        let new_block: Block = match self.body_wrapper.as_deref_mut() {
+            Some(body_wrapper) => {
+                let wrapped_block = body_wrapper(self.struct_context, &node, new_block.to_token_stream());
+
+                syn::parse2(wrapped_block).unwrap_or_else(|e| {
+                    emit_error!(node.sig.ident, "wrapped body of exported method is not a block: {}", e);
+                    new_block
+                })
+            }
+            None => new_block,
+        };
+
+        // Inside `catch_panic`, so that the middleware sees panics unwinding through it
+        let new_block: Block = match self.middleware {
+            Some(middleware) => parse_quote_spanned! { node.span() => {
+                ::robusta_jni::__private::call_with_middleware::<#middleware, _>(&env, #java_class_name, #method_name, || #new_block)
+            }},
+            None => new_block,
+        };
+
+        // Unwinding into the JVM is undefined behavior: a panic throws an exception instead (see `robusta_jni::panic`)
+        let new_block: Block = if self.call_type.catches_panics() {
+            parse_quote_spanned! { node.span() => {
+                match ::robusta_jni::panic::catch_panic(env, || #new_block) {
+                    ::core::option::Option::Some(result) => result,
+                    ::core::option::Option::None => unsafe { ::core::mem::zeroed() },
+                }
+            }}
+        } else {
+            new_block
+        };
+
+        // With the `ref-debug` feature, references created during the call are counted under the Java name of the method
+        let new_block: Block = parse_quote_spanned! { node.span() => {
+            ::robusta_jni::__private::ref_site!(#java_method_name);
+            #new_block
+        }};
+
+        // `#[export_name]` is kept on the function, exporting it under a symbol of its own instead of the JNI name
+        let export_name = node.attrs.iter().find(|a| a.path.is_ident("export_name"));
+        let hidden = match get_hidden_export(&node.attrs) {
+            Some(attr) if !self.register_natives => {
+                emit_error!(attr, "methods can only be hidden when they're registered with `RegisterNatives`";
+                    note = "the JVM looks up the native methods of other modules by their exported symbol";
+                    help = "register the methods of the module with `#[bridge(register_natives)]`");
+                false
+            }
+            Some(attr) => {
+                if let Some(export_name) = export_name {
+                    emit_error!(export_name, "hidden methods can't have an `#[export_name]`";
+                        note = "hidden by `{}`", attr.to_token_stream());
+                }
+                true
+            }
+            None => self.hidden && export_name.is_none(),
+        };
+        let exported_symbol = !hidden && (export_name.is_some() || !self.register_natives);
+
+        let impl_item_attributes = {
+            let mut attributes = self.attribute_filter.wrapper_attributes(node.attrs.clone());
+            // Registered functions are only called through the pointer given to `RegisterNatives`
+            if exported_symbol && export_name.is_none() {
+                attributes.push(parse_quote! { #[no_mangle] });
+            }
+            // Conversion types of tuples (e.g. `<(String, i64, bool) as TryIntoJavaValue<'env>>::Target`) are spelled out in the signature
+            attributes.push(parse_quote! { #[allow(clippy::type_complexity)] });
+
+            /* The wrapper is never called from Rust, so it doesn't need to be deprecated itself; it does however call
+             * the deprecated method, which would make the user crate trip on its own deprecation lint. */
+            if deprecation.is_some() {
+                attributes.push(parse_quote! { #[allow(deprecated)] });
+            }
+            attributes
+        };
+
+        let node_span = node.span();
+        let vis = if !exported_symbol {
+            Visibility::Inherited
+        } else {
+            Visibility::Public(VisPublic {
+                pub_token: Token![pub](node_span),
+            })
+        };
+        let is_static = !is_self_method(&node.sig);
+        let (param_sig_types, return_sig_type) = (jni_signature.param_sig_types.clone(), jni_signature.return_sig_type.clone());
+        let sig = self.transform_signature(&node.sig, jni_signature, java_name.clone());
+
+        // The symbol of an overload has the argument descriptor inferred from the syntax of the parameter types, as symbols are
+        // literals: the compiler checks it against their `Signature`s, which are the parameter types the JVM looks it up with
+        let new_block: Block = match &self.overload_descriptor {
+            Some(descriptor) if exported_symbol && export_name.is_none() && self.struct_context.struct_type_params.is_empty() => {
+                let params = static_sig_types(&self.struct_context.struct_type, &param_sig_types);
+                let message = format!(
+                    "the native symbol of `{}` is for the parameter types `{}`, which differ from their `Signature`s: register the \
+                     methods of the module with `#[bridge(register_natives)]`, or give the overload another Java name",
+                    node.sig.ident, descriptor
+                );
+                parse_quote_spanned! { node_span => {
+                    const _: () = ::core::assert!(::robusta_jni::signature::is_params_descriptor(&[#(#params),*], #descriptor), #message);
+                    #new_block
+                }}
+            }
+            _ => new_block,
+        };
+
+        if self.register_natives {
+            let class = [
+                self.struct_context
+                    .package
+                    .as_ref()
+                    .map(|p| p.to_string().replace('.', "/"))
+                    .unwrap_or_default(),
+                self.struct_context.class_name(),
+            ]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("/");
+            let name = java_name.unwrap_or_else(|| node.sig.ident.to_string());
+            let method = method_metadata(
+                &self.struct_context.struct_type,
+                &class,
+                &name,
+                &param_sig_types,
+                return_sig_type.as_ref(),
+                is_static,
+            );
+            let fn_ident = &sig.ident;
+            self.native = Some(quote! {
+                ::robusta_jni::natives::NativeMethod {
+                    method: #method,
+                    fn_ptr: #fn_ident as *mut ::core::ffi::c_void,
+                }
+            });
+        }
+
+        ImplItemMethod {
+            attrs: impl_item_attributes,
+            vis,
+            defaultness: node.defaultness,
+            sig,
+            block: new_block,
+        }
+    }
+
+    /// Transform original signature in JNI-ready one, including JClass and JNIEnv parameters into the function signature.
+    fn fold_signature(&mut self, node: Signature) -> Signature {
+        let jni_signature =
+            JNISignature::new(node.clone(), &self.struct_context, self.call_type.clone(), None, None);
+
+        self.transform_signature(&node, jni_signature, None)
+    }
+}
+
+impl<'ctx> ExternJNIMethodTransformer<'ctx> {
+    fn transform_signature(&self, node: &Signature, jni_signature: JNISignature, java_name: Option<String>) -> Signature {
+        let mut sig = jni_signature.transformed_signature;
+
+        let java_method_name = java_name.unwrap_or_else(|| sig.ident.to_string());
+        let mut jni_method_name = jni_function_name(
+            &self
+                .struct_context
+                .package
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            &self.struct_context.class_name(),
+            &java_method_name,
+        );
+
+        // Overloaded native methods are told apart by the long form of their symbol, which includes the argument descriptor
+        if let Some(descriptor) = &self.overload_descriptor {
+            jni_method_name.push_str("__");
+            jni_method_name.push_str(&mangle_jni_name(descriptor));
+        }
+
+        sig.inputs = {
+            let mut res = Punctuated::new();
+            res.push(parse_quote!(env: ::robusta_jni::jni::JNIEnv<'env>));
+
+            if !is_self_method(node) {
+                res.push(parse_quote!(class: ::robusta_jni::jni::objects::JClass<'env>));
+            }
+
+            res.extend(sig.inputs);
+            res
+        };
+
+        sig.ident = Ident::new(&jni_method_name, sig.ident.span());
+        sig.abi = Some(Abi {
+            extern_token: Extern { span: sig.span() },
+            name: Some(LitStr::new("system", sig.span())),
+        });
+
+        sig
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use proc_macro2::TokenStream;
+
+    use super::*;
+    use crate::transformation::{CallTypeAttribute, JavaPath, RenameRule};
+
+    fn setup_package(
+        package: Option<JavaPath>,
+        struct_name: String,
+        method_name: String,
+    ) -> ImplItemMethod {
+        let struct_name_token_stream = TokenStream::from_str(&struct_name).unwrap();
+        let method_name_token_stream = TokenStream::from_str(&method_name).unwrap();
+
+        let method: ImplItemMethod =
+            parse_quote! { pub extern "jni" fn #method_name_token_stream() {} };
+        let struct_context = StructContext {
+            struct_type: parse_quote! { #struct_name_token_stream },
+            struct_name,
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package,
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: false,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        transformer.fold_impl_item_method(method)
+    }
+
+    #[test]
+    fn jni_method_is_public() {
+        let output = setup_package(None, "Foo".into(), "foo".into());
+        assert!(matches!(output.vis, Visibility::Public(_)))
+    }
+
+    #[test]
+    fn jni_method_follows_naming_scheme() {
+        let output_no_package = setup_package(None, "Foo".into(), "foo".into());
+        assert_eq!(
+            output_no_package.sig.ident.to_string(),
+            format!("Java_Foo_foo")
+        );
+
+        let output_with_package = setup_package(
+            Some(JavaPath::from_str("com.bar.quux").unwrap()),
+            "Foo".into(),
+            "foo".into(),
+        );
+        assert_eq!(
+            output_with_package.sig.ident.to_string(),
+            format!("Java_com_bar_quux_Foo_foo")
+        );
+    }
+
+    #[test]
+    fn jni_method_name_is_mangled() {
+        let output = setup_package(
+            Some(JavaPath::from_str("com.my_company").unwrap()),
+            "Foo".into(),
+            "get_name".into(),
+        );
+        assert_eq!(output.sig.ident.to_string(), "Java_com_my_1company_Foo_get_1name");
+    }
+
+    #[test]
+    fn jni_method_has_no_mangle() {
+        let output = setup_package(None, "Foo".into(), "foo".into());
+        let no_mangle = parse_quote! { #[no_mangle] };
+        assert!(output.attrs.contains(&no_mangle));
+    }
+
+    #[test]
+    fn jni_method_has_system_abi() {
+        let output = setup_package(None, "Foo".into(), "foo".into());
+        assert_eq!(output.sig.abi.unwrap().name.unwrap().value(), "system")
+    }
+
+    fn setup_with_method(method: ImplItemMethod) -> ImplItemMethod {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: Some(JavaPath::from_str("com.bar.quux").unwrap()),
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: false,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        transformer.fold_impl_item_method(method)
+    }
+
+    #[test]
+    fn java_name_changes_jni_symbol() {
+        let output = setup_with_method(parse_quote! {
+            #[java_name = "getUserName"]
+            pub extern "jni" fn get_user_name() {}
+        });
+
+        assert_eq!(output.sig.ident.to_string(), "Java_com_bar_quux_Foo_getUserName");
+        assert!(!output.attrs.iter().any(|a| a.path.is_ident("java_name")));
+    }
+
+    #[test]
+    fn jni_method_keeps_user_attributes() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: Some(JavaPath::from_str("com.bar").unwrap()),
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: false,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::new(&[parse_quote! { tracing::instrument }]),
+            native: None,
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! {
+            /// Adds one.
+            #[inline]
+            #[tracing::instrument(skip_all)]
+            #[java_name = "increment"]
+            #[call_type(unchecked)]
+            pub extern "jni" fn add_one(value: i32) -> i32 {}
+        });
+        let tokens = output.to_token_stream().to_string();
+        assert!(tokens.contains(r#"# [doc = r" Adds one."]"#));
+        assert!(tokens.contains("# [inline]"));
+        assert!(tokens.contains("# [tracing :: instrument (skip_all)]"));
+        assert!(!tokens.contains("java_name"));
+        assert!(!tokens.contains("call_type"));
+    }
+
+    #[test]
+    fn overloaded_jni_method_has_long_symbol() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: Some(JavaPath::from_str("com.bar.quux").unwrap()),
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: Some("Ljava/lang/String;I".into()),
+            body_wrapper: None,
+            register_natives: false,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! {
+            #[java_name = "greet"]
+            pub extern "jni" fn greet_times(name: String, times: i32) {}
+        });
+        assert_eq!(output.sig.ident.to_string(), "Java_com_bar_quux_Foo_greet__Ljava_lang_String_2I");
+    }
+
+    #[test]
+    fn rename_all_changes_jni_symbol() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: Some(JavaPath::from_str("com.bar.quux").unwrap()),
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: Some(RenameRule::CamelCase),
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: false,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! { pub extern "jni" fn parse_json_url() {} });
+        assert_eq!(output.sig.ident.to_string(), "Java_com_bar_quux_Foo_parseJsonUrl");
+
+        // `#[java_name]` wins over the naming convention
+        let output = transformer.fold_impl_item_method(parse_quote! {
+            #[java_name = "parse_url"]
+            pub extern "jni" fn parse_json_url() {}
+        });
+        assert_eq!(output.sig.ident.to_string(), "Java_com_bar_quux_Foo_parse_1url");
+    }
+
+    #[test]
+    fn registered_methods_are_described_by_signatures() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo<'env> },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: Some(JavaPath::from_str("com.bar").unwrap()),
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: true,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! {
+            pub extern "jni" fn greet(self, env: JNIEnv, name: String, #[default = 1] times: i32) -> Result<Self, String> {}
+        });
+        assert!(!output.attrs.iter().any(|a| a.path.is_ident("no_mangle")));
+        assert!(matches!(output.vis, Visibility::Inherited));
+
+        let native = transformer.native.expect("registered method has no `NativeMethod`").to_string();
+        assert!(native.contains(r#"class : "com/bar/Foo""#));
+        assert!(native.contains(r#"name : "greet""#));
+        // The receiver and the environment aren't passed by Java
+        assert!(native.contains("params : { const PARAMS : & [& str] = & [:: robusta_jni :: signature :: parameter_signature (< String as :: robusta_jni :: convert :: Signature > :: SIG_TYPE) , :: robusta_jni :: signature :: parameter_signature (< :: robusta_jni :: convert :: JOption < i32 > as"));
+        assert!(native.contains("ret : < Result < Foo < 'static > , String > as :: robusta_jni :: convert :: Signature > :: SIG_TYPE"));
+        assert!(native.contains("is_static : false"));
+        assert!(native.contains("fn_ptr : Java_com_bar_Foo_greet as * mut :: core :: ffi :: c_void"));
+    }
+
+    #[test]
+    fn middleware_runs_inside_catch_panic() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: Some(JavaPath::from_str("com.bar").unwrap()),
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let middleware: Path = parse_quote! { crate::Counter };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: false,
+            middleware: Some(&middleware),
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! {
+            #[java_name = "greet"]
+            pub extern "jni" fn say_hello(name: String) -> String {}
+        });
+        let block = output.block.to_token_stream().to_string();
+        let catch_panic = block.find(":: robusta_jni :: panic :: catch_panic").expect("panics aren't caught");
+        let middleware = block
+            .find(r#":: robusta_jni :: __private :: call_with_middleware :: < crate :: Counter , _ > (& env , "com.bar.Foo" , "greet" ,"#)
+            .expect("no middleware call");
+        assert!(catch_panic < middleware);
+    }
+
+    #[test]
+    fn deprecated_jni_method_wrapper_allows_deprecated() {
+        let output = setup_with_method(parse_quote! {
+            #[deprecated(since = "1.2", note = "use `bar` instead")]
+            pub extern "jni" fn foo() {}
+        });
+
+        let allow_deprecated = parse_quote! { #[allow(deprecated)] };
+        assert!(output.attrs.contains(&allow_deprecated));
+        assert!(!output.attrs.iter().any(|a| a.path.is_ident("deprecated")));
+    }
+
+    #[test]
+    fn deprecation_warning_is_emitted_once() {
+        let output = setup_with_method(parse_quote! {
+            #[deprecated(since = "1.2", note = "use `bar` instead")]
+            #[deprecation_warning]
+            pub extern "jni" fn foo() {}
+        });
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.contains(":: robusta_jni :: __private :: Once"));
+        assert!(block.contains(":: robusta_jni :: __private :: eprintln !"));
+        assert!(block.contains(
+            "Warning: called native method `com.bar.quux.Foo.foo`, which is deprecated since 1.2: use `bar` instead"
+        ));
+        assert!(!output
+            .attrs
+            .iter()
+            .any(|a| a.path.is_ident("deprecation_warning")));
+    }
+
+    #[test]
+    fn default_params_are_passed_as_objects() {
+        let output = setup_with_method(parse_quote! {
+            pub extern "jni" fn foo(size: i32, #[default = "100"] capacity: i32) {}
+        });
+
+        let object_type: Type = parse_quote! { ::robusta_jni::jni::objects::JObject<'env> };
+        let args: Vec<_> = output.sig.inputs.into_iter().collect();
+        match args.as_slice() {
+            [_env, _class, FnArg::Typed(PatType { ty: ty_1, .. }), FnArg::Typed(PatType { ty: ty_2, attrs, .. })] => {
+                assert_ne!(
+                    ty_1.to_token_stream().to_string(),
+                    object_type.to_token_stream().to_string()
+                );
+                assert_eq!(
+                    ty_2.to_token_stream().to_string(),
+                    object_type.to_token_stream().to_string()
+                );
+                assert!(attrs.is_empty());
+            }
+            _ => panic!("unexpected JNI signature"),
+        }
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.contains("env . is_same_object (capacity , :: robusta_jni :: jni :: objects :: JObject :: null ()) ? { 100 }"));
+    }
+
+    #[test]
+    fn intern_cache_params_are_passed_as_strings() {
+        let output = setup_with_method(parse_quote! {
+            pub extern "jni" fn foo(#[intern_cache(size = 16)] tag: Arc<str>) {}
+        });
+
+        let string_type: Type = parse_quote! { ::robusta_jni::jni::objects::JString<'env> };
+        match output.sig.inputs.last() {
+            Some(FnArg::Typed(PatType { ty, attrs, .. })) => {
+                assert_eq!(
+                    ty.to_token_stream().to_string(),
+                    string_type.to_token_stream().to_string()
+                );
+                assert!(attrs.is_empty());
+            }
+            _ => panic!("unexpected JNI signature"),
+        }
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.contains("static CACHE : :: robusta_jni :: convert :: InternCache = :: robusta_jni :: convert :: InternCache :: new (16usize) ;"));
+        assert!(block.contains("CACHE . get (& env , tag) ?"));
+    }
+
+    #[test]
+    fn numeric_params_and_return_values_are_wrapped() {
+        let output = setup_with_method(parse_quote! {
+            #[saturate]
+            pub extern "jni" fn foo(#[reinterpret] flags: u32, count: u32) -> Result<u64, String> {}
+        });
+
+        let flags_type: Type = parse_quote! { <::robusta_jni::convert::numeric::Reinterpret<u32> as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source };
+        // Without an attribute, unsigned integers are widened to `long`
+        let count_type: Type = parse_quote! { <::robusta_jni::convert::numeric::Widen<u32> as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source };
+        match output.sig.inputs.iter().collect::<Vec<_>>().as_slice() {
+            [_env, _class, FnArg::Typed(PatType { ty: ty_1, attrs, .. }), FnArg::Typed(PatType { ty: ty_2, .. })] => {
+                assert_eq!(ty_1.to_token_stream().to_string(), flags_type.to_token_stream().to_string());
+                assert_eq!(ty_2.to_token_stream().to_string(), count_type.to_token_stream().to_string());
+                assert!(attrs.is_empty());
+            }
+            _ => panic!("unexpected JNI signature"),
+        }
+
+        let return_type: ReturnType = parse_quote! { -> <::robusta_jni::convert::numeric::Saturate<u64> as ::robusta_jni::convert::TryIntoJavaValue<'env>>::Target };
+        assert_eq!(output.sig.output.to_token_stream().to_string(), return_type.to_token_stream().to_string());
+        assert!(!output.attrs.iter().any(|a| a.path.is_ident("saturate")));
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.contains(
+            "< :: robusta_jni :: convert :: numeric :: Reinterpret < u32 > as :: robusta_jni :: convert :: TryFromJavaValue < 'env > > :: try_from (flags , env) ? . 0"
+        ));
+        assert!(block.contains(":: core :: result :: Result :: map (Foo :: foo ("));
+        assert!(block.contains(", :: robusta_jni :: convert :: numeric :: Saturate)"));
+    }
+
+    #[test]
+    fn converted_params_and_return_values_call_their_functions() {
+        let output = setup_with_method(parse_quote! {
+            #[convert(into_with = "b64::to_java", target = "JString<'_>")]
+            pub extern "jni" fn foo(#[convert(with = "b64::from_java", source = "JString<'env>")] data: Vec<u8>, #[convert(with = "raw")] raw: Raw) -> Result<Vec<u8>, String> {}
+        });
+
+        match output.sig.inputs.iter().collect::<Vec<_>>().as_slice() {
+            [_env, _class, FnArg::Typed(PatType { ty: ty_1, attrs, .. }), FnArg::Typed(PatType { ty: ty_2, .. })] => {
+                assert_eq!(ty_1.to_token_stream().to_string(), "JString < 'env >");
+                assert_eq!(ty_2.to_token_stream().to_string(), ":: robusta_jni :: jni :: objects :: JObject < 'env >");
+                assert!(attrs.is_empty());
+            }
+            _ => panic!("unexpected JNI signature"),
+        }
+        // `'_` is the lifetime of the call, like in the types of parameters
+        assert_eq!(output.sig.output.to_token_stream().to_string(), "-> JString < 'env >");
+        assert!(!output.attrs.iter().any(|a| a.path.is_ident("convert")));
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.contains("b64 :: from_java (:: core :: convert :: Into :: into (data) , env) ?"));
+        assert!(block.contains("raw (:: core :: convert :: Into :: into (raw) , env) ?"));
+        assert!(block.contains("Ok (v) => Ok (:: core :: convert :: Into :: into (b64 :: to_java (v , env) ?))"));
+    }
+
+    #[test]
+    fn unchecked_numeric_return_values_are_wrapped() {
+        let output = output_type(
+            parse_quote! {
+                #[truncate]
+                pub extern "jni" fn foo() -> i64 {}
+            },
+            CallType::Unchecked(Default::default()),
+        );
+        let target: ReturnType = parse_quote! { -> <::robusta_jni::convert::numeric::Truncate<i64> as ::robusta_jni::convert::IntoJavaValue<'env>>::Target };
+        assert_eq!(output, target.to_token_stream().to_string());
+    }
+
+    #[test]
+    fn unsigned_return_values_are_widened() {
+        let output = setup_with_method(parse_quote! {
+            pub extern "jni" fn foo() -> Result<usize, String> {}
+        });
+
+        let return_type: ReturnType = parse_quote! { -> <::robusta_jni::convert::numeric::Widen<usize> as ::robusta_jni::convert::TryIntoJavaValue<'env>>::Target };
+        assert_eq!(output.sig.output.to_token_stream().to_string(), return_type.to_token_stream().to_string());
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.contains(", :: robusta_jni :: convert :: numeric :: Widen)"));
+    }
+
+    #[test]
+    fn borrowed_params_are_passed_from_guards() {
+        let output = setup_with_method(parse_quote! {
+            pub extern "jni" fn foo(name: &str, data: &[u8]) {}
+        });
+
+        let name_type: Type = parse_quote! { <str as ::robusta_jni::convert::FromJavaValueRef<'env>>::Source };
+        let data_type: Type = parse_quote! { <[u8] as ::robusta_jni::convert::FromJavaValueRef<'env>>::Source };
+        match output.sig.inputs.iter().collect::<Vec<_>>().as_slice() {
+            [_env, _class, FnArg::Typed(PatType { ty: ty_1, .. }), FnArg::Typed(PatType { ty: ty_2, .. })] => {
+                assert_eq!(ty_1.to_token_stream().to_string(), name_type.to_token_stream().to_string());
+                assert_eq!(ty_2.to_token_stream().to_string(), data_type.to_token_stream().to_string());
+            }
+            _ => panic!("unexpected JNI signature"),
+        }
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.contains("& * < str as :: robusta_jni :: convert :: FromJavaValueRef < 'env > > :: borrow_from (name , env) ?"));
+        assert!(block.contains("& * < [u8] as :: robusta_jni :: convert :: FromJavaValueRef < 'env > > :: borrow_from (data , env) ?"));
+    }
+
+    fn setup_with_params(params: TokenStream, struct_name: String) -> ImplItemMethod {
+        let package = None;
+        let method_name = "foo".to_string();
+        let struct_name_token_stream = TokenStream::from_str(&struct_name).unwrap();
+        let method_name_token_stream = TokenStream::from_str(&method_name).unwrap();
+
+        let method: ImplItemMethod = parse_quote! {
+            pub extern "jni" fn #method_name_token_stream(#params) -> i32 {}
+        };
+
+        let struct_context = StructContext {
+            struct_type: parse_quote! { #struct_name_token_stream },
+            struct_name,
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package,
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: false,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        transformer.fold_impl_item_method(method)
+    }
+
+    #[test]
+    fn static_method_params() {
+        use quote::quote;
+
+        let param_type_1: TokenStream = parse_quote! { i32 };
+        let param_type_2: TokenStream = parse_quote! { FooBar };
+        let output = setup_with_params(
+            quote! { _1: #param_type_1, _2: #param_type_2 },
+            "Foo".to_string(),
+        );
+
+        let env_type: Type = parse_quote! { ::robusta_jni::jni::JNIEnv<'env> };
+        let class_type: Type = parse_quote! { ::robusta_jni::jni::objects::JClass<'env> };
+        let conv_type_1: Type = parse_quote! { <#param_type_1 as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source };
+        let conv_type_2: Type = parse_quote! { <#param_type_2 as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source };
+
+        let args: &[FnArg] = &output.sig.inputs.into_iter().collect::<Vec<_>>();
+        match args {
+            [FnArg::Typed(PatType { ty: ty_env, .. }), FnArg::Typed(PatType { ty: ty_class, .. }), FnArg::Typed(PatType { ty: ty_1, .. }), FnArg::Typed(PatType { ty: ty_2, .. })] =>
+            {
+                assert_eq!(
+                    ty_env.to_token_stream().to_string(),
+                    env_type.to_token_stream().to_string()
+                );
+                assert_eq!(
+                    ty_class.to_token_stream().to_string(),
+                    class_type.to_token_stream().to_string()
+                );
+                assert_eq!(
+                    ty_1.to_token_stream().to_string(),
+                    conv_type_1.to_token_stream().to_string()
+                );
+                assert_eq!(
+                    ty_2.to_token_stream().to_string(),
+                    conv_type_2.to_token_stream().to_string()
+                );
+            }
+
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn self_method_params() {
+        use quote::quote;
+
+        let struct_name = "Foo".to_string();
+        let struct_name_toks = TokenStream::from_str(&struct_name).unwrap();
+
+        let param_type_1: TokenStream = parse_quote! { i32 };
+        let param_type_2: TokenStream = parse_quote! { FooBar };
+        let output = setup_with_params(
+            quote! { self, _1: #param_type_1, _2: #param_type_2 },
+            struct_name.clone(),
+        );
+
+        let env_type: Type = parse_quote! { ::robusta_jni::jni::JNIEnv<'env> };
+        let self_conv_type: Type = parse_quote! { <#struct_name_toks as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source };
+        let conv_type_1: Type = parse_quote! { <#param_type_1 as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source };
+        let conv_type_2: Type = parse_quote! { <#param_type_2 as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source };
+
+        let args: &[FnArg] = &output.sig.inputs.into_iter().collect::<Vec<_>>();
+        match args {
+            [FnArg::Typed(PatType { ty: ty_env, .. }), FnArg::Typed(PatType { ty: ty_self, .. }), FnArg::Typed(PatType { ty: ty_1, .. }), FnArg::Typed(PatType { ty: ty_2, .. })] =>
+            {
+                assert_eq!(
+                    ty_env.to_token_stream().to_string(),
+                    env_type.to_token_stream().to_string()
+                );
+                assert_eq!(
+                    ty_self.to_token_stream().to_string(),
+                    self_conv_type.to_token_stream().to_string()
+                );
+                assert_eq!(
+                    ty_1.to_token_stream().to_string(),
+                    conv_type_1.to_token_stream().to_string()
+                );
+                assert_eq!(
+                    ty_2.to_token_stream().to_string(),
+                    conv_type_2.to_token_stream().to_string()
+                );
+            }
+
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn env_params_are_not_java_params() {
+        use quote::quote;
+
+        for params in [quote! { env: JNIEnv<'env>, _1: i32 }, quote! { self, env: &JNIEnv<'env>, _1: i32 }] {
+            let output = setup_with_params(params, "Foo".to_string());
+            let names: Vec<String> = output
+                .sig
+                .inputs
+                .iter()
+                .map(|a| match a {
+                    FnArg::Typed(PatType { pat, .. }) => pat.to_token_stream().to_string(),
+                    FnArg::Receiver(_) => "self".to_string(),
+                })
+                .collect();
+
+            assert_eq!(names.len(), 3, "{:?}", names);
+            assert_eq!(names.iter().filter(|n| *n == "env").count(), 1, "{:?}", names);
+        }
+    }
+
+    #[test]
+    fn leading_class_params_are_not_java_params() {
+        use quote::quote;
+
+        let cases = [
+            (quote! { class: JClass<'env>, _1: i32 }, vec!["env", "class", "_1"]),
+            (quote! { env: JNIEnv<'env>, class: JClass<'env>, _1: i32 }, vec!["env", "class", "_1"]),
+            (quote! { _1: i32, other: JClass<'env> }, vec!["env", "class", "_1", "other"]),
+        ];
+        for (params, expected) in cases {
+            let output = setup_with_params(params, "Foo".to_string());
+            let names: Vec<String> = output
+                .sig
+                .inputs
+                .iter()
+                .map(|a| match a {
+                    FnArg::Typed(PatType { pat, .. }) => pat.to_token_stream().to_string(),
+                    FnArg::Receiver(_) => "self".to_string(),
+                })
+                .collect();
+
+            assert_eq!(names, expected);
+        }
+    }
+
+    fn output_type(method: ImplItemMethod, call_type: CallType) -> String {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: None,
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type,
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: false,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        transformer.fold_impl_item_method(method).sig.output.to_token_stream().to_string()
+    }
+
+    #[test]
+    fn primitive_methods_are_called_directly() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: None,
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let wrapper = |method: ImplItemMethod, call_type: CallType| {
+            let mut transformer = ExternJNIMethodTransformer {
+                struct_context: &struct_context,
+                call_type,
+                overload_descriptor: None,
+                body_wrapper: None,
+                register_natives: false,
+                middleware: None,
+                hidden: false,
+                attribute_filter: AttributeFilter::default(),
+                native: None,
+            };
+            transformer.fold_impl_item_method(method)
+        };
+
+        let add: ImplItemMethod = parse_quote! { pub extern "jni" fn add(a: i64, b: i64) -> i64 {} };
+        let output = wrapper(add.clone(), CallType::Safe(None));
+        let signature: Signature = parse_quote! {
+            fn f(env: ::robusta_jni::jni::JNIEnv<'env>, class: ::robusta_jni::jni::objects::JClass<'env>, a: i64, b: i64) -> i64
+        };
+        assert_eq!(output.sig.inputs.to_token_stream().to_string(), signature.inputs.to_token_stream().to_string());
+        assert_eq!(output.sig.output.to_token_stream().to_string(), signature.output.to_token_stream().to_string());
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.contains("Foo :: add (a , b)"), "{}", block);
+        assert!(!block.contains("JavaValue"), "{}", block);
+        assert!(block.contains("catch_panic"), "{}", block);
+
+        let block = wrapper(add, CallType::Unchecked(Default::default())).block.to_token_stream().to_string();
+        assert!(!block.contains("JavaValue") && !block.contains("catch_panic"), "{}", block);
+
+        // Any other parameter or return type takes the usual path
+        let block = wrapper(parse_quote! { pub extern "jni" fn add(a: i64, b: bool) -> i64 {} }, CallType::Safe(None))
+            .block
+            .to_token_stream()
+            .to_string();
+        assert!(block.contains("TryFromJavaValue"), "{}", block);
+    }
+
+    #[test]
+    fn panics_are_caught_in_safe_methods_and_on_request() {
+        let catches_panics = |call_type: TokenStream| {
+            let attr: CallTypeAttribute = parse_quote! { #[call_type(#call_type)] };
+            let struct_context = StructContext {
+                struct_type: parse_quote! { Foo },
+                struct_name: "Foo".into(),
+                struct_lifetimes: vec![],
+                struct_type_params: vec![],
+                package: None,
+                flattened_structs: FlattenedStructs::new(),
+                rename_all: None,
+                instantiation: None,
+            };
+            let mut transformer = ExternJNIMethodTransformer {
+                struct_context: &struct_context,
+                call_type: attr.call_type,
+                overload_descriptor: None,
+                body_wrapper: None,
+                register_natives: false,
+                middleware: None,
+                hidden: false,
+                attribute_filter: AttributeFilter::default(),
+                native: None,
+            };
+
+            let output = transformer.fold_impl_item_method(parse_quote! { pub extern "jni" fn foo() -> i32 { 0 } });
+            output.block.to_token_stream().to_string().contains(":: robusta_jni :: panic :: catch_panic (env ,")
+        };
+
+        assert!(catches_panics(quote! { safe }));
+        assert!(catches_panics(quote! { safe(message = "failed") }));
+        assert!(!catches_panics(quote! { unchecked }));
+        assert!(catches_panics(quote! { unchecked(catch_panics) }));
+    }
+
+    #[test]
+    fn safe_result_return_types_return_ok_type() {
+        let string_output = output_type(
+            parse_quote! { pub extern "jni" fn foo() -> Result<String, String> {} },
+            CallType::Safe(None),
+        );
+        let string_target: ReturnType = parse_quote! { -> <String as ::robusta_jni::convert::TryIntoJavaValue<'env>>::Target };
+        assert_eq!(string_output, string_target.to_token_stream().to_string());
+
+        let unit_output = output_type(
+            parse_quote! { pub extern "jni" fn foo() -> Result<(), MyError> {} },
+            CallType::Safe(None),
+        );
+        assert_eq!(unit_output, "");
+    }
+
+    #[test]
+    fn unchecked_result_return_types_are_converted_whole() {
+        let output = output_type(
+            parse_quote! { pub extern "jni" fn foo() -> Result<(), MyError> {} },
+            CallType::Unchecked(Default::default()),
+        );
+        let target: ReturnType = parse_quote! { -> <Result<(), MyError> as ::robusta_jni::convert::IntoJavaValue<'env>>::Target };
+        assert_eq!(output, target.to_token_stream().to_string());
+    }
+
+    #[test]
+    fn elided_lifetimes_of_converted_types_are_env() {
+        let method: ImplItemMethod = parse_quote! { pub extern "jni" fn foo(name: JString<'_>, items: &Items) -> JString<'_> {} };
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: None,
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let signature = JNISignature::new(method.sig, &struct_context, CallType::Safe(None), None, None).transformed_signature;
+
+        let inputs: Vec<String> = signature.inputs.iter().map(|i| i.to_token_stream().to_string()).collect();
+        let name: FnArg = parse_quote! { name: <JString<'env> as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source };
+        let items: FnArg = parse_quote! { items: <&'env Items as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source };
+        assert_eq!(inputs, [name.to_token_stream().to_string(), items.to_token_stream().to_string()]);
+
+        let output: ReturnType = parse_quote! { -> <JString<'env> as ::robusta_jni::convert::TryIntoJavaValue<'env>>::Target };
+        assert_eq!(signature.output.to_token_stream().to_string(), output.to_token_stream().to_string());
+    }
+
+    #[test]
+    fn return_values_can_borrow_from_receivers() {
+        let method: ImplItemMethod = parse_quote! { pub extern "jni" fn text(&self) -> Cow<str> {} };
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Label<'env> },
+            struct_name: "Label".into(),
+            struct_lifetimes: vec![parse_quote! { 'env }],
+            struct_type_params: vec![],
+            package: None,
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        // The receiver is a temporary of the call, which the returned value borrows from until it's converted
+        let signature = JNISignature::new(method.sig, &struct_context, CallType::Safe(None), None, None).transformed_signature;
+
+        // Borrowed strings are converted as `String`s
+        let output: ReturnType = parse_quote! { -> <::robusta_jni::__private::String as ::robusta_jni::convert::TryIntoJavaValue<'env>>::Target };
+        assert_eq!(signature.output.to_token_stream().to_string(), output.to_token_stream().to_string());
+        let generics: Generics = parse_quote! { <'env: 'borrow, 'borrow> };
+        assert_eq!(signature.generics.to_token_stream().to_string(), generics.to_token_stream().to_string());
+    }
+
+    #[test]
+    fn named_lifetimes_of_converted_types_are_declared_as_env() {
+        let method: ImplItemMethod = parse_quote! { pub extern "jni" fn foo<'a>(name: JString<'a>, tag: Tag<'b>, data: &'c [u8]) {} };
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: None,
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let signature = JNISignature::new(method.sig, &struct_context, CallType::Safe(None), None, None).transformed_signature;
+
+        // `'b` comes from the `impl` block, and `'c` only borrows a parameter during the call
+        let generics: Generics = parse_quote! { <'a: 'env, 'env: 'borrow + 'a + 'b, 'borrow, 'b: 'env> };
+        assert_eq!(signature.generics.to_token_stream().to_string(), generics.to_token_stream().to_string());
+    }
+
+    #[test]
+    fn async_methods_return_completable_futures() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: None,
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: true,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! { pub async extern "jni" fn fetch(key: String) -> Result<i64, String> {} });
+        assert!(output.sig.asyncness.is_none());
+        let target: ReturnType = parse_quote! { -> ::robusta_jni::jni::objects::JObject<'env> };
+        assert_eq!(output.sig.output.to_token_stream().to_string(), target.to_token_stream().to_string());
+        assert!(output.block.to_token_stream().to_string().contains(r#":: robusta_jni :: __private :: spawn_completable (env , "java/lang/RuntimeException""#));
+        let native = transformer.native.expect("registered method has no `NativeMethod`").to_string();
+        assert!(native.contains(r#"ret : "Ljava/util/concurrent/CompletableFuture;""#));
+    }
+
+    #[test]
+    fn symbols_follow_export_visibility() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: None,
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let symbol_attributes = |method: ImplItemMethod, register_natives: bool, hidden: bool| {
+            let mut transformer = ExternJNIMethodTransformer {
+                struct_context: &struct_context,
+                call_type: CallType::Safe(None),
+                overload_descriptor: None,
+                body_wrapper: None,
+                register_natives,
+                middleware: None,
+                hidden,
+                attribute_filter: AttributeFilter::default(),
+                native: None,
+            };
+            let output = transformer.fold_impl_item_method(method);
+            assert!(!output.attrs.iter().any(|a| a.path.is_ident("export_visibility")));
+            let attributes: Vec<_> = output
+                .attrs
+                .iter()
+                .filter(|a| a.path.is_ident("no_mangle") || a.path.is_ident("export_name"))
+                .map(|a| a.to_token_stream().to_string())
+                .collect();
+            (matches!(output.vis, Visibility::Public(_)), attributes)
+        };
+
+        let no_mangle = vec!["# [no_mangle]".to_string()];
+        let export_name = vec![r#"# [export_name = "foo_bar"]"#.to_string()];
+        let cases: [(ImplItemMethod, _, _, _); 6] = [
+            (parse_quote! { pub extern "jni" fn bar() {} }, false, false, (true, no_mangle)),
+            (parse_quote! { pub extern "jni" fn bar() {} }, true, false, (false, vec![])),
+            (parse_quote! { #[export_visibility(hidden)] pub extern "jni" fn bar() {} }, true, false, (false, vec![])),
+            (parse_quote! { pub extern "jni" fn bar() {} }, true, true, (false, vec![])),
+            (parse_quote! { #[export_name = "foo_bar"] pub extern "jni" fn bar() {} }, false, false, (true, export_name.clone())),
+            // A method's own symbol wins over the visibility of its `impl` block
+            (parse_quote! { #[export_name = "foo_bar"] pub extern "jni" fn bar() {} }, true, true, (true, export_name)),
+        ];
+        for (method, register_natives, hidden, expected) in cases {
+            let label = method.to_token_stream().to_string();
+            assert_eq!(symbol_attributes(method, register_natives, hidden), expected, "{}", label);
+        }
+    }
+
+    #[test]
+    fn synchronized_methods_lock_their_object() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: None,
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: false,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        let cases = [
+            (parse_quote! { #[synchronized] pub extern "jni" fn increment(mut self) {} }, "env . lock_obj (:: robusta_jni :: convert :: JavaValue :: autobox (receiver , env))"),
+            (parse_quote! { #[synchronized] pub extern "jni" fn reset() {} }, "env . lock_obj (class)"),
+        ];
+        for (method, lock) in cases {
+            let output = transformer.fold_impl_item_method(method);
+            assert!(!output.attrs.iter().any(|a| a.path.is_ident("synchronized")));
+            assert!(output.block.to_token_stream().to_string().contains(lock), "{}", output.block.to_token_stream());
+        }
+    }
+
+    #[test]
+    fn static_method_taking_struct_by_value_params() {
+        use quote::quote;
+
+        let output = setup_with_params(quote! { a: Self, b: Foo }, "Foo".to_string());
+
+        let class_type: Type = parse_quote! { ::robusta_jni::jni::objects::JClass<'env> };
+        let struct_conv_type: Type = parse_quote! { <Foo as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source };
+
+        let types: Vec<String> = output
+            .sig
+            .inputs
+            .iter()
+            .map(|a| match a {
+                FnArg::Typed(PatType { ty, .. }) => ty.to_token_stream().to_string(),
+                FnArg::Receiver(_) => panic!("unexpected receiver"),
+            })
+            .collect();
+
+        assert_eq!(types.len(), 4);
+        assert_eq!(types[1], class_type.to_token_stream().to_string());
+        assert_eq!(types[2], struct_conv_type.to_token_stream().to_string());
+        assert_eq!(types[3], struct_conv_type.to_token_stream().to_string());
+    }
+
+    #[test]
+    fn flattened_params_are_passed_field_by_field() {
+        let mut flattened_structs = FlattenedStructs::new();
+        flattened_structs.insert(
+            "Config".to_string(),
+            vec![(parse_quote! { host }, parse_quote! { String }), (parse_quote! { port }, parse_quote! { i32 })],
+        );
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: None,
+            flattened_structs,
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ExternJNIMethodTransformer {
+            struct_context: &struct_context,
+            call_type: CallType::Safe(None),
+            overload_descriptor: None,
+            body_wrapper: None,
+            register_natives: false,
+            middleware: None,
+            hidden: false,
+            attribute_filter: AttributeFilter::default(),
+            native: None,
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! {
+            pub extern "jni" fn connect(#[flatten] config: Config, retries: i32) -> i32 {}
+        });
+
+        let params: Vec<String> = output.sig.inputs.iter().skip(2).map(|a| a.to_token_stream().to_string()).collect();
+        let expected: Vec<FnArg> = vec![
+            parse_quote! { config_host: <String as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source },
+            parse_quote! { config_port: <i32 as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source },
+            parse_quote! { retries: <i32 as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source },
+        ];
+        assert_eq!(params, expected.iter().map(|a| a.to_token_stream().to_string()).collect::<Vec<_>>());
+
+        let struct_literal: Expr = parse_quote! {
+            Config {
+                host: ::robusta_jni::convert::TryFromJavaValue::try_from(config_host, env)?,
+                port: ::robusta_jni::convert::TryFromJavaValue::try_from(config_port, env)?
+            }
+        };
+        assert!(output.block.to_token_stream().to_string().contains(&struct_literal.to_token_stream().to_string()));
+    }
+}
+
+/// How a parameter is converted from its JNI representation before calling the original method.
+enum ParamConversion {
+    /// Plain `(Try)FromJavaValue` conversion.
+    Standard,
+    /// `&self` or `&mut self` receiver, converted as the struct type `ty` and passed by reference.
+    ReceiverRef { ty: Type, mutable: bool },
+    /// Java primitive parameter (e.g. `i64`), whose conversion is the identity: the JNI value is passed as is.
+    Passthrough,
+    /// Parameter with a `#[default = ...]` attribute: Java passes a (boxed) object, and `value` is used in place of `null`.
+    Default { ty: Type, value: Box<Expr> },
+    /// `Arc<str>` parameter with an `#[intern_cache(size = ...)]` attribute, converted through a per-parameter cache.
+    InternCache { size: usize },
+    /// Integer parameter with a `#[truncate]`, `#[saturate]` or `#[reinterpret]` attribute, converted through `wrapper`.
+    Numeric { wrapper: Type },
+    /// `&str` or `&[u8]` parameter, borrowed from a guard kept alive until the method returns. `ty` is the referenced type.
+    Borrowed { ty: Type },
+    /// Parameter with a `#[convert(with = ...)]` attribute, passed by Java as `jni_type` and converted by `function`.
+    Converted { function: Path, jni_type: Type },
+    /// Parameter with a `#[flatten]` attribute, passed by Java as a parameter for each of the `fields` of its struct.
+    Flatten { struct_ident: Ident, fields: Vec<Ident> },
+    /// `JNIEnv` (or `&JNIEnv` when `by_ref`) parameter, given the environment of the call and not passed by Java.
+    Env { by_ref: bool },
+    /// Leading `JClass` parameter of a static method, given the class the method is called on and not passed by Java.
+    Class,
+}
+
+/// Types of `jni` and `robusta_jni` whose first parameter is the lifetime of a Java value. Written without it (e.g. `JString`), their
+/// lifetime is hidden rather than elided, and can't be mapped to `'env`: other types hiding a lifetime can't be recognized, and
+/// are only reported by rustc.
+const ENV_LIFETIME_TYPES: [&str; 11] = [
+    "JObject",
+    "JString",
+    "JClass",
+    "JThrowable",
+    "JByteBuffer",
+    "CriticalBytes",
+    "DirectByteBuffer",
+    "HandleRef",
+    "JAtomicLong",
+    "JavaCallback",
+    "ByJavaEquality",
+];
+
+/// Gives the elided and `'_` lifetimes of a converted type the lifetime of the Java values of the call, `'env`, and collects its
+/// other named lifetimes.
+#[derive(Default)]
+struct EnvLifetimes {
+    named: Vec<Lifetime>,
+}
+
+impl Fold for EnvLifetimes {
+    fn fold_lifetime(&mut self, lifetime: Lifetime) -> Lifetime {
+        if lifetime.ident == "_" {
+            return Lifetime::new("'env", lifetime.span());
+        }
+
+        let is_reserved = lifetime.ident == "env" || lifetime.ident == "borrow" || lifetime.ident == "static";
+        if !is_reserved && !self.named.contains(&lifetime) {
+            self.named.push(lifetime.clone());
+        }
+        lifetime
+    }
+
+    fn fold_type_reference(&mut self, mut reference: TypeReference) -> TypeReference {
+        let lifetime = reference.lifetime.take().unwrap_or_else(|| Lifetime::new("'env", reference.and_token.span));
+        TypeReference {
+            lifetime: Some(self.fold_lifetime(lifetime)),
+            elem: Box::new(self.fold_type(*reference.elem)),
+            ..reference
+        }
+    }
+
+    // `Cow<str>` elides the lifetime of what it borrows, like a reference
+    fn fold_path_segment(&mut self, mut segment: PathSegment) -> PathSegment {
+        if segment.ident == "Cow" {
+            if let PathArguments::AngleBracketed(a) = &mut segment.arguments {
+                if !a.args.iter().any(|a| matches!(a, GenericArgument::Lifetime(_))) {
+                    a.args.insert(0, GenericArgument::Lifetime(Lifetime::new("'env", segment.ident.span())));
+                }
+            }
+        }
+
+        let has_lifetime = match &segment.arguments {
+            PathArguments::AngleBracketed(a) => a.args.iter().any(|a| matches!(a, GenericArgument::Lifetime(_))),
+            _ => false,
+        };
+        if !has_lifetime && ENV_LIFETIME_TYPES.iter().any(|t| segment.ident == t) {
+            let others = if matches!(segment.arguments, PathArguments::AngleBracketed(_)) { ", ..." } else { "" };
+            emit_error!(segment.ident, "the lifetime of `{}` must be written", segment.ident;
+                help = "write `{}<'env{}>`, or `{}<'_{}>`", segment.ident, others, segment.ident, others;
+                note = "it's the lifetime of the Java values of the call");
+        }
+        syn::fold::fold_path_segment(self, segment)
+    }
+
+    // Function pointers elide and bind lifetimes of their own
+    fn fold_type_bare_fn(&mut self, bare_fn: TypeBareFn) -> TypeBareFn {
+        bare_fn
+    }
+}
+
+struct JNISignatureTransformer {
+    struct_freestanding_transformer: FreestandingTransformer,
+    struct_lifetimes: Vec<LifetimeDef>,
+    flattened_structs: FlattenedStructs,
+    call_type: CallType,
+    param_conversions: Vec<ParamConversion>,
+    /// Whether the method has a receiver, which comes before the environment parameter.
+    has_receiver: bool,
+    /// Conversion selected by an attribute on the method, for its return value.
+    return_conversion: Option<NumericConversion>,
+    /// Wrapper constructor applied to the return value (to its `Ok` value when `true`), if the return conversion is valid.
+    return_wrapper: Option<(Path, bool)>,
+    /// Function selected by `#[convert(into_with = ...)]` on the method, for its return value.
+    return_converter: Option<Converter>,
+    /// Whether the method only takes and returns Java primitives, which are passed as is.
+    passthrough: bool,
+    /// Whether the method is `async`, returning a `CompletableFuture` to Java.
+    asyncness: bool,
+    /// `SIG_TYPE` expression of each parameter passed by Java, for the method's descriptor.
+    param_sig_types: Vec<TokenStream>,
+    /// `SIG_TYPE` expression of the return type, if any.
+    return_sig_type: Option<TokenStream>,
+    /// Named lifetimes of converted parameter and return types, which are the lifetime of Java values, `'env`.
+    converted_lifetimes: Vec<Lifetime>,
+    /// Named lifetimes of `&str` and `&[u8]` parameters, which only borrow Java values during the call.
+    borrowed_lifetimes: Vec<Lifetime>,
+}
+
+impl JNISignatureTransformer {
+    fn new(
+        struct_freestanding_transformer: FreestandingTransformer,
+        struct_lifetimes: Vec<LifetimeDef>,
+        flattened_structs: FlattenedStructs,
+        call_type: CallType,
+        return_conversion: Option<NumericConversion>,
+        return_converter: Option<Converter>,
+    ) -> Self {
+        JNISignatureTransformer {
+            struct_freestanding_transformer,
+            struct_lifetimes,
+            flattened_structs,
+            call_type,
+            param_conversions: Vec::new(),
+            has_receiver: false,
+            return_conversion,
+            return_wrapper: None,
+            return_converter,
+            passthrough: false,
+            asyncness: false,
+            param_sig_types: Vec::new(),
+            return_sig_type: None,
+            converted_lifetimes: Vec::new(),
+            borrowed_lifetimes: Vec::new(),
+        }
+    }
+
+    /// Maps the elided lifetimes of the converted type `ty` to `'env`, keeping track of its named lifetimes.
+    fn map_lifetimes(&mut self, ty: Type) -> (Type, Vec<Lifetime>) {
+        let mut env_lifetimes = EnvLifetimes::default();
+        let ty = env_lifetimes.fold_type(ty);
+        for lifetime in &env_lifetimes.named {
+            if !self.converted_lifetimes.contains(lifetime) {
+                self.converted_lifetimes.push(lifetime.clone());
+            }
+        }
+
+        (ty, env_lifetimes.named)
+    }
+
+    /// Replaces the integer type in `rtype` (the `T` of `Result<T, ...>` types) with its wrapper, the one of `conversion` or,
+    /// without any, the one widening unsigned integers.
+    fn wrap_return_type(&mut self, conversion: Option<NumericConversion>, mut rtype: Type) -> Type {
+        let is_result = get_result_value_type(&rtype).is_some();
+        let value_type = get_result_value_type(&rtype).unwrap_or(&rtype);
+        let (wrapper, wrapper_path) = match conversion {
+            Some(conversion) => (conversion.wrapper_type(value_type), conversion.wrapper()),
+            None => (widened_type(value_type), widen_wrapper()),
+        };
+        let wrapper = match wrapper {
+            Some(wrapper) => wrapper,
+            None => return rtype,
+        };
+
+        if is_result {
+            if let Type::Path(p) = &mut rtype {
+                if let Some(PathArguments::AngleBracketed(a)) = p.path.segments.last_mut().map(|s| &mut s.arguments) {
+                    if let Some(value_type) = a.args.iter_mut().find_map(|a| match a {
+                        GenericArgument::Type(t) => Some(t),
+                        _ => None,
+                    }) {
+                        *value_type = wrapper;
+                    }
+                }
+            }
+        } else {
+            rtype = wrapper;
+        }
+
+        self.return_wrapper = Some((wrapper_path, is_result));
+        rtype
+    }
+
+    /// Expands a `#[flatten]` parameter into a parameter for each field of its struct, in declaration order, named after both.
+    fn fold_flattened_arg(&mut self, param: PatType) -> Vec<FnArg> {
+        let param_ident = match &*param.pat {
+            Pat::Ident(PatIdent { ident, .. }) => ident,
+            p => {
+                emit_error!(p, "`#[flatten]` parameters must be bound to a name");
+                return Vec::new();
+            }
+        };
+
+        // Parameters whose struct can't be flattened have been reported when validating the module
+        let (struct_ident, fields) = match &*param.ty {
+            Type::Path(p) => match p.path.segments.last().and_then(|s| Some((&s.ident, self.flattened_structs.get(&s.ident.to_string())?))) {
+                Some((struct_ident, fields)) => (struct_ident.clone(), fields.clone()),
+                None => return Vec::new(),
+            },
+            _ => return Vec::new(),
+        };
+
+        if param.attrs.iter().any(|a| a.path.is_ident("default") || a.path.is_ident("intern_cache") || a.path.is_ident("convert") || NumericConversion::is_attribute(a)) {
+            emit_error!(param, "`#[flatten]` can't be used with `#[default]`, `#[intern_cache]`, `#[convert]`, `#[truncate]`, `#[saturate]` or `#[reinterpret]`");
+        }
+
+        if self.param_conversions.iter().any(|c| matches!(c, ParamConversion::Default { .. })) {
+            emit_error!(param, "parameters following a `#[default]` parameter must have a default value too");
+        }
+
+        let args = fields
+            .iter()
+            .map(|(field, ty)| {
+                check_owned_type(ty);
+
+                let ident = format_ident!("{}_{}", param_ident, field, span = param_ident.span());
+                self.param_sig_types.push(quote_spanned! { ty.span() => ::robusta_jni::signature::parameter_signature(<#ty as ::robusta_jni::convert::Signature>::SIG_TYPE) });
+                let jni_conversion_type: Type = match &self.call_type {
+                    CallType::Safe(_) => parse_quote_spanned! { ty.span() => <#ty as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source },
+                    CallType::Unchecked { .. } => parse_quote_spanned! { ty.span() => <#ty as ::robusta_jni::convert::FromJavaValue<'env>>::Source },
+                };
+
+                parse_quote_spanned! { param_ident.span() => #ident: #jni_conversion_type }
+            })
+            .collect();
+
+        self.param_conversions.push(ParamConversion::Flatten {
+            struct_ident,
+            fields: fields.into_iter().map(|(field, _)| field).collect(),
+        });
+        args
+    }
+
+    fn transform_generics(&mut self, mut generics: Generics) -> Generics {
+        let generics_span = generics.span();
+        generics.params.extend(
+            self.struct_lifetimes
+                .iter()
+                .cloned()
+                .map(GenericParam::Lifetime),
+        );
+
+        let (env_lifetime, borrow_lifetime) = generics.params.iter_mut().fold((None, None), |acc, l| {
+            match l {
+                GenericParam::Lifetime(l) => {
+                    if l.lifetime.ident == "env" {
+                        if l.bounds.iter().any(|b| b.ident != "borrow") {
+                            emit_warning!(l, "using JNI-reserved `'env` lifetime with non `'borrow` bounds";
+                                note = "If you need to access to the lifetime of the `JNIEnv`, please use `'borrow` instead")
+                        }
+
+                        (Some(l), acc.1)
+                    } else if l.lifetime.ident == "borrow" {
+                        (acc.0, Some(l))
+                    } else {
+                        acc
+                    }
+                },
+                _ => acc
+            }
+        });
+
+        match (env_lifetime, borrow_lifetime) {
+            (Some(_), Some(_)) => {}
+            (Some(e), None) => {
+                let borrow_lifetime_value = Lifetime {
+                    apostrophe: generics_span,
+                    ident: Ident::new("borrow", generics_span),
+                };
+
+                e.bounds.push(borrow_lifetime_value.clone());
+
+                generics.params.push(GenericParam::Lifetime(LifetimeDef {
+                    attrs: vec![],
+                    lifetime: borrow_lifetime_value,
+                    colon_token: None,
+                    bounds: Default::default(),
+                }))
+            }
+            (None, Some(l)) => {
+                emit_error!(l, "Can't use JNI-reserved `'borrow` lifetime without accompanying `'env: 'borrow` lifetime";
+                    help = "Add `'env: 'borrow` lifetime here")
+            }
+            (None, None) => {
+                let borrow_lifetime_value = Lifetime {
+                    apostrophe: generics_span,
+                    ident: Ident::new("borrow", generics_span),
+                };
+
+                generics.params.push(GenericParam::Lifetime(LifetimeDef {
+                    attrs: vec![],
+                    lifetime: Lifetime {
+                        apostrophe: generics_span,
+                        ident: Ident::new("env", generics_span),
+                    },
+                    colon_token: None,
+                    bounds: {
+                        let mut p = Punctuated::new();
+                        p.push(borrow_lifetime_value.clone());
+                        p
+                    },
+                }));
+
+                generics.params.push(GenericParam::Lifetime(LifetimeDef {
+                    attrs: vec![],
+                    lifetime: borrow_lifetime_value,
+                    colon_token: None,
+                    bounds: Default::default(),
+                }))
+            }
+        }
+
+        // Other lifetimes of converted types, including those of the `impl` block, are declared on the function and equal to `'env`
+        for lifetime in &self.converted_lifetimes {
+            if !generics.lifetimes().any(|l| l.lifetime == *lifetime) {
+                generics.params.push(GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())));
+            }
+
+            for l in generics.lifetimes_mut() {
+                if l.lifetime == *lifetime && !l.bounds.iter().any(|b| b.ident == "env") {
+                    l.bounds.push(Lifetime::new("'env", lifetime.span()));
+                } else if l.lifetime.ident == "env" && !l.bounds.iter().any(|b| b == lifetime) {
+                    l.bounds.push(lifetime.clone());
+                }
+            }
+        }
+
+        generics
+    }
+}
+
+impl Fold for JNISignatureTransformer {
+    fn fold_fn_arg(&mut self, arg: FnArg) -> FnArg {
+        let is_receiver = match &arg {
+            FnArg::Receiver(_) => true,
+            FnArg::Typed(t) => matches!(&*t.pat, Pat::Ident(PatIdent { ident, .. }) if ident == "self"),
+        };
+        self.has_receiver |= is_receiver;
+        // Receivers are replaced by typed parameters spanned at the `impl` block
+        let arg_span = arg.span();
+
+        match self.struct_freestanding_transformer.fold_fn_arg(arg) {
+            FnArg::Receiver(_) => panic!("Bug -- please report to library author. Found receiver input after freestanding conversion"),
+            FnArg::Typed(t) if is_env_type(&t.ty) => {
+                if self.param_conversions.len() > usize::from(self.has_receiver) {
+                    emit_error!(t, "the `JNIEnv` parameter must be the first parameter{}", if self.has_receiver { " after `self`" } else { "" };
+                        note = "Java only passes the other parameters, the environment is the one of the call");
+                }
+                if let Type::Reference(TypeReference { mutability: Some(m), .. }) = &*t.ty {
+                    emit_error!(m, "explicit environment parameter can't be `&mut JNIEnv`";
+                        help = "take `JNIEnv` by value or by shared reference, it's `Copy`");
+                }
+
+                if self.asyncness {
+                    emit_error!(t, "`async` methods can't take the environment";
+                        note = "the future runs after the call has returned, when its environment can't be used anymore");
+                }
+
+                self.param_conversions.push(ParamConversion::Env { by_ref: matches!(&*t.ty, Type::Reference(_)) });
+                FnArg::Typed(t)
+            }
+            // Later `JClass` parameters are `Class` objects passed by Java
+            FnArg::Typed(t)
+                if is_class_type(&t.ty)
+                    && self.param_conversions.iter().skip(usize::from(self.has_receiver)).all(|c| matches!(c, ParamConversion::Env { .. })) =>
+            {
+                if self.has_receiver {
+                    emit_error!(t, "instance methods can't take the class as a parameter";
+                        note = "JNI calls instance methods with their object, which is converted to `self`";
+                        help = "remove `self` to make the method `static`, or call `env.get_object_class` on the object");
+                }
+                if self.asyncness {
+                    emit_error!(t, "`async` methods can't take the class as a parameter";
+                        note = "the future runs after the call has returned, when its local references can't be used anymore");
+                }
+
+                self.param_conversions.push(ParamConversion::Class);
+                FnArg::Typed(t)
+            }
+            FnArg::Typed(mut t) => {
+                let default_value = get_default_value(&t);
+                let intern_cache_size = get_intern_cache_size(&t);
+                let numeric_conversion = get_numeric_conversion(&t.attrs);
+                let converter = get_converter(&t.attrs, true);
+                let follows_default = self.param_conversions.iter().any(|c| matches!(c, ParamConversion::Default { .. }));
+
+                if default_value.is_none() && follows_default {
+                    emit_error!(t, "parameters following a `#[default]` parameter must have a default value too");
+                }
+
+                if default_value.is_some() && intern_cache_size.is_some() {
+                    emit_error!(t, "`#[default]` and `#[intern_cache]` can't be used on the same parameter");
+                }
+
+                if numeric_conversion.is_some() && (default_value.is_some() || intern_cache_size.is_some()) {
+                    emit_error!(t, "`#[truncate]`, `#[saturate]` and `#[reinterpret]` can't be used with `#[default]` or `#[intern_cache]`");
+                }
+
+                if converter.is_some() && (default_value.is_some() || intern_cache_size.is_some() || numeric_conversion.is_some()) {
+                    emit_error!(t, "`#[convert]` can't be used with `#[default]`, `#[intern_cache]`, `#[truncate]`, `#[saturate]` or `#[reinterpret]`";
+                        note = "the function of `#[convert]` does the whole conversion");
+                }
+
+                // The descriptor of a converted parameter is the one of the JNI type Java passes
+                let descriptor = check_sig_descriptor(&t.attrs, Some(converter.as_ref().map_or(&*t.ty, |c| &c.jni_type)))
+                    .or_else(|| check_java_list(&t.attrs, Some(converter.as_ref().map_or(&*t.ty, |c| &c.jni_type))));
+
+                t.attrs.retain(|a| !is_bridge_attribute(a));
+                let is_borrowed = borrowed_type(&t.ty).is_some() && default_value.is_none() && intern_cache_size.is_none() && numeric_conversion.is_none() && converter.is_none();
+                // References to borrowed values and to the receiver only live during the call, unlike Java values
+                let original_input_type = match *t.ty {
+                    Type::Reference(mut r) if is_borrowed || is_receiver => {
+                        if let Some(lifetime) = r.lifetime.as_ref().filter(|_| is_borrowed) {
+                            self.borrowed_lifetimes.push(lifetime.clone());
+                        }
+                        r.elem = Box::new(self.map_lifetimes(*r.elem).0);
+                        Box::new(Type::Reference(r))
+                    }
+                    ty => Box::new(self.map_lifetimes(ty).0),
+                };
+                let borrowed_input_type = borrowed_type(&original_input_type).filter(|_| is_borrowed);
+
+                match borrowed_input_type.and_then(|r| r.lifetime.as_ref()) {
+                    Some(l) if l.ident == "env" || l.ident == "borrow" => {
+                        emit_error!(l, "borrowed parameters can't outlive the call";
+                            help = "remove the `{}` lifetime", l;
+                            note = "`&str` and `&[u8]` parameters borrow Java values only until the method returns")
+                    }
+                    _ => {}
+                }
+
+                if borrowed_input_type.is_none() {
+                    check_owned_type(&original_input_type);
+                }
+
+                let conversion = match (converter, default_value, intern_cache_size, numeric_conversion, borrowed_input_type) {
+                    (Some(Converter { function, jni_type }), ..) => ParamConversion::Converted { function, jni_type: self.map_lifetimes(jni_type).0 },
+                    (None, Some(value), _, _, _) => ParamConversion::Default { ty: (*original_input_type).clone(), value: Box::new(value) },
+                    (None, None, Some(size), _, _) => ParamConversion::InternCache { size },
+                    (None, None, None, Some(numeric_conversion), _) => numeric_conversion
+                        .wrapper_type(&original_input_type)
+                        .map_or(ParamConversion::Standard, |wrapper| ParamConversion::Numeric { wrapper }),
+                    (None, None, None, None, Some(r)) => ParamConversion::Borrowed { ty: (*r.elem).clone() },
+                    (None, None, None, None, None) if is_receiver && matches!(&*original_input_type, Type::Reference(_)) => match &*original_input_type {
+                        Type::Reference(r) => ParamConversion::ReceiverRef { ty: (*r.elem).clone(), mutable: r.mutability.is_some() },
+                        _ => unreachable!(),
+                    },
+                    (None, None, None, None, None) if self.passthrough && is_passthrough_type(&original_input_type) => ParamConversion::Passthrough,
+                    (None, None, None, None, None) => widened_type(&original_input_type).map_or(ParamConversion::Standard, |wrapper| ParamConversion::Numeric { wrapper }),
+                };
+
+                // The future is `'static`, so it can't borrow anything from the call
+                match &conversion {
+                    ParamConversion::Borrowed { ty } if self.asyncness => {
+                        emit_error!(original_input_type, "`async` methods can't take borrowed parameters";
+                            help = "take an owned `{}` instead", if ty.to_token_stream().to_string() == "str" { "String" } else { "Vec<u8>" };
+                            note = "the future runs after the call has returned, when the Java value can't be borrowed anymore");
+                    }
+                    ParamConversion::ReceiverRef { .. } if self.asyncness => {
+                        emit_error!(arg_span, "`async` methods can't take `self` by reference";
+                            help = "take `self` by value";
+                            note = "the future runs after the call has returned, so it owns the receiver");
+                    }
+                    _ => {}
+                }
+
+                let jni_conversion_type: Type = match (&self.call_type, &conversion) {
+                    (_, ParamConversion::Default { .. }) => parse_quote_spanned! { original_input_type.span() => ::robusta_jni::jni::objects::JObject<'env> },
+                    (_, ParamConversion::InternCache { .. }) => parse_quote_spanned! { original_input_type.span() => ::robusta_jni::jni::objects::JString<'env> },
+                    (CallType::Safe(_), ParamConversion::Numeric { wrapper }) => parse_quote_spanned! { original_input_type.span() => <#wrapper as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source },
+                    (CallType::Unchecked { .. }, ParamConversion::Numeric { wrapper }) => parse_quote_spanned! { original_input_type.span() => <#wrapper as ::robusta_jni::convert::FromJavaValue<'env>>::Source },
+                    (_, ParamConversion::Borrowed { ty }) => parse_quote_spanned! { original_input_type.span() => <#ty as ::robusta_jni::convert::FromJavaValueRef<'env>>::Source },
+                    (_, ParamConversion::Passthrough) => (*original_input_type).clone(),
+                    (_, ParamConversion::Converted { jni_type, .. }) => jni_type.clone(),
+                    (CallType::Safe(_), ParamConversion::Standard) => parse_quote_spanned! { original_input_type.span() => <#original_input_type as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source },
+                    (CallType::Unchecked { .. }, ParamConversion::Standard) => parse_quote_spanned! { original_input_type.span() => <#original_input_type as ::robusta_jni::convert::FromJavaValue<'env>>::Source },
+                    (CallType::Safe(_), ParamConversion::ReceiverRef { ty, .. }) => parse_quote_spanned! { original_input_type.span() => <#ty as ::robusta_jni::convert::TryFromJavaValue<'env>>::Source },
+                    (CallType::Unchecked { .. }, ParamConversion::ReceiverRef { ty, .. }) => parse_quote_spanned! { original_input_type.span() => <#ty as ::robusta_jni::convert::FromJavaValue<'env>>::Source },
+                    (_, ParamConversion::Flatten { .. }) => unreachable!("`#[flatten]` parameters are expanded by `fold_flattened_arg`"),
+                    (_, ParamConversion::Env { .. }) | (_, ParamConversion::Class) => unreachable!("environment and class parameters aren't converted"),
+                };
+
+                // The object a method is called on isn't part of its descriptor, and `Vec`s are taken from any `List`
+                if !is_receiver {
+                    let span = original_input_type.span();
+                    self.param_sig_types.push(match (&conversion, &descriptor) {
+                        (_, Some(descriptor)) => quote! { #descriptor },
+                        (ParamConversion::Default { ty, .. }, _) => quote_spanned! { span => ::robusta_jni::signature::parameter_signature(<::robusta_jni::convert::JOption<#ty> as ::robusta_jni::convert::Signature>::SIG_TYPE) },
+                        (ParamConversion::InternCache { .. }, _) => quote_spanned! { span => <::robusta_jni::jni::objects::JString as ::robusta_jni::convert::Signature>::SIG_TYPE },
+                        (ParamConversion::Numeric { wrapper }, _) => quote_spanned! { span => <#wrapper as ::robusta_jni::convert::Signature>::SIG_TYPE },
+                        (ParamConversion::Borrowed { ty }, _) => quote_spanned! { span => <#ty as ::robusta_jni::convert::FromJavaValueRef>::SIG_TYPE },
+                        (ParamConversion::Converted { jni_type, .. }, _) => quote_spanned! { span => ::robusta_jni::signature::parameter_signature(<#jni_type as ::robusta_jni::convert::Signature>::SIG_TYPE) },
+                        _ => quote_spanned! { span => ::robusta_jni::signature::parameter_signature(<#original_input_type as ::robusta_jni::convert::Signature>::SIG_TYPE) },
+                    });
+                }
+
+                self.param_conversions.push(conversion);
+
+                if let Pat::Ident(PatIdent { mutability, .. }) = t.pat.as_mut() {
+                    *mutability = None
+                }
+
+                FnArg::Typed(PatType {
+                    attrs: t.attrs,
+                    pat: t.pat,
+                    colon_token: t.colon_token,
+                    ty: Box::new(jni_conversion_type),
+                })
+            }
+        }
+    }
+
+    fn fold_return_type(&mut self, return_type: ReturnType) -> ReturnType {
+        // The output of the future completes the `CompletableFuture` returned right away
+        if self.asyncness {
+            if let (Some(_), ReturnType::Type(_, rtype)) = (self.return_conversion, &return_type) {
+                emit_error!(rtype, "return values of `async` methods can't be converted with `#[truncate]`, `#[saturate]` or `#[reinterpret]`";
+                    note = "they complete a `CompletableFuture`, as boxed values");
+            }
+            self.return_sig_type = Some(quote! { "Ljava/util/concurrent/CompletableFuture;" });
+            let span = return_type.span();
+            return ReturnType::Type(Token![->](span), parse_quote_spanned! { span => ::robusta_jni::jni::objects::JObject<'env> });
+        }
+
+        // Java gets back the JNI type the function of `#[convert]` converts the return value to
+        if let (Some(converter), ReturnType::Type(arrow, _)) = (self.return_converter.clone(), &return_type) {
+            let (jni_type, _) = self.map_lifetimes(converter.jni_type);
+            self.return_sig_type = Some(quote_spanned! { jni_type.span() => <#jni_type as ::robusta_jni::convert::Signature>::SIG_TYPE });
+            return ReturnType::Type(*arrow, Box::new(jni_type));
+        }
+
+        if self.passthrough {
+            if let ReturnType::Type(_, rtype) = &return_type {
+                self.return_sig_type = Some(quote_spanned! { rtype.span() => <#rtype as ::robusta_jni::convert::Signature>::SIG_TYPE });
+            }
+            return return_type;
+        }
+
+        let return_type = match return_type {
+            ReturnType::Type(arrow, rtype) => {
+                let rtype = self.struct_freestanding_transformer.fold_type(*rtype);
+                let (rtype, lifetimes) = self.map_lifetimes(rtype);
+                for lifetime in lifetimes.iter().filter(|l| self.borrowed_lifetimes.contains(l)) {
+                    emit_error!(lifetime, "return values can't borrow `&str` and `&[u8]` parameters";
+                        note = "`{}` borrows the Java value of a parameter, which is released when the method returns", lifetime;
+                        help = "return an owned value instead, such as a `String` or a `Vec<u8>`");
+                }
+                let rtype = self.wrap_return_type(self.return_conversion, rtype);
+                // Only the `Ok` value of a `Result` is returned to Java, which is the `Signature` of `Result` too
+                self.return_sig_type = Some(quote_spanned! { rtype.span() => <#rtype as ::robusta_jni::convert::Signature>::SIG_TYPE });
+
+                // In safe methods, an `Err` becomes a Java exception and only the `Ok` value is returned to Java
+                let rtype = match (&self.call_type, get_result_types(&rtype)) {
+                    (CallType::Safe(_), Some((ok_type, _))) => ok_type.clone(),
+                    _ => rtype,
+                };
+                // Borrowed strings are returned as the `String`s they're converted to
+                let rtype = owned_string_value(&rtype, parse_quote! { v }).map_or(rtype, |(owned, _)| owned);
+                ReturnType::Type(arrow, Box::new(rtype))
+            }
+            ReturnType::Default => ReturnType::Default,
+        };
+
+        match return_type {
+            ReturnType::Default => return_type,
+            ReturnType::Type(ref arrow, ref rtype) => match (&**rtype, self.call_type.clone()) {
+                (Type::Path(p), CallType::Unchecked { .. }) => ReturnType::Type(
+                    *arrow,
+                    parse_quote_spanned! { p.span() => <#p as ::robusta_jni::convert::IntoJavaValue<'env>>::Target },
+                ),
+
+                (Type::Path(p), CallType::Safe(_)) => ReturnType::Type(
+                    *arrow,
+                    parse_quote_spanned! { p.span() => <#p as ::robusta_jni::convert::TryIntoJavaValue<'env>>::Target },
+                ),
+
+                (Type::Reference(r), CallType::Unchecked { .. }) => ReturnType::Type(
+                    *arrow,
+                    parse_quote_spanned! { r.span() => <#r as ::robusta_jni::convert::IntoJavaValue<'env>>::Target },
+                ),
+
+                (Type::Reference(r), CallType::Safe(_)) => ReturnType::Type(
+                    *arrow,
+                    parse_quote_spanned! { r.span() => <#r as ::robusta_jni::convert::TryIntoJavaValue<'env>>::Target },
+                ),
+                (Type::Tuple(TypeTuple { elems, .. }), _) if elems.is_empty() => {
+                    ReturnType::Default
+                }
+                (Type::Tuple(t), CallType::Unchecked { .. }) => ReturnType::Type(
+                    *arrow,
+                    parse_quote_spanned! { t.span() => <#t as ::robusta_jni::convert::IntoJavaValue<'env>>::Target },
+                ),
+
+                (Type::Tuple(t), CallType::Safe(_)) => ReturnType::Type(
+                    *arrow,
+                    parse_quote_spanned! { t.span() => <#t as ::robusta_jni::convert::TryIntoJavaValue<'env>>::Target },
+                ),
+                _ => {
+                    emit_error!(return_type, "Only type or type paths are permitted as type ascriptions in function params");
+                    return_type
+                }
+            },
+        }
+    }
+
+    fn fold_signature(&mut self, node: Signature) -> Signature {
+        self.asyncness = node.asyncness.is_some();
+        self.passthrough = is_passthrough_signature(&node) && self.return_conversion.is_none() && self.return_converter.is_none() && !self.asyncness;
+
+        // The generics declare the lifetimes found in the parameters and return type
+        let inputs = node
+            .inputs
+            .into_iter()
+            .flat_map(|f| match f {
+                FnArg::Typed(t) if t.attrs.iter().any(|a| a.path.is_ident("flatten")) => self.fold_flattened_arg(t),
+                f => {
+                    // The environment parameter is left out of the JNI function's parameters, which already start with it
+                    let arg = self.fold_fn_arg(f);
+                    match self.param_conversions.last() {
+                        Some(ParamConversion::Env { .. }) | Some(ParamConversion::Class) => Vec::new(),
+                        _ => vec![arg],
+                    }
+                }
+            })
+            .collect();
+        let output = self.fold_return_type(node.output);
+
+        Signature {
+            asyncness: None,
+            abi: node.abi.map(|a| self.fold_abi(a)),
+            ident: self.fold_ident(node.ident),
+            generics: self.transform_generics(node.generics),
+            inputs,
+            variadic: node.variadic.map(|v| self.fold_variadic(v)),
+            output,
+            ..node
+        }
+    }
+}
+
+/// The `CompletableFuture` returned by the `async` method called by `method_call`, completed by its future on the spawner
+/// (see `robusta_jni::runtime`). Outputs that aren't a `Result` can't fail.
+fn spawn_completable(signature: &Signature, method_call: &Expr, exception_class: &str, message: Option<&str>) -> Expr {
+    let span = signature.span();
+    // Parameters are converted by the call, on the calling thread, before the future is spawned
+    let future: Expr = match &signature.output {
+        ReturnType::Type(_, ty) if get_result_types(ty).is_some() => method_call.clone(),
+        _ => parse_quote_spanned! { span => {
+            let future = #method_call;
+            async move { ::core::result::Result::<_, ::core::convert::Infallible>::Ok(future.await) }
+        }},
+    };
+    let message = match message {
+        Some(m) => quote! { ::core::option::Option::Some(#m) },
+        None => quote! { ::core::option::Option::None },
+    };
+
+    parse_quote_spanned! { span =>
+        ::robusta_jni::__private::spawn_completable(env, #exception_class, #message, #future)
+    }
+}
+
+/// The `&mut self` receiver of `signature`, if any.
+fn mutable_receiver(signature: &Signature) -> Option<&FnArg> {
+    signature.inputs.iter().find(|arg| match arg {
+        FnArg::Receiver(r) => r.reference.is_some() && r.mutability.is_some(),
+        FnArg::Typed(t) => {
+            matches!(&*t.pat, Pat::Ident(PatIdent { ident, .. }) if ident == "self")
+                && matches!(&*t.ty, Type::Reference(r) if r.mutability.is_some())
+        }
+    })
+}
+
+/// Whether `signature` only has Java primitive parameters and return type, besides the environment and class parameters.
+fn is_passthrough_signature(signature: &Signature) -> bool {
+    let passthrough_return = match &signature.output {
+        ReturnType::Default => true,
+        ReturnType::Type(_, rtype) => is_passthrough_type(rtype) || matches!(&**rtype, Type::Tuple(t) if t.elems.is_empty()),
+    };
+
+    passthrough_return
+        && signature.inputs.iter().all(|arg| match arg {
+            FnArg::Receiver(_) => false,
+            FnArg::Typed(t) => {
+                t.attrs.is_empty()
+                    && !matches!(&*t.pat, Pat::Ident(PatIdent { ident, .. }) if ident == "self")
+                    && (is_passthrough_type(&t.ty) || is_env_type(&t.ty) || is_class_type(&t.ty))
+            }
+        })
+}
+
+struct JNISignature {
+    transformed_signature: Signature,
+    call_type: CallType,
+    struct_name: String,
+    /// The struct with its type parameters replaced, to call the method through, if the struct is generic.
+    bridged_struct_type: Option<Path>,
+    param_conversions: Vec<ParamConversion>,
+    return_wrapper: Option<(Path, bool)>,
+    passthrough: bool,
+    asyncness: bool,
+    param_sig_types: Vec<TokenStream>,
+    return_sig_type: Option<TokenStream>,
+}
+
+impl JNISignature {
+    fn new(
+        signature: Signature,
+        struct_context: &StructContext,
+        call_type: CallType,
+        return_conversion: Option<NumericConversion>,
+        return_converter: Option<Converter>,
+    ) -> JNISignature {
+        let freestanding_transformer =
+            FreestandingTransformer::new(struct_context.struct_type.clone());
+        let mut jni_signature_transformer = JNISignatureTransformer::new(
+            freestanding_transformer,
+            struct_context.struct_lifetimes.clone(),
+            struct_context.flattened_structs.clone(),
+            call_type.clone(),
+            return_conversion,
+            return_converter,
+        );
+
+        let transformed_signature = jni_signature_transformer.fold_signature(signature);
+
+        JNISignature {
+            transformed_signature,
+            call_type,
+            struct_name: struct_context.struct_name.clone(),
+            // Methods of instantiations are called on the instantiated struct, even from impl blocks without type parameters
+            bridged_struct_type: Some(&struct_context.struct_type_params)
+                .filter(|params| !params.is_empty() || struct_context.instantiation.is_some())
+                .map(|params| bridged_struct_type(&struct_context.struct_type, params)),
+            param_conversions: jni_signature_transformer.param_conversions,
+            return_wrapper: jni_signature_transformer.return_wrapper,
+            passthrough: jni_signature_transformer.passthrough,
+            asyncness: jni_signature_transformer.asyncness,
+            param_sig_types: jni_signature_transformer.param_sig_types,
+            return_sig_type: jni_signature_transformer.return_sig_type,
+        }
+    }
+
+    /// Whether the method only takes and returns Java primitives (besides the environment and class), so that no conversion can
+    /// fail and the wrapper can call it directly.
+    fn is_passthrough(&self) -> bool {
+        // A later `JClass` parameter is a `Class` object passed by Java, which is converted
+        self.passthrough && !self.param_conversions.iter().any(|c| matches!(c, ParamConversion::Standard | ParamConversion::ReceiverRef { .. }))
+    }
+
+    /// Whether the method takes the class it's called on, which the wrapper passes through.
+    fn takes_class(&self) -> bool {
+        self.param_conversions.iter().any(|c| matches!(c, ParamConversion::Class))
+    }
+
+    fn args_iter(&self) -> impl Iterator<Item = &PatType> {
+        self.transformed_signature.inputs.iter()
+            .map(|a| match a {
+                FnArg::Receiver(_) => panic!("Bug -- please report to library author. Found receiver type in freestanding signature!"),
+                FnArg::Typed(p) => p
+            })
+    }
+
+    /// Converts the JNI parameter `ident` to the type of the original parameter.
+    fn input_param(&self, ident: &Ident, conversion: &ParamConversion) -> Expr {
+        match (&self.call_type, conversion) {
+            (CallType::Safe(_), ParamConversion::Standard) => parse_quote_spanned! { ident.span() => ::robusta_jni::convert::TryFromJavaValue::try_from(#ident, env)? },
+            (CallType::Unchecked { .. }, ParamConversion::Standard) => parse_quote_spanned! { ident.span() => ::robusta_jni::convert::FromJavaValue::from(#ident, env) },
+            // The receiver is a temporary of the statement making the call, borrowed for the duration of the call
+            (CallType::Safe(_), ParamConversion::ReceiverRef { ty, mutable: false }) => parse_quote_spanned! { ident.span() =>
+                &<#ty as ::robusta_jni::convert::TryFromJavaValue<'env>>::try_from(#ident, env)?
+            },
+            (CallType::Safe(_), ParamConversion::ReceiverRef { ty, mutable: true }) => parse_quote_spanned! { ident.span() =>
+                &mut <#ty as ::robusta_jni::convert::TryFromJavaValue<'env>>::try_from(#ident, env)?
+            },
+            (CallType::Unchecked { .. }, ParamConversion::ReceiverRef { ty, mutable: false }) => parse_quote_spanned! { ident.span() =>
+                &<#ty as ::robusta_jni::convert::FromJavaValue<'env>>::from(#ident, env)
+            },
+            (CallType::Unchecked { .. }, ParamConversion::ReceiverRef { ty, mutable: true }) => parse_quote_spanned! { ident.span() =>
+                &mut <#ty as ::robusta_jni::convert::FromJavaValue<'env>>::from(#ident, env)
+            },
+            (CallType::Safe(_), ParamConversion::Default { ty, value }) => parse_quote_spanned! { ident.span() =>
+                if env.is_same_object(#ident, ::robusta_jni::jni::objects::JObject::null())? {
+                    #value
+                } else {
+                    <#ty as ::robusta_jni::convert::TryFromJavaValue<'env>>::try_from(::robusta_jni::convert::JavaValue::try_unbox(#ident, env)?, env)?
+                }
+            },
+            (CallType::Unchecked { .. }, ParamConversion::Default { ty, value }) => parse_quote_spanned! { ident.span() =>
+                if env.is_same_object(#ident, ::robusta_jni::jni::objects::JObject::null()).unwrap() {
+                    #value
+                } else {
+                    <#ty as ::robusta_jni::convert::FromJavaValue<'env>>::from(::robusta_jni::convert::JavaValue::unbox(#ident, env), env)
+                }
+            },
+            (CallType::Safe(_), ParamConversion::InternCache { size }) => parse_quote_spanned! { ident.span() => {
+                static CACHE: ::robusta_jni::convert::InternCache = ::robusta_jni::convert::InternCache::new(#size);
+                CACHE.get(&env, #ident)?
+            }},
+            (CallType::Unchecked { .. }, ParamConversion::InternCache { size }) => parse_quote_spanned! { ident.span() => {
+                static CACHE: ::robusta_jni::convert::InternCache = ::robusta_jni::convert::InternCache::new(#size);
+                CACHE.get(&env, #ident).unwrap()
+            }},
+            (CallType::Safe(_), ParamConversion::Numeric { wrapper }) => parse_quote_spanned! { ident.span() =>
+                <#wrapper as ::robusta_jni::convert::TryFromJavaValue<'env>>::try_from(#ident, env)?.0
+            },
+            (CallType::Unchecked { .. }, ParamConversion::Numeric { wrapper }) => parse_quote_spanned! { ident.span() =>
+                <#wrapper as ::robusta_jni::convert::FromJavaValue<'env>>::from(#ident, env).0
+            },
+            // The guard is a temporary of the statement making the call, so it's dropped once the return value has been converted
+            (CallType::Safe(_), ParamConversion::Borrowed { ty }) => parse_quote_spanned! { ident.span() =>
+                &*<#ty as ::robusta_jni::convert::FromJavaValueRef<'env>>::borrow_from(#ident, env)?
+            },
+            (CallType::Unchecked { .. }, ParamConversion::Borrowed { ty }) => parse_quote_spanned! { ident.span() =>
+                &*<#ty as ::robusta_jni::convert::FromJavaValueRef<'env>>::borrow_from(#ident, env).unwrap()
+            },
+            // `Into` lets the function take any type the JNI value converts to, e.g. a `JString` from the default `JObject`
+            (CallType::Safe(_), ParamConversion::Converted { function, .. }) => parse_quote_spanned! { function.span() =>
+                #function(::core::convert::Into::into(#ident), env)?
+            },
+            (CallType::Unchecked { .. }, ParamConversion::Converted { function, .. }) => parse_quote_spanned! { function.span() =>
+                #function(::core::convert::Into::into(#ident), env)
+            },
+            (_, ParamConversion::Passthrough) => parse_quote_spanned! { ident.span() => #ident },
+            (_, ParamConversion::Flatten { .. }) => unreachable!("flattened parameters are converted field by field"),
+            (_, ParamConversion::Env { .. }) | (_, ParamConversion::Class) => unreachable!("environment and class parameters aren't converted"),
+        }
+    }
+
+    fn signature_call(&self) -> Expr {
+        let method_call_inputs: Punctuated<Expr, Token![,]> = {
+            let mut args = self.args_iter().map(|p| match p.pat.as_ref() {
+                Pat::Ident(PatIdent { ident, .. }) => ident,
+                _ => panic!("Bug -- please report to library author. Found non-ident FnArg pattern")
+            });
+
+            let result: Vec<Expr> = self.param_conversions.iter()
+                .map(|conversion| match conversion {
+                    // The struct is assembled from its fields, each converted from its own parameter
+                    ParamConversion::Flatten { struct_ident, fields } => {
+                        let values = fields.iter().map(|_| self.input_param(args.next().unwrap(), &ParamConversion::Standard));
+                        parse_quote_spanned! { struct_ident.span() => #struct_ident { #(#fields: #values),* } }
+                    }
+                    ParamConversion::Env { by_ref: false } => parse_quote! { env },
+                    ParamConversion::Env { by_ref: true } => parse_quote! { &env },
+                    ParamConversion::Class => parse_quote! { class },
+                    conversion => self.input_param(args.next().unwrap(), conversion),
+                })
+                .collect();
+
+            Punctuated::from_iter(result.into_iter())
+        };
+
+        let signature_span = self.transformed_signature.span();
+        let struct_name = Ident::new(&self.struct_name, signature_span);
+        let method_name = self.transformed_signature.ident.clone();
+
+        let method_call: Expr = match &self.bridged_struct_type {
+            Some(struct_type) => parse_quote_spanned! { signature_span =>
+                <#struct_type>::#method_name(#method_call_inputs)
+            },
+            None => parse_quote_spanned! { signature_span =>
+                #struct_name::#method_name(#method_call_inputs)
+            },
+        };
+
+        match &self.return_wrapper {
+            Some((wrapper, true)) => parse_quote_spanned! { signature_span => ::core::result::Result::map(#method_call, #wrapper) },
+            Some((wrapper, false)) => parse_quote_spanned! { signature_span => #wrapper(#method_call) },
+            None => method_call,
+        }
+    }
+
+    fn transformed_signature(&self) -> &Signature {
+        &self.transformed_signature
+    }
+}
@@ -0,0 +1,1292 @@
+use inflector::cases::camelcase::to_camel_case;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use proc_macro_error::{abort, emit_error, emit_warning};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::fold::Fold;
+use syn::spanned::Spanned;
+use syn::{parse_quote, Expr, GenericArgument, Lifetime, Path, PathArguments, Type, TypePath};
+use syn::{Attribute, Block, FnArg, ImplItemMethod, Pat, PatIdent, PatType, ReturnType, Signature};
+
+use crate::transformation::context::StructContext;
+use crate::transformation::utils::{check_array_types, check_java_list, check_owned_type, check_sig_descriptor, get_call_type, get_dispatch, get_field_attribute, get_java_name, get_retry, owned_string_value, widen_wrapper, widened_type, FieldAttribute, NumericConversion};
+use crate::transformation::{CallType, CallTypeAttribute, SafeParams};
+use crate::utils::{get_abi, get_env_arg, is_env_ref, is_self_method};
+use std::collections::BTreeSet;
+
+pub struct ImportedMethodTransformer<'ctx> {
+    pub(crate) struct_context: &'ctx StructContext,
+    /// A `MethodMetadata` expression for each imported method, see [`method_metadata`].
+    pub(crate) metadata: Vec<TokenStream>,
+}
+
+impl<'ctx> Fold for ImportedMethodTransformer<'ctx> {
+    fn fold_impl_item_method(&mut self, node: ImplItemMethod) -> ImplItemMethod {
+        let abi = get_abi(&node.sig);
+        match (&node.vis, &abi.as_deref()) {
+            (_, Some("java")) => {
+                let constructor_attribute = node.attrs.iter().find(|a| {
+                    a.path.get_ident().map(ToString::to_string).as_deref() == Some("constructor")
+                });
+                let is_constructor = {
+                    match constructor_attribute {
+                        Some(a) => {
+                            if !a.tokens.is_empty() {
+                                emit_warning!(
+                                    a.tokens,
+                                    "#[constructor] attribute does not take parameters"
+                                )
+                            }
+                            true
+                        }
+                        None => false,
+                    }
+                };
+
+                let cached_attribute = node.attrs.iter().find(|a| {
+                    a.path.get_ident().map(ToString::to_string).as_deref() == Some("cached")
+                });
+                if let Some(a) = cached_attribute {
+                    if !a.tokens.is_empty() {
+                        emit_warning!(a.tokens, "#[cached] attribute does not take parameters")
+                    }
+                }
+
+                let static_method_attribute = node.attrs.iter().find(|a| a.path.is_ident("static_method"));
+                if let Some(a) = static_method_attribute {
+                    if !a.tokens.is_empty() {
+                        emit_warning!(a.tokens, "#[static_method] attribute does not take parameters")
+                    }
+                }
+
+                let attach_attribute = node.attrs.iter().find(|a| a.path.is_ident("attach"));
+                if let Some(a) = attach_attribute {
+                    if !a.tokens.is_empty() {
+                        emit_warning!(a.tokens, "#[attach] attribute does not take parameters")
+                    }
+                }
+
+                let returns_self_attribute = node.attrs.iter().find(|a| a.path.is_ident("returns_self"));
+                if let Some(a) = returns_self_attribute {
+                    if !a.tokens.is_empty() {
+                        emit_warning!(a.tokens, "#[returns_self] attribute does not take parameters")
+                    }
+                }
+
+                let field_attribute = get_field_attribute(&node.attrs);
+                let dispatch = get_dispatch(&node.attrs);
+                let retry = get_retry(&node.attrs);
+
+                let suspend_attribute = node.attrs.iter().find(|a| a.path.is_ident("suspend"));
+                if let Some(a) = suspend_attribute {
+                    if !a.tokens.is_empty() {
+                        emit_warning!(a.tokens, "#[suspend] attribute does not take parameters")
+                    }
+                }
+
+                let param_attributes = node.sig.inputs.iter().flat_map(|i| match i {
+                    FnArg::Typed(t) => t.attrs.iter(),
+                    FnArg::Receiver(r) => r.attrs.iter(),
+                });
+                for attr in node.attrs.iter().chain(param_attributes).filter(|a| NumericConversion::is_attribute(a)) {
+                    emit_error!(attr, "`#[{}]` can only be used on exported (`extern \"jni\"`) methods", attr.path.to_token_stream());
+                }
+
+                if !node.block.stmts.is_empty() {
+                    emit_error!(
+                        node.block,
+                        "`extern \"java\"` methods must have an empty body"
+                    )
+                }
+
+                let self_method = is_self_method(&node.sig);
+                let (signature, env_arg) = get_env_arg(node.sig.clone());
+
+                // `#[sig]` and `#[java_list]` attributes only change the descriptor of the called method
+                let param_descriptors: Vec<Option<String>> = signature
+                    .inputs
+                    .iter()
+                    .filter_map(|i| match i {
+                        FnArg::Typed(t) if !matches!(&*t.pat, Pat::Ident(PatIdent { ident, .. }) if ident == "self") => Some(t),
+                        _ => None,
+                    })
+                    .map(|t| check_sig_descriptor(&t.attrs, Some(&t.ty)).or_else(|| check_java_list(&t.attrs, Some(&t.ty))))
+                    .collect();
+                let return_descriptor = match &node.sig.output {
+                    _ if is_constructor && node.attrs.iter().any(|a| a.path.is_ident("sig")) => {
+                        emit_error!(node.sig.ident, "constructors can't have a `#[sig]`";
+                            note = "they return the constructed object");
+                        None
+                    }
+                    ReturnType::Type(_, ty) => check_sig_descriptor(&node.attrs, Some(ty)).or_else(|| check_java_list(&node.attrs, Some(ty))),
+                    ReturnType::Default => check_sig_descriptor(&node.attrs, None).or_else(|| check_java_list(&node.attrs, None)),
+                };
+                let mut original_signature = node.sig.clone();
+                original_signature.inputs.iter_mut().for_each(|i| {
+                    if let FnArg::Typed(t) = i {
+                        t.attrs.retain(|a| !a.path.is_ident("sig") && !a.path.is_ident("java_list"));
+                    }
+                });
+
+                let impl_item_attributes: Vec<_> = {
+                    let discarded_known_attributes: BTreeSet<&str> = {
+                        let mut h = BTreeSet::new();
+                        h.insert("call_type");
+                        h.insert("java_name");
+                        h.insert("retry");
+                        h.insert("sig");
+                        h.insert("java_list");
+
+                        if is_constructor {
+                            h.insert("constructor");
+                        }
+                        if field_attribute.is_some() {
+                            h.insert("field");
+                        }
+                        if cached_attribute.is_some() {
+                            h.insert("cached");
+                        }
+                        if dispatch.is_some() {
+                            h.insert("dispatch");
+                        }
+                        if suspend_attribute.is_some() {
+                            h.insert("suspend");
+                        }
+                        if static_method_attribute.is_some() {
+                            h.insert("static_method");
+                        }
+                        if attach_attribute.is_some() {
+                            h.insert("attach");
+                        }
+                        if returns_self_attribute.is_some() {
+                            h.insert("returns_self");
+                        }
+                        h
+                    };
+
+                    node.clone()
+                        .attrs
+                        .into_iter()
+                        .filter(|a| {
+                            !discarded_known_attributes
+                                .contains(&a.path.segments.to_token_stream().to_string().as_str())
+                        })
+                        .collect()
+                };
+
+                let dummy = ImplItemMethod {
+                    sig: Signature {
+                        abi: None,
+                        ..original_signature.clone()
+                    },
+                    block: parse_quote! {{
+                        unimplemented!()
+                    }},
+                    attrs: impl_item_attributes.clone(),
+                    ..node.clone()
+                };
+
+                if is_constructor && self_method {
+                    emit_error!(
+                        original_signature,
+                        "cannot have self methods declared as constructors"
+                    );
+
+                    return dummy;
+                }
+
+                // Methods without a `self` receiver are static anyway, the attribute makes sure they stay that way
+                if let Some(attr) = static_method_attribute {
+                    if self_method {
+                        emit_error!(attr, "static methods can't have a `self` receiver";
+                            help = "remove `#[static_method]` to call an instance method");
+                        return dummy;
+                    }
+                    if is_constructor {
+                        emit_error!(attr, "constructors can't be `#[static_method]`";
+                            note = "constructors are called on their class already");
+                        return dummy;
+                    }
+                    if field_attribute.is_some() {
+                        emit_error!(attr, "field accessors can't be `#[static_method]`";
+                            help = "use `#[field(static)]` for static fields");
+                        return dummy;
+                    }
+                }
+
+                if let Some((attr, _)) = &dispatch {
+                    let conflict = if is_constructor {
+                        Some("constructors")
+                    } else if field_attribute.is_some() {
+                        Some("field accessors")
+                    } else if cached_attribute.is_some() {
+                        Some("`#[cached]` methods")
+                    } else {
+                        None
+                    };
+
+                    if let Some(kind) = conflict {
+                        emit_error!(attr, "{} can't be dispatched to a toolkit thread", kind);
+                        return dummy;
+                    }
+                }
+
+                if let Some(attr) = suspend_attribute {
+                    let conflict = if is_constructor {
+                        Some("constructors")
+                    } else if field_attribute.is_some() {
+                        Some("field accessors")
+                    } else if cached_attribute.is_some() {
+                        Some("`#[cached]` methods")
+                    } else if dispatch.is_some() {
+                        Some("`#[dispatch]` methods")
+                    } else {
+                        None
+                    };
+
+                    if let Some(kind) = conflict {
+                        emit_error!(attr, "{} can't be Kotlin `suspend` functions", kind);
+                        return dummy;
+                    }
+                }
+
+                // Instance methods and constructors return references valid for the environment, which the attached
+                // environment doesn't outlive
+                if let Some(attr) = attach_attribute {
+                    let conflict = if self_method {
+                        Some("methods with a `self` receiver")
+                    } else if is_constructor {
+                        Some("constructors")
+                    } else if field_attribute.is_some() {
+                        Some("field accessors")
+                    } else {
+                        None
+                    };
+
+                    if let Some(kind) = conflict {
+                        emit_error!(attr, "{} can't be `#[attach]`", kind;
+                            note = "only static methods can be called with the environment of the current thread");
+                        return dummy;
+                    }
+                }
+
+                if let (Some((attr, _)), Some(_)) = (&retry, &field_attribute) {
+                    emit_error!(attr, "field accessors can't be retried");
+                    return dummy;
+                }
+
+                check_array_types(&node.sig);
+
+                let call_type_attribute = get_call_type(&node.attrs);
+                let call_type = call_type_attribute
+                    .as_ref()
+                    .map(|c| &c.call_type)
+                    .unwrap_or(&CallType::Safe(None));
+
+                if let Some(CallTypeAttribute { attr, .. }) = &call_type_attribute {
+                    if let CallType::Safe(Some(params)) = call_type {
+                        if let SafeParams {
+                            message: Some(_), ..
+                        }
+                        | SafeParams {
+                            exception_class: Some(_),
+                            ..
+                        } = params
+                        {
+                            abort!(attr, "can't have exception message or exception class for imported methods")
+                        }
+                    }
+                }
+
+                if is_constructor {
+                    let is_safe = matches!(call_type, CallType::Safe(_));
+                    let expected = if is_safe { "`-> JniResult<Self>`" } else { "`-> Self`" };
+
+                    match &original_signature.output {
+                        ReturnType::Type(_, ty) if !is_constructor_return_type(ty, &self.struct_context.struct_name, is_safe) => {
+                            emit_error!(ty, "constructors must return the constructed object, as `Self` or `JObject`"; help = "use {}", expected);
+                            return dummy;
+                        }
+                        ReturnType::Default => {
+                            emit_error!(original_signature.ident, "constructors must return the constructed object"; help = "add {}", expected);
+                            return dummy;
+                        }
+                        _ => {}
+                    }
+                }
+
+                // The receiver is returned as it is, so the method must own it, and the Java method's result is only checked
+                if let Some(attr) = returns_self_attribute {
+                    let is_safe = matches!(call_type, CallType::Safe(_));
+                    let expected = if is_safe { "`-> JniResult<Self>`" } else { "`-> Self`" };
+                    let by_value_receiver = matches!(node.sig.inputs.first(), Some(FnArg::Receiver(r)) if r.reference.is_none());
+
+                    if !self_method || !by_value_receiver {
+                        emit_error!(attr, "`#[returns_self]` methods must take `self` by value";
+                            note = "they return their receiver, for calls to be chained");
+                        return dummy;
+                    }
+                    let conflict = if field_attribute.is_some() {
+                        Some("field accessors")
+                    } else if suspend_attribute.is_some() {
+                        Some("`#[suspend]` methods")
+                    } else if return_descriptor.is_some() {
+                        Some("methods with a return `#[sig]` or `#[java_list]`")
+                    } else {
+                        None
+                    };
+                    if let Some(kind) = conflict {
+                        emit_error!(attr, "{} can't be `#[returns_self]`", kind);
+                        return dummy;
+                    }
+                    match &original_signature.output {
+                        ReturnType::Type(_, ty) if !is_self_return_type(ty, &self.struct_context.struct_name, is_safe) => {
+                            emit_error!(ty, "`#[returns_self]` methods must return `Self`"; help = "use {}", expected);
+                            return dummy;
+                        }
+                        ReturnType::Default => {
+                            emit_error!(original_signature.ident, "`#[returns_self]` methods must return `Self`"; help = "add {}", expected);
+                            return dummy;
+                        }
+                        _ => {}
+                    }
+                }
+
+                let jni_package_path = self
+                    .struct_context
+                    .package
+                    .as_ref()
+                    .map(|p| p.to_string())
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or_else(|| "".into())
+                    .replace('.', "/");
+
+                let java_class_path = [jni_package_path, self.struct_context.class_name()]
+                    .iter()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_owned())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let java_method_name = get_java_name(&node.attrs)
+                    .map(|n| n.value())
+                    .or_else(|| self.struct_context.renamed(&signature.ident))
+                    .unwrap_or_else(|| to_camel_case(&signature.ident.to_string()));
+
+                let input_types_conversions = signature
+                    .inputs
+                    .iter()
+                    .filter_map(|i| match i {
+                        FnArg::Typed(t) => match &*t.pat {
+                            Pat::Ident(PatIdent { ident, .. }) if ident == "self" => None,
+                            _ => Some((&t.ty, t.ty.span()))
+                        },
+                        FnArg::Receiver(_) => None,
+                    })
+                    .zip(&param_descriptors)
+                    .map(|((t, span), descriptor)| {
+                        let t = java_param_value(t, parse_quote! { v }).map_or_else(|| (**t).clone(), |(converted, _)| converted);
+                        if let Some(descriptor) = descriptor {
+                            quote_spanned! { span => #descriptor, }
+                        } else if let CallType::Safe(_) = call_type {
+                            quote_spanned! { span => <#t as ::robusta_jni::convert::TryIntoJavaValue>::SIG_TYPE, }
+                        } else {
+                            quote_spanned! { span => <#t as ::robusta_jni::convert::IntoJavaValue>::SIG_TYPE, }
+                        }
+                    })
+                    .fold(TokenStream::new(), |mut tok, t| {
+                        t.to_tokens(&mut tok);
+                        tok
+                    });
+
+                let output_type_span = {
+                    match &signature.output {
+                        ReturnType::Default => signature.output.span(),
+                        ReturnType::Type(_arrow, ref ty) => ty.span(),
+                    }
+                };
+
+                // Signature of the return type for `MethodMetadata`, `None` for `V`
+                let mut return_sig_type = None;
+                // Unsigned integers are returned through the wrapper widening them from `long`
+                let mut return_wrapper = None;
+                let mut output_conversion = match signature.output {
+                    ReturnType::Default => quote_spanned!(signature.output.span() => "V"),
+                    ReturnType::Type(_arrow, ref ty) => {
+                        if is_constructor {
+                            quote_spanned! { output_type_span => "V" }
+                        } else if returns_self_attribute.is_some() {
+                            // The Java method returns an object of the class, which isn't converted
+                            return_sig_type = Some(quote! { <Self as ::robusta_jni::convert::Signature>::SIG_TYPE });
+                            quote_spanned! { output_type_span => <Self as ::robusta_jni::convert::Signature>::SIG_TYPE }
+                        } else {
+                            match call_type {
+                                CallType::Safe(_) => {
+                                    let inner_result_ty = match &**ty {
+                                        Type::Path(TypePath { path, .. }) => {
+                                            path.segments.last().map(|s| match &s.arguments {
+                                                PathArguments::AngleBracketed(a) => {
+                                                    match &a.args.first().expect("return type must be `::robusta_jni::jni::errors::Result` when using \"java\" ABI with an implicit or \"safe\" `call_type`") {
+                                                        GenericArgument::Type(t) => t,
+                                                        _ => abort!(a, "first generic argument in return type must be a type")
+                                                    }
+                                                },
+                                                PathArguments::None => {
+                                                    let user_attribute_message = call_type_attribute.as_ref().map(|_| "because of this attribute");
+                                                    abort!(s, "return type must be `::robusta_jni::jni::errors::Result` when using \"java\" ABI with an implicit or \"safe\" `call_type`";
+                                                                        help = "replace `{}` with `Result<{}>`", s.ident, s.ident;
+                                                                        help =? call_type_attribute.as_ref().map(|c| c.attr.span()).unwrap() => user_attribute_message)
+                                                },
+                                                _ => abort!(s, "return type must be `::robusta_jni::jni::errors::Result` when using \"java\" ABI with an implicit or \"safe\" `call_type`")
+                                            })
+                                        },
+                                        _ => abort!(ty, "return type must be `::robusta_jni::jni::errors::Result` when using \"java\" ABI with an implicit or \"safe\" `call_type`")
+                                    }.unwrap();
+
+                                    check_owned_type(inner_result_ty);
+                                    return_wrapper = widened_type(inner_result_ty);
+                                    let inner_result_ty = return_wrapper.as_ref().unwrap_or(inner_result_ty);
+                                    return_sig_type = Some(quote! { <#inner_result_ty as ::robusta_jni::convert::TryFromJavaValue>::SIG_TYPE });
+                                    quote_spanned! { output_type_span => <#inner_result_ty as ::robusta_jni::convert::TryFromJavaValue>::SIG_TYPE }
+                                }
+                                CallType::Unchecked(_) => {
+                                    if let Type::Path(TypePath { path, .. }) = ty.as_ref() {
+                                        if let Some(r) =
+                                            path.segments.last().filter(|i| i.ident == "Result")
+                                        {
+                                            if let PathArguments::AngleBracketed(_) = r.arguments {
+                                                let call_type_span = call_type_attribute
+                                                    .as_ref()
+                                                    .map(|c| c.attr.span());
+                                                let call_type_hint = call_type_span.map(|_| {
+                                                    "maybe you meant `#[call_type(safe)]`?"
+                                                });
+
+                                                emit_warning!(ty, "using a `Result` type in a `#[call_type(unchecked)]` method";
+                                            hint =? call_type_span.unwrap() => call_type_hint)
+                                            }
+                                        }
+                                    }
+                                    check_owned_type(ty);
+                                    return_wrapper = widened_type(ty);
+                                    let ty = return_wrapper.as_ref().unwrap_or(ty);
+                                    return_sig_type = Some(quote! { <#ty as ::robusta_jni::convert::FromJavaValue>::SIG_TYPE });
+                                    quote_spanned! { output_type_span => <#ty as ::robusta_jni::convert::FromJavaValue>::SIG_TYPE }
+                                }
+                            }
+                        }
+                    }
+                };
+
+                if let Some(descriptor) = &return_descriptor {
+                    return_sig_type = Some(quote! { #descriptor });
+                    output_conversion = quote_spanned! { output_type_span => #descriptor };
+                }
+
+                let java_signature = quote_spanned! { signature.span() => ::robusta_jni::signature::descriptor(&[#input_types_conversions], #output_conversion) };
+
+                let input_conversions = signature.inputs.iter().fold(TokenStream::new(), |mut tok, input| {
+                    match input {
+                        FnArg::Receiver(_) => { tok }
+                        FnArg::Typed(t) => {
+                            let ty = &t.ty;
+                            let pat: TokenStream = {
+                                // The things we do for ~love~ nice compiler errors...
+                                // TODO: Check whether there is a better way to force spans onto token streams
+                                let pat = &t.pat;
+                                let mut p: TokenTree = parse_quote! { #pat };
+                                p.set_span(ty.span());
+                                p.into()
+                            };
+                            let (ty, pat) = match java_param_value(ty, parse_quote! { #pat }) {
+                                Some((converted, value)) => (converted, value.into_token_stream()),
+                                None => ((**ty).clone(), pat),
+                            };
+
+                            let conversion: TokenStream = if let CallType::Safe(_) = call_type {
+                                quote_spanned! { ty.span() => ::core::convert::Into::into(<#ty as ::robusta_jni::convert::TryIntoJavaValue>::try_into(#pat, env)?), }
+                            } else {
+                                quote_spanned! { ty.span() => ::core::convert::Into::into(<#ty as ::robusta_jni::convert::IntoJavaValue>::into(#pat, env)), }
+                            };
+                            conversion.to_tokens(&mut tok);
+                            tok
+                        }
+                    }
+                });
+
+                let return_expr = match call_type {
+                    // The returned reference is deleted, as it's the receiver itself for builder methods returning `this`
+                    CallType::Safe(_) if returns_self_attribute.is_some() => quote_spanned! { output_type_span =>
+                        res.and_then(|v| v.l())
+                            .and_then(|v| env.delete_local_ref(v))
+                            .map(|()| self)
+                    },
+                    CallType::Unchecked(_) if returns_self_attribute.is_some() => quote_spanned! { output_type_span => {
+                        env.delete_local_ref(res.l().unwrap()).unwrap();
+                        self
+                    }},
+                    CallType::Safe(_) => {
+                        if is_constructor {
+                            quote_spanned! { output_type_span =>
+                                res.and_then(|v| ::robusta_jni::convert::TryFromJavaValue::try_from(v, env))
+                            }
+                        } else {
+                            match &return_wrapper {
+                                Some(wrapper) => quote_spanned! { output_type_span =>
+                                    res.and_then(|v| ::core::convert::TryInto::try_into(::robusta_jni::convert::JValueWrapper::from(v)).map_err(::core::convert::Into::into))
+                                       .and_then(|v| <#wrapper as ::robusta_jni::convert::TryFromJavaValue>::try_from(v, env))
+                                       .map(|v| v.0)
+                                },
+                                None => quote_spanned! { output_type_span =>
+                                    res.and_then(|v| ::core::convert::TryInto::try_into(::robusta_jni::convert::JValueWrapper::from(v)).map_err(::core::convert::Into::into))
+                                       .and_then(|v| ::robusta_jni::convert::TryFromJavaValue::try_from(v, env))
+                                },
+                            }
+                        }
+                    }
+                    CallType::Unchecked(_) => {
+                        if is_constructor {
+                            quote_spanned! { output_type_span =>
+                                ::robusta_jni::convert::FromJavaValue::from(res, env)
+                            }
+                        } else {
+                            match &return_wrapper {
+                                Some(wrapper) => quote_spanned! { output_type_span =>
+                                    ::core::convert::TryInto::try_into(::robusta_jni::convert::JValueWrapper::from(res))
+                                        .map_err(::core::convert::Into::<::robusta_jni::jni::errors::Error>::into)
+                                        .map(|v| <#wrapper as ::robusta_jni::convert::FromJavaValue>::from(v, env).0)
+                                        .unwrap()
+                                },
+                                None => quote_spanned! { output_type_span =>
+                                    ::core::convert::TryInto::try_into(::robusta_jni::convert::JValueWrapper::from(res))
+                                        .map_err(::core::convert::Into::<::robusta_jni::jni::errors::Error>::into)
+                                        .map(|v| ::robusta_jni::convert::FromJavaValue::from(v, env))
+                                        .unwrap()
+                                },
+                            }
+                        }
+                    }
+                };
+
+                let env_value: TokenStream;
+                if let Some(env_arg) = env_arg {
+                    if let Some(attr) = attach_attribute {
+                        emit_error!(attr, "`#[attach]` methods can't have a `JNIEnv` parameter";
+                            help = "remove the parameter, they use the environment of the current thread");
+                        return dummy;
+                    }
+
+                    let env_is_ref = is_env_ref(&env_arg);
+                    let env_ident = match env_arg {
+                    FnArg::Typed(t) => {
+                        match *t.pat {
+                            Pat::Ident(PatIdent { ident, .. }) => ident,
+                            _ => panic!("non-ident pat in FnArg")
+                        }
+                    },
+                    _ => panic!("Bug -- please report to library author. Expected env parameter, found receiver")
+                };
+                    env_value = if env_is_ref { parse_quote!( *#env_ident ) } else { parse_quote!( #env_ident ) }
+                } else if self_method {
+                    env_value = parse_quote!(self.raw.jni_env())
+                } else if attach_attribute.is_some() {
+                    // Bound by the closure given to `with_attached_env`, see below
+                    env_value = parse_quote!(env)
+                } else {
+                    emit_error!(
+                        original_signature,
+                        "imported static methods must have a parameter of type `JNIEnv` as first parameter";
+                        help = "or use `#[attach]` to call the method with the environment of the current thread"
+                    );
+
+                    return dummy;
+                }
+
+                if let Some((attr, field)) = &field_attribute {
+                    if is_constructor {
+                        emit_error!(attr, "constructors can't be field accessors");
+                        return dummy;
+                    }
+                    if let Some(cached) = cached_attribute {
+                        emit_error!(cached, "field accessors can't be `#[cached]`");
+                        return dummy;
+                    }
+
+                    return match field_accessor_block(attr, field, &signature, call_type, &env_value, &java_class_path) {
+                        Some(block) => ImplItemMethod {
+                            sig: Signature {
+                                abi: None,
+                                ..original_signature
+                            },
+                            block,
+                            attrs: impl_item_attributes,
+                            ..node
+                        },
+                        None => dummy,
+                    };
+                }
+
+                // Without a return type, exceptions thrown by the Java method would be lost
+                if let (ReturnType::Default, CallType::Safe(_)) = (&original_signature.output, call_type) {
+                    emit_error!(original_signature.ident, "imported methods must return a `JniResult`";
+                        note = "exceptions thrown by the Java method are returned as `Err(Error::JavaException)`";
+                        help = "add `-> JniResult<()>`, or use `#[call_type(unchecked)]` to panic on exceptions instead");
+                    return dummy;
+                }
+
+                let mut param_sig_types: Vec<_> = signature
+                    .inputs
+                    .iter()
+                    .filter_map(|i| match i {
+                        FnArg::Typed(t) if !matches!(&*t.pat, Pat::Ident(PatIdent { ident, .. }) if ident == "self") => Some(&t.ty),
+                        _ => None,
+                    })
+                    .zip(&param_descriptors)
+                    .map(|(t, descriptor)| (java_param_value(t, parse_quote! { v }).map_or_else(|| (**t).clone(), |(converted, _)| converted), descriptor))
+                    .map(|(t, descriptor)| match (descriptor, call_type) {
+                        (Some(descriptor), _) => quote! { #descriptor },
+                        (None, CallType::Safe(_)) => quote! { <#t as ::robusta_jni::convert::TryIntoJavaValue>::SIG_TYPE },
+                        (None, CallType::Unchecked(_)) => quote! { <#t as ::robusta_jni::convert::IntoJavaValue>::SIG_TYPE },
+                    })
+                    .collect();
+                // The JVM method compiled from a `suspend` function takes a `Continuation` and returns `Object`
+                if suspend_attribute.is_some() {
+                    param_sig_types.push(quote! { "Lkotlin/coroutines/Continuation;" });
+                    return_sig_type = Some(quote! { "Ljava/lang/Object;" });
+                }
+                let metadata_name = if is_constructor { "<init>".to_string() } else { java_method_name.clone() };
+                let metadata = method_metadata(
+                    &self.struct_context.struct_type,
+                    &java_class_path,
+                    &metadata_name,
+                    &param_sig_types,
+                    return_sig_type.as_ref(),
+                    !self_method && !is_constructor,
+                );
+                // Expands to nothing unless `robusta_jni` is built with `verify-bindings`
+                let verify_binding = quote! { ::robusta_jni::__private::verify_binding!(env, #metadata); };
+                self.metadata.push(metadata);
+
+                let receiver_span = node.sig.inputs.first().map_or_else(Span::call_site, Spanned::span);
+                // `#[returns_self]` methods keep their receiver to return it, and convert a reference to it
+                let receiver_value = if returns_self_attribute.is_some() { quote! { &self } } else { quote! { self } };
+                let receiver = match call_type {
+                    CallType::Safe(_) => quote_spanned! { receiver_span =>
+                        ::robusta_jni::convert::JavaValue::autobox(::robusta_jni::convert::TryIntoJavaValue::try_into(#receiver_value, env)?, env)
+                    },
+                    CallType::Unchecked(_) => quote_spanned! { receiver_span =>
+                        ::robusta_jni::convert::JavaValue::autobox(::robusta_jni::convert::IntoJavaValue::into(#receiver_value, env), env)
+                    },
+                };
+
+                // With `#[retry]`, the receiver and arguments are converted once, before the first call
+                let (retry_setup, receiver, args) = match &retry {
+                    Some((_, retry)) => {
+                        let times = retry.times;
+                        let on = retry.binary_names();
+                        let backoff_ms = retry.backoff_ms;
+                        let conversions = if self_method {
+                            quote! { let (receiver, args): (_, &[::robusta_jni::jni::objects::JValue]) = (#receiver, &[#input_conversions]); }
+                        } else {
+                            quote! { let args: &[::robusta_jni::jni::objects::JValue] = &[#input_conversions]; }
+                        };
+
+                        (
+                            quote! {
+                                const RETRY: ::robusta_jni::retry::RetryPolicy = ::robusta_jni::retry::RetryPolicy {
+                                    times: #times,
+                                    on: &[#(#on),*],
+                                    backoff: ::core::time::Duration::from_millis(#backoff_ms),
+                                };
+                                #conversions
+                            },
+                            quote! { receiver },
+                            quote! { args },
+                        )
+                    }
+                    None => (TokenStream::new(), receiver, quote! { &[#input_conversions] }),
+                };
+
+                // With `#[cached]`, the class and method ID are looked up on the first call and kept in a `static`
+                let (method_cache, call_method, call_static_method, new_object) = if cached_attribute.is_some() {
+                    (
+                        quote! { static METHOD: ::robusta_jni::convert::CachedMethod = ::robusta_jni::convert::CachedMethod::new(); },
+                        quote! { METHOD.call(env, #receiver, #java_class_path, #java_method_name, || #java_signature, #args) },
+                        quote! { METHOD.call_static(env, #java_class_path, #java_method_name, || #java_signature, #args) },
+                        quote! { METHOD.new_object(env, #java_class_path, || #java_signature, #args) },
+                    )
+                } else if let Some((_, dispatch)) = dispatch {
+                    // Constructors can't be dispatched, see above
+                    let toolkit = dispatch.toolkit();
+                    (
+                        TokenStream::new(),
+                        quote! { #toolkit.call(env, #receiver, #java_class_path, #java_method_name, &#java_signature, #args) },
+                        quote! { #toolkit.call_static(env, #java_class_path, #java_method_name, &#java_signature, #args) },
+                        quote! { env.new_object(#java_class_path, #java_signature, #args) },
+                    )
+                } else if suspend_attribute.is_some() {
+                    // Constructors can't be `suspend`, see above
+                    (
+                        TokenStream::new(),
+                        quote! { ::robusta_jni::coroutines::call(env, #receiver, #java_class_path, #java_method_name, &#java_signature, #args) },
+                        quote! { ::robusta_jni::coroutines::call_static(env, #java_class_path, #java_method_name, &#java_signature, #args) },
+                        quote! { env.new_object(#java_class_path, #java_signature, #args) },
+                    )
+                } else {
+                    (
+                        TokenStream::new(),
+                        quote! { env.call_method(#receiver, #java_method_name, #java_signature, #args) },
+                        quote! { env.call_static_method(#java_class_path, #java_method_name, #java_signature, #args) },
+                        quote! { env.new_object(#java_class_path, #java_signature, #args) },
+                    )
+                };
+                let (method_cache, call_method, call_static_method, new_object) = if retry.is_some() {
+                    (
+                        quote! { #method_cache #retry_setup },
+                        quote! { RETRY.call(&env, || #call_method) },
+                        quote! { RETRY.call(&env, || #call_static_method) },
+                        quote! { RETRY.call(&env, || #new_object) },
+                    )
+                } else {
+                    (method_cache, call_method, call_static_method, new_object)
+                };
+
+                let block: Block = if self_method {
+                    let self_span = node.sig.inputs.iter().next().unwrap().span();
+                    match call_type {
+                        CallType::Safe(_) => {
+                            parse_quote_spanned! { self_span => {
+                                let env: ::robusta_jni::jni::JNIEnv<'_> = #env_value;
+                                ::robusta_jni::__private::debug_assert_outside_critical_region();
+                                #verify_binding
+                                #method_cache
+                                let res = #call_method;
+                                #return_expr
+                            }}
+                        }
+                        CallType::Unchecked(_) => {
+                            parse_quote_spanned! { self_span => {
+                                let env: ::robusta_jni::jni::JNIEnv<'_> = #env_value;
+                                ::robusta_jni::__private::debug_assert_outside_critical_region();
+                                #verify_binding
+                                #method_cache
+                                let res = #call_method.unwrap();
+                                #return_expr
+                            }}
+                        }
+                    }
+                } else {
+                    match call_type {
+                        CallType::Safe(_) => {
+                            if is_constructor {
+                                parse_quote! {{
+                                    let env: ::robusta_jni::jni::JNIEnv<'_> = #env_value;
+                                    ::robusta_jni::__private::debug_assert_outside_critical_region();
+                                    #verify_binding
+                                    #method_cache
+                                    let res = #new_object;
+                                    #return_expr
+                                }}
+                            } else {
+                                parse_quote! {{
+                                    let env: ::robusta_jni::jni::JNIEnv<'_> = #env_value;
+                                    ::robusta_jni::__private::debug_assert_outside_critical_region();
+                                    #verify_binding
+                                    #method_cache
+                                    let res = #call_static_method;
+                                    #return_expr
+                                }}
+                            }
+                        }
+                        CallType::Unchecked(_) => {
+                            if is_constructor {
+                                parse_quote! {{
+                                    let env: ::robusta_jni::jni::JNIEnv<'_> = #env_value;
+                                    ::robusta_jni::__private::debug_assert_outside_critical_region();
+                                    #verify_binding
+                                    #method_cache
+                                    let res = #new_object.unwrap();
+                                    #return_expr
+                                }}
+                            } else {
+                                parse_quote! {{
+                                    let env: ::robusta_jni::jni::JNIEnv<'_> = #env_value;
+                                    ::robusta_jni::__private::debug_assert_outside_critical_region();
+                                    #verify_binding
+                                    #method_cache
+                                    let res = #call_static_method.unwrap();
+                                    #return_expr
+                                }}
+                            }
+                        }
+                    }
+                };
+
+                // With `#[attach]`, the environment is the one of the current thread, attached if needed
+                let block = match (attach_attribute, call_type) {
+                    (None, _) => block,
+                    (Some(_), CallType::Safe(_)) => parse_quote! {{
+                        ::robusta_jni::vm::with_attached_env(|env| #block)
+                    }},
+                    (Some(_), CallType::Unchecked(_)) => parse_quote! {{
+                        ::robusta_jni::vm::with_attached_env(|env| ::core::result::Result::Ok(#block)).unwrap()
+                    }},
+                };
+
+                ImplItemMethod {
+                    sig: Signature {
+                        abi: None,
+                        ..original_signature
+                    },
+                    block,
+                    attrs: impl_item_attributes,
+                    ..node
+                }
+            }
+
+            _ => node,
+        }
+    }
+}
+
+/// A `MethodMetadata` expression describing an imported method, which can be used in `const` items outside of the impl block:
+/// `Self` is spelled out as the struct type, and every lifetime is replaced with `'static`.
+pub(crate) fn method_metadata(
+    struct_type: &Path,
+    class: &str,
+    name: &str,
+    param_sig_types: &[TokenStream],
+    return_sig_type: Option<&TokenStream>,
+    is_static: bool,
+) -> TokenStream {
+    let params = static_sig_types(struct_type, param_sig_types);
+    let ret = match return_sig_type {
+        Some(r) => static_sig_types(struct_type, std::slice::from_ref(r)).remove(0).into_token_stream(),
+        None => quote! { "V" },
+    };
+
+    // A named constant, as slices of `const fn` calls (e.g. `parameter_signature`) aren't promoted to `'static`
+    quote! {
+        ::robusta_jni::metadata::MethodMetadata {
+            class: #class,
+            name: #name,
+            params: {
+                const PARAMS: &[&str] = &[#(#params),*];
+                PARAMS
+            },
+            ret: #ret,
+            is_static: #is_static,
+        }
+    }
+}
+
+/// The type signature expressions `sig_types`, with `Self` spelled out as the struct type and every lifetime replaced with
+/// `'static`, so that they can be used in `const` items.
+pub(crate) fn static_sig_types(struct_type: &Path, sig_types: &[TokenStream]) -> Vec<Expr> {
+    let mut fold = StaticTypes { struct_type };
+    sig_types
+        .iter()
+        .map(|tokens| {
+            let expr: Expr = syn::parse2(tokens.clone()).expect("signature expressions are valid expressions");
+            fold.fold_expr(expr)
+        })
+        .collect()
+}
+
+/// Replaces `Self` with the struct type, and every lifetime with `'static`.
+struct StaticTypes<'p> {
+    struct_type: &'p Path,
+}
+
+impl Fold for StaticTypes<'_> {
+    fn fold_type(&mut self, ty: Type) -> Type {
+        match ty {
+            Type::Path(TypePath { qself: None, path }) if path.is_ident("Self") => {
+                let struct_type = self.struct_type.clone();
+                syn::fold::fold_type(self, parse_quote! { #struct_type })
+            }
+            _ => syn::fold::fold_type(self, ty),
+        }
+    }
+
+    fn fold_lifetime(&mut self, lifetime: Lifetime) -> Lifetime {
+        Lifetime::new("'static", lifetime.span())
+    }
+}
+
+/// Body of a method annotated with `#[field]`: a getter if it only takes the receiver (for instance fields) and the environment,
+/// a setter if it also takes the new value. The field descriptor is the `Signature` of the value type.
+fn field_accessor_block(
+    attr: &Attribute,
+    field: &FieldAttribute,
+    signature: &Signature,
+    call_type: &CallType,
+    env_value: &TokenStream,
+    java_class_path: &str,
+) -> Option<Block> {
+    let is_static = field.is_static.is_some();
+    if is_static == is_self_method(signature) {
+        if is_static {
+            emit_error!(attr, "static field accessors can't have a `self` receiver");
+        } else {
+            emit_error!(attr, "instance field accessors must have a `self` receiver"; help = "use `#[field(static)]` for static fields");
+        }
+        return None;
+    }
+
+    let name = field.name.clone().unwrap_or_else(|| {
+        let method_name = signature.ident.to_string();
+        let field_name = method_name
+            .strip_prefix("get_")
+            .or_else(|| method_name.strip_prefix("set_"))
+            .unwrap_or(&method_name);
+
+        to_camel_case(field_name)
+    });
+
+    let is_safe = matches!(call_type, CallType::Safe(_));
+    let (conversion_trait, unwrap) = if is_safe {
+        (quote! { ::robusta_jni::convert::TryIntoJavaValue }, quote! {})
+    } else {
+        (quote! { ::robusta_jni::convert::IntoJavaValue }, quote! { .unwrap() })
+    };
+    let object = if is_safe {
+        quote! { ::robusta_jni::convert::JavaValue::autobox(::robusta_jni::convert::TryIntoJavaValue::try_into(self, env)?, env) }
+    } else {
+        quote! { ::robusta_jni::convert::JavaValue::autobox(::robusta_jni::convert::IntoJavaValue::into(self, env), env) }
+    };
+
+    let params: Vec<&PatType> = signature
+        .inputs
+        .iter()
+        .filter_map(|i| match i {
+            FnArg::Typed(t) => match &*t.pat {
+                Pat::Ident(PatIdent { ident, .. }) if ident == "self" => None,
+                _ => Some(t),
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+
+    match params.as_slice() {
+        [] => {
+            let ty = match &signature.output {
+                ReturnType::Type(_, ty) if is_safe => result_ok_type(ty),
+                ReturnType::Type(_, ty) => Some(&**ty),
+                ReturnType::Default => None,
+            };
+            let ty = match ty {
+                Some(ty) => ty,
+                None => {
+                    let expected = if is_safe { "`-> JniResult<T>`" } else { "`-> T`" };
+                    emit_error!(signature, "field getters must return the field value"; help = "use {}", expected);
+                    return None;
+                }
+            };
+
+            // Unsigned integers are read through the wrapper widening them from `long`
+            let wrapper = widened_type(ty);
+            let (ty, unwrap_wrapper) = match &wrapper {
+                Some(wrapper) if is_safe => (wrapper, quote! { .map(|v| v.0) }),
+                Some(wrapper) => (wrapper, quote! { .0 }),
+                None => (ty, quote! {}),
+            };
+
+            let get = if is_static {
+                quote_spanned! { ty.span() => env.get_static_field(#java_class_path, #name, <#ty as #conversion_trait>::SIG_TYPE)#unwrap }
+            } else {
+                quote_spanned! { ty.span() => env.get_field(#object, #name, <#ty as #conversion_trait>::SIG_TYPE)#unwrap }
+            };
+            let conversion = if is_safe {
+                quote_spanned! { ty.span() =>
+                    res.and_then(|v| ::core::convert::TryInto::try_into(::robusta_jni::convert::JValueWrapper::from(v)).map_err(::core::convert::Into::into))
+                       .and_then(|v| <#ty as ::robusta_jni::convert::TryFromJavaValue>::try_from(v, env))
+                       #unwrap_wrapper
+                }
+            } else {
+                quote_spanned! { ty.span() =>
+                    ::core::convert::TryInto::try_into(::robusta_jni::convert::JValueWrapper::from(res))
+                        .map_err(::core::convert::Into::<::robusta_jni::jni::errors::Error>::into)
+                        .map(|v| <#ty as ::robusta_jni::convert::FromJavaValue>::from(v, env) #unwrap_wrapper)
+                        .unwrap()
+                }
+            };
+
+            Some(parse_quote! {{
+                let env: ::robusta_jni::jni::JNIEnv<'_> = #env_value;
+                ::robusta_jni::__private::debug_assert_outside_critical_region();
+                let res = #get;
+                #conversion
+            }})
+        }
+        [value] => {
+            let PatType { pat, ty, .. } = value;
+            let (ty, pat) = match java_param_value(ty, parse_quote! { #pat }) {
+                Some((converted, value)) => (converted, value.into_token_stream()),
+                None => ((**ty).clone(), pat.into_token_stream()),
+            };
+            let value = if is_safe {
+                quote_spanned! { ty.span() => ::core::convert::Into::into(<#ty as ::robusta_jni::convert::TryIntoJavaValue>::try_into(#pat, env)?) }
+            } else {
+                quote_spanned! { ty.span() => ::core::convert::Into::into(<#ty as ::robusta_jni::convert::IntoJavaValue>::into(#pat, env)) }
+            };
+
+            let set = if is_static {
+                quote_spanned! { ty.span() =>
+                    env.set_static_field(#java_class_path, (#java_class_path, #name, <#ty as #conversion_trait>::SIG_TYPE), #value)#unwrap
+                }
+            } else {
+                quote_spanned! { ty.span() => env.set_field(#object, #name, <#ty as #conversion_trait>::SIG_TYPE, #value)#unwrap }
+            };
+
+            Some(parse_quote! {{
+                let env: ::robusta_jni::jni::JNIEnv<'_> = #env_value;
+                ::robusta_jni::__private::debug_assert_outside_critical_region();
+                #set
+            }})
+        }
+        _ => {
+            emit_error!(signature.inputs, "field setters must take a single value besides the environment");
+            None
+        }
+    }
+}
+
+/// The type `value` of type `ty` is passed to Java as, and the expression converting it, if it isn't passed as is: borrowed
+/// strings are passed as `String`s, and unsigned integers through the wrapper widening them to `long`.
+fn java_param_value(ty: &Type, value: Expr) -> Option<(Type, Expr)> {
+    owned_string_value(ty, value.clone()).or_else(|| {
+        let wrapper = widened_type(ty)?;
+        let constructor = widen_wrapper();
+        Some((wrapper, parse_quote! { #constructor(#value) }))
+    })
+}
+
+/// `T` in `Result<T>`, `JniResult<T>` and similar.
+fn result_ok_type(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Path(TypePath { path, .. }) => match &path.segments.last()?.arguments {
+            PathArguments::AngleBracketed(a) => match a.args.first()? {
+                GenericArgument::Type(t) => Some(t),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether a `#[returns_self]` method can return `ty`: the struct itself, wrapped in a `Result` for safe calls.
+fn is_self_return_type(ty: &Type, struct_name: &str, is_safe: bool) -> bool {
+    let segment = match ty {
+        Type::Path(TypePath { path, .. }) => path.segments.last(),
+        _ => None,
+    };
+
+    match segment {
+        Some(s) if is_safe => {
+            (s.ident == "Result" || s.ident == "JniResult")
+                && matches!(&s.arguments, PathArguments::AngleBracketed(a) if matches!(
+                    a.args.first(),
+                    Some(GenericArgument::Type(t)) if is_self_return_type(t, struct_name, false)
+                ))
+        }
+        Some(s) => s.ident == "Self" || struct_name.split('<').next() == Some(s.ident.to_string().as_str()),
+        None => false,
+    }
+}
+
+/// Whether a constructor can return `ty`: the struct itself or a `JObject`, wrapped in a `Result` for safe calls.
+fn is_constructor_return_type(ty: &Type, struct_name: &str, is_safe: bool) -> bool {
+    let segment = match ty {
+        Type::Path(TypePath { path, .. }) => path.segments.last(),
+        _ => None,
+    };
+
+    match segment {
+        Some(s) if is_safe => {
+            (s.ident == "Result" || s.ident == "JniResult")
+                && matches!(&s.arguments, PathArguments::AngleBracketed(a) if matches!(
+                    a.args.first(),
+                    Some(GenericArgument::Type(t)) if is_constructor_return_type(t, struct_name, false)
+                ))
+        }
+        Some(s) => s.ident == "Self" || s.ident == "JObject" || struct_name.split('<').next() == Some(s.ident.to_string().as_str()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use syn::parse_quote;
+
+    use super::*;
+    use crate::transformation::JavaPath;
+    use crate::validation::FlattenedStructs;
+
+    #[test]
+    fn constructors_return_the_constructed_object() {
+        let returns = |ty: Type, is_safe| is_constructor_return_type(&ty, "Foo", is_safe);
+
+        assert!(returns(parse_quote! { JniResult<Self> }, true));
+        assert!(returns(parse_quote! { ::robusta_jni::jni::errors::Result<Foo<'env>> }, true));
+        assert!(returns(parse_quote! { Result<JObject<'env>> }, true));
+        assert!(returns(parse_quote! { Self }, false));
+        assert!(!returns(parse_quote! { Self }, true));
+        assert!(!returns(parse_quote! { JniResult<i32> }, true));
+        assert!(!returns(parse_quote! { JniResult<Self> }, false));
+        assert!(!returns(parse_quote! { Bar }, false));
+    }
+
+    #[test]
+    fn returns_self_methods_return_the_struct() {
+        let returns = |ty: Type, is_safe| is_self_return_type(&ty, "Foo", is_safe);
+
+        assert!(returns(parse_quote! { JniResult<Self> }, true));
+        assert!(returns(parse_quote! { Foo<'env> }, false));
+        assert!(!returns(parse_quote! { JniResult<JObject<'env>> }, true));
+        assert!(!returns(parse_quote! { Self }, true));
+    }
+
+    #[test]
+    fn metadata_types_are_static() {
+        let struct_type: Path = parse_quote! { Foo<'env> };
+        let params = [
+            quote! { <Self as ::robusta_jni::convert::TryIntoJavaValue>::SIG_TYPE },
+            quote! { <&'a [&str] as ::robusta_jni::convert::TryIntoJavaValue>::SIG_TYPE },
+        ];
+        let metadata = method_metadata(&struct_type, "com/example/Foo", "bar", &params, None, true).to_string();
+
+        assert!(metadata.contains("< Foo < 'static > as :: robusta_jni :: convert :: TryIntoJavaValue > :: SIG_TYPE"));
+        assert!(metadata.contains("< & 'static [& str] as"));
+        assert!(metadata.contains("ret : \"V\""));
+    }
+
+    #[test]
+    fn static_methods_call_their_class() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Integer<'env> },
+            struct_name: "Integer".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: Some(JavaPath::from_str("java.lang").unwrap()),
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ImportedMethodTransformer {
+            struct_context: &struct_context,
+            metadata: vec![],
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! {
+            #[static_method]
+            pub extern "java" fn value_of(env: JNIEnv<'env>, value: i32) -> JniResult<Self> {}
+        });
+        assert!(output.attrs.is_empty());
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.contains(r#"env . call_static_method ("java/lang/Integer" , "valueOf" ,"#));
+        assert!(block.contains(":: robusta_jni :: convert :: JValueWrapper :: from (v)"));
+
+        // The return type only needs to be converted from Java
+        let metadata = transformer.metadata[0].to_string();
+        assert!(metadata.contains("ret : < Integer < 'static > as :: robusta_jni :: convert :: TryFromJavaValue > :: SIG_TYPE"));
+        assert!(metadata.contains("is_static : true"));
+
+        // The same metadata is looked up before the call with `verify-bindings`
+        assert!(block.contains(&format!(":: robusta_jni :: __private :: verify_binding ! (env , {}) ;", metadata)));
+    }
+
+    #[test]
+    fn returns_self_methods_return_their_receiver() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Builder<'env> },
+            struct_name: "Builder".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: Some(JavaPath::from_str("com.bar").unwrap()),
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ImportedMethodTransformer {
+            struct_context: &struct_context,
+            metadata: vec![],
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! {
+            #[returns_self]
+            pub extern "java" fn width(self, env: JNIEnv<'env>, width: i32) -> JniResult<Self> {}
+        });
+        assert!(output.attrs.is_empty());
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.contains(":: robusta_jni :: convert :: TryIntoJavaValue :: try_into (& self , env)"));
+        assert!(block.contains(". map (| () | self)"));
+        assert!(!block.contains("TryFromJavaValue"));
+
+        // The descriptor returns the class, whose objects aren't converted
+        let metadata = transformer.metadata[0].to_string();
+        assert!(metadata.contains("ret : < Builder < 'static > as :: robusta_jni :: convert :: Signature > :: SIG_TYPE"));
+    }
+
+    #[test]
+    fn suspend_methods_take_a_continuation() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Foo<'env> },
+            struct_name: "Foo".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: Some(JavaPath::from_str("com.bar").unwrap()),
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ImportedMethodTransformer {
+            struct_context: &struct_context,
+            metadata: vec![],
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! {
+            #[suspend]
+            pub extern "java" fn fetch(&self, env: JNIEnv<'env>, url: String) -> JniResult<String> {}
+        });
+        assert!(output.attrs.is_empty());
+        assert!(output.block.to_token_stream().to_string().contains(":: robusta_jni :: coroutines :: call (env"));
+
+        // The descriptor used for the call is the Kotlin one, the metadata describes the compiled JVM method
+        let metadata = transformer.metadata[0].to_string();
+        assert!(metadata.contains(r#"TryIntoJavaValue > :: SIG_TYPE , "Lkotlin/coroutines/Continuation;"]"#));
+        assert!(metadata.contains(r#"ret : "Ljava/lang/Object;""#));
+    }
+
+    #[test]
+    fn attached_methods_take_no_env() {
+        let struct_context = StructContext {
+            struct_type: parse_quote! { Integer },
+            struct_name: "Integer".into(),
+            struct_lifetimes: vec![],
+            struct_type_params: vec![],
+            package: Some(JavaPath::from_str("java.lang").unwrap()),
+            flattened_structs: FlattenedStructs::new(),
+            rename_all: None,
+            instantiation: None,
+        };
+        let mut transformer = ImportedMethodTransformer {
+            struct_context: &struct_context,
+            metadata: vec![],
+        };
+
+        let output = transformer.fold_impl_item_method(parse_quote! {
+            #[attach]
+            pub extern "java" fn to_hex_string(value: i32) -> JniResult<String> {}
+        });
+        assert!(output.attrs.is_empty());
+        assert_eq!(output.sig.inputs.len(), 1);
+
+        let block = output.block.to_token_stream().to_string();
+        assert!(block.starts_with("{ :: robusta_jni :: vm :: with_attached_env (| env | {"));
+        assert!(block.contains(r#"env . call_static_method ("java/lang/Integer" , "toHexString" ,"#));
+    }
+}
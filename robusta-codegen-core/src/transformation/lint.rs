@@ -0,0 +1,316 @@
+//! Opt-in lint for raw `jni` calls in the bodies of a bridge module, enabled with `#[bridge(lint_raw_jni)]`.
+//!
+//! Modules migrated incrementally tend to keep direct `JNIEnv` calls (e.g. `env.call_method(...)`), which bypass robusta's
+//! conversions and exception handling. The lint flags calls to the `JNIEnv` methods of [`RAW_JNI_METHODS`] by name, suggesting
+//! the robusta equivalent. Calls that are meant to stay can be allowed with `#[allow_raw_jni]` on the expression, the statement,
+//! the function or the `impl` block containing them; the attribute is removed from the expanded module whether the lint is enabled or not.
+
+use darling::FromMeta;
+use proc_macro2::Ident;
+use proc_macro_error::{emit_error, emit_warning};
+use syn::fold::{self, Fold};
+use syn::visit::{self, Visit};
+use syn::{Attribute, Expr, ExprMethodCall, ImplItemMethod, ItemFn, ItemImpl, ItemMod, Local};
+
+const ALLOW_ATTRIBUTE: &str = "allow_raw_jni";
+
+/// `JNIEnv` methods with a robusta equivalent, with the suggestion for each group.
+const RAW_JNI_METHODS: &[(&[&str], &str)] = &[
+    (
+        &["call_method", "call_method_unchecked", "call_static_method", "call_static_method_unchecked"],
+        "declare the Java method as an `extern \"java\"` method of a bridged struct",
+    ),
+    (
+        &["new_object", "new_object_unchecked"],
+        "declare the constructor as an `extern \"java\"` method with `#[constructor]`",
+    ),
+    (
+        &["get_field", "get_field_unchecked", "set_field", "set_field_unchecked", "get_static_field", "set_static_field"],
+        "declare a field accessor, as an `extern \"java\"` method with `#[field]`",
+    ),
+    (
+        &["new_string", "get_string", "get_string_utf_chars"],
+        "take or return a `String` (or a `&str`), which robusta converts",
+    ),
+    (
+        &["new_object_array", "get_object_array_element", "set_object_array_element", "get_array_length", "byte_array_from_slice", "convert_byte_array"],
+        "take or return a `Vec<T>` (converted to an `ArrayList`) or a boxed slice of primitives (converted to an array)",
+    ),
+    (
+        &["throw", "throw_new"],
+        "return a `Result` from the exported method, whose `Err` is thrown as the exception of `#[call_type(safe(exception_class = ...))]`",
+    ),
+    (
+        &["find_class"],
+        "list the class in `#[bridge(preload_classes(...))]` and get it with `robusta_jni::preload::preloaded_class`",
+    ),
+];
+
+/// Level of `#[bridge(lint_raw_jni)]`: warnings by default, errors with `lint_raw_jni = "deny"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LintLevel {
+    Warn,
+    Deny,
+}
+
+impl FromMeta for LintLevel {
+    fn from_word() -> darling::Result<Self> {
+        Ok(LintLevel::Warn)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "warn" => Ok(LintLevel::Warn),
+            "deny" => Ok(LintLevel::Deny),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+/// A call to one of the [`RAW_JNI_METHODS`].
+#[derive(Debug)]
+pub(crate) struct RawJniCall {
+    pub(crate) method: Ident,
+    pub(crate) suggestion: &'static str,
+}
+
+/// Attributes of the expression, for each kind of expression that can have some.
+macro_rules! expr_attrs {
+    ($expr:expr => $($borrow:tt)+) => {
+        match $expr {
+            Expr::Array(e) => Some($($borrow)+ e.attrs),
+            Expr::Assign(e) => Some($($borrow)+ e.attrs),
+            Expr::AssignOp(e) => Some($($borrow)+ e.attrs),
+            Expr::Async(e) => Some($($borrow)+ e.attrs),
+            Expr::Await(e) => Some($($borrow)+ e.attrs),
+            Expr::Binary(e) => Some($($borrow)+ e.attrs),
+            Expr::Block(e) => Some($($borrow)+ e.attrs),
+            Expr::Box(e) => Some($($borrow)+ e.attrs),
+            Expr::Break(e) => Some($($borrow)+ e.attrs),
+            Expr::Call(e) => Some($($borrow)+ e.attrs),
+            Expr::Cast(e) => Some($($borrow)+ e.attrs),
+            Expr::Closure(e) => Some($($borrow)+ e.attrs),
+            Expr::Continue(e) => Some($($borrow)+ e.attrs),
+            Expr::Field(e) => Some($($borrow)+ e.attrs),
+            Expr::ForLoop(e) => Some($($borrow)+ e.attrs),
+            Expr::Group(e) => Some($($borrow)+ e.attrs),
+            Expr::If(e) => Some($($borrow)+ e.attrs),
+            Expr::Index(e) => Some($($borrow)+ e.attrs),
+            Expr::Let(e) => Some($($borrow)+ e.attrs),
+            Expr::Lit(e) => Some($($borrow)+ e.attrs),
+            Expr::Loop(e) => Some($($borrow)+ e.attrs),
+            Expr::Macro(e) => Some($($borrow)+ e.attrs),
+            Expr::Match(e) => Some($($borrow)+ e.attrs),
+            Expr::MethodCall(e) => Some($($borrow)+ e.attrs),
+            Expr::Paren(e) => Some($($borrow)+ e.attrs),
+            Expr::Path(e) => Some($($borrow)+ e.attrs),
+            Expr::Range(e) => Some($($borrow)+ e.attrs),
+            Expr::Reference(e) => Some($($borrow)+ e.attrs),
+            Expr::Repeat(e) => Some($($borrow)+ e.attrs),
+            Expr::Return(e) => Some($($borrow)+ e.attrs),
+            Expr::Struct(e) => Some($($borrow)+ e.attrs),
+            Expr::Try(e) => Some($($borrow)+ e.attrs),
+            Expr::TryBlock(e) => Some($($borrow)+ e.attrs),
+            Expr::Tuple(e) => Some($($borrow)+ e.attrs),
+            Expr::Type(e) => Some($($borrow)+ e.attrs),
+            Expr::Unary(e) => Some($($borrow)+ e.attrs),
+            Expr::Unsafe(e) => Some($($borrow)+ e.attrs),
+            Expr::While(e) => Some($($borrow)+ e.attrs),
+            Expr::Yield(e) => Some($($borrow)+ e.attrs),
+            _ => None,
+        }
+    };
+}
+
+fn is_allowed(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|a| a.path.is_ident(ALLOW_ATTRIBUTE))
+}
+
+#[derive(Default)]
+struct RawJniCallCollector {
+    calls: Vec<RawJniCall>,
+}
+
+impl<'ast> Visit<'ast> for RawJniCallCollector {
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        if !is_allowed(&node.attrs) {
+            visit::visit_item_impl(self, node);
+        }
+    }
+
+    fn visit_impl_item_method(&mut self, node: &'ast ImplItemMethod) {
+        if !is_allowed(&node.attrs) {
+            visit::visit_impl_item_method(self, node);
+        }
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        if !is_allowed(&node.attrs) {
+            visit::visit_item_fn(self, node);
+        }
+    }
+
+    fn visit_local(&mut self, node: &'ast Local) {
+        if !is_allowed(&node.attrs) {
+            visit::visit_local(self, node);
+        }
+    }
+
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        if !expr_attrs!(node => &).map_or(false, |attrs| is_allowed(attrs)) {
+            visit::visit_expr(self, node);
+        }
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        let method = node.method.to_string();
+        if let Some((_, suggestion)) = RAW_JNI_METHODS.iter().find(|(methods, _)| methods.contains(&method.as_str())) {
+            self.calls.push(RawJniCall {
+                method: node.method.clone(),
+                suggestion,
+            });
+        }
+
+        visit::visit_expr_method_call(self, node);
+    }
+}
+
+/// Calls to raw `JNIEnv` methods in the module, in source order, leaving out allowed ones.
+pub(crate) fn raw_jni_calls(module: &ItemMod) -> Vec<RawJniCall> {
+    let mut collector = RawJniCallCollector::default();
+    collector.visit_item_mod(module);
+    collector.calls
+}
+
+/// Reports each of the [`raw_jni_calls`] of the module at the given level.
+pub(crate) fn lint_raw_jni_calls(module: &ItemMod, level: LintLevel) {
+    for call in raw_jni_calls(module) {
+        let message = format!("raw JNI call to `{}` bypasses robusta's conversions and exception handling", call.method);
+        let note = "add `#[allow_raw_jni]` to the expression or its function to keep this call";
+
+        match level {
+            LintLevel::Warn => emit_warning!(call.method, message; help = call.suggestion; note = note),
+            LintLevel::Deny => emit_error!(call.method, message; help = call.suggestion; note = note),
+        }
+    }
+}
+
+/// Removes every `#[allow_raw_jni]` attribute, which isn't known to the compiler.
+pub(crate) struct AllowAttributeCleaner;
+
+impl Fold for AllowAttributeCleaner {
+    fn fold_item_impl(&mut self, mut node: ItemImpl) -> ItemImpl {
+        node.attrs.retain(|a| !a.path.is_ident(ALLOW_ATTRIBUTE));
+        fold::fold_item_impl(self, node)
+    }
+
+    fn fold_impl_item_method(&mut self, mut node: ImplItemMethod) -> ImplItemMethod {
+        node.attrs.retain(|a| !a.path.is_ident(ALLOW_ATTRIBUTE));
+        fold::fold_impl_item_method(self, node)
+    }
+
+    fn fold_item_fn(&mut self, mut node: ItemFn) -> ItemFn {
+        node.attrs.retain(|a| !a.path.is_ident(ALLOW_ATTRIBUTE));
+        fold::fold_item_fn(self, node)
+    }
+
+    fn fold_local(&mut self, mut node: Local) -> Local {
+        node.attrs.retain(|a| !a.path.is_ident(ALLOW_ATTRIBUTE));
+        fold::fold_local(self, node)
+    }
+
+    fn fold_expr(&mut self, mut node: Expr) -> Expr {
+        if let Some(attrs) = expr_attrs!(&mut node => &mut) {
+            attrs.retain(|a| !a.path.is_ident(ALLOW_ATTRIBUTE));
+        }
+        fold::fold_expr(self, node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::{quote, ToTokens};
+    use syn::parse_quote;
+
+    use super::*;
+    use crate::transformation::BridgeOptions;
+
+    fn module() -> ItemMod {
+        parse_quote! {
+            mod jni {
+                impl Foo {
+                    pub extern "jni" fn greet(env: JNIEnv, name: String) -> JniResult<()> {
+                        let greeting = env.new_string(format!("Hello, {}", name))?;
+                        #[allow_raw_jni]
+                        env.call_static_method("com/example/Log", "info", "(Ljava/lang/String;)V", &[greeting.into()])?;
+                        let class = #[allow_raw_jni] env.find_class("com/example/Log")?;
+                        env.throw_new("java/lang/IllegalStateException", "unreachable")
+                    }
+
+                    #[allow_raw_jni]
+                    pub extern "jni" fn allowed(env: JNIEnv) {
+                        env.call_method(JObject::null(), "run", "()V", &[]).unwrap();
+                    }
+                }
+
+                #[allow_raw_jni]
+                impl Bar {
+                    fn helper(env: JNIEnv) {
+                        env.get_field(JObject::null(), "count", "I").unwrap();
+                    }
+                }
+
+                fn free(env: JNIEnv, values: Vec<i32>) -> usize {
+                    #[allow_raw_jni]
+                    let array = env.new_object_array(0, "java/lang/Object", JObject::null()).unwrap();
+                    values.len()
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn raw_calls_have_suggestions() {
+        let calls: Vec<_> = raw_jni_calls(&module())
+            .into_iter()
+            .map(|c| (c.method.to_string(), c.suggestion))
+            .collect();
+
+        assert_eq!(
+            calls,
+            [
+                ("new_string".to_string(), "take or return a `String` (or a `&str`), which robusta converts"),
+                (
+                    "throw_new".to_string(),
+                    "return a `Result` from the exported method, whose `Err` is thrown as the exception of `#[call_type(safe(exception_class = ...))]`"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_methods_are_not_raw_calls() {
+        let module: ItemMod = parse_quote! {
+            mod jni {
+                fn free(values: Vec<i32>) -> Option<i32> {
+                    values.iter().copied().find(|v| *v > 0)
+                }
+            }
+        };
+
+        assert!(raw_jni_calls(&module).is_empty());
+    }
+
+    #[test]
+    fn allow_attributes_are_removed() {
+        let module = AllowAttributeCleaner.fold_item_mod(module());
+        assert!(!module.to_token_stream().to_string().contains("allow_raw_jni"));
+    }
+
+    #[test]
+    fn lint_level_defaults_to_warnings() {
+        assert_eq!(BridgeOptions::parse(quote! { lint_raw_jni }).lint_raw_jni, Some(LintLevel::Warn));
+        assert_eq!(BridgeOptions::parse(quote! { lint_raw_jni = "deny" }).lint_raw_jni, Some(LintLevel::Deny));
+        assert_eq!(BridgeOptions::parse(quote! {}).lint_raw_jni, None);
+    }
+}
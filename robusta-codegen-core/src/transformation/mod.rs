@@ -0,0 +1,1668 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use darling::FromMeta;
+use darling::util::Flag;
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use proc_macro_error::{emit_error, Diagnostic, Level};
+use quote::ToTokens;
+use syn::{Attribute, FnArg, GenericArgument, GenericParam, ImplItemMethod, Item, ItemImpl, ItemMod, ItemStruct, Lit, parse_quote, Pat, Path, PathArguments, PatIdent, PatType, Type, TypePath, TypeReference, Visibility, PathSegment};
+use syn::{Error, Expr, ImplItem, Meta, NestedMeta, Token};
+use syn::fold::Fold;
+use syn::parse::{Parse, Parser, ParseStream, ParseBuffer};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+use imported::ImportedMethodTransformer;
+
+use crate::transformation::exported::ExportedMethodTransformer;
+use crate::stubs::{for_each_impl, ClassNames};
+use crate::utils::{get_abi, path_name, struct_name};
+use crate::transformation::utils::{bridged_struct_type, get_call_type, get_hidden_export, instantiated_method, rebase_crate_paths};
+use crate::validation::{bridged_structs_note, JNIBridgeModule};
+use crate::transformation::context::StructContext;
+use crate::transformation::lint::{lint_raw_jni_calls, AllowAttributeCleaner, LintLevel};
+use crate::transformation::utils::{is_bridge_attribute, AttributeFilter};
+use std::fmt;
+
+#[macro_use]
+pub(crate) mod utils;
+pub(crate) mod dry_run;
+mod exported;
+mod imported;
+mod lint;
+pub(crate) mod context;
+
+#[derive(Copy, Clone)]
+pub(crate) enum ImplItemType {
+    Exported,
+    Imported,
+    Unexported,
+    /// Reported with an error, and left out of the output so that rustc doesn't add a less helpful one.
+    Unsupported,
+}
+
+/// Rewrites the body of an exported method's JNI wrapper, given the struct and the method being exported.
+pub(crate) type BodyWrapper<'w> = dyn FnMut(&StructContext, &ImplItemMethod, TokenStream) -> TokenStream + 'w;
+
+pub(crate) struct ModTransformer<'w> {
+    module: JNIBridgeModule,
+    options: BridgeOptions,
+    body_wrapper: Option<Box<BodyWrapper<'w>>>,
+    bridge_macros: BTreeSet<String>,
+    /// Call type set by a `#[call_type]` attribute on the module, for exported methods.
+    call_type: Option<CallType>,
+    /// A `MethodMetadata` expression for each imported method of the module, for `bridge_metadata()`.
+    imported_metadata: Vec<TokenStream>,
+    /// A `NativeMethod` expression for each exported method of the module, with `register_natives`.
+    natives: Vec<TokenStream>,
+    /// JNI symbols of the exported methods of the impl blocks transformed so far.
+    exported_symbols: BTreeSet<String>,
+}
+
+impl<'w> ModTransformer<'w> {
+    pub(crate) fn new(module: JNIBridgeModule) -> Self {
+        let bridge_macros = module
+            .module_decl
+            .content
+            .as_ref()
+            .map(|(_, items)| bridge_macros(items))
+            .unwrap_or_default();
+
+        ModTransformer {
+            module,
+            options: BridgeOptions::default(),
+            body_wrapper: None,
+            bridge_macros,
+            call_type: None,
+            imported_metadata: Vec::new(),
+            natives: Vec::new(),
+            exported_symbols: BTreeSet::new(),
+        }
+    }
+
+    pub(crate) fn with_body_wrapper(mut self, body_wrapper: Box<BodyWrapper<'w>>) -> Self {
+        self.body_wrapper = Some(body_wrapper);
+        self
+    }
+
+    pub(crate) fn with_options(mut self, options: BridgeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub(crate) fn transform_module(&mut self) -> TokenStream {
+        let module_decl = self.module.module_decl.clone();
+        if let Some(level) = self.options.lint_raw_jni {
+            lint_raw_jni_calls(&module_decl, level);
+        }
+        check_duplicate_exports(&self.module);
+        check_parameter_slots(&self.module);
+
+        let mut module_decl = AllowAttributeCleaner.fold_item_mod(module_decl);
+        self.call_type = get_call_type(&module_decl.attrs).map(|c| c.call_type);
+        module_decl.attrs.retain(|a| !a.path.is_ident("call_type"));
+
+        let preload_items = self.options.preload_items(&module_decl);
+
+        let mut module = self.fold_item_mod(module_decl);
+        let on_load_items = self.options.on_load_items(&module, &self.natives);
+        if let Some((_, items)) = &mut module.content {
+            items.extend(preload_items);
+            items.extend(on_load_items);
+            if self.options.debug_signatures.is_some() {
+                items.extend(signature_warnings(&self.module));
+            }
+
+            if !self.imported_metadata.is_empty() {
+                let metadata = &self.imported_metadata;
+                items.push(parse_quote! {
+                    /// Class, name and JNI descriptor of each imported method of this module.
+                    pub fn bridge_metadata() -> &'static [::robusta_jni::metadata::MethodMetadata] {
+                        const METHODS: &[::robusta_jni::metadata::MethodMetadata] = &[#(#metadata),*];
+                        METHODS
+                    }
+                });
+                // Makes the methods visible to `verify_bindings`, when `robusta_jni` is built with `verify-bindings`
+                items.push(parse_quote! {
+                    ::robusta_jni::__private::register_bindings!(bridge_metadata);
+                });
+            }
+        }
+
+        // Generated paths name `::robusta_jni`, unless the crate is used under another path
+        match &self.options.crate_path {
+            Some(crate_path) => rebase_crate_paths(module.into_token_stream(), crate_path),
+            None => module.into_token_stream(),
+        }
+    }
+
+    /// If the impl block is a standard impl block for a type, makes every exported fn a freestanding one
+    fn transform_item_impl(&mut self, node: ItemImpl) -> TokenStream {
+        let mut impl_export_visitor = ImplExportVisitor {
+            bridge_macros: self.bridge_macros.clone(),
+            ..Default::default()
+        };
+        impl_export_visitor.visit_item_impl(&node);
+
+        let (preserved_items, transformed_items) = if let Type::Path(p) = &*node.self_ty {
+            // Paths to other modules are reported by their last segment, the struct that's missing from this module
+            let struct_name = struct_name(&p.path, &self.module.module_decl.ident)
+                .or_else(|| p.path.segments.last().map(|s| s.ident.to_string()))
+                .unwrap_or_default();
+            let struct_package = self.module.package_map.get(&struct_name).cloned().flatten();
+
+            // Structs with methods but no `package` attribute have been reported when validating the module
+            if struct_package.is_none() && self.module.unattributed_structs.contains(&struct_name) {
+                return node.to_token_stream();
+            } else if struct_package.is_none() {
+                // No bindings are generated for the impl block, which would only show up as an `UnsatisfiedLinkError` at runtime
+                emit_error!(p.path, "can't find package for struct `{}`", struct_name;
+                    note = "{}", bridged_structs_note(self.module.package_map.keys());
+                    help = "declare `{}` in this module, with a `#[package(com.example)]` attribute", struct_name);
+                return node.to_token_stream();
+            }
+
+            let path_lifetimes: BTreeSet<String> = p.path.segments.iter().filter_map(|s: &PathSegment| {
+                if let PathArguments::AngleBracketed(a) = &s.arguments {
+                    Some(a.args.iter().filter_map(|g| {
+                        match g {
+                            GenericArgument::Lifetime(l) => Some(l.ident.to_string()),
+                            _ => None
+                        }
+                    }))
+                } else {
+                    None
+                }
+            }).flatten().collect();
+
+            let struct_lifetimes: Vec<_> = node.generics.params.iter().filter_map(|p| {
+                match p {
+                    GenericParam::Lifetime(l) if path_lifetimes.contains(&l.lifetime.ident.to_string()) => {
+                        Some(l.clone())
+                    }
+                    _ => None
+                }
+            })
+                .collect();
+
+            // Native functions can't be generic, so they use the struct with the default of each type parameter, or `()`
+            let struct_defaults: Vec<Option<Type>> = self.module.module_decl.content.iter()
+                .flat_map(|(_, items)| items)
+                .find_map(|i| match i {
+                    Item::Struct(s) if s.ident == struct_name => Some(s.generics.type_params().map(|t| t.default.clone()).collect()),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            let path_types: Vec<&Type> = match p.path.segments.last().map(|s| &s.arguments) {
+                Some(PathArguments::AngleBracketed(a)) => a.args.iter().filter_map(|g| match g {
+                    GenericArgument::Type(t) => Some(t),
+                    _ => None,
+                }).collect(),
+                _ => Vec::new(),
+            };
+
+            let struct_type_params: Vec<_> = node.generics.type_params().filter_map(|t| {
+                let position = path_types.iter().position(|ty| matches!(ty, Type::Path(p) if p.qself.is_none() && p.path.is_ident(&t.ident)))?;
+                let bridged_type = struct_defaults.get(position).cloned().flatten()
+                    .unwrap_or_else(|| parse_quote_spanned! { t.ident.span() => () });
+
+                Some((t.ident.clone(), bridged_type))
+            })
+                .collect();
+
+            let context = StructContext {
+                struct_type: p.path.clone(),
+                struct_name,
+                struct_lifetimes,
+                struct_type_params,
+                package: struct_package,
+                flattened_structs: self.module.flattened_structs.clone(),
+                rename_all: self.module.rename_all,
+                instantiation: None,
+            };
+
+            // Each instantiation matching the impl block is a Java class of its own, with its own native functions
+            let contexts: Vec<StructContext> = match self.module.instantiations.get(&context.struct_name) {
+                None => vec![context],
+                Some(instantiations) => {
+                    let matched: Vec<_> = instantiations.iter().filter_map(|i| Some((i, i.bindings(&node)?))).collect();
+                    if matched.is_empty() {
+                        let classes: Vec<_> = instantiations.iter()
+                            .map(|i| format!("`{}` for `{}`", i.class_name(&context.struct_name), path_name(&i.ty)))
+                            .collect();
+                        emit_error!(p.path, "`{}` isn't an instantiation of `{}`", path_name(&p.path), context.struct_name;
+                            note = "the Java classes of `{}` are {}", context.struct_name, classes.join(", ");
+                            help = "add it to the `#[instantiate]` attribute of `{}`, or implement the methods for one of its instantiations", context.struct_name);
+                        return node.to_token_stream();
+                    }
+
+                    // Imported methods call a single class, so they can't be shared by instantiations
+                    if matched.iter().any(|(_, bindings)| !bindings.is_empty()) {
+                        for (item, item_type) in impl_export_visitor.items.iter_mut() {
+                            if let (ImplItem::Method(m), ImplItemType::Imported) = (item, &item_type) {
+                                emit_error!(m.sig.ident, "imported method `{}` can't be declared for every instantiation of `{}`", m.sig.ident, context.struct_name;
+                                    note = "each instantiation is a Java class of its own";
+                                    help = "move it to an `impl` block for one instantiation, such as `{}`", path_name(&instantiations[0].ty));
+                                *item_type = ImplItemType::Unsupported;
+                            }
+                        }
+                    }
+
+                    matched
+                        .into_iter()
+                        .map(|(instantiation, bindings)| StructContext {
+                            struct_type: bridged_struct_type(&context.struct_type, &bindings),
+                            struct_type_params: bindings,
+                            instantiation: Some(instantiation.suffix.clone()),
+                            ..context.clone()
+                        })
+                        .collect()
+                }
+            };
+
+            let mut imported_fns_transformer = ImportedMethodTransformer {
+                struct_context: &contexts[0],
+                metadata: Vec::new(),
+            };
+            let attribute_filter = AttributeFilter::new(self.options.wrapper_attributes.as_ref().map_or(&[], |a| &a.0));
+            let mut impl_cleaner = ImplCleaner {
+                attribute_filter: &attribute_filter,
+            };
+
+            let preserved = impl_export_visitor
+                .items
+                .iter()
+                .map(|(i, t)| {
+                    let item = (*i).clone();
+                    match t {
+                        ImplItemType::Exported => impl_cleaner.fold_impl_item(item),
+                        ImplItemType::Imported => imported_fns_transformer
+                            .fold_impl_item(impl_cleaner.fold_impl_item(item)),
+                        ImplItemType::Unexported => item,
+                        ImplItemType::Unsupported => ImplItem::Verbatim(TokenStream::new()),
+                    }
+                })
+                .collect();
+
+            let exported: Vec<&ImplItem> = impl_export_visitor
+                .items
+                .iter()
+                .filter_map(|(i, t)| match t {
+                    ImplItemType::Exported => Some(*i),
+                    _ => None,
+                })
+                .collect();
+
+            let hidden = match get_hidden_export(&node.attrs) {
+                Some(attr) if self.options.register_natives.is_none() => {
+                    emit_error!(attr, "methods can only be hidden when they're registered with `RegisterNatives`";
+                        note = "the JVM looks up the native methods of other modules by their exported symbol";
+                        help = "register the methods of the module with `#[bridge(register_natives)]`");
+                    false
+                }
+                hidden => hidden.is_some(),
+            };
+
+            let mut transformed = Vec::new();
+            let mut symbols = BTreeMap::new();
+            for context in &contexts {
+                // Instantiations export the methods with the types of their instantiation in their signature
+                let items: Vec<(ImplItem, ImplItemType)> = exported
+                    .iter()
+                    .map(|i| match (i, &context.instantiation) {
+                        (ImplItem::Method(m), Some(_)) => {
+                            ImplItem::Method(instantiated_method(m, &context.struct_type_params, &context.struct_type))
+                        }
+                        _ => (*i).clone(),
+                    })
+                    .map(|i| (i, ImplItemType::Exported))
+                    .collect();
+                let item_refs: Vec<_> = items.iter().map(|(i, t)| (i, *t)).collect();
+
+                let mut exported_fns_transformer = ExportedMethodTransformer {
+                    struct_context: context,
+                    overloads: overloaded_methods(&self.module, &context.struct_name, &item_refs),
+                    // The innermost attribute wins: the method's, then the impl block's, then the module's
+                    call_type: get_call_type(&node.attrs)
+                        .map(|c| c.call_type)
+                        .or_else(|| self.call_type.clone())
+                        .unwrap_or(CallType::Safe(None)),
+                    body_wrapper: self.body_wrapper.as_deref_mut().map(|w| w as &mut BodyWrapper),
+                    register_natives: self.options.register_natives.is_some(),
+                    middleware: self.options.middleware.clone(),
+                    hidden,
+                    attribute_filter: attribute_filter.clone(),
+                    natives: Vec::new(),
+                };
+
+                for (item, _) in items {
+                    let method_ident = match &item {
+                        ImplItem::Method(m) => Some(m.sig.ident.clone()),
+                        _ => None,
+                    };
+                    let transformed_item = exported_fns_transformer.fold_impl_item(item);
+                    if let (Some(method_ident), ImplItem::Method(m)) = (method_ident, &transformed_item) {
+                        symbols.insert(m.sig.ident.to_string(), method_ident);
+                    }
+                    transformed.push(transformed_item);
+                }
+                self.natives.extend(exported_fns_transformer.natives);
+            }
+
+            // Methods of the same impl block exported under the same name and parameters have been reported already, and
+            // the impl blocks of a class by `check_duplicate_exports`
+            let shared_class = !struct_defaults.is_empty() && contexts[0].instantiation.is_none();
+            for (symbol, method_ident) in &symbols {
+                if shared_class && self.exported_symbols.contains(symbol) {
+                    emit_error!(method_ident, "`{}` is exported to the same native method as a method of another `impl` block of `{}`", method_ident, contexts[0].struct_name;
+                        note = "both are bound to the JNI symbol `{}`, as all the instantiations of `{}` share one Java class", symbol, contexts[0].struct_name;
+                        help = "bridge each instantiation of `{}` to its own Java class, with `#[instantiate(OfType = {}<...>)]` on the struct",
+                            contexts[0].struct_name, contexts[0].struct_name);
+                }
+            }
+            self.exported_symbols.extend(symbols.into_keys());
+
+            self.imported_metadata.extend(imported_fns_transformer.metadata);
+
+            (preserved, transformed)
+        } else {
+            (node.items, Vec::new())
+        };
+
+        let preserved_impl = ItemImpl {
+            attrs: node
+                .attrs
+                .into_iter()
+                .filter(|a| !a.path.is_ident("call_type") && !a.path.is_ident("export_visibility"))
+                .map(|a| self.fold_attribute(a))
+                .collect(),
+            generics: self.fold_generics(node.generics),
+            self_ty: Box::new(self.fold_type(*node.self_ty)),
+            items: preserved_items
+                .into_iter()
+                .map(|i| self.fold_impl_item(i))
+                .collect(),
+            ..node
+        };
+
+        // The impl block, followed by the JNI functions of its exported methods in declaration order
+        let mut stream = preserved_impl.into_token_stream();
+        for item in transformed_items {
+            item.to_tokens(&mut stream);
+        }
+        stream
+    }
+}
+
+impl<'w> Fold for ModTransformer<'w> {
+    fn fold_item(&mut self, node: Item) -> Item {
+        match node {
+            Item::Const(c) => Item::Const(self.fold_item_const(c)),
+            Item::Enum(e) => Item::Enum(self.fold_item_enum(e)),
+            Item::ExternCrate(c) => Item::ExternCrate(self.fold_item_extern_crate(c)),
+            Item::Fn(f) => match get_abi(&f.sig).filter(|abi| abi == "jni" || abi == "java") {
+                Some(abi) => {
+                    emit_error!(f.sig, "`extern \"{}\"` functions must be methods of a bridged struct", abi;
+                        help = "move it to the `impl` block of a struct with a `#[package]` attribute");
+                    Item::Verbatim(TokenStream::new())
+                }
+                None => Item::Fn(self.fold_item_fn(f)),
+            },
+            Item::ForeignMod(m) => Item::ForeignMod(self.fold_item_foreign_mod(m)),
+            // Trait impls can't declare exported or imported methods, and are kept as written
+            Item::Impl(i) if i.trait_.is_some() => Item::Impl(i),
+            Item::Impl(i) => Item::Verbatim(self.transform_item_impl(i)),
+            Item::Macro(m) => Item::Macro(self.fold_item_macro(m)),
+            Item::Macro2(m) => Item::Macro2(self.fold_item_macro2(m)),
+            Item::Mod(m) => Item::Mod(self.fold_item_mod(m)),
+            Item::Static(s) => Item::Static(self.fold_item_static(s)),
+            Item::Struct(s) => Item::Struct(self.fold_item_struct(s)),
+            Item::Trait(t) => Item::Trait(self.fold_item_trait(t)),
+            Item::TraitAlias(t) => Item::TraitAlias(self.fold_item_trait_alias(t)),
+            Item::Type(t) => Item::Type(self.fold_item_type(t)),
+            Item::Union(u) => Item::Union(self.fold_item_union(u)),
+            Item::Use(u) => Item::Use(self.fold_item_use(u)),
+            Item::Verbatim(ref tokens) if has_bridge_abi(tokens.clone()) => {
+                emit_error!(tokens, "unsupported item in `#[bridge]` module";
+                    note = "only methods in the `impl` block of a struct with a `#[package]` attribute can be `extern \"jni\"` or `extern \"java\"`");
+                Item::Verbatim(TokenStream::new())
+            }
+            Item::Verbatim(_) => node,
+            _ => node,
+        }
+    }
+
+    fn fold_item_mod(&mut self, mut node: ItemMod) -> ItemMod {
+        let allow_non_snake_case: Attribute = parse_quote! { #![allow(non_snake_case)] };
+
+        node.attrs
+            .extend_from_slice(&[allow_non_snake_case]);
+
+        ItemMod {
+            attrs: node.attrs,
+            vis: self.fold_visibility(node.vis),
+            mod_token: node.mod_token,
+            ident: self.fold_ident(node.ident),
+            content: node.content.map(|(brace, items)| {
+                (
+                    brace,
+                    items.into_iter().map(|i| self.fold_item(i)).collect(),
+                )
+            }),
+            semi: node.semi,
+        }
+    }
+
+    fn fold_item_struct(&mut self, node: ItemStruct) -> ItemStruct {
+        let struct_attributes = bridged_struct_attributes(node.attrs);
+
+        ItemStruct {
+            attrs: struct_attributes,
+            vis: node.vis,
+            struct_token: node.struct_token,
+            ident: node.ident,
+            generics: self.fold_generics(node.generics),
+            fields: self.fold_fields(node.fields),
+            semi_token: node.semi_token,
+        }
+    }
+}
+
+/// Attributes of a bridged struct, without the ones that only `#[bridge]` uses.
+fn bridged_struct_attributes(attrs: Vec<Attribute>) -> Vec<Attribute> {
+    /* The `#[bridge]` attribute macro has to discard `#[package()]` attributes, because they don't exists in standard Rust
+     * and currently there is no way for attribute macros to automatically introduce inert attributes (see: https://doc.rust-lang.org/reference/attributes.html#active-and-inert-attributes
+     * and rust-lang/issues/#65823).
+     * However, we want `#[package()]` to also be used in combination with auto-derive, and conversion traits (i.e. `Signature`, `(Try)IntoJavaValue`, `(Try)FromJavaValue`) *need* a `#[package]` attribute on the struct they are applied on.
+     * If we remove the package attribute blindly the traits cannot see it, and if we keep it the auto-derived traits cannot remove it (auto-derive macros cannot modify the existing token stream as proc macros).
+     * Here we check wether the struct has a `#[derive(TRAIT)]` (crudely with a string comparison and hoping the user never writes `#[derive(::robusta_jni::convert::TRAIT)]`)
+     * if it is present we don't remove `#[package]`, otherwise we remove it.
+     * This works because all conversion traits auto-derive macros also declare `#[package]` as a helper attribute
+     */
+    let traits_with_package_attr = BTreeSet::from([
+        "Signature",
+        "FromJavaValue",
+        "TryFromJavaValue",
+        "IntoJavaValue",
+        "TryIntoJavaValue"
+    ]);
+
+    let has_package_trait = attrs.iter()
+        .any(|a| {
+            let is_derive = a.path.get_ident().map(ToString::to_string).as_deref() == Some("derive");
+            let derived_traits = a.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+                .iter()
+                .flat_map(|p: &syn::punctuated::Punctuated<Ident, Token![,]>| p)
+                .map(|i| i.to_string())
+                .collect::<BTreeSet<String>>();
+            let needs_package_attr = derived_traits.iter().any(|t| traits_with_package_attr.contains(t.as_str()));
+
+            is_derive && needs_package_attr
+        });
+
+    // Likewise, `#[instantiate]` is kept for `Signature`, whose class depends on the instantiation
+    let has_signature_trait = attrs.iter().any(|a| {
+        a.path.is_ident("derive")
+            && a.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+                .is_ok_and(|traits| traits.iter().any(|t| t == "Signature"))
+    });
+
+    attrs
+        .into_iter()
+        .filter(|a| has_package_trait || a.path.to_token_stream().to_string().as_str() != "package")
+        .filter(|a| has_signature_trait || !a.path.is_ident("instantiate"))
+        .collect()
+}
+
+#[derive(Default)]
+pub struct ImplExportVisitor<'ast> {
+    pub(crate) items: Vec<(&'ast ImplItem, ImplItemType)>,
+    /// Names of the `macro_rules!` macros of the module that generate `extern "jni"` or `extern "java"` methods.
+    pub(crate) bridge_macros: BTreeSet<String>,
+}
+
+impl<'ast> Visit<'ast> for ImplExportVisitor<'ast> {
+    fn visit_impl_item(&mut self, node: &'ast ImplItem) {
+        if let Some(diagnostic) = unsupported_impl_item(node, &self.bridge_macros) {
+            diagnostic.emit();
+            self.items.push((node, ImplItemType::Unsupported));
+            return;
+        }
+
+        match node {
+            ImplItem::Method(method) => {
+                let abi = get_abi(&method.sig);
+
+                match abi.as_deref() {
+                    Some("jni") => self.items.push((node, ImplItemType::Exported)),
+                    Some("java") => self.items.push((node, ImplItemType::Imported)),
+                    _ => self.items.push((node, ImplItemType::Unexported)),
+                }
+            }
+            _ => self.items.push((node, ImplItemType::Unexported)),
+        }
+    }
+}
+
+/// Whether `tokens` contain an `extern "jni"` or `extern "java"` ABI, at any depth.
+fn has_bridge_abi(tokens: TokenStream) -> bool {
+    let mut previous_extern = false;
+
+    tokens.into_iter().any(|tree| {
+        let found = match &tree {
+            TokenTree::Group(g) => has_bridge_abi(g.stream()),
+            TokenTree::Literal(l) => previous_extern && matches!(l.to_string().as_str(), "\"jni\"" | "\"java\""),
+            _ => false,
+        };
+        previous_extern = matches!(&tree, TokenTree::Ident(i) if i == "extern");
+        found
+    })
+}
+
+/// Names of the `macro_rules!` macros declared in `items` whose expansion can contain bridged methods.
+pub(crate) fn bridge_macros(items: &[Item]) -> BTreeSet<String> {
+    items
+        .iter()
+        .filter_map(|i| match i {
+            Item::Macro(m) if m.mac.path.is_ident("macro_rules") && has_bridge_abi(m.mac.tokens.clone()) => {
+                m.ident.as_ref().map(ToString::to_string)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// An error for items of a bridged impl block that look like bridged methods but can't be exported or imported,
+/// which would otherwise be silently left alone (and then rejected by rustc for their unknown ABI).
+fn unsupported_impl_item(item: &ImplItem, bridge_macros: &BTreeSet<String>) -> Option<Diagnostic> {
+    match item {
+        ImplItem::Method(method) => {
+            let abi = get_abi(&method.sig).filter(|abi| abi == "jni" || abi == "java")?;
+            let qualifier = method
+                .sig
+                .constness
+                .map(|c| ("const", c.span()))
+                // Exported `async` methods return a `CompletableFuture` (see `robusta_jni::runtime`)
+                .or_else(|| method.sig.asyncness.filter(|_| abi == "java").map(|a| ("async", a.span())));
+
+            if let Some((qualifier, span)) = qualifier {
+                Some(
+                    Diagnostic::spanned(span, Level::Error, format!("`extern \"{}\"` methods can't be `{}`", abi, qualifier))
+                        .help(format!("remove `{}`", qualifier)),
+                )
+            } else if abi == "jni" && !matches!(method.vis, Visibility::Public(_)) {
+                // Restricted visibilities would leave the JNI function unreachable by the JVM just the same
+                let help = match &method.vis {
+                    Visibility::Inherited => "add `pub` to export it, or remove `extern \"jni\"` to keep it a Rust method".to_string(),
+                    vis => format!(
+                        "replace `{}` with `pub` to export it, or remove `extern \"jni\"` to keep it a Rust method",
+                        vis.to_token_stream().to_string().replace(" (", "(")
+                    ),
+                };
+                Some(
+                    Diagnostic::spanned(method.sig.span(), Level::Error, "`extern \"jni\"` methods must be public to be exported".into())
+                        .help(help)
+                        .note("private methods aren't exported, so Java would fail to find their native method at runtime".into()),
+                )
+            } else {
+                None
+            }
+        }
+        ImplItem::Macro(m) => {
+            let is_bridge_macro = m.mac.path.get_ident().is_some_and(|i| bridge_macros.contains(&i.to_string()));
+
+            if is_bridge_macro || has_bridge_abi(m.mac.tokens.clone()) {
+                Some(
+                    Diagnostic::spanned(m.mac.span(), Level::Error, "methods generated by macros can't be exported or imported".into())
+                        .note("`#[bridge]` processes impl blocks before macros are expanded, so it can't see the methods they generate".into())
+                        .help("declare `extern \"jni\"` and `extern \"java\"` methods directly in the impl block".into()),
+                )
+            } else {
+                None
+            }
+        }
+        ImplItem::Verbatim(tokens) if has_bridge_abi(tokens.clone()) => Some(
+            Diagnostic::spanned(tokens.span(), Level::Error, "unsupported item in bridged impl block".into())
+                .note("only methods and associated functions can be `extern \"jni\"` or `extern \"java\"`".into()),
+        ),
+        _ => None,
+    }
+}
+
+/// Maps the exported methods sharing their Java name with another exported method of the same impl block to the JNI descriptor
+/// of their arguments, e.g. `Ljava/lang/String;I`, which overloaded native methods need in their symbol.
+///
+/// Descriptors are inferred from the syntax of the parameter types, the same way as for Java stubs.
+fn overloaded_methods(
+    module: &JNIBridgeModule,
+    struct_name: &str,
+    items: &[(&ImplItem, ImplItemType)],
+) -> BTreeMap<Ident, String> {
+    let class_names = ClassNames {
+        package_map: &module.package_map,
+        self_name: struct_name,
+        flattened_structs: &module.flattened_structs,
+        error_codes: &module.error_codes,
+        rename_all: module.rename_all,
+        instantiations: &module.instantiations,
+    };
+    let stubs: Vec<_> = items
+        .iter()
+        .filter_map(|(i, t)| match (i, t) {
+            (ImplItem::Method(m), ImplItemType::Exported) if matches!(m.vis, Visibility::Public(_)) => {
+                let stub = class_names.method_stub(m);
+                let descriptor: String = stub.params.iter().map(|p| p.ty.descriptor()).collect();
+                Some((&m.sig.ident, stub.name, descriptor))
+            }
+            _ => None,
+        })
+        .collect();
+
+    // Methods exported with the same name and parameters would define the same native symbol
+    for (i, (ident, name, descriptor)) in stubs.iter().enumerate() {
+        if let Some((other, _, _)) = stubs[..i].iter().find(|(_, n, d)| n == name && d == descriptor) {
+            if class_names.rename_all.is_some() && (*other != name || *ident != name) {
+                emit_error!(ident, "`{}` and `{}` are both exported as `{}({})`", other, ident, name, descriptor;
+                    note = "`rename_all` converts the names of methods without a `#[java_name]`";
+                    help = "rename one of them, or give it another Java name with `#[java_name = \"...\"]`");
+            } else {
+                emit_error!(ident, "`{}` and `{}` are both exported as `{}({})`", other, ident, name, descriptor;
+                    help = "rename one of them, or give it another Java name with `#[java_name = \"...\"]`");
+            }
+        }
+    }
+
+    stubs
+        .iter()
+        .filter(|(ident, name, _)| stubs.iter().any(|(other, n, _)| other != ident && n == name))
+        .map(|(ident, _, descriptor)| ((*ident).clone(), descriptor.clone()))
+        .collect()
+}
+
+/// Reports exported methods of different impl blocks of a class that would define the same function, which rustc and the linker
+/// would only report by its mangled name. Methods of the same impl block are checked by `overloaded_methods`, and the impl
+/// blocks of a generic struct sharing one Java class by `fold_item_impl`.
+fn check_duplicate_exports(module: &JNIBridgeModule) {
+    // Java name, argument descriptor and whether the name is overloaded in its impl block, by class
+    let mut exported: BTreeMap<String, Vec<(Ident, String, String, bool)>> = BTreeMap::new();
+
+    for_each_impl(module, |struct_name, class_name, class_names, item_impl| {
+        let shared_class = !module.instantiations.contains_key(struct_name)
+            && module.module_decl.content.iter().flat_map(|(_, items)| items).any(|i| match i {
+                Item::Struct(s) => s.ident == struct_name && s.generics.type_params().next().is_some(),
+                _ => false,
+            });
+        if shared_class {
+            return;
+        }
+
+        let stubs: Vec<_> = item_impl
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                ImplItem::Method(m) if matches!(m.vis, Visibility::Public(_)) && get_abi(&m.sig).as_deref() == Some("jni") => {
+                    let stub = class_names.method_stub(m);
+                    let descriptor: String = stub.params.iter().map(|p| p.ty.descriptor()).collect();
+                    Some((m.sig.ident.clone(), stub.name, descriptor))
+                }
+                _ => None,
+            })
+            .collect();
+        let methods: Vec<_> = stubs
+            .iter()
+            .map(|(ident, name, descriptor)| {
+                let overloaded = stubs.iter().any(|(other, n, _)| other != ident && n == name);
+                (ident.clone(), name.clone(), descriptor.clone(), overloaded)
+            })
+            .collect();
+
+        let previous = exported.entry(class_name.to_string()).or_default();
+        for (ident, name, descriptor, overloaded) in &methods {
+            let first = previous.iter().find(|(_, n, d, o)| n == name && (d == descriptor || !(*o || *overloaded)));
+            match first {
+                Some((first, _, d, _)) if d == descriptor => {
+                    emit_error!(ident, "`{}` and `{}` are both exported as `{}({})` by `{}`", first, ident, name, descriptor, class_name;
+                        note = first.span() => "`{}` is exported by another impl block", first;
+                        help = "rename one of them, or give it another Java name with `#[java_name = \"...\"]`");
+                }
+                Some((first, _, _, _)) => {
+                    emit_error!(ident, "`{}` and `{}` overload `{}` of `{}` from different impl blocks", first, ident, name, class_name;
+                        note = first.span() => "`{}` is exported by another impl block", first;
+                        help = "declare the overloads in the same impl block, so that their symbols include their parameters");
+                }
+                None => {}
+            }
+        }
+        previous.extend(methods);
+    });
+}
+
+/// Parameter slots of a Java method, where `long` and `double` parameters take two slots, and `this` one.
+const MAX_PARAMETER_SLOTS: usize = 255;
+
+/// Reports exported methods with more parameters than a Java method can declare, which the JVM would reject when loading the
+/// class declaring them.
+fn check_parameter_slots(module: &JNIBridgeModule) {
+    for_each_impl(module, |_, class_name, class_names, item_impl| {
+        for m in exported_methods(item_impl) {
+            let stub = class_names.method_stub(m);
+            let slots = stub.params.iter().map(|p| if matches!(p.ty.descriptor().as_str(), "J" | "D") { 2 } else { 1 }).sum::<usize>()
+                + usize::from(!stub.is_static);
+            if slots > MAX_PARAMETER_SLOTS {
+                emit_error!(m.sig.ident, "`{}` of `{}` takes {} parameter slots, more than the {} of a Java method", m.sig.ident, class_name, slots, MAX_PARAMETER_SLOTS;
+                    note = "`long` and `double` parameters take two slots, and the instance of non-static methods one";
+                    help = "group parameters in a struct passed as a single object");
+            }
+        }
+    });
+}
+
+/// A warning with the JNI descriptor of each exported method, for `#[bridge(debug_signatures)]`.
+///
+/// Descriptors are inferred from the types as written, like the Java stubs, which is what the Java declarations must match.
+fn signature_warnings(module: &JNIBridgeModule) -> Vec<Item> {
+    let mut warnings = Vec::new();
+    for_each_impl(module, |struct_name, class_name, class_names, item_impl| {
+        let class = match module.package_map.get(struct_name).cloned().flatten() {
+            Some(package) if !package.0.is_empty() => format!("{}/{}", package.to_classpath_path(), class_name),
+            _ => class_name.to_string(),
+        };
+
+        for m in exported_methods(item_impl) {
+            let stub = class_names.method_stub(m);
+            let params: String = stub.params.iter().map(|p| p.ty.descriptor()).collect();
+            let message = format!(
+                "`{}::{}` is exported as {}native method `{}.{}({}){}`",
+                struct_name,
+                m.sig.ident,
+                if stub.is_static { "static " } else { "" },
+                class,
+                stub.name,
+                params,
+                stub.return_type.descriptor()
+            );
+            warnings.push(dry_run::warning(m.sig.ident.span(), "BridgeSignature", &message));
+        }
+    });
+    warnings
+}
+
+/// The exported methods of an impl block.
+fn exported_methods(item_impl: &ItemImpl) -> impl Iterator<Item = &ImplItemMethod> {
+    item_impl.items.iter().filter_map(|i| match i {
+        ImplItem::Method(m) if matches!(m.vis, Visibility::Public(_)) && get_abi(&m.sig).as_deref() == Some("jni") => Some(m),
+        _ => None,
+    })
+}
+
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub(crate) struct JavaPath(String);
+
+impl Display for JavaPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub(crate) const JAVA_RESERVED_WORDS: &[&str] = &[
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const",
+    "continue", "default", "do", "double", "else", "enum", "extends", "false", "final", "finally",
+    "float", "for", "goto", "if", "implements", "import", "instanceof", "int", "interface", "long",
+    "native", "new", "null", "package", "private", "protected", "public", "return", "short", "static",
+    "strictfp", "super", "switch", "synchronized", "this", "throw", "throws", "transient", "true", "try",
+    "void", "volatile", "while", "_",
+];
+
+/// Checks that no segment of a dot-separated path is a Java reserved word.
+fn check_reserved_words(path: &str) -> Result<(), String> {
+    match path.split('.').find(|segment| JAVA_RESERVED_WORDS.contains(segment)) {
+        Some(segment) => Err(format!("invalid path: `{}` is a reserved Java keyword", segment)),
+        None => Ok(()),
+    }
+}
+
+impl FromStr for JavaPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.to_string().replace(' ', "");
+        if input.contains('-') {
+            Err("package names can't contain dashes".into())
+        } else {
+            check_reserved_words(&input)?;
+            Ok(JavaPath(input))
+        }
+    }
+}
+
+impl JavaPath {
+    pub fn to_classpath_path(&self) -> String {
+        self.0.replace('.', "/")
+    }
+}
+
+impl Parse for JavaPath {
+    fn parse<'a>(input: &'a ParseBuffer<'a>) -> syn::Result<Self> {
+        let tokens = Punctuated::<Ident, Token![.]>::parse_terminated(input)?
+            .to_token_stream();
+        let package = tokens
+            .to_string();
+
+        JavaPath::from_str(&package).map_err(|e| Error::new_spanned(tokens, e))
+    }
+}
+
+impl FromMeta for JavaPath {
+    fn from_value(value: &Lit) -> darling::Result<Self> {
+        use darling::Error;
+
+        if let Lit::Str(literal) = value {
+            let path = literal.value();
+            Self::from_string(&path)
+        } else {
+            Err(Error::custom("invalid type"))
+        }
+    }
+
+    fn from_string(path: &str) -> darling::Result<Self> {
+        use darling::Error;
+        if path.contains('-') {
+            Err(Error::custom(
+                "invalid path: packages and classes cannot contain dashes",
+            ))
+        } else {
+            let tokens = TokenStream::from_str(&path).map_err(|_| {
+                Error::custom("cannot create token stream for java path parsing")
+            })?;
+            let _parsed: Punctuated<Ident, Token![.]> =
+                Punctuated::<Ident, Token![.]>::parse_separated_nonempty
+                    .parse2(tokens)
+                    .map_err(|e| Error::custom(format!("cannot parse java path ({})", e)))?;
+            check_reserved_words(path).map_err(Error::custom)?;
+
+            Ok(JavaPath(path.into()))
+        }
+    }
+}
+
+/// Turns exported methods into plain Rust methods, kept in the `impl` block for Rust callers.
+struct ImplCleaner<'a> {
+    attribute_filter: &'a AttributeFilter,
+}
+
+impl<'a> Fold for ImplCleaner<'a> {
+    fn fold_impl_item_method(&mut self, mut node: ImplItemMethod) -> ImplItemMethod {
+        let abi = node
+            .sig
+            .abi
+            .as_ref()
+            .and_then(|l| l.name.as_ref().map(|n| n.value()));
+
+        match (&node.vis, &abi.as_deref()) {
+            (Visibility::Public(_), Some("jni")) => {
+                node.sig.abi = None;
+                node.sig.inputs.iter_mut().for_each(|i| {
+                    if let FnArg::Typed(t) = i {
+                        t.attrs.retain(|a| !is_bridge_attribute(a));
+                    }
+                });
+                node.attrs = self.attribute_filter.method_attributes(node.attrs);
+
+                node
+            }
+            (_, _) => node,
+        }
+    }
+}
+
+struct FreestandingTransformer {
+    struct_type: Path,
+}
+
+impl FreestandingTransformer {
+    fn new(struct_type: Path) -> Self {
+        FreestandingTransformer {
+            struct_type,
+        }
+    }
+}
+
+impl Fold for FreestandingTransformer {
+    fn fold_fn_arg(&mut self, arg: FnArg) -> FnArg {
+        match arg {
+            FnArg::Receiver(r) => {
+                let receiver_span = r.span();
+
+                let needs_env_lifetime = self.struct_type.segments.iter().any(|s| {
+                    if let PathArguments::AngleBracketed(a) = &s.arguments {
+                        a.args
+                            .iter()
+                            .filter_map(|g| match g {
+                                GenericArgument::Lifetime(l) => Some(l),
+                                _ => None,
+                            })
+                            .all(|l| l.ident != "env")
+                    } else {
+                        false
+                    }
+                });
+
+                if needs_env_lifetime {
+                    Diagnostic::spanned(self.struct_type.span(), dry_run::rejection_level(), "must have one `'env` lifetime in impl to support self methods when using lifetime-parametrized struct".into())
+                        .emit();
+                }
+
+                let self_type = match r.reference.clone() {
+                    Some((and_token, lifetime)) => Type::Reference(TypeReference {
+                        and_token,
+                        lifetime,
+                        mutability: r.mutability,
+                        elem: Box::new(Type::Path(TypePath {
+                            qself: None,
+                            path: self.struct_type.clone(),
+                        })),
+                    }),
+
+                    None => Type::Path(TypePath {
+                        qself: None,
+                        path: self.struct_type.clone(),
+                    }),
+                };
+
+                FnArg::Typed(PatType {
+                    attrs: r.attrs,
+                    pat: Box::new(Pat::Ident(PatIdent {
+                        attrs: vec![],
+                        by_ref: None,
+                        mutability: None,
+                        ident: Ident::new("receiver", receiver_span),
+                        subpat: None,
+                    })),
+                    colon_token: Token![:](receiver_span),
+                    ty: Box::new(parse_quote! { #self_type }),
+                })
+            }
+
+            FnArg::Typed(t) => match &*t.pat {
+                Pat::Ident(ident) if ident.ident == "self" => {
+                    let pat_span = t.span();
+                    let self_type = &*t.ty;
+                    FnArg::Typed(PatType {
+                        attrs: t.attrs,
+                        pat: Box::new(Pat::Ident(PatIdent {
+                            attrs: ident.attrs.clone(),
+                            by_ref: ident.by_ref,
+                            mutability: ident.mutability,
+                            ident: Ident::new("receiver", pat_span),
+                            subpat: ident.subpat.clone(),
+                        })),
+                        colon_token: t.colon_token,
+                        ty: Box::new(parse_quote! { #self_type }),
+                    })
+                }
+                _ => FnArg::Typed(self.fold_pat_type(t)),
+            },
+        }
+    }
+
+    /// Freestanding functions live outside of the impl block, so `Self` must be spelled out as the struct type.
+    fn fold_type(&mut self, ty: Type) -> Type {
+        match ty {
+            Type::Path(TypePath { qself: None, path }) if path.is_ident("Self") => Type::Path(TypePath {
+                qself: None,
+                path: self.struct_type.clone(),
+            }),
+            _ => syn::fold::fold_type(self, ty),
+        }
+    }
+}
+
+#[derive(Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct SafeParams {
+    pub(crate) exception_class: Option<JavaPath>,
+    pub(crate) message: Option<String>,
+}
+
+#[derive(Clone, Default, FromMeta)]
+#[darling(default)]
+pub struct UncheckedParams {
+    /// Turn panics into Java exceptions, as safe methods always do.
+    pub(crate) catch_panics: Flag,
+}
+
+#[derive(Clone, FromMeta)]
+pub enum CallType {
+    Safe(Option<SafeParams>),
+    Unchecked(Option<UncheckedParams>),
+}
+
+impl CallType {
+    /// Whether exported methods catch panics, throwing them as Java exceptions.
+    pub(crate) fn catches_panics(&self) -> bool {
+        match self {
+            CallType::Safe(_) => true,
+            CallType::Unchecked(params) => params.as_ref().is_some_and(|p| p.catch_panics.is_some()),
+        }
+    }
+}
+
+/// Classes listed in `#[bridge(preload_classes(...))]`, in order.
+#[derive(Clone, Default)]
+pub(crate) struct PreloadClasses(pub(crate) Vec<JavaPath>);
+
+impl FromMeta for PreloadClasses {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                NestedMeta::Lit(lit) => JavaPath::from_value(lit),
+                NestedMeta::Meta(_) => Err(darling::Error::custom("expected a class name, as in `\"com.example.Foo\"`")),
+            })
+            .collect::<darling::Result<_>>()
+            .map(PreloadClasses)
+    }
+}
+
+/// Attribute paths of `#[bridge(wrapper_attributes(tracing::instrument, ...))]`.
+#[derive(Clone, Default)]
+pub(crate) struct WrapperAttributes(pub(crate) Vec<Path>);
+
+impl FromMeta for WrapperAttributes {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                NestedMeta::Meta(Meta::Path(path)) => Ok(path.clone()),
+                _ => Err(darling::Error::custom("expected an attribute path, as in `tracing::instrument`")),
+            })
+            .collect::<darling::Result<_>>()
+            .map(WrapperAttributes)
+    }
+}
+
+/// Naming convention of the Java methods bridged by a module, from `#[bridge(rename_all = "...")]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RenameRule {
+    /// `snake_case` Rust names to `camelCase` Java names, e.g. `parse_json_url` to `parseJsonUrl`.
+    CamelCase,
+}
+
+impl RenameRule {
+    /// The Java name of a method named `rust_name`.
+    ///
+    /// Each word after the first (following an underscore) starts with an uppercase letter, and the rest of the word is kept as is:
+    /// acronyms are words like others (`parse_json_url` becomes `parseJsonUrl`), and leading underscores are kept.
+    pub(crate) fn apply(self, rust_name: &str) -> String {
+        match self {
+            RenameRule::CamelCase => {
+                let words = rust_name.trim_start_matches('_');
+                let mut java_name = rust_name[..rust_name.len() - words.len()].to_string();
+                for (i, word) in words.split('_').filter(|w| !w.is_empty()).enumerate() {
+                    let mut chars = word.chars();
+                    match chars.next() {
+                        Some(first) if i > 0 => java_name.extend(first.to_uppercase().chain(chars)),
+                        _ => java_name.push_str(word),
+                    }
+                }
+
+                java_name
+            }
+        }
+    }
+}
+
+impl FromMeta for RenameRule {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "camelCase" => Ok(RenameRule::CamelCase),
+            _ => Err(darling::Error::custom(format!("unknown naming convention `{}`, expected `camelCase`", value))),
+        }
+    }
+}
+
+/// Options of the `#[bridge]` attribute itself.
+#[derive(Clone, Default, FromMeta)]
+#[darling(default)]
+pub(crate) struct BridgeOptions {
+    pub(crate) preload_classes: Option<PreloadClasses>,
+    /// Don't generate `JNI_OnLoad`, leaving calls to the generated `preload` function to the user.
+    pub(crate) manual_preload: Flag,
+    /// Report raw `JNIEnv` calls in the bodies of the module, see the `lint` module.
+    pub(crate) lint_raw_jni: Option<LintLevel>,
+    /// Convert the names of the module's methods without a `#[java_name]` to Java names with this rule.
+    pub(crate) rename_all: Option<RenameRule>,
+    /// Register the exported methods from `JNI_OnLoad`, rather than exporting a symbol for each of them.
+    pub(crate) register_natives: Flag,
+    /// Type implementing `robusta_jni::middleware::Middleware`, run around every exported method of the module.
+    pub(crate) middleware: Option<Path>,
+    /// Report what would be rejected as warnings, and leave the module untransformed, see the `dry_run` module.
+    pub(crate) dry_run: Flag,
+    /// Report the JNI descriptor of each exported method as a warning, to compare it with the Java declaration.
+    pub(crate) debug_signatures: Flag,
+    /// Directory of the module's file, which the files of its `include!` items are relative to, see the `files` module.
+    pub(crate) path: Option<String>,
+    /// Attributes of exported methods only kept on their JNI function, e.g. `tracing::instrument`, see `AttributeFilter`.
+    pub(crate) wrapper_attributes: Option<WrapperAttributes>,
+    /// Path of the `robusta_jni` crate in the generated code, e.g. `my_facade::robusta` for a crate re-exporting it.
+    #[darling(rename = "crate")]
+    pub(crate) crate_path: Option<Path>,
+}
+
+impl BridgeOptions {
+    /// Parses the arguments of `#[bridge(...)]`, reporting invalid ones.
+    pub(crate) fn parse(args: TokenStream) -> Self {
+        let options = Punctuated::<NestedMeta, Token![,]>::parse_terminated
+            .parse2(args.clone())
+            .map_err(|e| emit_error!(e.span(), "invalid `bridge` attribute ({})", e))
+            .and_then(|nested| {
+                let nested: Vec<_> = nested.into_iter().collect();
+                BridgeOptions::from_list(&nested).map_err(|e| emit_error!(args, "invalid `bridge` attribute ({})", e))
+            })
+            .unwrap_or_default();
+
+        if options.manual_preload.is_some() && options.preload_classes.is_none() {
+            emit_error!(args, "`manual_preload` has no effect without `preload_classes`";
+                help = "list the classes to preload with `preload_classes(\"com.example.Foo\", ...)`");
+        }
+
+        options
+    }
+
+    /// The `preload` function of a module with `preload_classes`.
+    fn preload_items(&self, module: &ItemMod) -> Vec<Item> {
+        let listed = match &self.preload_classes {
+            Some(PreloadClasses(classes)) => classes.iter().map(JavaPath::to_classpath_path),
+            None => return Vec::new(),
+        };
+
+        let mut exception_classes = ExceptionClassCollector::default();
+        exception_classes.visit_item_mod(module);
+
+        let mut classes: Vec<String> = Vec::new();
+        for class in listed.chain(exception_classes.classes) {
+            if !classes.contains(&class) {
+                classes.push(class);
+            }
+        }
+
+        vec![parse_quote! {
+            /// Resolves the classes listed in `preload_classes`, then the exception classes and the classes used by conversions.
+            pub fn preload(env: &::robusta_jni::jni::JNIEnv) -> ::robusta_jni::jni::errors::Result<()> {
+                ::robusta_jni::preload::preload_classes(*env, &[#(#classes),*])
+            }
+        }]
+    }
+
+    /// The `register_natives` function of a module with `register_natives`, and the `JNI_OnLoad` function preloading classes
+    /// (unless `manual_preload` is set) and registering native methods, if the module does either.
+    ///
+    /// `module` is the transformed module, and `natives` the `NativeMethod` expressions of its exported methods.
+    fn on_load_items(&self, module: &ItemMod, natives: &[TokenStream]) -> Vec<Item> {
+        let preload = self.preload_classes.is_some() && self.manual_preload.is_none();
+        let register_natives = self.register_natives.is_some();
+
+        let mut items: Vec<Item> = Vec::new();
+        if register_natives {
+            items.push(parse_quote! {
+                /// Registers the exported methods of this module with their classes.
+                pub fn register_natives(env: &::robusta_jni::jni::JNIEnv) -> ::robusta_jni::jni::errors::Result<()> {
+                    ::robusta_jni::natives::register_natives(env, &[#(#natives),*])
+                }
+            });
+        }
+
+        let on_load: Expr = match (preload, register_natives) {
+            (false, false) => return items,
+            (true, false) => parse_quote! { preload },
+            (false, true) => parse_quote! { register_natives },
+            (true, true) => parse_quote! { |env: &::robusta_jni::jni::JNIEnv| preload(env).and_then(|()| register_natives(env)) },
+        };
+
+        // Only one `JNI_OnLoad` can be defined, which would otherwise be reported as a less helpful duplicate definition
+        let user_on_load = module.content.iter().flat_map(|(_, items)| items).find_map(|i| match i {
+            Item::Fn(f) if f.sig.ident == "JNI_OnLoad" => Some(&f.sig.ident),
+            _ => None,
+        });
+        if let Some(ident) = user_on_load {
+            if register_natives {
+                emit_error!(ident, "`JNI_OnLoad` is generated for `#[bridge(register_natives)]`";
+                    note = "a library can only have one `JNI_OnLoad`, which registers the native methods of this module";
+                    help = "remove this function");
+            } else {
+                emit_error!(ident, "`JNI_OnLoad` is generated for `#[bridge(preload_classes(...))]`";
+                    note = "a library can only have one `JNI_OnLoad`";
+                    help = "add `manual_preload`, and call the generated `preload(env)` from this function");
+            }
+            return items;
+        }
+
+        items.push(parse_quote! {
+            #[no_mangle]
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
+            pub extern "system" fn JNI_OnLoad(
+                vm: *mut ::robusta_jni::jni::sys::JavaVM,
+                _reserved: *mut ::core::ffi::c_void,
+            ) -> ::robusta_jni::jni::sys::jint {
+                unsafe { ::robusta_jni::preload::on_load(vm, #on_load) }
+            }
+        });
+
+        items
+    }
+}
+
+/// Collects the exception classes configured with `#[call_type(safe(exception_class = ...))]`, in declaration order.
+#[derive(Default)]
+struct ExceptionClassCollector {
+    classes: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for ExceptionClassCollector {
+    fn visit_impl_item_method(&mut self, node: &'ast ImplItemMethod) {
+        // Invalid attributes are reported when transforming the module
+        let exception_class = node
+            .attrs
+            .iter()
+            .filter(|a| a.path.is_ident("call_type"))
+            .filter_map(|a| syn::parse2::<CallTypeAttribute>(a.to_token_stream()).ok())
+            .find_map(|a| match a.call_type {
+                CallType::Safe(Some(SafeParams { exception_class, .. })) => exception_class,
+                _ => None,
+            });
+
+        self.classes.extend(exception_class.map(|c| c.to_classpath_path()));
+    }
+}
+
+pub struct CallTypeAttribute {
+    pub(crate) attr: Attribute,
+    pub(crate) call_type: CallType,
+}
+
+impl Parse for CallTypeAttribute {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let attribute = input
+            .call(Attribute::parse_outer)?
+            .first()
+            .cloned()
+            .ok_or_else(|| Error::new(input.span(), "Invalid parsing of `call_type` attribute "))?;
+
+        if attribute
+            .path
+            .get_ident()
+            .ok_or_else(|| Error::new(attribute.path.span(), "expected identifier for attribute"))?
+            != "call_type"
+        {
+            return Err(Error::new(
+                attribute.path.span(),
+                "expected identifier `call_type` for attribute",
+            ));
+        }
+
+        let attr_meta: Meta = attribute.parse_meta()?;
+
+        // Special-case `call_type(safe)` and `call_type(unchecked)` without further parentheses
+        // TODO: Find out if it's possible to use darling to allow `call_type(safe)` *and* `call_type(safe(message = "foo"))` etc.
+        let meta_string = attr_meta.to_token_stream().to_string().replace(' ', "");
+        if meta_string == "call_type(safe)" {
+            Ok(CallTypeAttribute {
+                attr: attribute,
+                call_type: CallType::Safe(None),
+            })
+        } else if meta_string == "call_type(unchecked)" {
+            Ok(CallTypeAttribute {
+                attr: attribute,
+                call_type: CallType::Unchecked(None),
+            })
+        } else {
+            CallType::from_meta(&attr_meta)
+                .map_err(|e| {
+                    Error::new(
+                        attr_meta.span(),
+                        format!("invalid `call_type` attribute options ({})", e),
+                    )
+                })
+                .map(|c| {
+                    CallTypeAttribute {
+                        attr: attribute,
+                        call_type: c,
+                    }
+                })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unsupported_message(item: ImplItem, bridge_macros: &[&str]) -> Option<String> {
+        let bridge_macros = bridge_macros.iter().map(ToString::to_string).collect();
+        unsupported_impl_item(&item, &bridge_macros).map(|d| d.message().to_string())
+    }
+
+    #[test]
+    fn cleaned_methods_keep_user_attributes() {
+        let method: ImplItemMethod = parse_quote! {
+            /// Adds one.
+            #[inline]
+            #[tracing::instrument]
+            #[java_name = "increment"]
+            #[export_name = "add_one"]
+            pub extern "jni" fn add_one(#[default = 1] value: i32) -> i32 { value + 1 }
+        };
+        let attributes = |wrapper_attributes: &[Path]| {
+            let attribute_filter = AttributeFilter::new(wrapper_attributes);
+            let cleaned = ImplCleaner { attribute_filter: &attribute_filter }.fold_impl_item_method(method.clone());
+            assert!(cleaned.sig.abi.is_none());
+            cleaned.to_token_stream().to_string()
+        };
+
+        let cleaned = attributes(&[]);
+        assert!(cleaned.contains(r#"# [doc = r" Adds one."]"#));
+        assert!(cleaned.contains("# [inline]"));
+        assert!(cleaned.contains("# [tracing :: instrument]"));
+        assert!(!cleaned.contains("java_name") && !cleaned.contains("export_name") && !cleaned.contains("default"));
+
+        // Listed attributes are left to the JNI function
+        let cleaned = attributes(&[parse_quote! { tracing::instrument }]);
+        assert!(cleaned.contains("# [inline]"));
+        assert!(!cleaned.contains("instrument"));
+    }
+
+    #[test]
+    fn macros_generating_bridged_methods_are_found() {
+        let items: Vec<Item> = vec![
+            parse_quote! { macro_rules! exported { () => { pub extern "jni" fn foo() {} }; } },
+            parse_quote! { macro_rules! imported { ($name:ident) => { pub extern "java" fn $name(env: JNIEnv) {} }; } },
+            parse_quote! { macro_rules! plain { () => { pub fn foo() {} }; } },
+            parse_quote! { macro_rules! abi_string { () => { "jni" }; } },
+        ];
+
+        let expected: BTreeSet<String> = ["exported", "imported"].iter().map(ToString::to_string).collect();
+        assert_eq!(bridge_macros(&items), expected);
+    }
+
+    #[test]
+    fn macro_generated_methods_are_rejected() {
+        let message = Some("methods generated by macros can't be exported or imported".to_string());
+
+        assert_eq!(unsupported_message(parse_quote! { exported!(); }, &["exported"]), message);
+        assert_eq!(unsupported_message(parse_quote! { wrap! { pub extern "jni" fn foo() {} } }, &[]), message);
+        assert_eq!(unsupported_message(parse_quote! { plain!(); }, &["exported"]), None);
+    }
+
+    #[test]
+    fn exported_consts_are_rejected() {
+        assert_eq!(
+            unsupported_message(parse_quote! { pub const extern "jni" fn answer() -> i32 { 42 } }, &[]),
+            Some("`extern \"jni\"` methods can't be `const`".to_string())
+        );
+        assert_eq!(unsupported_message(parse_quote! { pub const ANSWER: i32 = 42; }, &[]), None);
+        assert_eq!(unsupported_message(parse_quote! { pub const fn answer() -> i32 { 42 } }, &[]), None);
+    }
+
+    #[test]
+    fn private_exported_methods_are_rejected() {
+        assert_eq!(
+            unsupported_message(parse_quote! { extern "jni" fn foo() {} }, &[]),
+            Some("`extern \"jni\"` methods must be public to be exported".to_string())
+        );
+        assert_eq!(
+            unsupported_message(parse_quote! { pub(crate) extern "jni" fn foo() {} }, &[]),
+            Some("`extern \"jni\"` methods must be public to be exported".to_string())
+        );
+        assert_eq!(unsupported_message(parse_quote! { pub extern "jni" fn foo() {} }, &[]), None);
+        assert_eq!(unsupported_message(parse_quote! { extern "java" fn foo(env: JNIEnv) {} }, &[]), None);
+    }
+
+    fn preload_items(classes: Option<&[&str]>, manual_preload: bool) -> Vec<String> {
+        on_load_items(classes, manual_preload, false)
+    }
+
+    fn on_load_items(classes: Option<&[&str]>, manual_preload: bool, register_natives: bool) -> Vec<String> {
+        let flag = |present| if present { Flag::present() } else { Flag::default() };
+        let options = BridgeOptions {
+            preload_classes: classes.map(|c| PreloadClasses(c.iter().map(|c| JavaPath::from_str(c).unwrap()).collect())),
+            manual_preload: flag(manual_preload),
+            register_natives: flag(register_natives),
+            ..Default::default()
+        };
+        let module: ItemMod = parse_quote! {
+            mod jni {
+                impl Foo {
+                    #[call_type(safe(exception_class = "java.io.IOException"))]
+                    pub extern "jni" fn read() -> Result<i32, String> { Ok(0) }
+
+                    #[call_type(safe(exception_class = "com.example.Invalid"))]
+                    pub extern "jni" fn check() -> Result<(), String> { Ok(()) }
+                }
+            }
+        };
+
+        let natives = [quote::quote! { NATIVE }];
+        options
+            .preload_items(&module)
+            .into_iter()
+            .chain(options.on_load_items(&module, &natives))
+            .map(|i| i.to_token_stream().to_string())
+            .collect()
+    }
+
+    /// A label for each item of the expanded module, in order, e.g. `fn Java_com_example_Foo_bar`.
+    fn expanded_items(module: TokenStream) -> Vec<String> {
+        let module: JNIBridgeModule = syn::parse2(module).unwrap();
+        let options = BridgeOptions {
+            preload_classes: Some(PreloadClasses(vec![JavaPath::from_str("com.example.Registry").unwrap()])),
+            ..Default::default()
+        };
+        let expanded: ItemMod = syn::parse2(ModTransformer::new(module).with_options(options).transform_module()).unwrap();
+
+        expanded
+            .content
+            .unwrap()
+            .1
+            .iter()
+            .map(|item| match item {
+                Item::Fn(f) => format!("fn {}", f.sig.ident),
+                Item::Struct(s) => format!("struct {}", s.ident),
+                Item::Impl(i) => {
+                    let methods: Vec<_> = i
+                        .items
+                        .iter()
+                        .filter_map(|m| match m {
+                            ImplItem::Method(m) => Some(m.sig.ident.to_string()),
+                            _ => None,
+                        })
+                        .collect();
+                    format!("impl {} {{ {} }}", i.self_ty.to_token_stream(), methods.join(", "))
+                }
+                other => other.to_token_stream().to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn expansion_order_follows_declarations() {
+        let structs = quote::quote! {
+            #[package(com.example)]
+            pub struct Foo;
+
+            #[package(com.example)]
+            pub struct Bar<'env> {
+                raw: JObject<'env>,
+            }
+        };
+        let shuffled_structs = quote::quote! {
+            #[package(com.example)]
+            pub struct Bar<'env> {
+                raw: JObject<'env>,
+            }
+
+            #[package(com.example)]
+            pub struct Foo;
+        };
+        let impls = quote::quote! {
+            impl Foo {
+                pub extern "jni" fn zeta(a: i32) -> i32 { a }
+                pub extern "jni" fn alpha(a: String) -> String { a }
+                #[java_name = "alpha"]
+                pub extern "jni" fn alpha_int(a: i32) -> i32 { a }
+                fn helper() {}
+            }
+
+            impl<'env> Bar<'env> {
+                pub extern "java" fn second(env: JNIEnv<'env>, a: i32) -> JniResult<i32> {}
+                pub extern "java" fn first(&self, env: JNIEnv<'env>) -> JniResult<String> {}
+                pub extern "jni" fn exported(self) {}
+            }
+        };
+
+        let module = |structs: &TokenStream| quote::quote! { mod jni { #structs #impls } };
+        let items = expanded_items(module(&structs));
+        assert_eq!(items, expanded_items(module(&structs)));
+
+        // Moving the structs only moves their own items
+        let without_structs = |items: Vec<String>| -> Vec<String> { items.into_iter().filter(|i| !i.starts_with("struct")).collect() };
+        assert_eq!(without_structs(items.clone()), without_structs(expanded_items(module(&shuffled_structs))));
+
+        assert_eq!(
+            items,
+            [
+                "struct Foo",
+                "struct Bar",
+                "impl Foo { zeta, alpha, alpha_int, helper }",
+                "fn Java_com_example_Foo_zeta",
+                "fn Java_com_example_Foo_alpha__Ljava_lang_String_2",
+                "fn Java_com_example_Foo_alpha__I",
+                "impl Bar < 'env > { second, first, exported }",
+                "fn Java_com_example_Bar_exported",
+                "fn preload",
+                "fn JNI_OnLoad",
+                "fn bridge_metadata",
+                ":: robusta_jni :: __private :: register_bindings ! (bridge_metadata) ;",
+            ]
+        );
+    }
+
+    #[test]
+    fn preloaded_classes_include_exception_classes() {
+        let items = preload_items(Some(&["com.example.Invalid", "com.example.Registry"]), false);
+
+        assert_eq!(items.len(), 2);
+        assert!(items[0].contains(r#"& ["com/example/Invalid" , "com/example/Registry" , "java/io/IOException"]"#));
+        assert!(items[1].contains("fn JNI_OnLoad"));
+    }
+
+    #[test]
+    fn manual_preload_has_no_on_load() {
+        let items = preload_items(Some(&["com.example.Registry"]), true);
+
+        assert_eq!(items.len(), 1);
+        assert!(items[0].contains("pub fn preload"));
+        assert!(preload_items(None, false).is_empty());
+    }
+
+    #[test]
+    fn registering_natives_shares_on_load_with_preloading() {
+        let items = on_load_items(None, false, true);
+        assert_eq!(items.len(), 2);
+        assert!(items[0].contains("pub fn register_natives"));
+        assert!(items[0].contains("& [NATIVE]"));
+        assert!(items[1].contains("on_load (vm , register_natives)"));
+
+        let items = on_load_items(Some(&["com.example.Registry"]), false, true);
+        assert_eq!(items.len(), 3);
+        assert!(items[2].contains("preload (env) . and_then (| () | register_natives (env))"));
+
+        // Classes preloaded manually are left out of `JNI_OnLoad`
+        let items = on_load_items(Some(&["com.example.Registry"]), true, true);
+        assert!(items[2].contains("on_load (vm , register_natives)"));
+    }
+
+    #[test]
+    fn snake_case_names_are_renamed_to_camel_case() {
+        let rename = |name: &str| RenameRule::CamelCase.apply(name);
+        assert_eq!(rename("parse_json_url"), "parseJsonUrl");
+        assert_eq!(rename("get_2d_point"), "get2dPoint");
+        assert_eq!(rename("to_HTML"), "toHTML");
+        assert_eq!(rename("alreadyCamel"), "alreadyCamel");
+        assert_eq!(rename("_internal_id"), "_internalId");
+        assert_eq!(rename("double__underscore_"), "doubleUnderscore");
+    }
+
+    #[test]
+    fn rename_all_accepts_camel_case_only() {
+        assert_eq!(BridgeOptions::parse(quote::quote! { rename_all = "camelCase" }).rename_all, Some(RenameRule::CamelCase));
+        assert_eq!(BridgeOptions::parse(quote::quote! {}).rename_all, None);
+        assert!(RenameRule::from_string("PascalCase").is_err());
+    }
+
+    #[test]
+    fn generated_paths_use_configured_crate() {
+        let module: JNIBridgeModule = syn::parse2(quote::quote! {
+            mod jni {
+                #[package(com.example)]
+                pub struct Foo;
+
+                impl Foo {
+                    pub extern "jni" fn length(name: String) -> i32 { name.len() as i32 }
+                }
+            }
+        })
+        .unwrap();
+        let options = BridgeOptions::parse(quote::quote! { crate = "my_facade::robusta", register_natives });
+        let expanded = ModTransformer::new(module).with_options(options).transform_module().to_string();
+
+        assert!(expanded.contains("my_facade :: robusta :: natives :: register_natives"));
+        assert!(expanded.contains("< String as my_facade :: robusta :: convert :: TryFromJavaValue"));
+        assert!(!expanded.contains(":: robusta_jni"));
+    }
+
+    #[test]
+    fn trait_impls_are_kept_as_written() {
+        let display_impl: ItemImpl = parse_quote! {
+            #[allow(unused)]
+            impl std::fmt::Display for Foo {
+                #[inline]
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { write!(f, "Foo") }
+            }
+        };
+        let drop_impl: ItemImpl = parse_quote! {
+            impl Drop for Foo {
+                fn drop(&mut self) {}
+            }
+        };
+        let module: JNIBridgeModule = syn::parse2(quote::quote! {
+            mod jni {
+                #[package(com.example)]
+                pub struct Foo;
+
+                impl Foo {
+                    pub extern "jni" fn answer() -> i32 { 42 }
+                }
+
+                #display_impl
+                #drop_impl
+            }
+        })
+        .unwrap();
+        let expanded: ItemMod = syn::parse2(ModTransformer::new(module).transform_module()).unwrap();
+
+        let trait_impls: Vec<String> = expanded
+            .content
+            .unwrap()
+            .1
+            .iter()
+            .filter_map(|item| match item {
+                Item::Impl(i) if i.trait_.is_some() => Some(i.to_token_stream().to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(trait_impls, [display_impl.to_token_stream().to_string(), drop_impl.to_token_stream().to_string()]);
+    }
+}
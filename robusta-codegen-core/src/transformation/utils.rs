@@ -0,0 +1,1263 @@
+use darling::util::Flag;
+use darling::FromMeta;
+use proc_macro2::{Group, Ident, Spacing, Span, TokenStream, TokenTree};
+use proc_macro_error::emit_error;
+use quote::ToTokens;
+use syn::{parse_quote, Attribute, Expr, ExprLit, ExprUnary, FnArg, GenericArgument, ImplItemMethod, Lit, LitStr, Meta, MetaList, MetaNameValue, NestedMeta, PatType, Path, PathArguments, PathSegment, Receiver, Signature, Type, TypeReference, UnOp};
+use syn::fold::{self, Fold};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+use crate::stubs::JavaType;
+use crate::transformation::context::StructContext;
+use crate::transformation::{CallTypeAttribute, JAVA_RESERVED_WORDS};
+
+/// The `#[call_type]` attribute among `attrs`, of a method, an impl block or the bridge module.
+pub(crate) fn get_call_type(attrs: &[Attribute]) -> Option<CallTypeAttribute> {
+    attrs.iter().find(|a| a.path.is_ident("call_type")).and_then(|call_type_attr| {
+        syn::parse2(call_type_attr.to_token_stream()).map_err(|e| {
+            emit_error!(e.span(), e);
+            e
+        }).ok()
+    })
+}
+
+/// Deprecation details of an exported method, as declared by Rust's own `#[deprecated]` attribute.
+#[derive(Clone, Default, FromMeta)]
+#[darling(default)]
+pub(crate) struct Deprecation {
+    pub(crate) since: Option<String>,
+    pub(crate) note: Option<String>,
+}
+
+impl Deprecation {
+    /// Human-readable description, e.g. "deprecated since 1.2: use `bar` instead".
+    pub(crate) fn describe(&self) -> String {
+        let mut description = String::from("deprecated");
+        if let Some(since) = &self.since {
+            description.push_str(&format!(" since {}", since));
+        }
+        if let Some(note) = &self.note {
+            description.push_str(&format!(": {}", note));
+        }
+
+        description
+    }
+}
+
+/// Parses every accepted form of `#[deprecated]` (bare, `= "note"` and `(since = "..", note = "..")`).
+pub(crate) fn get_deprecation(attrs: &[Attribute]) -> Option<Deprecation> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("deprecated"))?;
+
+    match attr.parse_meta() {
+        Ok(Meta::Path(_)) => Some(Deprecation::default()),
+        Ok(Meta::NameValue(MetaNameValue { lit: Lit::Str(note), .. })) => Some(Deprecation {
+            since: None,
+            note: Some(note.value()),
+        }),
+        Ok(meta) => Deprecation::from_meta(&meta)
+            .map_err(|e| emit_error!(attr, "invalid `deprecated` attribute ({})", e))
+            .ok(),
+        Err(e) => {
+            emit_error!(e.span(), "invalid `deprecated` attribute ({})", e);
+            None
+        }
+    }
+}
+
+/// Options of `#[field]`, which turns an imported method into an accessor of a Java field.
+#[derive(Clone, Default, FromMeta)]
+#[darling(default)]
+pub(crate) struct FieldAttribute {
+    /// Java name of the field, derived from the method name if omitted.
+    pub(crate) name: Option<String>,
+    #[darling(rename = "static")]
+    pub(crate) is_static: Flag,
+}
+
+/// Parses every accepted form of `#[field]` (bare and `(name = "..", static)`), returning the attribute with its options.
+pub(crate) fn get_field_attribute(attrs: &[Attribute]) -> Option<(&Attribute, FieldAttribute)> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("field"))?;
+
+    let field = match attr.parse_meta() {
+        Ok(Meta::Path(_)) => Some(FieldAttribute::default()),
+        Ok(meta) => FieldAttribute::from_meta(&meta)
+            .map_err(|e| emit_error!(attr, "invalid `field` attribute ({})", e))
+            .ok(),
+        Err(e) => {
+            emit_error!(e.span(), "invalid `field` attribute ({})", e);
+            None
+        }
+    };
+
+    field.map(|f| (attr, f))
+}
+
+/// Toolkit thread an imported method is called on, from `#[dispatch(swing)]` or `#[dispatch(javafx)]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Dispatch {
+    Swing,
+    JavaFx,
+}
+
+impl Dispatch {
+    /// The matching `robusta_jni::dispatch::Toolkit`.
+    pub(crate) fn toolkit(self) -> Path {
+        match self {
+            Dispatch::Swing => parse_quote! { ::robusta_jni::dispatch::Toolkit::Swing },
+            Dispatch::JavaFx => parse_quote! { ::robusta_jni::dispatch::Toolkit::JavaFx },
+        }
+    }
+}
+
+/// Parses the `#[dispatch(...)]` attribute of an imported method, returning the attribute with the selected toolkit.
+pub(crate) fn get_dispatch(attrs: &[Attribute]) -> Option<(&Attribute, Dispatch)> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("dispatch"))?;
+
+    let toolkit = match attr.parse_meta() {
+        Ok(Meta::List(MetaList { nested, .. })) if nested.len() == 1 => match nested.first() {
+            Some(NestedMeta::Meta(Meta::Path(p))) if p.is_ident("swing") => Some(Dispatch::Swing),
+            Some(NestedMeta::Meta(Meta::Path(p))) if p.is_ident("javafx") => Some(Dispatch::JavaFx),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    match toolkit {
+        Some(toolkit) => Some((attr, toolkit)),
+        None => {
+            emit_error!(attr, "expected a toolkit, as in `#[dispatch(swing)]` or `#[dispatch(javafx)]`");
+            None
+        }
+    }
+}
+
+/// Parses the `#[export_visibility(hidden)]` attribute of an exported method or `impl` block, returning it if valid.
+pub(crate) fn get_hidden_export(attrs: &[Attribute]) -> Option<&Attribute> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("export_visibility"))?;
+
+    match attr.parse_meta() {
+        Ok(Meta::List(MetaList { nested, .. })) if nested.len() == 1 && matches!(nested.first(), Some(NestedMeta::Meta(Meta::Path(p))) if p.is_ident("hidden")) => Some(attr),
+        _ => {
+            emit_error!(attr, "expected `#[export_visibility(hidden)]`";
+                help = "to export a method under another symbol, use `#[export_name = \"...\"]`");
+            None
+        }
+    }
+}
+
+/// Options of `#[retry]`, which calls an imported method again when it throws one of the listed exceptions.
+#[derive(Clone, FromMeta)]
+pub(crate) struct RetryAttribute {
+    /// Number of retries after the first call.
+    pub(crate) times: u32,
+    /// Java names of the exception classes retried on, e.g. `java.lang.IllegalStateException`.
+    #[darling(multiple)]
+    pub(crate) on: Vec<LitStr>,
+    #[darling(default)]
+    pub(crate) backoff_ms: u64,
+}
+
+impl RetryAttribute {
+    /// Binary names of the exception classes retried on, e.g. `java/lang/IllegalStateException`.
+    pub(crate) fn binary_names(&self) -> Vec<String> {
+        self.on.iter().map(|c| c.value().replace('.', "/")).collect()
+    }
+}
+
+/// Parses the `#[retry(...)]` attribute of an imported method, returning the attribute with its options.
+pub(crate) fn get_retry(attrs: &[Attribute]) -> Option<(&Attribute, RetryAttribute)> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("retry"))?;
+
+    let retry = match attr.parse_meta().map(|meta| RetryAttribute::from_meta(&meta)) {
+        Ok(Ok(retry)) => retry,
+        Ok(Err(e)) => {
+            emit_error!(attr, "invalid `retry` attribute ({})", e;
+                help = "use `#[retry(times = 3, on = \"java.lang.IllegalStateException\", backoff_ms = 10)]`");
+            return None;
+        }
+        Err(e) => {
+            emit_error!(e.span(), "invalid `retry` attribute ({})", e);
+            return None;
+        }
+    };
+
+    if retry.on.is_empty() {
+        emit_error!(attr, "`#[retry]` needs the exception classes to retry on";
+            help = "add them with `on = \"java.lang.IllegalStateException\"`, once for each class");
+        return None;
+    }
+    let mut valid = true;
+    for class in &retry.on {
+        let name = class.value();
+        if name.contains('/') {
+            emit_error!(class, "exception classes are given by their Java name";
+                help = "use `on = \"{}\"`", name.replace('/', "."));
+            valid = false;
+        } else if name.is_empty() || name.split('.').any(|s| s.is_empty() || !s.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')) {
+            emit_error!(class, "`{}` is not a valid Java class name", name);
+            valid = false;
+        }
+    }
+
+    Some((attr, retry)).filter(|_| valid)
+}
+
+/// Parses the `#[java_name = "..."]` attribute of a method, returning the name of its Java counterpart.
+pub(crate) fn get_java_name(attrs: &[Attribute]) -> Option<LitStr> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("java_name"))?;
+
+    let name = match attr.parse_meta() {
+        Ok(Meta::NameValue(MetaNameValue { lit: Lit::Str(name), .. })) => name,
+        _ => {
+            emit_error!(attr, "expected a Java method name, as in `#[java_name = \"getName\"]`");
+            return None;
+        }
+    };
+
+    let value = name.value();
+    let error = if value.contains('.') {
+        Some("Java method names can't contain dots".to_string())
+    } else if value.contains('-') {
+        Some("Java method names can't contain dashes".to_string())
+    } else if !value.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+        || !value.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    {
+        Some(format!("`{}` is not a valid Java method name", value))
+    } else if JAVA_RESERVED_WORDS.contains(&value.as_str()) {
+        Some(format!("`{}` is a reserved Java keyword", value))
+    } else {
+        None
+    };
+
+    match error {
+        Some(e) => {
+            emit_error!(name, e);
+            None
+        }
+        None => Some(name),
+    }
+}
+
+macro_rules! parse_quote_spanned {
+    ($span:expr => $($tt:tt)*) => {
+        syn::parse2(quote::quote_spanned!($span => $($tt)*)).unwrap_or_else(|e| panic!("{}", e))
+    };
+}
+
+/// Parses the `#[default = ...]` attribute of an exported method parameter, validating the literal against
+/// the parameter type when this is one of the library-provided ones.
+///
+/// String literals are taken verbatim for `String` parameters, and parsed as Rust expressions otherwise
+/// (i.e. `#[default = "100"]` is the same as `#[default = 100]`).
+pub(crate) fn get_default_value(param: &PatType) -> Option<Expr> {
+    let attr = param.attrs.iter().find(|a| a.path.is_ident("default"))?;
+
+    let lit = match attr.parse_meta() {
+        Ok(Meta::NameValue(MetaNameValue { lit, .. })) => lit,
+        _ => {
+            emit_error!(attr, "expected a default value, as in `#[default = \"...\"]`");
+            return None;
+        }
+    };
+
+    let type_name = match &*param.ty {
+        Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    };
+
+    if let (Some("String"), Lit::Str(s)) = (type_name.as_deref(), &lit) {
+        return Some(parse_quote_spanned! { lit.span() => <::robusta_jni::__private::String as ::core::convert::From<_>>::from(#s) });
+    }
+
+    let value: Expr = match &lit {
+        Lit::Str(s) => match s.parse() {
+            Ok(e) => e,
+            Err(e) => {
+                emit_error!(s, "invalid default value ({})", e);
+                return None;
+            }
+        },
+        l => parse_quote! { #l },
+    };
+
+    let (is_negative, value_lit) = match &value {
+        Expr::Lit(ExprLit { lit, .. }) => (false, Some(lit)),
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) => match &**expr {
+            Expr::Lit(ExprLit { lit, .. }) => (true, Some(lit)),
+            _ => (true, None),
+        },
+        _ => (false, None),
+    };
+
+    let integer_range: Option<(i128, i128)> = match type_name.as_deref() {
+        Some("i8") | Some("jbyte") => Some((i8::MIN.into(), i8::MAX.into())),
+        Some("i16") | Some("jshort") => Some((i16::MIN.into(), i16::MAX.into())),
+        Some("i32") | Some("jint") => Some((i32::MIN.into(), i32::MAX.into())),
+        Some("i64") | Some("jlong") => Some((i64::MIN.into(), i64::MAX.into())),
+        Some("u32") => Some((0, u32::MAX.into())),
+        Some("u64") | Some("usize") => Some((0, u64::MAX.into())),
+        _ => None,
+    };
+
+    let type_name = type_name.unwrap_or_default();
+    let is_valid = match (type_name.as_str(), integer_range, value_lit) {
+        (_, Some((min, max)), Some(Lit::Int(i))) => i
+            .base10_parse::<i128>()
+            .map(|v| if is_negative { -v } else { v })
+            .is_ok_and(|v| min <= v && v <= max),
+        (_, Some(_), _) => false,
+        ("f32", _, Some(Lit::Float(_)))
+        | ("f64", _, Some(Lit::Float(_)))
+        | ("jfloat", _, Some(Lit::Float(_)))
+        | ("jdouble", _, Some(Lit::Float(_)))
+        | ("f32", _, Some(Lit::Int(_)))
+        | ("f64", _, Some(Lit::Int(_)))
+        | ("jfloat", _, Some(Lit::Int(_)))
+        | ("jdouble", _, Some(Lit::Int(_))) => true,
+        ("f32", _, _) | ("f64", _, _) | ("jfloat", _, _) | ("jdouble", _, _) => false,
+        ("bool", _, Some(Lit::Bool(_))) => !is_negative,
+        ("char", _, Some(Lit::Char(_))) => !is_negative,
+        ("bool", _, _) | ("char", _, _) | ("String", _, _) => false,
+        _ => true,
+    };
+
+    if is_valid {
+        Some(value)
+    } else {
+        emit_error!(lit, "default value `{}` is not a valid `{}`", value.to_token_stream(), type_name);
+        None
+    }
+}
+
+#[derive(FromMeta)]
+struct InternCacheOptions {
+    size: usize,
+}
+
+/// Parses the `#[intern_cache(size = ...)]` attribute of an exported method parameter, returning the cache size.
+///
+/// Only `Arc<str>` parameters can be cached.
+pub(crate) fn get_intern_cache_size(param: &PatType) -> Option<usize> {
+    let attr = param.attrs.iter().find(|a| a.path.is_ident("intern_cache"))?;
+
+    let options = match attr.parse_meta() {
+        Ok(meta) => InternCacheOptions::from_meta(&meta)
+            .map_err(|e| emit_error!(attr, "invalid `intern_cache` attribute ({})", e))
+            .ok()?,
+        Err(e) => {
+            emit_error!(e.span(), "invalid `intern_cache` attribute ({})", e);
+            return None;
+        }
+    };
+
+    let is_arc_str = match &*param.ty {
+        Type::Path(p) => p
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.to_token_stream().to_string() == "Arc < str >"),
+        _ => false,
+    };
+
+    if !is_arc_str {
+        emit_error!(param.ty, "`#[intern_cache]` parameters must be of type `Arc<str>`");
+        None
+    } else if options.size == 0 {
+        emit_error!(attr, "`intern_cache` size must be greater than zero");
+        None
+    } else {
+        Some(options.size)
+    }
+}
+
+#[derive(Default, FromMeta)]
+#[darling(default)]
+struct ConvertOptions {
+    with: Option<LitStr>,
+    into_with: Option<LitStr>,
+    source: Option<LitStr>,
+    target: Option<LitStr>,
+}
+
+/// A function converting a parameter of an exported method from its JNI value, or its return value to a JNI value, given with
+/// `#[convert(with = "...")]` on the parameter or `#[convert(into_with = "...")]` on the method.
+#[derive(Clone)]
+pub(crate) struct Converter {
+    /// Path of the function, spanned at the attribute so that errors about it point there.
+    pub(crate) function: Path,
+    /// JNI type Java passes or gets back: the `source` or `target` of the attribute, `JObject<'env>` if it has none. Its `'_`
+    /// lifetimes are mapped to `'env` like those of parameter types, but it must name them: `JString` has no lifetime to map.
+    pub(crate) jni_type: Type,
+}
+
+/// Parses the `#[convert]` attribute among `attrs`, of a parameter (`with` and `source`) or of a method (`into_with` and `target`).
+pub(crate) fn get_converter(attrs: &[Attribute], is_param: bool) -> Option<Converter> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("convert"))?;
+
+    let options = match attr.parse_meta() {
+        Ok(meta) => ConvertOptions::from_meta(&meta)
+            .map_err(|e| emit_error!(attr, "invalid `convert` attribute ({})", e))
+            .ok()?,
+        Err(e) => {
+            emit_error!(e.span(), "invalid `convert` attribute ({})", e);
+            return None;
+        }
+    };
+
+    let (function, jni_type, misplaced) = if is_param {
+        (options.with, options.source, options.into_with.is_some() || options.target.is_some())
+    } else {
+        (options.into_with, options.target, options.with.is_some() || options.source.is_some())
+    };
+    if misplaced {
+        if is_param {
+            emit_error!(attr, "`into_with` and `target` convert return values";
+                help = "put `#[convert(into_with = \"...\")]` on the method, and use `with` and `source` on parameters");
+        } else {
+            emit_error!(attr, "`with` and `source` convert parameters";
+                help = "put `#[convert(with = \"...\")]` on the parameter, and use `into_with` and `target` on the method");
+        }
+        return None;
+    }
+
+    let function = match function {
+        Some(function) => function,
+        None if is_param => {
+            emit_error!(attr, "`#[convert]` needs the function converting the parameter";
+                help = "add `with = \"...\"`, naming a `fn(Source, JNIEnv<'env>) -> T`");
+            return None;
+        }
+        None => {
+            emit_error!(attr, "`#[convert]` needs the function converting the return value";
+                help = "add `into_with = \"...\"`, naming a `fn(T, JNIEnv<'env>) -> Target`");
+            return None;
+        }
+    };
+
+    // Parsed tokens get the span of the string, so that rustc reports ill-typed functions at the attribute
+    let function: Path = match function.parse() {
+        Ok(path) => path,
+        Err(_) => {
+            emit_error!(function, "`{}` isn't a path to a function", function.value());
+            return None;
+        }
+    };
+    let jni_type = match jni_type {
+        Some(ty) => match ty.parse() {
+            Ok(ty) => ty,
+            Err(_) => {
+                emit_error!(ty, "`{}` isn't a type", ty.value();
+                    help = "name the JNI type Java passes, e.g. `JString<'env>` or `jint`");
+                return None;
+            }
+        },
+        None => parse_quote_spanned! { attr.span() => ::robusta_jni::jni::objects::JObject<'env> },
+    };
+
+    Some(Converter { function, jni_type })
+}
+
+/// JNI type of the value converted by the `#[convert]` attribute among `attrs`, of a parameter or of a method, for Java stubs.
+///
+/// Invalid attributes are reported by [`get_converter`].
+pub(crate) fn get_converted_jni_type(attrs: &[Attribute], is_param: bool) -> Option<Type> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("convert"))?;
+    let options = ConvertOptions::from_meta(&attr.parse_meta().ok()?).ok()?;
+
+    match if is_param { options.source } else { options.target } {
+        Some(ty) => ty.parse().ok(),
+        None => Some(parse_quote! { JObject }),
+    }
+}
+
+/// A `#[truncate]`, `#[saturate]` or `#[reinterpret]` attribute, converting an integer parameter (or, on the method, the return value)
+/// of an exported method through one of the wrappers of `robusta_jni::convert::numeric`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NumericConversion {
+    Truncate,
+    Saturate,
+    Reinterpret,
+}
+
+impl NumericConversion {
+    const ALL: [NumericConversion; 3] = [NumericConversion::Truncate, NumericConversion::Saturate, NumericConversion::Reinterpret];
+
+    fn attribute_name(self) -> &'static str {
+        match self {
+            NumericConversion::Truncate => "truncate",
+            NumericConversion::Saturate => "saturate",
+            NumericConversion::Reinterpret => "reinterpret",
+        }
+    }
+
+    pub(crate) fn is_attribute(attr: &Attribute) -> bool {
+        NumericConversion::ALL.iter().any(|c| attr.path.is_ident(c.attribute_name()))
+    }
+
+    /// The conversion selected in `attrs`, without any validation.
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> Option<NumericConversion> {
+        NumericConversion::ALL.iter().copied().find(|c| attrs.iter().any(|a| a.path.is_ident(c.attribute_name())))
+    }
+
+    /// Name of the Java primitive a value of type `ty` is converted to, if the conversion supports `ty`.
+    pub(crate) fn java_primitive(self, ty: &Type) -> Option<&'static str> {
+        let type_name = match ty {
+            Type::Path(p) if p.qself.is_none() => p.path.get_ident()?.to_string(),
+            _ => return None,
+        };
+
+        match (self, type_name.as_str()) {
+            (NumericConversion::Truncate, "i64" | "u32" | "u64" | "usize") => Some("int"),
+            (NumericConversion::Saturate, "i64" | "u32" | "u64" | "usize") => Some("int"),
+            (NumericConversion::Reinterpret, "u8") => Some("byte"),
+            (NumericConversion::Reinterpret, "u16") => Some("short"),
+            (NumericConversion::Reinterpret, "u32") => Some("int"),
+            (NumericConversion::Reinterpret, "u64") => Some("long"),
+            _ => None,
+        }
+    }
+
+    /// The wrapper converting values of type `ty`, after reporting unsupported types.
+    pub(crate) fn wrapper_type(self, ty: &Type) -> Option<Type> {
+        if self.java_primitive(ty).is_none() {
+            let supported_types = match self {
+                NumericConversion::Truncate | NumericConversion::Saturate => "`i64`, `u32`, `u64` and `usize`",
+                NumericConversion::Reinterpret => "`u8`, `u16`, `u32` and `u64`",
+            };
+
+            emit_error!(ty, "`#[{}]` can't be used on values of type `{}`", self.attribute_name(), ty.to_token_stream();
+                note = "supported types are {}", supported_types);
+            return None;
+        }
+
+        let wrapper = self.wrapper();
+        Some(parse_quote_spanned! { ty.span() => #wrapper<#ty> })
+    }
+
+    /// Path of the wrapper, which is also its constructor.
+    pub(crate) fn wrapper(self) -> Path {
+        match self {
+            NumericConversion::Truncate => parse_quote! { ::robusta_jni::convert::numeric::Truncate },
+            NumericConversion::Saturate => parse_quote! { ::robusta_jni::convert::numeric::Saturate },
+            NumericConversion::Reinterpret => parse_quote! { ::robusta_jni::convert::numeric::Reinterpret },
+        }
+    }
+}
+
+/// Path of the wrapper converting `u32`, `u64` and `usize` values without a `#[truncate]`, `#[saturate]` or `#[reinterpret]`
+/// attribute, which widens them to `long`.
+pub(crate) fn widen_wrapper() -> Path {
+    parse_quote! { ::robusta_jni::convert::numeric::Widen }
+}
+
+/// The type of the wrapper widening values of type `ty` to `long`, if `ty` is `u32`, `u64` or `usize`.
+///
+/// These have no conversion trait implementations of their own, which would make `.into()` calls on integers ambiguous
+/// wherever the traits are in scope.
+pub(crate) fn widened_type(ty: &Type) -> Option<Type> {
+    match ty {
+        Type::Path(p) if p.qself.is_none() && ["u32", "u64", "usize"].iter().any(|t| p.path.is_ident(t)) => {
+            let wrapper = widen_wrapper();
+            Some(parse_quote_spanned! { ty.span() => #wrapper<#ty> })
+        }
+        Type::Group(g) => widened_type(&g.elem),
+        _ => None,
+    }
+}
+
+/// Parses the `#[truncate]`, `#[saturate]` or `#[reinterpret]` attribute in `attrs`, reporting duplicates and arguments.
+pub(crate) fn get_numeric_conversion(attrs: &[Attribute]) -> Option<NumericConversion> {
+    let mut attributes = attrs.iter().filter(|a| NumericConversion::is_attribute(a));
+    let attr = attributes.next()?;
+
+    if let Some(other) = attributes.next() {
+        emit_error!(other, "only one of `#[truncate]`, `#[saturate]` and `#[reinterpret]` can be used on the same value");
+    }
+    if !attr.tokens.is_empty() {
+        emit_error!(attr.tokens, "`#[{}]` doesn't take arguments", attr.path.to_token_stream());
+    }
+
+    NumericConversion::from_attrs(std::slice::from_ref(attr))
+}
+
+/// The value type of a return type spelled as `Result<T, ...>`, including single-parameter aliases such as `jni::errors::Result<T>`.
+pub(crate) fn get_result_value_type(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.last()?,
+        _ => return None,
+    };
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(a) if segment.ident == "Result" => a.args.iter().find_map(|a| match a {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the `T` and `E` types of a return type spelled as `Result<T, E>`.
+///
+/// Single-parameter aliases such as `jni::errors::Result<T>` don't match, and are converted through their `TryIntoJavaValue` implementation instead.
+pub(crate) fn get_result_types(ty: &Type) -> Option<(&Type, &Type)> {
+    let segment = match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.last()?,
+        _ => return None,
+    };
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(a) if segment.ident == "Result" => {
+            let mut types = a.args.iter().filter_map(|a| match a {
+                GenericArgument::Type(t) => Some(t),
+                _ => None,
+            });
+
+            match (types.next(), types.next(), types.next()) {
+                (Some(ok), Some(err), None) => Some((ok, err)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The owned type to use instead of `ty` for values converted from Java, if `ty` borrows strings or slices, e.g. `Vec<String>` for `&[&str]`.
+///
+/// Java values are copied into Rust values, which leaves nothing for a reference to borrow from.
+pub(crate) fn owned_type(ty: &Type) -> Option<Type> {
+    struct Owned {
+        changed: bool,
+    }
+
+    impl Fold for Owned {
+        fn fold_type(&mut self, ty: Type) -> Type {
+            match ty {
+                Type::Reference(r) => match *r.elem {
+                    Type::Path(p) if p.qself.is_none() && p.path.is_ident("str") => {
+                        self.changed = true;
+                        parse_quote! { String }
+                    }
+                    Type::Slice(s) => {
+                        self.changed = true;
+                        let elem = self.fold_type(*s.elem);
+                        parse_quote! { Vec<#elem> }
+                    }
+                    elem => Type::Reference(TypeReference {
+                        elem: Box::new(self.fold_type(elem)),
+                        ..r
+                    }),
+                },
+                ty => fold::fold_type(self, ty),
+            }
+        }
+    }
+
+    let mut owned = Owned { changed: false };
+    let ty = owned.fold_type(ty.clone());
+    Some(ty).filter(|_| owned.changed)
+}
+
+/// The `String` type to convert `value` of type `ty` to Java as, and the expression converting it, if `ty` is `&str` or `Cow<str>`,
+/// or a `Vec` or the value of a `Result` of them, e.g. `Vec<String>` for `Vec<&str>`.
+///
+/// These have no conversion trait implementations of their own: `"...".into()` would otherwise be ambiguous between `Into` and
+/// `IntoJavaValue` wherever both traits are in scope.
+pub(crate) fn owned_string_value(ty: &Type, value: Expr) -> Option<(Type, Expr)> {
+    fn first_type_arg<'a>(ty: &'a Type, ident: &str) -> Option<&'a Type> {
+        match ty {
+            Type::Path(p) if p.qself.is_none() => match p.path.segments.last() {
+                Some(PathSegment { ident: i, arguments: PathArguments::AngleBracketed(a) }) if i == ident => a.args.iter().find_map(|a| match a {
+                    GenericArgument::Type(t) => Some(t),
+                    _ => None,
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+    fn is_borrowed_string(ty: &Type) -> bool {
+        let is_str = |ty: &Type| matches!(ty, Type::Path(p) if p.qself.is_none() && p.path.is_ident("str"));
+        match ty {
+            Type::Reference(r) => r.mutability.is_none() && is_str(&r.elem),
+            Type::Group(g) => is_borrowed_string(&g.elem),
+            ty => first_type_arg(ty, "Cow").is_some_and(is_str),
+        }
+    }
+
+    if is_borrowed_string(ty) {
+        Some((parse_quote! { ::robusta_jni::__private::String }, parse_quote! { <::robusta_jni::__private::String as ::core::convert::From<_>>::from(#value) }))
+    } else if first_type_arg(ty, "Vec").is_some_and(is_borrowed_string) {
+        Some((parse_quote! { ::robusta_jni::__private::Vec<::robusta_jni::__private::String> }, parse_quote! { ::robusta_jni::__private::owned_strings(#value) }))
+    } else {
+        let (ok_type, err_type) = get_result_types(ty)?;
+        let (owned, conversion) = owned_string_value(ok_type, parse_quote! { v })?;
+        Some((parse_quote! { ::core::result::Result<#owned, #err_type> }, parse_quote! { ::core::result::Result::map(#value, |v| #conversion) }))
+    }
+}
+
+/// `ty` if it is `&str` or `&[u8]`, the types exported methods can borrow from Java values for the duration of a call.
+pub(crate) fn borrowed_type(ty: &Type) -> Option<&TypeReference> {
+    let is_ident = |ty: &Type, ident: &str| matches!(ty, Type::Path(p) if p.qself.is_none() && p.path.is_ident(ident));
+
+    match ty {
+        Type::Reference(r) if r.mutability.is_none() => match &*r.elem {
+            elem if is_ident(elem, "str") => Some(r),
+            Type::Slice(s) if is_ident(&s.elem, "u8") => Some(r),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether `ty` is a Java primitive type whose conversions are the identity, e.g. `i64` or `jint`, which exported methods
+/// can take and return without conversion.
+pub(crate) fn is_passthrough_type(ty: &Type) -> bool {
+    const PASSTHROUGH_TYPES: &[&str] = &[
+        "i8", "i16", "i32", "i64", "f32", "f64", "jbyte", "jshort", "jint", "jlong", "jfloat", "jdouble",
+    ];
+
+    match ty {
+        Type::Path(p) if p.qself.is_none() => p
+            .path
+            .segments
+            .last()
+            .is_some_and(|s| s.arguments.is_empty() && PASSTHROUGH_TYPES.iter().any(|t| s.ident == t)),
+        Type::Group(g) => is_passthrough_type(&g.elem),
+        Type::Paren(p) => is_passthrough_type(&p.elem),
+        _ => false,
+    }
+}
+
+/// Whether `ty` is a raw object reference, `JObject` or `jobject`, which is passed to and from Java as is.
+pub(crate) fn is_raw_object_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.last().is_some_and(|s| s.ident == "JObject" || s.ident == "jobject"),
+        Type::Group(g) => is_raw_object_type(&g.elem),
+        Type::Paren(p) => is_raw_object_type(&p.elem),
+        _ => false,
+    }
+}
+
+/// Descriptor given with `#[sig("Lcom/example/Callback;")]` among `attrs`, if it's the descriptor of a class or array type.
+///
+/// Invalid attributes are reported by [`check_sig_descriptor`].
+pub(crate) fn get_sig_descriptor(attrs: &[Attribute]) -> Option<String> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("sig"))?;
+    let descriptor = attr.parse_args::<LitStr>().ok()?.value();
+
+    match JavaType::from_descriptor(&descriptor)? {
+        JavaType::Object(_) | JavaType::Array(_) => Some(descriptor),
+        _ => None,
+    }
+}
+
+/// Reports the `#[sig]` attribute among `attrs` if it's invalid, or if `ty`, the type it gives the descriptor of (or its `T` if
+/// it's a `Result<T, ...>` or `JniResult<T>`), isn't a raw object reference. Returns the descriptor otherwise.
+pub(crate) fn check_sig_descriptor(attrs: &[Attribute], ty: Option<&Type>) -> Option<String> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("sig"))?;
+    let descriptor = match attr.parse_args::<LitStr>() {
+        Ok(descriptor) => descriptor,
+        Err(_) => {
+            emit_error!(attr, "expected a JNI type descriptor";
+                help = "write it as a string, e.g. `#[sig(\"Ljava/lang/Runnable;\")]`");
+            return None;
+        }
+    };
+
+    match JavaType::from_descriptor(&descriptor.value()) {
+        Some(JavaType::Object(_)) | Some(JavaType::Array(_)) => {}
+        Some(_) => {
+            emit_error!(descriptor, "`{}` isn't the descriptor of a class or array type", descriptor.value();
+                note = "`#[sig]` parameters and return values are Java objects");
+            return None;
+        }
+        None => {
+            emit_error!(descriptor, "`{}` isn't a valid JNI type descriptor", descriptor.value();
+                help = "classes are written `L` + the class name with slashes + `;`, e.g. `Ljava/lang/Runnable;`, and arrays `[` + the element type");
+            return None;
+        }
+    }
+
+    let value_type = ty.map(|ty| match ty {
+        Type::Path(p) if p.path.segments.last().is_some_and(|s| s.ident == "JniResult") => match &p.path.segments.last().unwrap().arguments {
+            PathArguments::AngleBracketed(a) => a.args.iter().find_map(|a| match a {
+                GenericArgument::Type(t) => Some(t),
+                _ => None,
+            }),
+            _ => None,
+        }
+        .unwrap_or(ty),
+        ty => get_result_value_type(ty).unwrap_or(ty),
+    });
+
+    match value_type {
+        Some(ty) if is_raw_object_type(ty) => Some(descriptor.value()),
+        _ => {
+            emit_error!(attr, "`#[sig]` can only be used on `JObject` parameters and return values";
+                note = "the descriptor of other types is the one of their conversion");
+            None
+        }
+    }
+}
+
+/// Attributes of bridged methods and their parameters that are only known to `#[bridge]`.
+pub(crate) const BRIDGE_ATTRIBUTES: &[&str] = &[
+    "attach",
+    "cached",
+    "call_type",
+    "constructor",
+    "convert",
+    "default",
+    "deprecation_warning",
+    "dispatch",
+    "export_visibility",
+    "field",
+    "flatten",
+    "intern_cache",
+    "java_list",
+    "java_name",
+    "reinterpret",
+    "retry",
+    "returns_self",
+    "saturate",
+    "sig",
+    "static_method",
+    "suspend",
+    "synchronized",
+    "truncate",
+];
+
+/// Whether `attr` is one of the [`BRIDGE_ATTRIBUTES`].
+pub(crate) fn is_bridge_attribute(attr: &Attribute) -> bool {
+    BRIDGE_ATTRIBUTES.iter().any(|name| attr.path.is_ident(name))
+}
+
+/// Sorts the attributes of an exported method between the Rust method kept in its `impl` block and the JNI function
+/// generated for it.
+///
+/// Both get every attribute the user wrote (doc comments, `#[inline]`, `#[cfg]`, ...) but the [`BRIDGE_ATTRIBUTES`], except
+/// the ones listed in `#[bridge(wrapper_attributes(...))]`, which only the JNI function gets: with `tracing::instrument`,
+/// the calls from Java are traced with their conversions, rather than twice.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AttributeFilter {
+    /// Paths of the attributes only kept on the JNI function, e.g. `tracing :: instrument`.
+    wrapper_only: Vec<String>,
+}
+
+impl AttributeFilter {
+    pub(crate) fn new(wrapper_only: &[Path]) -> Self {
+        AttributeFilter {
+            wrapper_only: wrapper_only.iter().map(|p| p.to_token_stream().to_string()).collect(),
+        }
+    }
+
+    fn is_wrapper_only(&self, attr: &Attribute) -> bool {
+        let path = attr.path.to_token_stream().to_string();
+        self.wrapper_only.contains(&path)
+    }
+
+    /// Attributes of the Rust method, which isn't exported: its JNI function is.
+    pub(crate) fn method_attributes(&self, attrs: Vec<Attribute>) -> Vec<Attribute> {
+        attrs
+            .into_iter()
+            .filter(|a| !is_bridge_attribute(a) && !self.is_wrapper_only(a))
+            .filter(|a| !a.path.is_ident("export_name"))
+            .collect()
+    }
+
+    /// Attributes of the JNI function, which is never called from Rust, so isn't deprecated itself.
+    pub(crate) fn wrapper_attributes(&self, attrs: Vec<Attribute>) -> Vec<Attribute> {
+        attrs
+            .into_iter()
+            .filter(|a| !is_bridge_attribute(a) && !a.path.is_ident("deprecated"))
+            .collect()
+    }
+}
+
+/// Descriptor of `java.util.List`, which exported methods take `Vec` parameters as.
+pub(crate) const LIST_DESCRIPTOR: &str = "Ljava/util/List;";
+
+/// Whether `ty` is a `Vec`, or an `Option`, `JOption`, `Result` or `JniResult` of one.
+fn is_vec_type(ty: &Type) -> bool {
+    let segment = match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.last(),
+        _ => None,
+    };
+
+    match segment {
+        Some(s) if s.ident == "Vec" => true,
+        Some(s) if ["Option", "JOption", "Result", "JniResult"].iter().any(|w| s.ident == w) => match &s.arguments {
+            PathArguments::AngleBracketed(a) => a.args.iter().find_map(|a| match a {
+                GenericArgument::Type(t) => Some(is_vec_type(t)),
+                _ => None,
+            }) == Some(true),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Descriptor of `java.util.List` if `attrs` have a `#[java_list]` attribute for `ty`, a `Vec` converted to or from an
+/// `ArrayList` otherwise. Reports the attribute if `ty` isn't a `Vec` (or an `Option` or `Result` of one).
+pub(crate) fn check_java_list(attrs: &[Attribute], ty: Option<&Type>) -> Option<String> {
+    let attr = attrs.iter().find(|a| a.path.is_ident("java_list"))?;
+    if !attr.tokens.is_empty() {
+        emit_error!(attr.tokens, "`#[java_list]` takes no arguments");
+    }
+
+    if !ty.is_some_and(is_vec_type) {
+        emit_error!(attr, "`#[java_list]` can only be used on `Vec` parameters and return values";
+            note = "it declares them as `java.util.List`, instead of `java.util.ArrayList`");
+        return None;
+    }
+
+    Some(LIST_DESCRIPTOR.to_string())
+}
+
+/// Reports types of values converted from Java that borrow strings or slices, suggesting their [`owned_type`].
+pub(crate) fn check_owned_type(ty: &Type) {
+    if let Some(owned) = owned_type(ty) {
+        emit_error!(ty, "borrowed strings and slices can't be converted from Java";
+            help = "use `{}` instead", owned.to_token_stream();
+            note = "Java values are copied into Rust values, which leaves nothing to borrow from");
+    }
+}
+
+/// The type of the elements of `JavaArray<T>` in `segment`, ignoring `Option`s around them.
+fn java_array_element(segment: &PathSegment) -> Option<&Type> {
+    fn first_type_arg(segment: &PathSegment) -> Option<&Type> {
+        match &segment.arguments {
+            PathArguments::AngleBracketed(a) => a.args.iter().find_map(|a| match a {
+                GenericArgument::Type(t) => Some(t),
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
+
+    let mut element = first_type_arg(segment).filter(|_| segment.ident == "JavaArray")?;
+    while let Type::Path(p) = element {
+        match p.path.segments.last() {
+            Some(s) if s.ident == "Option" => element = first_type_arg(s)?,
+            _ => break,
+        }
+    }
+
+    Some(element)
+}
+
+/// Reports `JavaArray`s of arrays or `Vec`s in the parameter and return types of `sig`, which have no conversion.
+pub(crate) fn check_array_types(sig: &Signature) {
+    struct NestedArrays;
+
+    impl<'ast> Visit<'ast> for NestedArrays {
+        fn visit_path_segment(&mut self, segment: &'ast PathSegment) {
+            let is_collection = match java_array_element(segment) {
+                Some(Type::Path(p)) => p.path.segments.last().is_some_and(|s| s.ident == "JavaArray" || s.ident == "Vec"),
+                Some(Type::Array(_)) | Some(Type::Slice(_)) => true,
+                _ => false,
+            };
+
+            if is_collection {
+                emit_error!(segment, "`JavaArray` elements can't be arrays or `Vec`s";
+                    note = "nested Java arrays such as `String[][]` are not supported";
+                    help = "use `JavaArray<JObject>`, and convert each element with `TryFromJavaValue`");
+            }
+
+            syn::visit::visit_path_segment(self, segment);
+        }
+    }
+
+    for input in &sig.inputs {
+        if let FnArg::Typed(t) = input {
+            NestedArrays.visit_type(&t.ty);
+        }
+    }
+    NestedArrays.visit_return_type(&sig.output);
+}
+
+/// Reports `CriticalBytes` parameters followed by other parameters, which would be converted inside the critical region.
+pub(crate) fn check_critical_params(sig: &Signature) {
+    let last = sig.inputs.len().saturating_sub(1);
+    for (i, input) in sig.inputs.iter().enumerate() {
+        let is_critical = match input {
+            FnArg::Typed(PatType { ty, .. }) => match &**ty {
+                Type::Path(p) => p.path.segments.last().is_some_and(|s| s.ident == "CriticalBytes"),
+                _ => false,
+            },
+            FnArg::Receiver(_) => false,
+        };
+
+        if is_critical && i != last {
+            emit_error!(input, "`CriticalBytes` must be the last parameter";
+                note = "the parameters after it would be converted inside its critical region, where calling into the JVM isn't allowed";
+                help = "move this parameter after the others");
+        }
+    }
+}
+
+/// Replaces type parameters with the type native functions are bridged with, and `Self` with the bridged struct if set.
+struct BridgedTypes<'a> {
+    params: &'a [(Ident, Type)],
+    self_ty: Option<&'a Path>,
+}
+
+impl<'a> Fold for BridgedTypes<'a> {
+    fn fold_type(&mut self, ty: Type) -> Type {
+        match &ty {
+            Type::Path(p) if p.qself.is_none() => match self.params.iter().find(|(ident, _)| p.path.is_ident(ident)) {
+                Some((_, bridged)) => bridged.clone(),
+                None => match self.self_ty {
+                    Some(self_ty) if p.path.is_ident("Self") => parse_quote_spanned! { p.span() => #self_ty },
+                    _ => fold::fold_type(self, ty),
+                },
+            },
+            _ => fold::fold_type(self, ty),
+        }
+    }
+}
+
+/// `struct_type` with each of the type parameters `params` replaced by the type native functions are bridged with.
+pub(crate) fn bridged_struct_type(struct_type: &Path, params: &[(Ident, Type)]) -> Path {
+    BridgedTypes { params, self_ty: None }.fold_path(struct_type.clone())
+}
+
+/// `method` with the types of an instantiation of its struct in its signature: each of the type parameters `params` replaced by
+/// its type in the instantiation, and `Self` by the instantiated struct `struct_type`.
+pub(crate) fn instantiated_method(method: &ImplItemMethod, params: &[(Ident, Type)], struct_type: &Path) -> ImplItemMethod {
+    let mut bridged_types = BridgedTypes { params, self_ty: Some(struct_type) };
+    ImplItemMethod {
+        sig: bridged_types.fold_signature(method.sig.clone()),
+        ..method.clone()
+    }
+}
+
+/// Reports the first use in `sig` of a type parameter of the struct, directly or through `Self`. Returns whether there's none.
+pub(crate) fn check_struct_type_params(sig: &Signature, context: &StructContext) -> bool {
+    struct TypeParamUse<'a> {
+        params: &'a [(Ident, Type)],
+        found: Option<(Span, String)>,
+    }
+
+    impl<'a, 'ast> Visit<'ast> for TypeParamUse<'a> {
+        fn visit_receiver(&mut self, receiver: &'ast Receiver) {
+            self.found.get_or_insert((receiver.self_token.span(), "Self".to_string()));
+        }
+
+        fn visit_path(&mut self, path: &'ast Path) {
+            if let Some(first) = path.segments.first() {
+                if first.ident == "Self" || self.params.iter().any(|(ident, _)| first.ident == *ident) {
+                    self.found.get_or_insert((first.ident.span(), first.ident.to_string()));
+                }
+            }
+
+            syn::visit::visit_path(self, path);
+        }
+    }
+
+    // The signatures of instantiations have their types, and `Self` is the instantiated struct
+    if context.struct_type_params.is_empty() || context.instantiation.is_some() {
+        return true;
+    }
+
+    let mut visitor = TypeParamUse {
+        params: &context.struct_type_params,
+        found: None,
+    };
+    visitor.visit_signature(sig);
+
+    let (span, name) = match visitor.found {
+        Some(found) => found,
+        None => return true,
+    };
+
+    let type_params = context.struct_type_params.iter().map(|(ident, _)| format!("`{}`", ident)).collect::<Vec<_>>().join(", ");
+    let bridged = match bridged_struct_type(&context.struct_type, &context.struct_type_params).segments.last().map(|s| &s.arguments) {
+        Some(PathArguments::AngleBracketed(a)) => {
+            let args: Vec<String> = a.args.iter().map(|a| a.to_token_stream().to_string().replace(' ', "")).collect();
+            format!("{}<{}>", context.struct_name, args.join(", "))
+        }
+        _ => context.struct_name.clone(),
+    };
+    if name == "Self" {
+        emit_error!(span, "native method `{}` can't use `Self`, which is generic over {}", sig.ident, type_params;
+            note = "Java calls native methods with a single signature, so they can't be generic";
+            help = "move `{}` to an `impl` block for a concrete type, such as `{}`", sig.ident, bridged;
+            help = "or bridge each instantiation of `{}` to its own Java class, with `#[instantiate(OfType = {}<...>)]` on the struct",
+                context.struct_name, context.struct_name);
+    } else {
+        emit_error!(span, "native method `{}` can't use type parameter `{}` of `{}`", sig.ident, name, context.struct_name;
+            note = "Java calls native methods with a single signature, so they can't be generic";
+            help = "move `{}` to an `impl` block for a concrete type, such as `{}`", sig.ident, bridged;
+            help = "or bridge each instantiation of `{}` to its own Java class, with `#[instantiate(OfType = {}<...>)]` on the struct",
+                context.struct_name, context.struct_name);
+    }
+
+    false
+}
+
+/// Rust keywords that can precede a path starting with `::`, e.g. in `as ::robusta_jni::convert::Signature`.
+const KEYWORDS_BEFORE_PATHS: &[&str] = &[
+    "as", "box", "break", "const", "dyn", "else", "for", "if", "impl", "in", "let", "match", "move", "mut", "pub", "ref",
+    "return", "static", "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// `tokens` with every path starting with `::robusta_jni` starting with `crate_path` instead, at any depth.
+///
+/// Paths where `robusta_jni` follows another segment (e.g. `facade::robusta_jni`) are left alone.
+pub(crate) fn rebase_crate_paths(tokens: TokenStream, crate_path: &Path) -> TokenStream {
+    let trees: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut rebased = TokenStream::new();
+    let mut i = 0;
+
+    while i < trees.len() {
+        // `::` continues a path after a segment, or after the closing `>` of generic arguments (but not of `->` or `=>`)
+        let is_leading_colon = match (i.checked_sub(2).map(|p| &trees[p]), i.checked_sub(1).map(|p| &trees[p])) {
+            (_, Some(TokenTree::Ident(ident))) => KEYWORDS_BEFORE_PATHS.contains(&ident.to_string().as_str()),
+            (Some(TokenTree::Punct(arrow)), Some(TokenTree::Punct(punct))) if punct.as_char() == '>' && arrow.spacing() == Spacing::Joint => {
+                matches!(arrow.as_char(), '-' | '=')
+            }
+            (_, Some(TokenTree::Punct(punct))) => punct.as_char() != '>',
+            _ => true,
+        };
+        let is_crate_path = matches!(&trees[i..], [
+            TokenTree::Punct(first),
+            TokenTree::Punct(second),
+            TokenTree::Ident(name),
+            ..
+        ] if first.as_char() == ':' && first.spacing() == Spacing::Joint && second.as_char() == ':' && name == "robusta_jni");
+
+        if is_leading_colon && is_crate_path {
+            crate_path.to_tokens(&mut rebased);
+            i += 3;
+            continue;
+        }
+
+        match &trees[i] {
+            TokenTree::Group(group) => {
+                let mut rebased_group = Group::new(group.delimiter(), rebase_crate_paths(group.stream(), crate_path));
+                rebased_group.set_span(group.span());
+                rebased.extend(std::iter::once(TokenTree::Group(rebased_group)));
+            }
+            tree => rebased.extend(std::iter::once(tree.clone())),
+        }
+        i += 1;
+    }
+
+    rebased
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owned(ty: Type) -> Option<String> {
+        owned_type(&ty).map(|ty| ty.to_token_stream().to_string())
+    }
+
+    #[test]
+    fn borrowed_strings_and_slices_have_owned_types() {
+        assert_eq!(owned(parse_quote! { &str }).as_deref(), Some("String"));
+        assert_eq!(owned(parse_quote! { &[&str] }).as_deref(), Some("Vec < String >"));
+        assert_eq!(owned(parse_quote! { Vec<&'a str> }).as_deref(), Some("Vec < String >"));
+        assert_eq!(owned(parse_quote! { Option<&[i32]> }).as_deref(), Some("Option < Vec < i32 > >"));
+    }
+
+    #[test]
+    fn owned_types_are_left_alone() {
+        assert_eq!(owned(parse_quote! { String }), None);
+        assert_eq!(owned(parse_quote! { Vec<String> }), None);
+        assert_eq!(owned(parse_quote! { JObject<'env> }), None);
+    }
+
+    #[test]
+    fn borrowed_strings_are_converted_to_java_as_strings() {
+        let owned_string = |ty: Type| owned_string_value(&ty, parse_quote! { value }).map(|(ty, _)| ty.to_token_stream().to_string());
+        let string: Type = parse_quote! { ::robusta_jni::__private::String };
+        let strings: Type = parse_quote! { ::robusta_jni::__private::Vec<::robusta_jni::__private::String> };
+
+        assert_eq!(owned_string(parse_quote! { &'a str }), Some(string.to_token_stream().to_string()));
+        assert_eq!(owned_string(parse_quote! { std::borrow::Cow<'_, str> }), Some(string.to_token_stream().to_string()));
+        assert_eq!(owned_string(parse_quote! { Vec<&str> }), Some(strings.to_token_stream().to_string()));
+        assert_eq!(owned_string(parse_quote! { Vec<Cow<str>> }), Some(strings.to_token_stream().to_string()));
+        assert!(owned_string(parse_quote! { Result<&'static str, MyError> }).is_some_and(|ty| ty.ends_with("String , MyError >")));
+        assert_eq!(owned_string(parse_quote! { String }), None);
+        assert_eq!(owned_string(parse_quote! { Cow<[u8]> }), None);
+        assert_eq!(owned_string(parse_quote! { &[&str] }), None);
+    }
+
+    #[test]
+    fn only_str_and_byte_slices_are_borrowed() {
+        let borrowed = |ty: Type| borrowed_type(&ty).is_some();
+        assert!(borrowed(parse_quote! { &str }));
+        assert!(borrowed(parse_quote! { &'a [u8] }));
+        assert!(!borrowed(parse_quote! { &mut [u8] }));
+        assert!(!borrowed(parse_quote! { &[i8] }));
+        assert!(!borrowed(parse_quote! { &[&str] }));
+        assert!(!borrowed(parse_quote! { Option<&str> }));
+    }
+
+    #[test]
+    fn only_java_primitives_pass_through() {
+        let passthrough = |ty: Type| is_passthrough_type(&ty);
+        assert!(passthrough(parse_quote! { i64 }));
+        assert!(passthrough(parse_quote! { f32 }));
+        assert!(passthrough(parse_quote! { ::robusta_jni::jni::sys::jint }));
+        assert!(!passthrough(parse_quote! { bool }));
+        assert!(!passthrough(parse_quote! { u32 }));
+        assert!(!passthrough(parse_quote! { Option<i32> }));
+        assert!(!passthrough(parse_quote! { JniResult<i32> }));
+    }
+
+    #[test]
+    fn bridged_struct_types_replace_type_params() {
+        let params: Vec<(Ident, Type)> = vec![(parse_quote! { T }, parse_quote! { () }), (parse_quote! { U }, parse_quote! { i64 })];
+        let bridged = |path: Path| bridged_struct_type(&path, &params).to_token_stream().to_string();
+        assert_eq!(bridged(parse_quote! { Holder<'env, T, U> }), "Holder < 'env , () , i64 >");
+        assert_eq!(bridged(parse_quote! { Holder<Vec<T>, Option<V> > }), "Holder < Vec < () > , Option < V > >");
+        assert_eq!(bridged(parse_quote! { Holder }), "Holder");
+    }
+
+    #[test]
+    fn java_array_elements_are_found_through_options() {
+        let element = |segment: PathSegment| java_array_element(&segment).map(|t| t.to_token_stream().to_string());
+        assert_eq!(element(parse_quote! { JavaArray<String> }).as_deref(), Some("String"));
+        assert_eq!(element(parse_quote! { JavaArray<Option<JavaArray<i32>>> }).as_deref(), Some("JavaArray < i32 >"));
+        assert_eq!(element(parse_quote! { Vec<String> }), None);
+    }
+
+    #[test]
+    fn dispatch_selects_toolkit() {
+        let dispatch = |attr: Attribute| get_dispatch(std::slice::from_ref(&attr)).map(|(_, d)| d);
+        assert_eq!(dispatch(parse_quote! { #[dispatch(swing)] }), Some(Dispatch::Swing));
+        assert_eq!(dispatch(parse_quote! { #[dispatch(javafx)] }), Some(Dispatch::JavaFx));
+        assert_eq!(dispatch(parse_quote! { #[cached] }), None);
+    }
+
+    #[test]
+    fn retry_classes_are_binary_names() {
+        let attr: Attribute = parse_quote! { #[retry(times = 2, on = "java.lang.IllegalStateException", on = "java.util.Map$Entry")] };
+        let (_, retry) = get_retry(std::slice::from_ref(&attr)).unwrap();
+        assert_eq!(retry.times, 2);
+        assert_eq!(retry.backoff_ms, 0);
+        assert_eq!(retry.binary_names(), ["java/lang/IllegalStateException", "java/util/Map$Entry"]);
+    }
+
+    #[test]
+    fn crate_paths_are_rebased() {
+        let crate_path: Path = parse_quote! { my_facade::robusta };
+        let rebased = |tokens: TokenStream| rebase_crate_paths(tokens, &crate_path).to_string();
+        assert_eq!(
+            rebased(quote::quote! { <Foo as ::robusta_jni::convert::Signature>::SIG_TYPE }),
+            "< Foo as my_facade :: robusta :: convert :: Signature > :: SIG_TYPE"
+        );
+        assert_eq!(
+            rebased(quote::quote! { fn f(env: &::robusta_jni::jni::JNIEnv) { ::robusta_jni::preload::on_load(vm) } }),
+            "fn f (env : & my_facade :: robusta :: jni :: JNIEnv) { my_facade :: robusta :: preload :: on_load (vm) }"
+        );
+        assert_eq!(rebased(quote::quote! { fn f() -> ::robusta_jni::jni::sys::jint }), "fn f () -> my_facade :: robusta :: jni :: sys :: jint");
+        assert_eq!(rebased(quote::quote! { facade::robusta_jni::convert }), "facade :: robusta_jni :: convert");
+        assert_eq!(rebased(quote::quote! { ::robusta_jni_extra::convert }), ":: robusta_jni_extra :: convert");
+    }
+}
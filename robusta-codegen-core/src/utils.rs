@@ -0,0 +1,242 @@
+use std::iter;
+
+use quote::ToTokens;
+
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_quote, Attribute, FnArg, Ident, ItemImpl, Pat, PatIdent, PatType, Path, PathArguments, Signature, Token, Type,
+    TypeReference,
+};
+
+pub fn canonicalize_path(path: &Path) -> Path {
+    let mut result = path.clone();
+    result.segments = result
+        .segments
+        .into_iter()
+        .map(|mut seg| {
+            seg.arguments = PathArguments::None;
+            seg
+        })
+        .collect();
+
+    result
+}
+
+/// Name of the struct of the bridge module `module` that `path` refers to, which is its key in the module's package map.
+///
+/// Generic arguments are ignored, and so are leading `self::` and `crate::` qualifiers and the path of the module itself:
+/// `Foo`, `Foo<'env>`, `self::Foo` and `crate::bridge::Foo` all refer to the struct `Foo` of the module `bridge`. Paths to
+/// other modules (e.g. `super::Foo`) refer to no struct of the module.
+pub fn struct_name(path: &Path, module: &Ident) -> Option<String> {
+    let segments: Vec<&Ident> = path.segments.iter().map(|s| &s.ident).collect();
+    let after_module = segments.iter().rposition(|s| *s == module).map_or(0, |i| i + 1);
+    let qualifiers = segments[after_module..].iter().take_while(|s| **s == "self" || **s == "crate").count();
+
+    match segments[after_module + qualifiers..] {
+        [name] => Some(name.to_string()),
+        _ => None,
+    }
+}
+
+/// `path` as written in diagnostics, e.g. `Holder<String, i32>`.
+pub fn path_name(path: &Path) -> String {
+    path.to_token_stream().to_string().replace(' ', "").replace(',', ", ")
+}
+
+/// Name of the struct of the bridge module `module` that `item_impl` is declared on, see [`struct_name`].
+pub fn impl_struct_name(item_impl: &ItemImpl, module: &Ident) -> Option<String> {
+    match &*item_impl.self_ty {
+        Type::Path(p) => struct_name(&p.path, module),
+        _ => None,
+    }
+}
+
+pub fn is_self_method(signature: &Signature) -> bool {
+    signature.inputs.iter().any(|i| match i {
+        FnArg::Receiver(_) => true,
+        FnArg::Typed(t) => match &*t.pat {
+            Pat::Ident(PatIdent { ident, .. }) => ident == "self",
+            _ => false,
+        },
+    })
+}
+
+/// Whether `ty` is the type at `full_path`, imported (by its last segment) or by its full path.
+fn is_jni_path(ty: &Type, full_path: Path) -> bool {
+    match ty {
+        Type::Path(t) => {
+            let canonicalized_type_path = canonicalize_path(&t.path);
+            let imported_path = full_path.segments.last().map(|s| Path::from(s.ident.clone()));
+
+            canonicalized_type_path == full_path || Some(canonicalized_type_path) == imported_path
+        }
+        _ => false,
+    }
+}
+
+/// Whether `ty` is `JNIEnv`, imported or by its full path.
+fn is_env_path(ty: &Type) -> bool {
+    is_jni_path(ty, parse_quote! { ::robusta_jni::jni::JNIEnv })
+}
+
+/// Whether `ty` is `JClass`, imported or by its full path.
+pub fn is_class_type(ty: &Type) -> bool {
+    is_jni_path(ty, parse_quote! { ::robusta_jni::jni::objects::JClass })
+}
+
+/// Whether `ty` is `JNIEnv` or `&JNIEnv`, the types of explicit environment parameters.
+pub fn is_env_type(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(TypeReference { elem, .. }) => is_env_path(elem),
+        ty => is_env_path(ty),
+    }
+}
+
+/// Whether the explicit environment parameter `env_arg` is a `&JNIEnv`, rather than a `JNIEnv`.
+pub fn is_env_ref(env_arg: &FnArg) -> bool {
+    matches!(env_arg, FnArg::Typed(PatType { ty, .. }) if matches!(&**ty, Type::Reference(_)))
+}
+
+/// Splits the explicit environment parameter, of type `JNIEnv` or `&JNIEnv`, off the signature. It must be the first
+/// parameter, after `self` for methods.
+pub fn get_env_arg(signature: Signature) -> (Signature, Option<FnArg>) {
+    let self_method = is_self_method(&signature);
+
+    // Check whether second argument (first exluding self) is of type JNIEnv, if so we take it out from the signature
+    let possible_env_arg = if !self_method {
+        signature.inputs.iter().next()
+    } else {
+        signature.inputs.iter().nth(1)
+    };
+
+    let has_explicit_env_arg = matches!(possible_env_arg, Some(FnArg::Typed(PatType { ty, .. })) if is_env_type(ty));
+
+    let (transformed_signature, env_arg): (Signature, Option<FnArg>) = if has_explicit_env_arg {
+        let mut inner_signature = signature;
+
+        let mut iter = inner_signature.inputs.into_iter();
+
+        if self_method {
+            let self_arg = iter.next();
+            let env_arg = iter.next();
+
+            inner_signature.inputs = iter::once(self_arg.unwrap()).chain(iter).collect();
+            (inner_signature, env_arg)
+        } else {
+            let env_arg = iter.next();
+            inner_signature.inputs = iter.collect();
+
+            (inner_signature, env_arg)
+        }
+    } else {
+        (signature, None)
+    };
+
+    (transformed_signature, env_arg)
+}
+
+/// Whether the attributes derive the trait `name`, named by any path ending with it.
+pub fn derives(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().filter(|a| a.path.is_ident("derive")).any(|a| {
+        a.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+            .is_ok_and(|paths| paths.iter().any(|p| p.segments.last().is_some_and(|s| s.ident == name)))
+    })
+}
+
+pub fn get_abi(sig: &Signature) -> Option<String> {
+    sig.abi
+        .as_ref()
+        .and_then(|l| l.name.as_ref().map(|n| n.value()))
+}
+
+/// Escapes a Java name for use in the symbol of a native method, following the JNI specification:
+/// `.` and `/` separators become `_`, `_` becomes `_1`, `;` becomes `_2`, `[` becomes `_3` and every other character
+/// that isn't an ASCII letter or digit becomes `_0xxxx`, with `xxxx` its UTF-16 code unit(s) in lowercase hexadecimal.
+pub fn mangle_jni_name(name: &str) -> String {
+    let mut mangled = String::with_capacity(name.len());
+
+    for c in name.chars() {
+        match c {
+            '.' | '/' => mangled.push('_'),
+            '_' => mangled.push_str("_1"),
+            ';' => mangled.push_str("_2"),
+            '[' => mangled.push_str("_3"),
+            c if c.is_ascii_alphanumeric() => mangled.push(c),
+            c => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    mangled.push_str(&format!("_0{:04x}", unit));
+                }
+            }
+        }
+    }
+
+    mangled
+}
+
+/// Symbol of the native function implementing method `method` of class `class` in package `package` (possibly empty),
+/// e.g. `Java_com_example_Foo_bar`.
+pub fn jni_function_name(package: &str, class: &str, method: &str) -> String {
+    ["Java", &mangle_jni_name(package), &mangle_jni_name(class), &mangle_jni_name(method)]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_owned())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn plain_names_are_joined() {
+        assert_eq!(jni_function_name("com.example", "Foo", "bar"), "Java_com_example_Foo_bar");
+        assert_eq!(jni_function_name("", "Foo", "bar"), "Java_Foo_bar");
+    }
+
+    #[test]
+    fn underscores_are_escaped() {
+        assert_eq!(jni_function_name("com.my_company.core", "Foo", "get_name"), "Java_com_my_1company_core_Foo_get_1name");
+    }
+
+    #[test]
+    fn digits_after_underscores_stay_unambiguous() {
+        assert_eq!(mangle_jni_name("foo_1bar"), "foo_11bar");
+        assert_eq!(mangle_jni_name("v_2"), "v_12");
+    }
+
+    #[test]
+    fn non_ascii_characters_are_escaped() {
+        assert_eq!(mangle_jni_name("caffè"), "caff_000e8");
+        assert_eq!(mangle_jni_name("Inner$Class"), "Inner_00024Class");
+        assert_eq!(mangle_jni_name("clef𝄞"), "clef_0d834_0dd1e");
+    }
+
+    #[test]
+    fn descriptor_characters_are_escaped() {
+        assert_eq!(mangle_jni_name("[Ljava/lang/String;"), "_3Ljava_lang_String_2");
+    }
+
+    #[test]
+    fn struct_names_ignore_qualifiers_and_generics() {
+        let module: Ident = parse_quote! { bridge };
+        let name = |path: Path| struct_name(&path, &module);
+
+        assert_eq!(name(parse_quote! { Foo }).as_deref(), Some("Foo"));
+        assert_eq!(name(parse_quote! { Foo<'env> }).as_deref(), Some("Foo"));
+        assert_eq!(name(parse_quote! { self::Foo<'env, 'borrow> }).as_deref(), Some("Foo"));
+        assert_eq!(name(parse_quote! { crate::bridge::Foo }).as_deref(), Some("Foo"));
+        assert_eq!(name(parse_quote! { bridge::Foo }).as_deref(), Some("Foo"));
+        assert_eq!(name(parse_quote! { crate::Foo }).as_deref(), Some("Foo"));
+    }
+
+    #[test]
+    fn paths_to_other_modules_name_no_struct() {
+        let module: Ident = parse_quote! { bridge };
+        let name = |path: Path| struct_name(&path, &module);
+
+        assert_eq!(name(parse_quote! { super::Foo }), None);
+        assert_eq!(name(parse_quote! { other::Foo }), None);
+        assert_eq!(name(parse_quote! { crate::bridge::inner::Foo }), None);
+    }
+}
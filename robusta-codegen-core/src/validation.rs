@@ -0,0 +1,817 @@
+use core::option::Option::{None, Some};
+use core::result::Result::{Err, Ok};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+use proc_macro_error::{emit_error, emit_warning};
+use quote::ToTokens;
+use syn::{
+    Attribute, Error, Fields, FnArg, GenericArgument, GenericParam, Ident, ImplItem, ImplItemMethod, Item, ItemEnum, ItemImpl, ItemMod, ItemStruct,
+    ItemType, Path, PathArguments, Result, Token, Type,
+};
+use syn::parse::{Parse, ParseBuffer, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+use crate::derive::java_enum::java_constant_names;
+use crate::transformation::{JavaPath, RenameRule};
+use crate::utils::{derives, get_abi, impl_struct_name, path_name, struct_name};
+
+/// Derives converting a struct to and from its Java class.
+const CONVERSION_DERIVES: &[&str] = &["FromJavaValue", "TryFromJavaValue", "IntoJavaValue", "TryIntoJavaValue"];
+
+struct AttribItemChecker {
+    valid: bool,
+}
+
+impl AttribItemChecker {
+    fn new() -> Self {
+        AttribItemChecker { valid: true }
+    }
+}
+
+impl<'ast> Visit<'ast> for AttribItemChecker {
+    fn visit_item(&mut self, node: &'ast Item) {
+        let has_package_attribute =
+            |a: &Attribute| a.path.segments.first().unwrap().ident == "package";
+        match node {
+            Item::Struct(_) => {}
+            Item::Const(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            // Error code enums name the Java class of their `nameOf` native method, and Java enums their class
+            Item::Enum(i)
+                if i.attrs.iter().any(has_package_attribute) && !derives(&i.attrs, "ErrorCode") && !derives(&i.attrs, "JavaEnum") =>
+            {
+                emit_error!(i.span(), "`package` attribute used on non-struct type";
+                    help = i.enum_token.span() => "replace `enum` with `struct`";
+                    help = "or add `#[derive(JavaEnum)]`, to convert it to the Java enum of the same name");
+                self.valid = false;
+            }
+            Item::ExternCrate(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Fn(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::ForeignMod(i) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Impl(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Macro(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Macro2(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Mod(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Static(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Trait(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::TraitAlias(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Type(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Union(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Use(i) if i.attrs.iter().any(has_package_attribute) => {
+                emit_error!(i.span(), "`package` attribute used on non-struct type");
+                self.valid = false;
+            }
+            Item::Verbatim(_) => {}
+            _ => {}
+        }
+    }
+}
+
+#[derive(Default)]
+struct ImplAccumulator<'ast> {
+    impls: Vec<&'ast ItemImpl>,
+}
+
+impl<'ast> Visit<'ast> for ImplAccumulator<'ast> {
+    fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
+        // Trait impls don't bridge anything, and can be declared for any type
+        if node.trait_.is_none() {
+            self.impls.push(node);
+        }
+    }
+}
+
+/// Fields of the structs passed as separate Java parameters by `#[flatten]` parameters of exported methods, by struct name.
+pub(crate) type FlattenedStructs = BTreeMap<String, Vec<(Ident, Type)>>;
+
+struct FlattenedStructCollector<'ast> {
+    module_ident: &'ast Ident,
+    module_structs: Vec<&'ast ItemStruct>,
+    flattened_structs: FlattenedStructs,
+    valid: bool,
+}
+
+impl<'ast> FlattenedStructCollector<'ast> {
+    fn new(module_decl: &'ast ItemMod) -> Self {
+        let module_structs = match &module_decl.content {
+            Some((_, items)) => items.iter().filter_map(|i| match i {
+                Item::Struct(s) => Some(s),
+                _ => None,
+            }).collect(),
+            None => Vec::new(),
+        };
+
+        FlattenedStructCollector {
+            module_ident: &module_decl.ident,
+            module_structs,
+            flattened_structs: BTreeMap::new(),
+            valid: true,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for FlattenedStructCollector<'ast> {
+    fn visit_impl_item_method(&mut self, node: &'ast ImplItemMethod) {
+        let flattened_params = node.sig.inputs.iter().filter_map(|arg| match arg {
+            FnArg::Typed(t) if t.attrs.iter().any(|a| a.path.is_ident("flatten")) => Some(t),
+            _ => None,
+        });
+
+        for param in flattened_params {
+            if get_abi(&node.sig).as_deref() != Some("jni") {
+                emit_error!(param, "`#[flatten]` is only supported on parameters of exported methods";
+                    note = "exported methods are declared with `pub extern \"jni\" fn`");
+                self.valid = false;
+                continue;
+            }
+
+            let item_struct = match &*param.ty {
+                Type::Path(p) => struct_name(&p.path, self.module_ident)
+                    .and_then(|name| self.module_structs.iter().find(|s| s.ident == name)),
+                _ => None,
+            };
+            let item_struct = match item_struct {
+                Some(s) => s,
+                None => {
+                    emit_error!(param.ty, "can't find struct `{}` to flatten", param.ty.to_token_stream();
+                        help = "declare the struct in this module, with named fields";
+                        note = "the fields of the struct become the Java parameters of the method");
+                    self.valid = false;
+                    continue;
+                }
+            };
+
+            let fields = match &item_struct.fields {
+                Fields::Named(f) => &f.named,
+                _ => {
+                    emit_error!(param.ty, "can't flatten `{}`, which has no named fields", item_struct.ident;
+                        help = item_struct.ident.span() => "declare the fields of `{}` with names", item_struct.ident);
+                    self.valid = false;
+                    continue;
+                }
+            };
+
+            if let Some(nested) = fields.iter().find(|f| f.attrs.iter().any(|a| a.path.is_ident("flatten"))) {
+                emit_error!(nested, "nested `#[flatten]` is not supported";
+                    help = "declare the fields of `{}` in `{}` instead", nested.ty.to_token_stream(), item_struct.ident);
+                self.valid = false;
+                continue;
+            }
+
+            let fields = fields.iter().map(|f| (f.ident.clone().unwrap(), f.ty.clone())).collect();
+            self.flattened_structs.insert(item_struct.ident.to_string(), fields);
+        }
+    }
+}
+
+/// Derives implementing `Signature`.
+const SIGNATURE_DERIVES: &[&str] = &["Signature", "ErrorCode", "JavaEnum"];
+
+/// Types whose JNI type is the one of their first type argument, e.g. `Option<T>`, or an array of it, e.g. `Vec<T>`.
+const SIGNATURE_WRAPPERS: &[&str] = &["Option", "Box", "Vec", "JOption", "JavaArray"];
+
+/// Reports parameters of bridged methods whose type is declared in the module without implementing `Signature`, which would
+/// otherwise only fail with unsatisfied trait bounds in the generated code.
+///
+/// Other types are left to rustc, as their implementations can't be known. Modules with item macros aren't checked either,
+/// since the macros can implement `Signature`.
+struct SignatureChecker<'ast> {
+    module_ident: &'ast Ident,
+    /// Structs and enums of the module without a `Signature` implementation in it.
+    unsigned_types: BTreeSet<String>,
+    valid: bool,
+}
+
+impl<'ast> SignatureChecker<'ast> {
+    fn new(module_decl: &'ast ItemMod) -> Self {
+        let items: &[Item] = module_decl.content.as_ref().map_or(&[], |(_, items)| items);
+
+        let mut unsigned_types = BTreeSet::new();
+        if !items.iter().any(|i| matches!(i, Item::Macro(_))) {
+            unsigned_types = items
+                .iter()
+                .filter_map(|i| match i {
+                    Item::Struct(s) if !SIGNATURE_DERIVES.iter().any(|d| derives(&s.attrs, d)) => Some(s.ident.to_string()),
+                    Item::Enum(e) if !SIGNATURE_DERIVES.iter().any(|d| derives(&e.attrs, d)) => Some(e.ident.to_string()),
+                    _ => None,
+                })
+                .collect();
+
+            for item_impl in items.iter().filter_map(|i| match i {
+                Item::Impl(i) => Some(i),
+                _ => None,
+            }) {
+                let implements_signature = item_impl
+                    .trait_
+                    .as_ref()
+                    .and_then(|(_, path, _)| path.segments.last())
+                    .is_some_and(|s| s.ident == "Signature");
+                let self_ty = match &*item_impl.self_ty {
+                    Type::Reference(r) => &*r.elem,
+                    ty => ty,
+                };
+                if let (true, Type::Path(p)) = (implements_signature, self_ty) {
+                    if let Some(name) = struct_name(&p.path, &module_decl.ident) {
+                        unsigned_types.remove(&name);
+                    }
+                }
+            }
+        }
+
+        SignatureChecker {
+            module_ident: &module_decl.ident,
+            unsigned_types,
+            valid: true,
+        }
+    }
+
+    /// Name of the type of the module without `Signature` that `ty` needs the implementation of, if any.
+    fn unsigned_type(&self, ty: &Type) -> Option<String> {
+        match ty {
+            Type::Reference(r) => self.unsigned_type(&r.elem),
+            Type::Path(p) => {
+                let last = p.path.segments.last()?;
+                match &last.arguments {
+                    PathArguments::AngleBracketed(args) if SIGNATURE_WRAPPERS.iter().any(|w| last.ident == w) => {
+                        args.args.iter().find_map(|a| match a {
+                            GenericArgument::Type(t) => self.unsigned_type(t),
+                            _ => None,
+                        })
+                    }
+                    _ => struct_name(&p.path, self.module_ident).filter(|name| self.unsigned_types.contains(name)),
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for SignatureChecker<'ast> {
+    fn visit_impl_item_method(&mut self, node: &'ast ImplItemMethod) {
+        if !matches!(get_abi(&node.sig).as_deref(), Some("jni") | Some("java")) {
+            return;
+        }
+
+        // Converted and flattened parameters aren't passed as their own type, and `#[sig]` gives the JNI type of the others
+        let params = node.sig.inputs.iter().filter_map(|arg| match arg {
+            FnArg::Typed(t) if !t.attrs.iter().any(|a| ["convert", "flatten", "sig"].iter().any(|n| a.path.is_ident(n))) => Some(t),
+            _ => None,
+        });
+
+        for param in params {
+            if let Some(name) = self.unsigned_type(&param.ty) {
+                emit_error!(param.ty, "`{}` doesn't implement `Signature`", name;
+                    note = "the JNI type of every parameter is part of the descriptor of the method";
+                    help = "derive it with `#[derive(Signature)]`, or implement `Signature` for `{}` in this module", name);
+                self.valid = false;
+            }
+        }
+    }
+}
+
+/// A Java class bridging a generic struct with given type arguments, declared with `#[instantiate]` on the struct.
+#[derive(Clone)]
+pub(crate) struct Instantiation {
+    /// Suffix of the class name, e.g. `OfString` for the `HolderOfString` class of `Holder<String>`.
+    pub(crate) suffix: Ident,
+    /// The struct with the type arguments of the instantiation, e.g. `Holder<String>`.
+    pub(crate) ty: Path,
+}
+
+/// Instantiations of the generic structs with an `#[instantiate]` attribute, by struct name.
+pub(crate) type Instantiations = BTreeMap<String, Vec<Instantiation>>;
+
+impl Parse for Instantiation {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let suffix = input.parse()?;
+        input.parse::<Token![=]>()?;
+
+        Ok(Instantiation {
+            suffix,
+            ty: input.parse()?,
+        })
+    }
+}
+
+impl Instantiation {
+    /// Parses an `#[instantiate(OfString = Holder<String>, OfLong = Holder<i64>)]` attribute.
+    pub(crate) fn parse_attribute(attr: &Attribute) -> Result<Vec<Instantiation>> {
+        attr.parse_args_with(Punctuated::<Instantiation, Token![,]>::parse_terminated)
+            .map(|instantiations| instantiations.into_iter().collect())
+    }
+
+    /// Simple name of the Java class, the name of the struct followed by the suffix.
+    pub(crate) fn class_name(&self, struct_name: &str) -> String {
+        format!("{}{}", struct_name, self.suffix)
+    }
+
+    /// Whether the type arguments of `path` (lifetimes aside) are those of this instantiation.
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        let args = |path: &Path| type_args(path).iter().map(|t| t.to_token_stream().to_string()).collect::<Vec<_>>();
+        args(path) == args(&self.ty)
+    }
+
+    /// The type of each type parameter of `item_impl` in this instantiation, if its self type matches it: `T` is `String`
+    /// for `impl<T> Holder<T>` and `Holder<String>`, and `impl Holder<String>` has no type parameter to bind.
+    pub(crate) fn bindings(&self, item_impl: &ItemImpl) -> Option<Vec<(Ident, Type)>> {
+        let self_path = match &*item_impl.self_ty {
+            Type::Path(p) => &p.path,
+            _ => return None,
+        };
+        let (impl_args, instantiated_args) = (type_args(self_path), type_args(&self.ty));
+        if impl_args.len() != instantiated_args.len() {
+            return None;
+        }
+
+        let mut bindings: Vec<(Ident, Type)> = Vec::new();
+        for (impl_arg, instantiated_arg) in impl_args.into_iter().zip(instantiated_args) {
+            let param = match impl_arg {
+                Type::Path(p) if p.qself.is_none() => {
+                    item_impl.generics.type_params().find(|t| p.path.is_ident(&t.ident)).map(|t| &t.ident)
+                }
+                _ => None,
+            };
+
+            let same = |a: &Type, b: &Type| a.to_token_stream().to_string() == b.to_token_stream().to_string();
+            match param {
+                Some(param) => match bindings.iter().find(|(ident, _)| ident == param) {
+                    Some((_, bound)) if !same(bound, instantiated_arg) => return None,
+                    Some(_) => {}
+                    None => bindings.push((param.clone(), instantiated_arg.clone())),
+                },
+                None if !same(impl_arg, instantiated_arg) => return None,
+                None => {}
+            }
+        }
+
+        Some(bindings)
+    }
+}
+
+/// Type arguments of the last segment of `path`.
+fn type_args(path: &Path) -> Vec<&Type> {
+    match path.segments.last().map(|s| &s.arguments) {
+        Some(PathArguments::AngleBracketed(a)) => a
+            .args
+            .iter()
+            .filter_map(|a| match a {
+                GenericArgument::Type(t) => Some(t),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Parses and checks the `#[instantiate]` attributes of `item_struct`, reporting invalid instantiations.
+fn struct_instantiations(item_struct: &ItemStruct, module_ident: &Ident) -> Option<Vec<Instantiation>> {
+    let mut valid = true;
+    let mut instantiations: Vec<Instantiation> = Vec::new();
+    for attr in item_struct.attrs.iter().filter(|a| a.path.is_ident("instantiate")) {
+        match Instantiation::parse_attribute(attr) {
+            Ok(parsed) => instantiations.extend(parsed),
+            Err(e) => {
+                emit_error!(e.span(), "{}", e; help = "declare each Java class as `Suffix = Struct<Type, ...>`, e.g. `OfString = {}<String>`", item_struct.ident);
+                valid = false;
+            }
+        }
+    }
+
+    let type_params = item_struct.generics.type_params().count();
+    if type_params == 0 {
+        if let Some(attr) = item_struct.attrs.iter().find(|a| a.path.is_ident("instantiate")) {
+            emit_error!(attr, "`{}` has no type parameters to instantiate", item_struct.ident;
+                help = "remove the `#[instantiate]` attribute");
+            return None;
+        }
+    }
+
+    for (i, instantiation) in instantiations.iter().enumerate() {
+        let ty = path_name(&instantiation.ty);
+        if struct_name(&instantiation.ty, module_ident).as_deref() != Some(item_struct.ident.to_string().as_str()) {
+            emit_error!(instantiation.ty, "`{}` isn't an instantiation of `{}`", ty, item_struct.ident;
+                help = "use `{}` with the type arguments of the Java class", item_struct.ident);
+            valid = false;
+        } else if type_args(&instantiation.ty).len() != type_params {
+            emit_error!(instantiation.ty, "`{}` has {} type arguments instead of {}", ty, type_args(&instantiation.ty).len(), type_params;
+                note = "each instantiation gives every type parameter of the struct a concrete type");
+            valid = false;
+        } else if let Some(other) = instantiations[..i].iter().find(|o| o.suffix == instantiation.suffix) {
+            emit_error!(instantiation.suffix, "`{}` is already the class of `{}`",
+                instantiation.class_name(&item_struct.ident.to_string()), path_name(&other.ty);
+                help = "give each instantiation its own suffix");
+            valid = false;
+        } else if let Some(other) = instantiations[..i].iter().find(|o| o.matches(&instantiation.ty)) {
+            emit_error!(instantiation.ty, "`{}` is already bridged to `{}`", ty, other.class_name(&item_struct.ident.to_string());
+                help = "remove one of them");
+            valid = false;
+        }
+    }
+
+    if valid {
+        Some(instantiations)
+    } else {
+        None
+    }
+}
+
+enum StructDeclarationKind {
+    // structs with `package` attrib and impl
+    Bridged,
+    // structs with `package` attrib but no impl
+    UnImpl,
+    // structs without `package` attrib but with impl
+    UnAttrib,
+    // structs without `package` attrib and no impl
+    Bare,
+}
+
+struct StructDeclVisitor<'ast> {
+    module_ident: &'ast Ident,
+    module_structs: Vec<(&'ast ItemStruct, StructDeclarationKind)>,
+    // all module impls
+    module_impls: Vec<&'ast ItemImpl>,
+}
+
+impl<'ast> StructDeclVisitor<'ast> {
+    fn new(module_ident: &'ast Ident, module_impls: Vec<&'ast ItemImpl>) -> Self {
+        StructDeclVisitor {
+            module_ident,
+            module_structs: Vec::new(),
+            module_impls,
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for StructDeclVisitor<'ast> {
+    fn visit_item_struct(&mut self, node: &'ast ItemStruct) {
+        let struct_name = node.ident.to_string();
+        let has_package_attrib = node
+            .attrs
+            .iter()
+            .any(|a| a.path.segments.first().unwrap().ident == "package");
+        let has_impl = self
+            .module_impls
+            .iter()
+            .filter_map(|i| impl_struct_name(i, self.module_ident))
+            .any(|s| s == struct_name);
+
+        let declaration_kind = match (has_package_attrib, has_impl) {
+            (true, true) => StructDeclarationKind::Bridged,
+            (true, false) => StructDeclarationKind::UnImpl,
+            (false, true) => StructDeclarationKind::UnAttrib,
+            (false, false) => StructDeclarationKind::Bare,
+        };
+
+        self.module_structs.push((node, declaration_kind))
+    }
+}
+
+/// The first exported and the first imported method of the impl blocks of `struct_name`.
+fn struct_methods<'ast>(module_impls: &[&'ast ItemImpl], module_ident: &Ident, struct_name: &Ident) -> (Option<&'ast Ident>, Option<&'ast Ident>) {
+    let methods: Vec<&ImplItemMethod> = module_impls
+        .iter()
+        .filter(|i| impl_struct_name(i, module_ident).is_some_and(|name| *struct_name == name))
+        .flat_map(|i| &i.items)
+        .filter_map(|item| match item {
+            ImplItem::Method(m) => Some(m),
+            _ => None,
+        })
+        .collect();
+    let first_with_abi = |abi: &str| methods.iter().find(|m| get_abi(&m.sig).as_deref() == Some(abi)).map(|m| &m.sig.ident);
+
+    (first_with_abi("jni"), first_with_abi("java"))
+}
+
+/// A fieldless struct standing for a Java enum, bridged like the structs of the module.
+fn enum_struct(item_enum: &ItemEnum) -> ItemStruct {
+    ItemStruct {
+        attrs: item_enum.attrs.clone(),
+        vis: item_enum.vis.clone(),
+        struct_token: Token![struct](item_enum.enum_token.span),
+        ident: item_enum.ident.clone(),
+        generics: item_enum.generics.clone(),
+        fields: Fields::Unit,
+        semi_token: Some(Token![;](item_enum.brace_token.span)),
+    }
+}
+
+/// Note listing the bridged structs of a module, for diagnostics about structs that couldn't be found.
+pub(crate) fn bridged_structs_note(names: impl IntoIterator<Item = impl Display>) -> String {
+    let names: Vec<_> = names.into_iter().map(|n| format!("`{}`", n)).collect();
+    if names.is_empty() {
+        "there are no bridged structs in this module".to_string()
+    } else {
+        format!("bridged structs in this module: {}", names.join(", "))
+    }
+}
+
+pub(crate) struct JNIBridgeModule {
+    pub(crate) module_decl: ItemMod,
+    pub(crate) package_map: BTreeMap<String, Option<JavaPath>>,
+    pub(crate) flattened_structs: FlattenedStructs,
+    /// Names of the enums deriving `ErrorCode`, converted to `int`s.
+    pub(crate) error_codes: BTreeSet<String>,
+    /// Naming convention of the Java methods, set from the `#[bridge]` options.
+    pub(crate) rename_all: Option<RenameRule>,
+    /// Java classes of the generic structs with an `#[instantiate]` attribute.
+    pub(crate) instantiations: Instantiations,
+    /// Names of the constants of each enum deriving `JavaEnum`, converted to Java enums.
+    pub(crate) java_enums: BTreeMap<String, Vec<String>>,
+    /// Names of the structs with methods but no `package` attribute, which have been reported already.
+    pub(crate) unattributed_structs: BTreeSet<String>,
+}
+
+impl Parse for JNIBridgeModule {
+    fn parse(input: &ParseBuffer) -> Result<Self> {
+        let module_decl: ItemMod = input.parse().map_err(|e| {
+            Error::new(
+                e.span(),
+                "`bridge` attribute is supported on mod items only",
+            )
+        })?;
+
+        let span = module_decl.span();
+        match JNIBridgeModule::validate(module_decl) {
+            (module, true) => Ok(module),
+            (_, false) => Err(Error::new(span, "`bridge` macro expansion failed due to previous errors")),
+        }
+    }
+}
+
+impl JNIBridgeModule {
+    /// Checks the items of `module_decl`, reporting invalid ones, and whether they're all valid.
+    ///
+    /// Invalid structs are left out of the returned module, which can still be transformed to report the errors of the rest
+    /// of the module, as `#[bridge(dry_run)]` does.
+    pub(crate) fn validate(module_decl: ItemMod) -> (Self, bool) {
+        let mut valid_input;
+        let mut attribute_checker = AttribItemChecker::new();
+        attribute_checker.visit_item_mod(&module_decl);
+        valid_input = attribute_checker.valid;
+
+        let mut flattened_struct_collector = FlattenedStructCollector::new(&module_decl);
+        flattened_struct_collector.visit_item_mod(&module_decl);
+        valid_input &= flattened_struct_collector.valid;
+
+        let mut signature_checker = SignatureChecker::new(&module_decl);
+        signature_checker.visit_item_mod(&module_decl);
+        valid_input &= signature_checker.valid;
+        let flattened_structs = flattened_struct_collector.flattened_structs;
+
+        let mut impl_visitor = ImplAccumulator::default();
+        impl_visitor.visit_item_mod(&module_decl);
+
+        let mut mod_visitor = StructDeclVisitor::new(&module_decl.ident, impl_visitor.impls);
+        mod_visitor.visit_item_mod(&module_decl);
+
+        // Java enums are bridged like fieldless structs, so that they can have exported methods
+        let java_enums: Vec<&ItemEnum> = match &module_decl.content {
+            Some((_, items)) => items
+                .iter()
+                .filter_map(|i| match i {
+                    Item::Enum(e) if derives(&e.attrs, "JavaEnum") && e.attrs.iter().any(|a| a.path.is_ident("package")) => Some(e),
+                    _ => None,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let java_enum_structs: Vec<ItemStruct> = java_enums.iter().map(|e| enum_struct(e)).collect();
+
+        let mut unattributed_structs = Vec::new();
+        // Data structs converted with the accessors of their class have no methods, but are the Java types of parameters
+        let mut data_structs = Vec::new();
+        let module_impls = &mod_visitor.module_impls;
+        let mut bridged_structs: Vec<_> = mod_visitor.module_structs.into_iter()
+            .filter_map(|(struct_item, decl_kind)| {
+                match decl_kind {
+                    StructDeclarationKind::Bridged => Some(struct_item),
+                    StructDeclarationKind::UnImpl if CONVERSION_DERIVES.iter().any(|d| derives(&struct_item.attrs, d)) => {
+                        data_structs.push(struct_item);
+                        None
+                    }
+                    StructDeclarationKind::UnImpl => {
+                        emit_warning!(struct_item, "ignoring struct without declared methods"; help = "add methods using an `impl` block");
+                        None
+                    }
+                    StructDeclarationKind::UnAttrib => {
+                        // Imported methods need the class too, to be called: name the first one when there are no exported methods
+                        let note = match struct_methods(module_impls, &module_decl.ident, &struct_item.ident) {
+                            (None, Some(imported)) => format!("`{}::{}` calls a method of the Java class of `{}`, which is looked up with its package", struct_item.ident, imported, struct_item.ident),
+                            _ => "Java classes of bridged structs need a package to bind their native methods".to_string(),
+                        };
+                        emit_error!(struct_item.ident, "struct without required `package` attribute";
+                            help = struct_item.struct_token.span() => "add `#[package(com.example)]` before `struct {}`, with the Java package of its class", struct_item.ident;
+                            note = note);
+                        unattributed_structs.push(struct_item.ident.to_string());
+                        valid_input = false;
+                        None
+                    }
+                    // Structs of `#[flatten]` parameters are never bridged themselves
+                    StructDeclarationKind::Bare if flattened_structs.contains_key(&struct_item.ident.to_string()) => None,
+                    StructDeclarationKind::Bare => {
+                        emit_warning!(struct_item, "ignoring struct with no `package` attribute and no implementation";
+                            help = struct_item.span() => "add a #[package(...)] attribute";
+                            note = "structs with declared methods require package attribute for correct translation");
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        bridged_structs.extend(&java_enum_structs);
+
+        let structs_idents: Vec<_> = bridged_structs.iter().map(|s| &s.ident).collect();
+        let type_aliases: Vec<&ItemType> = match &module_decl.content {
+            Some((_, items)) => items.iter().filter_map(|i| match i {
+                Item::Type(t) => Some(t),
+                _ => None,
+            }).collect(),
+            None => Vec::new(),
+        };
+        let bridged_impls: Vec<_> = mod_visitor
+            .module_impls
+            .iter()
+            .filter_map(|item_impl| {
+                let struct_name = impl_struct_name(item_impl, &module_decl.ident)?;
+                structs_idents
+                    .iter()
+                    .position(|id| *id == &struct_name)
+                    .map(|pos| (bridged_structs[pos], *item_impl))
+            })
+            .map(|(s, i)| (s.clone(), i.clone()))
+            .collect();
+
+        mod_visitor
+            .module_impls
+            .into_iter()
+            .filter(|i| {
+                if let Type::Path(p) = &*i.self_ty {
+                    // Impls on paths to other modules are named by their full path, which matches no struct of the module
+                    let struct_name = impl_struct_name(i, &module_decl.ident)
+                        .unwrap_or_else(|| p.path.to_token_stream().to_string());
+                    let has_generics = i
+                        .generics
+                        .params
+                        .iter()
+                        .filter_map(|g| match g {
+                            GenericParam::Type(t) => Some(&t.ident),
+                            _ => None,
+                        })
+                        .next()
+                        .is_some();
+
+                    // Structs declared without a `package` attribute have been reported already
+                    !unattributed_structs.contains(&struct_name) && !bridged_impls
+                        .iter()
+                        .map(|(_, i)| i)
+                        .filter_map(|i| {
+                            // *Very* conservative check to avoid hassles with checking struct name in where clauses
+                            // Should refactor into something proper or just delete this
+                            if !has_generics {
+                                impl_struct_name(i, &module_decl.ident)
+                            } else {
+                                Some(struct_name.clone()) // ignore this impl item
+                            }
+                        })
+                        .any(|bridged_name| bridged_name == struct_name)
+                } else {
+                    false
+                }
+            })
+            .for_each(|lone_impl| {
+                let alias = type_aliases.iter().find(|a| {
+                    impl_struct_name(lone_impl, &module_decl.ident).is_some_and(|name| a.ident == name)
+                });
+
+                match alias {
+                    Some(alias) => emit_error!(
+                        lone_impl.self_ty,
+                        "impl declared on type alias \"{}\"", alias.ident;
+                        note = "type aliases aren't resolved when matching `impl` blocks to bridged structs";
+                        help = "implement the methods on `{}` instead", alias.ty.to_token_stream()
+                    ),
+                    None => emit_error!(
+                        lone_impl.self_ty,
+                        "impl declared without corresponding struct \"{}\"",
+                        lone_impl.self_ty.to_token_stream();
+                        note = "{}", bridged_structs_note(&structs_idents)
+                    ),
+                }
+                valid_input = false;
+            });
+
+        let package_map: BTreeMap<String, Option<JavaPath>> = bridged_structs
+            .iter()
+            .chain(&data_structs)
+            .map(|s| {
+                let name = s.ident.to_string();
+                let mut packages = Vec::new();
+                for attr in s.attrs.iter().filter(|a| a.path.segments.last().unwrap().ident == "package") {
+                    match attr.parse_args::<JavaPath>() {
+                        Ok(path) => packages.push((attr, path)),
+                        Err(e) => {
+                            emit_error!(e.span(), e);
+                            valid_input = false;
+                        }
+                    }
+                }
+
+                let mut packages = packages.into_iter();
+                let (first_attr, package_path) = match packages.next() {
+                    Some(package) => package,
+                    None => return (name, None),
+                };
+
+                // The first package would be used, binding the native methods to a class Java doesn't declare them in
+                for (attr, other) in packages.filter(|(_, p)| *p != package_path) {
+                    emit_error!(attr, "`{}` is bridged to both `{}` and `{}`", s.ident, package_path, other;
+                        note = first_attr.span() => "a struct is bridged to a single Java class, in the package of its first `#[package]`";
+                        help = "remove one of the `#[package]` attributes");
+                    valid_input = false;
+                }
+
+                let package = Some(package_path);
+
+                (name, package)
+            })
+            .collect();
+
+        let mut instantiations = BTreeMap::new();
+        for s in bridged_structs.iter().filter(|s| s.attrs.iter().any(|a| a.path.is_ident("instantiate"))) {
+            match struct_instantiations(s, &module_decl.ident) {
+                Some(i) => {
+                    instantiations.insert(s.ident.to_string(), i);
+                }
+                None => valid_input = false,
+            }
+        }
+
+        let error_codes = match &module_decl.content {
+            Some((_, items)) => items
+                .iter()
+                .filter_map(|i| match i {
+                    Item::Enum(e) if derives(&e.attrs, "ErrorCode") => Some(e.ident.to_string()),
+                    _ => None,
+                })
+                .collect(),
+            None => BTreeSet::new(),
+        };
+
+        let java_enums = java_enums.iter().map(|e| (e.ident.to_string(), java_constant_names(&e.variants))).collect();
+
+        let module = JNIBridgeModule {
+            module_decl,
+            package_map,
+            flattened_structs,
+            error_codes,
+            rename_all: None,
+            instantiations,
+            java_enums,
+            unattributed_structs: unattributed_structs.into_iter().collect(),
+        };
+
+        (module, valid_input)
+    }
+}
@@ -1,3 +0,0 @@
-pub(crate) mod signature;
-pub(crate) mod convert;
-mod utils;
@@ -3,30 +3,27 @@ use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
 use syn::{parse_macro_input, DeriveInput};
 
-use validation::JNIBridgeModule;
-
-use crate::transformation::ModTransformer;
-use derive::signature::signature_macro_derive;
-use crate::derive::convert::{into_java_value_macro_derive, tryinto_java_value_macro_derive, from_java_value_macro_derive, tryfrom_java_value_macro_derive};
-
-mod transformation;
-mod utils;
-mod validation;
-mod derive;
+use robusta_codegen_core::__private::{
+    bridge_dry_run, error_code_macro_derive, from_java_value_macro_derive, inline_module_files, into_java_value_macro_derive,
+    java_enum_macro_derive, java_exception_macro_derive, signature_macro_derive, tryfrom_java_value_macro_derive, tryinto_java_value_macro_derive,
+};
+use robusta_codegen_core::api::BridgeModule;
 
 #[proc_macro_error]
 #[proc_macro_attribute]
-pub fn bridge(_args: TokenStream, raw_input: TokenStream) -> TokenStream {
-    let module_data = parse_macro_input!(raw_input as JNIBridgeModule);
+pub fn bridge(args: TokenStream, raw_input: TokenStream) -> TokenStream {
+    let raw_input: TokenStream = inline_module_files(args.clone().into(), raw_input.into()).into();
+    if let Some(module) = bridge_dry_run(args.clone().into(), raw_input.clone().into()) {
+        return module.into();
+    }
 
-    let mut transformer = ModTransformer::new(module_data);
-    let tokens = transformer.transform_module();
+    let module = parse_macro_input!(raw_input as BridgeModule);
 
-    tokens.into()
+    module.with_args(args.into()).expand().into()
 }
 
 #[proc_macro_error]
-#[proc_macro_derive(Signature, attributes(package))]
+#[proc_macro_derive(Signature, attributes(package, instantiate, signature))]
 pub fn signature_derive(raw_input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(raw_input as DeriveInput);
 
@@ -34,7 +31,7 @@ pub fn signature_derive(raw_input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro_error]
-#[proc_macro_derive(IntoJavaValue, attributes(package, instance, field))]
+#[proc_macro_derive(IntoJavaValue, attributes(package, instance, field, lazy, bean, java_field))]
 pub fn into_java_value_derive(raw_input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(raw_input as DeriveInput);
 
@@ -42,7 +39,7 @@ pub fn into_java_value_derive(raw_input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro_error]
-#[proc_macro_derive(TryIntoJavaValue, attributes(package, instance, field))]
+#[proc_macro_derive(TryIntoJavaValue, attributes(package, instance, field, lazy, bean, java_field))]
 pub fn tryinto_java_value_derive(raw_input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(raw_input as DeriveInput);
 
@@ -50,7 +47,7 @@ pub fn tryinto_java_value_derive(raw_input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro_error]
-#[proc_macro_derive(FromJavaValue, attributes(package, instance, field))]
+#[proc_macro_derive(FromJavaValue, attributes(package, instance, field, lazy, bean, java_field))]
 pub fn from_java_value_derive(raw_input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(raw_input as DeriveInput);
 
@@ -58,9 +55,33 @@ pub fn from_java_value_derive(raw_input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro_error]
-#[proc_macro_derive(TryFromJavaValue, attributes(package, instance, field))]
+#[proc_macro_derive(TryFromJavaValue, attributes(package, instance, field, lazy, bean, java_field))]
 pub fn tryfrom_java_value_derive(raw_input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(raw_input as DeriveInput);
 
     tryfrom_java_value_macro_derive(input).into()
 }
+
+#[proc_macro_error]
+#[proc_macro_derive(ErrorCode, attributes(package))]
+pub fn error_code_derive(raw_input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(raw_input as DeriveInput);
+
+    error_code_macro_derive(input).into()
+}
+
+#[proc_macro_error]
+#[proc_macro_derive(JavaEnum, attributes(package, by_ordinal, java_name))]
+pub fn java_enum_derive(raw_input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(raw_input as DeriveInput);
+
+    java_enum_macro_derive(input).into()
+}
+
+#[proc_macro_error]
+#[proc_macro_derive(JavaException, attributes(java_exception))]
+pub fn java_exception_derive(raw_input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(raw_input as DeriveInput);
+
+    java_exception_macro_derive(input).into()
+}
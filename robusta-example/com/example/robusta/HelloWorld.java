@@ -5,7 +5,7 @@ import java.util.*;
 class HelloWorld {
     private String foo = "";
 
-    private static native ArrayList<String> special(ArrayList<Integer> input1, int in2);
+    private static native ArrayList<String> special(List<Integer> input1, int in2);
 
     // pub extern "java" fn staticJavaAdd(i: i32, u: i32) -> i32 {}
     public static int staticJavaAdd(int i, int u) {
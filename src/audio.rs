@@ -0,0 +1,169 @@
+//! Interleaved audio samples.
+//!
+//! Java audio APIs (e.g. Android's `AudioTrack`) take interleaved `short[]` or `float[]` buffers, whose channel count must be known
+//! to make sense of them. [`Samples`] keeps the two together, and is converted to and from a small Java class holding both:
+//!
+//! | **Rust**         | **Java**                     |
+//! |------------------|------------------------------|
+//! | `Samples<i16>`   | `robusta.audio.ShortSamples` |
+//! | `Samples<f32>`   | `robusta.audio.FloatSamples` |
+//!
+//! Both Java classes have public final `data` and `channels` fields, and a `frames()` method. Their sources are generated together
+//! with the Java stubs of any `#[bridge]` module using `Samples` (see [Generating Java stubs](crate#generating-java-stubs)).
+//!
+//! ```ignore
+//! pub extern "jni" fn render(frames: i32) -> Samples<i16> {
+//!     Samples::new(synthesize_stereo(frames), 2).unwrap()
+//! }
+//! ```
+//!
+//! ```java
+//! ShortSamples samples = Synth.render(256);
+//! track.write(samples.data, 0, samples.data.length);
+//! ```
+//!
+
+use std::fmt;
+use std::slice::ChunksExact;
+
+use jni::errors::{Error, Result};
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+/// Interleaved samples of one or more audio channels: frame `i` is `data[i * channels..(i + 1) * channels]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Samples<T> {
+    data: Vec<T>,
+    channels: usize,
+}
+
+/// Samples that can't be split into whole frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidSamples {
+    /// Number of samples.
+    pub len: usize,
+    /// Number of channels.
+    pub channels: usize,
+}
+
+impl fmt::Display for InvalidSamples {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.channels == 0 {
+            f.write_str("channel count must be positive")
+        } else {
+            write!(f, "{} samples are not a multiple of the channel count {}", self.len, self.channels)
+        }
+    }
+}
+
+impl std::error::Error for InvalidSamples {}
+
+impl<T> Samples<T> {
+    /// Interleaved `data` of `channels` channels, failing unless its length is a multiple of a positive `channels`.
+    pub fn new(data: Vec<T>, channels: usize) -> std::result::Result<Self, InvalidSamples> {
+        if channels == 0 || !data.len().is_multiple_of(channels) {
+            return Err(InvalidSamples {
+                len: data.len(),
+                channels,
+            });
+        }
+
+        Ok(Samples { data, channels })
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Number of frames, i.e. samples per channel.
+    pub fn frames(&self) -> usize {
+        self.data.len() / self.channels
+    }
+
+    /// Interleaved samples.
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Iterates over frames, each with a sample per channel.
+    pub fn iter_frames(&self) -> ChunksExact<'_, T> {
+        self.data.chunks_exact(self.channels)
+    }
+
+    pub fn into_data(self) -> Vec<T> {
+        self.data
+    }
+}
+
+fn to_jint(value: usize) -> Result<i32> {
+    <i32 as std::convert::TryFrom<usize>>::try_from(value).map_err(|_| Error::WrongJValueType("usize", "jint"))
+}
+
+macro_rules! sample_formats {
+    ($($type:ty: $class:literal ($array_sig:literal) [$new_array:ident, $set_region:ident, $get_region:ident]),+ $(,)?) => {
+        $(
+            impl Signature for Samples<$type> {
+                const SIG_TYPE: &'static str = concat!("L", $class, ";");
+            }
+
+            impl<'env> TryIntoJavaValue<'env> for Samples<$type> {
+                type Target = JObject<'env>;
+
+                fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+                    let array = env.$new_array(to_jint(self.data.len())?)?;
+                    env.$set_region(array, 0, &self.data)?;
+
+                    env.new_object(
+                        $class,
+                        concat!("(", $array_sig, "I)V"),
+                        &[JValue::Object(<JObject as From<_>>::from(array)), JValue::Int(to_jint(self.channels)?)],
+                    )
+                }
+            }
+
+            impl<'env> TryFromJavaValue<'env> for Samples<$type> {
+                type Source = JObject<'env>;
+
+                fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+                    if s.is_null() {
+                        return Err(Error::NullPtr($class));
+                    }
+
+                    let array = env.get_field(s, "data", $array_sig)?.l()?.into_inner();
+                    let channels = env.get_field(s, "channels", "I")?.i()?;
+
+                    let mut data = vec![Default::default(); env.get_array_length(array)? as usize];
+                    env.$get_region(array, 0, &mut data)?;
+
+                    <usize as std::convert::TryFrom<i32>>::try_from(channels)
+                        .ok()
+                        .and_then(|channels| Samples::new(data, channels).ok())
+                        .ok_or(Error::WrongJValueType("interleaved samples", "samples of incomplete frames"))
+                }
+            }
+
+            impl<'env> IntoJavaValue<'env> for Samples<$type> {
+                type Target = JObject<'env>;
+
+                fn into(self, env: JNIEnv<'env>) -> Self::Target {
+                    TryIntoJavaValue::try_into(self, env).unwrap()
+                }
+            }
+
+            impl<'env> FromJavaValue<'env> for Samples<$type> {
+                type Source = JObject<'env>;
+
+                fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+                    TryFromJavaValue::try_from(s, env).unwrap()
+                }
+            }
+        )+
+    };
+}
+
+sample_formats! {
+    i16: "robusta/audio/ShortSamples" ("[S") [new_short_array, set_short_array_region, get_short_array_region],
+    f32: "robusta/audio/FloatSamples" ("[F") [new_float_array, set_float_array_region, get_float_array_region],
+}
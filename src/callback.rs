@@ -0,0 +1,312 @@
+//! Calling Java listeners from Rust threads.
+//!
+//! Invoking a Java method from many Rust threads means attaching each of them to the JVM, with all of them contending on it.
+//! A [`QueuedCallback`] instead funnels calls through a bounded queue: producers on any thread push plain Rust values
+//! (no JVM attachment needed), and a single dispatcher thread, attached once, converts them and invokes the listener in order.
+//!
+//! ```ignore
+//! let callback = QueuedCallback::<i32>::new(&env, listener, "onProgress", 1024, OverflowPolicy::Block)?;
+//!
+//! // from any thread
+//! callback.call(42)?;
+//!
+//! // deliver everything still queued, then detach the dispatcher thread
+//! callback.shutdown(Shutdown::Drain);
+//! ```
+//!
+//! Calls pushed by the same thread reach Java in the order they were pushed.
+//!
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+
+use jni::errors::Result as JniResult;
+use jni::objects::{GlobalRef, JObject, JValue};
+use jni::{JNIEnv, JavaVM};
+
+use crate::convert::TryIntoJavaValue;
+use crate::debug::Tracked;
+
+// Local references created by a single call are released as soon as it completes.
+const LOCAL_FRAME_CAPACITY: i32 = 16;
+
+/// What [`QueuedCallback::call`] does when the queue is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait until the dispatcher makes room.
+    Block,
+    /// Discard the oldest queued value to make room.
+    DropOldest,
+    /// Give the value back with [`CallbackError::Full`].
+    Error,
+}
+
+/// How [`QueuedCallback::shutdown`] treats values still in the queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shutdown {
+    /// Deliver all queued values before stopping.
+    Drain,
+    /// Drop queued values without delivering them.
+    Discard,
+}
+
+/// A value that could not be queued, given back to the caller.
+#[derive(PartialEq, Eq)]
+pub enum CallbackError<T> {
+    /// The queue is full and the overflow policy is [`OverflowPolicy::Error`].
+    Full(T),
+    /// The callback is shutting down.
+    Closed(T),
+}
+
+impl<T> CallbackError<T> {
+    /// Returns the value that could not be queued.
+    pub fn into_inner(self) -> T {
+        match self {
+            CallbackError::Full(v) | CallbackError::Closed(v) => v,
+        }
+    }
+}
+
+impl<T> fmt::Debug for CallbackError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallbackError::Full(_) => f.write_str("Full(..)"),
+            CallbackError::Closed(_) => f.write_str("Closed(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for CallbackError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallbackError::Full(_) => f.write_str("callback queue is full"),
+            CallbackError::Closed(_) => f.write_str("callback is shut down"),
+        }
+    }
+}
+
+impl<T> std::error::Error for CallbackError<T> {}
+
+struct State<T> {
+    values: VecDeque<T>,
+    closed: bool,
+}
+
+struct Queue<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+    policy: OverflowPolicy,
+    failures: AtomicUsize,
+}
+
+impl<T> Queue<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push(&self, value: T) -> Result<(), CallbackError<T>> {
+        let mut state = self.lock();
+
+        while !state.closed && state.values.len() >= self.capacity {
+            match self.policy {
+                OverflowPolicy::Block => state = self.not_full.wait(state).unwrap_or_else(|e| e.into_inner()),
+                OverflowPolicy::DropOldest => {
+                    state.values.pop_front();
+                }
+                OverflowPolicy::Error => return Err(CallbackError::Full(value)),
+            }
+        }
+
+        if state.closed {
+            return Err(CallbackError::Closed(value));
+        }
+
+        state.values.push_back(value);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    /// Waits for the next value, returning `None` once the queue is closed and empty.
+    fn pop(&self) -> Option<T> {
+        let mut state = self.lock();
+
+        loop {
+            if let Some(value) = state.values.pop_front() {
+                self.not_full.notify_one();
+                return Some(value);
+            }
+
+            if state.closed {
+                return None;
+            }
+
+            state = self.not_empty.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    fn close(&self, mode: Shutdown) {
+        let mut state = self.lock();
+        state.closed = true;
+        if mode == Shutdown::Discard {
+            state.values.clear();
+        }
+
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+}
+
+struct Listener {
+    object: GlobalRef,
+    _tracked: Tracked,
+    method: String,
+    signature: String,
+}
+
+/// A Java listener method called with values queued from any Rust thread.
+///
+/// The listener method must take a single parameter whose type matches `T`'s [`Signature`](crate::convert::Signature) and return `void`.
+/// It can be shared between producer threads, e.g. in an [`Arc`].
+/// Dropping a `QueuedCallback` is the same as calling [`shutdown`](QueuedCallback::shutdown) with [`Shutdown::Drain`].
+pub struct QueuedCallback<T: Send + 'static> {
+    queue: Arc<Queue<T>>,
+    dispatcher: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl<T> QueuedCallback<T>
+where
+    T: Send + 'static + for<'env> TryIntoJavaValue<'env>,
+    for<'env> <T as TryIntoJavaValue<'env>>::Target: Into<JValue<'env>>,
+{
+    /// Starts a dispatcher thread calling `method` on `listener`, with room for `capacity` pending values.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub fn new(
+        env: &JNIEnv,
+        listener: JObject,
+        method: &str,
+        capacity: usize,
+        policy: OverflowPolicy,
+    ) -> JniResult<Self> {
+        assert!(capacity > 0, "callback queue capacity must be positive");
+
+        let signature = crate::signature::descriptor(&[<T as TryIntoJavaValue>::SIG_TYPE], "V");
+        // Fail early if there's no such method, rather than on every call
+        env.get_method_id(env.get_object_class(listener)?, method, signature.as_str())?;
+
+        let vm = env.get_java_vm()?;
+        let listener = Listener {
+            object: env.new_global_ref(listener)?,
+            _tracked: Tracked::global("QueuedCallback"),
+            method: method.into(),
+            signature,
+        };
+
+        let queue = Arc::new(Queue {
+            state: Mutex::new(State {
+                values: VecDeque::with_capacity(capacity),
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+            policy,
+            failures: AtomicUsize::new(0),
+        });
+
+        let dispatcher = {
+            let queue = queue.clone();
+            std::thread::Builder::new()
+                .name(format!("robusta-callback-{}", listener.method))
+                .spawn(move || Self::dispatch(vm, listener, &queue))
+                .expect("can't spawn callback dispatcher thread")
+        };
+
+        Ok(QueuedCallback {
+            queue,
+            dispatcher: Mutex::new(Some(dispatcher)),
+        })
+    }
+
+    fn dispatch(vm: JavaVM, listener: Listener, queue: &Queue<T>) {
+        let env = match vm.attach_current_thread() {
+            Ok(env) => env,
+            Err(_) => {
+                // Nobody is going to deliver these
+                queue.close(Shutdown::Discard);
+                queue.failures.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        };
+
+        while let Some(value) = queue.pop() {
+            let result = env.with_local_frame(LOCAL_FRAME_CAPACITY, || {
+                let value = TryIntoJavaValue::try_into(value, *env)?;
+                env.call_method(
+                    listener.object.as_obj(),
+                    listener.method.as_str(),
+                    listener.signature.as_str(),
+                    &[value.into()],
+                )?;
+                Ok(JObject::null())
+            });
+
+            if result.is_err() {
+                // Leave the thread usable for the following calls
+                if env.exception_check().unwrap_or(false) {
+                    let _ = env.exception_describe();
+                    let _ = env.exception_clear();
+                }
+                queue.failures.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        // `env` is dropped here, detaching the dispatcher thread
+    }
+}
+
+impl<T: Send + 'static> QueuedCallback<T> {
+    /// Queues a call to the listener with `value`, handling a full queue according to the [`OverflowPolicy`].
+    pub fn call(&self, value: T) -> Result<(), CallbackError<T>> {
+        self.queue.push(value)
+    }
+
+    /// Number of calls that failed so far, either converting the value or throwing in Java.
+    ///
+    /// Exceptions thrown by the listener are printed and cleared.
+    pub fn failures(&self) -> usize {
+        self.queue.failures.load(Ordering::Relaxed)
+    }
+
+    /// Stops accepting calls and waits for the dispatcher thread to finish and detach from the JVM.
+    ///
+    /// After this returns, the listener is not called anymore: queued values have been either delivered or dropped, depending on `mode`.
+    /// Later calls fail with [`CallbackError::Closed`]. When called by the listener itself, this doesn't wait for the dispatcher thread.
+    pub fn shutdown(&self, mode: Shutdown) {
+        self.queue.close(mode);
+
+        let mut dispatcher = self.dispatcher.lock().unwrap_or_else(|e| e.into_inner());
+        let is_dispatcher_thread = dispatcher
+            .as_ref()
+            .is_some_and(|d| d.thread().id() == std::thread::current().id());
+
+        if !is_dispatcher_thread {
+            if let Some(dispatcher) = dispatcher.take() {
+                let _ = dispatcher.join();
+            }
+        }
+    }
+}
+
+impl<T: Send + 'static> Drop for QueuedCallback<T> {
+    fn drop(&mut self) {
+        self.shutdown(Shutdown::Drain);
+    }
+}
@@ -0,0 +1,187 @@
+//! Conversions through another type, then mapped to the Rust type.
+//!
+//! A value received from Java often needs a last step after its conversion, e.g. a `String` parsed as an `IpAddr`.
+//! [`Adapt<T, M>`](Adapt) is converted as `T`, then mapped by `M`, a type implementing [`Mapper<T>`](Mapper):
+//!
+//! ```ignore
+//! pub enum ParseIp {}
+//!
+//! impl Mapper<String> for ParseIp {
+//!     type Output = IpAddr;
+//!     type Error = AddrParseError;
+//!
+//!     fn map(value: String) -> Result<IpAddr, AddrParseError> {
+//!         value.parse()
+//!     }
+//! }
+//!
+//! pub extern "jni" fn isLoopback(address: Adapt<String, ParseIp>) -> bool {
+//!     address.is_loopback()
+//! }
+//! ```
+//!
+//! is called from Java as `boolean isLoopback(String address)`. Values converted to Java are first mapped back to `T` by
+//! `M`, which must then also implement [`ReverseMapper<T>`](ReverseMapper).
+//!
+//! When mapping fails, safe conversions throw a `java.lang.IllegalArgumentException` with the error as message (even with a
+//! custom `exception_class`, as the exception is already pending), and unchecked ones panic.
+//!
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use jni::errors::{Error, Result};
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+/// Maps values converted as `T` to another type, for [`Adapt<T, Self>`](Adapt).
+///
+/// Mappers are usually uninhabited types, as they're only used through their associated items.
+pub trait Mapper<T> {
+    /// Type of the mapped values.
+    type Output;
+    /// Error returned when a value can't be mapped.
+    type Error: fmt::Display;
+
+    fn map(value: T) -> std::result::Result<Self::Output, Self::Error>;
+}
+
+/// Maps values back to `T`, for [`Adapt<T, Self>`](Adapt) values converted to Java.
+pub trait ReverseMapper<T>: Mapper<T> {
+    fn unmap(value: Self::Output) -> T;
+}
+
+/// A value converted as `T`, and mapped by `M`, see the [module documentation](self).
+pub struct Adapt<T, M: Mapper<T>> {
+    value: M::Output,
+    mapper: PhantomData<fn() -> (T, M)>,
+}
+
+impl<T, M: Mapper<T>> Adapt<T, M> {
+    pub fn new(value: M::Output) -> Self {
+        Adapt {
+            value,
+            mapper: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> M::Output {
+        self.value
+    }
+}
+
+// Implemented by hand, since derives would require `T` and `M` themselves to implement the traits
+
+impl<T, M: Mapper<T>> Clone for Adapt<T, M>
+where
+    M::Output: Clone,
+{
+    fn clone(&self) -> Self {
+        Adapt::new(self.value.clone())
+    }
+}
+
+impl<T, M: Mapper<T>> fmt::Debug for Adapt<T, M>
+where
+    M::Output: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl<T, M: Mapper<T>> PartialEq for Adapt<T, M>
+where
+    M::Output: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T, M: Mapper<T>> Eq for Adapt<T, M> where M::Output: Eq {}
+
+impl<T, M: Mapper<T>> Deref for Adapt<T, M> {
+    type Target = M::Output;
+
+    fn deref(&self) -> &M::Output {
+        &self.value
+    }
+}
+
+impl<T, M: Mapper<T>> DerefMut for Adapt<T, M> {
+    fn deref_mut(&mut self) -> &mut M::Output {
+        &mut self.value
+    }
+}
+
+impl<T: Signature, M: Mapper<T>> Signature for Adapt<T, M> {
+    const SIG_TYPE: &'static str = <T as Signature>::SIG_TYPE;
+}
+
+impl<'env, T, M> TryFromJavaValue<'env> for Adapt<T, M>
+where
+    T: TryFromJavaValue<'env>,
+    M: Mapper<T>,
+{
+    type Source = T::Source;
+
+    const SIG_TYPE: &'static str = <T as TryFromJavaValue>::SIG_TYPE;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        match M::map(T::try_from(s, env)?) {
+            Ok(value) => Ok(Adapt::new(value)),
+            Err(e) => {
+                env.throw_new("java/lang/IllegalArgumentException", e.to_string())?;
+                Err(Error::JavaException)
+            }
+        }
+    }
+}
+
+impl<'env, T, M> FromJavaValue<'env> for Adapt<T, M>
+where
+    T: FromJavaValue<'env>,
+    M: Mapper<T>,
+{
+    type Source = T::Source;
+
+    const SIG_TYPE: &'static str = <T as FromJavaValue>::SIG_TYPE;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        match M::map(T::from(s, env)) {
+            Ok(value) => Adapt::new(value),
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+impl<'env, T, M> TryIntoJavaValue<'env> for Adapt<T, M>
+where
+    T: TryIntoJavaValue<'env>,
+    M: ReverseMapper<T>,
+{
+    type Target = T::Target;
+
+    const SIG_TYPE: &'static str = <T as TryIntoJavaValue>::SIG_TYPE;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        T::try_into(M::unmap(self.value), env)
+    }
+}
+
+impl<'env, T, M> IntoJavaValue<'env> for Adapt<T, M>
+where
+    T: IntoJavaValue<'env>,
+    M: ReverseMapper<T>,
+{
+    type Target = T::Target;
+
+    const SIG_TYPE: &'static str = <T as IntoJavaValue>::SIG_TYPE;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        T::into(M::unmap(self.value), env)
+    }
+}
@@ -0,0 +1,249 @@
+//! Conversions between `Vec`s and Java arrays.
+//!
+//! `Vec<T>` is converted to `java.util.ArrayList`, and from any `java.util.List`. Java APIs taking or returning arrays (e.g. `String[]` or `int[]`) can
+//! use [`JavaArray`] instead:
+//!
+//! ```ignore
+//! pub extern "jni" fn lengths(values: JavaArray<String>) -> JavaArray<i32> {
+//!     JavaArray(values.0.iter().map(|v| v.len() as i32).collect())
+//! }
+//! ```
+//!
+//! is called from Java as `int[] lengths(String[] values)`.
+//!
+//! `Box<[String]>` is also converted to and from `String[]`, by a dedicated conversion that releases the local reference of
+//! each element directly instead of pushing a local frame for it: it's the fastest way to pass many strings, as long as the
+//! arrays have no `null` elements.
+//!
+//! Elements can be primitives (except unsigned integers), converted with the `Get/Set<Type>ArrayRegion` JNI functions, or objects
+//! whose type implements [`ArrayElement`]: `String`, [`Boxed<T>`](crate::convert::Boxed), [`JObject`] (as `Object[]`), and `Option`s
+//! of these, whose `None` values are `null` elements. To use arrays of your own classes, implement [`ArrayElement`] for them.
+//!
+//! Nested arrays (e.g. `JavaArray<JavaArray<String>>` for a `String[][]`) are not supported, and neither are arrays of `ArrayList`s.
+//!
+
+use std::convert::TryFrom;
+use std::ops::{Deref, DerefMut};
+
+use jni::errors::{Error, Result};
+use jni::objects::JObject;
+use jni::sys::{jboolean, jchar, jsize};
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, JavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+/// Elements converted to and from a Java array, e.g. `JavaArray<String>` and `String[]`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JavaArray<T>(pub Vec<T>);
+
+impl<T> From<Vec<T>> for JavaArray<T> {
+    fn from(elements: Vec<T>) -> Self {
+        JavaArray(elements)
+    }
+}
+
+impl<T> Deref for JavaArray<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for JavaArray<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.0
+    }
+}
+
+/// Object types that can be elements of a [`JavaArray`].
+///
+/// ```ignore
+/// impl ArrayElement for Point<'_> {
+///     const ARRAY_SIG_TYPE: &'static str = "[Lcom/example/Point;";
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be an element of a `JavaArray`",
+    note = "elements can be primitives, or objects whose type implements `ArrayElement`",
+    note = "nested arrays and arrays of `Vec`s are not supported"
+)]
+pub trait ArrayElement {
+    /// Signature of an array of this type, e.g. `[Ljava/lang/String;`.
+    const ARRAY_SIG_TYPE: &'static str;
+}
+
+impl ArrayElement for String {
+    const ARRAY_SIG_TYPE: &'static str = "[Ljava/lang/String;";
+}
+
+impl ArrayElement for JObject<'_> {
+    const ARRAY_SIG_TYPE: &'static str = "[Ljava/lang/Object;";
+}
+
+/// `None` elements are `null`.
+impl<T: ArrayElement> ArrayElement for Option<T> {
+    const ARRAY_SIG_TYPE: &'static str = T::ARRAY_SIG_TYPE;
+}
+
+macro_rules! boxed_elements {
+    ($($type:ty: $class:literal),+ $(,)?) => {
+        $(
+            impl ArrayElement for crate::convert::Boxed<$type> {
+                const ARRAY_SIG_TYPE: &'static str = concat!("[L", $class, ";");
+            }
+        )+
+    };
+}
+
+boxed_elements! {
+    bool: "java/lang/Boolean",
+    i8: "java/lang/Byte",
+    char: "java/lang/Character",
+    i16: "java/lang/Short",
+    i32: "java/lang/Integer",
+    i64: "java/lang/Long",
+    f32: "java/lang/Float",
+    f64: "java/lang/Double",
+}
+
+fn array_length(len: usize) -> Result<jsize> {
+    <jsize as TryFrom<usize>>::try_from(len).map_err(|_| Error::WrongJValueType("usize", "jsize"))
+}
+
+impl<T: ArrayElement> Signature for JavaArray<T> {
+    const SIG_TYPE: &'static str = T::ARRAY_SIG_TYPE;
+}
+
+impl<'env, T> TryIntoJavaValue<'env> for JavaArray<T>
+where
+    T: ArrayElement + TryIntoJavaValue<'env>,
+{
+    type Target = JObject<'env>;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        let sig = T::ARRAY_SIG_TYPE;
+        let element_class = &sig[2..sig.len() - 1];
+        let array = env.new_object_array(array_length(self.0.len())?, element_class, JObject::null())?;
+
+        for (i, element) in self.0.into_iter().enumerate() {
+            // Local references created by the element's conversion are released as soon as it's stored
+            env.with_local_frame(1, || {
+                let element = JavaValue::autobox(TryIntoJavaValue::try_into(element, env)?, env);
+                env.set_object_array_element(array, i as jsize, element)?;
+                Ok(JObject::null())
+            })?;
+        }
+
+        Ok(<JObject as From<_>>::from(array))
+    }
+}
+
+impl<'env, T, U> TryFromJavaValue<'env> for JavaArray<T>
+where
+    T: ArrayElement + TryFromJavaValue<'env, Source = U>,
+    U: JavaValue<'env>,
+{
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        let array = s.into_inner();
+        let len = env.get_array_length(array)?;
+
+        (0..len)
+            .map(|i| T::try_from(U::try_unbox(env.get_object_array_element(array, i)?, env)?, env))
+            .collect::<Result<_>>()
+            .map(JavaArray)
+    }
+}
+
+impl<'env, T> IntoJavaValue<'env> for JavaArray<T>
+where
+    T: ArrayElement + TryIntoJavaValue<'env>,
+{
+    type Target = JObject<'env>;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        TryIntoJavaValue::try_into(self, env).unwrap()
+    }
+}
+
+impl<'env, T, U> FromJavaValue<'env> for JavaArray<T>
+where
+    T: ArrayElement + TryFromJavaValue<'env, Source = U>,
+    U: JavaValue<'env>,
+{
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        TryFromJavaValue::try_from(s, env).unwrap()
+    }
+}
+
+/// Arrays of primitives, copied in a single JNI call. `bool`s and `char`s go through a buffer of `jboolean`s or `jchar`s.
+macro_rules! primitive_arrays {
+    ($($type:ty: $sig:literal [$new:ident, $set:ident, $get:ident] $(as $raw:ty)?),+ $(,)?) => {
+        $(
+            impl Signature for JavaArray<$type> {
+                const SIG_TYPE: &'static str = $sig;
+            }
+
+            impl<'env> TryIntoJavaValue<'env> for JavaArray<$type> {
+                type Target = JObject<'env>;
+
+                fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+                    let elements = self.0;
+                    $(
+                        let elements = elements.into_iter().map(|e| TryIntoJavaValue::try_into(e, env)).collect::<Result<Vec<$raw>>>()?;
+                    )?
+                    let array = env.$new(array_length(elements.len())?)?;
+                    env.$set(array, 0, &elements)?;
+                    Ok(<JObject as From<_>>::from(array))
+                }
+            }
+
+            impl<'env> TryFromJavaValue<'env> for JavaArray<$type> {
+                type Source = JObject<'env>;
+
+                fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+                    let array = s.into_inner();
+                    let len = env.get_array_length(array)?;
+                    let mut buf = vec![Default::default(); len as usize];
+                    env.$get(array, 0, &mut buf)?;
+
+                    $(
+                        let buf = buf.into_iter().map(|e: $raw| TryFromJavaValue::try_from(e, env)).collect::<Result<Vec<$type>>>()?;
+                    )?
+                    Ok(JavaArray(buf))
+                }
+            }
+
+            impl<'env> IntoJavaValue<'env> for JavaArray<$type> {
+                type Target = JObject<'env>;
+
+                fn into(self, env: JNIEnv<'env>) -> Self::Target {
+                    TryIntoJavaValue::try_into(self, env).unwrap()
+                }
+            }
+
+            impl<'env> FromJavaValue<'env> for JavaArray<$type> {
+                type Source = JObject<'env>;
+
+                fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+                    TryFromJavaValue::try_from(s, env).unwrap()
+                }
+            }
+        )+
+    };
+}
+
+primitive_arrays! {
+    bool: "[Z" [new_boolean_array, set_boolean_array_region, get_boolean_array_region] as jboolean,
+    i8: "[B" [new_byte_array, set_byte_array_region, get_byte_array_region],
+    char: "[C" [new_char_array, set_char_array_region, get_char_array_region] as jchar,
+    i16: "[S" [new_short_array, set_short_array_region, get_short_array_region],
+    i32: "[I" [new_int_array, set_int_array_region, get_int_array_region],
+    i64: "[J" [new_long_array, set_long_array_region, get_long_array_region],
+    f32: "[F" [new_float_array, set_float_array_region, get_float_array_region],
+    f64: "[D" [new_double_array, set_double_array_region, get_double_array_region],
+}
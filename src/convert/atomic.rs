@@ -0,0 +1,215 @@
+//! Counters shared with Java as `java.util.concurrent.atomic.AtomicLong`s.
+//!
+//! [`JAtomicLong`] wraps an `AtomicLong` instance, so that both sides read and update the same value without any further
+//! synchronization. It's converted to and from `AtomicLong` parameters and return values of exported and imported methods:
+//!
+//! ```ignore
+//! pub extern "jni" fn count_lines(text: String, counter: JAtomicLong<'env>) -> JniResult<()> {
+//!     counter.fetch_add(text.lines().count() as i64)?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! A [`JAtomicLong`] is only valid during the native call it was received in; to keep updating a counter from other threads,
+//! turn it into a [`GlobalAtomicLong`] with [`JAtomicLong::to_global`].
+//!
+//! # Memory ordering
+//! Operations are calls to the corresponding `AtomicLong` methods, so they have the semantics the Java memory model gives them:
+//! [`get`](JAtomicLong::get) and [`set`](JAtomicLong::set) behave like reads and writes of a `volatile` variable,
+//! and [`fetch_add`](JAtomicLong::fetch_add) and [`compare_exchange`](JAtomicLong::compare_exchange) are atomic read-modify-write
+//! operations, comparable to Rust atomics with `Ordering::SeqCst`.
+//!
+
+use std::sync::OnceLock;
+
+use jni::errors::{Error, Result};
+use jni::objects::{GlobalRef, JMethodID, JObject, JValue};
+use jni::signature::{JavaType, Primitive};
+use jni::sys::jmethodID;
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+use crate::debug::Tracked;
+
+const ATOMIC_LONG_CLASS: &str = "java/util/concurrent/atomic/AtomicLong";
+
+/// The `AtomicLong` class with the IDs of the methods used by [`JAtomicLong`].
+struct AtomicLongClass {
+    class: GlobalRef,
+    _tracked: Tracked,
+    init: jmethodID,
+    get: jmethodID,
+    set: jmethodID,
+    get_and_add: jmethodID,
+    compare_and_set: jmethodID,
+}
+
+// Method IDs are valid on every thread as long as their class is loaded, which the global reference guarantees
+unsafe impl Send for AtomicLongClass {}
+unsafe impl Sync for AtomicLongClass {}
+
+impl AtomicLongClass {
+    fn get(env: JNIEnv) -> Result<&'static AtomicLongClass> {
+        static CACHE: OnceLock<AtomicLongClass> = OnceLock::new();
+
+        if let Some(class) = CACHE.get() {
+            return Ok(class);
+        }
+
+        let local_class = env.find_class(ATOMIC_LONG_CLASS)?;
+        let method = |name: &str, sig: &str| env.get_method_id(local_class, name, sig).map(JMethodID::into_inner);
+        let class = AtomicLongClass {
+            init: method("<init>", "(J)V")?,
+            get: method("get", "()J")?,
+            set: method("set", "(J)V")?,
+            get_and_add: method("getAndAdd", "(J)J")?,
+            compare_and_set: method("compareAndSet", "(JJ)Z")?,
+            class: env.new_global_ref(local_class)?,
+            _tracked: Tracked::global("JAtomicLong"),
+        };
+        env.delete_local_ref(local_class.into())?;
+
+        // Another thread may have won the race, in which case the IDs are the same anyway
+        Ok(CACHE.get_or_init(|| class))
+    }
+}
+
+/// A `java.util.concurrent.atomic.AtomicLong`, valid as long as its local reference.
+#[derive(Clone, Copy)]
+pub struct JAtomicLong<'env> {
+    env: JNIEnv<'env>,
+    obj: JObject<'env>,
+    class: &'static AtomicLongClass,
+}
+
+impl<'env> JAtomicLong<'env> {
+    /// Creates a new `AtomicLong` holding `value`.
+    pub fn new(env: JNIEnv<'env>, value: i64) -> Result<Self> {
+        let class = AtomicLongClass::get(env)?;
+        let obj = env.new_object_unchecked(&class.class, JMethodID::from(class.init), &[JValue::Long(value)])?;
+
+        Ok(JAtomicLong { env, obj, class })
+    }
+
+    /// Wraps `obj`, failing if it's `null` or not an `AtomicLong`.
+    pub fn from_object(env: JNIEnv<'env>, obj: JObject<'env>) -> Result<Self> {
+        if obj.is_null() {
+            return Err(Error::NullPtr(ATOMIC_LONG_CLASS));
+        }
+
+        let class = AtomicLongClass::get(env)?;
+        if !env.is_instance_of(obj, &class.class)? {
+            return Err(Error::WrongJValueType(ATOMIC_LONG_CLASS, "object"));
+        }
+
+        Ok(JAtomicLong { env, obj, class })
+    }
+
+    /// The wrapped `AtomicLong`.
+    pub fn as_obj(&self) -> JObject<'env> {
+        self.obj
+    }
+
+    fn call(&self, method: jmethodID, ret: Primitive, args: &[JValue]) -> Result<JValue<'env>> {
+        self.env
+            .call_method_unchecked(self.obj, JMethodID::from(method), JavaType::Primitive(ret), args)
+    }
+
+    /// Current value (`AtomicLong.get`).
+    pub fn get(&self) -> Result<i64> {
+        self.call(self.class.get, Primitive::Long, &[])?.j()
+    }
+
+    /// Sets the value (`AtomicLong.set`).
+    pub fn set(&self, value: i64) -> Result<()> {
+        self.call(self.class.set, Primitive::Void, &[JValue::Long(value)])?.v()
+    }
+
+    /// Adds `delta`, returning the previous value (`AtomicLong.getAndAdd`).
+    pub fn fetch_add(&self, delta: i64) -> Result<i64> {
+        self.call(self.class.get_and_add, Primitive::Long, &[JValue::Long(delta)])?.j()
+    }
+
+    /// Sets the value to `new` if it's `current`, like [`AtomicI64::compare_exchange`](std::sync::atomic::AtomicI64::compare_exchange):
+    /// returns `Ok(current)` if the value was updated, and `Err` with a value different from `current` otherwise.
+    ///
+    /// Implemented with `AtomicLong.compareAndSet`, so the value returned in `Err` is read separately from the comparison.
+    pub fn compare_exchange(&self, current: i64, new: i64) -> Result<std::result::Result<i64, i64>> {
+        loop {
+            let args = [JValue::Long(current), JValue::Long(new)];
+            if self.call(self.class.compare_and_set, Primitive::Boolean, &args)?.z()? {
+                return Ok(Ok(current));
+            }
+
+            // If the value went back to `current` in the meantime, try again rather than report a failure with `current`
+            let actual = self.get()?;
+            if actual != current {
+                return Ok(Err(actual));
+            }
+        }
+    }
+
+    /// A reference to the same `AtomicLong` usable from any thread, and after the current native call returns.
+    pub fn to_global(&self) -> Result<GlobalAtomicLong> {
+        Ok(GlobalAtomicLong {
+            obj: self.env.new_global_ref(self.obj)?,
+            _tracked: Tracked::global("GlobalAtomicLong"),
+        })
+    }
+}
+
+/// A global reference to an `AtomicLong`, which can be sent to other threads.
+///
+/// Operations go through [`bind`](GlobalAtomicLong::bind), with the environment of the calling thread.
+#[derive(Clone)]
+pub struct GlobalAtomicLong {
+    obj: GlobalRef,
+    _tracked: Tracked,
+}
+
+impl GlobalAtomicLong {
+    /// The `AtomicLong`, usable with the environment of the current thread.
+    pub fn bind<'a>(&'a self, env: JNIEnv<'a>) -> Result<JAtomicLong<'a>> {
+        Ok(JAtomicLong {
+            env,
+            obj: self.obj.as_obj(),
+            class: AtomicLongClass::get(env)?,
+        })
+    }
+}
+
+impl<'env> Signature for JAtomicLong<'env> {
+    const SIG_TYPE: &'static str = "Ljava/util/concurrent/atomic/AtomicLong;";
+}
+
+impl<'env> TryIntoJavaValue<'env> for JAtomicLong<'env> {
+    type Target = JObject<'env>;
+
+    fn try_into(self, _env: JNIEnv<'env>) -> Result<Self::Target> {
+        Ok(self.obj)
+    }
+}
+
+impl<'env> TryFromJavaValue<'env> for JAtomicLong<'env> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        JAtomicLong::from_object(env, s)
+    }
+}
+
+impl<'env> IntoJavaValue<'env> for JAtomicLong<'env> {
+    type Target = JObject<'env>;
+
+    fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+        self.obj
+    }
+}
+
+impl<'env> FromJavaValue<'env> for JAtomicLong<'env> {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        JAtomicLong::from_object(env, s).unwrap()
+    }
+}
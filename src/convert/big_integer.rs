@@ -0,0 +1,239 @@
+//! Conversions between 128-bit integers and `java.math.BigInteger`.
+//!
+//! [`i128`] and [`u128`] are converted to and from `java.math.BigInteger`:
+//!
+//! ```ignore
+//! pub extern "jni" fn total(amounts: Vec<i64>) -> i128 {
+//!     amounts.into_iter().map(|amount| amount as i128).sum()
+//! }
+//! ```
+//!
+//! is called from Java as `BigInteger total(ArrayList<Long> amounts)`. Java objects are created with the `BigInteger(byte[])`
+//! constructor from the big-endian two's-complement bytes of the value, and read back with `toByteArray`.
+//!
+//! Every `i128` and `u128` fits in a `BigInteger`, but a `BigInteger` received from Java may not fit in the Rust type: such values
+//! throw a `java.lang.ArithmeticException` with safe conversions, like `BigInteger.longValueExact` does, and panic with unchecked
+//! ones.
+//!
+//! [`BigIntegerBytes`] carries the bytes themselves, for integers of any size.
+//!
+
+use jni::errors::{Error, Result};
+use jni::objects::{JObject, JString, JValue};
+use jni::sys::jbyteArray;
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+const BIG_INTEGER_CLASS: &str = "java/math/BigInteger";
+
+/// A `java.math.BigInteger` as its big-endian two's-complement bytes, as given by `BigInteger.toByteArray`.
+///
+/// The bytes of a `BigInteger` received from Java are the shortest representation of its value; those given to Java can have
+/// any number of leading sign bytes, but can't be empty.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigIntegerBytes(pub Vec<u8>);
+
+/// The two's-complement bytes of `value`.
+fn read_bytes(env: JNIEnv, value: JObject) -> Result<Vec<u8>> {
+    if value.is_null() {
+        return Err(Error::NullPtr(BIG_INTEGER_CLASS));
+    }
+
+    let bytes = env.call_method(value, "toByteArray", "()[B", &[])?.l()?;
+    let result = env.convert_byte_array(bytes.into_inner() as jbyteArray)?;
+    env.delete_local_ref(bytes)?;
+    Ok(result)
+}
+
+/// A new `BigInteger` from its two's-complement `bytes`.
+fn new_big_integer<'env>(env: JNIEnv<'env>, bytes: &[u8]) -> Result<JObject<'env>> {
+    let array = <JObject as From<_>>::from(env.byte_array_from_slice(bytes)?);
+    let result = env.new_object(BIG_INTEGER_CLASS, "([B)V", &[JValue::Object(array)]);
+    env.delete_local_ref(array)?;
+    result
+}
+
+/// `bytes` with their sign extended or stripped to `N` bytes, if the value fits in them.
+fn fit_bytes<const N: usize>(bytes: &[u8]) -> Option<[u8; N]> {
+    let sign = match bytes.first() {
+        Some(&first) if first & 0x80 != 0 => 0xff,
+        _ => 0,
+    };
+    let significant = bytes.iter().position(|&b| b != sign).unwrap_or(bytes.len());
+    // The highest bit of the remaining bytes must still give the sign
+    let start = match bytes.get(significant) {
+        Some(&b) if (b & 0x80 != 0) != (sign != 0) => significant.saturating_sub(1),
+        _ => significant,
+    };
+    let value = &bytes[start..];
+    if value.len() > N {
+        return None;
+    }
+
+    let mut result = [sign; N];
+    result[N - value.len()..].copy_from_slice(value);
+    Some(result)
+}
+
+fn i128_from_bytes(bytes: &[u8]) -> Option<i128> {
+    fit_bytes(bytes).map(i128::from_be_bytes)
+}
+
+fn u128_from_bytes(bytes: &[u8]) -> Option<u128> {
+    // A leading zero byte keeps values above `i128::MAX` positive
+    match (bytes.first(), fit_bytes::<17>(bytes)) {
+        (Some(first), _) if first & 0x80 != 0 => None,
+        (_, Some([0, value @ ..])) => Some(u128::from_be_bytes(value)),
+        _ => None,
+    }
+}
+
+fn i128_to_bytes(value: i128) -> Vec<u8> {
+    value.to_be_bytes().to_vec()
+}
+
+fn u128_to_bytes(value: u128) -> Vec<u8> {
+    let mut bytes = vec![0];
+    bytes.extend_from_slice(&value.to_be_bytes());
+    bytes
+}
+
+/// The message of the exception or panic for a `BigInteger` out of range of `rust_type`.
+fn out_of_range_message(env: JNIEnv, value: JObject, rust_type: &str) -> Result<String> {
+    let text = env.call_method(value, "toString", "()Ljava/lang/String;", &[])?.l()?;
+    let text = <String as From<_>>::from(env.get_string(<JString as From<_>>::from(text))?);
+    Ok(format!("BigInteger {} is out of the range of {}", text, rust_type))
+}
+
+macro_rules! big_integer_types {
+    ($($type:ty: ($to_bytes:ident, $from_bytes:ident)),+ $(,)?) => {
+        $(
+            impl Signature for $type {
+                const SIG_TYPE: &'static str = "Ljava/math/BigInteger;";
+            }
+
+            impl<'env> TryIntoJavaValue<'env> for $type {
+                type Target = JObject<'env>;
+
+                fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+                    new_big_integer(env, &$to_bytes(self))
+                }
+            }
+
+            impl<'env> TryFromJavaValue<'env> for $type {
+                type Source = JObject<'env>;
+
+                fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+                    match $from_bytes(&read_bytes(env, s)?) {
+                        Some(value) => Ok(value),
+                        None => {
+                            env.throw_new("java/lang/ArithmeticException", out_of_range_message(env, s, stringify!($type))?)?;
+                            Err(Error::JavaException)
+                        }
+                    }
+                }
+            }
+
+            impl<'env> IntoJavaValue<'env> for $type {
+                type Target = JObject<'env>;
+
+                fn into(self, env: JNIEnv<'env>) -> Self::Target {
+                    new_big_integer(env, &$to_bytes(self)).unwrap()
+                }
+            }
+
+            impl<'env> FromJavaValue<'env> for $type {
+                type Source = JObject<'env>;
+
+                fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+                    $from_bytes(&read_bytes(env, s).unwrap())
+                        .unwrap_or_else(|| panic!("{}", out_of_range_message(env, s, stringify!($type)).unwrap()))
+                }
+            }
+        )+
+    };
+}
+
+big_integer_types! {
+    i128: (i128_to_bytes, i128_from_bytes),
+    u128: (u128_to_bytes, u128_from_bytes),
+}
+
+impl Signature for BigIntegerBytes {
+    const SIG_TYPE: &'static str = "Ljava/math/BigInteger;";
+}
+
+impl<'env> TryIntoJavaValue<'env> for BigIntegerBytes {
+    type Target = JObject<'env>;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        // `BigInteger(byte[])` throws a `NumberFormatException` for empty arrays
+        new_big_integer(env, &self.0)
+    }
+}
+
+impl<'env> TryFromJavaValue<'env> for BigIntegerBytes {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        read_bytes(env, s).map(BigIntegerBytes)
+    }
+}
+
+impl<'env> IntoJavaValue<'env> for BigIntegerBytes {
+    type Target = JObject<'env>;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        new_big_integer(env, &self.0).unwrap()
+    }
+}
+
+impl<'env> FromJavaValue<'env> for BigIntegerBytes {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        BigIntegerBytes(read_bytes(env, s).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortest_bytes_are_sign_extended() {
+        assert_eq!(i128_from_bytes(&[0x7f]), Some(127));
+        assert_eq!(i128_from_bytes(&[0xff]), Some(-1));
+        assert_eq!(i128_from_bytes(&[0x00, 0x80]), Some(128));
+        assert_eq!(i128_from_bytes(&[0xff, 0x7f]), Some(-129));
+        assert_eq!(u128_from_bytes(&[0x00, 0xff]), Some(255));
+    }
+
+    #[test]
+    fn values_round_trip_through_their_bytes() {
+        for value in [0, 1, -1, i64::MIN as i128, i128::MIN, i128::MAX] {
+            assert_eq!(i128_from_bytes(&i128_to_bytes(value)), Some(value));
+        }
+        for value in [0, 1, i128::MAX as u128, i128::MAX as u128 + 1, u128::MAX] {
+            assert_eq!(u128_from_bytes(&u128_to_bytes(value)), Some(value));
+        }
+    }
+
+    #[test]
+    fn values_out_of_range_are_rejected() {
+        // 2^127, the shortest bytes of `i128::MAX + 1`
+        let above_i128 = [&[0x00, 0x80][..], &[0; 15]].concat();
+        assert_eq!(i128_from_bytes(&above_i128), None);
+        assert_eq!(u128_from_bytes(&above_i128), Some(1 << 127));
+
+        // -2^127 - 1
+        let below_i128 = [&[0xff, 0x7f][..], &[0xff; 15]].concat();
+        assert_eq!(i128_from_bytes(&below_i128), None);
+
+        // 2^128
+        let above_u128 = [&[0x01][..], &[0; 16]].concat();
+        assert_eq!(u128_from_bytes(&above_u128), None);
+        assert_eq!(u128_from_bytes(&[0xff]), None);
+    }
+}
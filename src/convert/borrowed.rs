@@ -0,0 +1,176 @@
+//! Borrowed string and byte array parameters.
+//!
+//! Exported methods can take `&str` and `&[u8]` parameters, which borrow the contents of the Java `String` or `byte[]` for the
+//! duration of the call instead of copying them into a `String` or `Vec<u8>`:
+//!
+//! ```ignore
+//! pub extern "jni" fn checksum(name: &str, data: &[u8]) -> i64 {
+//!     /* ... */
+//! }
+//! ```
+//!
+//! is called from Java as `long checksum(String name, byte[] data)`.
+//!
+//! For each borrowed parameter, the generated wrapper holds a guard ([`StrGuard`] or [`ByteArrayGuard`]) until the method has returned
+//! and its return value has been converted. The guard releases the JVM buffer when dropped, which also happens when the method panics.
+//! A `null` string or array fails the conversion (or panics with `#[call_type(unchecked)]`).
+//!
+//! Strings are borrowed through `GetStringUTFChars`, whose "modified UTF-8" only differs from UTF-8 for `'\0'` and characters outside the
+//! Basic Multilingual Plane (e.g. emojis, encoded as surrogate pairs). Strings containing either are decoded into an owned `String`
+//! held by the guard, so the method always sees valid UTF-8.
+//!
+//! Byte arrays are borrowed through `GetByteArrayElements` rather than `GetPrimitiveArrayCritical`: calling back into the JVM, which
+//! the method is free to do, is not allowed inside a critical region. Depending on the JVM the bytes are pinned or copied, and in
+//! either case they are released without copying anything back. [`CriticalBytes`](crate::convert::CriticalBytes) parameters use
+//! a critical region instead, for methods that don't call back into the JVM.
+//!
+
+use std::borrow::Cow;
+use std::ops::Deref;
+use std::os::raw::c_char;
+use std::{ptr, slice};
+
+use jni::errors::{Error, Result};
+use jni::objects::{JObject, JString};
+use jni::strings::JNIStr;
+use jni::sys::{jbyte, jbyteArray, JNI_ABORT};
+use jni::JNIEnv;
+
+use crate::convert::JavaValue;
+
+/// Conversion trait from Java values to guards that Rust values are borrowed from. Used for `&T` parameters of exported methods.
+///
+/// Implemented for [`str`] and `[u8]`.
+pub trait FromJavaValueRef<'env> {
+    /// Conversion source type.
+    type Source: JavaValue<'env>;
+
+    /// Keeps the Java value's contents available until dropped.
+    type Guard: Deref<Target = Self>;
+
+    /// [Java type signature](crate::convert::Signature) of the borrowed value.
+    const SIG_TYPE: &'static str;
+
+    /// Perform the conversion.
+    fn borrow_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self::Guard>;
+}
+
+/// Contents of a Java `String`, released when dropped.
+pub struct StrGuard<'env> {
+    env: JNIEnv<'env>,
+    string: JString<'env>,
+    chars: *const c_char,
+    /// The string decoded into UTF-8, if its modified UTF-8 isn't valid UTF-8.
+    decoded: Option<String>,
+}
+
+impl<'env> Deref for StrGuard<'env> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match &self.decoded {
+            Some(decoded) => decoded,
+            // SAFETY: `chars` stays valid until released on drop, and was checked to be valid UTF-8 in `borrow_from`
+            None => unsafe { std::str::from_utf8_unchecked(JNIStr::from_ptr(self.chars).to_bytes()) },
+        }
+    }
+}
+
+impl<'env> Drop for StrGuard<'env> {
+    fn drop(&mut self) {
+        // Safe to call with a pending exception, and in any case there's no one to report an error to
+        let _ = self.env.release_string_utf_chars(self.string, self.chars);
+    }
+}
+
+impl<'env> FromJavaValueRef<'env> for str {
+    type Source = JString<'env>;
+    type Guard = StrGuard<'env>;
+
+    const SIG_TYPE: &'static str = "Ljava/lang/String;";
+
+    fn borrow_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self::Guard> {
+        let chars = env.get_string_utf_chars(s)?;
+        // SAFETY: `chars` is a NUL-terminated string returned by the JVM
+        let decoded = match Cow::from(unsafe { JNIStr::from_ptr(chars) }) {
+            Cow::Borrowed(_) => None,
+            Cow::Owned(decoded) => Some(decoded),
+        };
+
+        Ok(StrGuard {
+            env,
+            string: s,
+            chars,
+            decoded,
+        })
+    }
+}
+
+/// Contents of a Java `byte[]`, released when dropped.
+pub struct ByteArrayGuard<'env> {
+    env: JNIEnv<'env>,
+    array: jbyteArray,
+    elements: *mut jbyte,
+    len: usize,
+}
+
+impl<'env> Deref for ByteArrayGuard<'env> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: `elements` points to `len` bytes, valid until released on drop
+        unsafe { slice::from_raw_parts(self.elements as *const u8, self.len) }
+    }
+}
+
+impl<'env> Drop for ByteArrayGuard<'env> {
+    fn drop(&mut self) {
+        let raw = self.env.get_native_interface();
+        // SAFETY: `elements` was returned by `GetByteArrayElements` for `array`, and is released once.
+        // Safe to call with a pending exception, and `JNI_ABORT` discards the (unchanged) contents of copies.
+        unsafe {
+            if let Some(release) = (**raw).ReleaseByteArrayElements {
+                release(raw, self.array, self.elements, JNI_ABORT);
+            }
+        }
+    }
+}
+
+impl<'env> FromJavaValueRef<'env> for [u8] {
+    type Source = JObject<'env>;
+    type Guard = ByteArrayGuard<'env>;
+
+    const SIG_TYPE: &'static str = "[B";
+
+    fn borrow_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self::Guard> {
+        if s.is_null() {
+            return Err(Error::NullPtr("byte array"));
+        }
+
+        let array: jbyteArray = s.into_inner();
+        let len = env.get_array_length(array)? as usize;
+        let raw = env.get_native_interface();
+        // SAFETY: `array` is a non-null `byte[]` reference (the type of the parameter in the Java stub)
+        let elements = unsafe {
+            match (**raw).GetByteArrayElements {
+                Some(get) => get(raw, array, ptr::null_mut()),
+                None => return Err(Error::JNIEnvMethodNotFound("GetByteArrayElements")),
+            }
+        };
+
+        if elements.is_null() {
+            return Err(if env.exception_check()? {
+                Error::JavaException
+            } else {
+                Error::NullPtr("GetByteArrayElements result")
+            });
+        }
+
+        Ok(ByteArrayGuard {
+            env,
+            array,
+            elements,
+            len,
+        })
+    }
+}
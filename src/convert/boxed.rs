@@ -0,0 +1,167 @@
+//! Conversions between primitives and their `java.lang` wrapper classes.
+//!
+//! Primitives are passed to Java as primitives, and `Option`s of primitives as nullable wrapper objects.
+//! When a Java API deals in wrapper objects that are never `null` (e.g. `List<Integer>`, or values returned by reflection),
+//! [`Boxed`] converts them directly:
+//!
+//! ```ignore
+//! pub extern "jni" fn sum(values: Vec<Boxed<i64>>) -> Boxed<i64> {
+//!     Boxed(values.into_iter().map(|v| v.0).sum())
+//! }
+//! ```
+//!
+//! is called from Java as `Long sum(ArrayList<Long> values)`.
+//!
+//! Wrapper objects are created with `valueOf`, so that small values come from the caches of the wrapper classes.
+//! When converting from Java, a `null` or an object of another class makes the conversion fail (or panic with `#[call_type(unchecked)]`).
+//!
+//! Elements of `Vec`s, `JavaArray`s and tuples are boxed the same way: a `Vec<i32>` is an `ArrayList<Integer>`, and a
+//! `Vec<Option<i32>>` one that can hold `null`s. Unboxing a `null` element of a `Vec<i32>` fails the conversion as well.
+//!
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use jni::errors::{Error, Result};
+use jni::objects::{GlobalRef, JMethodID, JObject, JStaticMethodID, JValue};
+use jni::signature::JavaType;
+use jni::sys::jmethodID;
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+use crate::debug::Tracked;
+
+/// A primitive converted to and from an instance of its wrapper class, e.g. `Boxed<i32>` and `java.lang.Integer`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Boxed<T>(pub T);
+
+impl<T> From<T> for Boxed<T> {
+    fn from(value: T) -> Self {
+        Boxed(value)
+    }
+}
+
+/// A wrapper class with the IDs of its `valueOf` and unboxing methods.
+struct WrapperClass {
+    class: GlobalRef,
+    _tracked: Tracked,
+    value_of: jmethodID,
+    unbox: jmethodID,
+}
+
+// Method IDs are valid on every thread as long as their class is loaded, which the global reference guarantees
+unsafe impl Send for WrapperClass {}
+unsafe impl Sync for WrapperClass {}
+
+impl WrapperClass {
+    fn get<'c>(
+        cache: &'c OnceLock<WrapperClass>,
+        env: JNIEnv,
+        class: &str,
+        primitive_sig: &str,
+        unbox_method: &str,
+    ) -> Result<&'c WrapperClass> {
+        if let Some(wrapper) = cache.get() {
+            return Ok(wrapper);
+        }
+
+        let local_class = env.find_class(class)?;
+        let value_of_sig = format!("({})L{};", primitive_sig, class);
+        let wrapper = WrapperClass {
+            value_of: env.get_static_method_id(local_class, "valueOf", value_of_sig)?.into_inner(),
+            unbox: env.get_method_id(local_class, unbox_method, format!("(){}", primitive_sig))?.into_inner(),
+            class: env.new_global_ref(local_class)?,
+            _tracked: Tracked::global("Boxed"),
+        };
+        env.delete_local_ref(local_class.into())?;
+
+        // Another thread may have won the race, in which case the IDs are the same anyway
+        Ok(cache.get_or_init(|| wrapper))
+    }
+
+    fn check_instance(&self, env: JNIEnv, s: JObject, class: &'static str) -> Result<()> {
+        if s.is_null() {
+            return Err(Error::NullPtr(class));
+        }
+
+        if !env.is_instance_of(s, &self.class)? {
+            return Err(Error::WrongJValueType(class, "object"));
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! boxed_types {
+    ($($type:ty: $class:literal ($sig:literal) [$unbox_method:literal] |$value:ident, $env:ident| $from_value:expr),+ $(,)?) => {
+        $(
+            impl Boxed<$type> {
+                fn wrapper_class(env: JNIEnv) -> Result<&'static WrapperClass> {
+                    static CACHE: OnceLock<WrapperClass> = OnceLock::new();
+                    WrapperClass::get(&CACHE, env, $class, $sig, $unbox_method)
+                }
+            }
+
+            impl Signature for Boxed<$type> {
+                const SIG_TYPE: &'static str = concat!("L", $class, ";");
+            }
+
+            impl<'env> TryIntoJavaValue<'env> for Boxed<$type> {
+                type Target = JObject<'env>;
+
+                fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+                    let wrapper = Self::wrapper_class(env)?;
+                    let value = JValue::from(TryIntoJavaValue::try_into(self.0, env)?);
+
+                    env.call_static_method_unchecked(
+                        &wrapper.class,
+                        JStaticMethodID::from(wrapper.value_of),
+                        JavaType::from_str(concat!("L", $class, ";")).unwrap(),
+                        &[value],
+                    )?
+                    .l()
+                }
+            }
+
+            impl<'env> TryFromJavaValue<'env> for Boxed<$type> {
+                type Source = JObject<'env>;
+
+                fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+                    let wrapper = Self::wrapper_class(env)?;
+                    wrapper.check_instance(env, s, $class)?;
+
+                    let $value = env.call_method_unchecked(s, JMethodID::from(wrapper.unbox), JavaType::from_str($sig).unwrap(), &[])?;
+                    let $env = env;
+                    Ok(Boxed($from_value?))
+                }
+            }
+
+            impl<'env> IntoJavaValue<'env> for Boxed<$type> {
+                type Target = JObject<'env>;
+
+                fn into(self, env: JNIEnv<'env>) -> Self::Target {
+                    TryIntoJavaValue::try_into(self, env).unwrap()
+                }
+            }
+
+            impl<'env> FromJavaValue<'env> for Boxed<$type> {
+                type Source = JObject<'env>;
+
+                fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+                    TryFromJavaValue::try_from(s, env).unwrap()
+                }
+            }
+        )+
+    };
+}
+
+boxed_types! {
+    bool: "java/lang/Boolean" ("Z") ["booleanValue"] |value, _env| value.z(),
+    i8: "java/lang/Byte" ("B") ["byteValue"] |value, _env| value.b(),
+    char: "java/lang/Character" ("C") ["charValue"] |value, env| value.c().and_then(|c| TryFromJavaValue::try_from(c, env)),
+    i16: "java/lang/Short" ("S") ["shortValue"] |value, _env| value.s(),
+    i32: "java/lang/Integer" ("I") ["intValue"] |value, _env| value.i(),
+    i64: "java/lang/Long" ("J") ["longValue"] |value, _env| value.j(),
+    f32: "java/lang/Float" ("F") ["floatValue"] |value, _env| value.f(),
+    f64: "java/lang/Double" ("D") ["doubleValue"] |value, _env| value.d(),
+}
@@ -0,0 +1,257 @@
+//! Direct `java.nio.ByteBuffer`s, shared with Java without copying their bytes.
+//!
+//! Direct buffers are backed by memory outside of the Java heap, which native code can access in place, e.g. to exchange
+//! payloads with Netty or NIO channels. Two types are converted to and from `ByteBuffer`s:
+//!  * [`DirectByteBuffer<'env>`] borrows the bytes of a buffer allocated by Java (e.g. with `ByteBuffer.allocateDirect`), from
+//!    its position to its limit, for the duration of a native call.
+//!  * [`DirectBytes`] is a Rust allocation given to Java as a direct buffer, and taken back when Java passes the buffer to a
+//!    native method again.
+//!
+//! ```ignore
+//! pub extern "jni" fn checksum(payload: DirectByteBuffer<'_>) -> i64 {
+//!     payload.iter().map(|b| *b as i64).sum()
+//! }
+//!
+//! pub extern "jni" fn encode(message: String) -> DirectBytes {
+//!     DirectBytes::from(message.into_bytes())
+//! }
+//!
+//! pub extern "jni" fn release(buffer: DirectBytes) {}
+//! ```
+//!
+//! # Ownership of `DirectBytes`
+//! The JVM doesn't free the memory of buffers created from native code. Returning a `DirectBytes` gives its allocation to
+//! Java, where it's registered as live until Java passes the buffer back to a native method taking a `DirectBytes` parameter:
+//! the allocation is then owned by Rust again, and freed when the parameter is dropped (unless the method returns it again).
+//! Buffers that are never given back leak their memory, and Java must not use a buffer after giving it back.
+//!
+//! Taking back a buffer that isn't a live `DirectBytes` allocation, like a buffer allocated by Java, a slice of a buffer or a
+//! buffer given back already, fails the conversion (or panics with `#[call_type(unchecked)]`) instead of freeing it.
+//!
+//! # Checks
+//! Converting a buffer that isn't direct to a `DirectByteBuffer` fails (or panics with `#[call_type(unchecked)]`), as does a
+//! `null` buffer. Java code writing to a buffer while Rust reads it is a data race: only share a buffer with one side at a time.
+//!
+
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use jni::errors::{Error, Result};
+use jni::objects::{JByteBuffer, JObject};
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+/// Length of the live `DirectBytes` allocations given to Java, by address. Empty allocations aren't registered.
+fn live_allocations() -> MutexGuard<'static, HashMap<usize, usize>> {
+    static LIVE: OnceLock<Mutex<HashMap<usize, usize>>> = OnceLock::new();
+    LIVE.get_or_init(Default::default).lock().unwrap()
+}
+
+/// Address and capacity of a direct buffer, failing if `buffer` is `null` or isn't direct.
+fn direct_bytes(buffer: JByteBuffer, env: JNIEnv) -> Result<(*mut u8, usize)> {
+    if buffer.is_null() {
+        return Err(Error::NullPtr("byte buffer"));
+    }
+
+    match env.get_direct_buffer_address(buffer) {
+        Ok(bytes) => Ok((bytes.as_mut_ptr(), bytes.len())),
+        // Heap buffers have no address
+        Err(_) => Err(Error::WrongJValueType("direct ByteBuffer", "non-direct ByteBuffer")),
+    }
+}
+
+/// Bytes of a direct `ByteBuffer` allocated by Java, from its position to its limit. See the [module documentation](self).
+pub struct DirectByteBuffer<'env> {
+    raw: JByteBuffer<'env>,
+    bytes: *const u8,
+    len: usize,
+}
+
+impl<'env> DirectByteBuffer<'env> {
+    /// Borrows the bytes of `buffer` from its position to its limit, failing if it isn't a direct buffer.
+    pub fn new(buffer: JByteBuffer<'env>, env: JNIEnv<'env>) -> Result<Self> {
+        let (address, capacity) = direct_bytes(buffer, env)?;
+        let position = env.call_method(buffer, "position", "()I", &[])?.i()? as usize;
+        let limit = env.call_method(buffer, "limit", "()I", &[])?.i()? as usize;
+        debug_assert!(position <= limit && limit <= capacity);
+
+        Ok(DirectByteBuffer {
+            raw: buffer,
+            // SAFETY: `ByteBuffer`s keep their position and limit within their capacity
+            bytes: unsafe { address.add(position) },
+            len: limit - position,
+        })
+    }
+
+    /// The buffer.
+    pub fn raw(&self) -> JByteBuffer<'env> {
+        self.raw
+    }
+}
+
+impl<'env> Deref for DirectByteBuffer<'env> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the buffer is reachable through `raw` for `'env`, which keeps its memory allocated
+        unsafe { std::slice::from_raw_parts(self.bytes, self.len) }
+    }
+}
+
+/// Bytes allocated by Rust and given to Java as a direct `ByteBuffer`. See the [module documentation](self) for ownership.
+pub struct DirectBytes(Box<[u8]>);
+
+impl DirectBytes {
+    /// Bytes to give to Java.
+    pub fn new(bytes: impl Into<Box<[u8]>>) -> Self {
+        DirectBytes(bytes.into())
+    }
+
+    /// The bytes, dropping the buffer.
+    pub fn into_boxed_slice(self) -> Box<[u8]> {
+        self.0
+    }
+
+    /// The bytes, dropping the buffer.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0.into_vec()
+    }
+
+    /// Gives the bytes to Java, as a direct buffer that's live until it's taken back.
+    pub fn into_buffer<'env>(self, env: JNIEnv<'env>) -> Result<JByteBuffer<'env>> {
+        let len = self.0.len();
+        let address = Box::into_raw(self.0) as *mut u8;
+        // SAFETY: the allocation is leaked until the buffer is taken back
+        let bytes = unsafe { std::slice::from_raw_parts_mut(address, len) };
+
+        match env.new_direct_byte_buffer(bytes) {
+            Ok(buffer) => {
+                if len > 0 {
+                    live_allocations().insert(address as usize, len);
+                }
+                Ok(buffer)
+            }
+            Err(e) => {
+                // SAFETY: the allocation wasn't given to Java
+                drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(address, len)) });
+                Err(e)
+            }
+        }
+    }
+
+    /// Takes the bytes of `buffer` back, failing if it isn't a live buffer created by [`DirectBytes::into_buffer`].
+    pub fn take(buffer: JByteBuffer, env: JNIEnv) -> Result<Self> {
+        let (address, capacity) = direct_bytes(buffer, env)?;
+        if capacity == 0 {
+            return Ok(DirectBytes(Box::default()));
+        }
+
+        if live_allocations().remove(&(address as usize)) != Some(capacity) {
+            return Err(Error::WrongJValueType("DirectBytes", "ByteBuffer not created by DirectBytes, or given back already"));
+        }
+
+        // SAFETY: the allocation was leaked by `into_buffer`, with this length, and not taken back since
+        Ok(DirectBytes(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(address, capacity)) }))
+    }
+}
+
+impl From<Vec<u8>> for DirectBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        DirectBytes::new(bytes)
+    }
+}
+
+impl From<Box<[u8]>> for DirectBytes {
+    fn from(bytes: Box<[u8]>) -> Self {
+        DirectBytes(bytes)
+    }
+}
+
+impl Deref for DirectBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl DerefMut for DirectBytes {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl<'env> Signature for DirectByteBuffer<'env> {
+    const SIG_TYPE: &'static str = "Ljava/nio/ByteBuffer;";
+}
+
+impl Signature for DirectBytes {
+    const SIG_TYPE: &'static str = "Ljava/nio/ByteBuffer;";
+}
+
+impl<'env> TryFromJavaValue<'env> for DirectByteBuffer<'env> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        DirectByteBuffer::new(JByteBuffer::from(s), env)
+    }
+}
+
+impl<'env> FromJavaValue<'env> for DirectByteBuffer<'env> {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        DirectByteBuffer::new(JByteBuffer::from(s), env).expect("can't access the bytes of a direct ByteBuffer")
+    }
+}
+
+impl<'env> TryIntoJavaValue<'env> for DirectByteBuffer<'env> {
+    type Target = JObject<'env>;
+
+    fn try_into(self, _env: JNIEnv<'env>) -> Result<Self::Target> {
+        Ok(<JObject as From<_>>::from(self.raw))
+    }
+}
+
+impl<'env> IntoJavaValue<'env> for DirectByteBuffer<'env> {
+    type Target = JObject<'env>;
+
+    fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+        <JObject as From<_>>::from(self.raw)
+    }
+}
+
+impl<'env> TryFromJavaValue<'env> for DirectBytes {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        DirectBytes::take(JByteBuffer::from(s), env)
+    }
+}
+
+impl<'env> FromJavaValue<'env> for DirectBytes {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        DirectBytes::take(JByteBuffer::from(s), env).expect("can't take back the bytes of a direct ByteBuffer")
+    }
+}
+
+impl<'env> TryIntoJavaValue<'env> for DirectBytes {
+    type Target = JObject<'env>;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        self.into_buffer(env).map(<JObject as From<_>>::from)
+    }
+}
+
+impl<'env> IntoJavaValue<'env> for DirectBytes {
+    type Target = JObject<'env>;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        <JObject as From<_>>::from(self.into_buffer(env).expect("can't create a direct ByteBuffer"))
+    }
+}
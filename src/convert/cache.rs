@@ -0,0 +1,132 @@
+//! Cached method lookups for imported methods annotated with `#[cached]`.
+//!
+//! Calling a Java method through JNI by name means looking up its class and method ID on every call. A [`CachedMethod`]
+//! does both lookups on the first call only, and keeps the results for all later calls, from any thread:
+//!
+//! ```ignore
+//! #[cached]
+//! pub extern "java" fn checksum(&self, env: &JNIEnv, data: Vec<i8>) -> JniResult<i64> {}
+//! ```
+//!
+//! The cache holds a global reference to the class, so that it can't be unloaded (which would invalidate the method ID) while
+//! the method may still be called. Since each cache is a `static`, this means for the lifetime of the process.
+//!
+
+use std::sync::OnceLock;
+
+use jni::errors::{Error, Result};
+use jni::objects::{GlobalRef, JMethodID, JObject, JStaticMethodID, JValue};
+use jni::signature::{JavaType, TypeSignature};
+use jni::sys::jmethodID;
+use jni::JNIEnv;
+
+use crate::debug::Tracked;
+
+/// A method ID together with its class and return type.
+struct ResolvedMethod {
+    class: GlobalRef,
+    _tracked: Tracked,
+    id: jmethodID,
+    ret: JavaType,
+}
+
+// Method IDs are valid on every thread as long as their class is loaded, which the global reference guarantees
+unsafe impl Send for ResolvedMethod {}
+unsafe impl Sync for ResolvedMethod {}
+
+/// A method of a Java class, looked up on first use.
+///
+/// Each `CachedMethod` must only be used for a single method: the class, name and signature given on later calls are ignored.
+pub struct CachedMethod {
+    method: OnceLock<ResolvedMethod>,
+}
+
+impl Default for CachedMethod {
+    fn default() -> Self {
+        CachedMethod::new()
+    }
+}
+
+impl CachedMethod {
+    pub const fn new() -> Self {
+        CachedMethod {
+            method: OnceLock::new(),
+        }
+    }
+
+    fn resolve(
+        &self,
+        env: JNIEnv,
+        class: &str,
+        name: &str,
+        signature: impl FnOnce() -> String,
+        is_static: bool,
+    ) -> Result<&ResolvedMethod> {
+        if let Some(method) = self.method.get() {
+            return Ok(method);
+        }
+
+        let signature = signature();
+        let ret = TypeSignature::from_str(&signature)?.ret;
+
+        let local_class = env.find_class(class)?;
+        let id = if is_static {
+            env.get_static_method_id(local_class, name, signature.as_str())?.into_inner()
+        } else {
+            env.get_method_id(local_class, name, signature.as_str())?.into_inner()
+        };
+        let method = ResolvedMethod {
+            class: env.new_global_ref(local_class)?,
+            _tracked: Tracked::global("CachedMethod"),
+            id,
+            ret,
+        };
+        env.delete_local_ref(local_class.into())?;
+
+        // Another thread may have won the race, in which case the ID is the same anyway
+        Ok(self.method.get_or_init(|| method))
+    }
+
+    /// Calls the instance method `name` of `class` on `obj`.
+    pub fn call<'a>(
+        &self,
+        env: JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: &str,
+        name: &str,
+        signature: impl FnOnce() -> String,
+        args: &[JValue],
+    ) -> Result<JValue<'a>> {
+        if obj.is_null() {
+            return Err(Error::NullPtr("call_method obj argument"));
+        }
+
+        let method = self.resolve(env, class, name, signature, false)?;
+        env.call_method_unchecked(obj, JMethodID::from(method.id), method.ret.clone(), args)
+    }
+
+    /// Calls the static method `name` of `class`.
+    pub fn call_static<'a>(
+        &self,
+        env: JNIEnv<'a>,
+        class: &str,
+        name: &str,
+        signature: impl FnOnce() -> String,
+        args: &[JValue],
+    ) -> Result<JValue<'a>> {
+        let method = self.resolve(env, class, name, signature, true)?;
+        env.call_static_method_unchecked(&method.class, JStaticMethodID::from(method.id), method.ret.clone(), args)
+    }
+
+    /// Creates a new object of `class` with the constructor of the given signature.
+    pub fn new_object<'a>(
+        &self,
+        env: JNIEnv<'a>,
+        class: &str,
+        signature: impl FnOnce() -> String,
+        args: &[JValue],
+    ) -> Result<JObject<'a>> {
+        let method = self.resolve(env, class, "<init>", signature, false)?;
+        env.new_object_unchecked(&method.class, JMethodID::from(method.id), args)
+    }
+}
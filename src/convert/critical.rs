@@ -0,0 +1,157 @@
+//! Byte array parameters accessed inside a JNI critical region.
+//!
+//! [`CriticalBytes`] gives an exported method direct access to the contents of a Java `byte[]` through
+//! `GetPrimitiveArrayCritical`, which most JVMs implement by pinning the array (or pausing the garbage collector) rather than
+//! copying it, unlike `Vec<u8>` parameters or the `GetByteArrayElements` used by `&[u8]` parameters (see
+//! [`borrowed`](crate::convert::borrowed)). For large payloads (images, serialized messages), this saves copying the whole array:
+//!
+//! ```ignore
+//! pub extern "jni" fn invert<'env>(mut pixels: CriticalBytes<'env>) {
+//!     pixels.iter_mut().for_each(|p| *p = !*p);
+//! }
+//! ```
+//!
+//! is called from Java as `void invert(byte[] pixels)`. Changes made through [`DerefMut`] are visible to Java once the
+//! parameter is dropped, at the latest when the method returns: the array is then released with mode `0`, which copies them
+//! back if the JVM made a copy. Arrays that weren't borrowed mutably are released with `JNI_ABORT`, without copying anything.
+//!
+//! Inside the critical region, i.e. while the `CriticalBytes` is alive, the thread must not call into the JVM at all: no JNI
+//! calls, no imported methods, no conversions, and no blocking on other threads that might. To make that easier to respect:
+//!  * `CriticalBytes` doesn't give access to the environment.
+//!  * It must be the last parameter of its method, so that no other parameter is converted inside the region (reported at
+//!    compile time).
+//!  * In debug builds, imported methods assert that they're not called inside a critical region.
+//!
+//! Drop the `CriticalBytes` (or move the work on the bytes into its own scope) before calling back into Java. The region is also
+//! left when the method panics, as the array is released while unwinding. A `null` array fails the conversion (or panics with
+//! `#[call_type(unchecked)]`).
+//!
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::{ptr, slice};
+
+use jni::errors::{Error, Result};
+use jni::sys::{jbyteArray, JNI_ABORT};
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, Signature, TryFromJavaValue};
+
+thread_local! {
+    /// Number of critical regions entered by the thread and not left yet.
+    static CRITICAL_REGIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Whether the thread is inside a critical region, i.e. a [`CriticalBytes`] is alive on it.
+pub fn in_critical_region() -> bool {
+    CRITICAL_REGIONS.with(Cell::get) > 0
+}
+
+/// Asserts that the thread isn't inside a critical region, in debug builds. Used by the generated code before calling into the JVM.
+#[doc(hidden)]
+#[inline]
+pub fn debug_assert_outside_critical_region() {
+    debug_assert!(
+        !in_critical_region(),
+        "called into the JVM while a `CriticalBytes` is alive on this thread; drop it first"
+    );
+}
+
+/// Contents of a Java `byte[]`, accessed inside a critical region until dropped. See the [module documentation](self).
+pub struct CriticalBytes<'env> {
+    raw: *mut jni::sys::JNIEnv,
+    array: jbyteArray,
+    elements: *mut u8,
+    len: usize,
+    /// Whether the contents were borrowed mutably, and must be copied back to the array.
+    modified: bool,
+    env: PhantomData<JNIEnv<'env>>,
+}
+
+impl<'env> CriticalBytes<'env> {
+    fn enter(array: jbyteArray, env: JNIEnv<'env>) -> Result<Self> {
+        if array.is_null() {
+            return Err(Error::NullPtr("byte array"));
+        }
+
+        let len = env.get_array_length(array)? as usize;
+        let raw = env.get_native_interface();
+        // SAFETY: `array` is a non-null `byte[]` reference (the type of the parameter in the Java stub)
+        let elements = unsafe {
+            match (**raw).GetPrimitiveArrayCritical {
+                Some(get) => get(raw, array, ptr::null_mut()),
+                None => return Err(Error::JNIEnvMethodNotFound("GetPrimitiveArrayCritical")),
+            }
+        };
+
+        if elements.is_null() {
+            return Err(if env.exception_check()? {
+                Error::JavaException
+            } else {
+                Error::NullPtr("GetPrimitiveArrayCritical result")
+            });
+        }
+
+        CRITICAL_REGIONS.with(|regions| regions.set(regions.get() + 1));
+        Ok(CriticalBytes {
+            raw,
+            array,
+            elements: elements as *mut u8,
+            len,
+            modified: false,
+            env: PhantomData,
+        })
+    }
+}
+
+impl<'env> Deref for CriticalBytes<'env> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: `elements` points to `len` bytes, valid until released on drop
+        unsafe { slice::from_raw_parts(self.elements, self.len) }
+    }
+}
+
+impl<'env> DerefMut for CriticalBytes<'env> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.modified = true;
+        // SAFETY: `elements` points to `len` bytes, valid until released on drop, and only borrowed through `self`
+        unsafe { slice::from_raw_parts_mut(self.elements, self.len) }
+    }
+}
+
+impl<'env> Drop for CriticalBytes<'env> {
+    fn drop(&mut self) {
+        let mode = if self.modified { 0 } else { JNI_ABORT };
+        // SAFETY: `elements` was returned by `GetPrimitiveArrayCritical` for `array`, and is released once.
+        // Safe to call with a pending exception.
+        unsafe {
+            if let Some(release) = (**self.raw).ReleasePrimitiveArrayCritical {
+                release(self.raw, self.array, self.elements.cast(), mode);
+            }
+        }
+        CRITICAL_REGIONS.with(|regions| regions.set(regions.get() - 1));
+    }
+}
+
+impl<'env> Signature for CriticalBytes<'env> {
+    const SIG_TYPE: &'static str = "[B";
+}
+
+impl<'env> TryFromJavaValue<'env> for CriticalBytes<'env> {
+    type Source = jbyteArray;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        CriticalBytes::enter(s, env)
+    }
+}
+
+impl<'env> FromJavaValue<'env> for CriticalBytes<'env> {
+    type Source = jbyteArray;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        CriticalBytes::enter(s, env).unwrap()
+    }
+}
@@ -0,0 +1,213 @@
+//! Text exchanged with Java as bytes in a given charset.
+//!
+//! Some Java APIs pass text around as `byte[]`s in an agreed charset (e.g. ISO-8859-1 or Shift_JIS). [`EncodedString`] is a
+//! `String` on the Rust side and such a `byte[]` on the Java side, encoded and decoded by Java with `String.getBytes(Charset)`
+//! and `new String(byte[], Charset)`. The charset is a type implementing [`Charset`]:
+//!
+//! ```ignore
+//! pub extern "jni" fn normalize(name: EncodedString<ShiftJis>) -> EncodedString<ShiftJis> {
+//!     EncodedString::new(name.trim().to_string())
+//! }
+//! ```
+//!
+//! is called from Java as `byte[] normalize(byte[] name)`.
+//!
+//! Java replaces characters that can't be encoded in the charset with its replacement byte sequence (`?` for ISO-8859-1), and
+//! malformed input with U+FFFD when decoding. A charset the JVM doesn't support makes the conversion fail, with the
+//! `UnsupportedCharsetException` (or `IllegalCharsetNameException`) thrown by `Charset.forName` pending.
+//!
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use jni::errors::{Error, Result};
+use jni::objects::{JObject, JString, JValue};
+use jni::JNIEnv;
+
+use crate::convert::{CachedMethod, FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+/// A charset supported by the JVM, for [`EncodedString`].
+///
+/// ```ignore
+/// enum Cp1252 {}
+///
+/// impl Charset for Cp1252 {
+///     const NAME: &'static str = "windows-1252";
+/// }
+/// ```
+pub trait Charset {
+    /// Name of the charset, as given to `java.nio.charset.Charset.forName`.
+    const NAME: &'static str;
+}
+
+/// ISO-8859-1 (Latin-1).
+pub enum Latin1 {}
+
+impl Charset for Latin1 {
+    const NAME: &'static str = "ISO-8859-1";
+}
+
+/// Shift_JIS.
+pub enum ShiftJis {}
+
+impl Charset for ShiftJis {
+    const NAME: &'static str = "Shift_JIS";
+}
+
+/// UTF-16, little-endian without byte order mark.
+pub enum Utf16Le {}
+
+impl Charset for Utf16Le {
+    const NAME: &'static str = "UTF-16LE";
+}
+
+/// A string converted to and from a Java `byte[]` in the charset `C`.
+pub struct EncodedString<C> {
+    string: String,
+    charset: PhantomData<fn() -> C>,
+}
+
+impl<C> EncodedString<C> {
+    pub fn new(string: String) -> Self {
+        EncodedString {
+            string,
+            charset: PhantomData,
+        }
+    }
+
+    pub fn into_string(self) -> String {
+        self.string
+    }
+}
+
+// Implemented by hand, since derives would require `C` itself to implement the traits
+
+impl<C> Clone for EncodedString<C> {
+    fn clone(&self) -> Self {
+        EncodedString::new(self.string.clone())
+    }
+}
+
+impl<C> fmt::Debug for EncodedString<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.string, f)
+    }
+}
+
+impl<C> PartialEq for EncodedString<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.string == other.string
+    }
+}
+
+impl<C> Eq for EncodedString<C> {}
+
+impl<C> From<String> for EncodedString<C> {
+    fn from(string: String) -> Self {
+        EncodedString::new(string)
+    }
+}
+
+impl<C> Deref for EncodedString<C> {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.string
+    }
+}
+
+impl<C> DerefMut for EncodedString<C> {
+    fn deref_mut(&mut self) -> &mut String {
+        &mut self.string
+    }
+}
+
+/// The `java.nio.charset.Charset` named `name`.
+fn java_charset<'env>(env: JNIEnv<'env>, name: &str) -> Result<JObject<'env>> {
+    static FOR_NAME: CachedMethod = CachedMethod::new();
+
+    let name = env.new_string(name)?;
+    let charset = FOR_NAME
+        .call_static(
+            env,
+            "java/nio/charset/Charset",
+            "forName",
+            || "(Ljava/lang/String;)Ljava/nio/charset/Charset;".to_string(),
+            &[JValue::Object(<JObject as From<_>>::from(name))],
+        )?
+        .l()?;
+    env.delete_local_ref(<JObject as From<_>>::from(name))?;
+
+    Ok(charset)
+}
+
+impl<C> Signature for EncodedString<C> {
+    const SIG_TYPE: &'static str = "[B";
+}
+
+impl<'env, C: Charset> TryIntoJavaValue<'env> for EncodedString<C> {
+    type Target = JObject<'env>;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        static GET_BYTES: CachedMethod = CachedMethod::new();
+
+        let charset = java_charset(env, C::NAME)?;
+        let string = env.new_string(self.string)?;
+        let bytes = GET_BYTES
+            .call(
+                env,
+                <JObject as From<_>>::from(string),
+                "java/lang/String",
+                "getBytes",
+                || "(Ljava/nio/charset/Charset;)[B".to_string(),
+                &[JValue::Object(charset)],
+            )?
+            .l()?;
+
+        env.delete_local_ref(<JObject as From<_>>::from(string))?;
+        env.delete_local_ref(charset)?;
+        Ok(bytes)
+    }
+}
+
+impl<'env, C: Charset> TryFromJavaValue<'env> for EncodedString<C> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        static NEW_STRING: CachedMethod = CachedMethod::new();
+
+        if s.is_null() {
+            return Err(Error::NullPtr("encoded string bytes"));
+        }
+
+        let charset = java_charset(env, C::NAME)?;
+        let string = NEW_STRING.new_object(
+            env,
+            "java/lang/String",
+            || "([BLjava/nio/charset/Charset;)V".to_string(),
+            &[JValue::Object(s), JValue::Object(charset)],
+        )?;
+        let decoded = <String as From<_>>::from(env.get_string(<JString as From<_>>::from(string))?);
+
+        env.delete_local_ref(string)?;
+        env.delete_local_ref(charset)?;
+        Ok(EncodedString::new(decoded))
+    }
+}
+
+impl<'env, C: Charset> IntoJavaValue<'env> for EncodedString<C> {
+    type Target = JObject<'env>;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        TryIntoJavaValue::try_into(self, env).unwrap()
+    }
+}
+
+impl<'env, C: Charset> FromJavaValue<'env> for EncodedString<C> {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        TryFromJavaValue::try_from(s, env).unwrap()
+    }
+}
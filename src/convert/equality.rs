@@ -0,0 +1,229 @@
+//! Java objects as keys of Rust maps and sets.
+//!
+//! [`ByJavaEquality`] wraps a Java object so that it's compared with its `equals` method and hashed with its `hashCode`,
+//! following Java rather than reference identity: two distinct `java.util.UUID` objects for the same UUID are the same key.
+//!
+//! ```ignore
+//! pub extern "jni" fn distinctCount<'env>(ids: Vec<ByJavaEquality<'env>>) -> i32 {
+//!     ids.into_iter().collect::<HashSet<_>>().len() as i32
+//! }
+//! ```
+//!
+//! # Trade-offs
+//! `hashCode` is called once, when the object is wrapped, and the result is kept: hashing is then as cheap as for an `i32`,
+//! but objects whose hash code changes afterwards (e.g. mutable collections) must not be used as keys, just as in a Java `HashMap`.
+//!
+//! `equals` is only called to compare objects with the same hash code, and needs the environment, so a [`ByJavaEquality`] keeps
+//! the one it was created with and can't outlive the native call. `PartialEq` panics if `equals` throws an exception;
+//! use [`ByJavaEquality::java_equals`] to handle it instead.
+//!
+//! Keys that must outlive the call, or be used from other threads, can be compared by their string representation instead:
+//! [`ByJavaString`] calls `toString` once, and compares and hashes the resulting `String`, without needing the environment
+//! afterwards. It's only correct for classes whose `toString` identifies their value, such as `UUID`, `BigInteger` or enums.
+//!
+
+use std::hash::{Hash, Hasher};
+
+use jni::errors::{Error, Result};
+use jni::objects::{GlobalRef, JObject, JString, JValue};
+use jni::JNIEnv;
+
+use crate::convert::{CachedMethod, FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+use crate::debug::{self, Tracked};
+
+const OBJECT_CLASS: &str = "java/lang/Object";
+
+/// A Java object compared with `equals` and hashed with `hashCode`, valid as long as its local reference.
+#[derive(Clone, Copy)]
+pub struct ByJavaEquality<'env> {
+    env: JNIEnv<'env>,
+    obj: JObject<'env>,
+    hash: i32,
+}
+
+impl<'env> ByJavaEquality<'env> {
+    /// Wraps `obj`, calling its `hashCode` method. Fails if `obj` is `null`.
+    pub fn new(env: JNIEnv<'env>, obj: JObject<'env>) -> Result<Self> {
+        static HASH_CODE: CachedMethod = CachedMethod::new();
+
+        if obj.is_null() {
+            return Err(Error::NullPtr("ByJavaEquality object"));
+        }
+
+        let hash = HASH_CODE.call(env, obj, OBJECT_CLASS, "hashCode", || "()I".to_string(), &[])?.i()?;
+        Ok(ByJavaEquality { env, obj, hash })
+    }
+
+    /// The wrapped object.
+    pub fn as_obj(&self) -> JObject<'env> {
+        self.obj
+    }
+
+    /// Hash code of the object, as returned by `hashCode` when it was wrapped.
+    pub fn hash_code(&self) -> i32 {
+        self.hash
+    }
+
+    /// Whether the objects are equal according to `equals`, or the exception it threw.
+    pub fn java_equals(&self, other: &ByJavaEquality) -> Result<bool> {
+        static EQUALS: CachedMethod = CachedMethod::new();
+
+        EQUALS
+            .call(
+                self.env,
+                self.obj,
+                OBJECT_CLASS,
+                "equals",
+                || "(Ljava/lang/Object;)Z".to_string(),
+                &[JValue::Object(other.obj)],
+            )?
+            .z()
+    }
+}
+
+impl PartialEq for ByJavaEquality<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        // Equal objects have equal hash codes, which saves calling `equals` for most pairs of different objects
+        self.hash == other.hash && self.java_equals(other).expect("`equals` threw a Java exception")
+    }
+}
+
+impl Eq for ByJavaEquality<'_> {}
+
+impl Hash for ByJavaEquality<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state)
+    }
+}
+
+impl<'env> Signature for ByJavaEquality<'env> {
+    const SIG_TYPE: &'static str = "Ljava/lang/Object;";
+}
+
+impl<'env> TryIntoJavaValue<'env> for ByJavaEquality<'env> {
+    type Target = JObject<'env>;
+
+    fn try_into(self, _env: JNIEnv<'env>) -> Result<Self::Target> {
+        Ok(self.obj)
+    }
+}
+
+impl<'env> TryFromJavaValue<'env> for ByJavaEquality<'env> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        ByJavaEquality::new(env, s)
+    }
+}
+
+impl<'env> IntoJavaValue<'env> for ByJavaEquality<'env> {
+    type Target = JObject<'env>;
+
+    fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+        self.obj
+    }
+}
+
+impl<'env> FromJavaValue<'env> for ByJavaEquality<'env> {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        ByJavaEquality::new(env, s).unwrap()
+    }
+}
+
+/// A Java object compared and hashed by the string returned by its `toString` method when it was wrapped.
+///
+/// It holds a global reference to the object, so it can be kept after the native call returns and sent to other threads.
+#[derive(Clone)]
+pub struct ByJavaString {
+    obj: GlobalRef,
+    _tracked: Tracked,
+    key: String,
+}
+
+impl ByJavaString {
+    /// Wraps `obj`, calling its `toString` method. Fails if `obj` is `null`.
+    pub fn new(env: JNIEnv, obj: JObject) -> Result<Self> {
+        static TO_STRING: CachedMethod = CachedMethod::new();
+
+        if obj.is_null() {
+            return Err(Error::NullPtr("ByJavaString object"));
+        }
+
+        let string = TO_STRING
+            .call(env, obj, OBJECT_CLASS, "toString", || "()Ljava/lang/String;".to_string(), &[])?
+            .l()?;
+        let key = <String as From<_>>::from(env.get_string(<JString as From<_>>::from(string))?);
+        env.delete_local_ref(string)?;
+
+        Ok(ByJavaString {
+            obj: env.new_global_ref(obj)?,
+            _tracked: Tracked::global("ByJavaString"),
+            key,
+        })
+    }
+
+    /// The wrapped object.
+    pub fn as_obj(&self) -> JObject<'_> {
+        self.obj.as_obj()
+    }
+
+    /// The string representation of the object the key is compared by.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl PartialEq for ByJavaString {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for ByJavaString {}
+
+impl Hash for ByJavaString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state)
+    }
+}
+
+impl Signature for ByJavaString {
+    const SIG_TYPE: &'static str = "Ljava/lang/Object;";
+}
+
+impl<'env> TryIntoJavaValue<'env> for ByJavaString {
+    type Target = JObject<'env>;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        // The local reference is created while `self` still holds the global one
+        let obj = env.new_local_ref::<JObject>(<JObject as From<_>>::from(self.obj.as_obj().into_inner()))?;
+        debug::local_created("ByJavaString");
+        Ok(obj)
+    }
+}
+
+impl<'env> TryFromJavaValue<'env> for ByJavaString {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        ByJavaString::new(env, s)
+    }
+}
+
+impl<'env> IntoJavaValue<'env> for ByJavaString {
+    type Target = JObject<'env>;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        TryIntoJavaValue::try_into(self, env).unwrap()
+    }
+}
+
+impl<'env> FromJavaValue<'env> for ByJavaString {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        ByJavaString::new(env, s).unwrap()
+    }
+}
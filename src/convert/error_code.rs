@@ -0,0 +1,79 @@
+//! Error code enums, converted to Java `int`s.
+//!
+//! `#[derive(ErrorCode)]` on a fieldless enum whose variants all have an explicit discriminant converts it to and from the
+//! discriminant as an `int`, and implements [`ErrorCode`] to look codes and names up from Rust:
+//!
+//! ```ignore
+//! #[derive(Clone, Copy, Debug, PartialEq, ErrorCode)]
+//! #[package(com.example)]
+//! pub enum Status {
+//!     Ok = 0,
+//!     NotFound = 2,
+//!     Busy = 7,
+//! }
+//! ```
+//!
+//! The Java class `com.example.Status` declares a `static native String nameOf(int code)` returning the name of the variant
+//! with that code (e.g. `"NotFound"`), or `null` for unknown codes, so that Java can log readable messages. Its stub, written
+//! with the other [Java stubs](crate#generating-java-stubs), also declares a constant for each variant (e.g. `NOT_FOUND = 2`).
+//!
+//! Codes coming from Java must be the discriminant of a variant: unknown ones throw a `java.lang.IllegalArgumentException`
+//! with safe conversions (even with a custom `exception_class`, as the exception is already pending), and panic with unchecked
+//! ones. Java code sending codes a library version may not know yet should have it take an `i32` and call
+//! [`ErrorCode::from_code`] instead, handling `None`.
+//!
+
+use jni::errors::{Error, Result};
+use jni::objects::JObject;
+use jni::sys::{jint, jstring};
+use jni::JNIEnv;
+
+/// A fieldless enum converted to its discriminant, see the [module documentation](self). Implemented by `#[derive(ErrorCode)]`.
+pub trait ErrorCode: Sized {
+    /// Code and name of each variant, in declaration order.
+    const CODES: &'static [(i32, &'static str)];
+
+    /// Discriminant of the variant.
+    fn code(&self) -> i32;
+
+    /// The variant with discriminant `code`, if any.
+    fn from_code(code: i32) -> Option<Self>;
+
+    /// Name of the variant, e.g. `NotFound`.
+    fn name(&self) -> &'static str {
+        Self::name_of(self.code()).expect("`ErrorCode::CODES` is missing a variant")
+    }
+
+    /// Name of the variant with discriminant `code`, if any.
+    fn name_of(code: i32) -> Option<&'static str> {
+        Self::CODES.iter().find(|(c, _)| *c == code).map(|(_, name)| *name)
+    }
+}
+
+/// The variant with discriminant `code`, or an `IllegalArgumentException` thrown for unknown codes. Used by the generated code.
+#[doc(hidden)]
+pub fn checked_from_code<T: ErrorCode>(env: JNIEnv, code: jint, enum_name: &str) -> Result<T> {
+    match T::from_code(code) {
+        Some(value) => Ok(value),
+        None => {
+            env.throw_new("java/lang/IllegalArgumentException", format!("{} is not a known `{}` code", code, enum_name))?;
+            Err(Error::JavaException)
+        }
+    }
+}
+
+/// The variant with discriminant `code`, panicking for unknown codes. Used by the generated code.
+#[doc(hidden)]
+pub fn unchecked_from_code<T: ErrorCode>(code: jint, enum_name: &str) -> T {
+    T::from_code(code).unwrap_or_else(|| panic!("{} is not a known `{}` code", code, enum_name))
+}
+
+/// Body of the generated `nameOf` native method: the name of the variant with discriminant `code`, or `null`.
+#[doc(hidden)]
+pub fn java_name_of<T: ErrorCode>(env: JNIEnv, code: jint) -> jstring {
+    match T::name_of(code).map(|name| env.new_string(name)) {
+        Some(Ok(name)) => name.into_inner(),
+        // Creating the string failed with an `OutOfMemoryError` pending, thrown when returning to Java
+        Some(Err(_)) | None => JObject::null().into_inner(),
+    }
+}
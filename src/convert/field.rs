@@ -52,7 +52,8 @@ where
 impl<'env, T> Field<'env, T>
 where
     T: Signature + TryIntoJavaValue<'env> + TryFromJavaValue<'env>,
-    <T as TryFromJavaValue<'env>>::Source: TryFrom<JValueWrapper<'env>, Error = JniError>,
+    <T as TryFromJavaValue<'env>>::Source: TryFrom<JValueWrapper<'env>>,
+    <<T as TryFromJavaValue<'env>>::Source as TryFrom<JValueWrapper<'env>>>::Error: Into<JniError>,
     JValue<'env>: From<<T as TryIntoJavaValue<'env>>::Target>,
 {
     pub fn set(&mut self, value: T) -> JniResult<()> {
@@ -72,7 +73,7 @@ where
         )?;
 
         let f = JValueWrapper::from(res);
-        TryInto::try_into(f).and_then(|v| TryFromJavaValue::try_from(v, self.env))
+        TryInto::try_into(f).map_err(Into::into).and_then(|v| TryFromJavaValue::try_from(v, self.env))
     }
 
     // Java object is not sufficient to retrieve parent object / field owner
@@ -102,7 +103,8 @@ where
 impl<'env, T> Field<'env, T>
 where
     T: Signature + IntoJavaValue<'env> + FromJavaValue<'env>,
-    <T as FromJavaValue<'env>>::Source: TryFrom<JValueWrapper<'env>, Error = JniError>,
+    <T as FromJavaValue<'env>>::Source: TryFrom<JValueWrapper<'env>>,
+    <<T as FromJavaValue<'env>>::Source as TryFrom<JValueWrapper<'env>>>::Error: Into<JniError>,
     JValue<'env>: From<<T as IntoJavaValue<'env>>::Target>,
 {
     pub fn set_unchecked(&mut self, value: T) {
@@ -125,6 +127,7 @@ where
             .unwrap();
 
         TryInto::try_into(JValueWrapper::from(res))
+            .map_err(Into::<JniError>::into)
             .map(|v| FromJavaValue::from(v, self.env))
             .unwrap()
     }
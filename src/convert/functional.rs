@@ -0,0 +1,225 @@
+//! Java functional interfaces called from Rust.
+//!
+//! [`JavaCallback`] wraps a `Runnable`, `Consumer` or `BiConsumer`, depending on the number of its arguments, received
+//! as a parameter of an exported method (usually a Java lambda), and calls it with Rust values:
+//!
+//! ```ignore
+//! pub extern "jni" fn for_each_line(text: String, callback: JavaCallback<'env, (String,)>) -> JniResult<()> {
+//!     for line in text.lines() {
+//!         callback.call((line.to_string(),))?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! is called from Java as `forEachLine(String text, Consumer callback)`. The arguments are converted like the parameters of
+//! imported methods, and boxed: `Consumer<Integer>` is a `JavaCallback<'env, (i32,)>`. An exception thrown by the callback
+//! is returned as an `Err`, and left pending for the Java caller of the native method.
+//!
+//! A [`JavaCallback`] is only valid during the native call it was received in; to call it later or from other threads,
+//! turn it into a [`GlobalJavaCallback`] with [`JavaCallback::to_global`]. Its [`call`](GlobalJavaCallback::call) attaches the
+//! calling thread to the JVM as needed.
+//!
+
+use std::marker::PhantomData;
+
+use jni::errors::{Error, Result};
+use jni::objects::{GlobalRef, JObject, JValue};
+use jni::{JNIEnv, JavaVM};
+
+use crate::convert::{FromJavaValue, IntoJavaValue, JavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+use crate::debug::Tracked;
+
+// Local references created by a single call are released as soon as it completes.
+const LOCAL_FRAME_CAPACITY: i32 = 16;
+
+/// Arguments of a [`JavaCallback`], as a tuple: `()`, `(A,)` or `(A, B)`.
+///
+/// Their number selects the functional interface of the callback: `Runnable`, `Consumer` or `BiConsumer`.
+pub trait CallbackArgs {
+    /// Class name of the functional interface, with slashes.
+    const INTERFACE: &'static str;
+    /// Descriptor of the functional interface.
+    const SIG_TYPE: &'static str;
+    /// Name of the method called, `run` or `accept`.
+    const METHOD: &'static str;
+    /// Descriptor of the method called: as the interfaces are generic, its parameters are `Object`s.
+    const METHOD_SIG: &'static str;
+
+    /// Converts and boxes each argument.
+    fn into_objects<'env>(self, env: JNIEnv<'env>) -> Result<Vec<JObject<'env>>>;
+}
+
+impl CallbackArgs for () {
+    const INTERFACE: &'static str = "java/lang/Runnable";
+    const SIG_TYPE: &'static str = "Ljava/lang/Runnable;";
+    const METHOD: &'static str = "run";
+    const METHOD_SIG: &'static str = "()V";
+
+    fn into_objects<'env>(self, _env: JNIEnv<'env>) -> Result<Vec<JObject<'env>>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<A> CallbackArgs for (A,)
+where
+    A: for<'env> TryIntoJavaValue<'env>,
+{
+    const INTERFACE: &'static str = "java/util/function/Consumer";
+    const SIG_TYPE: &'static str = "Ljava/util/function/Consumer;";
+    const METHOD: &'static str = "accept";
+    const METHOD_SIG: &'static str = "(Ljava/lang/Object;)V";
+
+    fn into_objects<'env>(self, env: JNIEnv<'env>) -> Result<Vec<JObject<'env>>> {
+        Ok(vec![TryIntoJavaValue::try_into(self.0, env)?.autobox(env)])
+    }
+}
+
+impl<A, B> CallbackArgs for (A, B)
+where
+    A: for<'env> TryIntoJavaValue<'env>,
+    B: for<'env> TryIntoJavaValue<'env>,
+{
+    const INTERFACE: &'static str = "java/util/function/BiConsumer";
+    const SIG_TYPE: &'static str = "Ljava/util/function/BiConsumer;";
+    const METHOD: &'static str = "accept";
+    const METHOD_SIG: &'static str = "(Ljava/lang/Object;Ljava/lang/Object;)V";
+
+    fn into_objects<'env>(self, env: JNIEnv<'env>) -> Result<Vec<JObject<'env>>> {
+        Ok(vec![
+            TryIntoJavaValue::try_into(self.0, env)?.autobox(env),
+            TryIntoJavaValue::try_into(self.1, env)?.autobox(env),
+        ])
+    }
+}
+
+/// A Java `Runnable`, `Consumer` or `BiConsumer` taking `Args`, valid as long as its local reference.
+pub struct JavaCallback<'env, Args: CallbackArgs> {
+    env: JNIEnv<'env>,
+    obj: JObject<'env>,
+    args: PhantomData<fn(Args)>,
+}
+
+// Not derived, as `Args` doesn't need to be `Copy`
+impl<'env, Args: CallbackArgs> Clone for JavaCallback<'env, Args> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'env, Args: CallbackArgs> Copy for JavaCallback<'env, Args> {}
+
+impl<'env, Args: CallbackArgs> JavaCallback<'env, Args> {
+    /// Wraps `obj`, failing if it's `null` or doesn't implement the functional interface of `Args`.
+    pub fn from_object(env: JNIEnv<'env>, obj: JObject<'env>) -> Result<Self> {
+        if obj.is_null() {
+            return Err(Error::NullPtr(Args::INTERFACE));
+        }
+        if !env.is_instance_of(obj, Args::INTERFACE)? {
+            return Err(Error::WrongJValueType(Args::INTERFACE, "object"));
+        }
+
+        Ok(JavaCallback {
+            env,
+            obj,
+            args: PhantomData,
+        })
+    }
+
+    /// The wrapped functional interface.
+    pub fn as_obj(&self) -> JObject<'env> {
+        self.obj
+    }
+
+    /// Calls the callback with `args`, returning `Err(Error::JavaException)` if it throws.
+    pub fn call(&self, args: Args) -> Result<()> {
+        self.env.with_local_frame(LOCAL_FRAME_CAPACITY, || {
+            let args: Vec<JValue> = args.into_objects(self.env)?.into_iter().map(JValue::Object).collect();
+            self.env.call_method(self.obj, Args::METHOD, Args::METHOD_SIG, &args)?;
+            Ok(JObject::null())
+        })?;
+
+        Ok(())
+    }
+
+    /// A reference to the same callback usable from any thread, and after the current native call returns.
+    pub fn to_global(&self) -> Result<GlobalJavaCallback<Args>> {
+        Ok(GlobalJavaCallback {
+            vm: self.env.get_java_vm()?,
+            obj: self.env.new_global_ref(self.obj)?,
+            _tracked: Tracked::global("GlobalJavaCallback"),
+            args: PhantomData,
+        })
+    }
+}
+
+/// A global reference to a Java `Runnable`, `Consumer` or `BiConsumer`, which can be sent to other threads.
+pub struct GlobalJavaCallback<Args: CallbackArgs> {
+    vm: JavaVM,
+    obj: GlobalRef,
+    _tracked: Tracked,
+    args: PhantomData<fn(Args)>,
+}
+
+impl<Args: CallbackArgs> GlobalJavaCallback<Args> {
+    /// The callback, usable with the environment of the current thread.
+    pub fn bind<'a>(&'a self, env: JNIEnv<'a>) -> JavaCallback<'a, Args> {
+        JavaCallback {
+            env,
+            obj: self.obj.as_obj(),
+            args: PhantomData,
+        }
+    }
+
+    /// Calls the callback with `args` from the current thread, attaching it to the JVM for the duration of the call if it
+    /// isn't attached already.
+    ///
+    /// Returns `Err(Error::JavaException)` if the callback throws. The exception is left pending on threads that were
+    /// already attached, and cleared before detaching the others.
+    pub fn call(&self, args: Args) -> Result<()> {
+        let was_attached = self.vm.get_env().is_ok();
+        let env = self.vm.attach_current_thread()?;
+
+        let result = self.bind(*env).call(args);
+        if result.is_err() && !was_attached {
+            let _ = env.exception_clear();
+        }
+        result
+    }
+}
+
+impl<'env, Args: CallbackArgs> Signature for JavaCallback<'env, Args> {
+    const SIG_TYPE: &'static str = Args::SIG_TYPE;
+}
+
+impl<'env, Args: CallbackArgs> TryIntoJavaValue<'env> for JavaCallback<'env, Args> {
+    type Target = JObject<'env>;
+
+    fn try_into(self, _env: JNIEnv<'env>) -> Result<Self::Target> {
+        Ok(self.obj)
+    }
+}
+
+impl<'env, Args: CallbackArgs> TryFromJavaValue<'env> for JavaCallback<'env, Args> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        JavaCallback::from_object(env, s)
+    }
+}
+
+impl<'env, Args: CallbackArgs> IntoJavaValue<'env> for JavaCallback<'env, Args> {
+    type Target = JObject<'env>;
+
+    fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+        self.obj
+    }
+}
+
+impl<'env, Args: CallbackArgs> FromJavaValue<'env> for JavaCallback<'env, Args> {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        JavaCallback::from_object(env, s).unwrap()
+    }
+}
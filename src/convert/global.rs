@@ -0,0 +1,305 @@
+//! Java objects kept by Rust after the native call they were received in.
+//!
+//! Local references, like the `JObject<'env>` of a parameter, are deleted by the JVM when the native call returns: keeping one
+//! in Rust state (a `static`, a struct given to another thread, ...) is a use-after-free. Two types keep Java objects instead:
+//!  * [`Global<T>`] holds a global reference, which keeps the object alive until it's dropped.
+//!  * [`Weak<T>`] holds a weak global reference, which doesn't keep the object from being collected, and is upgraded to the
+//!    object while it's still alive.
+//!
+//! Both are `Send` and `Sync`, and are converted to and from the Java type of `T`, whose value is converted from the object when
+//! it's needed, with an environment of the thread using it:
+//!
+//! ```ignore
+//! static LISTENER: Mutex<Option<Global<Listener>>> = Mutex::new(None);
+//!
+//! pub extern "jni" fn register(listener: Global<Listener>) {
+//!     *LISTENER.lock().unwrap() = Some(listener);
+//! }
+//!
+//! // Later, from any thread attached to the JVM
+//! let listener: Listener = LISTENER.lock().unwrap().as_ref().unwrap().materialize(env)?;
+//! ```
+//!
+//! The references are deleted when the values are dropped, from any thread: threads that aren't attached to the JVM are
+//! attached for the time it takes. Clones share the reference of the value they're cloned from.
+//!
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use jni::errors::{Error, Result};
+use jni::objects::{GlobalRef, JObject};
+use jni::sys::jobject;
+use jni::{JNIEnv, JavaVM};
+
+use crate::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+use crate::debug::{self, Tracked};
+
+/// A Java object of the Java type of `T`, kept alive by a global reference. See the [module documentation](self).
+pub struct Global<T> {
+    obj: GlobalRef,
+    _tracked: Tracked,
+    // `T` is only converted from the object, which is shared between threads
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Global<T> {
+    /// Converts `value` to its Java object, and keeps the object.
+    pub fn new<'env>(env: JNIEnv<'env>, value: T) -> Result<Self>
+    where
+        T: TryIntoJavaValue<'env>,
+        <T as TryIntoJavaValue<'env>>::Target: Into<JObject<'env>>,
+    {
+        let obj: JObject = TryIntoJavaValue::try_into(value, env)?.into();
+        Global::from_obj(env, obj)
+    }
+
+    /// Keeps `obj`, which must be of the Java type of `T`.
+    pub fn from_obj(env: JNIEnv, obj: JObject) -> Result<Self> {
+        Ok(Global {
+            obj: env.new_global_ref(obj)?,
+            _tracked: Tracked::global("Global"),
+            _type: PhantomData,
+        })
+    }
+
+    /// The object.
+    pub fn as_obj(&self) -> JObject<'_> {
+        self.obj.as_obj()
+    }
+
+    /// A local reference to the object, valid during the native call `env` belongs to.
+    pub fn local<'env>(&self, env: JNIEnv<'env>) -> Result<JObject<'env>> {
+        let obj = env.new_local_ref::<JObject>(<JObject as From<_>>::from(self.obj.as_obj().into_inner()))?;
+        debug::local_created("Global");
+        Ok(obj)
+    }
+
+    /// Converts the object to `T`, with the environment of the current thread.
+    pub fn materialize<'env>(&self, env: JNIEnv<'env>) -> Result<T>
+    where
+        T: TryFromJavaValue<'env>,
+        <T as TryFromJavaValue<'env>>::Source: From<JObject<'env>>,
+    {
+        let obj = self.local(env)?;
+        <T as TryFromJavaValue<'env>>::try_from(Into::into(obj), env)
+    }
+
+    /// A weak reference to the object.
+    pub fn downgrade(&self, env: JNIEnv) -> Result<Weak<T>> {
+        Weak::from_obj(env, self.obj.as_obj())
+    }
+}
+
+impl<T> Clone for Global<T> {
+    fn clone(&self) -> Self {
+        Global {
+            obj: self.obj.clone(),
+            _tracked: self._tracked.clone(),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Global<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Global").field(&self.obj.as_obj().into_inner()).finish()
+    }
+}
+
+/// A weak global reference, deleted when the last `Weak` sharing it is dropped.
+struct WeakRef {
+    raw: jobject,
+    vm: JavaVM,
+}
+
+// Weak global references are valid on every thread, and only deleted on drop
+unsafe impl Send for WeakRef {}
+unsafe impl Sync for WeakRef {}
+
+impl Drop for WeakRef {
+    fn drop(&mut self) {
+        let delete = |env: &JNIEnv| {
+            let raw = env.get_native_interface();
+            // SAFETY: `raw` is a live weak global reference, deleted once
+            unsafe {
+                if let Some(delete) = (**raw).DeleteWeakGlobalRef {
+                    delete(raw, self.raw);
+                }
+            }
+        };
+
+        match self.vm.get_env() {
+            Ok(env) => delete(&env),
+            Err(_) => {
+                if let Ok(env) = self.vm.attach_current_thread() {
+                    delete(&env);
+                }
+            }
+        }
+    }
+}
+
+/// A Java object of the Java type of `T`, which can be collected while it's referenced. See the [module documentation](self).
+pub struct Weak<T> {
+    weak: Arc<WeakRef>,
+    _tracked: Tracked,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Weak<T> {
+    /// A weak reference to `obj`, which must be of the Java type of `T`.
+    pub fn from_obj(env: JNIEnv, obj: JObject) -> Result<Self> {
+        let raw = env.get_native_interface();
+        // SAFETY: `obj` is a valid reference, or `null`
+        let weak = unsafe {
+            match (**raw).NewWeakGlobalRef {
+                Some(new) => new(raw, obj.into_inner()),
+                None => return Err(Error::JNIEnvMethodNotFound("NewWeakGlobalRef")),
+            }
+        };
+        if weak.is_null() && !obj.is_null() {
+            return Err(Error::NullPtr("NewWeakGlobalRef result"));
+        }
+
+        Ok(Weak {
+            weak: Arc::new(WeakRef { raw: weak, vm: env.get_java_vm()? }),
+            _tracked: Tracked::global("Weak"),
+            _type: PhantomData,
+        })
+    }
+
+    /// A local reference to the object, or `None` if it was collected.
+    pub fn local<'env>(&self, env: JNIEnv<'env>) -> Result<Option<JObject<'env>>> {
+        if self.weak.raw.is_null() {
+            return Ok(None);
+        }
+
+        // A new local reference to a weak reference is `null` once the object is collected
+        let obj = env.new_local_ref::<JObject>(<JObject as From<_>>::from(self.weak.raw))?;
+        if obj.is_null() {
+            return Ok(None);
+        }
+        debug::local_created("Weak");
+        Ok(Some(obj))
+    }
+
+    /// Converts the object to `T`, or `None` if it was collected.
+    pub fn upgrade<'env>(&self, env: JNIEnv<'env>) -> Result<Option<T>>
+    where
+        T: TryFromJavaValue<'env>,
+        <T as TryFromJavaValue<'env>>::Source: From<JObject<'env>>,
+    {
+        match self.local(env)? {
+            Some(obj) => <T as TryFromJavaValue<'env>>::try_from(Into::into(obj), env).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// A global reference to the object, keeping it alive, or `None` if it was collected.
+    pub fn upgrade_global(&self, env: JNIEnv) -> Result<Option<Global<T>>> {
+        match self.local(env)? {
+            Some(obj) => {
+                let global = Global::from_obj(env, obj);
+                env.delete_local_ref(obj)?;
+                global.map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Whether the object was collected.
+    pub fn is_collected(&self, env: JNIEnv) -> Result<bool> {
+        env.is_same_object(<JObject as From<_>>::from(self.weak.raw), JObject::null())
+    }
+}
+
+impl<T> Clone for Weak<T> {
+    fn clone(&self) -> Self {
+        Weak {
+            weak: self.weak.clone(),
+            _tracked: self._tracked.clone(),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Weak<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Weak").field(&self.weak.raw).finish()
+    }
+}
+
+impl<T: Signature> Signature for Global<T> {
+    const SIG_TYPE: &'static str = T::SIG_TYPE;
+}
+
+impl<T: Signature> Signature for Weak<T> {
+    const SIG_TYPE: &'static str = T::SIG_TYPE;
+}
+
+impl<'env, T: Signature> TryIntoJavaValue<'env> for Global<T> {
+    type Target = JObject<'env>;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        self.local(env)
+    }
+}
+
+impl<'env, T: Signature> TryFromJavaValue<'env> for Global<T> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        Global::from_obj(env, s)
+    }
+}
+
+impl<'env, T: Signature> IntoJavaValue<'env> for Global<T> {
+    type Target = JObject<'env>;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        self.local(env).unwrap()
+    }
+}
+
+impl<'env, T: Signature> FromJavaValue<'env> for Global<T> {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        Global::from_obj(env, s).unwrap()
+    }
+}
+
+/// Collected objects are converted to `null`.
+impl<'env, T: Signature> TryIntoJavaValue<'env> for Weak<T> {
+    type Target = JObject<'env>;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        Ok(self.local(env)?.unwrap_or_else(JObject::null))
+    }
+}
+
+impl<'env, T: Signature> TryFromJavaValue<'env> for Weak<T> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        Weak::from_obj(env, s)
+    }
+}
+
+impl<'env, T: Signature> IntoJavaValue<'env> for Weak<T> {
+    type Target = JObject<'env>;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        TryIntoJavaValue::try_into(self, env).unwrap()
+    }
+}
+
+impl<'env, T: Signature> FromJavaValue<'env> for Weak<T> {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        Weak::from_obj(env, s).unwrap()
+    }
+}
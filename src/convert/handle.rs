@@ -0,0 +1,299 @@
+//! Rust state owned by Java objects, through `long` handles.
+//!
+//! A common JNI pattern is to keep a `Box<State>` pointer in a `long` field of a Java object, and to pass it back to each
+//! native call. [`Handle<T>`] and [`HandleRef<'env, T>`] do the pointer juggling: both are converted to and from `long`s,
+//!
+//! ```ignore
+//! pub extern "jni" fn create(capacity: i32) -> Handle<Buffer> {
+//!     Handle::new(Buffer::with_capacity(capacity as usize))
+//! }
+//!
+//! pub extern "jni" fn len(buffer: HandleRef<'_, Buffer>) -> i32 {
+//!     buffer.len() as i32
+//! }
+//!
+//! pub extern "jni" fn destroy(buffer: Handle<Buffer>) {}
+//! ```
+//!
+//! and the type of a parameter tells whether the method borrows the state or consumes it: a [`HandleRef`] parameter borrows
+//! it for the duration of the call, and a [`Handle`] parameter takes it back, so that it's dropped when the method returns
+//! (unless the method returns it again).
+//!
+//! # Checks
+//! Live handles are registered with their type. Fallible conversions (the default `#[call_type(safe)]`) fail on a `0` handle,
+//! on a handle that was already consumed and on a handle of another type, instead of dereferencing it.
+//!
+//! With `#[call_type(unchecked)]`, handles are trusted: only `0` is checked for, with a panic. Passing a consumed handle
+//! is undefined behavior, as it would be with hand-written pointer casts.
+//!
+//! In both cases, Java must not consume a handle while another thread uses it, which is why the state must be [`Sync`].
+//!
+
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use jni::errors::{Error, Result};
+use jni::sys::jlong;
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+/// Number of live handles by address and type: handles of zero-sized types share their address.
+type LiveHandles = HashMap<(usize, TypeId), usize>;
+
+fn live_handles() -> MutexGuard<'static, LiveHandles> {
+    static LIVE: OnceLock<Mutex<LiveHandles>> = OnceLock::new();
+    LIVE.get_or_init(Default::default).lock().unwrap()
+}
+
+fn key<T: 'static>(handle: jlong) -> (usize, TypeId) {
+    (handle as usize, TypeId::of::<T>())
+}
+
+/// Unregisters `handle`, returning whether it was live.
+fn release(live: &mut LiveHandles, key: (usize, TypeId)) -> bool {
+    let count = match live.get_mut(&key) {
+        Some(count) => count,
+        None => return false,
+    };
+
+    *count -= 1;
+    if *count == 0 {
+        live.remove(&key);
+    }
+    true
+}
+
+fn not_live<T>(handle: jlong) -> Error {
+    if handle == 0 {
+        Error::NullPtr("handle")
+    } else {
+        Error::WrongJValueType(type_name::<T>(), "destroyed handle, or handle of another type")
+    }
+}
+
+/// Rust state owned by Java through a `long` handle.
+///
+/// Returning a `Handle<T>` gives the state to Java, and taking a `Handle<T>` parameter takes it back.
+pub struct Handle<T: Send + Sync + 'static>(Box<T>);
+
+impl<T: Send + Sync + 'static> Handle<T> {
+    /// Boxes `value`, to give it to Java.
+    pub fn new(value: T) -> Self {
+        Handle(Box::new(value))
+    }
+
+    /// The state, dropping the handle.
+    pub fn into_inner(self) -> T {
+        *self.0
+    }
+
+    /// Gives the state to Java, as a handle that's live until it's consumed.
+    pub fn into_raw(self) -> jlong {
+        let handle = Box::into_raw(self.0) as jlong;
+        *live_handles().entry(key::<T>(handle)).or_default() += 1;
+        handle
+    }
+
+    /// Takes the state of `handle` back, failing if it isn't a live handle of a `T`.
+    pub fn take(handle: jlong) -> Result<Self> {
+        if !release(&mut live_handles(), key::<T>(handle)) {
+            return Err(not_live::<T>(handle));
+        }
+
+        // SAFETY: the handle was created by `into_raw` for a `T`, and not consumed since
+        Ok(Handle(unsafe { Box::from_raw(handle as *mut T) }))
+    }
+
+    /// Takes the state of `handle` back, without checking it.
+    ///
+    /// # Safety
+    /// `handle` must have been created by [`Handle::into_raw`] for a `T`, and not consumed since.
+    ///
+    /// # Panics
+    /// If `handle` is `0`.
+    pub unsafe fn from_raw(handle: jlong) -> Self {
+        assert_ne!(handle, 0, "null handle");
+        release(&mut live_handles(), key::<T>(handle));
+        Handle(Box::from_raw(handle as *mut T))
+    }
+}
+
+impl<T: Send + Sync + 'static> Deref for Handle<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Send + Sync + 'static> DerefMut for Handle<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Rust state owned by Java through a `long` handle, borrowed for the duration of a native call.
+pub struct HandleRef<'env, T: Send + Sync + 'static>(&'env T);
+
+impl<'env, T: Send + Sync + 'static> HandleRef<'env, T> {
+    /// Borrows the state of `handle`, failing if it isn't a live handle of a `T`.
+    pub fn get(handle: jlong) -> Result<Self> {
+        if !live_handles().contains_key(&key::<T>(handle)) {
+            return Err(not_live::<T>(handle));
+        }
+
+        // SAFETY: the handle is live, and Java doesn't consume it while it's borrowed
+        Ok(HandleRef(unsafe { &*(handle as *const T) }))
+    }
+
+    /// Borrows the state of `handle`, without checking it.
+    ///
+    /// # Safety
+    /// `handle` must have been created by [`Handle::into_raw`] for a `T`, and not be consumed while it's borrowed.
+    ///
+    /// # Panics
+    /// If `handle` is `0`.
+    pub unsafe fn from_raw(handle: jlong) -> Self {
+        assert_ne!(handle, 0, "null handle");
+        HandleRef(&*(handle as *const T))
+    }
+}
+
+impl<'env, T: Send + Sync + 'static> Deref for HandleRef<'env, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.0
+    }
+}
+
+impl<T: Send + Sync + 'static> Signature for Handle<T> {
+    const SIG_TYPE: &'static str = <jlong as Signature>::SIG_TYPE;
+}
+
+impl<'env, T: Send + Sync + 'static> Signature for HandleRef<'env, T> {
+    const SIG_TYPE: &'static str = <jlong as Signature>::SIG_TYPE;
+}
+
+impl<'env, T: Send + Sync + 'static> TryIntoJavaValue<'env> for Handle<T> {
+    type Target = jlong;
+
+    fn try_into(self, _env: JNIEnv<'env>) -> Result<Self::Target> {
+        Ok(self.into_raw())
+    }
+}
+
+impl<'env, T: Send + Sync + 'static> IntoJavaValue<'env> for Handle<T> {
+    type Target = jlong;
+
+    fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+        self.into_raw()
+    }
+}
+
+impl<'env, T: Send + Sync + 'static> TryFromJavaValue<'env> for Handle<T> {
+    type Source = jlong;
+
+    fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> Result<Self> {
+        Handle::take(s)
+    }
+}
+
+impl<'env, T: Send + Sync + 'static> FromJavaValue<'env> for Handle<T> {
+    type Source = jlong;
+
+    fn from(s: Self::Source, _env: JNIEnv<'env>) -> Self {
+        // SAFETY: unchecked conversions trust Java with its handles
+        unsafe { Handle::from_raw(s) }
+    }
+}
+
+impl<'env, T: Send + Sync + 'static> TryFromJavaValue<'env> for HandleRef<'env, T> {
+    type Source = jlong;
+
+    fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> Result<Self> {
+        HandleRef::get(s)
+    }
+}
+
+impl<'env, T: Send + Sync + 'static> FromJavaValue<'env> for HandleRef<'env, T> {
+    type Source = jlong;
+
+    fn from(s: Self::Source, _env: JNIEnv<'env>) -> Self {
+        // SAFETY: unchecked conversions trust Java with its handles
+        unsafe { HandleRef::from_raw(s) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    struct Counted<'a>(&'a AtomicUsize);
+
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    // These tests don't need a JVM, so that `cargo miri test handle` can check the pointer casts
+
+    #[test]
+    fn handles_are_borrowed_and_consumed() {
+        let handle = Handle::new(vec![1, 2, 3]).into_raw();
+
+        assert_eq!(HandleRef::<Vec<i32>>::get(handle).unwrap().len(), 3);
+        assert_eq!(unsafe { HandleRef::<Vec<i32>>::from_raw(handle) }[2], 3);
+
+        let mut owned = Handle::<Vec<i32>>::take(handle).unwrap();
+        owned.push(4);
+        assert_eq!(owned.into_inner(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn consumed_handles_are_dropped_once() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        let handle = Handle::new(Counted(&DROPS)).into_raw();
+        drop(Handle::<Counted<'static>>::take(handle).unwrap());
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+
+        assert!(Handle::<Counted<'static>>::take(handle).is_err());
+        assert!(HandleRef::<Counted<'static>>::get(handle).is_err());
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+
+        let handle = Handle::new(Counted(&DROPS)).into_raw();
+        drop(unsafe { Handle::<Counted<'static>>::from_raw(handle) });
+        assert_eq!(DROPS.load(Ordering::SeqCst), 2);
+        assert!(Handle::<Counted<'static>>::take(handle).is_err());
+    }
+
+    #[test]
+    fn invalid_handles_are_rejected() {
+        assert!(matches!(Handle::<String>::take(0), Err(Error::NullPtr(_))));
+        assert!(matches!(HandleRef::<String>::get(0), Err(Error::NullPtr(_))));
+
+        let handle = Handle::new("text".to_string()).into_raw();
+        assert!(matches!(HandleRef::<Vec<u8>>::get(handle), Err(Error::WrongJValueType(_, _))));
+        assert!(Handle::<Vec<u8>>::take(handle).is_err());
+        assert_eq!(Handle::<String>::take(handle).unwrap().into_inner(), "text");
+    }
+
+    #[test]
+    fn handles_of_zero_sized_types_are_counted() {
+        let first = Handle::new(()).into_raw();
+        let second = Handle::new(()).into_raw();
+        assert_eq!(first, second);
+
+        assert!(Handle::<()>::take(first).is_ok());
+        assert!(HandleRef::<()>::get(second).is_ok());
+        assert!(Handle::<()>::take(second).is_ok());
+        assert!(Handle::<()>::take(second).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+//! Caching of string conversions.
+//!
+//! Exported methods that receive the same few `String` values over and over (e.g. enum-like tags) can skip
+//! repeated conversions by declaring an `Arc<str>` parameter with the `#[intern_cache]` attribute:
+//!
+//! ```ignore
+//! pub extern "jni" fn tagged(#[intern_cache(size = 64)] tag: Arc<str>) -> i32 { ... }
+//! ```
+//!
+//! Each annotated parameter gets its own [`InternCache`], holding at most `size` entries with least-recently-used eviction.
+//!
+
+use std::sync::{Arc, Mutex};
+
+use jni::errors::{Error, Result};
+use jni::objects::{GlobalRef, JObject, JString};
+use jni::JNIEnv;
+
+use crate::debug::Tracked;
+
+struct Entry {
+    hash: i32,
+    string: GlobalRef,
+    _tracked: Tracked,
+    value: Arc<str>,
+}
+
+/// A fixed-size, thread-safe cache of Java strings already converted to Rust.
+///
+/// Entries are looked up by `String.hashCode()`, which Java computes at most once per string instance.
+/// Candidates with a matching hash are then compared by identity first and by content second,
+/// so that distinct strings with colliding hashes never share a cached value.
+pub struct InternCache {
+    capacity: usize,
+    // Most recently used entries come first
+    entries: Mutex<Vec<Entry>>,
+}
+
+impl InternCache {
+    /// Creates an empty cache holding at most `capacity` strings.
+    pub const fn new(capacity: usize) -> Self {
+        InternCache {
+            capacity,
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the Rust counterpart of `s`, converting it only if no equal string is in the cache.
+    pub fn get(&self, env: &JNIEnv, s: JString) -> Result<Arc<str>> {
+        if env.is_same_object(s, JObject::null())? {
+            return Err(Error::NullPtr("intern cache input string"));
+        }
+
+        let hash = env.call_method(s, "hashCode", "()I", &[])?.i()?;
+
+        // `equals` calls into Java, which must not happen while other threads wait on the lock
+        let candidates: Vec<(GlobalRef, Arc<str>)> = self
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.hash == hash)
+            .map(|entry| (entry.string.clone(), entry.value.clone()))
+            .collect();
+
+        for (string, value) in candidates {
+            if Self::is_equal(env, string.as_obj(), s.into())? {
+                let mut entries = self.entries.lock().unwrap();
+                // The entry may have been evicted since the snapshot
+                if let Some(i) = entries.iter().position(|entry| Arc::ptr_eq(&entry.value, &value)) {
+                    let entry = entries.remove(i);
+                    entries.insert(0, entry);
+                }
+                return Ok(value);
+            }
+        }
+
+        let value: Arc<str> = String::from(env.get_string(s)?).into();
+        let entry = Entry {
+            hash,
+            string: env.new_global_ref(s)?,
+            _tracked: Tracked::global("InternCache"),
+            value: value.clone(),
+        };
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(0, entry);
+        entries.truncate(self.capacity);
+
+        Ok(value)
+    }
+
+    fn is_equal(env: &JNIEnv, cached: JObject, s: JObject) -> Result<bool> {
+        if env.is_same_object(cached, s)? {
+            Ok(true)
+        } else {
+            env.call_method(cached, "equals", "(Ljava/lang/Object;)Z", &[s.into()])?
+                .z()
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Fieldless enums converted to Java enums.
+//!
+//! `#[derive(JavaEnum)]` on a fieldless enum with a `#[package()]` attribute converts each variant to the constant of the Java
+//! enum of the same name in that package:
+//!
+//! ```ignore
+//! #[derive(Clone, Copy, Debug, PartialEq, JavaEnum)]
+//! #[package(com.example)]
+//! pub enum Status {
+//!     Ok,
+//!     Degraded,
+//!     #[java_name = "OFFLINE"]
+//!     Down,
+//! }
+//! ```
+//!
+//! is converted to and from `com.example.Status`, whose constants are named after the variants in `SCREAMING_SNAKE_CASE`
+//! (`OK`, `DEGRADED`), unless they have a `#[java_name]` attribute. Variants are converted with `Status.valueOf(String)` and
+//! `name()`, so that constants can be reordered on either side. With `#[by_ordinal]` on the enum, they are converted by
+//! position instead (the first variant is `Status.values()[0]`), which saves the string conversions but requires both enums to
+//! declare their constants in the same order.
+//!
+//! Declared in a `#[bridge]` module, the enum can have exported methods like a bridged struct, which are called from Java on the
+//! constants. The Java stub of the enum (see [Generating Java stubs](crate#generating-java-stubs)) declares a constant for each
+//! variant, and the native methods.
+//!
+//! Constants unknown to the other side, e.g. added to the Java enum by a newer library version, throw a
+//! `java.lang.IllegalArgumentException` with safe conversions (even with a custom `exception_class`, as the exception is
+//! already pending), and panic with unchecked ones.
+//!
+
+use jni::errors::{Error, Result};
+use jni::objects::{JObject, JString};
+use jni::sys::jsize;
+use jni::JNIEnv;
+
+/// A fieldless enum converted to a Java enum, see the [module documentation](self). Implemented by `#[derive(JavaEnum)]`.
+pub trait JavaEnum: Sized {
+    /// Binary name of the Java enum, e.g. `com/example/Status`.
+    const CLASS: &'static str;
+    /// Name of the Java constant of each variant, in declaration order.
+    const CONSTANTS: &'static [&'static str];
+    /// Whether variants are converted by ordinal, rather than by name.
+    const BY_ORDINAL: bool;
+
+    /// Position of the variant in the declaration of the enum.
+    fn index(&self) -> usize;
+
+    /// The variant at position `index` in the declaration of the enum, if any.
+    fn from_index(index: usize) -> Option<Self>;
+
+    /// Name of the Java constant of the variant, e.g. `DEGRADED`.
+    fn java_name(&self) -> &'static str {
+        Self::CONSTANTS[self.index()]
+    }
+}
+
+/// The Java constant of `value`, or `Err` with a message if the Java enum has no such constant.
+fn to_java<'env, T: JavaEnum>(value: &T, env: JNIEnv<'env>) -> Result<std::result::Result<JObject<'env>, String>> {
+    let enum_sig = format!("L{};", T::CLASS);
+    if T::BY_ORDINAL {
+        let values = env.call_static_method(T::CLASS, "values", format!("()[{}", enum_sig), &[])?.l()?;
+        let index = value.index();
+        if index >= env.get_array_length(values.into_inner())? as usize {
+            return Ok(Err(format!("`{}` has no constant with ordinal {}", T::CLASS.replace('/', "."), index)));
+        }
+
+        let constant = env.get_object_array_element(values.into_inner(), index as jsize)?;
+        env.delete_local_ref(values)?;
+        Ok(Ok(constant))
+    } else {
+        // `valueOf` throws its own `IllegalArgumentException` for unknown names
+        let name = env.new_string(value.java_name())?;
+        let constant = env.call_static_method(T::CLASS, "valueOf", format!("(Ljava/lang/String;){}", enum_sig), &[name.into()]);
+        env.delete_local_ref(name.into())?;
+        Ok(Ok(constant?.l()?))
+    }
+}
+
+/// The variant of the Java constant `constant`, or `Err` with a message if the Rust enum has no such variant.
+fn from_java<T: JavaEnum>(constant: JObject, env: JNIEnv) -> Result<std::result::Result<T, String>> {
+    if constant.is_null() {
+        return Err(Error::NullPtr(T::CLASS));
+    }
+
+    if T::BY_ORDINAL {
+        let ordinal = env.call_method(constant, "ordinal", "()I", &[])?.i()?;
+        Ok(T::from_index(ordinal as usize).ok_or_else(|| format!("no variant has ordinal {} of `{}`", ordinal, T::CLASS.replace('/', "."))))
+    } else {
+        let java_name = JString::from(env.call_method(constant, "name", "()Ljava/lang/String;", &[])?.l()?);
+        let name: String = env.get_string(java_name)?.into();
+        env.delete_local_ref(java_name.into())?;
+        let variant = T::CONSTANTS.iter().position(|c| *c == name).and_then(T::from_index);
+        Ok(variant.ok_or_else(|| format!("no variant has the name of constant `{}` of `{}`", name, T::CLASS.replace('/', "."))))
+    }
+}
+
+/// Throws an `IllegalArgumentException` for a constant unknown to the other side.
+fn unknown_constant<T>(env: JNIEnv, message: String) -> Result<T> {
+    env.throw_new("java/lang/IllegalArgumentException", message)?;
+    Err(Error::JavaException)
+}
+
+/// The Java constant of `value`, or an `IllegalArgumentException` thrown if there's none. Used by the generated code.
+#[doc(hidden)]
+pub fn checked_to_java<'env, T: JavaEnum>(value: &T, env: JNIEnv<'env>) -> Result<JObject<'env>> {
+    to_java(value, env)?.or_else(|message| unknown_constant(env, message))
+}
+
+/// The Java constant of `value`, panicking if there's none. Used by the generated code.
+#[doc(hidden)]
+pub fn unchecked_to_java<'env, T: JavaEnum>(value: &T, env: JNIEnv<'env>) -> JObject<'env> {
+    to_java(value, env).unwrap().unwrap_or_else(|message| panic!("{}", message))
+}
+
+/// The variant of `constant`, or an `IllegalArgumentException` thrown if there's none. Used by the generated code.
+#[doc(hidden)]
+pub fn checked_from_java<T: JavaEnum>(constant: JObject, env: JNIEnv) -> Result<T> {
+    from_java(constant, env)?.or_else(|message| unknown_constant(env, message))
+}
+
+/// The variant of `constant`, panicking if there's none. Used by the generated code.
+#[doc(hidden)]
+pub fn unchecked_from_java<T: JavaEnum>(constant: JObject, env: JNIEnv) -> T {
+    from_java(constant, env).unwrap().unwrap_or_else(|message| panic!("{}", message))
+}
@@ -0,0 +1,151 @@
+//! Struct fields converted on first access.
+
+use std::cell::OnceCell;
+use std::convert::{TryFrom, TryInto};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use jni::errors::Error as JniError;
+use jni::errors::Result as JniResult;
+use jni::objects::{GlobalRef, JFieldID, JObject};
+use jni::signature::JavaType;
+use jni::sys::jfieldID;
+use jni::{JNIEnv, JavaVM};
+
+use crate::convert::{JValueWrapper, Signature, TryFromJavaValue};
+
+// Local references created while reading an owned field are released as soon as it's converted.
+const LOCAL_FRAME_CAPACITY: i32 = 16;
+
+/// A field of a Java object, read and converted to `T` the first time it's accessed, and cached afterwards.
+///
+/// Fields annotated with `#[lazy]` in a struct deriving [`TryFromJavaValue`] (or [`FromJavaValue`](crate::convert::FromJavaValue))
+/// are `Lazy` fields, read from the field with the same name on the Java instance. Converting the struct only looks up the
+/// field ID, so that expensive fields (e.g. large arrays) are only copied when they're used:
+///
+/// ```ignore
+/// #[derive(Signature, TryFromJavaValue)]
+/// #[package(com.example)]
+/// pub struct Document<'env> {
+///     #[instance]
+///     raw: AutoLocal<'env>,
+///     title: String,
+///     #[lazy]
+///     payload: Lazy<'env, JavaArray<i8>>,
+/// }
+///
+/// let payload: &JavaArray<i8> = document.payload.get(env)?;
+/// ```
+///
+/// Like [`Field`](crate::convert::Field), a `Lazy` borrows the local reference of the Java object it was created from, so it
+/// must not outlive the `#[instance]` field of its struct. Use [`into_owned`](Lazy::into_owned) to keep it past the native
+/// call, or to access it from another thread.
+pub struct Lazy<'env, T> {
+    obj: JObject<'env>,
+    field_id: JFieldID<'env>,
+    value: OnceCell<T>,
+}
+
+impl<'env, T> Lazy<'env, T>
+where
+    T: Signature,
+{
+    /// Looks up the field `field_name` of the class `classpath_path` on `obj`, without reading it.
+    pub fn new(env: JNIEnv<'env>, obj: JObject<'env>, classpath_path: &str, field_name: &str) -> JniResult<Self> {
+        let field_id = env.get_field_id(classpath_path, field_name, <T as Signature>::SIG_TYPE)?;
+
+        Ok(Lazy {
+            obj,
+            field_id,
+            value: OnceCell::new(),
+        })
+    }
+
+    /// Whether the field has already been read.
+    pub fn is_loaded(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /// Makes an [`OwnedLazy`] of the same field, holding a global reference to the object. The value is kept if already read.
+    pub fn into_owned(self, env: JNIEnv<'env>) -> JniResult<OwnedLazy<T>> {
+        Ok(OwnedLazy {
+            obj: env.new_global_ref(self.obj)?,
+            vm: env.get_java_vm()?,
+            field_id: self.field_id.into_inner(),
+            value: self.value.into_inner().map_or_else(OnceLock::new, OnceLock::from),
+        })
+    }
+}
+
+impl<'env, T> Lazy<'env, T>
+where
+    T: Signature + TryFromJavaValue<'env>,
+    <T as TryFromJavaValue<'env>>::Source: TryFrom<JValueWrapper<'env>>,
+    <<T as TryFromJavaValue<'env>>::Source as TryFrom<JValueWrapper<'env>>>::Error: Into<JniError>,
+{
+    /// Returns the converted value of the field, reading it on the first call only.
+    pub fn get(&self, env: JNIEnv<'env>) -> JniResult<&T> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let value = read(env, self.obj, self.field_id)?;
+        Ok(self.value.get_or_init(|| value))
+    }
+}
+
+/// A [`Lazy`] field holding a global reference to its object, which can be accessed from any thread.
+///
+/// The field is read from the thread accessing it first, which is attached to the JVM for the duration of the read if it
+/// isn't already. Threads accessing it concurrently before it's cached may each read it, and all get the first value.
+pub struct OwnedLazy<T> {
+    obj: GlobalRef,
+    vm: JavaVM,
+    field_id: jfieldID,
+    value: OnceLock<T>,
+}
+
+// Field IDs are valid on every thread as long as their class is loaded, which the global reference to the object guarantees
+unsafe impl<T: Send> Send for OwnedLazy<T> {}
+unsafe impl<T: Send + Sync> Sync for OwnedLazy<T> {}
+
+impl<T> OwnedLazy<T> {
+    /// Whether the field has already been read.
+    pub fn is_loaded(&self) -> bool {
+        self.value.get().is_some()
+    }
+}
+
+impl<T> OwnedLazy<T>
+where
+    T: Signature + for<'env> TryFromJavaValue<'env>,
+    for<'env> <T as TryFromJavaValue<'env>>::Source: TryFrom<JValueWrapper<'env>>,
+    for<'env> <<T as TryFromJavaValue<'env>>::Source as TryFrom<JValueWrapper<'env>>>::Error: Into<JniError>,
+{
+    /// Returns the converted value of the field, reading it on the first call only.
+    pub fn get(&self) -> JniResult<&T> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let env = self.vm.attach_current_thread()?;
+        let mut value = None;
+        env.with_local_frame(LOCAL_FRAME_CAPACITY, || {
+            value = Some(read(*env, self.obj.as_obj(), JFieldID::from(self.field_id))?);
+            Ok(JObject::null())
+        })?;
+
+        Ok(self.value.get_or_init(|| value.unwrap()))
+    }
+}
+
+/// Reads the field `field_id` of `obj`, converted to `T`.
+fn read<'env, T>(env: JNIEnv<'env>, obj: JObject<'env>, field_id: JFieldID<'env>) -> JniResult<T>
+where
+    T: Signature + TryFromJavaValue<'env>,
+    <T as TryFromJavaValue<'env>>::Source: TryFrom<JValueWrapper<'env>>,
+    <<T as TryFromJavaValue<'env>>::Source as TryFrom<JValueWrapper<'env>>>::Error: Into<JniError>,
+{
+    let value = env.get_field_unchecked(obj, field_id, JavaType::from_str(<T as Signature>::SIG_TYPE)?)?;
+    TryInto::try_into(JValueWrapper::from(value)).map_err(Into::into).and_then(|v| TryFromJavaValue::try_from(v, env))
+}
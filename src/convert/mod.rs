@@ -35,7 +35,16 @@
 //! annotate your function signature with a [`jni::errors::Result<T>`] return type.
 //!
 //! When used with `#[call_type(safe)]`, if an `Err` is returned a Java exception is thrown (the one specified in the `call_type` attribute,
-//! or `java.lang.RuntimeException` if omitted).
+//! or `java.lang.RuntimeException` if omitted). If the specified class can't be thrown at runtime (e.g. it was removed by a code shrinker),
+//! a `java.lang.RuntimeException` is thrown instead, with a message mentioning both the error and the missing class.
+//!
+//! Safe methods can also return a `Result<T, E>` with any error type implementing [`Display`](std::fmt::Display):
+//! the `Ok` value is converted as usual, and an `Err` throws an exception with the error's `Display` output as message
+//! (unless a `message` is specified in the `call_type` attribute). With `#[call_type(unchecked)]`, an `Err` panics instead.
+//!
+//! # Conversion table
+//! Every conversion provided by the library is listed, with its Java type and descriptor, in the [`CONVERSIONS`](table::CONVERSIONS)
+//! table.
 //!
 
 use jni::errors::Error;
@@ -57,24 +66,76 @@ thread_local! {
    };
 }
 
+pub use adapt::*;
+pub use array::*;
+pub use atomic::*;
+pub use big_integer::BigIntegerBytes;
+pub use borrowed::*;
+pub use boxed::*;
+pub use buffer::{DirectByteBuffer, DirectBytes};
+pub use cache::*;
+pub use critical::CriticalBytes;
+pub use encoded::*;
+pub use equality::*;
+pub use error_code::ErrorCode;
 pub use field::*;
-pub use robusta_codegen::Signature;
+pub use functional::*;
+pub use global::{Global, Weak};
+pub use handle::{Handle, HandleRef};
+pub use intern::*;
+pub use java_enum::JavaEnum;
+pub use lazy::*;
+pub use numeric::*;
+pub use passthrough::*;
+pub use robusta_codegen::{ErrorCode, JavaEnum, Signature};
 pub use safe::*;
 pub use unchecked::*;
 
+pub mod adapt;
+pub mod array;
+pub mod atomic;
+pub mod big_integer;
+pub mod borrowed;
+pub mod boxed;
+pub mod buffer;
+pub mod cache;
+pub mod critical;
+pub mod encoded;
+pub mod equality;
+pub mod error_code;
 pub mod field;
+pub mod functional;
+pub mod global;
+pub mod handle;
+pub mod intern;
+pub mod java_enum;
+pub mod lazy;
+pub mod numeric;
+pub mod passthrough;
 pub mod safe;
+pub mod table;
+pub mod time;
+pub mod tuple;
 pub mod unchecked;
 
 /// A trait for types that are ffi-safe to use with JNI. It is implemented for primitives, [JObject](jni::objects::JObject) and [jobject](jni::sys::jobject).
 /// Users that want automatic conversion should instead implement [FromJavaValue], [IntoJavaValue] and/or [TryFromJavaValue], [TryIntoJavaValue]
 pub trait JavaValue<'env> {
-    /// Convert instance to a [`JObject`].
+    /// Convert instance to a [`JObject`], boxing primitives in their `java.lang` wrapper class. Objects are returned as they are.
     fn autobox(self, env: JNIEnv<'env>) -> JObject<'env>;
 
-    /// Convert [`JObject`] to the implementing type.
+    /// Convert [`JObject`] to the implementing type, unboxing primitives. Objects are returned as they are.
     fn unbox(s: JObject<'env>, env: JNIEnv<'env>) -> Self;
 
+    /// Like [`unbox`](JavaValue::unbox), failing instead of panicking when a primitive is unboxed from `null` or from an
+    /// object of another class than its wrapper class. Used by the checked conversions of containers, e.g. `Vec<i32>`.
+    fn try_unbox(s: JObject<'env>, env: JNIEnv<'env>) -> jni::errors::Result<Self>
+    where
+        Self: Sized,
+    {
+        Ok(Self::unbox(s, env))
+    }
+
     fn from_ptr(env: JNIEnv<'env>, ptr: jni::sys::jobject) -> Self
     where
         Self: Sized,
@@ -92,6 +153,18 @@ pub trait JavaValue<'env> {
 /// While you can implement this trait manually, you should probably use the derive macro.
 ///
 /// The derive macro requires a `#[package()]` attribute on implementing structs (most likely you already have that).
+/// It also works on enums, and a `#[signature("Lcom/example/Foo;")]` attribute gives the descriptor of the class explicitly,
+/// e.g. for a wrapper type named differently from its Java class. The descriptor is checked at compile time:
+///
+/// ```ignore
+/// #[derive(Signature)]
+/// #[signature("Lcom/example/Image$Format;")]
+/// pub struct ImageFormat<'env> {
+///     raw: JObject<'env>,
+/// }
+/// ```
+///
+/// Tuple structs and unions can't derive it.
 ///
 pub trait Signature {
     /// [Java type signature](https://docs.oracle.com/en/java/javase/15/docs/specs/jni/types.html#type-signatures) for the implementing type.
@@ -117,6 +190,19 @@ macro_rules! jvalue_types {
                     .unwrap().[<$sig:lower>]()
                     .unwrap()))
             }
+
+            fn try_unbox(s: JObject<'env>, env: JNIEnv<'env>) -> jni::errors::Result<Self> {
+                let class = concat!("java/lang/", stringify!($boxed));
+                if s.is_null() {
+                    return Err(jni::errors::Error::NullPtr(class));
+                }
+                if !env.is_instance_of(s, class)? {
+                    return Err(jni::errors::Error::WrongJValueType(class, "object"));
+                }
+
+                paste!(Ok(Into::into(env.call_method_unchecked(s, (class, stringify!($unbox_method), concat!("()", stringify!($sig))), JavaType::from_str(stringify!($sig)).unwrap(), &[])?
+                    .[<$sig:lower>]()?)))
+            }
         }
     };
 
@@ -143,8 +229,9 @@ impl Signature for () {
 }
 
 impl<'env> JavaValue<'env> for () {
+    /// The only value of `java.lang.Void`, `null`.
     fn autobox(self, _env: JNIEnv<'env>) -> JObject<'env> {
-        panic!("called `JavaValue::autobox` on unit value")
+        JObject::null()
     }
 
     fn unbox(_s: JObject<'env>, _env: JNIEnv<'env>) -> Self {}
@@ -188,28 +275,39 @@ impl<'env> JavaValue<'env> for JString<'env> {
     }
 }
 
-impl<'env, T: JavaValue<'env> + Signature> JavaValue<'env> for Option<T>
-where
-    JOption<T>: for<'borrow> FromJavaValue<'env, Source = JObject<'env>>,
-{
-    fn autobox(self, env: JNIEnv<'env>) -> JObject<'env> {
-        IntoJavaValue::into(<JOption<T> as From<Self>>::from(self), env)
-    }
-
-    /// Convert [`JObject`] to the implementing type.
-    fn unbox(s: JObject<'env>, env: JNIEnv<'env>) -> Self {
-        let option = <JOption<T> as FromJavaValue>::from(s, env);
-        Into::into(option)
-    }
-}
-impl<T: Signature> Signature for jni::errors::Result<T> {
+impl<T: Signature, E> Signature for std::result::Result<T, E> {
     const SIG_TYPE: &'static str = <T as Signature>::SIG_TYPE;
 }
 
 impl<T: Signature> Signature for JOption<T> {
-    const SIG_TYPE: &'static str = <T as Signature>::SIG_TYPE;
+    const SIG_TYPE: &'static str = boxed_signature(<T as Signature>::SIG_TYPE);
+}
+
+/// Signature of a value that can be `null`: the wrapper class for primitive signatures, and `signature` itself otherwise.
+pub(crate) const fn boxed_signature(signature: &'static str) -> &'static str {
+    match signature.as_bytes() {
+        b"Z" => "Ljava/lang/Boolean;",
+        b"B" => "Ljava/lang/Byte;",
+        b"C" => "Ljava/lang/Character;",
+        b"S" => "Ljava/lang/Short;",
+        b"I" => "Ljava/lang/Integer;",
+        b"J" => "Ljava/lang/Long;",
+        b"F" => "Ljava/lang/Float;",
+        b"D" => "Ljava/lang/Double;",
+        _ => signature,
+    }
 }
 
+/// A [`JValue`] returned by JNI calls, such as `JNIEnv::call_method`, unpacked to Rust values with [`TryFrom`]:
+///
+/// ```ignore
+/// let count: jint = JValueWrapper::from(env.call_method(list, "size", "()I", &[])?).try_into()?;
+/// let name: String = JValueWrapper::from(env.call_method(user, "getName", "()Ljava/lang/String;", &[])?).try_into()?;
+/// ```
+///
+/// Primitives and objects are unpacked from the variant of their type, failing with [`WrongJValueType`] for other variants.
+/// Objects are also converted to [`String`]s and [`Vec`]s with their [`TryFromJavaValue`] implementation, using the environment of the
+/// current thread.
 pub struct JValueWrapper<'a>(pub JValue<'a>);
 
 impl<'a> From<JValue<'a>> for JValueWrapper<'a> {
@@ -224,124 +322,104 @@ impl<'a> From<JValueWrapper<'a>> for JValue<'a> {
     }
 }
 
-impl<'a> TryFrom<JValueWrapper<'a>> for jboolean {
-    type Error = jni::errors::Error;
-
-    fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
-        match value.0 {
-            JValue::Bool(b) => Ok(b),
-            _ => Err(Error::WrongJValueType("bool", value.0.type_name()).into()),
-        }
-    }
-}
-
-impl<'a> TryFrom<JValueWrapper<'a>> for jbyte {
-    type Error = jni::errors::Error;
-
-    fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
-        match value.0 {
-            JValue::Byte(b) => Ok(b),
-            _ => Err(Error::WrongJValueType("byte", value.0.type_name()).into()),
-        }
-    }
-}
-
-impl<'a> TryFrom<JValueWrapper<'a>> for jchar {
-    type Error = jni::errors::Error;
-
-    fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
-        match value.0 {
-            JValue::Char(c) => Ok(c),
-            _ => Err(Error::WrongJValueType("char", value.0.type_name()).into()),
-        }
-    }
-}
-
-impl<'a> TryFrom<JValueWrapper<'a>> for jdouble {
-    type Error = jni::errors::Error;
-
-    fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
-        match value.0 {
-            JValue::Double(d) => Ok(d),
-            _ => Err(Error::WrongJValueType("double", value.0.type_name()).into()),
+/// Error unpacking a [`JValueWrapper`] holding another variant than the one of the type it's unpacked to.
+///
+/// Types are named like `JValue::type_name` does, e.g. `int` or `object`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WrongJValueType {
+    pub expected: &'static str,
+    pub actual: &'static str,
+}
+
+impl WrongJValueType {
+    fn new(expected: &'static str, value: &JValue) -> Self {
+        WrongJValueType {
+            expected,
+            actual: value.type_name(),
         }
     }
 }
 
-impl<'a> TryFrom<JValueWrapper<'a>> for jfloat {
-    type Error = jni::errors::Error;
-
-    fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
-        match value.0 {
-            JValue::Float(f) => Ok(f),
-            _ => Err(Error::WrongJValueType("float", value.0.type_name()).into()),
-        }
+// The same message as `jni::errors::Error::WrongJValueType`, which it's converted to
+impl std::fmt::Display for WrongJValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid JValue type cast: {}. Actual type: {}", self.expected, self.actual)
     }
 }
 
-impl<'a> TryFrom<JValueWrapper<'a>> for jint {
-    type Error = jni::errors::Error;
+impl std::error::Error for WrongJValueType {}
 
-    fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
-        match value.0 {
-            JValue::Int(i) => Ok(i),
-            _ => Err(Error::WrongJValueType("int", value.0.type_name()).into()),
-        }
+impl From<WrongJValueType> for Error {
+    fn from(e: WrongJValueType) -> Self {
+        Error::WrongJValueType(e.expected, e.actual)
     }
 }
 
-impl<'a> TryFrom<JValueWrapper<'a>> for jshort {
-    type Error = jni::errors::Error;
+macro_rules! jvalue_variants {
+    ($($type:ty: $variant:ident ($value:ident) => $result:expr, $name:literal);+ $(;)?) => {
+        $(
+            impl<'a> TryFrom<JValueWrapper<'a>> for $type {
+                type Error = WrongJValueType;
 
-    fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
-        match value.0 {
-            JValue::Short(s) => Ok(s),
-            _ => Err(Error::WrongJValueType("short", value.0.type_name()).into()),
-        }
-    }
+                fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
+                    match value.0 {
+                        JValue::$variant($value) => Ok($result),
+                        value => Err(WrongJValueType::new($name, &value)),
+                    }
+                }
+            }
+        )+
+    };
 }
 
-impl<'a> TryFrom<JValueWrapper<'a>> for jlong {
-    type Error = jni::errors::Error;
-
-    fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
-        match value.0 {
-            JValue::Long(l) => Ok(l),
-            _ => Err(Error::WrongJValueType("long", value.0.type_name()).into()),
-        }
-    }
+jvalue_variants! {
+    jboolean: Bool(b) => b, "bool";
+    bool: Bool(b) => b != 0, "bool";
+    jbyte: Byte(b) => b, "byte";
+    jchar: Char(c) => c, "char";
+    jdouble: Double(d) => d, "double";
+    jfloat: Float(f) => f, "float";
+    jint: Int(i) => i, "int";
+    jshort: Short(s) => s, "short";
+    jlong: Long(l) => l, "long";
+    JObject<'a>: Object(o) => o, "object";
+    JString<'a>: Object(o) => <JString as From<_>>::from(o), "string";
 }
 
 impl<'a> TryFrom<JValueWrapper<'a>> for () {
-    type Error = jni::errors::Error;
+    type Error = WrongJValueType;
 
     fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
         match value.0 {
             JValue::Void => Ok(()),
-            _ => Err(Error::WrongJValueType("void", value.0.type_name()).into()),
+            value => Err(WrongJValueType::new("void", &value)),
         }
     }
 }
 
-impl<'a> TryFrom<JValueWrapper<'a>> for JObject<'a> {
-    type Error = jni::errors::Error;
+/// The environment of the current thread, for conversions of objects unpacked from a [`JValueWrapper`].
+fn current_env<'a>() -> JNIEnv<'a> {
+    unsafe { JNIEnv::from_raw(ENV.with(|env| env.get_native_interface())).unwrap() }
+}
+
+impl<'a> TryFrom<JValueWrapper<'a>> for String {
+    type Error = Error;
 
     fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
-        match value.0 {
-            JValue::Object(o) => Ok(o),
-            _ => Err(Error::WrongJValueType("object", value.0.type_name()).into()),
-        }
+        let s = <JString as TryFrom<_>>::try_from(value)?;
+        TryFromJavaValue::try_from(s, current_env())
     }
 }
 
-impl<'a> TryFrom<JValueWrapper<'a>> for JString<'a> {
-    type Error = jni::errors::Error;
+impl<'a, T> TryFrom<JValueWrapper<'a>> for Vec<T>
+where
+    Vec<T>: TryFromJavaValue<'a, Source = JObject<'a>>,
+{
+    type Error = Error;
 
     fn try_from(value: JValueWrapper<'a>) -> Result<Self, Self::Error> {
-        match value.0 {
-            JValue::Object(o) => Ok(From::from(o)),
-            value => Err(Error::WrongJValueType("string", value.type_name()).into()),
-        }
+        let s = <JObject as TryFrom<_>>::try_from(value)?;
+        TryFromJavaValue::try_from(s, current_env())
     }
 }
 
@@ -376,12 +454,45 @@ where
 
     fn try_from(wrapper: JValueWrapper<'env>) -> Result<Self, Self::Error> {
         match wrapper.0 {
-            JValue::Object(value) => Ok(JOption::Some(T::try_from(value, unsafe {
-                JNIEnv::from_raw(ENV.with(|env| env.get_native_interface())).unwrap()
-            })?)),
+            JValue::Object(value) if value.is_null() => Ok(JOption::None),
+            JValue::Object(value) => Ok(JOption::Some(T::try_from(value, current_env())?)),
 
             JValue::Void => Ok(JOption::None),
-            _ => unreachable!(),
+            value => Err(WrongJValueType::new("object", &value).into()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use super::*;
+
+    #[test]
+    fn primitives_are_unpacked_from_their_variant() {
+        assert_eq!(TryInto::<jint>::try_into(JValueWrapper(JValue::Int(42))), Ok(42));
+        assert_eq!(TryInto::<jlong>::try_into(JValueWrapper(JValue::Long(-1))), Ok(-1));
+        assert_eq!(TryInto::<jchar>::try_into(JValueWrapper(JValue::Char(0x41))), Ok(0x41));
+        assert_eq!(TryInto::<bool>::try_into(JValueWrapper(JValue::Bool(1))), Ok(true));
+        assert_eq!(TryInto::<jdouble>::try_into(JValueWrapper(JValue::Double(0.5))), Ok(0.5));
+        assert_eq!(TryInto::<()>::try_into(JValueWrapper(JValue::Void)), Ok(()));
+        assert!(TryInto::<JObject>::try_into(JValueWrapper(JValue::Object(JObject::null()))).unwrap().is_null());
+    }
+
+    #[test]
+    fn other_variants_name_the_expected_and_actual_types() {
+        assert_eq!(
+            TryInto::<jint>::try_into(JValueWrapper(JValue::Long(42))),
+            Err(WrongJValueType { expected: "int", actual: "long" })
+        );
+        assert_eq!(
+            TryInto::<JObject>::try_into(JValueWrapper(JValue::Void)).unwrap_err().to_string(),
+            "Invalid JValue type cast: object. Actual type: void"
+        );
+
+        let error = TryInto::<String>::try_into(JValueWrapper(JValue::Int(42))).unwrap_err();
+        assert!(matches!(error, Error::WrongJValueType("string", "int")));
+        assert_eq!(error.to_string(), "Invalid JValue type cast: string. Actual type: int");
+    }
+}
@@ -0,0 +1,449 @@
+//! Truncating, saturating and reinterpreting conversions of integers.
+//!
+//! By default, unsigned integers are widened to `long` through [`Widen`], and values that don't fit in the destination type make
+//! the conversion fail.
+//! When a value must travel as a narrower Java primitive instead, a parameter of an exported method can be annotated with one of
+//! `#[truncate]`, `#[saturate]` and `#[reinterpret]`, which convert it through the wrappers of this module.
+//! The same attributes on the method itself apply to its return value (or to the `Ok` value of a `Result`):
+//!
+//! ```ignore
+//! #[saturate]
+//! pub extern "jni" fn elapsed_millis(#[reinterpret] flags: u32) -> u64 {
+//!     // ...
+//! }
+//! ```
+//!
+//! is called from Java as `int elapsedMillis(int flags)`.
+//!
+//! | **Attribute**    | **Rust types**       | **Java type**          | **Rust to Java**                       | **Java to Rust**                                                   |
+//! |------------------|----------------------|------------------------|----------------------------------------|--------------------------------------------------------------------|
+//! | `#[truncate]`    | i64, u32, u64, usize | int                    | lowest 32 bits, as a signed `int`      | sign-extended into `i64`, zero-extended into unsigned types        |
+//! | `#[saturate]`    | i64, u32, u64, usize | int                    | clamped to `Integer.MIN_VALUE..=Integer.MAX_VALUE` | clamped to the range of the Rust type (negative values become 0) |
+//! | `#[reinterpret]` | u8, u16, u32, u64    | byte, short, int, long | same bits, as a signed value           | same bits, as an unsigned value                                    |
+//!
+//! Reinterpreting conversions are lossless, so a `u32` with its highest bit set arrives in Java as a negative `int` and
+//! goes back to Rust unchanged (Java code can read it with `Integer.toUnsignedLong`). Truncating and saturating conversions
+//! never fail, even with `#[call_type(safe)]`.
+//!
+//! # Debug hooks
+//!
+//! In debug builds, truncating and saturating conversions that change the numeric value of an integer call the hook set with
+//! [`set_lossy_conversion_hook`], if any. Tests can use it to assert that values never actually get truncated:
+//!
+//! ```
+//! use robusta_jni::convert::numeric::set_lossy_conversion_hook;
+//!
+//! set_lossy_conversion_hook(Some(|c| panic!("{}", c)));
+//! ```
+//!
+//! Release builds skip the check entirely.
+//!
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::{PoisonError, RwLock};
+
+use jni::errors::{Error, Result};
+use jni::sys::{jbyte, jint, jlong, jshort};
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+/// An integer converted to and from a Java `int` by keeping its lowest 32 bits, selected with `#[truncate]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Truncate<T>(pub T);
+
+/// An integer converted to and from a Java `int` by clamping it to the destination range, selected with `#[saturate]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Saturate<T>(pub T);
+
+/// An unsigned integer converted to and from the signed Java primitive of the same width, bit for bit, selected with `#[reinterpret]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Reinterpret<T>(pub T);
+
+/// A `u32`, `u64` or `usize` converted to and from a Java `long`, which the generated code converts these types through
+/// when they don't have any of the attributes above.
+///
+/// Values out of the range of the destination type (negative `long`s, or `u64` and `usize` values above [`i64::MAX`]) throw a
+/// `java.lang.IllegalArgumentException` with the value in its message, or panic with `#[call_type(unchecked)]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Widen<T>(pub T);
+
+impl<T> From<T> for Truncate<T> {
+    fn from(value: T) -> Self {
+        Truncate(value)
+    }
+}
+
+impl<T> From<T> for Saturate<T> {
+    fn from(value: T) -> Self {
+        Saturate(value)
+    }
+}
+
+impl<T> From<T> for Reinterpret<T> {
+    fn from(value: T) -> Self {
+        Reinterpret(value)
+    }
+}
+
+impl<T> From<T> for Widen<T> {
+    fn from(value: T) -> Self {
+        Widen(value)
+    }
+}
+
+/// A truncating or saturating conversion that changed the numeric value of an integer, reported to the hook set with [`set_lossy_conversion_hook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LossyConversion {
+    /// Attribute selecting the conversion, `"truncate"` or `"saturate"`.
+    pub attribute: &'static str,
+    /// Type of the original value, e.g. `"u64"` or `"int"`.
+    pub from_type: &'static str,
+    /// Type of the converted value.
+    pub to_type: &'static str,
+    pub value: i128,
+    pub converted: i128,
+}
+
+impl fmt::Display for LossyConversion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`#[{}]` converted {} value {} to {} {}",
+            self.attribute, self.from_type, self.value, self.to_type, self.converted
+        )
+    }
+}
+
+/// Hook called in debug builds for each [`LossyConversion`].
+pub type LossyConversionHook = fn(&LossyConversion);
+
+static LOSSY_CONVERSION_HOOK: RwLock<Option<LossyConversionHook>> = RwLock::new(None);
+
+/// Sets the hook called in debug builds when a truncating or saturating conversion changes a value, replacing the previous one.
+/// `None` removes it.
+pub fn set_lossy_conversion_hook(hook: Option<LossyConversionHook>) {
+    *LOSSY_CONVERSION_HOOK.write().unwrap_or_else(PoisonError::into_inner) = hook;
+}
+
+#[inline]
+fn check_lossless(attribute: &'static str, from_type: &'static str, to_type: &'static str, value: i128, converted: i128) {
+    if cfg!(debug_assertions) && value != converted {
+        let hook = *LOSSY_CONVERSION_HOOK.read().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(hook) = hook {
+            hook(&LossyConversion {
+                attribute,
+                from_type,
+                to_type,
+                value,
+                converted,
+            });
+        }
+    }
+}
+
+/// Implements the conversion traits of a wrapper in terms of its `to_java` and `from_java` methods, which never fail.
+macro_rules! wrapper_conversions {
+    ($wrapper:ident<$type:ty>: $java_type:ty) => {
+        impl Signature for $wrapper<$type> {
+            const SIG_TYPE: &'static str = <$java_type as Signature>::SIG_TYPE;
+        }
+
+        impl<'env> TryIntoJavaValue<'env> for $wrapper<$type> {
+            type Target = $java_type;
+
+            fn try_into(self, _env: JNIEnv<'env>) -> Result<Self::Target> {
+                Ok(self.to_java())
+            }
+        }
+
+        impl<'env> TryFromJavaValue<'env> for $wrapper<$type> {
+            type Source = $java_type;
+
+            fn try_from(s: Self::Source, _env: JNIEnv<'env>) -> Result<Self> {
+                Ok(Self::from_java(s))
+            }
+        }
+
+        impl<'env> IntoJavaValue<'env> for $wrapper<$type> {
+            type Target = $java_type;
+
+            fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+                self.to_java()
+            }
+        }
+
+        impl<'env> FromJavaValue<'env> for $wrapper<$type> {
+            type Source = $java_type;
+
+            fn from(s: Self::Source, _env: JNIEnv<'env>) -> Self {
+                Self::from_java(s)
+            }
+        }
+    };
+}
+
+/// `$from_java` extends a Java `int` into the Rust type: the `int` itself is named `$value`.
+macro_rules! lossy_types {
+    ($($type:ty |$value:ident| $from_java:expr),+ $(,)?) => {
+        $(
+            impl Truncate<$type> {
+                /// Lowest 32 bits of the value, as a signed `int`.
+                pub fn to_java(self) -> jint {
+                    let converted = self.0 as jint;
+                    check_lossless("truncate", stringify!($type), "int", self.0 as i128, converted as i128);
+                    converted
+                }
+
+                /// The `int`, sign-extended into signed types and zero-extended into unsigned ones.
+                pub fn from_java($value: jint) -> Self {
+                    let converted: $type = $from_java;
+                    check_lossless("truncate", "int", stringify!($type), $value as i128, converted as i128);
+                    Truncate(converted)
+                }
+            }
+
+            impl Saturate<$type> {
+                /// The value, clamped to the range of `int`.
+                pub fn to_java(self) -> jint {
+                    let converted = (self.0 as i128).clamp(jint::MIN as i128, jint::MAX as i128) as jint;
+                    check_lossless("saturate", stringify!($type), "int", self.0 as i128, converted as i128);
+                    converted
+                }
+
+                /// The `int`, clamped to the range of the Rust type.
+                pub fn from_java(value: jint) -> Self {
+                    let converted = (value as i128).clamp(<$type>::MIN as i128, <$type>::MAX as i128) as $type;
+                    check_lossless("saturate", "int", stringify!($type), value as i128, converted as i128);
+                    Saturate(converted)
+                }
+            }
+
+            wrapper_conversions!(Truncate<$type>: jint);
+            wrapper_conversions!(Saturate<$type>: jint);
+        )+
+    };
+}
+
+lossy_types! {
+    i64 |value| value as i64,
+    u32 |value| value as u32,
+    u64 |value| value as u32 as u64,
+    usize |value| value as u32 as usize,
+}
+
+macro_rules! reinterpret_types {
+    ($($type:ty: $java_type:ty),+ $(,)?) => {
+        $(
+            impl Reinterpret<$type> {
+                /// The bits of the value, as a signed value.
+                pub fn to_java(self) -> $java_type {
+                    self.0 as $java_type
+                }
+
+                /// The bits of the Java value, as an unsigned value.
+                pub fn from_java(value: $java_type) -> Self {
+                    Reinterpret(value as $type)
+                }
+            }
+
+            wrapper_conversions!(Reinterpret<$type>: $java_type);
+        )+
+    };
+}
+
+reinterpret_types! {
+    u8: jbyte,
+    u16: jshort,
+    u32: jint,
+    u64: jlong,
+}
+
+/// The message of the exception or panic for `value`, out of the range of `to_type`.
+fn out_of_range_message(value: impl fmt::Display, to_type: &str) -> String {
+    format!("{} is out of the range of {}", value, to_type)
+}
+
+/// Throws a `java.lang.IllegalArgumentException` for `value`, out of the range of `to_type`.
+fn out_of_range<T>(env: JNIEnv, value: impl fmt::Display, to_type: &str) -> Result<T> {
+    env.throw_new("java/lang/IllegalArgumentException", out_of_range_message(value, to_type))?;
+    Err(Error::JavaException)
+}
+
+macro_rules! widened_types {
+    ($($type:ty),+ $(,)?) => {
+        $(
+            impl Signature for Widen<$type> {
+                const SIG_TYPE: &'static str = <jlong as Signature>::SIG_TYPE;
+            }
+
+            impl<'env> TryIntoJavaValue<'env> for Widen<$type> {
+                type Target = jlong;
+
+                fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+                    <jlong as TryFrom<_>>::try_from(self.0).or_else(|_| out_of_range(env, self.0, "long"))
+                }
+            }
+
+            impl<'env> TryFromJavaValue<'env> for Widen<$type> {
+                type Source = jlong;
+
+                fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+                    <$type as TryFrom<_>>::try_from(s).map(Widen).or_else(|_| out_of_range(env, s, stringify!($type)))
+                }
+            }
+
+            impl<'env> IntoJavaValue<'env> for Widen<$type> {
+                type Target = jlong;
+
+                fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+                    <jlong as TryFrom<_>>::try_from(self.0).unwrap_or_else(|_| panic!("{}", out_of_range_message(self.0, "long")))
+                }
+            }
+
+            impl<'env> FromJavaValue<'env> for Widen<$type> {
+                type Source = jlong;
+
+                fn from(s: Self::Source, _env: JNIEnv<'env>) -> Self {
+                    <$type as TryFrom<_>>::try_from(s).map(Widen).unwrap_or_else(|_| panic!("{}", out_of_range_message(s, stringify!($type))))
+                }
+            }
+        )+
+    };
+}
+
+widened_types!(u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    thread_local! {
+        static REPORTED: RefCell<Vec<LossyConversion>> = RefCell::new(Vec::new());
+    }
+
+    /// Runs `f`, returning the lossy conversions it reported on this thread.
+    fn reported(f: impl FnOnce()) -> Vec<LossyConversion> {
+        set_lossy_conversion_hook(Some(|c| REPORTED.with(|r| r.borrow_mut().push(*c))));
+        f();
+        REPORTED.with(|r| r.borrow_mut().drain(..).collect())
+    }
+
+    #[test]
+    fn truncate_keeps_lowest_bits() {
+        assert_eq!(Truncate(0i64).to_java(), 0);
+        assert_eq!(Truncate(i32::MAX as i64).to_java(), i32::MAX);
+        assert_eq!(Truncate(i32::MIN as i64).to_java(), i32::MIN);
+        assert_eq!(Truncate(1i64 << 32).to_java(), 0);
+        assert_eq!(Truncate(-1i64).to_java(), -1);
+        assert_eq!(Truncate(i64::MAX).to_java(), -1);
+        assert_eq!(Truncate(i64::MIN).to_java(), 0);
+        assert_eq!(Truncate(u32::MAX).to_java(), -1);
+        assert_eq!(Truncate(u64::MAX).to_java(), -1);
+        assert_eq!(Truncate(0x1_8000_0000u64).to_java(), i32::MIN);
+        assert_eq!(Truncate(usize::MAX).to_java(), -1);
+    }
+
+    #[test]
+    fn truncate_extends_from_java() {
+        assert_eq!(Truncate::<i64>::from_java(-1), Truncate(-1));
+        assert_eq!(Truncate::<i64>::from_java(i32::MIN), Truncate(i32::MIN as i64));
+        assert_eq!(Truncate::<u32>::from_java(-1), Truncate(u32::MAX));
+        assert_eq!(Truncate::<u64>::from_java(-1), Truncate(u32::MAX as u64));
+        assert_eq!(Truncate::<u64>::from_java(i32::MAX), Truncate(i32::MAX as u64));
+        assert_eq!(Truncate::<usize>::from_java(i32::MIN), Truncate(0x8000_0000));
+    }
+
+    #[test]
+    fn saturate_clamps_to_int() {
+        assert_eq!(Saturate(0i64).to_java(), 0);
+        assert_eq!(Saturate(i32::MAX as i64).to_java(), i32::MAX);
+        assert_eq!(Saturate(i32::MAX as i64 + 1).to_java(), i32::MAX);
+        assert_eq!(Saturate(i32::MIN as i64).to_java(), i32::MIN);
+        assert_eq!(Saturate(i32::MIN as i64 - 1).to_java(), i32::MIN);
+        assert_eq!(Saturate(i64::MAX).to_java(), i32::MAX);
+        assert_eq!(Saturate(i64::MIN).to_java(), i32::MIN);
+        assert_eq!(Saturate(u32::MAX).to_java(), i32::MAX);
+        assert_eq!(Saturate(u64::MAX).to_java(), i32::MAX);
+        assert_eq!(Saturate(usize::MAX).to_java(), i32::MAX);
+    }
+
+    #[test]
+    fn saturate_clamps_from_java() {
+        assert_eq!(Saturate::<i64>::from_java(i32::MIN), Saturate(i32::MIN as i64));
+        assert_eq!(Saturate::<u32>::from_java(-1), Saturate(0));
+        assert_eq!(Saturate::<u64>::from_java(i32::MIN), Saturate(0));
+        assert_eq!(Saturate::<u64>::from_java(i32::MAX), Saturate(i32::MAX as u64));
+        assert_eq!(Saturate::<usize>::from_java(0), Saturate(0));
+    }
+
+    #[test]
+    fn reinterpret_keeps_bits() {
+        assert_eq!(Reinterpret(u8::MAX).to_java(), -1);
+        assert_eq!(Reinterpret(0x80u8).to_java(), i8::MIN);
+        assert_eq!(Reinterpret(u16::MAX).to_java(), -1);
+        assert_eq!(Reinterpret(u32::MAX).to_java(), -1);
+        assert_eq!(Reinterpret(0x8000_0000u32).to_java(), i32::MIN);
+        assert_eq!(Reinterpret(i32::MAX as u32).to_java(), i32::MAX);
+        assert_eq!(Reinterpret(u64::MAX).to_java(), -1);
+
+        assert_eq!(Reinterpret::<u8>::from_java(-1), Reinterpret(u8::MAX));
+        assert_eq!(Reinterpret::<u16>::from_java(i16::MIN), Reinterpret(0x8000));
+        assert_eq!(Reinterpret::<u32>::from_java(i32::MIN), Reinterpret(0x8000_0000));
+        assert_eq!(Reinterpret::<u64>::from_java(i64::MIN), Reinterpret(1 << 63));
+
+        for value in [0, 1, i32::MAX as u32, 0x8000_0000, u32::MAX] {
+            assert_eq!(Reinterpret::<u32>::from_java(Reinterpret(value).to_java()), Reinterpret(value));
+        }
+    }
+
+    #[test]
+    fn lossy_conversions_are_reported() {
+        let reports = reported(|| {
+            Saturate(u64::MAX).to_java();
+            Truncate::<u32>::from_java(-2);
+        });
+
+        if cfg!(debug_assertions) {
+            assert_eq!(
+                reports,
+                [
+                    LossyConversion {
+                        attribute: "saturate",
+                        from_type: "u64",
+                        to_type: "int",
+                        value: u64::MAX as i128,
+                        converted: i32::MAX as i128,
+                    },
+                    LossyConversion {
+                        attribute: "truncate",
+                        from_type: "int",
+                        to_type: "u32",
+                        value: -2,
+                        converted: (u32::MAX - 1) as i128,
+                    },
+                ]
+            );
+            assert_eq!(reports[0].to_string(), "`#[saturate]` converted u64 value 18446744073709551615 to int 2147483647");
+        } else {
+            assert!(reports.is_empty());
+        }
+    }
+
+    #[test]
+    fn lossless_conversions_are_not_reported() {
+        let reports = reported(|| {
+            Truncate(i32::MIN as i64).to_java();
+            Truncate::<i64>::from_java(-1);
+            Saturate(i32::MAX as u32).to_java();
+            Saturate::<u64>::from_java(0);
+            Reinterpret(u32::MAX).to_java();
+        });
+
+        assert!(reports.is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! Java objects carried through Rust code without being interpreted.
+//!
+//! [`Passthrough`] is the type to use for "any Java object": it's converted to and from `java.lang.Object` as is, without any
+//! check or conversion, so an object received from one call can be handed to another one later and Java gets back the very same
+//! object. Unlike a raw [`JObject`], it keeps the environment it was received with, to print the class of the object when debugging.
+//!
+//! ```ignore
+//! pub extern "jni" fn select<'env>(first: Passthrough<'env>, second: Passthrough<'env>, pick_first: bool) -> Passthrough<'env> {
+//!     if pick_first { first } else { second }
+//! }
+//! ```
+//!
+//! is called from Java as `Object select(Object first, Object second, boolean pick_first)`.
+//!
+//! A [`Passthrough`] is only valid during the native call it was received in, like its local reference. [`PassthroughOwned`]
+//! holds a global reference instead, so it can be kept after the call returns and sent to other threads: [`Passthrough::upgrade`]
+//! and [`PassthroughOwned::downgrade`] convert between the two. Both can be `null`, and elements of `Vec`s, `Option`s and
+//! [`JavaArray`](crate::convert::JavaArray)s (as `Object[]`).
+//!
+
+use std::fmt;
+use std::sync::Arc;
+
+use jni::errors::{Error, Result};
+use jni::objects::{GlobalRef, JObject, JString};
+use jni::{JNIEnv, JavaVM};
+
+use crate::convert::{ArrayElement, CachedMethod, FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+use crate::debug::{self, Tracked};
+
+/// Name of the class of `obj`, e.g. `java.util.UUID`.
+fn class_name(env: JNIEnv, obj: JObject) -> Result<String> {
+    static GET_NAME: CachedMethod = CachedMethod::new();
+
+    if obj.is_null() {
+        return Ok("null".to_string());
+    }
+
+    let class = <JObject as From<_>>::from(env.get_object_class(obj)?);
+    let name = GET_NAME
+        .call(env, class, "java/lang/Class", "getName", || "()Ljava/lang/String;".to_string(), &[])?
+        .l()?;
+    let result = <String as From<_>>::from(env.get_string(<JString as From<_>>::from(name))?);
+
+    env.delete_local_ref(name)?;
+    env.delete_local_ref(class)?;
+    Ok(result)
+}
+
+/// Writes `Name(class)`, with the class name of `obj` if it can be found.
+fn debug_class(f: &mut fmt::Formatter<'_>, name: &str, env: Option<JNIEnv>, obj: JObject) -> fmt::Result {
+    let class = env.map(|env| {
+        class_name(env, obj).map_err(|e| {
+            // Debugging mustn't leave an exception pending
+            if let Error::JavaException = e {
+                let _ = env.exception_clear();
+            }
+        })
+    });
+
+    match class {
+        Some(Ok(class)) => f.debug_tuple(name).field(&format_args!("{}", class)).finish(),
+        _ => f.debug_tuple(name).field(&format_args!("<unknown class>")).finish(),
+    }
+}
+
+/// A Java object of any class, passed back to Java unchanged. Valid as long as its local reference.
+#[derive(Clone, Copy)]
+pub struct Passthrough<'env> {
+    env: JNIEnv<'env>,
+    obj: JObject<'env>,
+}
+
+impl<'env> Passthrough<'env> {
+    pub fn new(env: JNIEnv<'env>, obj: JObject<'env>) -> Self {
+        Passthrough { env, obj }
+    }
+
+    /// The wrapped object.
+    pub fn as_obj(&self) -> JObject<'env> {
+        self.obj
+    }
+
+    /// A global reference to the object, which can be kept after the native call returns.
+    pub fn upgrade(&self) -> Result<PassthroughOwned> {
+        PassthroughOwned::new(self.env, self.obj)
+    }
+}
+
+impl fmt::Debug for Passthrough<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_class(f, "Passthrough", Some(self.env), self.obj)
+    }
+}
+
+impl<'env> Signature for Passthrough<'env> {
+    const SIG_TYPE: &'static str = "Ljava/lang/Object;";
+}
+
+impl ArrayElement for Passthrough<'_> {
+    const ARRAY_SIG_TYPE: &'static str = "[Ljava/lang/Object;";
+}
+
+impl<'env> TryIntoJavaValue<'env> for Passthrough<'env> {
+    type Target = JObject<'env>;
+
+    fn try_into(self, _env: JNIEnv<'env>) -> Result<Self::Target> {
+        Ok(self.obj)
+    }
+}
+
+impl<'env> TryFromJavaValue<'env> for Passthrough<'env> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        Ok(Passthrough::new(env, s))
+    }
+}
+
+impl<'env> IntoJavaValue<'env> for Passthrough<'env> {
+    type Target = JObject<'env>;
+
+    fn into(self, _env: JNIEnv<'env>) -> Self::Target {
+        self.obj
+    }
+}
+
+impl<'env> FromJavaValue<'env> for Passthrough<'env> {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        Passthrough::new(env, s)
+    }
+}
+
+/// A Java object of any class, passed back to Java unchanged, held by a global reference.
+///
+/// It can be kept after the native call returns and sent to other threads.
+#[derive(Clone)]
+pub struct PassthroughOwned {
+    obj: GlobalRef,
+    _tracked: Tracked,
+    // Only used to find the class name for `Debug`, from threads attached to the JVM
+    vm: Arc<JavaVM>,
+}
+
+impl PassthroughOwned {
+    pub fn new(env: JNIEnv, obj: JObject) -> Result<Self> {
+        Ok(PassthroughOwned {
+            obj: env.new_global_ref(obj)?,
+            _tracked: Tracked::global("PassthroughOwned"),
+            vm: Arc::new(env.get_java_vm()?),
+        })
+    }
+
+    /// The wrapped object.
+    pub fn as_obj(&self) -> JObject<'_> {
+        self.obj.as_obj()
+    }
+
+    /// A local reference to the object, valid during the native call `env` belongs to.
+    pub fn downgrade<'env>(&self, env: JNIEnv<'env>) -> Result<Passthrough<'env>> {
+        let obj = env.new_local_ref::<JObject>(<JObject as From<_>>::from(self.obj.as_obj().into_inner()))?;
+        debug::local_created("PassthroughOwned");
+        Ok(Passthrough::new(env, obj))
+    }
+}
+
+impl fmt::Debug for PassthroughOwned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_class(f, "PassthroughOwned", self.vm.get_env().ok(), self.obj.as_obj())
+    }
+}
+
+impl Signature for PassthroughOwned {
+    const SIG_TYPE: &'static str = "Ljava/lang/Object;";
+}
+
+impl ArrayElement for PassthroughOwned {
+    const ARRAY_SIG_TYPE: &'static str = "[Ljava/lang/Object;";
+}
+
+impl<'env> TryIntoJavaValue<'env> for PassthroughOwned {
+    type Target = JObject<'env>;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        Ok(self.downgrade(env)?.as_obj())
+    }
+}
+
+impl<'env> TryFromJavaValue<'env> for PassthroughOwned {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        PassthroughOwned::new(env, s)
+    }
+}
+
+impl<'env> IntoJavaValue<'env> for PassthroughOwned {
+    type Target = JObject<'env>;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        TryIntoJavaValue::try_into(self, env).unwrap()
+    }
+}
+
+impl<'env> FromJavaValue<'env> for PassthroughOwned {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        PassthroughOwned::new(env, s).unwrap()
+    }
+}
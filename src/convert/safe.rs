@@ -13,12 +13,12 @@
 //! #[call_type(safe(exception_class = "java.io.IOException", message = "Error while calling JNI function!"))]
 //! ```
 //!
-//! Both of these parameters are optional. By default, the exception class is `java.lang.RuntimeException`.
+//! Both of these parameters are optional. By default, the exception class is `java.lang.RuntimeException` and the message is the `Display` output of the error.
 //!
 
 use jni::errors::{Error, Result};
 use jni::objects::{JList, JObject, JString, JValue};
-use jni::sys::{jboolean, jbooleanArray, jchar, jobject};
+use jni::sys::{jboolean, jbooleanArray, jchar, jobject, jobjectArray};
 use jni::JNIEnv;
 
 use crate::convert::unchecked::{FromJavaValue, IntoJavaValue};
@@ -26,7 +26,7 @@ use crate::convert::{JavaValue, Signature};
 
 pub use robusta_codegen::{TryFromJavaValue, TryIntoJavaValue};
 
-use super::JOption;
+use super::{boxed_signature, JOption};
 
 /// Conversion trait from Rust values to Java values, analogous to [TryInto](std::convert::TryInto). Used when converting types returned from JNI-available functions.
 ///
@@ -57,6 +57,7 @@ pub trait TryIntoJavaValue<'env>: Signature {
 /// When using the derive macro, the deriving struct **must** have a [`AutoLocal`] field annotated with both `'env` and `'borrow` lifetimes and a `#[instance]` attribute.
 /// This fields keeps a [local reference](https://docs.oracle.com/en/java/javase/15/docs/specs/jni/design.html#global-and-local-references) to the underlying Java object.
 /// All other fields are automatically initialized from fields on the Java instance with the same name.
+/// Fields annotated with `#[lazy]` are [`Lazy`](crate::convert::Lazy) fields instead, only read when first accessed.
 ///
 /// Example:
 ///
@@ -137,6 +138,67 @@ impl<'env> TryFromJavaValue<'env> for String {
     }
 }
 
+/// Creates a `String[]`, copying each string directly from the slice.
+fn new_string_array<S: AsRef<str>>(env: JNIEnv, strings: &[S]) -> Result<jobjectArray> {
+    let len = <i32 as std::convert::TryFrom<usize>>::try_from(strings.len()).map_err(|_| Error::WrongJValueType("usize", "jint"))?;
+    let array = env.new_object_array(len, "java/lang/String", JObject::null())?;
+
+    for (i, s) in (0..len).zip(strings) {
+        let string = env.new_string(s.as_ref())?;
+        env.set_object_array_element(array, i, string)?;
+        env.delete_local_ref(<JObject as From<_>>::from(string))?;
+    }
+
+    Ok(array)
+}
+
+impl<'env> TryIntoJavaValue<'env> for &[&str] {
+    type Target = jobjectArray;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        new_string_array(env, self)
+    }
+}
+
+impl<'env> TryIntoJavaValue<'env> for &[String] {
+    type Target = jobjectArray;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        new_string_array(env, self)
+    }
+}
+
+impl<'env> TryIntoJavaValue<'env> for Box<[String]> {
+    type Target = jobjectArray;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        new_string_array(env, &self)
+    }
+}
+
+/// `null` elements fail the conversion: use `JavaArray<Option<String>>` for arrays that can have some.
+impl<'env> TryFromJavaValue<'env> for Box<[String]> {
+    type Source = JObject<'env>;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        let array = s.into_inner();
+        let len = env.get_array_length(array)?;
+        let mut strings = Vec::with_capacity(len as usize);
+
+        for i in 0..len {
+            let element = env.get_object_array_element(array, i)?;
+            if element.is_null() {
+                return Err(Error::NullPtr("String[] element"));
+            }
+
+            strings.push(env.get_string(<JString as From<_>>::from(element))?.into());
+            env.delete_local_ref(element)?;
+        }
+
+        Ok(strings.into_boxed_slice())
+    }
+}
+
 impl<'env> TryIntoJavaValue<'env> for bool {
     type Target = jboolean;
 
@@ -204,6 +266,7 @@ impl<'env> TryFromJavaValue<'env> for Box<[bool]> {
     }
 }
 
+/// Elements converted to objects, like bridged structs, are added as they are, and primitives are boxed.
 impl<'env, T> TryIntoJavaValue<'env> for Vec<T>
 where
     T: TryIntoJavaValue<'env>,
@@ -218,21 +281,15 @@ where
         )?;
         let list = JList::from_env(&env, obj)?;
 
-        let _: Result<Vec<_>> = self
-            .into_iter()
-            .map::<Result<_>, _>(|el| {
-                Ok(JavaValue::autobox(
-                    TryIntoJavaValue::try_into(el, env)?,
-                    env,
-                ))
-            })
-            .map(|el| Ok(list.add(el?)))
-            .collect();
+        for el in self {
+            list.add(JavaValue::autobox(TryIntoJavaValue::try_into(el, env)?, env))?;
+        }
 
         Ok(list.into_inner())
     }
 }
 
+/// Elements converted from objects, like bridged structs, are taken as they are, and primitives are unboxed.
 impl<'env, T, U> TryFromJavaValue<'env> for Vec<T>
 where
     T: TryFromJavaValue<'env, Source = U>,
@@ -244,7 +301,7 @@ where
         let list = JList::from_env(&env, s)?;
 
         list.iter()?
-            .map(|el| T::try_from(U::unbox(el, env), env))
+            .map(|el| T::try_from(U::try_unbox(el, env)?, env))
             .collect()
     }
 }
@@ -264,12 +321,65 @@ where
     }
 }
 
-impl<'env, T: Signature + IntoJavaValue<'env>> TryIntoJavaValue<'env> for JOption<T> {
-    type Target = <JOption<T> as IntoJavaValue<'env>>::Target;
+/// `None` is converted to `null`, and primitives in `Some` to instances of their wrapper class.
+impl<'env, T> TryIntoJavaValue<'env> for Option<T>
+where
+    T: TryIntoJavaValue<'env>,
+{
+    type Target = JObject<'env>;
 
-    const SIG_TYPE: &'static str = <Self as Signature>::SIG_TYPE;
+    const SIG_TYPE: &'static str = boxed_signature(<T as TryIntoJavaValue<'env>>::SIG_TYPE);
 
     fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
-        Ok(IntoJavaValue::into(self, env))
+        match self {
+            Some(value) => Ok(TryIntoJavaValue::try_into(value, env)?.autobox(env)),
+            None => Ok(JObject::null()),
+        }
+    }
+}
+
+/// `null` is converted to `None`, and instances of wrapper classes to `Some` primitive.
+impl<'env, T, U> TryFromJavaValue<'env> for Option<T>
+where
+    T: TryFromJavaValue<'env, Source = U>,
+    U: JavaValue<'env>,
+{
+    type Source = JObject<'env>;
+
+    const SIG_TYPE: &'static str = boxed_signature(<T as TryFromJavaValue<'env>>::SIG_TYPE);
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        if s.is_null() {
+            Ok(None)
+        } else {
+            TryFromJavaValue::try_from(U::try_unbox(s, env)?, env).map(Some)
+        }
+    }
+}
+
+impl<'env, T> TryIntoJavaValue<'env> for JOption<T>
+where
+    T: TryIntoJavaValue<'env>,
+{
+    type Target = JObject<'env>;
+
+    const SIG_TYPE: &'static str = <Option<T> as TryIntoJavaValue<'env>>::SIG_TYPE;
+
+    fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+        TryIntoJavaValue::try_into(<JOption<T> as Into<Option<T>>>::into(self), env)
+    }
+}
+
+impl<'env, T, U> TryFromJavaValue<'env> for JOption<T>
+where
+    T: TryFromJavaValue<'env, Source = U>,
+    U: JavaValue<'env>,
+{
+    type Source = JObject<'env>;
+
+    const SIG_TYPE: &'static str = <Option<T> as TryFromJavaValue<'env>>::SIG_TYPE;
+
+    fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+        TryFromJavaValue::try_from(s, env).map(<JOption<T> as From<Option<T>>>::from)
     }
 }
@@ -0,0 +1,221 @@
+//! The conversion table: every conversion provided by the library, with its Java type and descriptor.
+//!
+//! [`CONVERSIONS`] is the single source of the table in these docs: its rows are generated from the same list the tests check
+//! against the [`Signature`](crate::convert::Signature) of each type, so that the documented descriptors are the ones used by `#[bridge]`.
+//!
+
+// Types of the conversions, whose signatures are only read by the tests
+#[cfg(test)]
+use jni::objects::{JObject, JString};
+
+#[cfg(test)]
+use crate::audio::Samples;
+#[cfg(test)]
+use crate::convert::{
+    BigIntegerBytes, Boxed, ByJavaEquality, ByJavaString, CriticalBytes, DirectByteBuffer, DirectBytes, EncodedString, Field, Global, Handle, HandleRef, JAtomicLong, JOption, JavaArray, JavaCallback,
+    Latin1, Passthrough, PassthroughOwned, Reinterpret, Saturate, Signature, Truncate, Weak,
+};
+
+/// A conversion provided by the library.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conversion {
+    /// The Rust type, e.g. `Vec<i64>`.
+    pub rust: &'static str,
+    /// The Java type it's converted to and from, e.g. `java.util.ArrayList<Long>`.
+    pub java: &'static str,
+    /// The type signature of the Rust type, e.g. `Ljava/util/ArrayList;`.
+    pub descriptor: &'static str,
+}
+
+/// Declares the conversion table, and its documentation. Each row gives the Rust type as written in the table, the type itself
+/// (whose `SIG_TYPE` the tests check the descriptor against), its Java type and its descriptor.
+macro_rules! conversions {
+    ($($rust:literal: $ty:ty => $java:literal, $descriptor:literal;)+) => {
+        /// Every conversion provided by the library, with the Java type it's converted to and from, and its descriptor
+        /// ([`Signature::SIG_TYPE`](crate::convert::Signature::SIG_TYPE)). Generic conversions are listed with examples of their type parameters.
+        ///
+        /// Tuples are `kotlin.Pair`s and `kotlin.Triple`s with the `kotlin-tuples` feature, and `chrono::DateTime<Utc>` (with
+        /// the `chrono` feature) is converted like `SystemTime`. Conversions through another type, like
+        /// [`Adapt<T, M>`](crate::convert::Adapt) converted as `T`, have the descriptor of that type.
+        ///
+        #[doc = concat!(
+            "| Rust | Java | Descriptor |\n",
+            "|------|------|------------|\n",
+            $("| `", $rust, "` | `", $java, "` | `", $descriptor, "` |\n",)+
+        )]
+        pub const CONVERSIONS: &[Conversion] = &[
+            $(Conversion { rust: $rust, java: $java, descriptor: $descriptor },)+
+        ];
+
+        /// `SIG_TYPE` of the type of each conversion, in the order of [`CONVERSIONS`].
+        #[cfg(test)]
+        const SIG_TYPES: &[&str] = &[$(<$ty as Signature>::SIG_TYPE,)+];
+    };
+}
+
+conversions! {
+    "bool": bool => "boolean", "Z";
+    "i8": i8 => "byte", "B";
+    "char": char => "char", "C";
+    "i16": i16 => "short", "S";
+    "i32": i32 => "int", "I";
+    "i64": i64 => "long", "J";
+    "f32": f32 => "float", "F";
+    "f64": f64 => "double", "D";
+    "u32": u32 => "long", "J";
+    "u64": u64 => "long", "J";
+    "usize": usize => "long", "J";
+    "()": () => "void", "V";
+    "Truncate<u32>": Truncate<u32> => "int", "I";
+    "Truncate<u64>": Truncate<u64> => "int", "I";
+    "Truncate<usize>": Truncate<usize> => "int", "I";
+    "Truncate<i64>": Truncate<i64> => "int", "I";
+    "Saturate<u32>": Saturate<u32> => "int", "I";
+    "Saturate<u64>": Saturate<u64> => "int", "I";
+    "Saturate<usize>": Saturate<usize> => "int", "I";
+    "Saturate<i64>": Saturate<i64> => "int", "I";
+    "Reinterpret<u8>": Reinterpret<u8> => "byte", "B";
+    "Reinterpret<u16>": Reinterpret<u16> => "short", "S";
+    "Reinterpret<u32>": Reinterpret<u32> => "int", "I";
+    "Reinterpret<u64>": Reinterpret<u64> => "long", "J";
+    "String": String => "java.lang.String", "Ljava/lang/String;";
+    "&str": &str => "java.lang.String", "Ljava/lang/String;";
+    "Cow<str>": std::borrow::Cow<'static, str> => "java.lang.String", "Ljava/lang/String;";
+    "&[&str]": &[&str] => "java.lang.String[]", "[Ljava/lang/String;";
+    "&[String]": &[String] => "java.lang.String[]", "[Ljava/lang/String;";
+    "Box<[String]>": Box<[String]> => "java.lang.String[]", "[Ljava/lang/String;";
+    "EncodedString<Latin1>": EncodedString<Latin1> => "byte[]", "[B";
+    "CriticalBytes<'env>": CriticalBytes<'static> => "byte[]", "[B";
+    "Box<[bool]>": Box<[bool]> => "boolean[]", "[Z";
+    "Boxed<bool>": Boxed<bool> => "java.lang.Boolean", "Ljava/lang/Boolean;";
+    "Boxed<i8>": Boxed<i8> => "java.lang.Byte", "Ljava/lang/Byte;";
+    "Boxed<char>": Boxed<char> => "java.lang.Character", "Ljava/lang/Character;";
+    "Boxed<i16>": Boxed<i16> => "java.lang.Short", "Ljava/lang/Short;";
+    "Boxed<i32>": Boxed<i32> => "java.lang.Integer", "Ljava/lang/Integer;";
+    "Boxed<i64>": Boxed<i64> => "java.lang.Long", "Ljava/lang/Long;";
+    "Boxed<f32>": Boxed<f32> => "java.lang.Float", "Ljava/lang/Float;";
+    "Boxed<f64>": Boxed<f64> => "java.lang.Double", "Ljava/lang/Double;";
+    "Option<i32>": Option<i32> => "java.lang.Integer", "Ljava/lang/Integer;";
+    "Option<String>": Option<String> => "java.lang.String", "Ljava/lang/String;";
+    "JOption<i64>": JOption<i64> => "java.lang.Long", "Ljava/lang/Long;";
+    "Result<i32, String>": Result<i32, String> => "int", "I";
+    "Vec<i64>": Vec<i64> => "java.util.ArrayList<Long>", "Ljava/util/ArrayList;";
+    "Vec<&str>": Vec<&str> => "java.util.ArrayList<String>", "Ljava/util/ArrayList;";
+    "JavaArray<bool>": JavaArray<bool> => "boolean[]", "[Z";
+    "JavaArray<i8>": JavaArray<i8> => "byte[]", "[B";
+    "JavaArray<char>": JavaArray<char> => "char[]", "[C";
+    "JavaArray<i16>": JavaArray<i16> => "short[]", "[S";
+    "JavaArray<i32>": JavaArray<i32> => "int[]", "[I";
+    "JavaArray<i64>": JavaArray<i64> => "long[]", "[J";
+    "JavaArray<f32>": JavaArray<f32> => "float[]", "[F";
+    "JavaArray<f64>": JavaArray<f64> => "double[]", "[D";
+    "JavaArray<String>": JavaArray<String> => "java.lang.String[]", "[Ljava/lang/String;";
+    "JavaArray<Boxed<i64>>": JavaArray<Boxed<i64>> => "java.lang.Long[]", "[Ljava/lang/Long;";
+    "JavaArray<Option<String>>": JavaArray<Option<String>> => "java.lang.String[]", "[Ljava/lang/String;";
+    "JavaArray<JObject<'env>>": JavaArray<JObject<'static>> => "java.lang.Object[]", "[Ljava/lang/Object;";
+    "(i32, String)": (i32, String) => "java.lang.Object[]", "[Ljava/lang/Object;";
+    "(i32, String, bool)": (i32, String, bool) => "java.lang.Object[]", "[Ljava/lang/Object;";
+    "i128": i128 => "java.math.BigInteger", "Ljava/math/BigInteger;";
+    "u128": u128 => "java.math.BigInteger", "Ljava/math/BigInteger;";
+    "BigIntegerBytes": BigIntegerBytes => "java.math.BigInteger", "Ljava/math/BigInteger;";
+    "Duration": std::time::Duration => "java.time.Duration", "Ljava/time/Duration;";
+    "SystemTime": std::time::SystemTime => "java.time.Instant", "Ljava/time/Instant;";
+    "JAtomicLong<'env>": JAtomicLong<'static> => "java.util.concurrent.atomic.AtomicLong", "Ljava/util/concurrent/atomic/AtomicLong;";
+    "JavaCallback<'env, ()>": JavaCallback<'static, ()> => "java.lang.Runnable", "Ljava/lang/Runnable;";
+    "JavaCallback<'env, (String,)>": JavaCallback<'static, (String,)> => "java.util.function.Consumer<String>", "Ljava/util/function/Consumer;";
+    "JavaCallback<'env, (String, i32)>": JavaCallback<'static, (String, i32)> => "java.util.function.BiConsumer<String, Integer>", "Ljava/util/function/BiConsumer;";
+    "Samples<i16>": Samples<i16> => "robusta.audio.ShortSamples", "Lrobusta/audio/ShortSamples;";
+    "Samples<f32>": Samples<f32> => "robusta.audio.FloatSamples", "Lrobusta/audio/FloatSamples;";
+    "Handle<String>": Handle<String> => "long", "J";
+    "HandleRef<'env, String>": HandleRef<'static, String> => "long", "J";
+    "DirectByteBuffer<'env>": DirectByteBuffer<'static> => "java.nio.ByteBuffer", "Ljava/nio/ByteBuffer;";
+    "DirectBytes": DirectBytes => "java.nio.ByteBuffer", "Ljava/nio/ByteBuffer;";
+    "Field<'env, i32>": Field<'static, i32> => "int", "I";
+    "ByJavaEquality<'env>": ByJavaEquality<'static> => "java.lang.Object", "Ljava/lang/Object;";
+    "ByJavaString": ByJavaString => "java.lang.Object", "Ljava/lang/Object;";
+    "Passthrough<'env>": Passthrough<'static> => "java.lang.Object", "Ljava/lang/Object;";
+    "PassthroughOwned": PassthroughOwned => "java.lang.Object", "Ljava/lang/Object;";
+    "Global<String>": Global<String> => "java.lang.String", "Ljava/lang/String;";
+    "Weak<String>": Weak<String> => "java.lang.String", "Ljava/lang/String;";
+    "JObject<'env>": JObject<'static> => "java.lang.Object", "Ljava/lang/Object;";
+    "JString<'env>": JString<'static> => "java.lang.String", "Ljava/lang/String;";
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::Path;
+
+    use super::*;
+
+    /// Types implementing `Signature` as the type they're converted through, which have no descriptor of their own.
+    const CONVERTED_THROUGH_OTHER_TYPES: &[&str] = &["Adapt"];
+
+    /// Name of the type of a conversion (or an `impl` header), leaving out references, paths and type parameters.
+    fn type_name(ty: &str) -> String {
+        let ty = ty.trim_start_matches('&').trim_start_matches("mut ");
+        let path_end = ty.find('<').unwrap_or(ty.len());
+        let ty = &ty[ty[..path_end].rfind("::").map_or(0, |i| i + 2)..];
+        match ty.find(|c: char| !(c.is_alphanumeric() || c == '_')) {
+            Some(0) | None => ty.replace(' ', ""),
+            Some(end) => ty[..end].to_string(),
+        }
+    }
+
+    /// Names of the types implementing `Signature` in the files under `dir`, except those implemented by macros for each of
+    /// their arguments (e.g. `impl Signature for Boxed<$type>`), whose instances are listed one by one in the table.
+    fn signature_impls(dir: &Path, types: &mut BTreeSet<String>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                signature_impls(&path, types);
+            } else if path.extension().map_or(false, |e| e == "rs") {
+                for line in fs::read_to_string(&path).unwrap().lines().map(str::trim) {
+                    let ty = match line.split_once("Signature for ") {
+                        Some((header, ty)) if header.starts_with("impl") => ty.trim_end_matches('{').trim(),
+                        _ => continue,
+                    };
+                    if !ty.contains('$') {
+                        types.insert(type_name(ty));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn descriptors_are_signatures_of_their_types() {
+        assert_eq!(CONVERSIONS.len(), SIG_TYPES.len());
+        for (conversion, sig_type) in CONVERSIONS.iter().zip(SIG_TYPES) {
+            // The table describes tuples without the `kotlin-tuples` feature
+            if cfg!(feature = "kotlin-tuples") && conversion.rust.starts_with('(') {
+                continue;
+            }
+            assert_eq!(conversion.descriptor, *sig_type, "descriptor of `{}`", conversion.rust);
+        }
+    }
+
+    #[test]
+    fn every_signature_impl_is_in_the_table() {
+        let mut implemented = BTreeSet::new();
+        signature_impls(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut implemented);
+        let listed: BTreeSet<String> = CONVERSIONS.iter().map(|c| type_name(c.rust)).collect();
+
+        let missing: Vec<_> = implemented
+            .iter()
+            .filter(|ty| !listed.contains(*ty) && !CONVERTED_THROUGH_OTHER_TYPES.contains(&ty.as_str()))
+            .collect();
+        assert!(missing.is_empty(), "`Signature` implementations missing from the conversion table: {:?}", missing);
+    }
+
+    #[test]
+    fn type_names_leave_out_type_parameters() {
+        assert_eq!(type_name("Vec<T>"), "Vec");
+        assert_eq!(type_name("std::result::Result<T, E>"), "Result");
+        assert_eq!(type_name("&[&str]"), "[&str]");
+        assert_eq!(type_name("&str"), "str");
+        assert_eq!(type_name("()"), "()");
+        assert_eq!(type_name("JObject<'env>"), "JObject");
+    }
+}
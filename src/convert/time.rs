@@ -0,0 +1,187 @@
+//! Conversions between `std::time` types and `java.time` classes.
+//!
+//! [`Duration`] is converted to and from `java.time.Duration`, and [`SystemTime`] to and from `java.time.Instant`:
+//!
+//! ```ignore
+//! pub extern "jni" fn elapsedSince(start: SystemTime) -> Duration {
+//!     start.elapsed().unwrap_or_default()
+//! }
+//! ```
+//!
+//! is called from Java as `Duration elapsedSince(Instant start)`. Java objects are created with `Duration.ofSeconds(long, long)`
+//! and `Instant.ofEpochSecond(long, long)`, and read back with `getSeconds`/`getEpochSecond` and `getNano`.
+//!
+//! All of these types have a precision of one nanosecond, so conversions never truncate. Their ranges differ though:
+//!  * A negative `java.time.Duration` has no `Duration` counterpart, nor has a `Duration` of more than `Long.MAX_VALUE` seconds a
+//!    `java.time.Duration` one.
+//!  * `SystemTime`s before the epoch are converted to `Instant`s with negative epoch seconds, and back if the platform's
+//!    `SystemTime` can hold them. `Instant`s are limited to the years -1000000000 to 1000000000.
+//!
+//! Values out of range of their destination type throw a `java.lang.IllegalArgumentException` with safe conversions
+//! (a `java.time.DateTimeException` for `Instant`s, thrown by `Instant.ofEpochSecond`), and panic with unchecked ones.
+//!
+//! With the `chrono` feature, `chrono::DateTime<Utc>` is converted to and from `java.time.Instant` too. Leap seconds, which
+//! `Instant` doesn't have, are converted to the first nanoseconds of the next second.
+//!
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use jni::errors::{Error, Result};
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+const NANOS_PER_SECOND: u32 = 1_000_000_000;
+
+/// Seconds and nanoseconds of `value` read with its `seconds_getter` and `getNano`.
+fn read_parts(env: JNIEnv, value: JObject, class: &'static str, seconds_getter: &str) -> Result<(i64, u32)> {
+    if value.is_null() {
+        return Err(Error::NullPtr(class));
+    }
+
+    let seconds = env.call_method(value, seconds_getter, "()J", &[])?.j()?;
+    let nanos = env.call_method(value, "getNano", "()I", &[])?.i()?;
+    Ok((seconds, nanos as u32))
+}
+
+/// A new instance of `class`, created by its static `factory` method from seconds and nanoseconds.
+fn new_instance<'env>(env: JNIEnv<'env>, class: &str, factory: &str, seconds: i64, nanos: u32) -> Result<JObject<'env>> {
+    env.call_static_method(
+        class,
+        factory,
+        format!("(JJ)L{};", class),
+        &[JValue::Long(seconds), JValue::Long(nanos as i64)],
+    )?
+    .l()
+}
+
+/// Throws a `java.lang.IllegalArgumentException` for a value out of range of its destination type.
+fn out_of_range<T>(env: JNIEnv, message: String) -> Result<T> {
+    env.throw_new("java/lang/IllegalArgumentException", message)?;
+    Err(Error::JavaException)
+}
+
+fn duration_to_parts(value: Duration) -> std::result::Result<(i64, u32), String> {
+    if value.as_secs() > i64::MAX as u64 {
+        return Err(format!("{:?} is too long for a java.time.Duration", value));
+    }
+
+    Ok((value.as_secs() as i64, value.subsec_nanos()))
+}
+
+fn duration_from_parts(seconds: i64, nanos: u32) -> std::result::Result<Duration, String> {
+    if seconds < 0 {
+        return Err(format!("negative duration of {} s and {} ns can't be converted to a Duration", seconds, nanos));
+    }
+
+    Ok(Duration::new(seconds as u64, nanos))
+}
+
+fn system_time_to_parts(value: SystemTime) -> std::result::Result<(i64, u32), String> {
+    let out_of_range = || format!("{:?} is out of the range of java.time.Instant", value);
+
+    match value.duration_since(UNIX_EPOCH) {
+        Ok(after) => duration_to_parts(after).map_err(|_| out_of_range()),
+        // Instants before the epoch count their nanoseconds forward from a negative second, like `Instant.getNano`
+        Err(before) => {
+            let (seconds, nanos) = duration_to_parts(before.duration()).map_err(|_| out_of_range())?;
+            match nanos {
+                0 => Ok((-seconds, 0)),
+                nanos => Ok((-seconds - 1, NANOS_PER_SECOND - nanos)),
+            }
+        }
+    }
+}
+
+fn system_time_from_parts(seconds: i64, nanos: u32) -> std::result::Result<SystemTime, String> {
+    let time = if seconds >= 0 {
+        UNIX_EPOCH.checked_add(Duration::new(seconds as u64, nanos))
+    } else {
+        UNIX_EPOCH
+            .checked_sub(Duration::from_secs(seconds.unsigned_abs()))
+            .and_then(|t| t.checked_add(Duration::from_nanos(nanos as u64)))
+    };
+
+    time.ok_or_else(|| format!("instant of {} s and {} ns since the epoch can't be converted to a SystemTime", seconds, nanos))
+}
+
+#[cfg(feature = "chrono")]
+fn date_time_to_parts(value: chrono::DateTime<chrono::Utc>) -> std::result::Result<(i64, u32), String> {
+    Ok((value.timestamp(), value.timestamp_subsec_nanos()))
+}
+
+#[cfg(feature = "chrono")]
+fn date_time_from_parts(seconds: i64, nanos: u32) -> std::result::Result<chrono::DateTime<chrono::Utc>, String> {
+    use chrono::TimeZone;
+
+    chrono::Utc
+        .timestamp_opt(seconds, nanos)
+        .single()
+        .ok_or_else(|| format!("instant of {} s and {} ns since the epoch can't be converted to a DateTime", seconds, nanos))
+}
+
+macro_rules! time_types {
+    ($($(#[$attr:meta])* $type:ty: $class:literal [$factory:literal, $seconds_getter:literal] ($to_parts:ident, $from_parts:ident)),+ $(,)?) => {
+        $(
+            $(#[$attr])*
+            impl Signature for $type {
+                const SIG_TYPE: &'static str = concat!("L", $class, ";");
+            }
+
+            $(#[$attr])*
+            impl<'env> TryIntoJavaValue<'env> for $type {
+                type Target = JObject<'env>;
+
+                fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+                    let (seconds, nanos) = match $to_parts(self) {
+                        Ok(parts) => parts,
+                        Err(message) => return out_of_range(env, message),
+                    };
+
+                    new_instance(env, $class, $factory, seconds, nanos)
+                }
+            }
+
+            $(#[$attr])*
+            impl<'env> TryFromJavaValue<'env> for $type {
+                type Source = JObject<'env>;
+
+                fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+                    let (seconds, nanos) = read_parts(env, s, $class, $seconds_getter)?;
+                    match $from_parts(seconds, nanos) {
+                        Ok(value) => Ok(value),
+                        Err(message) => out_of_range(env, message),
+                    }
+                }
+            }
+
+            $(#[$attr])*
+            impl<'env> IntoJavaValue<'env> for $type {
+                type Target = JObject<'env>;
+
+                fn into(self, env: JNIEnv<'env>) -> Self::Target {
+                    let (seconds, nanos) = $to_parts(self).unwrap_or_else(|message| panic!("{}", message));
+                    new_instance(env, $class, $factory, seconds, nanos).unwrap()
+                }
+            }
+
+            $(#[$attr])*
+            impl<'env> FromJavaValue<'env> for $type {
+                type Source = JObject<'env>;
+
+                fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+                    let (seconds, nanos) = read_parts(env, s, $class, $seconds_getter).unwrap();
+                    $from_parts(seconds, nanos).unwrap_or_else(|message| panic!("{}", message))
+                }
+            }
+        )+
+    };
+}
+
+time_types! {
+    Duration: "java/time/Duration" ["ofSeconds", "getSeconds"] (duration_to_parts, duration_from_parts),
+    SystemTime: "java/time/Instant" ["ofEpochSecond", "getEpochSecond"] (system_time_to_parts, system_time_from_parts),
+    #[cfg(feature = "chrono")]
+    chrono::DateTime<chrono::Utc>: "java/time/Instant" ["ofEpochSecond", "getEpochSecond"] (date_time_to_parts, date_time_from_parts),
+}
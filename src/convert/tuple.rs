@@ -0,0 +1,147 @@
+//! Conversions between tuples and Java carrier objects.
+//!
+//! Java has no tuples, so 2- and 3-tuples are converted to an `Object[]` holding their elements, converted as usual and boxed
+//! if they're primitives:
+//!
+//! ```ignore
+//! pub extern "jni" fn nameAndCount(name: String) -> (String, i32) {
+//!     let count = name.chars().count() as i32;
+//!     (name, count)
+//! }
+//! ```
+//!
+//! is called from Java as `Object[] nameAndCount(String name)`, returning e.g. `{"abc", Integer.valueOf(3)}`. Tuple parameters
+//! read their elements from such an array, which must have as many elements as the tuple.
+//!
+//! With the `kotlin-tuples` feature, tuples are converted to `kotlin.Pair` and `kotlin.Triple` instead, which must then be on the
+//! class path.
+//!
+//! Tuples can be elements of other tuples, `Vec`s or `Option`s, but not of [`JavaArray`](crate::convert::JavaArray)s.
+//!
+
+use jni::errors::{Error, Result};
+use jni::objects::JObject;
+#[cfg(feature = "kotlin-tuples")]
+use jni::objects::JValue;
+use jni::sys::jsize;
+use jni::JNIEnv;
+
+use crate::convert::{FromJavaValue, IntoJavaValue, JavaValue, Signature, TryFromJavaValue, TryIntoJavaValue};
+
+/// A carrier object of class `class` holding `elements`.
+#[cfg(feature = "kotlin-tuples")]
+fn new_carrier<'env>(env: JNIEnv<'env>, class: &str, constructor_sig: &str, elements: &[JObject<'env>]) -> Result<JObject<'env>> {
+    let args: Vec<JValue> = elements.iter().map(|e| JValue::Object(*e)).collect();
+    env.new_object(class, constructor_sig, &args)
+}
+
+/// A carrier object of class `class` holding `elements`.
+#[cfg(not(feature = "kotlin-tuples"))]
+fn new_carrier<'env>(env: JNIEnv<'env>, _class: &str, _constructor_sig: &str, elements: &[JObject<'env>]) -> Result<JObject<'env>> {
+    let array = env.new_object_array(elements.len() as jsize, "java/lang/Object", JObject::null())?;
+    for (i, element) in elements.iter().enumerate() {
+        env.set_object_array_element(array, i as jsize, *element)?;
+    }
+
+    Ok(<JObject as From<_>>::from(array))
+}
+
+/// Checks that `carrier` holds `len` elements.
+#[cfg(feature = "kotlin-tuples")]
+fn check_carrier(_env: JNIEnv, _carrier: JObject, _len: jsize) -> Result<()> {
+    // The class of the carrier, hence its number of elements, is checked when calling its getters
+    Ok(())
+}
+
+/// Checks that `carrier` holds `len` elements.
+#[cfg(not(feature = "kotlin-tuples"))]
+fn check_carrier(env: JNIEnv, carrier: JObject, len: jsize) -> Result<()> {
+    if env.get_array_length(carrier.into_inner())? != len {
+        return Err(Error::WrongJValueType("Object[] with as many elements as the tuple", "Object[] of another length"));
+    }
+
+    Ok(())
+}
+
+/// Element `index` of `carrier`, read with its `getter` method.
+#[cfg(feature = "kotlin-tuples")]
+fn carrier_element<'env>(env: JNIEnv<'env>, carrier: JObject<'env>, _index: jsize, getter: &str) -> Result<JObject<'env>> {
+    env.call_method(carrier, getter, "()Ljava/lang/Object;", &[])?.l()
+}
+
+/// Element `index` of `carrier`.
+#[cfg(not(feature = "kotlin-tuples"))]
+fn carrier_element<'env>(env: JNIEnv<'env>, carrier: JObject<'env>, index: jsize, _getter: &str) -> Result<JObject<'env>> {
+    env.get_object_array_element(carrier.into_inner(), index)
+}
+
+macro_rules! tuples {
+    ($($len:literal: ($($t:ident $u:ident $i:tt $getter:literal),+) as $class:literal $constructor_sig:literal),+ $(,)?) => {
+        $(
+            impl<$($t),+> Signature for ($($t,)+) {
+                #[cfg(feature = "kotlin-tuples")]
+                const SIG_TYPE: &'static str = concat!("L", $class, ";");
+                #[cfg(not(feature = "kotlin-tuples"))]
+                const SIG_TYPE: &'static str = "[Ljava/lang/Object;";
+            }
+
+            impl<'env, $($t),+> TryIntoJavaValue<'env> for ($($t,)+)
+            where
+                $($t: TryIntoJavaValue<'env>),+
+            {
+                type Target = JObject<'env>;
+
+                fn try_into(self, env: JNIEnv<'env>) -> Result<Self::Target> {
+                    // Local references to the converted elements are released once the carrier holds them
+                    env.with_local_frame($len + 1, || {
+                        let elements = [$(JavaValue::autobox(TryIntoJavaValue::try_into(self.$i, env)?, env)),+];
+                        new_carrier(env, $class, $constructor_sig, &elements)
+                    })
+                }
+            }
+
+            impl<'env, $($t, $u),+> TryFromJavaValue<'env> for ($($t,)+)
+            where
+                $($t: TryFromJavaValue<'env, Source = $u>, $u: JavaValue<'env>),+
+            {
+                type Source = JObject<'env>;
+
+                fn try_from(s: Self::Source, env: JNIEnv<'env>) -> Result<Self> {
+                    if s.is_null() {
+                        return Err(Error::NullPtr("tuple"));
+                    }
+
+                    check_carrier(env, s, $len)?;
+                    Ok(($($t::try_from($u::try_unbox(carrier_element(env, s, $i, $getter)?, env)?, env)?,)+))
+                }
+            }
+
+            impl<'env, $($t),+> IntoJavaValue<'env> for ($($t,)+)
+            where
+                $($t: TryIntoJavaValue<'env>),+
+            {
+                type Target = JObject<'env>;
+
+                fn into(self, env: JNIEnv<'env>) -> Self::Target {
+                    TryIntoJavaValue::try_into(self, env).unwrap()
+                }
+            }
+
+            impl<'env, $($t, $u),+> FromJavaValue<'env> for ($($t,)+)
+            where
+                $($t: TryFromJavaValue<'env, Source = $u>, $u: JavaValue<'env>),+
+            {
+                type Source = JObject<'env>;
+
+                fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+                    TryFromJavaValue::try_from(s, env).unwrap()
+                }
+            }
+        )+
+    };
+}
+
+tuples! {
+    2: (A U 0 "getFirst", B V 1 "getSecond") as "kotlin/Pair" "(Ljava/lang/Object;Ljava/lang/Object;)V",
+    3: (A U 0 "getFirst", B V 1 "getSecond", C W 2 "getThird") as "kotlin/Triple" "(Ljava/lang/Object;Ljava/lang/Object;Ljava/lang/Object;)V",
+}
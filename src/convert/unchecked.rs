@@ -8,20 +8,25 @@
 //! #[call_type(unchecked)]
 //! ```
 //!
-//! **These functions *will* panic should any conversion fail.**
+//! **These functions *will* panic should any conversion fail.** Such panics (and any other panic of the method) abort the process,
+//! unless the method catches them and throws a Java exception instead:
+//!
+//! ```ignore
+//! #[call_type(unchecked(catch_panics))]
+//! ```
 //!
 
-use std::convert::TryFrom;
+use std::borrow::Cow;
 
 use jni::objects::{JList, JObject, JString, JValue};
-use jni::sys::{jboolean, jbooleanArray, jchar, jobject, jstring};
+use jni::sys::{jboolean, jbooleanArray, jchar, jlong, jobject, jobjectArray, jstring};
 use jni::JNIEnv;
 
 use crate::convert::{JavaValue, Signature};
 
 pub use robusta_codegen::{FromJavaValue, IntoJavaValue};
 
-use super::{JOption, JValueWrapper};
+use super::{boxed_signature, JOption};
 
 /// Conversion trait from Rust values to Java values, analogous to [Into]. Used when converting types returned from JNI-available functions.
 ///
@@ -110,6 +115,68 @@ impl<'env> FromJavaValue<'env> for String {
     }
 }
 
+impl Signature for &str {
+    const SIG_TYPE: &'static str = <String as Signature>::SIG_TYPE;
+}
+
+impl Signature for Cow<'_, str> {
+    const SIG_TYPE: &'static str = <String as Signature>::SIG_TYPE;
+}
+
+/// `strings` as `String`s, which `Vec<&str>` and `Vec<Cow<str>>` values are converted to Java as. Used by the generated code.
+///
+/// `&str` and `Cow<str>` have no conversion trait implementations, which would make `"...".into()` ambiguous between `Into`
+/// and `IntoJavaValue`: the generated code converts them to `String` first.
+#[doc(hidden)]
+pub fn owned_strings<S: Into<String>>(strings: Vec<S>) -> Vec<String> {
+    strings.into_iter().map(Into::into).collect()
+}
+
+impl Signature for &[&str] {
+    const SIG_TYPE: &'static str = "[Ljava/lang/String;";
+}
+
+impl Signature for &[String] {
+    const SIG_TYPE: &'static str = "[Ljava/lang/String;";
+}
+
+impl Signature for Box<[String]> {
+    const SIG_TYPE: &'static str = "[Ljava/lang/String;";
+}
+
+impl<'env> IntoJavaValue<'env> for &[&str] {
+    type Target = jobjectArray;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        crate::convert::TryIntoJavaValue::try_into(self, env).unwrap()
+    }
+}
+
+impl<'env> IntoJavaValue<'env> for &[String] {
+    type Target = jobjectArray;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        crate::convert::TryIntoJavaValue::try_into(self, env).unwrap()
+    }
+}
+
+impl<'env> IntoJavaValue<'env> for Box<[String]> {
+    type Target = jobjectArray;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        crate::convert::TryIntoJavaValue::try_into(self, env).unwrap()
+    }
+}
+
+/// Panics on `null` elements.
+impl<'env> FromJavaValue<'env> for Box<[String]> {
+    type Source = JObject<'env>;
+
+    fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
+        crate::convert::TryFromJavaValue::try_from(s, env).unwrap()
+    }
+}
+
 impl<'env> IntoJavaValue<'env> for bool {
     type Target = jboolean;
 
@@ -138,11 +205,12 @@ impl Signature for char {
     const SIG_TYPE: &'static str = <jchar as Signature>::SIG_TYPE;
 }
 
+/// Primitives wrapped in an `Option` are passed as their wrapper class, e.g. `Option<i32>` as `java.lang.Integer`.
 impl<T> Signature for Option<T>
 where
     T: Signature,
 {
-    const SIG_TYPE: &'static str = <T as Signature>::SIG_TYPE;
+    const SIG_TYPE: &'static str = boxed_signature(<T as Signature>::SIG_TYPE);
 }
 
 impl<'env> IntoJavaValue<'env> for char {
@@ -164,6 +232,23 @@ impl<'env> FromJavaValue<'env> for char {
     }
 }
 
+/// Unsigned integers are widened to `long` on the Java side (`u8` and `u16` are not listed here, as they're
+/// respectively `jboolean` and `jchar`).
+///
+/// They're converted through [`Widen`](crate::convert::numeric::Widen) by the generated code: conversion trait
+/// implementations on them would make `.into()` and `::from` calls on integers ambiguous wherever the traits are in scope.
+macro_rules! unsigned_types {
+    ($($type:ty),+) => {
+        $(
+            impl Signature for $type {
+                const SIG_TYPE: &'static str = <jlong as Signature>::SIG_TYPE;
+            }
+        )+
+    };
+}
+
+unsigned_types!(u32, u64, usize);
+
 impl<'env> IntoJavaValue<'env> for Box<[bool]> {
     type Target = jbooleanArray;
 
@@ -192,6 +277,7 @@ impl<T> Signature for Vec<T> {
     const SIG_TYPE: &'static str = "Ljava/util/ArrayList;";
 }
 
+/// Elements converted to objects, like bridged structs, are added as they are, and primitives are boxed.
 impl<'env, T> IntoJavaValue<'env> for Vec<T>
 where
     T: IntoJavaValue<'env>,
@@ -218,6 +304,7 @@ where
     }
 }
 
+/// Elements converted from objects, like bridged structs, are taken as they are, and primitives are unboxed.
 impl<'env, T, U> FromJavaValue<'env> for Vec<T>
 where
     T: FromJavaValue<'env, Source = U>,
@@ -235,9 +322,11 @@ where
     }
 }
 
-impl<'env, T> IntoJavaValue<'env> for jni::errors::Result<T>
+/// Panics if the returned value is `Err`.
+impl<'env, T, E> IntoJavaValue<'env> for std::result::Result<T, E>
 where
     T: IntoJavaValue<'env>,
+    E: std::fmt::Debug,
 {
     type Target = <T as IntoJavaValue<'env>>::Target;
 
@@ -246,14 +335,16 @@ where
     }
 }
 
-impl<'env, T> IntoJavaValue<'env> for JOption<T>
+/// `None` is converted to `null`, and primitives in `Some` to instances of their wrapper class.
+impl<'env, T> IntoJavaValue<'env> for Option<T>
 where
     T: IntoJavaValue<'env>,
 {
     type Target = JObject<'env>;
 
+    const SIG_TYPE: &'static str = boxed_signature(<T as IntoJavaValue<'env>>::SIG_TYPE);
+
     fn into(self, env: JNIEnv<'env>) -> Self::Target {
-        use JOption::*;
         match self {
             Some(value) => IntoJavaValue::into(value, env).autobox(env),
             None => JObject::null(),
@@ -261,32 +352,44 @@ where
     }
 }
 
-impl<'env, T> FromJavaValue<'env> for JOption<T>
+/// `null` is converted to `None`, and instances of wrapper classes to `Some` primitive.
+impl<'env, T, U> FromJavaValue<'env> for Option<T>
 where
-    T: FromJavaValue<'env, Source = JObject<'env>>,
+    T: FromJavaValue<'env, Source = U>,
+    U: JavaValue<'env>,
 {
     type Source = JObject<'env>;
 
+    const SIG_TYPE: &'static str = boxed_signature(<T as FromJavaValue<'env>>::SIG_TYPE);
+
     fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
-        use JOption::*;
-        let s2 = s.clone();
-        if env.is_same_object(s, JObject::null()).unwrap() {
-            Some(<T as FromJavaValue>::from(s2, env))
-        } else {
+        if s.is_null() {
             None
+        } else {
+            Some(FromJavaValue::from(U::unbox(s, env), env))
         }
     }
 }
 
-impl<'env> FromJavaValue<'env> for Option<String> {
-    type Source = <String as FromJavaValue<'env>>::Source;
+impl<'env, T> IntoJavaValue<'env> for JOption<T>
+where
+    T: IntoJavaValue<'env>,
+{
+    type Target = JObject<'env>;
+
+    fn into(self, env: JNIEnv<'env>) -> Self::Target {
+        IntoJavaValue::into(<JOption<T> as Into<Option<T>>>::into(self), env)
+    }
+}
+
+impl<'env, T, U> FromJavaValue<'env> for JOption<T>
+where
+    T: FromJavaValue<'env, Source = U>,
+    U: JavaValue<'env>,
+{
+    type Source = JObject<'env>;
 
     fn from(s: Self::Source, env: JNIEnv<'env>) -> Self {
-        let s2 = s.clone();
-        if env.is_same_object(s, JObject::null()).unwrap() {
-            Some(<String as FromJavaValue>::from(s2, env))
-        } else {
-            None
-        }
+        <JOption<T> as From<Option<T>>>::from(FromJavaValue::from(s, env))
     }
 }
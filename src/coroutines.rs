@@ -0,0 +1,88 @@
+//! Calling Kotlin `suspend` functions.
+//!
+//! A Kotlin `suspend fun` is compiled to a JVM method taking an additional `kotlin.coroutines.Continuation` parameter and
+//! returning `Object`, which can't be called directly from native code. An imported method annotated with `#[suspend]` is
+//! declared with the parameters and return type of the Kotlin function instead, and the calling thread blocks until the
+//! function completes, as with `runBlocking`:
+//!
+//! ```ignore
+//! // suspend fun fetch(url: String): String
+//! #[suspend]
+//! pub extern "java" fn fetch(&self, env: JNIEnv<'env>, url: String) -> JniResult<String> {}
+//! ```
+//!
+//! The call goes through `robusta.coroutines.Suspend`, a small Java class whose source is generated together with the Java stubs
+//! of the module (see [Generating Java stubs](crate#generating-java-stubs)). It starts the function in
+//! `kotlinx.coroutines.BuildersKt.runBlocking`, found through reflection with the class loader of the called class, so
+//! `kotlinx-coroutines-core` must be on the class path of the called class, but not of the Java stubs. Exceptions thrown by the
+//! function are thrown to the caller.
+//!
+//! As for [dispatched](crate::dispatch) calls, arguments are boxed into an `Object[]` and the function is called through
+//! reflection. Parameters of type `Option<T>` are passed as `null` for `None`, as for Kotlin's nullable types.
+//!
+
+use jni::errors::{Error, Result};
+use jni::objects::{JObject, JValue};
+use jni::signature::{JavaType, TypeSignature};
+use jni::JNIEnv;
+
+use crate::dispatch::{boxed_array, reflected_method, unboxed};
+
+const CONTINUATION_CLASS: &str = "kotlin/coroutines/Continuation";
+const SUSPEND_CLASS: &str = "robusta/coroutines/Suspend";
+const INVOKE_SIGNATURE: &str = "(Ljava/lang/reflect/Method;Ljava/lang/Object;[Ljava/lang/Object;)Ljava/lang/Object;";
+
+/// Calls the `suspend` instance method `name` of `class` on `obj`, blocking until it completes.
+///
+/// `signature` is the descriptor of the method as declared in Kotlin, without its `Continuation` parameter.
+pub fn call<'a>(env: JNIEnv<'a>, obj: JObject<'a>, class: &str, name: &str, signature: &str, args: &[JValue]) -> Result<JValue<'a>> {
+    if obj.is_null() {
+        return Err(Error::NullPtr("call_method obj argument"));
+    }
+
+    invoke(env, Some(obj), class, name, signature, args)
+}
+
+/// Calls the `suspend` static method `name` of `class`, blocking until it completes.
+///
+/// `signature` is the descriptor of the method as declared in Kotlin, without its `Continuation` parameter.
+pub fn call_static<'a>(env: JNIEnv<'a>, class: &str, name: &str, signature: &str, args: &[JValue]) -> Result<JValue<'a>> {
+    invoke(env, None, class, name, signature, args)
+}
+
+fn invoke<'a>(
+    env: JNIEnv<'a>,
+    obj: Option<JObject<'a>>,
+    class: &str,
+    name: &str,
+    signature: &str,
+    args: &[JValue],
+) -> Result<JValue<'a>> {
+    // The method compiled from the `suspend` function takes a `Continuation` and returns `Object`
+    let TypeSignature { args: mut params, ret } = TypeSignature::from_str(signature)?;
+    params.push(JavaType::Object(CONTINUATION_CLASS.to_string()));
+    let suspend_signature = TypeSignature { args: params, ret: JavaType::Object("java/lang/Object".to_string()) };
+
+    let class = env.find_class(class)?;
+    let method = reflected_method(env, class, name, &suspend_signature.to_string(), obj.is_none())?;
+    let boxed_args = boxed_array(env, args)?;
+
+    let result = env
+        .call_static_method(
+            SUSPEND_CLASS,
+            "invoke",
+            INVOKE_SIGNATURE,
+            &[
+                JValue::Object(method),
+                JValue::Object(obj.unwrap_or_else(JObject::null)),
+                JValue::Object(boxed_args.into()),
+            ],
+        )?
+        .l()?;
+
+    env.delete_local_ref(method)?;
+    env.delete_local_ref(boxed_args.into())?;
+    env.delete_local_ref(class.into())?;
+
+    unboxed(env, result, &ret)
+}
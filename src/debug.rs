@@ -0,0 +1,210 @@
+//! Tally of the Java references created by robusta, to track down reference leaks.
+//!
+//! With the `ref-debug` feature, each reference robusta creates and keeps or hands out is counted, by the native method it was
+//! created in (its site, e.g. `com.example.Foo.bar`) and the kind of value holding it (e.g. `PassthroughOwned`):
+//!
+//! * global references, held by owned wrappers ([`PassthroughOwned`](crate::convert::PassthroughOwned),
+//!   [`ByJavaString`](crate::convert::ByJavaString), [`GlobalAtomicLong`](crate::convert::GlobalAtomicLong)), callbacks and caches,
+//!   are deleted when the last value holding them is dropped;
+//! * local references handed out by conversions (e.g. [`PassthroughOwned::downgrade`](crate::convert::PassthroughOwned::downgrade))
+//!   are deleted by the JVM when the native method they were created in returns, or never on threads outside native methods.
+//!
+//! [`ref_report`] lists the references that haven't been deleted yet:
+//!
+//! ```ignore
+//! for count in robusta_jni::debug::ref_report() {
+//!     eprintln!("{:?}: {} outstanding {}", count.site, count.outstanding(), count.kind);
+//! }
+//! ```
+//!
+//! Caches keep their references for as long as the library is loaded, so they always show up. [`set_panic_threshold`] makes
+//! creating a reference panic once a site has too many outstanding references of a kind, to find where a leak comes from.
+//!
+//! Without the feature, the module is empty and the generated code doesn't track anything.
+//!
+
+#[cfg(feature = "ref-debug")]
+use std::cell::RefCell;
+#[cfg(feature = "ref-debug")]
+use std::collections::BTreeMap;
+#[cfg(feature = "ref-debug")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "ref-debug")]
+use std::sync::{Arc, Mutex};
+
+/// Site and kind of references, see [`RefCount`].
+#[cfg(feature = "ref-debug")]
+type Key = (Option<&'static str>, &'static str);
+
+#[cfg(feature = "ref-debug")]
+static TALLY: Mutex<BTreeMap<Key, (usize, usize)>> = Mutex::new(BTreeMap::new());
+
+#[cfg(feature = "ref-debug")]
+static PANIC_THRESHOLD: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+#[cfg(feature = "ref-debug")]
+thread_local! {
+    /// Native methods being called on this thread, innermost last, with the kinds of the local references created in each.
+    static SITES: RefCell<Vec<(&'static str, Vec<&'static str>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// References of one kind created at one site, and how many of them have been deleted.
+#[cfg(feature = "ref-debug")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefCount {
+    /// Java name of the native method the references were created in, or `None` outside native methods.
+    pub site: Option<&'static str>,
+    /// Value holding the references, e.g. `PassthroughOwned` or `CachedMethod`.
+    pub kind: &'static str,
+    pub created: usize,
+    pub deleted: usize,
+}
+
+#[cfg(feature = "ref-debug")]
+impl RefCount {
+    /// References that haven't been deleted yet.
+    pub fn outstanding(&self) -> usize {
+        self.created - self.deleted
+    }
+}
+
+/// Counts of the references with outstanding ones, by site and kind.
+#[cfg(feature = "ref-debug")]
+pub fn ref_report() -> Vec<RefCount> {
+    let tally = TALLY.lock().unwrap_or_else(|e| e.into_inner());
+
+    tally
+        .iter()
+        .filter(|(_, (created, deleted))| created > deleted)
+        .map(|(&(site, kind), &(created, deleted))| RefCount {
+            site,
+            kind,
+            created,
+            deleted,
+        })
+        .collect()
+}
+
+/// Makes creating a reference panic when its site already has `threshold` outstanding references of its kind, or never with `None`.
+#[cfg(feature = "ref-debug")]
+pub fn set_panic_threshold(threshold: Option<usize>) {
+    PANIC_THRESHOLD.store(threshold.unwrap_or(usize::MAX), Ordering::SeqCst);
+}
+
+#[cfg(feature = "ref-debug")]
+fn created(kind: &'static str) -> Key {
+    let key = (SITES.with(|s| s.borrow().last().map(|(site, _)| *site)), kind);
+
+    let outstanding = {
+        let mut tally = TALLY.lock().unwrap_or_else(|e| e.into_inner());
+        let (created, deleted) = tally.entry(key).or_default();
+        let outstanding = *created - *deleted;
+        if outstanding < PANIC_THRESHOLD.load(Ordering::SeqCst) {
+            *created += 1;
+            return key;
+        }
+
+        outstanding
+    };
+
+    // The tally is unlocked first, so that it isn't poisoned
+    panic!("{} outstanding references held by `{}` created in {}", outstanding, kind, key.0.unwrap_or("no native method"))
+}
+
+#[cfg(feature = "ref-debug")]
+fn deleted(key: Key, count: usize) {
+    let mut tally = TALLY.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((_, deleted)) = tally.get_mut(&key) {
+        *deleted += count;
+    }
+}
+
+/// Records a local reference handed out by a value of `kind`, deleted when the current native method returns.
+#[cfg(feature = "ref-debug")]
+pub(crate) fn local_created(kind: &'static str) {
+    let key = created(kind);
+    SITES.with(|s| {
+        if let Some((_, locals)) = s.borrow_mut().last_mut() {
+            locals.push(key.1);
+        }
+    });
+}
+
+#[cfg(not(feature = "ref-debug"))]
+#[inline(always)]
+pub(crate) fn local_created(_kind: &'static str) {}
+
+/// A global reference held by a value of `kind`, counted as deleted when the last clone is dropped.
+#[derive(Clone, Debug)]
+pub(crate) struct Tracked {
+    #[cfg(feature = "ref-debug")]
+    _global: Arc<TrackedGlobal>,
+}
+
+impl Tracked {
+    #[cfg(feature = "ref-debug")]
+    pub(crate) fn global(kind: &'static str) -> Self {
+        Tracked {
+            _global: Arc::new(TrackedGlobal(created(kind))),
+        }
+    }
+
+    #[cfg(not(feature = "ref-debug"))]
+    #[inline(always)]
+    pub(crate) fn global(_kind: &'static str) -> Self {
+        Tracked {}
+    }
+}
+
+#[cfg(feature = "ref-debug")]
+#[derive(Debug)]
+struct TrackedGlobal(Key);
+
+#[cfg(feature = "ref-debug")]
+impl Drop for TrackedGlobal {
+    fn drop(&mut self) {
+        deleted(self.0, 1);
+    }
+}
+
+/// The native method being called, for the duration of the call. Used by the generated code.
+#[cfg(feature = "ref-debug")]
+#[doc(hidden)]
+pub struct RefSite(());
+
+#[cfg(feature = "ref-debug")]
+impl RefSite {
+    pub fn enter(site: &'static str) -> Self {
+        SITES.with(|s| s.borrow_mut().push((site, Vec::new())));
+        RefSite(())
+    }
+}
+
+#[cfg(feature = "ref-debug")]
+impl Drop for RefSite {
+    fn drop(&mut self) {
+        // The JVM deletes the local references created during the call once it returns
+        if let Some((site, locals)) = SITES.with(|s| s.borrow_mut().pop()) {
+            for kind in locals {
+                deleted((Some(site), kind), 1);
+            }
+        }
+    }
+}
+
+/// Records `$site` as the native method being called until the end of the enclosing block.
+#[cfg(feature = "ref-debug")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ref_site {
+    ($site:expr) => {
+        let _ref_site = $crate::debug::RefSite::enter($site);
+    };
+}
+
+#[cfg(not(feature = "ref-debug"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ref_site {
+    ($site:expr) => {};
+}
@@ -0,0 +1,193 @@
+//! Calling imported methods on the thread of a GUI toolkit.
+//!
+//! Swing and JavaFX objects must only be used from their toolkit's thread (the Swing event dispatch thread, or the JavaFX
+//! application thread). An imported method annotated with `#[dispatch(swing)]` or `#[dispatch(javafx)]` is called on that thread,
+//! and the calling thread waits for it to return:
+//!
+//! ```ignore
+//! #[dispatch(swing)]
+//! pub extern "java" fn setText(&self, env: JNIEnv<'env>, text: String) -> JniResult<()> {}
+//! ```
+//!
+//! The call goes through `robusta.dispatch.Dispatch`, a small Java class whose source is generated together with the Java stubs
+//! of the module (see [Generating Java stubs](crate#generating-java-stubs)). It hands the call to `SwingUtilities.invokeAndWait`
+//! or `Platform.runLater`, and gives its result back to the calling thread, or throws its exception there. Methods called from the
+//! toolkit's thread itself are called directly, since waiting for the toolkit's thread from that thread would never return.
+//!
+//! Arguments are boxed into an `Object[]` and the method is called through reflection, so dispatched calls are noticeably slower
+//! than direct ones. JavaFX must be on the class path, and its toolkit started, for `javafx` calls to succeed.
+//!
+
+use jni::errors::{Error, Result};
+use jni::objects::{JClass, JObject, JValue};
+use jni::signature::{JavaType, Primitive, TypeSignature};
+use jni::sys::{jboolean, jobjectArray, jsize};
+use jni::JNIEnv;
+
+const DISPATCH_CLASS: &str = "robusta/dispatch/Dispatch";
+const INVOKE_SIGNATURE: &str = "(Ljava/lang/String;Ljava/lang/reflect/Method;Ljava/lang/Object;[Ljava/lang/Object;)Ljava/lang/Object;";
+
+/// A GUI toolkit whose thread methods are called on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Toolkit {
+    /// Swing (and AWT), whose methods are called on the event dispatch thread.
+    Swing,
+    /// JavaFX, whose methods are called on the application thread.
+    JavaFx,
+}
+
+impl Toolkit {
+    fn name(self) -> &'static str {
+        match self {
+            Toolkit::Swing => "swing",
+            Toolkit::JavaFx => "javafx",
+        }
+    }
+
+    /// Calls the instance method `name` of `class` on `obj`, on the toolkit's thread.
+    pub fn call<'a>(
+        self,
+        env: JNIEnv<'a>,
+        obj: JObject<'a>,
+        class: &str,
+        name: &str,
+        signature: &str,
+        args: &[JValue],
+    ) -> Result<JValue<'a>> {
+        if obj.is_null() {
+            return Err(Error::NullPtr("call_method obj argument"));
+        }
+
+        self.invoke(env, Some(obj), class, name, signature, args)
+    }
+
+    /// Calls the static method `name` of `class`, on the toolkit's thread.
+    pub fn call_static<'a>(
+        self,
+        env: JNIEnv<'a>,
+        class: &str,
+        name: &str,
+        signature: &str,
+        args: &[JValue],
+    ) -> Result<JValue<'a>> {
+        self.invoke(env, None, class, name, signature, args)
+    }
+
+    fn invoke<'a>(
+        self,
+        env: JNIEnv<'a>,
+        obj: Option<JObject<'a>>,
+        class: &str,
+        name: &str,
+        signature: &str,
+        args: &[JValue],
+    ) -> Result<JValue<'a>> {
+        let ret = TypeSignature::from_str(signature)?.ret;
+        let class = env.find_class(class)?;
+        let method = reflected_method(env, class, name, signature, obj.is_none())?;
+
+        let boxed_args = boxed_array(env, args)?;
+
+        let toolkit = env.new_string(self.name())?;
+        let result = env
+            .call_static_method(
+                DISPATCH_CLASS,
+                "invoke",
+                INVOKE_SIGNATURE,
+                &[
+                    JValue::Object(toolkit.into()),
+                    JValue::Object(method),
+                    JValue::Object(obj.unwrap_or_else(JObject::null)),
+                    JValue::Object(boxed_args.into()),
+                ],
+            )?
+            .l()?;
+
+        env.delete_local_ref(toolkit.into())?;
+        env.delete_local_ref(method)?;
+        env.delete_local_ref(boxed_args.into())?;
+        env.delete_local_ref(class.into())?;
+
+        unboxed(env, result, &ret)
+    }
+}
+
+/// The `java.lang.reflect.Method` of a method of `class`.
+pub(crate) fn reflected_method<'a>(env: JNIEnv<'a>, class: JClass<'a>, name: &str, signature: &str, is_static: bool) -> Result<JObject<'a>> {
+    let id = if is_static {
+        env.get_static_method_id(class, name, signature)?.into_inner()
+    } else {
+        env.get_method_id(class, name, signature)?.into_inner()
+    };
+
+    let raw = env.get_native_interface();
+    // SAFETY: `id` is a method ID of `class`, static if and only if `is_static` is
+    let method = unsafe {
+        match (**raw).ToReflectedMethod {
+            Some(to_reflected) => to_reflected(raw, class.into_inner(), id, is_static as jboolean),
+            None => return Err(Error::JNIEnvMethodNotFound("ToReflectedMethod")),
+        }
+    };
+
+    if method.is_null() {
+        return Err(if env.exception_check()? {
+            Error::JavaException
+        } else {
+            Error::NullPtr("ToReflectedMethod result")
+        });
+    }
+
+    Ok(JObject::from(method))
+}
+
+/// `args` as an `Object[]`, boxing primitives.
+pub(crate) fn boxed_array(env: JNIEnv, args: &[JValue]) -> Result<jobjectArray> {
+    let array = env.new_object_array(args.len() as jsize, "java/lang/Object", JObject::null())?;
+    for (i, arg) in args.iter().enumerate() {
+        let boxed = boxed(env, *arg)?;
+        env.set_object_array_element(array, i as jsize, boxed)?;
+        if !matches!(arg, JValue::Object(_)) {
+            env.delete_local_ref(boxed)?;
+        }
+    }
+
+    Ok(array)
+}
+
+/// `value` as an object, boxing primitives.
+fn boxed<'a>(env: JNIEnv<'a>, value: JValue<'a>) -> Result<JObject<'a>> {
+    let (class, signature) = match value {
+        JValue::Object(o) => return Ok(o),
+        JValue::Bool(_) => ("java/lang/Boolean", "(Z)Ljava/lang/Boolean;"),
+        JValue::Byte(_) => ("java/lang/Byte", "(B)Ljava/lang/Byte;"),
+        JValue::Char(_) => ("java/lang/Character", "(C)Ljava/lang/Character;"),
+        JValue::Short(_) => ("java/lang/Short", "(S)Ljava/lang/Short;"),
+        JValue::Int(_) => ("java/lang/Integer", "(I)Ljava/lang/Integer;"),
+        JValue::Long(_) => ("java/lang/Long", "(J)Ljava/lang/Long;"),
+        JValue::Float(_) => ("java/lang/Float", "(F)Ljava/lang/Float;"),
+        JValue::Double(_) => ("java/lang/Double", "(D)Ljava/lang/Double;"),
+        JValue::Void => return Err(Error::WrongJValueType("void", "argument")),
+    };
+
+    env.call_static_method(class, "valueOf", signature, &[value])?.l()
+}
+
+/// The result of `Method.invoke` as a value of type `ret`, unboxing primitives.
+pub(crate) fn unboxed<'a>(env: JNIEnv<'a>, value: JObject<'a>, ret: &JavaType) -> Result<JValue<'a>> {
+    let (method, signature) = match ret {
+        JavaType::Primitive(Primitive::Void) => return Ok(JValue::Void),
+        JavaType::Primitive(Primitive::Boolean) => ("booleanValue", "()Z"),
+        JavaType::Primitive(Primitive::Byte) => ("byteValue", "()B"),
+        JavaType::Primitive(Primitive::Char) => ("charValue", "()C"),
+        JavaType::Primitive(Primitive::Short) => ("shortValue", "()S"),
+        JavaType::Primitive(Primitive::Int) => ("intValue", "()I"),
+        JavaType::Primitive(Primitive::Long) => ("longValue", "()J"),
+        JavaType::Primitive(Primitive::Float) => ("floatValue", "()F"),
+        JavaType::Primitive(Primitive::Double) => ("doubleValue", "()D"),
+        _ => return Ok(JValue::Object(value)),
+    };
+
+    let unboxed = env.call_method(value, method, signature, &[])?;
+    env.delete_local_ref(value)?;
+    Ok(unboxed)
+}
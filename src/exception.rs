@@ -0,0 +1,153 @@
+//! Errors thrown as Java exceptions of specific classes.
+//!
+//! Safe exported methods returning a `Result<T, E>` throw an exception of the class of their `#[call_type]` when they return
+//! an `Err`. If `E` implements [`IntoJavaException`], usually with `#[derive(JavaException)]`, each error chooses its own
+//! class instead:
+//!
+//! ```ignore
+//! #[derive(Debug, JavaException)]
+//! pub enum RepoError {
+//!     #[java_exception("java.util.NoSuchElementException")]
+//!     NotFound,
+//!     #[java_exception("java.lang.SecurityException")]
+//!     PermissionDenied,
+//!     #[java_exception("java.io.IOException")]
+//!     Io(std::io::Error),
+//!     Corrupted,
+//! }
+//!
+//! pub extern "jni" fn load(key: String) -> Result<String, RepoError> { ... }
+//! ```
+//!
+//! `RepoError::NotFound` throws a `java.util.NoSuchElementException` from `load`, and `RepoError::Corrupted`, without an
+//! attribute, throws the exception of the method (`java.lang.RuntimeException` by default). The attribute can also be put on
+//! the enum, for its variants without one, or on a struct.
+//!
+//! The error type must implement [`Error`]: the message of the exception is its `Display` output, followed by those of its
+//! [`source`](Error::source)s, e.g. `I/O error: No such file or directory (os error 2)`. A `message` given in the `#[call_type]`
+//! attribute replaces it, like for other errors. Exception classes must have a constructor taking the message as a `String`:
+//! when it can't be called (or the class can't be found), a `java.lang.RuntimeException` is thrown instead, mentioning the class.
+//!
+//! Async methods and unchecked methods don't use the class of their errors.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+pub use robusta_codegen::JavaException;
+
+/// An error thrown as a Java exception of its own class by safe exported methods, see the [module documentation](self).
+/// Implemented by `#[derive(JavaException)]`.
+pub trait IntoJavaException: Error {
+    /// Class of the exception thrown for this error, with slashes, e.g. `java/util/NoSuchElementException`, or `None` to throw
+    /// the exception of the method.
+    fn exception_class(&self) -> Option<&'static str>;
+
+    /// Message of the exception: the `Display` output of the error, followed by those of its sources.
+    fn exception_message(&self) -> String {
+        let mut message = self.to_string();
+        let mut source = self.source();
+        while let Some(error) = source {
+            message.push_str(": ");
+            message.push_str(&error.to_string());
+            source = error.source();
+        }
+        message
+    }
+}
+
+/// The error returned by a safe exported method, with the class of its exception if it isn't the method's.
+#[derive(Debug)]
+struct MethodError {
+    class: Option<&'static str>,
+    message: String,
+}
+
+impl Display for MethodError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for MethodError {}
+
+/// An error returned by a safe exported method. Boxed by [`TypedError`] if its type implements [`IntoJavaException`], and by
+/// [`DisplayedError`] otherwise: the methods of both traits are called on an `&ErrorRef`, which only needs an autoref
+/// for `DisplayedError`, so that `TypedError` is used when it's implemented.
+#[doc(hidden)]
+pub struct ErrorRef<'a, E>(pub &'a E);
+
+#[doc(hidden)]
+pub trait TypedError {
+    fn to_method_error(&self) -> Box<dyn Error>;
+}
+
+impl<E: IntoJavaException> TypedError for ErrorRef<'_, E> {
+    fn to_method_error(&self) -> Box<dyn Error> {
+        Box::new(MethodError {
+            class: self.0.exception_class(),
+            message: self.0.exception_message(),
+        })
+    }
+}
+
+#[doc(hidden)]
+pub trait DisplayedError {
+    fn to_method_error(&self) -> Box<dyn Error>;
+}
+
+impl<E: Display> DisplayedError for &ErrorRef<'_, E> {
+    fn to_method_error(&self) -> Box<dyn Error> {
+        self.0.to_string().into()
+    }
+}
+
+/// Class of the exception to throw for `error`, if it's an error with a class of its own.
+#[doc(hidden)]
+pub fn exception_class_of(error: &(dyn Error + 'static)) -> Option<&'static str> {
+    error.downcast_ref::<MethodError>().and_then(|e| e.class)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct Unreadable(io::Error);
+
+    impl Display for Unreadable {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.write_str("can't read the repository")
+        }
+    }
+
+    impl Error for Unreadable {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    impl IntoJavaException for Unreadable {
+        fn exception_class(&self) -> Option<&'static str> {
+            Some("java/io/IOException")
+        }
+    }
+
+    #[test]
+    fn typed_errors_keep_their_class() {
+        let error = Unreadable(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+        let boxed = (&ErrorRef(&error)).to_method_error();
+
+        assert_eq!(exception_class_of(&*boxed), Some("java/io/IOException"));
+        assert_eq!(boxed.to_string(), "can't read the repository: no such file");
+    }
+
+    #[test]
+    fn other_errors_throw_the_exception_of_the_method() {
+        let boxed = (&ErrorRef(&"invalid key")).to_method_error();
+
+        assert_eq!(exception_class_of(&*boxed), None);
+        assert_eq!(boxed.to_string(), "invalid key");
+    }
+}
@@ -46,13 +46,27 @@
 //! Each method can optionally specify a `#[call_type]` attribute that will determine how conversions between Rust and Java types are performed.
 //! For more information about conversions and `#[call_type]`, check out the [convert](convert) module.
 //!
+//! The attribute can also be put on an `impl` block, or on the module after `#[bridge]`, to set the call type of the native
+//! methods inside it that don't have their own: the innermost attribute is used.
+//!
 //! In general, **all input and output types must implement proper conversion traits**
 //! (input types must implement `(Try)FromJavaValue` and output types must implement `(Try)IntoJavaValue`)
 //!
-//! Native methods can optionally accept a [`JNIEnv`] parameter as first parameter (after `self` if present).
+//! Native methods can optionally accept a [`JNIEnv`] (or `&JNIEnv`) parameter as first parameter (after `self` if present),
+//! to use the environment of the call directly, e.g. to throw an exception of their own. It isn't a parameter of the Java method.
+//! Static methods can likewise take the class they're called on, as a `JClass<'env>` parameter following the environment one
+//! (or first without it), e.g. to read its static fields. Instance methods can't, as JNI gives them their object instead.
 //!
 //! Methods are declared as standard Rust functions with public visibility and "jni" ABI, and are matched by name with Java methods.
-//! No special handling is needed.
+//! No special handling is needed. They can't be `const` (see [below](#async-methods) for `async`), and must be written in the `impl` block itself:
+//! `#[bridge]` runs before macros in the block are expanded, so methods generated by a macro invocation are reported as an error.
+//!
+//! Methods with a `self` receiver are bound to Java instance methods (`native`), and associated functions without one are bound
+//! to `static native` methods. An associated function can still take the struct (or `Self`) as a regular parameter:
+//! only a `self` receiver makes it an instance method. Static and instance methods can be mixed freely in the same `impl` block.
+//! The receiver is converted from the Java object like any other parameter. Structs without lifetime parameters, which own a
+//! copy of what they need from the object, can take `&self` and `&mut self` receivers as well as `self`: the struct is converted,
+//! then borrowed for the call.
 //!
 //! Example:
 //!
@@ -96,6 +110,230 @@
 //! # }
 //! ```
 //!
+//! ## Default parameter values
+//! Trailing parameters can be given a default value with a `#[default = "..."]` attribute.
+//! Such parameters are received as (boxed) Java objects, and the default value is used when Java passes `null`:
+//!
+//! ```ignore
+//! pub extern "jni" fn resize(self, width: i32, #[default = "100"] height: i32) -> i32 { ... }
+//! ```
+//!
+//! is called from Java as `resize(int width, Integer height)`. The default value is checked against the parameter type at compile time.
+//!
+//! ## Caching string parameters
+//! `Arc<str>` parameters annotated with `#[intern_cache(size = N)]` are received as Java `String`s and converted through a cache
+//! of the last `N` distinct values, which avoids repeated conversions of frequently passed strings (e.g. enum-like tags).
+//! See the [`InternCache`](convert::InternCache) documentation for details.
+//!
+//! ## Narrowing integers
+//! Integer parameters annotated with `#[truncate]` or `#[saturate]` are received as Java `int`s and narrowed (or widened back)
+//! without ever failing, while `#[reinterpret]` passes unsigned integers as the signed Java primitive of the same width, bit for bit.
+//! The same attributes on the method apply to its return value:
+//!
+//! ```ignore
+//! #[reinterpret]
+//! pub extern "jni" fn toggle_flags(#[reinterpret] flags: u32, #[saturate] mask: u64) -> u32 { ... }
+//! ```
+//!
+//! is called from Java as `int toggleFlags(int flags, int mask)`. See the [`numeric`](convert::numeric) module for the exact semantics of each attribute.
+//!
+//! ## Flattening parameters
+//! A parameter annotated with `#[flatten]` is received as a Java parameter for each field of its struct, in declaration order,
+//! and the struct is assembled from them before calling the method. The struct must be declared in the same module, with named
+//! fields whose types are convertible as usual:
+//!
+//! ```ignore
+//! pub struct Endpoint {
+//!     host: String,
+//!     port: i32,
+//! }
+//!
+//! pub extern "jni" fn connect(#[flatten] endpoint: Endpoint, timeout: i32) -> bool { ... }
+//! ```
+//!
+//! is called from Java as `connect(String endpoint_host, int endpoint_port, int timeout)`. Fields can't be flattened themselves.
+//!
+//! ## Converting with functions
+//! A parameter annotated with `#[convert(with = "path::to::function")]` is converted by that function instead of the conversion
+//! traits, e.g. to decode a Java `String` to bytes without a newtype. The function takes the JNI value and the environment, and
+//! returns the parameter, in a `Result` for safe methods: `fn(JString<'env>, JNIEnv<'env>) -> Result<Vec<u8>, E>`. Java passes
+//! the JNI type given by `source = "JString<'env>"`, or a `JObject` without it, which is converted to what the function takes with `Into`.
+//! On the method, `#[convert(into_with = "...", target = "...")]` likewise converts the return value (its `Ok` value in safe methods):
+//!
+//! ```ignore
+//! #[convert(into_with = "base64::to_java", target = "JString<'env>")]
+//! pub extern "jni" fn checksum(#[convert(with = "base64::from_java", source = "JString<'env>")] data: Vec<u8>) -> Vec<u8> { ... }
+//! ```
+//!
+//! is called from Java as `String checksum(String data)`. The lifetime of `source` and `target` types must be written, as `'env`
+//! or `'_`. Without `source` or `target`, the Java type is `Object`, unless a `#[sig]` attribute gives it (see below).
+//!
+//! ## Raw objects
+//! `JObject` parameters and return values are passed as is, for methods doing their own JNI calls on them. Their Java type is
+//! `Object`, unless a `#[sig("...")]` attribute gives the JNI descriptor of their actual class or array type, which is then
+//! used in the method's descriptor (for `RegisterNatives` and imported method calls) and Java stub:
+//!
+//! ```ignore
+//! #[sig("Lcom/example/Listener;")]
+//! pub extern "jni" fn wrap<'env>(env: &JNIEnv<'env>, #[sig("Lcom/example/Callback;")] callback: JObject<'env>) -> JniResult<JObject<'env>> { ... }
+//! ```
+//!
+//! is called from Java as `Listener wrap(Callback callback)`. On the method, the attribute applies to the return value.
+//! Imported methods accept it too, to call Java methods whose parameters aren't `Object`s.
+//!
+//! ## Lists
+//! `Vec` parameters of exported methods are declared as `java.util.List`, so Java callers can pass any list (an `ArrayList`, a
+//! `LinkedList`, `List.of(...)`, ...), while `Vec` return values are `ArrayList`s. `#[java_list]` declares a returned `Vec` as a
+//! `List` instead:
+//!
+//! ```ignore
+//! #[java_list]
+//! pub extern "jni" fn names(ids: Vec<i64>) -> Vec<String> { ... }
+//! ```
+//!
+//! is called from Java as `List<String> names(List<Long> ids)`. Imported methods accept it on parameters and return values too,
+//! to call Java methods declared with `List`s, which `ArrayList` descriptors wouldn't find.
+//!
+//! ## Java method names
+//! By default the Java method has the same name as the Rust one (native symbols are escaped as the JNI specification requires, so underscores are fine).
+//! A different Java name can be chosen with `#[java_name = "..."]`, leaving the Rust method name untouched for Rust callers:
+//!
+//! ```ignore
+//! #[java_name = "getUserName"]
+//! pub extern "jni" fn get_user_name(self) -> String { ... }
+//! ```
+//!
+//! Giving several exported methods of an impl block the same Java name declares Java overloads: their native symbols then use
+//! the long form of the JNI specification, which includes the argument types. Like for Java stubs, these are inferred from
+//! the syntax of the parameter types, and checked at compile time against the `Signature::SIG_TYPE` of each parameter. Overloads taking
+//! other types with a custom `Signature` fail that check: register them with `#[bridge(register_natives)]`, whose descriptors
+//! come from `SIG_TYPE` directly.
+//!
+//! `#[bridge(rename_all = "camelCase")]` gives every method of the module without a `#[java_name]` the camelCase version of
+//! its Rust name, in native symbols, Java stubs and calls to imported methods: `fn parse_json_url` becomes `parseJsonUrl`.
+//! Only the letter after each underscore is uppercased, so `to_HTML` becomes `toHTML`. Two exported methods ending up with the same
+//! Java name and parameters are reported as an error.
+//!
+//! ## Generic structs
+//! Native functions can't be generic, so native methods in an `impl` block with type parameters (such as
+//! `impl<'env, T: Default> Holder<'env, T>`) are called with each parameter replaced by its default in the struct declaration,
+//! or `()` if it has none. Their signature can't use the type parameters, directly or through `Self`: such methods belong in an
+//! `impl` block for a concrete type, like `impl<'env> Holder<'env, i32>`.
+//!
+//! Java classes can't be generic either, so all these methods belong to the same class. To bridge several instantiations of a struct,
+//! each to a Java class of its own, list them with `#[instantiate]`: each class is named after the struct, followed by a suffix.
+//!
+//! ```ignore
+//! #[package(com.example)]
+//! #[instantiate(OfString = Holder<String>, OfLong = Holder<i64>)]
+//! pub struct Holder<T> { ... }
+//!
+//! impl<T: Debug> Holder<T> {
+//!     pub extern "jni" fn describe(value: T) -> String { ... }
+//! }
+//!
+//! impl Holder<i64> {
+//!     pub extern "jni" fn twice(value: i64) -> i64 { ... }
+//! }
+//! ```
+//!
+//! declares `String describe(String)` in `com.example.HolderOfString`, and `String describe(long)` and `long twice(long)` in
+//! `com.example.HolderOfLong`. Methods of generic `impl` blocks are exported for each instantiation, and can use the type
+//! parameters and `Self`, which the instantiation determines. Imported methods call a single class, so they must be declared in
+//! an `impl` block for one instantiation, like `impl Holder<String>`. `#[derive(Signature)]` gives each instantiation the
+//! signature of its class.
+//!
+//! ## Synchronized methods
+//! Each call converts its receiver anew, so a `&mut self` borrow is only exclusive to the call: nothing keeps Java threads
+//! from calling methods on the same object at the same time, and state shared through the Java object (e.g. a
+//! [`Field`](convert::Field), or a handle to Rust data) can be read and written concurrently. Methods annotated with
+//! `#[synchronized]` hold the monitor of the object they're called on (or of the class, for static methods) for the whole call,
+//! like Java's `synchronized` methods: they never run concurrently with each other, nor with Java code synchronizing on the
+//! same object. The monitor is released when the method returns, throws or panics. `async` methods can't be `#[synchronized]`,
+//! and neither can methods taking `&mut self`: they take `mut self` by value instead.
+//!
+//! ## Deprecating native methods
+//! Native methods can be marked with Rust's own `#[deprecated]` attribute: the deprecation is kept on the Rust method,
+//! while the generated JNI function is left undeprecated so that it doesn't trigger the lint on your crate.
+//!
+//! Adding `#[deprecation_warning]` next to `#[deprecated]` makes the generated function print a warning to stderr
+//! (including the deprecation note) the first time the method is called from Java.
+//!
+//! ## Primitive methods
+//! Static native methods whose parameters and return type are all Java primitives with identity conversions (`i8`, `i16`,
+//! `i32`, `i64`, `f32`, `f64` or their `jni::sys` aliases, returning one of them or nothing), besides the environment and class
+//! parameters, are called directly: no conversion can fail, so the generated function passes the JNI values through and skips
+//! the conversion and exception handling code, which matters for very short methods called often, like
+//! `fn add(a: i64, b: i64) -> i64`. `bool` and `char` need a conversion, and `Result` returns the usual error handling.
+//!
+//! A panic in such a method still throws a `RuntimeException`, as in any safe method. With `#[call_type(unchecked)]` the
+//! panic isn't caught, leaving nothing between the JVM and the method: a panic then aborts the whole process, so only use it
+//! for methods that can't panic (e.g. with wrapping arithmetic).
+//!
+//! ## Async methods
+//! Native methods can be `async fn`s. The Java method returns a `CompletableFuture` right away, and the future of the
+//! method runs on an executor set with [`runtime::set_spawner`], completing the `CompletableFuture` with its result (or
+//! exceptionally with its error or panic). Such methods take owned parameters only (and `self` by value), since the future
+//! outlives the call. See the [`runtime`] module for details.
+//!
+//! ## Generating Java stubs
+//! If the `ROBUSTA_JAVA_OUTPUT_DIR` environment variable is set at compile time, `#[bridge]` writes a `.java` file for each bridged struct
+//! in that directory (following the package structure), with `native` declarations for all of its exported methods.
+//! The variable can be set for a single crate from its build script:
+//!
+//! ```ignore
+//! println!("cargo:rustc-env=ROBUSTA_JAVA_OUTPUT_DIR={}", output_dir.display());
+//! ```
+//!
+//! Java types are inferred from the Rust types as they're written in the method signatures: types that are neither library-provided conversions
+//! nor bridged structs of the same module are declared as `Object`.
+//! Parameters with a default value get additional overloads that omit them.
+//! Java classes needed by library-provided conversions used in the module (e.g. `robusta.audio.ShortSamples` for [`Samples<i16>`](audio::Samples))
+//! or by [`#[dispatch]`](dispatch) and [`#[suspend]`](coroutines) methods are written in the same directory.
+//!
+//! Kotlin declarations are written in the same way, as `.kt` files, if `ROBUSTA_KOTLIN_OUTPUT_DIR` is set. Exported methods are
+//! `external fun`s of an `open class` (or an `enum class`), static ones in its `companion object` with `@JvmStatic`. Types are
+//! the Kotlin counterparts of the Java types, e.g. `Int`, `IntArray` or `List<String>`, and `Option`s are nullable:
+//!
+//! ```kotlin
+//! open class Foo {
+//!     external fun rename(name: String?): Boolean
+//!
+//!     companion object {
+//!         @JvmStatic
+//!         external fun total(values: List<Long>): Long
+//!     }
+//! }
+//! ```
+//!
+//! The Java classes of library-provided conversions are only written with the Java stubs.
+//!
+//! ## Listing exported methods in a manifest
+//! If the `ROBUSTA_MANIFEST` environment variable is set at compile time, `#[bridge]` also writes a JSON manifest to the file it
+//! names, listing the class (e.g. `com/example/Foo`), Java name, descriptor and call type of every exported method, and whether it
+//! is `#[deprecated]`, with its `since` and `note`:
+//!
+//! ```json
+//! {
+//!   "methods": [
+//!     {
+//!       "class": "com/example/Foo",
+//!       "name": "total",
+//!       "descriptor": "(Ljava/util/List;)J",
+//!       "call_type": "safe",
+//!       "is_static": true,
+//!       "deprecated": true,
+//!       "since": "1.2",
+//!       "note": "use `sum` instead"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Descriptors are inferred like the types of Java stubs. Methods are sorted, and each module only replaces the entries of its
+//! own classes, so the file can be committed and diffed in CI. Entries of classes removed from the crate stay until the file
+//! is deleted. Deprecated methods are also annotated with `@Deprecated` in Java stubs.
+//!
 //! # Adding Java methods
 //! You can also declare Java methods and `robusta` will generate binding glue to convert types and call methods on the Java side.
 //! Again, **all input and output types must implement proper conversion traits**: in this case it's the reverse from the Java to Rust case
@@ -104,6 +342,8 @@
 //! Methods are declared as standard Rust functions with public visibility, a "java" ABI and an empty body, and are matched by name with Java methods.
 //! Both static and non-static methods must accept a [`JNIEnv`] parameter as first parameter (after self if present).
 //!
+//! Snake case method names are converted to camel case when looking up the Java method, unless a name is given with `#[java_name = "..."]`.
+//!
 //! Constructors can be declared via a `#[constructor]` attribute on static methods, and are matched by their type signature.
 //!
 //! When using `#[call_type(safe)]` or omitting `call_type` attribute, the output type **must** be [`jni::errors::Result<T>`](jni::errors::Result)
@@ -112,6 +352,10 @@
 //! **When using `#[call_type(unchecked)]` if a Java exception is thrown while calling a method a panic is raised.**
 //!
 //! ## Static methods
+//! Methods without a `self` receiver call the static method of the struct's class with `CallStaticMethod`, so structs can also
+//! bridge library classes such as `java.lang.Integer`. `#[static_method]` marks them explicitly, and rejects receivers.
+//! Static methods returning nothing return `JniResult<()>`, and like other imported methods, they can return the struct itself
+//! if it implements `TryFromJavaValue`.
 //!
 //! Example:
 //! ```rust
@@ -136,6 +380,7 @@
 //!     # }
 //!     #
 //! impl A {
+//!     #[static_method]
 //!     pub extern "java" fn staticJavaMethod(
 //!         env: JNIEnv,
 //!         i: i32,
@@ -190,6 +435,10 @@
 //!
 //! ## Constructors
 //!
+//! Static `extern "java"` methods annotated with `#[constructor]` create a new object of the struct's class: its constructor
+//! signature is derived from the parameter types. Constructors return the new object, either as `Self` or as a raw `JObject`
+//! (wrapped in a `Result` unless the method is `#[call_type(unchecked)]`).
+//!
 //! Example:
 //! ```rust
 //! # use robusta_jni::bridge;
@@ -221,6 +470,245 @@
 //! # }
 //! ```
 //!
+//! ## Builder methods
+//! `extern "java"` methods annotated with `#[returns_self]` call Java methods returning `this`, such as the setters of builders.
+//! They take `self` by value and return it as it is, with the object it already has, instead of converting the returned object
+//! to a new value. Their descriptor still returns the struct's class. Constructors and builder methods chain fluently:
+//!
+//! ```ignore
+//! impl<'env> RequestBuilder<'env> {
+//!     #[constructor]
+//!     pub extern "java" fn new(env: JNIEnv<'env>, url: String) -> JniResult<Self> {}
+//!
+//!     #[returns_self]
+//!     pub extern "java" fn header(self, env: JNIEnv<'env>, name: String, value: String) -> JniResult<Self> {}
+//!
+//!     pub extern "java" fn build(&self, env: JNIEnv<'env>) -> JniResult<Request<'env>> {}
+//! }
+//!
+//! let request = RequestBuilder::new(env, url)?.header(env, "Accept".into(), "text/plain".into())?.build(env)?;
+//! ```
+//!
+//! The receiver is passed to Java as a reference, so `&Self` must implement `(Try)IntoJavaValue`.
+//!
+//! ## Accessing Java fields
+//! `extern "java"` methods annotated with `#[field]` read or write a field instead of calling a method: methods taking only
+//! the environment (and a `self` receiver) are getters, and methods also taking a value are setters. The field descriptor
+//! is the [`Signature`] of the value type, and its name is the method name without a `get_` or `set_` prefix, in camel case,
+//! unless one is given with `#[field(name = "...")]`. Static fields are accessed by methods annotated with `#[field(static)]`,
+//! which have no `self` receiver.
+//!
+//! ```ignore
+//! impl<'env> Counter<'env> {
+//!     #[field]
+//!     pub extern "java" fn get_count(&self, env: JNIEnv<'env>) -> JniResult<i32> {}
+//!
+//!     #[field]
+//!     pub extern "java" fn set_count(&self, env: JNIEnv<'env>, value: i32) -> JniResult<()> {}
+//!
+//!     #[field(static, name = "DEFAULT_LABEL")]
+//!     pub extern "java" fn default_label(env: JNIEnv<'env>) -> JniResult<String> {}
+//! }
+//! ```
+//!
+//! ## Caching method lookups
+//! By default, every call of an `extern "java"` method looks up the Java class and method by name. Methods (and constructors)
+//! annotated with `#[cached]` only do so on their first call, and keep the class (as a global reference) and method ID in a
+//! `static` for all later calls, from any thread. This makes calls in hot paths noticeably cheaper
+//! (see `integration-tests/benches/imported_calls.rs`).
+//!
+//! The global reference keeps the class loaded for the rest of the process, as its method IDs would be invalid otherwise.
+//! See [`CachedMethod`](convert::CachedMethod) for details.
+//!
+//! ```ignore
+//! impl<'env> Greeter<'env> {
+//!     #[cached]
+//!     pub extern "java" fn greet(&self, env: JNIEnv<'env>, name: String) -> JniResult<String> {}
+//! }
+//! ```
+//!
+//! ## Calling methods on a GUI toolkit's thread
+//! `extern "java"` methods annotated with `#[dispatch(swing)]` or `#[dispatch(javafx)]` are called on the Swing event dispatch thread
+//! or the JavaFX application thread, while the calling thread waits for their result (or exception). Calls made from that thread
+//! are made directly. Constructors, field accessors and `#[cached]` methods can't be dispatched.
+//! See the [dispatch] module for details.
+//!
+//! ```ignore
+//! impl<'env> StatusBar<'env> {
+//!     #[dispatch(swing)]
+//!     pub extern "java" fn set_text(&self, env: JNIEnv<'env>, text: String) -> JniResult<()> {}
+//! }
+//! ```
+//!
+//! ## Calling Kotlin `suspend` functions
+//! `extern "java"` methods annotated with `#[suspend]` call Kotlin `suspend` functions, blocking the calling thread until they
+//! complete. They're declared with the parameters and return type of the Kotlin function, without its `Continuation`.
+//! `kotlinx-coroutines-core` must be on the class path. Constructors, field accessors, `#[cached]` and `#[dispatch]` methods
+//! can't be `#[suspend]`. See the [coroutines] module for details.
+//!
+//! ```ignore
+//! impl<'env> Repository<'env> {
+//!     // suspend fun fetch(url: String): String
+//!     #[suspend]
+//!     pub extern "java" fn fetch(&self, env: JNIEnv<'env>, url: String) -> JniResult<String> {}
+//! }
+//! ```
+//!
+//! Parameters of type `Option<T>` are passed as `null` for `None`, so they match Kotlin's nullable types.
+//!
+//! ## Checking imported methods
+//! A mismatch between an `extern "java"` declaration and its Java method is only reported when the method is called, as a
+//! `NoSuchMethodError`. Each `#[bridge]` module with imported methods has a `bridge_metadata()` function listing their
+//! classes, names and JNI descriptors, so that a single test can check all of them against the JVM.
+//! See the [metadata] module for details.
+//!
+//! With the `verify-bindings` feature, the generated code does this itself: each imported method is looked up before its first
+//! call, which panics with its class, name and descriptor if it doesn't exist, and the generated `JNI_OnLoad` looks up all of
+//! them. [`verify_bindings`] checks every imported method of the program at once. See the [verify] module.
+//!
+//! The same descriptors can be computed from Rust types when calling Java through `JNIEnv` directly, e.g.
+//! `method_descriptor::<(String, i32), ()>()` for `(Ljava/lang/String;I)V`, or as constants with the [`descriptor!`] macro,
+//! e.g. `descriptor!((String, i32) -> bool)` for `(Ljava/lang/String;I)Z`. See the [signature] module.
+//!
+//! ## Retrying transient failures
+//! `extern "java"` methods annotated with `#[retry(times = 3, on = "java.lang.IllegalStateException", backoff_ms = 10)]` are
+//! called again, up to `times` more times, when they throw an instance of one of the `on` classes. Other exceptions are never
+//! retried. Field accessors can't be retried. See the [retry] module for details.
+//!
+//! ```ignore
+//! impl<'env> Registry<'env> {
+//!     #[retry(times = 3, on = "java.lang.IllegalStateException", backoff_ms = 10)]
+//!     pub extern "java" fn lookup(env: &JNIEnv<'env>, name: String) -> JniResult<String> {}
+//! }
+//! ```
+//!
+//! ## Calling Java lambdas
+//! A parameter of type [`JavaCallback<'env, Args>`](convert::JavaCallback) receives a Java `Runnable`, `Consumer` or `BiConsumer`
+//! (for `Args` of `()`, `(A,)` or `(A, B)`), and its `call` method calls it with Rust values. An exception thrown by the
+//! callback is returned as an `Err`. Its `to_global` method gives a [`GlobalJavaCallback`](convert::GlobalJavaCallback), which
+//! can be called later, from any thread. See the [`functional`](convert::functional) module for details.
+//!
+//! ## Calling Java listeners from Rust threads
+//! When many Rust threads call the same Java method, a [`QueuedCallback`](callback::QueuedCallback) queues the calls
+//! and delivers them from a single attached thread. See the [callback] module for details.
+//!
+//! ## Calling Java from any thread
+//! Once the JVM is known (it's set by the generated `JNI_OnLoad`, or with [`vm::set_java_vm`]), [`vm::with_attached_env`] gives
+//! the environment of the current thread to a closure, attaching the thread as a daemon thread if needed. Static imported
+//! methods annotated with `#[attach]` take no `JNIEnv` parameter and do this themselves, so that they can be called from any
+//! Rust thread. See the [vm] module for details.
+//!
+//! ## Preloading classes
+//! Classes listed in `#[bridge(preload_classes("com.example.Config", ...))]` are resolved in order when the native library
+//! is loaded, so that their static initializers run then rather than when a conversion or an imported method first needs them.
+//! Loading the library fails with a `NoClassDefFoundError` naming the first class that can't be found.
+//! See the [preload] module for details, and for preloading from your own `JNI_OnLoad`.
+//!
+//! ## Registering native methods
+//! Exported methods are normally found by the JVM through the name of their symbol, which requires exporting a `Java_...` symbol
+//! for each of them. With `#[bridge(register_natives)]`, the module registers its exported methods with `RegisterNatives` from a
+//! generated `JNI_OnLoad` instead, and their functions aren't exported. See the [natives] module for details.
+//!
+//! `#[export_visibility(hidden)]` on a method or an `impl` block states that its functions must not be exported: it's only
+//! allowed in modules with `register_natives`, where it guards against the attribute being removed from the module later on.
+//! Conversely, a method annotated with Rust's `#[export_name = "..."]` exports its function under that symbol (and not as
+//! `Java_...`), even when it's registered, e.g. for native code calling it directly.
+//!
+//! ## Running code around exported methods
+//! With `#[bridge(middleware = "crate::RequestIds")]`, every exported method of the module runs between the `before` and `after`
+//! methods of the named type, which implements [`Middleware`](middleware::Middleware): useful to set up thread-locals or request
+//! contexts, or to log calls. `after` also runs when the method throws or panics. See the [middleware] module for details.
+//!
+//! Attributes of exported methods other than `#[bridge]`'s own, such as doc comments, `#[inline]` or `#[cfg(...)]`, are kept
+//! both on the Rust method and on the JNI function generated for it. Those listed in `#[bridge(wrapper_attributes(...))]` are
+//! only kept on the JNI function, which calls from Java go through:
+//!
+//! ```ignore
+//! #[bridge(wrapper_attributes(tracing::instrument))]
+//! mod jni {
+//!     impl Parser {
+//!         #[tracing::instrument(skip_all)]
+//!         pub extern "jni" fn parse(input: String) -> JniResult<i32> { ... }
+//!     }
+//! }
+//! ```
+//!
+//! ## Using robusta through another crate
+//! The code generated by `#[bridge]` names this crate as `::robusta_jni`, which doesn't resolve when it's renamed in `Cargo.toml`
+//! or only reachable through a facade crate re-exporting it. `#[bridge(crate = "my_facade::robusta")]` gives the path to use instead,
+//! for the paths generated in the module and for `::robusta_jni` paths written in it. Derived conversions still name `::robusta_jni`.
+//!
+//! ## Finding raw JNI calls
+//! Modules migrated to robusta one method at a time often keep direct [`JNIEnv`] calls, which bypass conversions and exception
+//! handling. With `#[bridge(lint_raw_jni)]`, calls to `JNIEnv` methods that have a robusta equivalent (`call_method`, `new_string`,
+//! `throw_new`, ...) are reported inside the module, each with a suggestion. Calls that should stay can be marked with `#[allow_raw_jni]`,
+//! on the expression, statement, function or `impl` block:
+//!
+//! ```ignore
+//! #[bridge(lint_raw_jni = "deny")]
+//! mod jni {
+//!     fn log(env: JNIEnv, message: JString) -> JniResult<()> {
+//!         #[allow_raw_jni]
+//!         env.call_static_method("com/example/Log", "info", "(Ljava/lang/String;)V", &[message.into()])?;
+//!         Ok(())
+//!     }
+//! }
+//! ```
+//!
+//! Like other warnings of `#[bridge]`, the lint's warnings are only shown by nightly compilers: `lint_raw_jni = "deny"` reports errors instead.
+//!
+//! ## Checking a module before migrating it
+//! `#[bridge(dry_run)]` reports everything `#[bridge]` would reject in the module (unsupported types, structs without a package,
+//! methods exported under the same name, ...) as warnings, on stable compilers too, without generating anything: the module is
+//! left as plain Rust, with the attributes of `#[bridge]` and the `extern "jni"` ABIs removed. Imported methods panic when called.
+//!
+//! Each problem is reported as the use of a deprecated `RejectedByBridge` struct, at the start of the rejected code:
+//!
+//! ```text
+//! warning: use of deprecated unit struct `jni::_::RejectedByBridge`: struct without required `package` attribute
+//! ```
+//!
+//! ## Checking descriptors against Java declarations
+//! A Java declaration that doesn't match the descriptor of its native method only fails when it's called, with an
+//! `UnsatisfiedLinkError` or a `NoSuchMethodError`. `#[bridge(debug_signatures)]` reports the descriptor of each exported method
+//! as a warning, on stable compilers too, so that it can be compared with the Java declaration at build time:
+//!
+//! ```text
+//! warning: use of deprecated unit struct `jni::_::BridgeSignature`: `Counter::add` is exported as static native method `com/example/Counter.add(IJ)J`
+//! ```
+//!
+//! Descriptors are inferred from the types as written, like the generated Java stubs. Parameters of types declared in the
+//! module without a `Signature` implementation are reported by `#[bridge]` itself, as are methods with more parameters than the
+//! 255 slots of a Java method.
+//!
+//! ## Splitting a module across files
+//! `#[bridge]` only sees the tokens of its module: out-of-line `mod shapes;` declarations can't be in its input, and files
+//! included with `include!` are read by rustc after the module is bridged. With `#[bridge(path = "src/jni")]`, which gives the
+//! directory of the module's file relative to the crate root, `include!("...")` items are expanded by `#[bridge]` itself,
+//! anywhere in the module, so that the structs and methods of their files are bridged like the rest of the module:
+//!
+//! ```ignore
+//! // src/jni/mod.rs
+//! #[bridge(path = "src/jni")]
+//! pub mod bridge {
+//!     pub mod shapes {
+//!         include!("shapes.rs");
+//!     }
+//!
+//!     pub mod canvas {
+//!         include!("canvas.rs"); // can `use super::shapes::Square;`
+//!     }
+//! }
+//! ```
+//!
+//! Like with rustc, `include!`s in an included file are relative to its own directory, and included files can't have inner attributes.
+//! Without `path`, `include!` items are left to rustc, and their items aren't bridged.
+//!
+//! ## `#![no_std]` crates
+//! Code generated by `#[bridge]` only names `core` items and items re-exported by this crate, so bridge modules
+//! can be declared in `#![no_std]` crates (with `extern crate alloc` for `String` and `Vec` conversions). This crate, like `jni`,
+//! still depends on `std`, which is linked into the final library.
+//!
 //! # Conversion details and special lifetimes
 //! The procedural macro handles two special lifetimes specially: `'env` and `'borrow`.
 //!
@@ -232,12 +720,46 @@
 //! ```
 //! This lifetime is always used as the lifetime parameter of `JNIEnv` instances.
 //!
+//! Java values received and returned by exported methods all live as long as the environment of the call: elided and `'_`
+//! lifetimes of their types (e.g. `JString<'_>`) are `'env`, and other named lifetimes, declared on the method or its `impl` block,
+//! are required to be the same as `'env`. `&str` and `&[u8]` parameters only borrow Java values until the method returns, so return
+//! types can't use their lifetimes. A lifetime hidden in a path (e.g. `JString` instead of `JString<'_>`) can't be mapped to `'env`:
+//! it's reported for the `jni` and `robusta_jni` types, and by rustc for the others.
+//!
 //! When using `*FromJavaValue` derive macros your structs will be required to have both `'env` and `'borrow`,
 //! with the same bounds as in the trait definition. For more information, see the relevant traits documentation.
 //!
+//! ## Data structs
+//! Structs without an `#[instance]` field are plain data: deriving the conversion traits converts them to and from new objects
+//! of their Java class, read with its getters and created with a constructor taking every field, in order.
+//!
+//! ```ignore
+//! #[derive(Signature, TryFromJavaValue, TryIntoJavaValue)]
+//! #[package(com.example)]
+//! pub struct User {
+//!     #[java_field(name = "userName")]
+//!     pub name: String,
+//!     pub age: i32,
+//! }
+//! ```
+//!
+//! is read with `String getUserName()` and `int getAge()`, and created with `new User(String, int)`: properties are named
+//! after the fields in `camelCase`, unless renamed with `#[java_field(name = "...")]`. With `#[bean(setters)]` on the struct, objects
+//! are created with the no-argument constructor of the class and filled with its setters instead, e.g. `void setAge(int)`.
+//! The class is the one of the struct's [`Signature`], and the descriptors of the accessors are those of the field types.
+//!
 //! ## Raising exceptions
-//! You can make a Rust native method raise a Java exception simply by returning a [`jni::errors::Result`] with an `Err` variant.
+//! You can make a Rust native method raise a Java exception simply by returning a [`jni::errors::Result`] (or any `Result<T, E>` with `E: Display`) with an `Err` variant.
 //! See the [`convert`] module documentation for more information.
+//! If a Java exception is already pending when the error is returned (e.g. one thrown by a Java method called during a conversion),
+//! that exception is raised instead.
+//!
+//! Error types implementing [`IntoJavaException`](exception::IntoJavaException), usually with `#[derive(JavaException)]`, choose
+//! the class of their exception, e.g. with `#[java_exception("java.util.NoSuchElementException")]` on an enum variant.
+//! See the [`exception`] module.
+//!
+//! A panic in a safe native method throws a `java.lang.RuntimeException` with the panic message instead of unwinding into the JVM.
+//! Unchecked methods only do so with `#[call_type(unchecked(catch_panics))]`, see the [`panic`] module.
 //!
 //! ## Library-provided conversions
 //!
@@ -251,20 +773,68 @@
 //! | f64                                                                                | double                            |
 //! | i64                                                                                | long                              |
 //! | i16                                                                                | short                             |
+//! | u32, u64, usize§                                                                   | long                              |
+//! | i128, u128, [BigIntegerBytes](convert::BigIntegerBytes)                            | BigInteger, values out of range of the Rust type raise an exception (see [`big_integer`](convert::big_integer)) |
 //! | String                                                                             | String                            |
+//! | &str (Rust to Java, and parameters of exported methods)                           | String                            |
+//! | Cow\<str\> (Rust to Java only)                                                    | String                            |
+//! | &[u8] (parameters of exported methods only)                                        | byte[]                            |
+//! | [CriticalBytes\<'env\>](convert::CriticalBytes) (last parameter of exported methods only) | byte[], without copying it (see [`critical`](convert::critical)) |
+//! | &[&str], &[String] (Rust to Java only)                                             | String[]                          |
+//! | Box\<[String]\>§§                                                                 | String[]                          |
+//! | Boxed\<T\> (for each primitive `T` above)¶                                          | Integer, Long, Double, ...        |
+//! | [JAtomicLong\<'env\>](convert::JAtomicLong)                                        | AtomicLong                        |
+//! | [Handle\<T\>](convert::Handle), [HandleRef\<'env, T\>](convert::HandleRef)          | long, a pointer to Rust state owned by Java (see [`handle`](convert::handle)) |
+//! | [DirectByteBuffer\<'env\>](convert::DirectByteBuffer), [DirectBytes](convert::DirectBytes) | direct ByteBuffer, without copying it (see [`buffer`](convert::buffer)) |
+//! | [JavaCallback\<'env, Args\>](convert::JavaCallback)                              | Runnable, Consumer, BiConsumer (see [Calling Java lambdas](#calling-java-lambdas)) |
+//! | Samples\<i16\>, Samples\<f32\>¤                                                    | ShortSamples, FloatSamples        |
+//! | Option\<T\>                                                                          | T, `null` for `None` (Integer, Long, ... for primitives) |
 //! | Vec\<T\>†                                                                          | ArrayList\<T\>                    |
+//! | Vec\<&str\> (Rust to Java only)                                                    | ArrayList\<String\>               |
+//! | JavaArray\<T\>‖                                                                   | T[]                               |
+//! | [EncodedString\<C\>](convert::EncodedString)‖‖                                 | byte[]                            |
+//! | (A, B), (A, B, C)††                                                                | Object[] (`kotlin.Pair`, `kotlin.Triple` with the `kotlin-tuples` feature) |
+//! | [ByJavaEquality\<'env\>](convert::ByJavaEquality), [ByJavaString](convert::ByJavaString) | Object, as keys of Rust maps (see [`equality`](convert::equality)) |
+//! | [Passthrough\<'env\>](convert::Passthrough), [PassthroughOwned](convert::PassthroughOwned) | Object, passed back to Java unchanged (see [`passthrough`](convert::passthrough)) |
+//! | [Global\<T\>](convert::Global), [Weak\<T\>](convert::Weak)                      | T, kept after the call by a global or weak reference (see [`global`](convert::global)) |
+//! | Enums deriving [ErrorCode](convert::ErrorCode)                                  | int, with their names in a generated class (see [`error_code`](convert::error_code)) |
+//! | Enums deriving [JavaEnum](convert::JavaEnum)                                    | The Java enum of the same name (see [`java_enum`](convert::java_enum)) |
+//! | [Adapt\<T, M\>](convert::Adapt)                                                  | T, mapped by `M` on the Rust side (see [`adapt`](convert::adapt)) |
+//! | [Duration](std::time::Duration)‡‡                                                 | java.time.Duration                |
+//! | [SystemTime](std::time::SystemTime), `chrono::DateTime<Utc>` with the `chrono` feature‡‡ | java.time.Instant          |
 //! | [jni::JObject<'env>](jni::objects::JObject)                                      ‡ | *(any Java object as input type)* |
 //! | [jni::jobject](jni::sys::jobject)                                                    | *(any Java object as output)*     |
 //!
-//! † Type parameter `T` must implement proper conversion types
+//! † Type parameter `T` must implement proper conversion types. Parameters of exported methods are `List<T>`, and accept any list (see [Lists](#lists)).
+//! Primitive elements are boxed, and objects such as bridged structs are added as they are: a `Vec<User>` is an `ArrayList<User>`
 //!
 //! ‡ The special `'env` lifetime **must** be used
 //!
+//! § Converted through [`convert::numeric::Widen`], so only as bare parameters, return values and fields, not inside `Vec`s or `Option`s.
+//! Values that don't fit in the destination type raise an `IllegalArgumentException` naming the value (or a panic with `#[call_type(unchecked)]`), see [Narrowing integers](#narrowing-integers) for alternatives
+//!
+//! ¶ See [`Boxed`](convert::Boxed): useful where Java APIs need wrapper objects, e.g. `Vec<Boxed<i64>>` for an `ArrayList<Long>`
+//!
+//! ¤ See the [`audio`] module: interleaved audio buffers with their channel count, as generated `robusta.audio` classes
+//!
+//! ‖ See [`JavaArray`](convert::JavaArray): primitive, `String`, `Boxed<T>` and `JObject` elements (or `Option`s of these), and types implementing [`ArrayElement`](convert::ArrayElement)
+//!
+//! ‖‖ See [`encoded`](convert::encoded): text encoded in the charset `C` by Java, e.g. `EncodedString<Latin1>` for ISO-8859-1 bytes
+//!
+//! †† See the [`tuple`](convert::tuple) module: elements are converted as usual, and boxed if they're primitives
+//!
+//! ‡‡ See the [`time`](convert::time) module: negative `java.time.Duration`s and values out of range of the destination type raise an exception
+//!
+//! §§ Copied element by element without the `ArrayList` and its method lookups of `Vec<String>`, which makes it the fastest way
+//! to pass many strings. `null` elements raise an exception (or a panic with `#[call_type(unchecked)]`): use `JavaArray<Option<String>>` for arrays that can have some
+//!
 //! ## Limitations
 //!
 //! Currently there are some limitations in the conversion mechanism:
-//!  * Boxed types are supported only through the opaque `JObject`/`jobject` types
 //!  * Automatic type conversion is limited to the table outlined above, though easily extendable if needed.
+//!  * Borrowed strings and slices can only be passed to Java: values coming from Java must be owned, e.g. `Vec<String>` instead of `&[&str]`.
+//!    The exception are `&str` and `&[u8]` parameters of exported methods, which borrow the Java value for the duration of the call (see [`convert::borrowed`](convert::borrowed)).
+//!    Exported methods can return a `&str` or a `Cow<str>` borrowed from their receiver, which is converted before the receiver is dropped.
 //!
 //! [`Signature`]: convert::Signature
 //! [`JNIEnv`]: jni::JNIEnv
@@ -272,8 +842,52 @@
 
 pub use robusta_codegen::bridge;
 
+pub mod audio;
+pub mod callback;
 pub mod convert;
+pub mod coroutines;
+pub mod debug;
+pub mod dispatch;
+pub mod exception;
+pub mod metadata;
+pub mod middleware;
+pub mod natives;
+pub mod panic;
+pub mod preload;
+pub mod retry;
+pub mod runtime;
+pub mod signature;
+pub mod verify;
+pub mod vm;
 
 pub use jni;
 
 pub use static_assertions::assert_type_eq_all;
+
+#[cfg(feature = "verify-bindings")]
+pub use verify::verify_bindings;
+
+/// `std` items used by generated code, so that it also builds in `#![no_std]` crates (which can't name `::std`).
+#[doc(hidden)]
+pub mod __private {
+    pub use std::boxed::Box;
+    pub use std::error::Error;
+    pub use std::string::{String, ToString};
+    pub use std::sync::Once;
+    pub use std::vec::Vec;
+    pub use std::{eprintln, format, println};
+
+    #[cfg(feature = "verify-bindings")]
+    pub use inventory;
+
+    pub use crate::__ref_site as ref_site;
+    pub use crate::__register_bindings as register_bindings;
+    pub use crate::__verify_binding as verify_binding;
+    pub use crate::convert::critical::debug_assert_outside_critical_region;
+    pub use crate::convert::error_code::{checked_from_code, java_name_of, unchecked_from_code};
+    pub use crate::convert::java_enum::{checked_from_java, checked_to_java, unchecked_from_java, unchecked_to_java};
+    pub use crate::convert::unchecked::owned_strings;
+    pub use crate::exception::{exception_class_of, DisplayedError, ErrorRef, TypedError};
+    pub use crate::middleware::call_with_middleware;
+    pub use crate::runtime::spawn_completable;
+}
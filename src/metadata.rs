@@ -0,0 +1,82 @@
+//! Descriptors of imported methods, for checking them against the JVM.
+//!
+//! A typo in the parameter types of an `extern "java"` method only shows up when the method is called, as a `NoSuchMethodError`.
+//! To catch these earlier, every `#[bridge]` module declaring imported methods gets a `bridge_metadata()` function, listing the
+//! class, name and JNI descriptor of each of them (field accessors aside). Descriptors are assembled at compile time from the
+//! [`Signature`](crate::convert::Signature) of each type, so they are exactly those used for calls.
+//!
+//! A single test can then check that every imported method of a module exists:
+//!
+//! ```ignore
+//! #[test]
+//! fn imported_methods_exist() {
+//!     let env = /* ... */;
+//!     let unresolved = robusta_jni::metadata::unresolved(&env, jni::bridge_metadata()).unwrap();
+//!     assert!(unresolved.is_empty(), "missing Java methods: {:?}", unresolved);
+//! }
+//! ```
+//!
+
+use std::fmt;
+
+use jni::errors::Result;
+use jni::JNIEnv;
+
+/// An imported method, as declared in a `#[bridge]` module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MethodMetadata {
+    /// Binary name of the class, e.g. `com/example/Foo`.
+    pub class: &'static str,
+    /// Name of the Java method, `<init>` for constructors.
+    pub name: &'static str,
+    /// Type signature of each parameter.
+    pub params: &'static [&'static str],
+    /// Type signature of the return type, `V` for constructors and methods returning nothing.
+    pub ret: &'static str,
+    pub is_static: bool,
+}
+
+impl MethodMetadata {
+    /// JNI method descriptor, e.g. `(ILjava/lang/String;)V`.
+    pub fn descriptor(&self) -> String {
+        crate::signature::descriptor(self.params, self.ret)
+    }
+
+    /// Whether the method exists. Missing classes and methods aren't errors (their exception is cleared), other failures are.
+    pub fn exists(&self, env: &JNIEnv) -> Result<bool> {
+        let descriptor = self.descriptor();
+        let id = if self.is_static {
+            env.get_static_method_id(self.class, self.name, descriptor.as_str()).map(|_| ())
+        } else {
+            env.get_method_id(self.class, self.name, descriptor.as_str()).map(|_| ())
+        };
+
+        match id {
+            Ok(()) => Ok(true),
+            Err(_) if env.exception_check()? => {
+                env.exception_clear()?;
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl fmt::Display for MethodMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.is_static { "static " } else { "" };
+        write!(f, "{}{}.{}{}", kind, self.class.replace('/', "."), self.name, self.descriptor())
+    }
+}
+
+/// Returns the methods that don't exist, in order.
+pub fn unresolved(env: &JNIEnv, methods: &[MethodMetadata]) -> Result<Vec<MethodMetadata>> {
+    let mut unresolved = Vec::new();
+    for method in methods {
+        if !method.exists(env)? {
+            unresolved.push(*method);
+        }
+    }
+
+    Ok(unresolved)
+}
@@ -0,0 +1,118 @@
+//! Code run around every exported method of a module, for `#[bridge(middleware = "...")]`.
+//!
+//! A middleware is a type implementing [`Middleware`] and [`Default`], named by path in the `#[bridge]` attribute of a module:
+//!
+//! ```ignore
+//! #[derive(Default)]
+//! pub struct RequestIds;
+//!
+//! impl Middleware for RequestIds {
+//!     fn before(&self, ctx: &CallCtx) {
+//!         REQUEST_ID.with(|id| id.set(next_request_id()));
+//!     }
+//!
+//!     fn after(&self, ctx: &CallCtx, outcome: &CallOutcome) {
+//!         REQUEST_ID.with(|id| id.set(0));
+//!     }
+//! }
+//!
+//! #[bridge(middleware = "crate::RequestIds")]
+//! mod jni {
+//!     // ...
+//! }
+//! ```
+//!
+//! The wrapper of each exported method of the module creates a middleware with `Default::default()`, calls
+//! [`before`](Middleware::before), converts the parameters and runs the method, then calls [`after`](Middleware::after) with the
+//! [`CallOutcome`]. `after` runs on every path out of the method: when a conversion fails or the method returns an error (an
+//! exception is then pending), and while unwinding from a panic, before the panic is thrown as a Java exception (or aborts the
+//! process, for unchecked methods that don't catch panics).
+//!
+//! A middleware is created for each call, so it should be cheap to create, usually a unit struct, with any shared state in
+//! statics or thread-locals. Modules without a middleware don't generate any of this.
+//!
+
+use jni::JNIEnv;
+
+/// Code run before and after every exported method of a module, see the [module documentation](self).
+pub trait Middleware {
+    /// Called before the parameters of the method are converted.
+    fn before(&self, _ctx: &CallCtx) {}
+
+    /// Called after the method has returned, thrown or panicked.
+    fn after(&self, _ctx: &CallCtx, _outcome: &CallOutcome) {}
+}
+
+/// The call to an exported method, given to a [`Middleware`].
+pub struct CallCtx<'a, 'env> {
+    class: &'static str,
+    method: &'static str,
+    env: &'a JNIEnv<'env>,
+}
+
+impl<'a, 'env> CallCtx<'a, 'env> {
+    /// Fully qualified name of the Java class of the method, e.g. `com.example.Foo`.
+    pub fn class(&self) -> &'static str {
+        self.class
+    }
+
+    /// Java name of the method.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The environment the method is called with.
+    pub fn env(&self) -> &'a JNIEnv<'env> {
+        self.env
+    }
+}
+
+/// How a call to an exported method ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CallOutcome {
+    /// The method returned, without a pending Java exception.
+    Returned,
+    /// The method returned with a pending Java exception, e.g. thrown for a failed conversion or an `Err` result.
+    Threw,
+    /// The method panicked.
+    Panicked,
+}
+
+/// Calls `after` when dropped, with `Panicked` unless the call got to set another outcome.
+struct After<'m, 'c, 'a, 'env, M: Middleware> {
+    middleware: &'m M,
+    ctx: &'c CallCtx<'a, 'env>,
+    outcome: CallOutcome,
+}
+
+impl<'m, 'c, 'a, 'env, M: Middleware> Drop for After<'m, 'c, 'a, 'env, M> {
+    fn drop(&mut self) {
+        self.middleware.after(self.ctx, &self.outcome);
+    }
+}
+
+/// Runs the body `f` of the exported method `method` of `class` between the calls to a new `M`. Used by the generated code.
+#[doc(hidden)]
+pub fn call_with_middleware<'env, M, T>(env: &JNIEnv<'env>, class: &'static str, method: &'static str, f: impl FnOnce() -> T) -> T
+where
+    M: Middleware + Default,
+{
+    let middleware = M::default();
+    let ctx = CallCtx { class, method, env };
+    middleware.before(&ctx);
+
+    let mut after = After {
+        middleware: &middleware,
+        ctx: &ctx,
+        outcome: CallOutcome::Panicked,
+    };
+    let result = f();
+    after.outcome = if env.exception_check().unwrap_or(false) {
+        CallOutcome::Threw
+    } else {
+        CallOutcome::Returned
+    };
+
+    result
+}
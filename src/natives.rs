@@ -0,0 +1,63 @@
+//! Registration of exported methods with `RegisterNatives`, for `#[bridge(register_natives)]`.
+//!
+//! By default, the JVM finds each exported method through its `Java_...` symbol, named after its class, name and (for overloads)
+//! parameters. These symbols must be exported by the library, and stop matching when a code shrinker renames the Java classes.
+//! With `register_natives`, the exported methods of the module are registered with their classes when the library is loaded instead:
+//!
+//! ```ignore
+//! #[bridge(register_natives)]
+//! mod jni {
+//!     // ...
+//! }
+//! ```
+//!
+//! The module then has a `register_natives(env)` function registering its methods, called from a generated `JNI_OnLoad`
+//! (which also preloads the classes of [`preload_classes`](crate::preload), if any), and the functions of its exported methods
+//! are private and unmangled Rust functions. The JNI descriptor of each method is assembled from the
+//! [`Signature`](crate::convert::Signature) of its parameter and return types.
+//!
+//! Since a library can only have one `JNI_OnLoad`, at most one module of a library can use `register_natives`, and it can't
+//! define a `JNI_OnLoad` itself. If a class or method can't be found, `System.loadLibrary` throws the `NoClassDefFoundError`
+//! or `NoSuchMethodError` raised by the registration.
+//!
+
+use std::ffi::c_void;
+
+use jni::errors::Result;
+use jni::JNIEnv;
+
+use crate::metadata::MethodMetadata;
+
+/// An exported method, and the function implementing it.
+#[derive(Clone, Copy, Debug)]
+pub struct NativeMethod {
+    pub method: MethodMetadata,
+    /// The generated `extern "system"` function.
+    pub fn_ptr: *mut c_void,
+}
+
+/// Registers `methods` with their classes, with a `RegisterNatives` call for each class, in order of first appearance.
+pub fn register_natives(env: &JNIEnv, methods: &[NativeMethod]) -> Result<()> {
+    let mut classes: Vec<&str> = Vec::new();
+    for native in methods {
+        if !classes.contains(&native.method.class) {
+            classes.push(native.method.class);
+        }
+    }
+
+    for class in classes {
+        let class_methods: Vec<_> = methods
+            .iter()
+            .filter(|native| native.method.class == class)
+            .map(|native| jni::NativeMethod {
+                name: native.method.name.into(),
+                sig: native.method.descriptor().into(),
+                fn_ptr: native.fn_ptr,
+            })
+            .collect();
+
+        env.register_native_methods(class, &class_methods)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Panics in exported methods.
+//!
+//! A panic unwinding out of an exported method would cross the `extern "system"` boundary into the JVM, which is undefined
+//! behavior (and in practice aborts the process). Instead, the generated wrappers of safe methods run the method through
+//! [`catch_panic`]: a panic throws a `java.lang.RuntimeException` whose message is the panic message, and the method returns
+//! a zeroed value (`0`, `false` or `null`), which the JVM ignores since an exception is pending.
+//!
+//! Methods with `#[call_type(unchecked)]` panic on conversion errors, and only catch panics when asked to:
+//!
+//! ```ignore
+//! #[call_type(unchecked(catch_panics))]
+//! pub extern "jni" fn parse(value: String) -> i32 {
+//!     value.parse().unwrap()
+//! }
+//! ```
+//!
+//! The panic hook still runs as usual, so panics are reported on standard error as well.
+//!
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+use jni::errors::Result;
+use jni::objects::{JThrowable, JValue};
+use jni::JNIEnv;
+
+/// Message of a panic payload: the `&str` or `String` given to `panic!`, or a generic message for other payloads.
+pub fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&'static str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "native method panicked"
+    }
+}
+
+/// Throws a `java.lang.RuntimeException` with `message`. A Java exception that was already pending (e.g. the one a
+/// failed JNI call left behind before an `unwrap()`) becomes its cause.
+pub fn throw_panic(env: JNIEnv, message: &str) -> Result<()> {
+    if !env.exception_check()? {
+        return env.throw_new("java/lang/RuntimeException", message);
+    }
+
+    let cause = env.exception_occurred()?;
+    env.exception_clear()?;
+
+    let message = env.new_string(message)?;
+    let exception = env.new_object(
+        "java/lang/RuntimeException",
+        "(Ljava/lang/String;Ljava/lang/Throwable;)V",
+        &[JValue::Object(message.into()), JValue::Object(cause.into())],
+    )?;
+    env.throw(JThrowable::from(exception))
+}
+
+/// Runs `f`, returning its result, or throwing a `java.lang.RuntimeException` (see [`throw_panic`]) and returning `None` if it panics.
+pub fn catch_panic<T>(env: JNIEnv, f: impl FnOnce() -> T) -> Option<T> {
+    // Nothing observes the state `f` leaves behind after a panic: the exported method returns right away
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => Some(result),
+        Err(payload) => {
+            if let Err(e) = throw_panic(env, panic_message(&*payload)) {
+                println!("Error while throwing Java exception: {}", e);
+            }
+
+            None
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Eager class resolution, for `#[bridge(preload_classes(...))]`.
+//!
+//! Looking up a class through JNI runs its static initializer the first time, so when a class is first used by a conversion
+//! or an imported method, its initializer runs on whichever thread happens to need it. Listing classes in `preload_classes`
+//! resolves them (and runs their initializers) when the native library is loaded instead, in the given order:
+//!
+//! ```ignore
+//! #[bridge(preload_classes("com.example.Config", "com.example.Registry"))]
+//! mod jni {
+//!     // ...
+//! }
+//! ```
+//!
+//! The module then exports a `JNI_OnLoad` function doing the preloading, which makes `System.loadLibrary` throw a
+//! `NoClassDefFoundError` naming the first class that can't be resolved. Since a library can only have one `JNI_OnLoad`,
+//! at most one module of a library can use it: other modules (or libraries that already define their own `JNI_OnLoad`)
+//! can add `manual_preload`, and call the `preload(env)` function generated in the module themselves:
+//!
+//! ```ignore
+//! #[bridge(preload_classes("com.example.Config"), manual_preload)]
+//! mod bindings {
+//!     // ...
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "system" fn JNI_OnLoad(vm: *mut robusta_jni::jni::sys::JavaVM, _reserved: *mut c_void) -> robusta_jni::jni::sys::jint {
+//!     unsafe { robusta_jni::preload::on_load(vm, bindings::preload) }
+//! }
+//! ```
+//!
+//! Besides the listed classes, preloading resolves the exception classes configured with `#[call_type(safe(exception_class = ...))]`
+//! in the module, and the classes used by library-provided conversions (see [`CONVERSION_CLASSES`]).
+//!
+//! Preloaded classes are kept in a global cache for the lifetime of the process, see [`preloaded_class`].
+//!
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use jni::errors::Result;
+use jni::objects::{GlobalRef, JThrowable, JValue};
+use jni::sys::{jint, JNI_ERR, JNI_VERSION_1_8};
+use jni::{JNIEnv, JavaVM};
+
+use crate::debug::Tracked;
+
+/// Classes used by library-provided conversions, preloaded after the classes listed in `preload_classes`.
+pub const CONVERSION_CLASSES: &[&str] = &["java/lang/String", "java/util/ArrayList", "java/lang/RuntimeException"];
+
+static PRELOADED: OnceLock<Mutex<HashMap<String, (GlobalRef, Tracked)>>> = OnceLock::new();
+
+fn preloaded() -> &'static Mutex<HashMap<String, (GlobalRef, Tracked)>> {
+    PRELOADED.get_or_init(Default::default)
+}
+
+/// Returns a preloaded class, given its binary name (e.g. `com/example/Foo`).
+pub fn preloaded_class(name: &str) -> Option<GlobalRef> {
+    preloaded().lock().unwrap().get(name).map(|(class, _)| class.clone())
+}
+
+/// Resolves each class in `classes` (given by binary name, e.g. `com/example/Foo`) in order, then [`CONVERSION_CLASSES`].
+///
+/// If a class can't be resolved, a `NoClassDefFoundError` naming it is left pending (with the original exception as its cause),
+/// and the following classes aren't resolved.
+pub fn preload_classes(env: JNIEnv, classes: &[&str]) -> Result<()> {
+    for &class in classes.iter().chain(CONVERSION_CLASSES) {
+        if preloaded_class(class).is_some() {
+            continue;
+        }
+
+        let local_class = match env.find_class(class) {
+            Ok(local_class) => local_class,
+            Err(e) => {
+                if env.exception_check()? {
+                    rethrow_preload_error(env, class)?;
+                }
+                return Err(e);
+            }
+        };
+
+        let global_class = env.new_global_ref(local_class)?;
+        env.delete_local_ref(local_class.into())?;
+        preloaded().lock().unwrap().insert(class.to_string(), (global_class, Tracked::global("preload")));
+    }
+
+    Ok(())
+}
+
+/// Replaces the pending exception with a `NoClassDefFoundError` naming `class`, caused by the original exception.
+fn rethrow_preload_error(env: JNIEnv, class: &str) -> Result<()> {
+    let cause = env.exception_occurred()?;
+    env.exception_clear()?;
+
+    let message = env.new_string(format!("can't preload class `{}`", class.replace('/', ".")))?;
+    let error = env.new_object("java/lang/NoClassDefFoundError", "(Ljava/lang/String;)V", &[JValue::from(message)])?;
+    env.call_method(error, "initCause", "(Ljava/lang/Throwable;)Ljava/lang/Throwable;", &[JValue::from(cause)])?;
+
+    env.throw(JThrowable::from(error))
+}
+
+/// Implementation of the `JNI_OnLoad` function generated by `#[bridge(preload_classes(...))]`, calling `preload`.
+///
+/// With `#[bridge(register_natives)]`, `preload` also registers the native methods (see [`natives`](crate::natives)).
+/// With the `verify-bindings` feature, the imported methods of the library are then looked up (see [`verify`](crate::verify)).
+/// When it fails, its pending exception is thrown by `System.loadLibrary`. The JVM is also kept for
+/// [`vm::with_attached_env`](crate::vm::with_attached_env).
+///
+/// # Safety
+///
+/// `vm` must be the pointer given to `JNI_OnLoad` by the JVM.
+pub unsafe fn on_load(vm: *mut jni::sys::JavaVM, preload: impl FnOnce(&JNIEnv) -> Result<()>) -> jint {
+    let vm = match JavaVM::from_raw(vm) {
+        Ok(vm) => vm,
+        Err(_) => return JNI_ERR,
+    };
+    let env = match vm.get_env() {
+        Ok(env) => env,
+        Err(_) => return JNI_ERR,
+    };
+    if let Ok(vm) = env.get_java_vm() {
+        crate::vm::set_java_vm(vm);
+    }
+
+    // With `verify-bindings`, the imported methods of the library are looked up once the classes are loaded
+    #[cfg(feature = "verify-bindings")]
+    let preload = |env: &JNIEnv| preload(env).and_then(|()| crate::verify::verify_on_load(env));
+
+    match preload(&env) {
+        Ok(()) => JNI_VERSION_1_8,
+        // The JVM fails loading the library with the pending exception, if any
+        Err(_) if env.exception_check().unwrap_or(false) => JNI_VERSION_1_8,
+        Err(_) => JNI_ERR,
+    }
+}
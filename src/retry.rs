@@ -0,0 +1,84 @@
+//! Retrying imported methods that fail with transient exceptions.
+//!
+//! Some calls into Java fail for reasons that go away on their own, e.g. a thread interrupted at the wrong time, or a class
+//! loader still being set up during warmup. An `extern "java"` method annotated with `#[retry]` is called again when it throws one
+//! of the listed exceptions:
+//!
+//! ```ignore
+//! #[retry(times = 3, on = "java.lang.IllegalStateException", backoff_ms = 10)]
+//! pub extern "java" fn fetch(env: &JNIEnv<'env>, key: String) -> JniResult<String> {}
+//! ```
+//!
+//! is called up to 4 times (the first call and 3 retries), waiting 10 milliseconds before each retry, as long as it throws an
+//! `IllegalStateException` (or a subclass). `on` can be given several times to retry on several exception classes, and
+//! `backoff_ms` defaults to 0. Exceptions of other classes are never retried: they're left pending, and the method returns
+//! `Err(Error::JavaException)` (or panics with `#[call_type(unchecked)]`) right away, as without `#[retry]`. When the last retry
+//! fails, its exception is left pending in the same way.
+//!
+//! Parameters are converted once, before the first call, and the result is converted after the last one. The exception of each
+//! retried call is cleared before waiting. An exception class that can't be found is treated as having no instances.
+//!
+//! [`RetryPolicy`] can also be used directly, around calls made through `JNIEnv`.
+//!
+
+use std::thread;
+use std::time::Duration;
+
+use jni::errors::{Error, Result};
+use jni::JNIEnv;
+
+/// How often, and on which exceptions, a call is retried. See the [module documentation](self).
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Number of retries after the first call.
+    pub times: u32,
+    /// Binary names of the exception classes retried on, e.g. `java/lang/IllegalStateException`.
+    pub on: &'static [&'static str],
+    /// Time waited before each retry.
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Calls `call` until it succeeds, fails with an exception that isn't retried on, or has been retried `times` times.
+    /// The exception of the last call, if any, is left pending.
+    pub fn call<T>(&self, env: &JNIEnv, mut call: impl FnMut() -> Result<T>) -> Result<T> {
+        let mut retries = 0;
+        loop {
+            match call() {
+                Err(Error::JavaException) if retries < self.times && self.clear_retried(env)? => {
+                    retries += 1;
+                    thread::sleep(self.backoff);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Whether the pending exception is an instance of a class retried on, clearing it if so.
+    fn clear_retried(&self, env: &JNIEnv) -> Result<bool> {
+        let exception = env.exception_occurred()?;
+        if exception.is_null() {
+            return Ok(false);
+        }
+
+        // `IsInstanceOf` can't be called with a pending exception
+        env.exception_clear()?;
+        let mut retried = false;
+        for class in self.on {
+            match env.is_instance_of(exception, *class) {
+                Ok(true) => {
+                    retried = true;
+                    break;
+                }
+                Ok(false) => {}
+                Err(_) => env.exception_clear()?,
+            }
+        }
+
+        if !retried {
+            env.throw(exception)?;
+        }
+        env.delete_local_ref(exception.into())?;
+        Ok(retried)
+    }
+}
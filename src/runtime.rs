@@ -0,0 +1,174 @@
+//! Running `async` exported methods.
+//!
+//! An exported method can be an `async fn`. Its Java method returns a `java.util.concurrent.CompletableFuture` right away,
+//! without blocking the calling Java thread, and the future of the method runs on an executor provided by the application:
+//!
+//! ```ignore
+//! impl Indexer {
+//!     pub async extern "jni" fn index(path: String) -> Result<i64, IndexError> {
+//!         let files = walk(&path).await?;
+//!         Ok(files.len() as i64)
+//!     }
+//! }
+//!
+//! // Once, before the first call (e.g. from `JNI_OnLoad` or an exported `init` method)
+//! robusta_jni::runtime::set_spawner(|future| {
+//!     tokio_runtime().spawn(future);
+//! });
+//! ```
+//!
+//! is called from Java as `CompletableFuture<Long> index(String path)`. Parameters are converted on the calling thread, and
+//! the future is handed to the [spawner](set_spawner). When it completes, the thread it completes on is attached to the JVM
+//! (unless it already is), and the `CompletableFuture` is completed with the converted value (boxed for primitives, and `null`
+//! for `()`). An `Err` result, a failed conversion or a panic completes it exceptionally instead, with a
+//! `java.lang.RuntimeException` (or the `exception_class` of `#[call_type(safe(...))]`) whose message is the error, or with the
+//! exception thrown by the conversion.
+//!
+//! The future must be `Send + 'static`, so `async` methods can't borrow anything from the call: they take owned parameters
+//! (`String` rather than `&str`, no `JNIEnv` or `JObject`), and `self` rather than `&self`. Calling one before a spawner is
+//! set throws a `java.lang.IllegalStateException`.
+//!
+//! Attaching a thread to the JVM is costly, so executors whose threads complete many futures should attach them once, e.g.
+//! with `JavaVM::attach_current_thread_permanently` from the thread start hook of the executor.
+//!
+
+use std::fmt::Display;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{PoisonError, RwLock};
+use std::task::{Context, Poll};
+use std::thread;
+
+use jni::errors::{Error, Result};
+use jni::objects::{GlobalRef, JObject, JThrowable, JValue};
+use jni::{JNIEnv, JavaVM};
+
+use crate::convert::{JavaValue, TryIntoJavaValue};
+use crate::panic::panic_message;
+
+const COMPLETABLE_FUTURE_CLASS: &str = "java/util/concurrent/CompletableFuture";
+
+// Local references created while completing a future are released as soon as it's completed.
+const LOCAL_FRAME_CAPACITY: i32 = 16;
+
+/// A future spawned by an `async` exported method, which completes its `CompletableFuture` when done.
+pub type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+type Spawner = Box<dyn Fn(BoxFuture) + Send + Sync>;
+
+static SPAWNER: RwLock<Option<Spawner>> = RwLock::new(None);
+
+/// Sets the function running the futures of `async` exported methods, e.g. spawning them on a Tokio runtime. Replaces the
+/// previous one, if any, for later calls.
+pub fn set_spawner(spawner: impl Fn(BoxFuture) + Send + Sync + 'static) {
+    *SPAWNER.write().unwrap_or_else(PoisonError::into_inner) = Some(Box::new(spawner));
+}
+
+/// Polls a future, catching the panics it raises.
+struct CatchUnwind<F>(Pin<Box<F>>);
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = thread::Result<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // The future isn't polled again after panicking
+        match panic::catch_unwind(AssertUnwindSafe(|| self.0.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+/// Returns a new `CompletableFuture`, completed by `future` once spawned. `Err` results complete it exceptionally, with an
+/// `exception_class` whose message is `message` or the error. Used by the generated code.
+#[doc(hidden)]
+pub fn spawn_completable<'env, F, T, E>(env: JNIEnv<'env>, exception_class: &'static str, message: Option<&'static str>, future: F) -> Result<JObject<'env>>
+where
+    F: Future<Output = std::result::Result<T, E>> + Send + 'static,
+    T: for<'a> TryIntoJavaValue<'a>,
+    E: Display,
+{
+    let spawner = SPAWNER.read().unwrap_or_else(PoisonError::into_inner);
+    let spawner = match spawner.as_ref() {
+        Some(spawner) => spawner,
+        None => {
+            env.throw_new(
+                "java/lang/IllegalStateException",
+                "no spawner for `async` native methods, set one with `robusta_jni::runtime::set_spawner`",
+            )?;
+            return Err(Error::JavaException);
+        }
+    };
+
+    let completable = env.new_object(COMPLETABLE_FUTURE_CLASS, "()V", &[])?;
+    let target = env.new_global_ref(completable)?;
+    let vm = env.get_java_vm()?;
+    let future = CatchUnwind(Box::pin(future));
+    spawner(Box::pin(async move {
+        let output = future.await.map_err(|payload| panic_message(&*payload).to_string()).and_then(|result| {
+            result.map_err(|e| message.map_or_else(|| e.to_string(), String::from))
+        });
+        complete(&vm, &target, exception_class, output);
+    }));
+
+    Ok(completable)
+}
+
+/// Completes `target` with `output`, from the thread the future completed on.
+fn complete<T: for<'a> TryIntoJavaValue<'a>>(vm: &JavaVM, target: &GlobalRef, exception_class: &str, output: std::result::Result<T, String>) {
+    let env = match vm.attach_current_thread() {
+        Ok(env) => env,
+        Err(e) => {
+            println!("Error while attaching thread to complete a Java future: {}", e);
+            return;
+        }
+    };
+
+    let completed = env.with_local_frame(LOCAL_FRAME_CAPACITY, || {
+        match output {
+            Ok(value) => {
+                let value = TryIntoJavaValue::try_into(value, *env)?.autobox(*env);
+                env.call_method(target.as_obj(), "complete", "(Ljava/lang/Object;)Z", &[JValue::Object(value)])?;
+            }
+            Err(message) => {
+                let message = env.new_string(message)?;
+                let exception = env.new_object(exception_class, "(Ljava/lang/String;)V", &[JValue::Object(message.into())])?;
+                complete_exceptionally(&env, target, exception)?;
+            }
+        }
+        Ok(JObject::null())
+    });
+
+    if let Err(e) = completed {
+        // The conversion (or the configured exception class) failed: complete with what went wrong, rather than never
+        let result = env.with_local_frame(LOCAL_FRAME_CAPACITY, || {
+            let exception = if env.exception_check()? {
+                let exception = env.exception_occurred()?;
+                env.exception_clear()?;
+                exception.into()
+            } else {
+                let message = env.new_string(e.to_string())?;
+                env.new_object("java/lang/RuntimeException", "(Ljava/lang/String;)V", &[JValue::Object(message.into())])?
+            };
+            complete_exceptionally(&env, target, exception)?;
+            Ok(JObject::null())
+        });
+
+        if let Err(e) = result {
+            let _ = env.exception_clear();
+            println!("Error while completing a Java future: {}", e);
+        }
+    }
+}
+
+fn complete_exceptionally(env: &JNIEnv, target: &GlobalRef, exception: JObject) -> Result<()> {
+    env.call_method(
+        target.as_obj(),
+        "completeExceptionally",
+        "(Ljava/lang/Throwable;)Z",
+        &[JValue::Object(JThrowable::from(exception).into())],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,283 @@
+//! JNI descriptors of Rust types.
+//!
+//! Descriptors are assembled from the [`Signature`] of each type, as for the methods of `#[bridge]` modules, so code calling
+//! Java methods through `JNIEnv` directly doesn't have to spell them out:
+//!
+//! ```
+//! use robusta_jni::signature::{field_descriptor, method_descriptor};
+//!
+//! assert_eq!(method_descriptor::<(String, i32), ()>(), "(Ljava/lang/String;I)V");
+//! assert_eq!(method_descriptor::<(), Vec<i64>>(), "()Ljava/util/ArrayList;");
+//!
+//! const COUNT: &str = field_descriptor::<i64>();
+//! assert_eq!(COUNT, "J");
+//! ```
+//!
+//! Parameter types are given as a tuple (`()` for no parameters, `(T,)` for one), and a `()` return type is `void`.
+//! Type arguments are erased as by the Java compiler, e.g. a `Vec<Vec<i32>>` is an `ArrayList`.
+//!
+//! Field descriptors and the descriptors of parameter lists ([`Params::SIG_TYPES`]) are constants, [`method_descriptor`]
+//! assembles method descriptors at runtime, and the [`descriptor!`](crate::descriptor) macro at compile time, for build
+//! scripts and `RegisterNatives` tables:
+//!
+//! ```
+//! use robusta_jni::descriptor;
+//!
+//! const IS_VALID: &str = descriptor!((String, i32) -> bool);
+//! assert_eq!(IS_VALID, "(Ljava/lang/String;I)Z");
+//! assert_eq!(descriptor!(()), "()V");
+//! ```
+//!
+//! Like [`Signature`]s, these descriptors have `ArrayList`s for `Vec`s: exported methods declare their `Vec` parameters as
+//! `List`s instead (see [`parameter_signature`]).
+//!
+
+use crate::convert::Signature;
+
+/// Parameter types of a method, implemented for tuples of up to 12 types implementing [`Signature`].
+pub trait Params {
+    /// Type signature of each parameter, in order.
+    const SIG_TYPES: &'static [&'static str];
+}
+
+macro_rules! params {
+    ($($t:ident),*) => {
+        impl<$($t: Signature),*> Params for ($($t,)*) {
+            const SIG_TYPES: &'static [&'static str] = &[$(<$t as Signature>::SIG_TYPE),*];
+        }
+    };
+}
+
+params!();
+params!(A);
+params!(A, B);
+params!(A, B, C);
+params!(A, B, C, D);
+params!(A, B, C, D, E);
+params!(A, B, C, D, E, F);
+params!(A, B, C, D, E, F, G);
+params!(A, B, C, D, E, F, G, H);
+params!(A, B, C, D, E, F, G, H, I);
+params!(A, B, C, D, E, F, G, H, I, J);
+params!(A, B, C, D, E, F, G, H, I, J, K);
+params!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Descriptor of a field of type `T`, e.g. `I` or `Ljava/lang/String;`.
+pub const fn field_descriptor<T: Signature + ?Sized>() -> &'static str {
+    T::SIG_TYPE
+}
+
+/// Descriptor of a method taking `Args` (a tuple) and returning `Ret`, e.g. `(Ljava/lang/String;I)V`.
+pub fn method_descriptor<Args: Params, Ret: Signature + ?Sized>() -> String {
+    descriptor(Args::SIG_TYPES, Ret::SIG_TYPE)
+}
+
+/// Descriptor of a method with parameters of type signatures `params`, returning a value of type signature `ret`
+/// (`V` for `void`).
+pub fn descriptor(params: &[&str], ret: &str) -> String {
+    let len = params.iter().map(|p| p.len()).sum::<usize>() + ret.len() + 2;
+    let mut descriptor = String::with_capacity(len);
+    descriptor.push('(');
+    params.iter().for_each(|p| descriptor.push_str(p));
+    descriptor.push(')');
+    descriptor.push_str(ret);
+    descriptor
+}
+
+/// Descriptor of a method taking the parameter types in parentheses and returning the type after `->` (`void` without it), as
+/// a `&'static str` constant:
+///
+/// ```
+/// use robusta_jni::descriptor;
+/// use robusta_jni::convert::JavaArray;
+///
+/// assert_eq!(descriptor!((Vec<String>, Option<i32>) -> JavaArray<i64>), "(Ljava/util/ArrayList;Ljava/lang/Integer;)[J");
+/// assert_eq!(descriptor!((String,)), "(Ljava/lang/String;)V");
+/// ```
+///
+/// The types must implement [`Signature`](crate::convert::Signature), and can't be generic parameters of the enclosing item.
+#[macro_export]
+macro_rules! descriptor {
+    (($($param:ty),* $(,)?) -> $ret:ty) => {{
+        const PARAMS: &[&str] = &[$(<$param as $crate::convert::Signature>::SIG_TYPE),*];
+        const RET: &str = <$ret as $crate::convert::Signature>::SIG_TYPE;
+        const BYTES: [u8; $crate::signature::descriptor_len(PARAMS, RET)] = $crate::signature::descriptor_bytes(PARAMS, RET);
+        // SAFETY: `BYTES` are the concatenation of parentheses and `str`s, which is valid UTF-8
+        const DESCRIPTOR: &str = unsafe { ::core::str::from_utf8_unchecked(&BYTES) };
+        DESCRIPTOR
+    }};
+    (($($param:ty),* $(,)?)) => {
+        $crate::descriptor!(($($param),*) -> ())
+    };
+}
+
+#[doc(inline)]
+pub use crate::descriptor;
+
+/// Length of the descriptor assembled by [`descriptor_bytes`].
+#[doc(hidden)]
+pub const fn descriptor_len(params: &[&str], ret: &str) -> usize {
+    let mut len = ret.len() + 2;
+    let mut i = 0;
+    while i < params.len() {
+        len += params[i].len();
+        i += 1;
+    }
+    len
+}
+
+/// Bytes of the descriptor of a method with parameters of type signatures `params` returning `ret`, for [`descriptor!`].
+/// `N` must be the [`descriptor_len`] of the same type signatures.
+#[doc(hidden)]
+pub const fn descriptor_bytes<const N: usize>(params: &[&str], ret: &str) -> [u8; N] {
+    let mut bytes = [0; N];
+    let mut len = 0;
+
+    bytes[len] = b'(';
+    len += 1;
+    let mut i = 0;
+    while i < params.len() {
+        let param = params[i].as_bytes();
+        let mut j = 0;
+        while j < param.len() {
+            bytes[len] = param[j];
+            len += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    bytes[len] = b')';
+    len += 1;
+
+    let ret = ret.as_bytes();
+    let mut j = 0;
+    while j < ret.len() {
+        bytes[len] = ret[j];
+        len += 1;
+        j += 1;
+    }
+    bytes
+}
+
+/// Whether `descriptor` is the concatenation of the type signatures `params`, which checks the argument descriptor in the
+/// native symbol of an overloaded exported method.
+#[doc(hidden)]
+pub const fn is_params_descriptor(params: &[&str], descriptor: &str) -> bool {
+    let descriptor = descriptor.as_bytes();
+    let mut len = 0;
+    let mut i = 0;
+    while i < params.len() {
+        let param = params[i].as_bytes();
+        let mut j = 0;
+        while j < param.len() {
+            if len == descriptor.len() || descriptor[len] != param[j] {
+                return false;
+            }
+            len += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    len == descriptor.len()
+}
+
+/// Type signature of a parameter of an exported method taking a value of type signature `signature`.
+///
+/// `Vec`s are converted from any `java.util.List`, so exported methods declare their `Vec` parameters as `List`s, which Java
+/// callers can pass an `ArrayList`, a `LinkedList` or an unmodifiable list to. Other type signatures are unchanged.
+pub const fn parameter_signature(signature: &'static str) -> &'static str {
+    match signature.as_bytes() {
+        b"Ljava/util/ArrayList;" => "Ljava/util/List;",
+        _ => signature,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jni::objects::JObject;
+
+    use super::*;
+    use crate::convert::{Boxed, JavaArray};
+
+    #[test]
+    fn primitives() {
+        assert_eq!(method_descriptor::<(bool, i8, char, i16, i32, i64, f32, f64), ()>(), "(ZBCSIJFD)V");
+        assert_eq!(method_descriptor::<(), i32>(), "()I");
+        assert_eq!(method_descriptor::<(u64,), u32>(), "(J)J");
+        assert_eq!(field_descriptor::<f64>(), "D");
+    }
+
+    #[test]
+    fn objects() {
+        assert_eq!(method_descriptor::<(String,), String>(), "(Ljava/lang/String;)Ljava/lang/String;");
+        assert_eq!(method_descriptor::<(JObject<'static>, Boxed<i32>), ()>(), "(Ljava/lang/Object;Ljava/lang/Integer;)V");
+        assert_eq!(field_descriptor::<Option<i64>>(), "Ljava/lang/Long;");
+        assert_eq!(field_descriptor::<&str>(), "Ljava/lang/String;");
+    }
+
+    #[test]
+    fn arrays() {
+        assert_eq!(field_descriptor::<JavaArray<i32>>(), "[I");
+        assert_eq!(field_descriptor::<JavaArray<String>>(), "[Ljava/lang/String;");
+        assert_eq!(method_descriptor::<(JavaArray<i8>, Box<[bool]>), JavaArray<Boxed<i64>>>(), "([B[Z)[Ljava/lang/Long;");
+    }
+
+    #[test]
+    fn void_returns() {
+        assert_eq!(method_descriptor::<(), ()>(), "()V");
+        assert_eq!(method_descriptor::<(i32,), ()>(), "(I)V");
+        assert_eq!(descriptor(&[], "V"), "()V");
+    }
+
+    #[test]
+    fn generics_are_erased() {
+        assert_eq!(field_descriptor::<Vec<Vec<i32>>>(), "Ljava/util/ArrayList;");
+        assert_eq!(method_descriptor::<(Vec<String>, Option<Vec<i32>>), Result<Vec<Boxed<i64>>, String>>(), "(Ljava/util/ArrayList;Ljava/util/ArrayList;)Ljava/util/ArrayList;");
+    }
+
+    #[test]
+    fn vec_parameters_are_lists() {
+        const VEC: &str = parameter_signature(<Vec<String> as Signature>::SIG_TYPE);
+        assert_eq!(VEC, "Ljava/util/List;");
+        assert_eq!(parameter_signature(<Option<Vec<i32>> as Signature>::SIG_TYPE), "Ljava/util/List;");
+        assert_eq!(parameter_signature("I"), "I");
+        assert_eq!(parameter_signature("Ljava/lang/String;"), "Ljava/lang/String;");
+    }
+
+    #[test]
+    fn descriptors_are_constants() {
+        const CHECK: &str = descriptor!((String, i32) -> bool);
+        assert_eq!(CHECK, "(Ljava/lang/String;I)Z");
+        assert_eq!(descriptor!((u64,) -> u32), method_descriptor::<(u64,), u32>());
+    }
+
+    #[test]
+    fn descriptors_of_nested_types() {
+        assert_eq!(descriptor!((Vec<String>, Option<String>) -> Vec<Vec<i32>>), "(Ljava/util/ArrayList;Ljava/lang/String;)Ljava/util/ArrayList;");
+        assert_eq!(descriptor!((JavaArray<String>, Box<[bool]>) -> JavaArray<Boxed<i64>>), "([Ljava/lang/String;[Z)[Ljava/lang/Long;");
+        assert_eq!(descriptor!((Option<i64>, JObject<'static>,) -> Result<String, String>), "(Ljava/lang/Long;Ljava/lang/Object;)Ljava/lang/String;");
+    }
+
+    #[test]
+    fn descriptors_without_return_type_are_void() {
+        assert_eq!(descriptor!(()), "()V");
+        assert_eq!(descriptor!(() -> ()), "()V");
+        assert_eq!(descriptor!((i32, f64)), "(ID)V");
+    }
+
+    #[test]
+    fn param_signatures_are_constants() {
+        const PARAMS: &[&str] = <(String, JavaArray<i32>) as Params>::SIG_TYPES;
+        assert_eq!(PARAMS, ["Ljava/lang/String;", "[I"]);
+    }
+
+    #[test]
+    fn params_descriptors_are_concatenated_signatures() {
+        const MATCHES: bool = is_params_descriptor(&["Ljava/lang/String;", "I"], "Ljava/lang/String;I");
+        assert!(MATCHES);
+        assert!(is_params_descriptor(&[], ""));
+        assert!(!is_params_descriptor(&["[B"], "Ljava/lang/Object;"));
+        assert!(!is_params_descriptor(&["I"], "II"));
+        assert!(!is_params_descriptor(&["I", "I"], "I"));
+    }
+}
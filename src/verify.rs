@@ -0,0 +1,163 @@
+//! Checks of the imported methods against the JVM, before they're called.
+//!
+//! A mismatch between an `extern "java"` declaration and the Java class (a renamed method, a parameter of another type, ...)
+//! otherwise only shows up when the method is first called, as a `NoSuchMethodError`. With the `verify-bindings` feature, meant
+//! for debug and test builds:
+//!
+//! * each imported method is looked up before its first call, which panics if it doesn't exist, naming its class, name and
+//!   descriptor instead of making the call;
+//! * the `JNI_OnLoad` function generated for `#[bridge(preload_classes(...))]` and `#[bridge(register_natives)]` looks up every
+//!   imported method of the library, making `System.loadLibrary` throw a `NoSuchMethodError` listing those that don't exist;
+//! * [`verify_bindings`] looks up every imported method of the program, e.g. from a test:
+//!
+//! ```ignore
+//! #[test]
+//! fn imported_methods_exist() {
+//!     let env = /* ... */;
+//!     let unresolved = robusta_jni::verify_bindings(&env).unwrap();
+//!     assert!(unresolved.is_empty(), "{}", robusta_jni::verify::report(&unresolved));
+//! }
+//! ```
+//!
+//! Methods are described by the [`bridge_metadata()`](crate::metadata) of their module, which registers it with the feature
+//! (field accessors aren't checked). Lookups resolve classes like the calls do, with the class loader of the calling thread.
+//!
+//! Without the feature, the module is empty and the generated code doesn't check anything.
+//!
+
+#[cfg(feature = "verify-bindings")]
+use std::fmt;
+#[cfg(feature = "verify-bindings")]
+use std::sync::Once;
+
+#[cfg(feature = "verify-bindings")]
+use jni::errors::{Error, Result};
+#[cfg(feature = "verify-bindings")]
+use jni::objects::{JThrowable, JValue};
+#[cfg(feature = "verify-bindings")]
+use jni::JNIEnv;
+
+#[cfg(feature = "verify-bindings")]
+use crate::metadata::MethodMetadata;
+
+/// The imported methods of a `#[bridge]` module, registered by the generated code.
+#[cfg(feature = "verify-bindings")]
+#[doc(hidden)]
+pub struct ModuleBindings {
+    pub module: &'static str,
+    pub methods: fn() -> &'static [MethodMetadata],
+}
+
+#[cfg(feature = "verify-bindings")]
+inventory::collect!(ModuleBindings);
+
+/// An imported method that doesn't exist, and the module declaring it.
+#[cfg(feature = "verify-bindings")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnresolvedBinding {
+    /// Path of the `#[bridge]` module, e.g. `my_crate::jni`.
+    pub module: &'static str,
+    pub method: MethodMetadata,
+}
+
+#[cfg(feature = "verify-bindings")]
+impl fmt::Display for UnresolvedBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (declared in `{}`)", self.method, self.module)
+    }
+}
+
+/// Looks up every imported method of every `#[bridge]` module of the program, returning those that don't exist, by module path
+/// and in order of declaration.
+#[cfg(feature = "verify-bindings")]
+pub fn verify_bindings(env: &JNIEnv) -> Result<Vec<UnresolvedBinding>> {
+    let mut modules: Vec<&ModuleBindings> = inventory::iter::<ModuleBindings>.into_iter().collect();
+    modules.sort_by_key(|m| m.module);
+
+    let mut unresolved = Vec::new();
+    for module in modules {
+        for method in crate::metadata::unresolved(env, (module.methods)())? {
+            unresolved.push(UnresolvedBinding { module: module.module, method });
+        }
+    }
+
+    Ok(unresolved)
+}
+
+/// A message listing the methods of `unresolved`, one per line.
+#[cfg(feature = "verify-bindings")]
+pub fn report(unresolved: &[UnresolvedBinding]) -> String {
+    let mut report = format!("{} imported method(s) not found:", unresolved.len());
+    for binding in unresolved {
+        report.push_str(&format!("\n  {}", binding));
+    }
+
+    report
+}
+
+/// Looks up `method` before the first call of the imported method it describes, panicking if it doesn't exist.
+#[cfg(feature = "verify-bindings")]
+#[doc(hidden)]
+pub fn verify_before_first_call(env: &JNIEnv, method: &MethodMetadata, verified: &Once) {
+    if verified.is_completed() {
+        return;
+    }
+
+    match method.exists(env) {
+        Ok(true) => verified.call_once(|| {}),
+        Ok(false) => panic!("can't find imported method {}, check its parameter and return types against the Java declaration", method),
+        Err(e) => panic!("can't look up imported method {} ({})", method, e),
+    }
+}
+
+/// Looks up every imported method of the library, leaving a `NoSuchMethodError` listing those that don't exist pending.
+#[cfg(feature = "verify-bindings")]
+pub(crate) fn verify_on_load(env: &JNIEnv) -> Result<()> {
+    let unresolved = verify_bindings(env)?;
+    if unresolved.is_empty() {
+        return Ok(());
+    }
+
+    let message = env.new_string(report(&unresolved))?;
+    let error = env.new_object("java/lang/NoSuchMethodError", "(Ljava/lang/String;)V", &[JValue::from(message)])?;
+    env.throw(JThrowable::from(error))?;
+    Err(Error::JavaException)
+}
+
+/// Registers the imported methods of the enclosing module, listed by the function `$methods`.
+#[cfg(feature = "verify-bindings")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_bindings {
+    ($methods:path) => {
+        $crate::__private::inventory::submit! {
+            $crate::verify::ModuleBindings { module: ::core::module_path!(), methods: $methods }
+        }
+    };
+}
+
+#[cfg(not(feature = "verify-bindings"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_bindings {
+    ($methods:path) => {};
+}
+
+/// Looks up the imported method described by `$method` if it hasn't been called from this call site yet.
+#[cfg(feature = "verify-bindings")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __verify_binding {
+    ($env:expr, $method:expr) => {{
+        const BINDING: $crate::metadata::MethodMetadata = $method;
+        static VERIFIED: $crate::__private::Once = $crate::__private::Once::new();
+        $crate::verify::verify_before_first_call(&$env, &BINDING, &VERIFIED);
+    }};
+}
+
+#[cfg(not(feature = "verify-bindings"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __verify_binding {
+    ($env:expr, $method:expr) => {};
+}
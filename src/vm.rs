@@ -0,0 +1,78 @@
+//! Calling Java from threads the JVM didn't start.
+//!
+//! Imported methods need a [`JNIEnv`], which only exists on threads attached to the JVM. Rust threads (e.g. the workers of
+//! a Tokio runtime or of a Rayon pool) can get one with [`with_attached_env`], once the JVM is known:
+//!
+//! ```ignore
+//! // Once, e.g. from an exported `init` method
+//! robusta_jni::vm::set_java_vm(env.get_java_vm()?);
+//!
+//! // Then, from any thread
+//! let hex = robusta_jni::vm::with_attached_env(|env| Integer::toHexString(env, 255))?;
+//! ```
+//!
+//! The `JNI_OnLoad` function generated for `#[bridge(register_natives)]` and `#[bridge(preload_classes(...))]` sets the JVM
+//! itself, as does [`preload::on_load`](crate::preload::on_load).
+//!
+//! Threads that aren't attached yet are attached as daemon threads, so that they don't keep the JVM from exiting. The
+//! attachment is kept in a thread-local of the `jni` crate until the thread exits, where the thread is detached: attaching
+//! is costly, so later calls on the same thread reuse it. Local references created by the closure are released when it returns.
+//!
+//! Static imported methods annotated with `#[attach]` take no `JNIEnv` parameter, and call `with_attached_env` themselves:
+//!
+//! ```ignore
+//! #[attach]
+//! pub extern "java" fn toHexString(value: i32) -> JniResult<String> {}
+//! ```
+//!
+//! Their return values can't borrow from the environment, as they outlive the closure (e.g. `String` rather than `JString<'env>`).
+//!
+
+use std::sync::OnceLock;
+
+use jni::errors::{Error, Result};
+use jni::objects::JObject;
+use jni::{JNIEnv, JavaVM};
+
+// Local references created by the closure of `with_attached_env` are released when it returns.
+const LOCAL_FRAME_CAPACITY: i32 = 16;
+
+static JAVA_VM: OnceLock<JavaVM> = OnceLock::new();
+
+/// Sets the JVM threads are attached to by [`with_attached_env`]. A process has at most one JVM, so later calls are ignored.
+pub fn set_java_vm(vm: JavaVM) {
+    let _ = JAVA_VM.set(vm);
+}
+
+/// The JVM set with [`set_java_vm`], if any.
+pub fn java_vm() -> Option<&'static JavaVM> {
+    JAVA_VM.get()
+}
+
+/// Pops the local frame pushed by `with_attached_env`, even if its closure panics.
+struct LocalFrame<'env>(JNIEnv<'env>);
+
+impl Drop for LocalFrame<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.pop_local_frame(JObject::null());
+    }
+}
+
+/// Calls `f` with the environment of the current thread, attaching it to the JVM set with [`set_java_vm`] if needed.
+///
+/// Fails with [`Error::NullPtr`] if no JVM was set.
+pub fn with_attached_env<F, R>(f: F) -> Result<R>
+where
+    F: for<'env> FnOnce(JNIEnv<'env>) -> Result<R>,
+{
+    let vm = java_vm().ok_or(Error::NullPtr("Java VM, set with `robusta_jni::vm::set_java_vm`"))?;
+    // Threads started by the JVM, or already attached, keep their attachment
+    let env = match vm.get_env() {
+        Ok(env) => env,
+        Err(_) => vm.attach_current_thread_as_daemon()?,
+    };
+
+    env.push_local_frame(LOCAL_FRAME_CAPACITY)?;
+    let _frame = LocalFrame(env);
+    f(env)
+}